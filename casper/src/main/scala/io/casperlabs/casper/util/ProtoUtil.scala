@@ -650,7 +650,15 @@ object ProtoUtil {
         payment = payment,
         gasPrice = GAS_PRICE,
         authorizationKeys = d.approvals.map(_.approverPublicKey),
-        deployHash = d.deployHash
+        deployHash = d.deployHash,
+        signatures = d.approvals.map(
+          approval =>
+            ipc.DeploySignature(
+              publicKey = approval.approverPublicKey,
+              signature = approval.getSignature.sig,
+              sigAlgorithm = approval.getSignature.sigAlgorithm
+            )
+        )
       )
     }
   }
@@ -95,3 +95,44 @@ export declare function get_system_contract(system_contract_index: u32, dest_ptr
 export declare function get_main_purse(dest_ptr: usize): void;
 @external("env", "read_host_buffer")
 export declare function read_host_buffer(dest_ptr: usize, dest_size: u32, bytes_written: usize): i32;
+@external("env", "get_contract_metadata")
+export declare function get_contract_metadata(key_ptr: usize, key_size: u32, output_size: usize): i32;
+@external("env", "new_contract")
+export declare function new_contract(
+    entry_points_ptr: usize,
+    entry_points_size: u32,
+    named_keys_ptr: usize,
+    named_keys_size: u32,
+    package_hash_ptr: usize,
+    access_key_ptr: usize,
+): void;
+@external("env", "add_contract_version")
+export declare function add_contract_version(
+    package_hash_ptr: usize,
+    access_key_ptr: usize,
+    entry_points_ptr: usize,
+    entry_points_size: u32,
+    named_keys_ptr: usize,
+    named_keys_size: u32,
+    version_ptr: usize,
+): void;
+@external("env", "disable_contract_version")
+export declare function disable_contract_version(package_hash_ptr: usize, access_key_ptr: usize, version: u32): void;
+@external("env", "call_versioned_contract")
+export declare function call_versioned_contract(
+    package_hash_ptr: usize,
+    version: u32,
+    entry_point_ptr: usize,
+    entry_point_size: u32,
+    args_ptr: usize,
+    args_size: u32,
+    result_size: usize,
+): i32;
+@external("env", "get_call_stack")
+export declare function get_call_stack(output_size: usize): i32;
+@external("env", "random_bytes")
+export declare function random_bytes(dest_ptr: usize): void;
+@external("env", "blake2b")
+export declare function blake2b(data_ptr: usize, data_size: usize, dest_ptr: usize): void;
+@external("env", "sha256")
+export declare function sha256(data_ptr: usize, data_size: usize, dest_ptr: usize): void;
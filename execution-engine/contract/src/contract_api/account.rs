@@ -1,15 +1,16 @@
-use alloc::vec::Vec;
-use core::convert::TryFrom;
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use core::{convert::TryFrom, mem::MaybeUninit};
 
 use casperlabs_types::{
     account::{
-        ActionType, AddKeyFailure, PublicKey, PurseId, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight, PURSE_ID_SERIALIZED_LENGTH,
+        ActionType, AddKeyFailure, KeyUpdate, PublicKey, PurseId, RemoveKeyFailure,
+        SetThresholdFailure, UpdateKeyFailure, UpdateKeysFailure, Weight,
+        PURSE_ID_SERIALIZED_LENGTH,
     },
-    bytesrepr,
+    api_error, bytesrepr, ApiError,
 };
 
-use super::to_ptr;
+use super::{runtime, to_ptr};
 use crate::{contract_api, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
 
 pub fn get_main_purse() -> PurseId {
@@ -25,6 +26,47 @@ pub fn get_main_purse() -> PurseId {
     bytesrepr::deserialize(bytes).unwrap_or_revert()
 }
 
+/// Creates a new purse owned by the current account, and stores it in the account under the given
+/// name, next to its main purse.
+///
+/// Returns [`ApiError::DuplicateNamedPurse`] if the account already has a purse with that name.
+/// Can only be called from session or payment code.
+pub fn create_named_purse(name: &str) -> Result<PurseId, ApiError> {
+    let (name_ptr, name_size, _bytes) = to_ptr(name);
+    let mut purse_id_bytes = vec![0u8; PURSE_ID_SERIALIZED_LENGTH];
+    let ret = unsafe {
+        ext_ffi::create_named_purse(
+            name_ptr,
+            name_size,
+            purse_id_bytes.as_mut_ptr(),
+            purse_id_bytes.len(),
+        )
+    };
+    api_error::result_from(ret)?;
+    Ok(bytesrepr::deserialize(purse_id_bytes).unwrap_or_revert())
+}
+
+/// Returns the purse stored in the current account under the given name, if any.
+///
+/// Can only be called from session or payment code.
+pub fn get_named_purse(name: &str) -> Option<PurseId> {
+    let (name_ptr, name_size, _bytes) = to_ptr(name);
+    let mut purse_id_bytes = vec![0u8; PURSE_ID_SERIALIZED_LENGTH];
+    let ret = unsafe {
+        ext_ffi::get_named_purse(
+            name_ptr,
+            name_size,
+            purse_id_bytes.as_mut_ptr(),
+            purse_id_bytes.len(),
+        )
+    };
+    match api_error::result_from(ret) {
+        Ok(()) => Some(bytesrepr::deserialize(purse_id_bytes).unwrap_or_revert()),
+        Err(ApiError::MissingKey) => None,
+        Err(error) => runtime::revert(error),
+    }
+}
+
 pub fn set_action_threshold(
     permission_level: ActionType,
     threshold: Weight,
@@ -40,8 +82,14 @@ pub fn set_action_threshold(
 }
 
 /// Adds a public key with associated weight to an account.
-pub fn add_associated_key(public_key: PublicKey, weight: Weight) -> Result<(), AddKeyFailure> {
-    let (public_key_ptr, _public_key_size, _bytes) = to_ptr(public_key);
+///
+/// Accepts a [`PublicKey`], or a [`TaggedPublicKey`](casperlabs_types::account::TaggedPublicKey)
+/// of any supported algorithm.
+pub fn add_associated_key<T: Into<PublicKey>>(
+    public_key: T,
+    weight: Weight,
+) -> Result<(), AddKeyFailure> {
+    let (public_key_ptr, _public_key_size, _bytes) = to_ptr(public_key.into());
     // Cast of u8 (weight) into i32 is assumed to be always safe
     let result = unsafe { ext_ffi::add_associated_key(public_key_ptr, weight.value().into()) };
     if result == 0 {
@@ -52,8 +100,8 @@ pub fn add_associated_key(public_key: PublicKey, weight: Weight) -> Result<(), A
 }
 
 /// Removes a public key from associated keys on an account
-pub fn remove_associated_key(public_key: PublicKey) -> Result<(), RemoveKeyFailure> {
-    let (public_key_ptr, _public_key_size, _bytes) = to_ptr(public_key);
+pub fn remove_associated_key<T: Into<PublicKey>>(public_key: T) -> Result<(), RemoveKeyFailure> {
+    let (public_key_ptr, _public_key_size, _bytes) = to_ptr(public_key.into());
     let result = unsafe { ext_ffi::remove_associated_key(public_key_ptr) };
     if result == 0 {
         Ok(())
@@ -63,11 +111,11 @@ pub fn remove_associated_key(public_key: PublicKey) -> Result<(), RemoveKeyFailu
 }
 
 /// Updates the value stored under a public key associated with an account
-pub fn update_associated_key(
-    public_key: PublicKey,
+pub fn update_associated_key<T: Into<PublicKey>>(
+    public_key: T,
     weight: Weight,
 ) -> Result<(), UpdateKeyFailure> {
-    let (public_key_ptr, _public_key_size, _bytes) = to_ptr(public_key);
+    let (public_key_ptr, _public_key_size, _bytes) = to_ptr(public_key.into());
     // Cast of u8 (weight) into i32 is assumed to be always safe
     let result = unsafe { ext_ffi::update_associated_key(public_key_ptr, weight.value().into()) };
     if result == 0 {
@@ -76,3 +124,32 @@ pub fn update_associated_key(
         Err(UpdateKeyFailure::try_from(result).unwrap_or_revert())
     }
 }
+
+/// Applies a batch of changes to the associated keys of an account in a single step.
+///
+/// Either all of the updates are applied or none of them are.  The action thresholds are only
+/// checked against the resulting set of keys, so e.g. all the keys of a 3-of-5 account can be
+/// rotated at once without the account becoming unusable half-way through.
+pub fn update_associated_keys(updates: Vec<KeyUpdate>) -> Result<(), UpdateKeysFailure> {
+    let (updates_ptr, updates_size, _bytes) = to_ptr(updates);
+    let result = unsafe { ext_ffi::update_associated_keys(updates_ptr, updates_size) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(UpdateKeysFailure::try_from(result).unwrap_or_revert())
+    }
+}
+
+/// Returns the public keys associated with the account executing the deploy and their weights.
+///
+/// Changes made earlier in the same deploy are included.
+pub fn list_associated_keys() -> BTreeMap<PublicKey, Weight> {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret = unsafe { ext_ffi::list_associated_keys(output_size.as_mut_ptr()) };
+        api_error::result_from(ret).unwrap_or_revert();
+        unsafe { output_size.assume_init() }
+    };
+    let bytes = runtime::read_host_buffer(output_size).unwrap_or_revert();
+    bytesrepr::deserialize(bytes).unwrap_or_revert()
+}
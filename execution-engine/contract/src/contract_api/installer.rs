@@ -0,0 +1,53 @@
+//! Support for installer contracts, which store a contract package and record where they put it.
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use casperlabs_types::{
+    access_key_name, install_manifest_key_name, CLValue, EntryPoint, EntryPoints, InstallManifest,
+    Key,
+};
+
+use crate::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+
+/// Stores a contract package exposing the given entry points of the current module, whose first
+/// version is given `named_keys`, and returns its [`InstallManifest`] to the caller.
+///
+/// See [`install_package`] for the named keys put in the context of the installer.
+pub fn install(
+    package_name: &str,
+    entry_points: &[EntryPoint],
+    named_keys: BTreeMap<String, Key>,
+) -> ! {
+    let manifest = install_package(package_name, entry_points, named_keys);
+    runtime::ret(CLValue::from_t(manifest).unwrap_or_revert())
+}
+
+/// Stores a contract package exposing the given entry points of the current module, whose first
+/// version is given `named_keys`, and returns its [`InstallManifest`].
+///
+/// The following named keys are put in the context of the installer:
+/// * `package_name`: the hash of the package
+/// * [`access_key_name`]`(package_name)`: the access key of the package
+/// * [`install_manifest_key_name`]`(package_name)`: a URef to the manifest
+pub fn install_package(
+    package_name: &str,
+    entry_points: &[EntryPoint],
+    named_keys: BTreeMap<String, Key>,
+) -> InstallManifest {
+    let entry_points: EntryPoints = entry_points.iter().cloned().collect::<Vec<_>>().into();
+    let (package_hash, access_key) = storage::new_contract(entry_points, named_keys.clone());
+    let manifest = InstallManifest::new(package_hash, access_key, named_keys);
+
+    runtime::put_key(package_name, Key::Hash(package_hash));
+    runtime::put_key(&access_key_name(package_name), access_key.into());
+    let manifest_turef = storage::new_turef(manifest.clone());
+    runtime::put_key(
+        &install_manifest_key_name(package_name),
+        manifest_turef.into(),
+    );
+
+    manifest
+}
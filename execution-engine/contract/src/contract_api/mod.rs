@@ -1,12 +1,13 @@
 //! Contains support for writing smart contracts.
 
 pub mod account;
+pub mod installer;
 pub mod runtime;
 pub mod storage;
 pub mod system;
 mod turef;
 
-pub use turef::TURef;
+pub use turef::{AsTURef, TURef};
 
 use alloc::{
     alloc::{Alloc, Global},
@@ -5,11 +5,12 @@ use alloc::{collections::BTreeMap, string::String, vec::Vec};
 use core::mem::MaybeUninit;
 
 use casperlabs_types::{
-    account::{PublicKey, PUBLIC_KEY_SERIALIZED_LENGTH},
+    account::{PublicKey, PurseId, PUBLIC_KEY_SERIALIZED_LENGTH, PURSE_ID_SERIALIZED_LENGTH},
     api_error,
-    bytesrepr::{self, FromBytes},
-    ApiError, BlockTime, CLTyped, CLValue, ContractRef, Key, Phase, URef,
-    BLOCKTIME_SERIALIZED_LENGTH, PHASE_SERIALIZED_LENGTH,
+    bytesrepr::{self, FromBytes, U64_SERIALIZED_LENGTH},
+    ApiError, BlockTime, CLTyped, CLValue, CallStackElement, ContractMetadata, ContractRef,
+    ContractVersion, Key, Phase, URef, BLAKE2B_DIGEST_LENGTH, BLOCKTIME_SERIALIZED_LENGTH,
+    PHASE_SERIALIZED_LENGTH, RANDOM_BYTES_COUNT, SHA256_DIGEST_LENGTH,
 };
 
 use crate::{args_parser::ArgsParser, contract_api, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
@@ -67,6 +68,46 @@ pub fn call_contract<A: ArgsParser, T: CLTyped + FromBytes>(c_ptr: ContractRef,
     bytesrepr::deserialize(dest).unwrap_or_revert()
 }
 
+/// Calls `entry_point` of a contract package stored under `package_hash`, with the given
+/// arguments, and returns the value returned by the called contract.
+///
+/// If `version` is `None`, the highest enabled version of the package is called.  Reverts if the
+/// version is disabled, or if the arguments don't match the parameters of the entry point.
+pub fn call_versioned_contract<A: ArgsParser, T: CLTyped + FromBytes>(
+    package_hash: [u8; 32],
+    version: Option<ContractVersion>,
+    entry_point: &str,
+    args: A,
+) -> T {
+    let (entry_point_ptr, entry_point_size, _bytes1) = contract_api::to_ptr(entry_point);
+    let (args_ptr, args_size, _bytes2) = ArgsParser::parse(args)
+        .map(contract_api::to_ptr)
+        .unwrap_or_revert();
+
+    let bytes_written = {
+        let mut bytes_written = MaybeUninit::uninit();
+        let ret = unsafe {
+            ext_ffi::call_versioned_contract(
+                package_hash.as_ptr(),
+                // Versions start at 1, so the host interprets 0 as the highest enabled version
+                version.unwrap_or(0),
+                entry_point_ptr,
+                entry_point_size,
+                args_ptr,
+                args_size,
+                bytes_written.as_mut_ptr(),
+            )
+        };
+        api_error::result_from(ret).unwrap_or_revert();
+        unsafe { bytes_written.assume_init() }
+    };
+
+    let bytes_ptr = contract_api::alloc_bytes(bytes_written);
+    let mut dest: Vec<u8> = unsafe { Vec::from_raw_parts(bytes_ptr, bytes_written, bytes_written) };
+    read_host_buffer_into(&mut dest).unwrap_or_revert();
+    bytesrepr::deserialize(dest).unwrap_or_revert()
+}
+
 /// Takes the name of a function to store and a contract URef, and overwrites the value under
 /// that URef with a new Contract instance containing the original contract's named_keys, the
 /// current protocol version, and the newly created bytes of the stored function.
@@ -82,6 +123,106 @@ pub fn upgrade_contract_at_uref(name: &str, uref: URef) {
     }
 }
 
+/// Only allows deploys executed as one of `authorized_callers` to call the contract stored under
+/// `uref`, or lets anyone call it if `authorized_callers` is `None`.  Calls from other accounts
+/// fail before the contract is loaded, so its entry points don't need to check the caller.
+///
+/// `uref` must be writeable, and can't point at a system contract.  Upgrading the contract with
+/// [`upgrade_contract_at_uref`] keeps its authorized callers.
+pub fn update_contract_acl(
+    uref: URef,
+    authorized_callers: Option<Vec<PublicKey>>,
+) -> Result<(), ApiError> {
+    let key: Key = uref.into();
+    let (key_ptr, key_size, _key_bytes) = contract_api::to_ptr(key);
+    let (acl_ptr, acl_size, _acl_bytes) = contract_api::to_ptr(authorized_callers);
+    let ret = unsafe { ext_ffi::update_contract_acl(key_ptr, key_size, acl_ptr, acl_size) };
+    api_error::result_from(ret)
+}
+
+/// Returns the [`ContractMetadata`] embedded in the given stored contract, or `None` if it was
+/// compiled without it.
+///
+/// See [`CONTRACT_METADATA_SECTION`](casperlabs_types::CONTRACT_METADATA_SECTION) for how the
+/// metadata is embedded.
+pub fn get_contract_metadata(contract: ContractRef) -> Option<ContractMetadata> {
+    let key: Key = contract.into();
+    let (key_ptr, key_size, _bytes) = contract_api::to_ptr(key);
+
+    let value_size = {
+        let mut value_size = MaybeUninit::uninit();
+        let ret =
+            unsafe { ext_ffi::get_contract_metadata(key_ptr, key_size, value_size.as_mut_ptr()) };
+        match api_error::result_from(ret) {
+            Ok(_) => unsafe { value_size.assume_init() },
+            Err(ApiError::ValueNotFound) => return None,
+            Err(error) => revert(error),
+        }
+    };
+
+    let value_bytes = read_host_buffer(value_size).unwrap_or_revert();
+    Some(bytesrepr::deserialize(value_bytes).unwrap_or_revert())
+}
+
+/// Returns the chain of callers which led to the currently executing code, starting with the
+/// account executing the deploy and ending with the current context.
+pub fn get_call_stack() -> Vec<CallStackElement> {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret = unsafe { ext_ffi::get_call_stack(output_size.as_mut_ptr()) };
+        api_error::result_from(ret).unwrap_or_revert();
+        unsafe { output_size.assume_init() }
+    };
+    let bytes = read_host_buffer(output_size).unwrap_or_revert();
+    bytesrepr::deserialize(bytes).unwrap_or_revert()
+}
+
+/// Returns the keys which signed the deploy currently being executed, in ascending order.
+///
+/// These are the same for the session and payment code of the deploy and every contract they call.
+pub fn list_authorization_keys() -> Vec<PublicKey> {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret = unsafe { ext_ffi::list_authorization_keys(output_size.as_mut_ptr()) };
+        api_error::result_from(ret).unwrap_or_revert();
+        unsafe { output_size.assume_init() }
+    };
+    let bytes = read_host_buffer(output_size).unwrap_or_revert();
+    bytesrepr::deserialize(bytes).unwrap_or_revert()
+}
+
+/// Returns [`RANDOM_BYTES_COUNT`] random bytes.
+///
+/// The bytes are generated deterministically from the deploy hash and the current phase, so every
+/// node executing the deploy gets the same result; they are not secret, and are only as
+/// unpredictable as the deploy hash.  Calling this doesn't affect the addresses of `URef`s created
+/// afterwards.
+pub fn random_bytes() -> [u8; RANDOM_BYTES_COUNT] {
+    let mut bytes = [0u8; RANDOM_BYTES_COUNT];
+    unsafe { ext_ffi::random_bytes(bytes.as_mut_ptr()) };
+    bytes
+}
+
+/// Returns the 32-byte BLAKE2b digest of `data`.
+///
+/// Hashing is done by the host, which is much cheaper in gas than hashing in Wasm.
+pub fn blake2b<T: AsRef<[u8]>>(data: T) -> [u8; BLAKE2B_DIGEST_LENGTH] {
+    let data = data.as_ref();
+    let mut digest = [0u8; BLAKE2B_DIGEST_LENGTH];
+    unsafe { ext_ffi::blake2b(data.as_ptr(), data.len(), digest.as_mut_ptr()) };
+    digest
+}
+
+/// Returns the SHA-256 digest of `data`.
+///
+/// Hashing is done by the host, which is much cheaper in gas than hashing in Wasm.
+pub fn sha256<T: AsRef<[u8]>>(data: T) -> [u8; SHA256_DIGEST_LENGTH] {
+    let data = data.as_ref();
+    let mut digest = [0u8; SHA256_DIGEST_LENGTH];
+    unsafe { ext_ffi::sha256(data.as_ptr(), data.len(), digest.as_mut_ptr()) };
+    digest
+}
+
 fn get_arg_size(i: u32) -> Option<usize> {
     let mut arg_size: usize = 0;
     let ret = unsafe { ext_ffi::get_arg_size(i as usize, &mut arg_size as *mut usize) };
@@ -141,6 +282,26 @@ pub fn get_blocktime() -> BlockTime {
     bytesrepr::deserialize(bytes).unwrap_or_revert()
 }
 
+/// Returns the id of the era the current block belongs to.
+pub fn get_era_id() -> u64 {
+    let dest_ptr = contract_api::alloc_bytes(U64_SERIALIZED_LENGTH);
+    let bytes = unsafe {
+        ext_ffi::get_era_id(dest_ptr);
+        Vec::from_raw_parts(dest_ptr, U64_SERIALIZED_LENGTH, U64_SERIALIZED_LENGTH)
+    };
+    bytesrepr::deserialize(bytes).unwrap_or_revert()
+}
+
+/// Returns the height of the current block.
+pub fn get_block_height() -> u64 {
+    let dest_ptr = contract_api::alloc_bytes(U64_SERIALIZED_LENGTH);
+    let bytes = unsafe {
+        ext_ffi::get_block_height(dest_ptr);
+        Vec::from_raw_parts(dest_ptr, U64_SERIALIZED_LENGTH, U64_SERIALIZED_LENGTH)
+    };
+    bytesrepr::deserialize(bytes).unwrap_or_revert()
+}
+
 pub fn get_phase() -> Phase {
     let dest_ptr = contract_api::alloc_bytes(PHASE_SERIALIZED_LENGTH);
     unsafe { ext_ffi::get_phase(dest_ptr) };
@@ -175,7 +336,43 @@ pub fn get_key(name: &str) -> Option<Key> {
     Some(key)
 }
 
+/// Returns the key stored under the given name in the named keys of another account, e.g. the hash
+/// of a contract it has published.
+///
+/// The access rights of a [`URef`] are removed, so it can't be used to access the value it refers
+/// to.  Returns `None` if the account doesn't exist or has no key under the given name.
+pub fn get_named_key_of(account: PublicKey, name: &str) -> Option<Key> {
+    let (public_key_ptr, public_key_size, _bytes) = contract_api::to_ptr(account);
+    let (name_ptr, name_size, _bytes2) = contract_api::to_ptr(name);
+    let mut key_bytes = vec![0u8; Key::serialized_size_hint()];
+    let mut total_bytes: usize = 0;
+    let ret = unsafe {
+        ext_ffi::get_named_key_of(
+            public_key_ptr,
+            public_key_size,
+            name_ptr,
+            name_size,
+            key_bytes.as_mut_ptr(),
+            key_bytes.len(),
+            &mut total_bytes as *mut usize,
+        )
+    };
+    match api_error::result_from(ret) {
+        Ok(_) => {}
+        Err(ApiError::ValueNotFound) | Err(ApiError::MissingKey) => return None,
+        Err(e) => revert(e),
+    }
+    key_bytes.truncate(total_bytes);
+    let key: Key = bytesrepr::deserialize(key_bytes).unwrap_or_revert();
+    Some(key)
+}
+
 /// Check if the given name corresponds to a known unforgable reference
+///
+/// The lookup is performed against the named keys of the current context: the account's when
+/// running session or payment code, and the called contract's when running inside a stored
+/// contract.  Keys put or removed earlier in the same deploy are taken into account, including
+/// those put by payment code when checked from session code.
 pub fn has_key(name: &str) -> bool {
     let (name_ptr, name_size, _bytes) = contract_api::to_ptr(name);
     let result = unsafe { ext_ffi::has_key(name_ptr, name_size) };
@@ -213,6 +410,56 @@ pub fn list_named_keys() -> BTreeMap<String, Key> {
     bytesrepr::deserialize(bytes).unwrap_or_revert()
 }
 
+/// Takes ownership of `purse` for the currently executing stored contract.
+///
+/// The whole balance of `purse` is moved into a new purse, which is stored under `name` in the
+/// contract's named keys and returned.  Anyone else holding `purse`, such as the account which
+/// created it and passed it to the contract, is left with an empty purse and has no access to the
+/// new one, so the contract can hold funds in escrow.  Use [`URef::attenuate`] to hand out
+/// restricted copies of the new purse, e.g. an add-only one for deposits.
+///
+/// `purse` must be writeable.  Can only be called from a stored contract.
+pub fn transfer_uref_ownership(purse: PurseId, name: &str) -> Result<PurseId, ApiError> {
+    let (purse_id_ptr, purse_id_size, _purse_id_bytes) = contract_api::to_ptr(purse);
+    let (name_ptr, name_size, _name_bytes) = contract_api::to_ptr(name);
+    let mut new_purse_id_bytes = vec![0u8; PURSE_ID_SERIALIZED_LENGTH];
+    let ret = unsafe {
+        ext_ffi::transfer_uref_ownership(
+            purse_id_ptr,
+            purse_id_size,
+            name_ptr,
+            name_size,
+            new_purse_id_bytes.as_mut_ptr(),
+            new_purse_id_bytes.len(),
+        )
+    };
+    api_error::result_from(ret)?;
+    Ok(bytesrepr::deserialize(new_purse_id_bytes).unwrap_or_revert())
+}
+
+/// Logs `message` on the node executing the deploy, if it has contract logging enabled.
+///
+/// Logging is free of charge and has no effect on the outcome of the deploy.  Calls are compiled
+/// out of release builds of the contract, so it is meant for diagnostics while developing only.
+/// See [`fmt_buf`](crate::fmt_buf) for formatting messages without allocating.
+#[cfg_attr(not(debug_assertions), allow(unused_variables))]
+pub fn log(message: &str) {
+    #[cfg(debug_assertions)]
+    unsafe {
+        ext_ffi::log(message.as_ptr(), message.len())
+    }
+}
+
+/// Appends `text` to the output of the deploy, which test harnesses such as `WasmTestBuilder` make
+/// available once the deploy has been executed.
+///
+/// Engines executing deploys on a network don't collect the output, but printing succeeds all the
+/// same.  Returns [`ApiError::InvalidArgument`] if `text` isn't valid UTF-8.
+pub fn print(text: &str) -> Result<(), ApiError> {
+    let result = unsafe { ext_ffi::print(text.as_ptr(), text.len()) };
+    api_error::result_from(result)
+}
+
 /// checks if a uref is valid
 pub fn is_valid_uref(uref: URef) -> bool {
     let (uref_ptr, uref_size, _bytes) = contract_api::to_ptr(uref);
@@ -2,9 +2,11 @@ use alloc::{collections::BTreeMap, string::String, vec::Vec};
 use core::{convert::From, mem::MaybeUninit};
 
 use casperlabs_types::{
+    account::PublicKey,
     api_error,
     bytesrepr::{self, FromBytes, ToBytes},
-    AccessRights, ApiError, CLTyped, CLValue, ContractRef, Key, URef, KEY_UREF_SERIALIZED_LENGTH,
+    AccessRights, ApiError, CLTyped, CLValue, ContractRef, ContractVersion, EntryPoints, Key, Seed,
+    URef, KEY_UREF_SERIALIZED_LENGTH, LOCAL_SEED_LENGTH, UREF_SERIALIZED_LENGTH,
 };
 
 use crate::{
@@ -105,6 +107,86 @@ pub fn add_local<K: ToBytes, V: CLTyped + ToBytes>(key: K, value: V) {
     }
 }
 
+/// Returns the namespace of local state called `name`.
+///
+/// When the current contract was called through its contract package, the namespace is derived
+/// from the package hash, so that every version of the package reads and writes the same values in
+/// it.  Otherwise it is derived from the key of the current context.  The returned [`Seed`] can
+/// only be used by the contract which obtained it.
+pub fn namespace(name: &str) -> Seed {
+    let (name_ptr, name_size, _bytes) = contract_api::to_ptr(name);
+    let mut seed = [0u8; LOCAL_SEED_LENGTH];
+    unsafe { ext_ffi::namespace(name_ptr, name_size, seed.as_mut_ptr()) };
+    Seed::new(seed)
+}
+
+/// Reads the value under `key` in the namespace of local state given by `seed`.
+pub fn read_namespaced<K: ToBytes, V: CLTyped + FromBytes>(
+    seed: Seed,
+    key: &K,
+) -> Result<Option<V>, bytesrepr::Error> {
+    let seed = seed.value();
+    let key_bytes = key.to_bytes()?;
+
+    let value_size = {
+        let mut value_size = MaybeUninit::uninit();
+        let ret = unsafe {
+            ext_ffi::read_value_namespaced(
+                seed.as_ptr(),
+                key_bytes.as_ptr(),
+                key_bytes.len(),
+                value_size.as_mut_ptr(),
+            )
+        };
+        match api_error::result_from(ret) {
+            Ok(_) => unsafe { value_size.assume_init() },
+            Err(ApiError::ValueNotFound) => return Ok(None),
+            Err(e) => runtime::revert(e),
+        }
+    };
+
+    let value_bytes = runtime::read_host_buffer(value_size).unwrap_or_revert();
+    Ok(Some(bytesrepr::deserialize(value_bytes)?))
+}
+
+/// Writes `value` under `key` in the namespace of local state given by `seed`.
+pub fn write_namespaced<K: ToBytes, V: CLTyped + ToBytes>(seed: Seed, key: K, value: V) {
+    let seed = seed.value();
+    let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(key);
+
+    let cl_value = CLValue::from_t(value).unwrap_or_revert();
+    let (cl_value_ptr, cl_value_size, _bytes2) = contract_api::to_ptr(cl_value);
+
+    unsafe {
+        ext_ffi::write_namespaced(
+            seed.as_ptr(),
+            key_ptr,
+            key_size,
+            cl_value_ptr,
+            cl_value_size,
+        );
+    }
+}
+
+/// Adds `value` to the one currently under `key` in the namespace of local state given by `seed`.
+pub fn add_namespaced<K: ToBytes, V: CLTyped + ToBytes>(seed: Seed, key: K, value: V) {
+    let seed = seed.value();
+    let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(key);
+
+    let cl_value = CLValue::from_t(value).unwrap_or_revert();
+    let (cl_value_ptr, cl_value_size, _bytes2) = contract_api::to_ptr(cl_value);
+
+    unsafe {
+        ext_ffi::add_namespaced(
+            seed.as_ptr(),
+            key_ptr,
+            key_size,
+            cl_value_ptr,
+            cl_value_size,
+        );
+    }
+}
+
 /// Stores the serialized bytes of an exported function under a URef generated by the host.
 pub fn store_function(name: &str, named_keys: BTreeMap<String, Key>) -> ContractRef {
     let (fn_ptr, fn_size, _bytes1) = contract_api::to_ptr(name);
@@ -116,6 +198,21 @@ pub fn store_function(name: &str, named_keys: BTreeMap<String, Key>) -> Contract
     ContractRef::URef(URef::new(addr, AccessRights::READ_ADD_WRITE))
 }
 
+/// Stores the serialized bytes of an exported function under a URef generated by the host, only
+/// allowing deploys executed as one of `authorized_callers` to call it.
+///
+/// The authorized callers can be changed with [`runtime::update_contract_acl`].
+pub fn store_function_with_acl(
+    name: &str,
+    named_keys: BTreeMap<String, Key>,
+    authorized_callers: Vec<PublicKey>,
+) -> ContractRef {
+    let contract_ref = store_function(name, named_keys);
+    let uref = contract_ref.clone().into_uref().unwrap_or_revert();
+    runtime::update_contract_acl(uref, Some(authorized_callers)).unwrap_or_revert();
+    contract_ref
+}
+
 /// Stores the serialized bytes of an exported function at an immutable address generated by the
 /// host.
 pub fn store_function_at_hash(name: &str, named_keys: BTreeMap<String, Key>) -> ContractRef {
@@ -128,6 +225,76 @@ pub fn store_function_at_hash(name: &str, named_keys: BTreeMap<String, Key>) ->
     ContractRef::Hash(addr)
 }
 
+/// Creates a contract package whose first version exposes the given entry points of the current
+/// module.
+///
+/// Returns the hash of the package, which does not change when versions are added, and the
+/// package's access key, which is required to add or disable versions.
+pub fn new_contract(
+    entry_points: EntryPoints,
+    named_keys: BTreeMap<String, Key>,
+) -> ([u8; 32], URef) {
+    let (entry_points_ptr, entry_points_size, _bytes1) = contract_api::to_ptr(entry_points);
+    let (keys_ptr, keys_size, _bytes2) = contract_api::to_ptr(named_keys);
+    let mut package_hash = [0u8; 32];
+    let mut access_key_bytes = [0u8; UREF_SERIALIZED_LENGTH];
+    unsafe {
+        ext_ffi::new_contract(
+            entry_points_ptr,
+            entry_points_size,
+            keys_ptr,
+            keys_size,
+            package_hash.as_mut_ptr(),
+            access_key_bytes.as_mut_ptr(),
+        );
+    }
+    let access_key: URef = bytesrepr::deserialize(access_key_bytes.to_vec()).unwrap_or_revert();
+    (package_hash, access_key)
+}
+
+/// Adds a version exposing the given entry points of the current module to the contract package
+/// stored under `package_hash`, and returns the new version.
+///
+/// `access_key` must be the access key returned by [`new_contract`] when creating the package.
+pub fn add_contract_version(
+    package_hash: [u8; 32],
+    access_key: URef,
+    entry_points: EntryPoints,
+    named_keys: BTreeMap<String, Key>,
+) -> ContractVersion {
+    let (access_key_ptr, _access_key_size, _bytes1) = contract_api::to_ptr(access_key);
+    let (entry_points_ptr, entry_points_size, _bytes2) = contract_api::to_ptr(entry_points);
+    let (keys_ptr, keys_size, _bytes3) = contract_api::to_ptr(named_keys);
+    let mut version: ContractVersion = 0;
+    unsafe {
+        ext_ffi::add_contract_version(
+            package_hash.as_ptr(),
+            access_key_ptr,
+            entry_points_ptr,
+            entry_points_size,
+            keys_ptr,
+            keys_size,
+            &mut version as *mut ContractVersion,
+        );
+    }
+    version
+}
+
+/// Disables the given version of the contract package stored under `package_hash`, so it can no
+/// longer be called.
+///
+/// `access_key` must be the access key returned by [`new_contract`] when creating the package.
+pub fn disable_contract_version(
+    package_hash: [u8; 32],
+    access_key: URef,
+    version: ContractVersion,
+) {
+    let (access_key_ptr, _access_key_size, _bytes) = contract_api::to_ptr(access_key);
+    unsafe {
+        ext_ffi::disable_contract_version(package_hash.as_ptr(), access_key_ptr, version);
+    }
+}
+
 /// Returns a new unforgable pointer, where value is initialized to `init`
 pub fn new_turef<T: CLTyped + ToBytes>(init: T) -> TURef<T> {
     let key_ptr = contract_api::alloc_bytes(KEY_UREF_SERIALIZED_LENGTH);
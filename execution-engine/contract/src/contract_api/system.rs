@@ -91,8 +91,11 @@ pub fn get_balance(purse_id: PurseId) -> Option<U512> {
 
 /// Transfers `amount` of motes from default purse of the account to `target`
 /// account. If `target` does not exist it will create it.
-pub fn transfer_to_account(target: PublicKey, amount: U512) -> TransferResult {
-    let (target_ptr, target_size, _bytes1) = contract_api::to_ptr(target);
+///
+/// `target` can be given as a [`PublicKey`], or as a
+/// [`TaggedPublicKey`](casperlabs_types::account::TaggedPublicKey) of any supported algorithm.
+pub fn transfer_to_account<T: Into<PublicKey>>(target: T, amount: U512) -> TransferResult {
+    let (target_ptr, target_size, _bytes1) = contract_api::to_ptr(target.into());
     let (amount_ptr, amount_size, _bytes2) = contract_api::to_ptr(amount);
     let return_code =
         unsafe { ext_ffi::transfer_to_account(target_ptr, target_size, amount_ptr, amount_size) };
@@ -101,13 +104,13 @@ pub fn transfer_to_account(target: PublicKey, amount: U512) -> TransferResult {
 
 /// Transfers `amount` of motes from `source` purse to `target` account.
 /// If `target` does not exist it will create it.
-pub fn transfer_from_purse_to_account(
+pub fn transfer_from_purse_to_account<T: Into<PublicKey>>(
     source: PurseId,
-    target: PublicKey,
+    target: T,
     amount: U512,
 ) -> TransferResult {
     let (source_ptr, source_size, _bytes1) = contract_api::to_ptr(source);
-    let (target_ptr, target_size, _bytes2) = contract_api::to_ptr(target);
+    let (target_ptr, target_size, _bytes2) = contract_api::to_ptr(target.into());
     let (amount_ptr, amount_size, _bytes3) = contract_api::to_ptr(amount);
     let return_code = unsafe {
         ext_ffi::transfer_from_purse_to_account(
@@ -123,6 +126,11 @@ pub fn transfer_from_purse_to_account(
 }
 
 /// Transfers `amount` of motes from `source` purse to `target` purse.
+///
+/// If the mint rejects the transfer, its error is returned as [`ApiError::Mint`], e.g.
+/// `ApiError::from(mint::Error::InsufficientFunds)` if `source` doesn't hold `amount` motes.
+///
+/// [`ApiError::Mint`]: casperlabs_types::ApiError::Mint
 pub fn transfer_from_purse_to_purse(
     source: PurseId,
     target: PurseId,
@@ -141,9 +149,5 @@ pub fn transfer_from_purse_to_purse(
             amount_size,
         )
     };
-    if result == 0 {
-        Ok(())
-    } else {
-        Err(ApiError::Transfer)
-    }
+    api_error::result_from(result)
 }
@@ -103,6 +103,21 @@ impl<T: CLTyped> From<TURef<T>> for Key {
     }
 }
 
+/// Checked conversion of a [`Key`] into a [`TURef`].
+pub trait AsTURef {
+    /// Returns a [`TURef`] of the given type if `self` is a `Key::URef` with access rights.
+    ///
+    /// Returns [`ApiError::UnexpectedKeyVariant`] if `self` is not a `Key::URef`, or
+    /// [`ApiError::NoAccessRights`] if the `URef` has no access rights.
+    fn as_turef<T: CLTyped>(&self) -> Result<TURef<T>, ApiError>;
+}
+
+impl AsTURef for Key {
+    fn as_turef<T: CLTyped>(&self) -> Result<TURef<T>, ApiError> {
+        TURef::try_from(*self).map_err(ApiError::from)
+    }
+}
+
 impl From<Error> for ApiError {
     fn from(error: Error) -> Self {
         match error {
@@ -137,4 +152,20 @@ mod tests {
             );
         }
     }
+    #[test]
+    fn should_downcast_key_to_turef() {
+        let uref = URef::new([42; 32], AccessRights::READ);
+        let turef: TURef<String> = Key::URef(uref).as_turef().expect("should be a TURef");
+        assert_eq!(URef::from(turef), uref);
+
+        let no_access_rights = Key::URef(uref.remove_access_rights());
+        assert_eq!(
+            no_access_rights.as_turef::<String>(),
+            Err(ApiError::NoAccessRights)
+        );
+        assert_eq!(
+            Key::Hash([42; 32]).as_turef::<String>(),
+            Err(ApiError::UnexpectedKeyVariant)
+        );
+    }
 }
@@ -2,6 +2,9 @@
 //!
 //! Generally should not be used directly.  See the [`contract_api`](crate::contract_api) for
 //! high-level bindings suitable for writing smart contracts.
+//!
+//! The bindings are imported from the `env` namespace, i.e. they target ABI version 1 of the host
+//! functions, which are listed in `engine-core/HOST_FUNCTIONS.md`.
 extern "C" {
     pub fn read_value(key_ptr: *const u8, key_size: usize, output_size: *mut usize) -> i32;
     pub fn read_value_local(key_ptr: *const u8, key_size: usize, output_size: *mut usize) -> i32;
@@ -47,6 +50,15 @@ extern "C" {
         output_size: usize,
         bytes_written_ptr: *mut usize,
     ) -> i32;
+    pub fn get_named_key_of(
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+        name_ptr: *const u8,
+        name_size: usize,
+        output_ptr: *mut u8,
+        output_size: usize,
+        bytes_written_ptr: *mut usize,
+    ) -> i32;
     pub fn has_key(name_ptr: *const u8, name_size: usize) -> i32;
     pub fn put_key(name_ptr: *const u8, name_size: usize, key_ptr: *const u8, key_size: usize);
     pub fn remove_key(name_ptr: *const u8, name_size: usize);
@@ -99,5 +111,99 @@ extern "C" {
         dest_size: usize,
     ) -> i32;
     pub fn get_main_purse(dest_ptr: *mut u8);
+    pub fn create_named_purse(
+        name_ptr: *const u8,
+        name_size: usize,
+        purse_id_ptr: *mut u8,
+        purse_id_size: usize,
+    ) -> i32;
+    pub fn get_named_purse(
+        name_ptr: *const u8,
+        name_size: usize,
+        purse_id_ptr: *mut u8,
+        purse_id_size: usize,
+    ) -> i32;
     pub fn read_host_buffer(dest_ptr: *mut u8, dest_size: usize, bytes_written: *mut usize) -> i32;
+    pub fn get_contract_metadata(
+        key_ptr: *const u8,
+        key_size: usize,
+        output_size: *mut usize,
+    ) -> i32;
+    pub fn new_contract(
+        entry_points_ptr: *const u8,
+        entry_points_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        package_hash_ptr: *mut u8,
+        access_key_ptr: *mut u8,
+    );
+    pub fn add_contract_version(
+        package_hash_ptr: *const u8,
+        access_key_ptr: *const u8,
+        entry_points_ptr: *const u8,
+        entry_points_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        version_ptr: *mut u32,
+    );
+    pub fn disable_contract_version(
+        package_hash_ptr: *const u8,
+        access_key_ptr: *const u8,
+        version: u32,
+    );
+    pub fn call_versioned_contract(
+        package_hash_ptr: *const u8,
+        version: u32,
+        entry_point_ptr: *const u8,
+        entry_point_size: usize,
+        args_ptr: *const u8,
+        args_size: usize,
+        result_size: *mut usize,
+    ) -> i32;
+    pub fn get_call_stack(output_size: *mut usize) -> i32;
+    pub fn random_bytes(dest_ptr: *mut u8);
+    pub fn blake2b(data_ptr: *const u8, data_size: usize, dest_ptr: *mut u8);
+    pub fn sha256(data_ptr: *const u8, data_size: usize, dest_ptr: *mut u8);
+    pub fn log(message_ptr: *const u8, message_size: usize);
+    pub fn transfer_uref_ownership(
+        purse_id_ptr: *const u8,
+        purse_id_size: usize,
+        name_ptr: *const u8,
+        name_size: usize,
+        new_purse_id_ptr: *mut u8,
+        new_purse_id_size: usize,
+    ) -> i32;
+    pub fn update_contract_acl(
+        key_ptr: *const u8,
+        key_size: usize,
+        acl_ptr: *const u8,
+        acl_size: usize,
+    ) -> i32;
+    pub fn print(text_ptr: *const u8, text_size: usize) -> i32;
+    pub fn list_authorization_keys(output_size: *mut usize) -> i32;
+    pub fn namespace(name_ptr: *const u8, name_size: usize, dest_ptr: *mut u8);
+    pub fn read_value_namespaced(
+        seed_ptr: *const u8,
+        key_ptr: *const u8,
+        key_size: usize,
+        output_size: *mut usize,
+    ) -> i32;
+    pub fn write_namespaced(
+        seed_ptr: *const u8,
+        key_ptr: *const u8,
+        key_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+    );
+    pub fn add_namespaced(
+        seed_ptr: *const u8,
+        key_ptr: *const u8,
+        key_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+    );
+    pub fn get_era_id(dest_ptr: *const u8);
+    pub fn get_block_height(dest_ptr: *const u8);
+    pub fn update_associated_keys(updates_ptr: *const u8, updates_size: usize) -> i32;
+    pub fn list_associated_keys(output_size: *mut usize) -> i32;
 }
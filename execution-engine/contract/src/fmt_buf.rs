@@ -0,0 +1,159 @@
+//! Formatting into fixed-size buffers, for building messages without allocating.
+//!
+//! Output which doesn't fit into the buffer is dropped, so formatting never fails and never
+//! reverts.  This makes it suitable for diagnostics passed to [`runtime::log`], e.g. when
+//! reporting an error:
+//!
+//! ```rust,no_run
+//! use casperlabs_contract::{contract_api::runtime, fmt_buf};
+//! # let amount = 5;
+//!
+//! let mut buf = [0u8; 64];
+//! runtime::log(fmt_buf::format(&mut buf, format_args!("insufficient funds: {}", amount)));
+//! ```
+//!
+//! [`runtime::log`]: crate::contract_api::runtime::log
+
+use core::{
+    fmt::{self, Write},
+    str,
+};
+
+/// A [`Write`] implementation which writes into a borrowed byte buffer, truncating the output at
+/// the end of the buffer.
+///
+/// Only whole characters are written, so the contents are always valid UTF-8.
+#[derive(Debug)]
+pub struct FmtBuf<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+    truncated: bool,
+}
+
+impl<'a> FmtBuf<'a> {
+    /// Creates an empty `FmtBuf` writing into `buf`.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        FmtBuf {
+            buf,
+            len: 0,
+            truncated: false,
+        }
+    }
+
+    /// Returns the text written so far.
+    pub fn as_str(&self) -> &str {
+        // Can't fail, as only whole characters are copied into the buffer
+        str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+
+    /// Consumes the `FmtBuf`, returning the text written, borrowed for as long as the buffer.
+    pub fn into_str(self) -> &'a str {
+        let FmtBuf { buf, len, .. } = self;
+        str::from_utf8(&buf[..len]).unwrap_or_default()
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing has been written so far.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the size of the underlying buffer.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns `true` if any output was dropped because the buffer was full.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Discards the text written so far, so that the buffer can be reused.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.truncated = false;
+    }
+}
+
+impl<'a> Write for FmtBuf<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let available = self.buf.len() - self.len;
+        let mut count = s.len();
+        if count > available {
+            self.truncated = true;
+            count = available;
+            while !s.is_char_boundary(count) {
+                count -= 1;
+            }
+        }
+        self.buf[self.len..self.len + count].copy_from_slice(&s.as_bytes()[..count]);
+        self.len += count;
+        // Truncation isn't reported as an error, so that `write!` keeps going and the call site
+        // doesn't need to handle it
+        Ok(())
+    }
+}
+
+/// Formats `args` into `buf`, returning as much of the text as fits.
+pub fn format<'a>(buf: &'a mut [u8], args: fmt::Arguments) -> &'a str {
+    let mut fmt_buf = FmtBuf::new(buf);
+    // `FmtBuf` never fails, but a `Display` implementation may; its partial output is kept
+    let _ = fmt_buf.write_fmt(args);
+    fmt_buf.into_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+
+    use super::{format, FmtBuf};
+
+    #[test]
+    fn should_format_into_buffer() {
+        let mut buf = [0u8; 32];
+        let mut fmt_buf = FmtBuf::new(&mut buf);
+        write!(fmt_buf, "{}-{}", 12, "ab").unwrap();
+        assert_eq!(fmt_buf.as_str(), "12-ab");
+        assert_eq!(fmt_buf.len(), 5);
+        assert_eq!(fmt_buf.capacity(), 32);
+        assert!(!fmt_buf.is_truncated());
+
+        fmt_buf.clear();
+        assert!(fmt_buf.is_empty());
+        write!(fmt_buf, "{:?}", Some(1)).unwrap();
+        assert_eq!(fmt_buf.into_str(), "Some(1)");
+    }
+
+    #[test]
+    fn should_truncate_at_char_boundary() {
+        let mut buf = [0u8; 5];
+        let mut fmt_buf = FmtBuf::new(&mut buf);
+        // "é" is two bytes long, so only two of them fit after "a"
+        write!(fmt_buf, "aééé").unwrap();
+        assert_eq!(fmt_buf.as_str(), "aéé");
+        assert!(fmt_buf.is_truncated());
+
+        write!(fmt_buf, "b").unwrap();
+        assert_eq!(fmt_buf.as_str(), "aéé");
+    }
+
+    #[test]
+    fn should_format_into_slice() {
+        let mut buf = [0u8; 0];
+        assert_eq!(format(&mut buf, format_args!("{}", 1)), "");
+
+        let mut buf = [0u8; 8];
+        assert_eq!(
+            format(&mut buf, format_args!("{} {}", "too", "long")),
+            "too long"
+        );
+        assert_eq!(
+            format(&mut buf, format_args!("{} {}", "too", "long!")),
+            "too long"
+        );
+    }
+}
@@ -46,6 +46,18 @@
 //! # Writing Smart Contracts
 //! Support for writing smart contracts are contained in the [`contract_api`](crate::contract_api)
 //! module and its submodules.
+//!
+//! # Global Allocator
+//! By default, when built without the `std` feature, this crate installs
+//! [`wee_alloc`](https://docs.rs/wee_alloc) as the global allocator.  To supply a different one,
+//! disable the default features and declare your own `#[global_allocator]` in the contract:
+//!
+//! ```toml
+//! [dependencies]
+//! casperlabs-contract = { version = "0.22.0", default-features = false }
+//! ```
+//!
+//! The panic and allocation error handlers are provided in either case.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![feature(alloc_error_handler, allocator_api, core_intrinsics, lang_items)]
@@ -55,13 +67,14 @@ extern crate alloc;
 #[macro_use]
 extern crate std;
 
-#[cfg(not(any(feature = "std", test)))]
+#[cfg(all(not(any(feature = "std", test)), feature = "wee_alloc"))]
 #[global_allocator]
 pub static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 pub mod args_parser;
 pub mod contract_api;
 pub mod ext_ffi;
+pub mod fmt_buf;
 #[cfg(not(any(feature = "std", test)))]
 pub mod handlers;
 pub mod unwrap_or_revert;
@@ -0,0 +1,35 @@
+#![no_std]
+
+extern crate contract;
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::ApiError;
+
+enum Arg {
+    Iterations = 0,
+}
+
+#[repr(u16)]
+enum Error {
+    ZeroState = 0,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let iterations: u64 = runtime::get_arg(Arg::Iterations as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    // xorshift64 keeps the loop busy with plain arithmetic and no host calls
+    let mut state: u64 = 88_172_645_463_325_252;
+    for _ in 0..iterations {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+    }
+
+    // Using the result prevents the loop from being optimized away
+    if state == 0 {
+        runtime::revert(ApiError::User(Error::ZeroState as u16));
+    }
+}
@@ -0,0 +1,40 @@
+#![no_std]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::PublicKey, ApiError, U512};
+
+const DELEGATE_METHOD_NAME: &str = "delegate";
+
+// Delegating contract.
+//
+// Accepts the public key of a bonded validator as first argument, and the
+// delegated amount (of type `u64`) as second argument.
+// Issues delegation request to the PoS contract.
+#[no_mangle]
+pub extern "C" fn call() {
+    let pos_pointer = system::get_proof_of_stake();
+    let source_purse = account::get_main_purse();
+    let delegating_purse = system::create_purse();
+    let validator: PublicKey = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let delegate_amount: U512 = runtime::get_arg::<u64>(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+        .into();
+
+    system::transfer_from_purse_to_purse(source_purse, delegating_purse, delegate_amount)
+        .unwrap_or_revert();
+    runtime::call_contract(
+        pos_pointer,
+        (
+            DELEGATE_METHOD_NAME,
+            validator,
+            delegate_amount,
+            delegating_purse,
+        ),
+    )
+}
@@ -26,8 +26,8 @@ pub extern "C" fn call() {
 
     let purse_key = runtime::get_key(&purse_name).unwrap_or_revert_with(ApiError::InvalidPurseName);
     let purse = purse_key
-        .as_uref()
-        .map(|uref| PurseId::new(*uref))
+        .into_uref()
+        .map(PurseId::new)
         .unwrap_or_revert_with(ApiError::InvalidPurse);
 
     let amount: U512 = runtime::get_arg(Arg::Amount as u32)
@@ -0,0 +1,42 @@
+#![no_std]
+
+use contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::PublicKey, ApiError, U512};
+
+const REDELEGATE_METHOD_NAME: &str = "redelegate";
+
+// Redelegating contract.
+//
+// Accepts the public keys of the current and of the new validator as first
+// and second arguments, and the redelegated amount (of type `Option<u64>`) as
+// third argument.
+// Redelegating with `None` moves the whole delegation to the new validator.
+// Otherwise (`Some<u64>`) moves part of the delegated stakes.
+#[no_mangle]
+pub extern "C" fn call() {
+    let pos_pointer = system::get_proof_of_stake();
+
+    let from_validator: PublicKey = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let to_validator: PublicKey = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let arg_2: Option<u64> = runtime::get_arg(2)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let redelegate_amount: Option<U512> = arg_2.map(Into::into);
+
+    runtime::call_contract(
+        pos_pointer,
+        (
+            REDELEGATE_METHOD_NAME,
+            from_validator,
+            to_validator,
+            redelegate_amount,
+        ),
+    )
+}
@@ -1,3 +1,11 @@
+//! The standard payment code, which pays from the main purse of the account the amount of motes
+//! given as its first argument.
+//!
+//! Custom payment contracts can reuse the flow of [`StandardPayment::pay`] by implementing the
+//! provider traits for their own type, and overriding only the steps which differ.  For example, a
+//! contract paying in another token can override [`SourcePurseProvider`] to return a purse
+//! holding the motes bought with that token.
+
 #![no_std]
 
 use contract::{
@@ -12,18 +20,75 @@ enum Arg {
     Amount = 0,
 }
 
-pub fn delegate() {
-    let amount: U512 = runtime::get_arg(Arg::Amount as u32)
-        .unwrap_or_revert_with(ApiError::MissingArgument)
-        .unwrap_or_revert_with(ApiError::InvalidArgument);
+/// Provides the amount of motes to be paid.
+pub trait AmountProvider {
+    /// Returns the first argument of the payment code.
+    fn amount(&mut self) -> U512 {
+        runtime::get_arg(Arg::Amount as u32)
+            .unwrap_or_revert_with(ApiError::MissingArgument)
+            .unwrap_or_revert_with(ApiError::InvalidArgument)
+    }
+}
+
+/// Provides the purse holding the motes to be paid.
+pub trait SourcePurseProvider {
+    /// Returns the main purse of the account.
+    fn source_purse(&mut self, _amount: U512) -> Result<PurseId, ApiError> {
+        Ok(account::get_main_purse())
+    }
+}
+
+/// Provides the purse collecting the payment for the deploy.
+pub trait PaymentPurseProvider {
+    /// Returns the payment purse of the Proof of Stake contract.
+    fn payment_purse(&mut self) -> PurseId {
+        let pos_pointer = system::get_proof_of_stake();
+        runtime::call_contract(pos_pointer, (GET_PAYMENT_PURSE,))
+    }
+}
+
+/// Moves the paid motes between purses.
+pub trait TransferProvider {
+    fn transfer_from_purse_to_purse(
+        &mut self,
+        source: PurseId,
+        target: PurseId,
+        amount: U512,
+    ) -> Result<(), ApiError> {
+        system::transfer_from_purse_to_purse(source, target, amount)
+    }
+}
+
+/// The flow of the payment code, implemented for every type which implements the provider traits.
+pub trait StandardPayment:
+    AmountProvider + SourcePurseProvider + PaymentPurseProvider + TransferProvider
+{
+    fn pay(&mut self) -> Result<(), ApiError> {
+        let amount = self.amount();
+        let source = self.source_purse(amount)?;
+        let payment_purse = self.payment_purse();
+        self.transfer_from_purse_to_purse(source, payment_purse, amount)
+    }
+}
+
+impl<T> StandardPayment for T where
+    T: AmountProvider + SourcePurseProvider + PaymentPurseProvider + TransferProvider
+{
+}
+
+/// The standard payment, using the default implementation of every provider trait.
+pub struct MainPurse;
 
-    let main_purse: PurseId = account::get_main_purse();
+impl AmountProvider for MainPurse {}
 
-    let pos_pointer = system::get_proof_of_stake();
+impl SourcePurseProvider for MainPurse {}
 
-    let payment_purse: PurseId = runtime::call_contract(pos_pointer, (GET_PAYMENT_PURSE,));
+impl PaymentPurseProvider for MainPurse {}
 
-    system::transfer_from_purse_to_purse(main_purse, payment_purse, amount).unwrap_or_revert();
+impl TransferProvider for MainPurse {}
+
+pub fn delegate() {
+    MainPurse.pay().unwrap_or_revert();
 }
 
 #[cfg(not(feature = "lib"))]
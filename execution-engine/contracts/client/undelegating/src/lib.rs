@@ -0,0 +1,33 @@
+#![no_std]
+
+use contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::PublicKey, ApiError, U512};
+
+const UNDELEGATE_METHOD_NAME: &str = "undelegate";
+
+// Undelegating contract.
+//
+// Accepts the public key of the validator as first argument, and the
+// undelegated amount (of type `Option<u64>`) as second argument.
+// Undelegating with `None` withdraws the whole delegation to the validator.
+// Otherwise (`Some<u64>`) withdraws part of the delegated stakes.
+#[no_mangle]
+pub extern "C" fn call() {
+    let pos_pointer = system::get_proof_of_stake();
+
+    let validator: PublicKey = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let arg_1: Option<u64> = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let undelegate_amount: Option<U512> = arg_1.map(Into::into);
+
+    runtime::call_contract(
+        pos_pointer,
+        (UNDELEGATE_METHOD_NAME, validator, undelegate_amount),
+    )
+}
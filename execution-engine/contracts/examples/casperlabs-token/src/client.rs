@@ -0,0 +1,45 @@
+use contract::contract_api::runtime;
+use types::{account::PublicKey, ContractRef, U512};
+
+use crate::methods;
+
+/// A handle for calling a deployed token contract from session code or other contracts.
+///
+/// The token contract sees the account executing the deploy as its caller.  Failed operations
+/// revert the whole deploy with the corresponding [`Error`](crate::Error) as the user error code.
+#[derive(Debug, Clone)]
+pub struct TokenRef(ContractRef);
+
+impl TokenRef {
+    pub fn new(contract_ref: ContractRef) -> Self {
+        TokenRef(contract_ref)
+    }
+
+    pub fn contract_ref(&self) -> &ContractRef {
+        &self.0
+    }
+
+    pub fn balance_of(&self, owner: PublicKey) -> U512 {
+        runtime::call_contract(self.0.clone(), (methods::BALANCE_OF, owner))
+    }
+
+    pub fn total_supply(&self) -> U512 {
+        runtime::call_contract(self.0.clone(), (methods::TOTAL_SUPPLY,))
+    }
+
+    pub fn allowance(&self, owner: PublicKey, spender: PublicKey) -> U512 {
+        runtime::call_contract(self.0.clone(), (methods::ALLOWANCE, owner, spender))
+    }
+
+    pub fn transfer(&self, to: PublicKey, amount: U512) {
+        runtime::call_contract(self.0.clone(), (methods::TRANSFER, to, amount))
+    }
+
+    pub fn approve(&self, spender: PublicKey, amount: U512) {
+        runtime::call_contract(self.0.clone(), (methods::APPROVE, spender, amount))
+    }
+
+    pub fn transfer_from(&self, owner: PublicKey, to: PublicKey, amount: U512) {
+        runtime::call_contract(self.0.clone(), (methods::TRANSFER_FROM, owner, to, amount))
+    }
+}
@@ -0,0 +1,42 @@
+use types::ApiError;
+
+/// Errors returned by the operations of a [`Token`](crate::Token).
+///
+/// When reverting a token contract, these are converted into [`ApiError::User`] with the
+/// discriminant as the user error code.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u16)]
+pub enum Error {
+    /// The balance of the source account is lower than the amount to be moved.
+    InsufficientBalance = 1,
+    /// The allowance granted to the spender is lower than the amount to be moved.
+    InsufficientAllowance = 2,
+    /// A balance or the total supply would exceed the maximum amount.
+    Overflow = 3,
+    /// The token contract has already been initialized.
+    AlreadyInitialized = 4,
+    /// The token contract was called with an unknown method name.
+    UnknownMethod = 5,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::ApiError;
+
+    use super::Error;
+
+    #[test]
+    fn should_convert_to_user_error() {
+        assert_eq!(
+            ApiError::from(Error::InsufficientBalance),
+            ApiError::User(1)
+        );
+        assert_eq!(ApiError::from(Error::UnknownMethod), ApiError::User(5));
+    }
+}
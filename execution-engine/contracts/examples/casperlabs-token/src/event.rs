@@ -0,0 +1,165 @@
+use alloc::vec::Vec;
+
+use types::bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH};
+
+const TRANSFER_TAG: u8 = 0;
+const APPROVAL_TAG: u8 = 1;
+const MINT_TAG: u8 = 2;
+const BURN_TAG: u8 = 3;
+
+/// A change to the state of a token, recorded through [`Events::emit`](crate::Events::emit).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<Address, Amount> {
+    /// `amount` was moved from the balance of `from` to the balance of `to`.
+    Transfer {
+        from: Address,
+        to: Address,
+        amount: Amount,
+    },
+    /// `spender` was allowed to transfer up to `amount` from the balance of `owner`.
+    Approval {
+        owner: Address,
+        spender: Address,
+        amount: Amount,
+    },
+    /// `amount` was created and added to the balance of `to`.
+    Mint { to: Address, amount: Amount },
+    /// `amount` was removed from the balance of `from` and destroyed.
+    Burn { from: Address, amount: Amount },
+}
+
+impl<Address: ToBytes, Amount: ToBytes> ToBytes for Event<Address, Amount> {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = Vec::new();
+        match self {
+            Event::Transfer { from, to, amount } => {
+                result.push(TRANSFER_TAG);
+                result.append(&mut from.to_bytes()?);
+                result.append(&mut to.to_bytes()?);
+                result.append(&mut amount.to_bytes()?);
+            }
+            Event::Approval {
+                owner,
+                spender,
+                amount,
+            } => {
+                result.push(APPROVAL_TAG);
+                result.append(&mut owner.to_bytes()?);
+                result.append(&mut spender.to_bytes()?);
+                result.append(&mut amount.to_bytes()?);
+            }
+            Event::Mint { to, amount } => {
+                result.push(MINT_TAG);
+                result.append(&mut to.to_bytes()?);
+                result.append(&mut amount.to_bytes()?);
+            }
+            Event::Burn { from, amount } => {
+                result.push(BURN_TAG);
+                result.append(&mut from.to_bytes()?);
+                result.append(&mut amount.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                Event::Transfer { from, to, amount } => {
+                    from.serialized_length() + to.serialized_length() + amount.serialized_length()
+                }
+                Event::Approval {
+                    owner,
+                    spender,
+                    amount,
+                } => {
+                    owner.serialized_length()
+                        + spender.serialized_length()
+                        + amount.serialized_length()
+                }
+                Event::Mint { to, amount } => to.serialized_length() + amount.serialized_length(),
+                Event::Burn { from, amount } => {
+                    from.serialized_length() + amount.serialized_length()
+                }
+            }
+    }
+}
+
+impl<Address: FromBytes, Amount: FromBytes> FromBytes for Event<Address, Amount> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        match tag {
+            TRANSFER_TAG => {
+                let (from, rem) = Address::from_bytes(rem)?;
+                let (to, rem) = Address::from_bytes(rem)?;
+                let (amount, rem) = Amount::from_bytes(rem)?;
+                Ok((Event::Transfer { from, to, amount }, rem))
+            }
+            APPROVAL_TAG => {
+                let (owner, rem) = Address::from_bytes(rem)?;
+                let (spender, rem) = Address::from_bytes(rem)?;
+                let (amount, rem) = Amount::from_bytes(rem)?;
+                let event = Event::Approval {
+                    owner,
+                    spender,
+                    amount,
+                };
+                Ok((event, rem))
+            }
+            MINT_TAG => {
+                let (to, rem) = Address::from_bytes(rem)?;
+                let (amount, rem) = Amount::from_bytes(rem)?;
+                Ok((Event::Mint { to, amount }, rem))
+            }
+            BURN_TAG => {
+                let (from, rem) = Address::from_bytes(rem)?;
+                let (amount, rem) = Amount::from_bytes(rem)?;
+                Ok((Event::Burn { from, amount }, rem))
+            }
+            _ => Err(bytesrepr::Error::FormattingError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{
+        account::PublicKey,
+        bytesrepr::{self, ToBytes},
+        U512,
+    };
+
+    use super::Event;
+
+    #[test]
+    fn should_serialize_and_deserialize_events() {
+        let alice = PublicKey::new([1; 32]);
+        let bob = PublicKey::new([2; 32]);
+        let amount = U512::from(42);
+        let events = [
+            Event::Transfer {
+                from: alice,
+                to: bob,
+                amount,
+            },
+            Event::Approval {
+                owner: alice,
+                spender: bob,
+                amount,
+            },
+            Event::Mint { to: alice, amount },
+            Event::Burn { from: bob, amount },
+        ];
+        for event in events.iter() {
+            let bytes = event.to_bytes().unwrap();
+            let deserialized: Event<PublicKey, U512> = bytesrepr::deserialize(bytes).unwrap();
+            assert_eq!(&deserialized, event);
+        }
+    }
+
+    #[test]
+    fn should_fail_to_deserialize_unknown_tag() {
+        let result: Result<Event<PublicKey, U512>, _> = bytesrepr::deserialize(alloc::vec![4]);
+        assert_eq!(result, Err(bytesrepr::Error::FormattingError));
+    }
+}
@@ -0,0 +1,71 @@
+//! Keys under which [`LocalStorage`](crate::LocalStorage) keeps the state of a token.
+//!
+//! These are the keys passed to `storage::read_local` and `storage::write_local`, so the state can
+//! be queried from outside the contract as `Key::local(contract_hash, &key.to_bytes()?)`.
+
+use alloc::vec::Vec;
+
+use types::account::PublicKey;
+
+const BALANCE_PREFIX: u8 = 0;
+const ALLOWANCE_PREFIX: u8 = 1;
+const TOTAL_SUPPLY_PREFIX: u8 = 2;
+const EVENT_COUNT_PREFIX: u8 = 3;
+const EVENT_PREFIX: u8 = 4;
+const INITIALIZED_PREFIX: u8 = 5;
+
+fn prefixed(prefix: u8, parts: &[&[u8]]) -> Vec<u8> {
+    let mut key = Vec::with_capacity(1 + parts.iter().map(|part| part.len()).sum::<usize>());
+    key.push(prefix);
+    for part in parts {
+        key.extend_from_slice(part);
+    }
+    key
+}
+
+/// Key of the balance of `owner`, a `U512`.
+pub fn balance_key(owner: &PublicKey) -> Vec<u8> {
+    prefixed(BALANCE_PREFIX, &[&owner.value()])
+}
+
+/// Key of the amount `spender` may transfer on behalf of `owner`, a `U512`.
+pub fn allowance_key(owner: &PublicKey, spender: &PublicKey) -> Vec<u8> {
+    prefixed(ALLOWANCE_PREFIX, &[&owner.value(), &spender.value()])
+}
+
+/// Key of the total supply, a `U512`.
+pub fn total_supply_key() -> Vec<u8> {
+    prefixed(TOTAL_SUPPLY_PREFIX, &[])
+}
+
+/// Key of the number of events emitted so far, a `u64`.
+pub fn event_count_key() -> Vec<u8> {
+    prefixed(EVENT_COUNT_PREFIX, &[])
+}
+
+/// Key of the serialized [`Event`](crate::Event) with the given index, a `Vec<u8>`.
+pub fn event_key(index: u64) -> Vec<u8> {
+    prefixed(EVENT_PREFIX, &[&index.to_le_bytes()])
+}
+
+/// Key of the flag set once the token has been initialized, a `bool`.
+pub fn initialized_key() -> Vec<u8> {
+    prefixed(INITIALIZED_PREFIX, &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use types::account::PublicKey;
+
+    use super::*;
+
+    #[test]
+    fn should_not_collide() {
+        let alice = PublicKey::new([1; 32]);
+        let bob = PublicKey::new([2; 32]);
+        assert_ne!(balance_key(&alice), balance_key(&bob));
+        assert_ne!(allowance_key(&alice, &bob), allowance_key(&bob, &alice));
+        assert_ne!(event_key(0), event_key(1));
+        assert_ne!(total_supply_key(), event_count_key());
+    }
+}
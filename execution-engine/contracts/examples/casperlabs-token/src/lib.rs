@@ -0,0 +1,31 @@
+//! A fungible token standard for CasperLabs smart contracts.
+//!
+//! The [`Token`] trait implements the ERC20-style operations on top of three storage traits,
+//! [`Balances`], [`Allowances`] and [`Events`], so the same, tested logic can be reused whatever
+//! the token keeps its state in.
+//!
+//! With the `contract` feature enabled, [`LocalStorage`] implements those traits on the local
+//! state of the calling contract, and [`TokenRef`] calls a deployed token contract from another
+//! contract or session code.
+
+#![no_std]
+
+extern crate alloc;
+
+#[cfg(feature = "contract")]
+mod client;
+mod error;
+mod event;
+pub mod keys;
+#[cfg(feature = "contract")]
+mod local_storage;
+pub mod methods;
+mod token;
+
+#[cfg(feature = "contract")]
+pub use client::TokenRef;
+pub use error::Error;
+pub use event::Event;
+#[cfg(feature = "contract")]
+pub use local_storage::LocalStorage;
+pub use token::{Allowances, Balances, Events, Token};
@@ -0,0 +1,51 @@
+use contract::{contract_api::storage, unwrap_or_revert::UnwrapOrRevert};
+use types::{account::PublicKey, bytesrepr::ToBytes, U512};
+
+use crate::{keys, Allowances, Balances, Event, Events};
+
+/// Keeps the state of a token in the local state of the running contract, under the keys defined
+/// in [`keys`].
+///
+/// Events are serialized and stored under consecutive indices, so that clients can read them back
+/// without replaying deploys.  Reverts if the local state holds values of unexpected types.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct LocalStorage;
+
+impl Balances<PublicKey, U512> for LocalStorage {
+    fn read_balance(&mut self, owner: &PublicKey) -> Option<U512> {
+        storage::read_local(&keys::balance_key(owner)).unwrap_or_revert()
+    }
+
+    fn write_balance(&mut self, owner: &PublicKey, balance: U512) {
+        storage::write_local(keys::balance_key(owner), balance)
+    }
+
+    fn read_total_supply(&mut self) -> Option<U512> {
+        storage::read_local(&keys::total_supply_key()).unwrap_or_revert()
+    }
+
+    fn write_total_supply(&mut self, total_supply: U512) {
+        storage::write_local(keys::total_supply_key(), total_supply)
+    }
+}
+
+impl Allowances<PublicKey, U512> for LocalStorage {
+    fn read_allowance(&mut self, owner: &PublicKey, spender: &PublicKey) -> Option<U512> {
+        storage::read_local(&keys::allowance_key(owner, spender)).unwrap_or_revert()
+    }
+
+    fn write_allowance(&mut self, owner: &PublicKey, spender: &PublicKey, amount: U512) {
+        storage::write_local(keys::allowance_key(owner, spender), amount)
+    }
+}
+
+impl Events<PublicKey, U512> for LocalStorage {
+    fn emit(&mut self, event: Event<PublicKey, U512>) {
+        let index: u64 = storage::read_local(&keys::event_count_key())
+            .unwrap_or_revert()
+            .unwrap_or_default();
+        let event_bytes = event.into_bytes().unwrap_or_revert();
+        storage::write_local(keys::event_key(index), event_bytes);
+        storage::write_local(keys::event_count_key(), index + 1);
+    }
+}
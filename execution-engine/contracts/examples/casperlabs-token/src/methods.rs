@@ -0,0 +1,16 @@
+//! Names of the methods of a token contract, passed as its first argument.
+
+/// Mints the initial supply to the caller; only callable once.
+pub const INIT: &str = "init";
+/// Returns the balance of the given account.
+pub const BALANCE_OF: &str = "balance_of";
+/// Returns the total supply.
+pub const TOTAL_SUPPLY: &str = "total_supply";
+/// Returns the amount the given spender may still transfer from the given owner.
+pub const ALLOWANCE: &str = "allowance";
+/// Transfers an amount from the caller to the given recipient.
+pub const TRANSFER: &str = "transfer";
+/// Allows the given spender to transfer up to an amount from the caller.
+pub const APPROVE: &str = "approve";
+/// Transfers an amount from the given owner to the given recipient, on behalf of the caller.
+pub const TRANSFER_FROM: &str = "transfer_from";
@@ -0,0 +1,340 @@
+use num_traits::{CheckedAdd, CheckedSub, Zero};
+
+use crate::{Error, Event};
+
+/// Storage of the balances and the total supply of a token.
+///
+/// Accounts which never held any tokens have no stored balance, and are reported as holding zero
+/// tokens.
+pub trait Balances<Address, Amount> {
+    fn read_balance(&mut self, owner: &Address) -> Option<Amount>;
+    fn write_balance(&mut self, owner: &Address, balance: Amount);
+    fn read_total_supply(&mut self) -> Option<Amount>;
+    fn write_total_supply(&mut self, total_supply: Amount);
+}
+
+/// Storage of the amounts which spenders are allowed to transfer on behalf of owners.
+pub trait Allowances<Address, Amount> {
+    fn read_allowance(&mut self, owner: &Address, spender: &Address) -> Option<Amount>;
+    fn write_allowance(&mut self, owner: &Address, spender: &Address, amount: Amount);
+}
+
+/// Sink for the events describing every change to the state of a token.
+pub trait Events<Address, Amount> {
+    fn emit(&mut self, event: Event<Address, Amount>);
+}
+
+/// The operations of a fungible token.
+///
+/// Implemented for every type which implements [`Balances`], [`Allowances`] and [`Events`].  None
+/// of the operations checks who is calling it, so callers must pass the caller of the contract as
+/// the `owner` of transfers and approvals, and restrict minting and burning as needed.
+pub trait Token<Address, Amount>:
+    Balances<Address, Amount> + Allowances<Address, Amount> + Events<Address, Amount>
+where
+    Address: Clone,
+    Amount: Zero + CheckedAdd + CheckedSub + Copy,
+{
+    fn balance_of(&mut self, owner: &Address) -> Amount {
+        self.read_balance(owner).unwrap_or_else(Amount::zero)
+    }
+
+    fn total_supply(&mut self) -> Amount {
+        self.read_total_supply().unwrap_or_else(Amount::zero)
+    }
+
+    fn allowance(&mut self, owner: &Address, spender: &Address) -> Amount {
+        self.read_allowance(owner, spender)
+            .unwrap_or_else(Amount::zero)
+    }
+
+    /// Creates `amount` new tokens and adds them to the balance of `to`.
+    fn mint(&mut self, to: &Address, amount: Amount) -> Result<(), Error> {
+        let total_supply = self
+            .total_supply()
+            .checked_add(&amount)
+            .ok_or(Error::Overflow)?;
+        let balance = self
+            .balance_of(to)
+            .checked_add(&amount)
+            .ok_or(Error::Overflow)?;
+        self.write_total_supply(total_supply);
+        self.write_balance(to, balance);
+        self.emit(Event::Mint {
+            to: to.clone(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Destroys `amount` tokens from the balance of `from`.
+    fn burn(&mut self, from: &Address, amount: Amount) -> Result<(), Error> {
+        let balance = self
+            .balance_of(from)
+            .checked_sub(&amount)
+            .ok_or(Error::InsufficientBalance)?;
+        let total_supply = self
+            .total_supply()
+            .checked_sub(&amount)
+            .ok_or(Error::Overflow)?;
+        self.write_balance(from, balance);
+        self.write_total_supply(total_supply);
+        self.emit(Event::Burn {
+            from: from.clone(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Moves `amount` tokens from the balance of `from` to the balance of `to`.
+    fn transfer(&mut self, from: &Address, to: &Address, amount: Amount) -> Result<(), Error> {
+        let from_balance = self
+            .balance_of(from)
+            .checked_sub(&amount)
+            .ok_or(Error::InsufficientBalance)?;
+        self.write_balance(from, from_balance);
+        // Read after writing, so that transfers to oneself leave the balance unchanged.
+        let to_balance = self
+            .balance_of(to)
+            .checked_add(&amount)
+            .ok_or(Error::Overflow)?;
+        self.write_balance(to, to_balance);
+        self.emit(Event::Transfer {
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Allows `spender` to transfer up to `amount` tokens from the balance of `owner`, replacing
+    /// any previous allowance.
+    fn approve(&mut self, owner: &Address, spender: &Address, amount: Amount) {
+        self.write_allowance(owner, spender, amount);
+        self.emit(Event::Approval {
+            owner: owner.clone(),
+            spender: spender.clone(),
+            amount,
+        });
+    }
+
+    /// Moves `amount` tokens from the balance of `owner` to the balance of `to` on behalf of
+    /// `spender`, deducting them from the allowance of `spender`.
+    fn transfer_from(
+        &mut self,
+        spender: &Address,
+        owner: &Address,
+        to: &Address,
+        amount: Amount,
+    ) -> Result<(), Error> {
+        let allowance = self
+            .allowance(owner, spender)
+            .checked_sub(&amount)
+            .ok_or(Error::InsufficientAllowance)?;
+        self.transfer(owner, to, amount)?;
+        self.write_allowance(owner, spender, allowance);
+        Ok(())
+    }
+}
+
+impl<T, Address, Amount> Token<Address, Amount> for T
+where
+    T: Balances<Address, Amount> + Allowances<Address, Amount> + Events<Address, Amount>,
+    Address: Clone,
+    Amount: Zero + CheckedAdd + CheckedSub + Copy,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeMap, vec::Vec};
+
+    use super::{Allowances, Balances, Events, Token};
+    use crate::{Error, Event};
+
+    type Address = u8;
+    type Amount = u64;
+
+    const ALICE: Address = 1;
+    const BOB: Address = 2;
+    const CAROL: Address = 3;
+
+    #[derive(Default)]
+    struct InMemoryToken {
+        balances: BTreeMap<Address, Amount>,
+        allowances: BTreeMap<(Address, Address), Amount>,
+        total_supply: Option<Amount>,
+        events: Vec<Event<Address, Amount>>,
+    }
+
+    impl Balances<Address, Amount> for InMemoryToken {
+        fn read_balance(&mut self, owner: &Address) -> Option<Amount> {
+            self.balances.get(owner).copied()
+        }
+
+        fn write_balance(&mut self, owner: &Address, balance: Amount) {
+            self.balances.insert(*owner, balance);
+        }
+
+        fn read_total_supply(&mut self) -> Option<Amount> {
+            self.total_supply
+        }
+
+        fn write_total_supply(&mut self, total_supply: Amount) {
+            self.total_supply = Some(total_supply);
+        }
+    }
+
+    impl Allowances<Address, Amount> for InMemoryToken {
+        fn read_allowance(&mut self, owner: &Address, spender: &Address) -> Option<Amount> {
+            self.allowances.get(&(*owner, *spender)).copied()
+        }
+
+        fn write_allowance(&mut self, owner: &Address, spender: &Address, amount: Amount) {
+            self.allowances.insert((*owner, *spender), amount);
+        }
+    }
+
+    impl Events<Address, Amount> for InMemoryToken {
+        fn emit(&mut self, event: Event<Address, Amount>) {
+            self.events.push(event);
+        }
+    }
+
+    fn token_with_supply(supply: Amount) -> InMemoryToken {
+        let mut token = InMemoryToken::default();
+        token.mint(&ALICE, supply).unwrap();
+        token.events.clear();
+        token
+    }
+
+    #[test]
+    fn should_mint() {
+        let mut token = InMemoryToken::default();
+        assert_eq!(token.total_supply(), 0);
+        token.mint(&ALICE, 100).unwrap();
+        token.mint(&BOB, 20).unwrap();
+        assert_eq!(token.balance_of(&ALICE), 100);
+        assert_eq!(token.balance_of(&BOB), 20);
+        assert_eq!(token.total_supply(), 120);
+        assert_eq!(
+            token.events,
+            [
+                Event::Mint {
+                    to: ALICE,
+                    amount: 100
+                },
+                Event::Mint {
+                    to: BOB,
+                    amount: 20
+                }
+            ]
+        );
+        assert_eq!(token.mint(&BOB, Amount::max_value()), Err(Error::Overflow));
+        assert_eq!(token.total_supply(), 120);
+    }
+
+    #[test]
+    fn should_burn() {
+        let mut token = token_with_supply(100);
+        token.burn(&ALICE, 30).unwrap();
+        assert_eq!(token.balance_of(&ALICE), 70);
+        assert_eq!(token.total_supply(), 70);
+        assert_eq!(token.burn(&ALICE, 71), Err(Error::InsufficientBalance));
+        assert_eq!(
+            token.events,
+            [Event::Burn {
+                from: ALICE,
+                amount: 30
+            }]
+        );
+    }
+
+    #[test]
+    fn should_transfer() {
+        let mut token = token_with_supply(100);
+        token.transfer(&ALICE, &BOB, 40).unwrap();
+        assert_eq!(token.balance_of(&ALICE), 60);
+        assert_eq!(token.balance_of(&BOB), 40);
+        assert_eq!(token.total_supply(), 100);
+        assert_eq!(
+            token.events,
+            [Event::Transfer {
+                from: ALICE,
+                to: BOB,
+                amount: 40
+            }]
+        );
+    }
+
+    #[test]
+    fn should_transfer_to_self() {
+        let mut token = token_with_supply(100);
+        token.transfer(&ALICE, &ALICE, 40).unwrap();
+        assert_eq!(token.balance_of(&ALICE), 100);
+    }
+
+    #[test]
+    fn should_not_transfer_more_than_balance() {
+        let mut token = token_with_supply(100);
+        assert_eq!(
+            token.transfer(&ALICE, &BOB, 101),
+            Err(Error::InsufficientBalance)
+        );
+        assert_eq!(token.balance_of(&ALICE), 100);
+        assert_eq!(token.balance_of(&BOB), 0);
+        assert!(token.events.is_empty());
+    }
+
+    #[test]
+    fn should_transfer_from_within_allowance() {
+        let mut token = token_with_supply(100);
+        token.approve(&ALICE, &BOB, 50);
+        assert_eq!(token.allowance(&ALICE, &BOB), 50);
+        token.transfer_from(&BOB, &ALICE, &CAROL, 30).unwrap();
+        assert_eq!(token.balance_of(&ALICE), 70);
+        assert_eq!(token.balance_of(&CAROL), 30);
+        assert_eq!(token.allowance(&ALICE, &BOB), 20);
+        assert_eq!(
+            token.events,
+            [
+                Event::Approval {
+                    owner: ALICE,
+                    spender: BOB,
+                    amount: 50
+                },
+                Event::Transfer {
+                    from: ALICE,
+                    to: CAROL,
+                    amount: 30
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn should_not_transfer_from_beyond_allowance() {
+        let mut token = token_with_supply(100);
+        token.approve(&ALICE, &BOB, 50);
+        assert_eq!(
+            token.transfer_from(&BOB, &ALICE, &CAROL, 51),
+            Err(Error::InsufficientAllowance)
+        );
+        assert_eq!(
+            token.transfer_from(&CAROL, &ALICE, &CAROL, 1),
+            Err(Error::InsufficientAllowance)
+        );
+        assert_eq!(token.allowance(&ALICE, &BOB), 50);
+    }
+
+    #[test]
+    fn should_not_use_allowance_when_transfer_fails() {
+        let mut token = token_with_supply(100);
+        token.approve(&ALICE, &BOB, 500);
+        assert_eq!(
+            token.transfer_from(&BOB, &ALICE, &CAROL, 101),
+            Err(Error::InsufficientBalance)
+        );
+        assert_eq!(token.allowance(&ALICE, &BOB), 500);
+    }
+}
@@ -3,10 +3,9 @@
 extern crate alloc;
 
 use alloc::{collections::BTreeMap, string::String};
-use core::convert::TryInto;
 
 use contract::{
-    contract_api::{runtime, storage, TURef},
+    contract_api::{runtime, storage, AsTURef, TURef},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use types::{ApiError, CLValue, Key};
@@ -38,7 +37,7 @@ impl Into<ApiError> for Error {
 pub extern "C" fn counter_ext() {
     let turef: TURef<i32> = runtime::get_key(COUNT_KEY)
         .unwrap_or_revert()
-        .try_into()
+        .as_turef()
         .unwrap_or_revert();
 
     let method_name: String = runtime::get_arg(Arg::MethodName as u32)
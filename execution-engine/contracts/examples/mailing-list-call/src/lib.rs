@@ -3,10 +3,10 @@
 extern crate alloc;
 
 use alloc::{string::String, vec::Vec};
-use core::convert::{From, TryInto};
+use core::convert::From;
 
 use contract::{
-    contract_api::{runtime, storage, TURef},
+    contract_api::{runtime, storage, AsTURef, TURef},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use types::{ApiError, Key};
@@ -56,7 +56,7 @@ pub extern "C" fn call() {
     let args = (PUB_METHOD, message);
     runtime::call_contract::<_, ()>(contract_ref, args);
 
-    let list_key: TURef<Vec<String>> = sub_key.try_into().unwrap_or_revert();
+    let list_key: TURef<Vec<String>> = sub_key.as_turef().unwrap_or_revert();
     let messages = storage::read(list_key)
         .unwrap_or_revert_with(Error::GetMessagesURef)
         .unwrap_or_revert_with(Error::FindMessagesURef);
@@ -6,10 +6,9 @@ extern crate alloc;
 #[rustfmt::skip]
 use alloc::vec;
 use alloc::{collections::BTreeMap, string::String, vec::Vec};
-use core::convert::TryInto;
 
 use contract::{
-    contract_api::{runtime, storage, TURef},
+    contract_api::{runtime, storage, AsTURef, TURef},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use types::{ApiError, CLValue, Key};
@@ -36,7 +35,7 @@ impl Into<ApiError> for Error {
 
 fn get_list_key(name: &str) -> TURef<Vec<String>> {
     let key = runtime::get_key(name).unwrap_or_revert_with(ApiError::GetKey);
-    key.try_into().unwrap_or_revert()
+    key.as_turef().unwrap_or_revert()
 }
 
 fn update_list(name: String) {
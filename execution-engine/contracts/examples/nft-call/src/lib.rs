@@ -0,0 +1,114 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::PublicKey,
+    bytesrepr::{FromBytes, ToBytes},
+    ApiError, CLTyped, ContractRef, Key,
+};
+
+const RESULT_KEY: &str = "result";
+
+const METHOD_MINT: &str = "mint";
+const METHOD_BURN: &str = "burn";
+const METHOD_TRANSFER: &str = "transfer";
+const METHOD_APPROVE: &str = "approve";
+const METHOD_OWNER_OF: &str = "owner_of";
+const METHOD_METADATA: &str = "metadata";
+const METHOD_BALANCE_OF: &str = "balance_of";
+const METHOD_TOKENS_OF: &str = "tokens_of";
+const METHOD_TOTAL_SUPPLY: &str = "total_supply";
+
+enum Arg {
+    Nft = 0,
+    MethodName = 1,
+}
+
+#[repr(u16)]
+enum Error {
+    UnknownMethod = 6,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+fn get_arg<T: FromBytes>(index: u32) -> T {
+    runtime::get_arg(index)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+fn put_result<T: CLTyped + ToBytes>(value: T) {
+    runtime::put_key(RESULT_KEY, storage::new_turef(value).into());
+}
+
+/// Calls the NFT contract given as the first argument, passing it the method name and the
+/// remaining arguments.
+///
+/// The results of queries are stored under the "result" named key of the calling account.
+#[no_mangle]
+pub extern "C" fn call() {
+    let nft_key: Key = get_arg(Arg::Nft as u32);
+    let nft: ContractRef = nft_key
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    let method: String = get_arg(Arg::MethodName as u32);
+
+    match method.as_str() {
+        METHOD_MINT => {
+            let to: PublicKey = get_arg(2);
+            let token_id: String = get_arg(3);
+            let metadata: String = get_arg(4);
+            runtime::call_contract(nft, (METHOD_MINT, to, token_id, metadata))
+        }
+        METHOD_BURN => {
+            let token_id: String = get_arg(2);
+            runtime::call_contract(nft, (METHOD_BURN, token_id))
+        }
+        METHOD_TRANSFER => {
+            let to: PublicKey = get_arg(2);
+            let token_id: String = get_arg(3);
+            runtime::call_contract(nft, (METHOD_TRANSFER, to, token_id))
+        }
+        METHOD_APPROVE => {
+            let spender: Option<PublicKey> = get_arg(2);
+            let token_id: String = get_arg(3);
+            runtime::call_contract(nft, (METHOD_APPROVE, spender, token_id))
+        }
+        METHOD_OWNER_OF => {
+            let token_id: String = get_arg(2);
+            let owner: Option<PublicKey> = runtime::call_contract(nft, (METHOD_OWNER_OF, token_id));
+            put_result(owner);
+        }
+        METHOD_METADATA => {
+            let token_id: String = get_arg(2);
+            let metadata: Option<String> = runtime::call_contract(nft, (METHOD_METADATA, token_id));
+            put_result(metadata);
+        }
+        METHOD_BALANCE_OF => {
+            let owner: PublicKey = get_arg(2);
+            let balance: u64 = runtime::call_contract(nft, (METHOD_BALANCE_OF, owner));
+            put_result(balance);
+        }
+        METHOD_TOKENS_OF => {
+            let owner: PublicKey = get_arg(2);
+            let tokens: Vec<String> = runtime::call_contract(nft, (METHOD_TOKENS_OF, owner));
+            put_result(tokens);
+        }
+        METHOD_TOTAL_SUPPLY => {
+            let total_supply: u64 = runtime::call_contract(nft, (METHOD_TOTAL_SUPPLY,));
+            put_result(total_supply);
+        }
+        _ => runtime::revert(Error::UnknownMethod),
+    }
+}
@@ -0,0 +1,228 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::PublicKey,
+    bytesrepr::{FromBytes, ToBytes},
+    ApiError, CLTyped, CLValue,
+};
+
+const NFT_EXT: &str = "nft_ext";
+
+// Local keys holding the configuration and totals of the contract.
+const MINTER_KEY: &str = "minter";
+const TOTAL_SUPPLY_KEY: &str = "total_supply";
+
+// Namespaces of local state, each used as a dictionary.
+/// Token id => `Option<PublicKey>`, with `None` once the token has been burnt.
+const OWNERS: &str = "owners";
+/// Token id => `String`.
+const METADATA: &str = "metadata";
+/// Token id => `Option<PublicKey>` allowed to transfer or burn the token on behalf of its owner.
+const APPROVALS: &str = "approvals";
+/// Owner => `u64` number of tokens owned.
+const BALANCES: &str = "balances";
+/// `(owner, index)` => token id, for indices below the balance of the owner.
+const OWNED_TOKENS: &str = "owned_tokens";
+/// Token id => `u64` index of the token among the tokens of its owner.
+const OWNED_TOKEN_INDICES: &str = "owned_token_indices";
+
+const METHOD_INIT: &str = "init";
+const METHOD_MINT: &str = "mint";
+const METHOD_BURN: &str = "burn";
+const METHOD_TRANSFER: &str = "transfer";
+const METHOD_APPROVE: &str = "approve";
+const METHOD_OWNER_OF: &str = "owner_of";
+const METHOD_METADATA: &str = "metadata";
+const METHOD_BALANCE_OF: &str = "balance_of";
+const METHOD_TOKENS_OF: &str = "tokens_of";
+const METHOD_TOTAL_SUPPLY: &str = "total_supply";
+
+enum Arg {
+    NftName = 0,
+}
+
+#[repr(u16)]
+enum Error {
+    AlreadyInitialized = 1,
+    NotMinter = 2,
+    TokenAlreadyExists = 3,
+    TokenNotFound = 4,
+    NotAuthorized = 5,
+    UnknownMethod = 6,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+fn get_arg<T: FromBytes>(index: u32) -> T {
+    runtime::get_arg(index)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+fn ret<T: CLTyped + ToBytes>(value: T) -> ! {
+    runtime::ret(CLValue::from_t(value).unwrap_or_revert())
+}
+
+fn read<K: ToBytes, V: CLTyped + FromBytes>(namespace: &str, key: &K) -> Option<V> {
+    storage::read_namespaced(storage::namespace(namespace), key).unwrap_or_revert()
+}
+
+fn write<K: ToBytes, V: CLTyped + ToBytes>(namespace: &str, key: K, value: V) {
+    storage::write_namespaced(storage::namespace(namespace), key, value)
+}
+
+fn minter() -> PublicKey {
+    storage::read_local(&MINTER_KEY)
+        .unwrap_or_revert()
+        .unwrap_or_revert()
+}
+
+fn total_supply() -> u64 {
+    storage::read_local(&TOTAL_SUPPLY_KEY)
+        .unwrap_or_revert()
+        .unwrap_or_default()
+}
+
+fn owner_of(token_id: &str) -> Option<PublicKey> {
+    read::<_, Option<PublicKey>>(OWNERS, &token_id).flatten()
+}
+
+fn balance_of(owner: &PublicKey) -> u64 {
+    read(BALANCES, owner).unwrap_or_default()
+}
+
+fn tokens_of(owner: &PublicKey) -> Vec<String> {
+    (0..balance_of(owner))
+        .map(|index| read(OWNED_TOKENS, &(*owner, index)).unwrap_or_revert())
+        .collect()
+}
+
+fn add_owned_token(owner: &PublicKey, token_id: &str) {
+    let index = balance_of(owner);
+    write(OWNED_TOKENS, (*owner, index), token_id);
+    write(OWNED_TOKEN_INDICES, token_id, index);
+    write(BALANCES, *owner, index + 1);
+}
+
+fn remove_owned_token(owner: &PublicKey, token_id: &str) {
+    // Moves the last token of the owner into the slot of the removed one, so that the tokens of
+    // every owner stay at consecutive indices.
+    let last_index = balance_of(owner) - 1;
+    let index: u64 = read(OWNED_TOKEN_INDICES, &token_id).unwrap_or_revert();
+    if index != last_index {
+        let last_token_id: String = read(OWNED_TOKENS, &(*owner, last_index)).unwrap_or_revert();
+        write(OWNED_TOKENS, (*owner, index), last_token_id.clone());
+        write(OWNED_TOKEN_INDICES, last_token_id, index);
+    }
+    write(BALANCES, *owner, last_index);
+}
+
+/// Returns the owner of the token, if `caller` is either the owner or approved by them.
+fn authorized_owner(caller: &PublicKey, token_id: &str) -> Result<PublicKey, Error> {
+    let owner = owner_of(token_id).ok_or(Error::TokenNotFound)?;
+    let approved = read::<_, Option<PublicKey>>(APPROVALS, &token_id).flatten();
+    if *caller == owner || approved == Some(*caller) {
+        Ok(owner)
+    } else {
+        Err(Error::NotAuthorized)
+    }
+}
+
+fn init(caller: PublicKey) -> Result<(), Error> {
+    let minter: Option<PublicKey> = storage::read_local(&MINTER_KEY).unwrap_or_revert();
+    if minter.is_some() {
+        return Err(Error::AlreadyInitialized);
+    }
+    storage::write_local(MINTER_KEY, caller);
+    storage::write_local(TOTAL_SUPPLY_KEY, 0u64);
+    Ok(())
+}
+
+fn mint(caller: PublicKey, to: PublicKey, token_id: String, metadata: String) -> Result<(), Error> {
+    if caller != minter() {
+        return Err(Error::NotMinter);
+    }
+    // Ids of burnt tokens are never reused.
+    if read::<_, Option<PublicKey>>(OWNERS, &token_id).is_some() {
+        return Err(Error::TokenAlreadyExists);
+    }
+    write(OWNERS, token_id.as_str(), Some(to));
+    write(METADATA, token_id.as_str(), metadata);
+    add_owned_token(&to, &token_id);
+    storage::write_local(TOTAL_SUPPLY_KEY, total_supply() + 1);
+    Ok(())
+}
+
+fn burn(caller: PublicKey, token_id: String) -> Result<(), Error> {
+    let owner = authorized_owner(&caller, &token_id)?;
+    remove_owned_token(&owner, &token_id);
+    write(OWNERS, token_id.as_str(), None::<PublicKey>);
+    write(APPROVALS, token_id, None::<PublicKey>);
+    storage::write_local(TOTAL_SUPPLY_KEY, total_supply() - 1);
+    Ok(())
+}
+
+fn transfer(caller: PublicKey, to: PublicKey, token_id: String) -> Result<(), Error> {
+    let owner = authorized_owner(&caller, &token_id)?;
+    remove_owned_token(&owner, &token_id);
+    add_owned_token(&to, &token_id);
+    write(OWNERS, token_id.as_str(), Some(to));
+    write(APPROVALS, token_id, None::<PublicKey>);
+    Ok(())
+}
+
+fn approve(caller: PublicKey, spender: Option<PublicKey>, token_id: String) -> Result<(), Error> {
+    let owner = owner_of(&token_id).ok_or(Error::TokenNotFound)?;
+    if caller != owner {
+        return Err(Error::NotAuthorized);
+    }
+    write(APPROVALS, token_id, spender);
+    Ok(())
+}
+
+#[no_mangle]
+pub extern "C" fn nft_ext() {
+    let caller = runtime::get_caller();
+    let method: String = get_arg(0);
+
+    let result = match method.as_str() {
+        METHOD_INIT => init(caller),
+        METHOD_MINT => mint(caller, get_arg(1), get_arg(2), get_arg(3)),
+        METHOD_BURN => burn(caller, get_arg(1)),
+        METHOD_TRANSFER => transfer(caller, get_arg(1), get_arg(2)),
+        METHOD_APPROVE => approve(caller, get_arg(1), get_arg(2)),
+        METHOD_OWNER_OF => ret(owner_of(&get_arg::<String>(1))),
+        METHOD_METADATA => {
+            let token_id: String = get_arg(1);
+            let metadata: Option<String> =
+                owner_of(&token_id).and_then(|_| read(METADATA, &token_id));
+            ret(metadata)
+        }
+        METHOD_BALANCE_OF => ret(balance_of(&get_arg(1))),
+        METHOD_TOKENS_OF => ret(tokens_of(&get_arg(1))),
+        METHOD_TOTAL_SUPPLY => ret(total_supply()),
+        _ => Err(Error::UnknownMethod),
+    };
+    result.unwrap_or_revert()
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let nft_name: String = get_arg(Arg::NftName as u32);
+
+    let nft_ref = storage::store_function_at_hash(NFT_EXT, Default::default());
+    runtime::call_contract::<_, ()>(nft_ref.clone(), (METHOD_INIT,));
+    runtime::put_key(&nft_name, nft_ref.into());
+}
@@ -0,0 +1,59 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use token::{methods, Error, TokenRef};
+use types::{bytesrepr::FromBytes, ApiError, Key};
+
+const BALANCE_KEY: &str = "balance";
+const TOTAL_SUPPLY_KEY: &str = "total_supply";
+const ALLOWANCE_KEY: &str = "allowance";
+
+enum Arg {
+    Token = 0,
+    MethodName = 1,
+}
+
+fn get_arg<T: FromBytes>(index: u32) -> T {
+    runtime::get_arg(index)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+/// Calls the token contract given as the first argument, passing it the remaining arguments.
+///
+/// The results of queries are stored under named keys of the calling account.
+#[no_mangle]
+pub extern "C" fn call() {
+    let token_key: Key = get_arg(Arg::Token as u32);
+    let contract_ref = token_key
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    let token = TokenRef::new(contract_ref);
+    let method: String = get_arg(Arg::MethodName as u32);
+
+    match method.as_str() {
+        methods::BALANCE_OF => {
+            let balance = token.balance_of(get_arg(2));
+            runtime::put_key(BALANCE_KEY, storage::new_turef(balance).into());
+        }
+        methods::TOTAL_SUPPLY => {
+            let total_supply = token.total_supply();
+            runtime::put_key(TOTAL_SUPPLY_KEY, storage::new_turef(total_supply).into());
+        }
+        methods::ALLOWANCE => {
+            let allowance = token.allowance(get_arg(2), get_arg(3));
+            runtime::put_key(ALLOWANCE_KEY, storage::new_turef(allowance).into());
+        }
+        methods::TRANSFER => token.transfer(get_arg(2), get_arg(3)),
+        methods::APPROVE => token.approve(get_arg(2), get_arg(3)),
+        methods::TRANSFER_FROM => token.transfer_from(get_arg(2), get_arg(3), get_arg(4)),
+        _ => runtime::revert(Error::UnknownMethod),
+    }
+}
@@ -0,0 +1,77 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use token::{keys, methods, Error, LocalStorage, Token};
+use types::{
+    account::PublicKey,
+    bytesrepr::{FromBytes, ToBytes},
+    ApiError, CLTyped, CLValue, U512,
+};
+
+const TOKEN_EXT: &str = "token_ext";
+
+enum Arg {
+    TokenName = 0,
+    InitialSupply = 1,
+}
+
+fn get_arg<T: FromBytes>(index: u32) -> T {
+    runtime::get_arg(index)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+fn ret<T: CLTyped + ToBytes>(value: T) -> ! {
+    runtime::ret(CLValue::from_t(value).unwrap_or_revert())
+}
+
+fn init(token: &mut LocalStorage, caller: &PublicKey, initial_supply: U512) -> Result<(), Error> {
+    let initialized: Option<bool> =
+        storage::read_local(&keys::initialized_key()).unwrap_or_revert();
+    if initialized.is_some() {
+        return Err(Error::AlreadyInitialized);
+    }
+    storage::write_local(keys::initialized_key(), true);
+    token.mint(caller, initial_supply)
+}
+
+#[no_mangle]
+pub extern "C" fn token_ext() {
+    let mut token = LocalStorage;
+    let caller = runtime::get_caller();
+    let method: String = get_arg(0);
+
+    let result = match method.as_str() {
+        methods::INIT => init(&mut token, &caller, get_arg(1)),
+        methods::BALANCE_OF => ret(token.balance_of(&get_arg(1))),
+        methods::TOTAL_SUPPLY => ret(token.total_supply()),
+        methods::ALLOWANCE => ret(token.allowance(&get_arg(1), &get_arg(2))),
+        methods::TRANSFER => token.transfer(&caller, &get_arg(1), get_arg(2)),
+        methods::APPROVE => {
+            token.approve(&caller, &get_arg(1), get_arg(2));
+            Ok(())
+        }
+        methods::TRANSFER_FROM => {
+            token.transfer_from(&caller, &get_arg(1), &get_arg(2), get_arg(3))
+        }
+        _ => Err(Error::UnknownMethod),
+    };
+    result.unwrap_or_revert()
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let token_name: String = get_arg(Arg::TokenName as u32);
+    let initial_supply: U512 = get_arg(Arg::InitialSupply as u32);
+
+    let token_ref = storage::store_function_at_hash(TOKEN_EXT, Default::default());
+    runtime::call_contract::<_, ()>(token_ref.clone(), (methods::INIT, initial_supply));
+    runtime::put_key(&token_name, token_ref.into());
+}
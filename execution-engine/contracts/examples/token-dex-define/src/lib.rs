@@ -0,0 +1,126 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{account, runtime, storage, system, AsTURef, TURef},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use token::TokenRef;
+use types::{
+    account::{PublicKey, PurseId},
+    bytesrepr::{FromBytes, ToBytes},
+    ApiError, CLTyped, Key, U512,
+};
+
+const DEX_EXT: &str = "dex_ext";
+const METHOD_SWAP: &str = "swap";
+const TOKEN_KEY: &str = "token";
+const RESERVE_KEY: &str = "reserve";
+const OWNER_KEY: &str = "owner";
+const MOTES_PER_TOKEN_KEY: &str = "motes_per_token";
+
+enum Arg {
+    DexName = 0,
+    Token = 1,
+    MotesPerToken = 2,
+    ReserveAmount = 3,
+}
+
+#[repr(u16)]
+enum Error {
+    // Follows the codes of `token::Error`, which are passed through from the token contract.
+    UnknownMethod = 100,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+fn get_arg<T: FromBytes>(index: u32) -> T {
+    runtime::get_arg(index)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+fn get_named_key(name: &str) -> Key {
+    runtime::get_key(name).unwrap_or_revert_with(ApiError::GetKey)
+}
+
+fn read_named_value<T: CLTyped + FromBytes + ToBytes>(name: &str) -> T {
+    let turef: TURef<T> = get_named_key(name)
+        .as_turef()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    storage::read(turef)
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_revert_with(ApiError::ValueNotFound)
+}
+
+/// Sells motes from the reserve purse for tokens, at the fixed price set when the exchange was
+/// defined.
+///
+/// Takes the purse to receive the motes and the amount of motes to buy.  The tokens, rounded up to
+/// cover the whole amount, are transferred from the caller to the owner of the exchange.
+#[no_mangle]
+pub extern "C" fn dex_ext() {
+    let method: String = get_arg(0);
+    if method != METHOD_SWAP {
+        runtime::revert(Error::UnknownMethod);
+    }
+    let target: PurseId = get_arg(1);
+    let motes: U512 = get_arg(2);
+
+    let motes_per_token: U512 = read_named_value(MOTES_PER_TOKEN_KEY);
+    let mut tokens = motes / motes_per_token;
+    if !(motes % motes_per_token).is_zero() {
+        tokens += U512::one();
+    }
+
+    let token_ref = get_named_key(TOKEN_KEY)
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    let owner: PublicKey = read_named_value(OWNER_KEY);
+    // The token contract sees the account executing the deploy as its caller, so this spends the
+    // tokens of the buyer.
+    TokenRef::new(token_ref).transfer(owner, tokens);
+
+    let reserve = get_named_key(RESERVE_KEY)
+        .into_uref()
+        .map(PurseId::new)
+        .unwrap_or_revert_with(ApiError::InvalidPurse);
+    system::transfer_from_purse_to_purse(reserve, target, motes).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let dex_name: String = get_arg(Arg::DexName as u32);
+    let token: Key = get_arg(Arg::Token as u32);
+    let motes_per_token: U512 = get_arg(Arg::MotesPerToken as u32);
+    let reserve_amount: U512 = get_arg(Arg::ReserveAmount as u32);
+    if motes_per_token.is_zero() {
+        runtime::revert(ApiError::InvalidArgument);
+    }
+
+    let reserve = system::create_purse();
+    system::transfer_from_purse_to_purse(account::get_main_purse(), reserve, reserve_amount)
+        .unwrap_or_revert();
+
+    let mut named_keys: BTreeMap<String, Key> = BTreeMap::new();
+    named_keys.insert(TOKEN_KEY.into(), token);
+    named_keys.insert(RESERVE_KEY.into(), reserve.value().into());
+    named_keys.insert(
+        OWNER_KEY.into(),
+        storage::new_turef(runtime::get_caller()).into(),
+    );
+    named_keys.insert(
+        MOTES_PER_TOKEN_KEY.into(),
+        storage::new_turef(motes_per_token).into(),
+    );
+
+    let dex_ref = storage::store_function_at_hash(DEX_EXT, named_keys);
+    runtime::put_key(&dex_name, dex_ref.into());
+}
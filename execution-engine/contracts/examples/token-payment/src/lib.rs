@@ -0,0 +1,47 @@
+#![no_std]
+
+use contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use standard_payment::{
+    AmountProvider, PaymentPurseProvider, SourcePurseProvider, StandardPayment, TransferProvider,
+};
+use types::{account::PurseId, ApiError, Key, U512};
+
+const METHOD_SWAP: &str = "swap";
+
+enum Arg {
+    // The amount of motes to pay is the first argument, as for the standard payment.
+    Dex = 1,
+}
+
+/// Pays for the deploy in tokens, by buying the motes from the exchange contract given as the
+/// second argument.
+struct TokenPayment;
+
+impl AmountProvider for TokenPayment {}
+
+impl SourcePurseProvider for TokenPayment {
+    fn source_purse(&mut self, amount: U512) -> Result<PurseId, ApiError> {
+        let dex: Key = runtime::get_arg(Arg::Dex as u32)
+            .unwrap_or_revert_with(ApiError::MissingArgument)
+            .unwrap_or_revert_with(ApiError::InvalidArgument);
+        let dex_ref = dex
+            .to_contract_ref()
+            .ok_or(ApiError::UnexpectedKeyVariant)?;
+
+        let purse = system::create_purse();
+        runtime::call_contract::<_, ()>(dex_ref, (METHOD_SWAP, purse, amount));
+        Ok(purse)
+    }
+}
+
+impl PaymentPurseProvider for TokenPayment {}
+
+impl TransferProvider for TokenPayment {}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    TokenPayment.pay().unwrap_or_revert();
+}
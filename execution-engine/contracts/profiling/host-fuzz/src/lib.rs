@@ -0,0 +1,107 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::{
+    contract_api::{runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, TURef, U512};
+
+const CHECKSUM_KEY: &str = "host_fuzz_checksum";
+
+enum Arg {
+    Program = 0,
+}
+
+#[repr(u16)]
+enum Error {
+    InvalidOpcode = 0,
+    InvalidSlot = 1,
+    MissingValue = 2,
+}
+
+/// The host calls the fuzzer can request, each encoded as an `(opcode, operand)` byte pair.
+#[repr(u8)]
+enum Op {
+    NewURef = 0,
+    Write = 1,
+    Add = 2,
+    Read = 3,
+    WriteLocal = 4,
+    AddLocal = 5,
+    ReadLocal = 6,
+    Call = 7,
+}
+
+impl Op {
+    fn from_u8(value: u8) -> Option<Self> {
+        let op = match value {
+            0 => Op::NewURef,
+            1 => Op::Write,
+            2 => Op::Add,
+            3 => Op::Read,
+            4 => Op::WriteLocal,
+            5 => Op::AddLocal,
+            6 => Op::ReadLocal,
+            7 => Op::Call,
+            _ => return None,
+        };
+        Some(op)
+    }
+}
+
+fn slot(slots: &[TURef<U512>], operand: u8) -> TURef<U512> {
+    slots
+        .get(usize::from(operand))
+        .copied()
+        .unwrap_or_revert_with(ApiError::User(Error::InvalidSlot as u16))
+}
+
+/// Interprets the program passed as the first argument, and stores a checksum of every value read
+/// back under a named key so that the results of the reads end up in the effects of the deploy.
+#[no_mangle]
+pub extern "C" fn call() {
+    let program: Vec<u8> = runtime::get_arg(Arg::Program as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let mut slots: Vec<TURef<U512>> = Vec::new();
+    let mut checksum = U512::zero();
+
+    for instruction in program.chunks(2) {
+        let (opcode, operand) = match instruction {
+            [opcode, operand] => (*opcode, *operand),
+            _ => runtime::revert(ApiError::InvalidArgument),
+        };
+        let value = U512::from(operand);
+        match Op::from_u8(opcode).unwrap_or_revert_with(ApiError::User(Error::InvalidOpcode as u16))
+        {
+            Op::NewURef => slots.push(storage::new_turef(value)),
+            Op::Write => storage::write(slot(&slots, operand), value),
+            Op::Add => storage::add(slot(&slots, operand), value),
+            Op::Read => {
+                let read: U512 = storage::read(slot(&slots, operand))
+                    .unwrap_or_revert()
+                    .unwrap_or_revert_with(ApiError::User(Error::MissingValue as u16));
+                checksum = checksum.overflowing_add(read).0;
+            }
+            Op::WriteLocal => storage::write_local(operand, value),
+            Op::AddLocal => storage::add_local(operand, value),
+            Op::ReadLocal => {
+                let read: Option<U512> = storage::read_local(&operand).unwrap_or_revert();
+                checksum = checksum.overflowing_add(read.unwrap_or_default()).0;
+            }
+            Op::Call => {
+                let purse_id = system::create_purse();
+                let balance = system::get_balance(purse_id)
+                    .unwrap_or_revert_with(ApiError::User(Error::MissingValue as u16));
+                checksum = checksum.overflowing_add(balance).0;
+            }
+        }
+    }
+
+    runtime::put_key(CHECKSUM_KEY, storage::new_turef(checksum).into());
+}
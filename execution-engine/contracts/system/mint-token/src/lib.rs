@@ -45,13 +45,13 @@ pub fn delegate() {
             let ret = CLValue::from_t(uref).unwrap_or_revert();
             runtime::ret(ret)
         }
-        // Type: `fn balance(purse: URef) -> Option<U512>`
+        // Type: `fn balance(purse: URef) -> Result<Option<U512>, Error>`
         METHOD_BALANCE => {
             let uref: URef = runtime::get_arg(1)
                 .unwrap_or_revert_with(ApiError::MissingArgument)
                 .unwrap_or_revert_with(ApiError::InvalidArgument);
-            let balance: Option<U512> = mint_contract.balance(uref).unwrap_or_revert();
-            let ret = CLValue::from_t(balance).unwrap_or_revert();
+            let result: Result<Option<U512>, Error> = mint_contract.balance(uref);
+            let ret = CLValue::from_t(result).unwrap_or_revert();
             runtime::ret(ret)
         }
         // Type: `fn transfer(source: URef, target: URef, amount: U512) -> Result<(), Error>`
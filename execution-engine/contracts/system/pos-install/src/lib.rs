@@ -19,12 +19,16 @@ const PLACEHOLDER_KEY: Key = Key::Hash([0u8; 32]);
 const POS_BONDING_PURSE: &str = "pos_bonding_purse";
 const POS_PAYMENT_PURSE: &str = "pos_payment_purse";
 const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
+const POS_UNBONDING_DELAY: &str = "pos_unbonding_delay";
+const POS_SLASH_PERCENTAGE: &str = "pos_slash_percentage";
 const POS_FUNCTION_NAME: &str = "pos_ext";
 
 #[repr(u32)]
 enum Args {
     MintURef = 0,
     GenesisValidators = 1,
+    UnbondingDelay = 2,
+    SlashPercentage = 3,
 }
 
 #[no_mangle]
@@ -63,6 +67,27 @@ pub extern "C" fn call() {
         .map(|key| (key, PLACEHOLDER_KEY))
         .collect();
 
+    // The time from an unbonding request until the stakes are paid out.
+    let unbonding_delay: u64 = runtime::get_arg(Args::UnbondingDelay as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    named_keys.insert(
+        String::from(POS_UNBONDING_DELAY),
+        storage::new_turef(unbonding_delay).into(),
+    );
+
+    // The percentage of the stakes taken from a validator when it is slashed.
+    let slash_percentage: u8 = runtime::get_arg(Args::SlashPercentage as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    if slash_percentage > 100 {
+        runtime::revert(ApiError::InvalidArgument);
+    }
+    named_keys.insert(
+        String::from(POS_SLASH_PERCENTAGE),
+        storage::new_turef(slash_percentage).into(),
+    );
+
     let total_bonds: U512 = genesis_validators.values().fold(U512::zero(), |x, y| x + y);
 
     let bonding_purse = mint_purse(&mint, total_bonds);
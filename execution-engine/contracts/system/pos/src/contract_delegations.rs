@@ -0,0 +1,115 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+};
+use core::fmt::Write;
+
+use base16;
+
+use contract::contract_api::{runtime, storage, AsTURef, TURef};
+use proof_of_stake::{Delegations, DelegationsProvider};
+use types::{
+    account::PublicKey,
+    system_contract_errors::pos::{Error, Result},
+    Key, U512,
+};
+
+/// Prefix of the uref names encoding the delegations, in the format
+/// "d_<hex-formatted-delegator>_<hex-formatted-validator>_<amount>".
+const DELEGATION_PREFIX: &str = "d_";
+const UNDISTRIBUTED_FEES_KEY: &str = "pos_undistributed_fees";
+
+/// A `DelegationsProvider` that reads and writes the delegator ledger to/from
+/// the contract's known urefs.
+pub struct ContractDelegations;
+
+impl DelegationsProvider for ContractDelegations {
+    /// Reads the current delegations from the contract's known urefs.
+    fn read() -> Result<Delegations> {
+        let mut delegations = BTreeMap::new();
+        for (name, _) in runtime::list_named_keys() {
+            let mut split_name = name.split('_');
+            if Some("d") != split_name.next() {
+                continue;
+            }
+            let delegator = split_name
+                .next()
+                .ok_or(Error::DelegationsKeyDeserializationFailed)
+                .and_then(decode_public_key)?;
+            let validator = split_name
+                .next()
+                .ok_or(Error::DelegationsKeyDeserializationFailed)
+                .and_then(decode_public_key)?;
+            let amount = split_name
+                .next()
+                .and_then(|b| U512::from_dec_str(b).ok())
+                .ok_or(Error::DelegationsDeserializationFailed)?;
+            delegations.insert((delegator, validator), amount);
+        }
+        Ok(Delegations(delegations))
+    }
+
+    /// Writes the current delegations to the contract's known urefs.
+    fn write(delegations: &Delegations) {
+        // Encode the delegations as a set of uref names.
+        let mut new_urefs: BTreeSet<String> = delegations
+            .0
+            .iter()
+            .map(|((delegator, validator), amount)| {
+                let mut uref = String::from(DELEGATION_PREFIX);
+                write_public_key(&mut uref, delegator);
+                uref.push('_');
+                write_public_key(&mut uref, validator);
+                uref.write_fmt(format_args!("_{}", amount))
+                    .expect("Writing to a string cannot fail");
+                uref
+            })
+            .collect();
+        // Remove and add urefs to update the contract's known urefs accordingly.
+        for (name, _) in runtime::list_named_keys() {
+            if name.starts_with(DELEGATION_PREFIX) && !new_urefs.remove(&name) {
+                runtime::remove_key(&name);
+            }
+        }
+        for name in new_urefs {
+            runtime::put_key(&name, Key::Hash([0; 32]));
+        }
+    }
+
+    fn read_undistributed_fees() -> Result<U512> {
+        let key = match runtime::get_key(UNDISTRIBUTED_FEES_KEY) {
+            Some(key) => key,
+            None => return Ok(U512::zero()),
+        };
+        let turef: TURef<U512> = key
+            .as_turef()
+            .map_err(|_| Error::UndistributedFeesKeyUnexpectedType)?;
+        storage::read(turef)
+            .map_err(|_| Error::UndistributedFeesDeserializationFailed)
+            .map(Option::unwrap_or_default)
+    }
+
+    fn write_undistributed_fees(fees: U512) {
+        match runtime::get_key(UNDISTRIBUTED_FEES_KEY).map(|key| key.as_turef()) {
+            Some(Ok(turef)) => storage::write(turef, fees),
+            _ => runtime::put_key(UNDISTRIBUTED_FEES_KEY, storage::new_turef(fees).into()),
+        }
+    }
+}
+
+fn decode_public_key(hex_key: &str) -> Result<PublicKey> {
+    if hex_key.len() != 64 {
+        return Err(Error::DelegationsKeyDeserializationFailed);
+    }
+    let mut key_bytes = [0u8; 32];
+    let _bytes_written = base16::decode_slice(hex_key, &mut key_bytes)
+        .map_err(|_| Error::DelegationsKeyDeserializationFailed)?;
+    debug_assert!(_bytes_written == key_bytes.len());
+    Ok(PublicKey::new(key_bytes))
+}
+
+fn write_public_key(output: &mut String, public_key: &PublicKey) {
+    for byte in &public_key.value()[..32] {
+        write!(output, "{:02x}", byte).expect("Writing to a string cannot fail");
+    }
+}
@@ -1,10 +1,13 @@
-use contract::contract_api::system;
+use contract::contract_api::{runtime, system};
 use proof_of_stake::MintProvider;
 use types::{
     account::{PublicKey, PurseId},
-    TransferResult, U512,
+    system_contract_errors::mint,
+    TransferResult, URef, U512,
 };
 
+const METHOD_MINT: &str = "mint";
+
 pub struct ContractMint;
 
 impl MintProvider for ContractMint {
@@ -27,4 +30,10 @@ impl MintProvider for ContractMint {
     fn get_balance(purse: PurseId) -> Option<U512> {
         system::get_balance(purse)
     }
+
+    fn mint(amount: U512) -> Result<PurseId, ()> {
+        let result: Result<URef, mint::Error> =
+            runtime::call_contract(system::get_mint(), (METHOD_MINT, amount));
+        result.map(PurseId::new).map_err(|_| ())
+    }
 }
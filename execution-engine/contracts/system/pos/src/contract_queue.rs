@@ -1,8 +1,10 @@
-use contract::contract_api::storage;
+use contract::contract_api::{runtime, storage, AsTURef, TURef};
 use proof_of_stake::{Queue, QueueProvider};
+use types::system_contract_errors::pos::{Error, Result};
 
 const BONDING_KEY: u8 = 1;
 const UNBONDING_KEY: u8 = 2;
+const UNBONDING_DELAY_KEY: &str = "pos_unbonding_delay";
 
 /// A `QueueProvider` that reads and writes the queue to/from the contract's local state.
 pub struct ContractQueue;
@@ -31,4 +33,19 @@ impl QueueProvider for ContractQueue {
     fn write_unbonding(queue: Queue) {
         storage::write_local(UNBONDING_KEY, queue);
     }
+
+    /// Reads the unbonding delay set at genesis from the contract's known urefs.  If the contract
+    /// was installed without one, unbonded funds are paid out immediately.
+    fn read_unbonding_delay() -> Result<u64> {
+        let key = match runtime::get_key(UNBONDING_DELAY_KEY) {
+            Some(key) => key,
+            None => return Ok(0),
+        };
+        let turef: TURef<u64> = key
+            .as_turef()
+            .map_err(|_| Error::UnbondingDelayKeyUnexpectedType)?;
+        storage::read(turef)
+            .map_err(|_| Error::UnbondingDelayDeserializationFailed)?
+            .ok_or(Error::UnbondingDelayNotFound)
+    }
 }
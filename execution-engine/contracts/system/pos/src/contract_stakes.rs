@@ -6,7 +6,7 @@ use core::fmt::Write;
 
 use base16;
 
-use contract::contract_api::runtime;
+use contract::contract_api::{runtime, storage, AsTURef, TURef};
 use proof_of_stake::{Stakes, StakesProvider};
 use types::{
     account::PublicKey,
@@ -14,6 +14,9 @@ use types::{
     Key, U512,
 };
 
+const SLASH_PERCENTAGE_KEY: &str = "pos_slash_percentage";
+const LAST_REWARDED_ERA_KEY: &str = "pos_last_rewarded_era";
+
 /// A `StakesProvider` that reads and writes the stakes to/from the contract's
 /// known urefs.
 pub struct ContractStakes;
@@ -78,4 +81,37 @@ impl StakesProvider for ContractStakes {
             runtime::put_key(&name, Key::Hash([0; 32]));
         }
     }
+
+    /// Reads the slash percentage set at genesis from the contract's known urefs.  If the contract
+    /// was installed without one, validators are never slashed.
+    fn read_slash_percentage() -> Result<u8> {
+        let key = match runtime::get_key(SLASH_PERCENTAGE_KEY) {
+            Some(key) => key,
+            None => return Ok(0),
+        };
+        let turef: TURef<u8> = key
+            .as_turef()
+            .map_err(|_| Error::SlashPercentageKeyUnexpectedType)?;
+        storage::read(turef)
+            .map_err(|_| Error::SlashPercentageDeserializationFailed)?
+            .ok_or(Error::SlashPercentageNotFound)
+    }
+
+    fn read_last_rewarded_era() -> Result<Option<u64>> {
+        let key = match runtime::get_key(LAST_REWARDED_ERA_KEY) {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+        let turef: TURef<u64> = key
+            .as_turef()
+            .map_err(|_| Error::LastRewardedEraKeyUnexpectedType)?;
+        storage::read(turef).map_err(|_| Error::LastRewardedEraDeserializationFailed)
+    }
+
+    fn write_last_rewarded_era(era_id: u64) {
+        match runtime::get_key(LAST_REWARDED_ERA_KEY).map(|key| key.as_turef()) {
+            Some(Ok(turef)) => storage::write(turef, era_id),
+            _ => runtime::put_key(LAST_REWARDED_ERA_KEY, storage::new_turef(era_id).into()),
+        }
+    }
 }
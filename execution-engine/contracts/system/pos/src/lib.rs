@@ -2,12 +2,13 @@
 
 extern crate alloc;
 
+mod contract_delegations;
 mod contract_mint;
 mod contract_queue;
 mod contract_runtime;
 mod contract_stakes;
 
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 
 use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
 use proof_of_stake::ProofOfStake;
@@ -17,21 +18,30 @@ use types::{
 };
 
 use crate::{
-    contract_mint::ContractMint, contract_queue::ContractQueue, contract_runtime::ContractRuntime,
+    contract_delegations::ContractDelegations, contract_mint::ContractMint,
+    contract_queue::ContractQueue, contract_runtime::ContractRuntime,
     contract_stakes::ContractStakes,
 };
 
 const METHOD_BOND: &str = "bond";
 const METHOD_UNBOND: &str = "unbond";
+const METHOD_DELEGATE: &str = "delegate";
+const METHOD_UNDELEGATE: &str = "undelegate";
+const METHOD_REDELEGATE: &str = "redelegate";
 const METHOD_STEP: &str = "step";
+const METHOD_PROCESS_UNBOND_REQUESTS: &str = "process_unbond_requests";
+const METHOD_SLASH: &str = "slash";
+const METHOD_DISTRIBUTE_REWARDS: &str = "distribute_rewards";
 const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
 const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
 const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
 const METHOD_FINALIZE_PAYMENT: &str = "finalize_payment";
 
+const FULL_REFUND_PERCENTAGE: u8 = 100;
+
 pub struct ProofOfStakeContract;
 
-impl ProofOfStake<ContractMint, ContractQueue, ContractRuntime, ContractStakes>
+impl ProofOfStake<ContractMint, ContractQueue, ContractRuntime, ContractStakes, ContractDelegations>
     for ProofOfStakeContract
 {
 }
@@ -67,11 +77,84 @@ pub fn delegate() {
                 .unbond(validator, maybe_amount)
                 .unwrap_or_revert();
         }
+        // Type of this method: `fn delegate(validator: PublicKey, amount: U512, purse: URef)`
+        METHOD_DELEGATE => {
+            let delegator = runtime::get_caller();
+            let validator: PublicKey = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let amount: U512 = runtime::get_arg(2)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let source_uref: URef = runtime::get_arg(3)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            pos_contract
+                .delegate(delegator, validator, amount, source_uref)
+                .unwrap_or_revert();
+        }
+        // Type of this method: `fn undelegate(validator: PublicKey, amount: Option<U512>)`
+        METHOD_UNDELEGATE => {
+            let delegator = runtime::get_caller();
+            let validator: PublicKey = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let maybe_amount = runtime::get_arg(2)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            pos_contract
+                .undelegate(delegator, validator, maybe_amount)
+                .unwrap_or_revert();
+        }
+        // Type of this method:
+        // `fn redelegate(from_validator: PublicKey, to_validator: PublicKey, amount: Option<U512>)`
+        METHOD_REDELEGATE => {
+            let delegator = runtime::get_caller();
+            let from_validator: PublicKey = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let to_validator: PublicKey = runtime::get_arg(2)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let maybe_amount = runtime::get_arg(3)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            pos_contract
+                .redelegate(delegator, from_validator, to_validator, maybe_amount)
+                .unwrap_or_revert();
+        }
         // Type of this method: `fn step()`
         METHOD_STEP => {
             // This is called by the system in every block.
             pos_contract.step().unwrap_or_revert();
         }
+        // Type of this method: `fn process_unbond_requests()`
+        METHOD_PROCESS_UNBOND_REQUESTS => {
+            // This is called by the system when finalizing each deploy.
+            pos_contract.process_unbond_requests().unwrap_or_revert();
+        }
+        // Type of this method: `fn slash(validators: Vec<PublicKey>)`
+        METHOD_SLASH => {
+            // This is called by the system with the validators found equivocating.
+            let validators: Vec<PublicKey> = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            pos_contract.slash(validators).unwrap_or_revert();
+        }
+        // Type of this method: `fn distribute_rewards(era_id: u64, seigniorage_rate: u64)`
+        METHOD_DISTRIBUTE_REWARDS => {
+            // This is called by the system at the end of each era, with the seigniorage rate of
+            // the current protocol version.
+            let era_id: u64 = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let seigniorage_rate: u64 = runtime::get_arg(2)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            pos_contract
+                .distribute_rewards(era_id, seigniorage_rate)
+                .unwrap_or_revert();
+        }
         // Type of this method: `fn get_payment_purse() -> PurseId`
         METHOD_GET_PAYMENT_PURSE => {
             let rights_controlled_purse = pos_contract.get_payment_purse().unwrap_or_revert();
@@ -94,7 +177,8 @@ pub fn delegate() {
             let return_value = CLValue::from_t(maybe_purse_uref).unwrap_or_revert();
             runtime::ret(return_value);
         }
-        // Type of this method: `fn finalize_payment()`
+        // Type of this method: `fn finalize_payment(amount_spent: U512, account: PublicKey,
+        // refund_percentage: u8)`
         METHOD_FINALIZE_PAYMENT => {
             let amount_spent: U512 = runtime::get_arg(1)
                 .unwrap_or_revert_with(ApiError::MissingArgument)
@@ -102,8 +186,12 @@ pub fn delegate() {
             let account: PublicKey = runtime::get_arg(2)
                 .unwrap_or_revert_with(ApiError::MissingArgument)
                 .unwrap_or_revert_with(ApiError::InvalidArgument);
+            // Engines predating the refund percentage don't pass it, and refund in full
+            let refund_percentage: u8 = runtime::get_arg(3)
+                .unwrap_or(Ok(FULL_REFUND_PERCENTAGE))
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
             pos_contract
-                .finalize_payment(amount_spent, account)
+                .finalize_payment(amount_spent, account, refund_percentage)
                 .unwrap_or_revert();
         }
         _ => {}
@@ -5,7 +5,7 @@ extern crate alloc;
 use alloc::string::String;
 
 use contract::contract_api::runtime;
-use types::{Key, U512};
+use types::{system_contract_errors::mint::Error, Key, U512};
 
 #[no_mangle]
 pub extern "C" fn call() {
@@ -28,9 +28,11 @@ pub extern "C" fn call() {
 
     assert!(&result == "Success!");
 
-    let new_amount1: Option<U512> = runtime::call_contract(mint.clone(), ("balance", purse1));
-    let new_amount2: Option<U512> = runtime::call_contract(mint, ("balance", purse2));
+    let new_amount1: Result<Option<U512>, Error> =
+        runtime::call_contract(mint.clone(), ("balance", purse1));
+    let new_amount2: Result<Option<U512>, Error> =
+        runtime::call_contract(mint, ("balance", purse2));
 
-    assert!(new_amount1.unwrap() == U512::from(30));
-    assert!(new_amount2.unwrap() == U512::from(370));
+    assert!(new_amount1 == Ok(Some(U512::from(30))));
+    assert!(new_amount2 == Ok(Some(U512::from(370))));
 }
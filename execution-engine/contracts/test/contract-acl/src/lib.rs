@@ -0,0 +1,77 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::PublicKey, ApiError, Key};
+
+const PROTECTED_CONTRACT_NAME: &str = "acl_protected";
+const PROXY_CONTRACT_NAME: &str = "acl_proxy";
+const METHOD_STORE: &str = "store";
+const METHOD_UPDATE: &str = "update";
+const METHOD_CALL: &str = "call";
+
+#[no_mangle]
+pub extern "C" fn acl_protected_ext() {}
+
+/// Calls the protected contract, so that deploys of accounts which don't hold its `URef` can call
+/// it too.
+#[no_mangle]
+pub extern "C" fn proxy_ext() {
+    let contract_ref = runtime::get_key(PROTECTED_CONTRACT_NAME)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    runtime::call_contract::<_, ()>(contract_ref, ());
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    match method.as_str() {
+        METHOD_STORE => {
+            let authorized_callers: Vec<PublicKey> = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let protected_contract = storage::store_function_with_acl(
+                "acl_protected_ext",
+                BTreeMap::new(),
+                authorized_callers,
+            );
+            let protected_key: Key = protected_contract.into();
+            runtime::put_key(PROTECTED_CONTRACT_NAME, protected_key);
+
+            let mut proxy_named_keys = BTreeMap::new();
+            proxy_named_keys.insert(String::from(PROTECTED_CONTRACT_NAME), protected_key);
+            let proxy_contract = storage::store_function_at_hash("proxy_ext", proxy_named_keys);
+            runtime::put_key(PROXY_CONTRACT_NAME, proxy_contract.into());
+        }
+        METHOD_UPDATE => {
+            let authorized_callers: Option<Vec<PublicKey>> = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let uref = runtime::get_key(PROTECTED_CONTRACT_NAME)
+                .unwrap_or_revert_with(ApiError::GetKey)
+                .into_uref()
+                .unwrap_or_revert();
+            runtime::update_contract_acl(uref, authorized_callers).unwrap_or_revert();
+        }
+        METHOD_CALL => {
+            let proxy_key: Key = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let contract_ref = proxy_key
+                .to_contract_ref()
+                .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+            runtime::call_contract::<_, ()>(contract_ref, ());
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
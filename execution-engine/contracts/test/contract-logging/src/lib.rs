@@ -0,0 +1,21 @@
+#![no_std]
+
+use contract::{contract_api::runtime, ext_ffi, fmt_buf};
+
+const MESSAGE_BUFFER_SIZE: usize = 16;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let block_time: u64 = runtime::get_blocktime().into();
+    // Truncated, as the message doesn't fit into the buffer
+    let mut buf = [0u8; MESSAGE_BUFFER_SIZE];
+    let message = fmt_buf::format(&mut buf, format_args!("block time is {}", block_time));
+
+    // `runtime::log` is compiled out of release builds, so the host function is called directly
+    unsafe {
+        ext_ffi::log(message.as_ptr(), message.len());
+        // Out of bounds of the Wasm memory, which is ignored rather than trapping
+        ext_ffi::log(usize::max_value() as *const u8, MESSAGE_BUFFER_SIZE);
+    }
+    runtime::log(message);
+}
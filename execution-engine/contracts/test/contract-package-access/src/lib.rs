@@ -0,0 +1,47 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{CLType, CLValue, EntryPoint, EntryPointAccess, EntryPoints, Key, URef};
+
+const PACKAGE_NAME: &str = "restricted_package";
+const ACCESS_KEY_NAME: &str = "restricted_package_access";
+const GROUP_UREF_NAME: &str = "restricted_package_admin";
+const GROUP_ONLY_ENTRY_POINT: &str = "group_only";
+const ACCOUNT_ONLY_ENTRY_POINT: &str = "account_only";
+
+#[no_mangle]
+pub extern "C" fn group_only() {
+    let result: u32 = 1;
+    runtime::ret(CLValue::from_t(result).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn account_only() {
+    let result: u32 = 2;
+    runtime::ret(CLValue::from_t(result).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let group_uref: URef = storage::new_turef(()).into();
+
+    let entry_points: EntryPoints = vec![
+        EntryPoint::new(GROUP_ONLY_ENTRY_POINT, vec![], CLType::U32)
+            .with_access(EntryPointAccess::Groups(vec![group_uref])),
+        EntryPoint::new(ACCOUNT_ONLY_ENTRY_POINT, vec![], CLType::U32)
+            .with_access(EntryPointAccess::Accounts(vec![runtime::get_caller()])),
+    ]
+    .into();
+
+    let (package_hash, access_key) = storage::new_contract(entry_points, BTreeMap::new());
+    runtime::put_key(PACKAGE_NAME, Key::Hash(package_hash));
+    runtime::put_key(ACCESS_KEY_NAME, access_key.into());
+    runtime::put_key(GROUP_UREF_NAME, group_uref.into());
+}
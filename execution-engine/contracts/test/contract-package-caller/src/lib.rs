@@ -0,0 +1,36 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, ContractVersion, Key};
+
+const RESULT_NAME: &str = "result";
+
+enum Arg {
+    PackageHash = 0,
+    Version = 1,
+    EntryPoint = 2,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let package_hash: [u8; 32] = runtime::get_arg(Arg::PackageHash as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let version: Option<ContractVersion> = runtime::get_arg(Arg::Version as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let entry_point: String = runtime::get_arg(Arg::EntryPoint as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let result: u32 = runtime::call_versioned_contract(package_hash, version, &entry_point, ());
+    let result_uref = storage::new_turef(result);
+    runtime::put_key(RESULT_NAME, Key::from(result_uref));
+}
@@ -0,0 +1,114 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLType, CLValue, EntryPoint, EntryPoints, Key, Seed, URef};
+
+const PACKAGE_NAME: &str = "contract_package";
+const ACCESS_KEY_NAME: &str = "contract_package_access";
+const NAMESPACE_NAME: &str = "counter";
+const COUNT_KEY: &str = "count";
+const INCREMENT_ENTRY_POINT: &str = "increment";
+const INCREMENT_LOCAL_ENTRY_POINT: &str = "increment_local";
+const WRITE_FORGED_ENTRY_POINT: &str = "write_forged";
+const ACTION_INSTALL: &str = "install";
+const ACTION_UPGRADE: &str = "upgrade";
+
+#[repr(u16)]
+enum Error {
+    UnknownAction = 1,
+}
+
+impl Into<ApiError> for Error {
+    fn into(self) -> ApiError {
+        ApiError::User(self as u16)
+    }
+}
+
+enum Arg {
+    Action = 0,
+}
+
+/// Increments the counter in the "counter" namespace, which is shared by every version of the
+/// package, and returns its new value.
+#[no_mangle]
+pub extern "C" fn increment() {
+    let namespace = storage::namespace(NAMESPACE_NAME);
+    storage::add_namespaced(namespace, COUNT_KEY, 1u32);
+    let count: u32 = storage::read_namespaced(namespace, &COUNT_KEY)
+        .unwrap_or_revert()
+        .unwrap_or_revert_with(ApiError::ValueNotFound);
+    runtime::ret(CLValue::from_t(count).unwrap_or_revert())
+}
+
+/// Increments the counter in the local state of the current version, and returns its new value.
+#[no_mangle]
+pub extern "C" fn increment_local() {
+    let count: u32 = storage::read_local(&COUNT_KEY)
+        .unwrap_or_revert()
+        .unwrap_or_default()
+        + 1;
+    storage::write_local(COUNT_KEY, count);
+    runtime::ret(CLValue::from_t(count).unwrap_or_revert())
+}
+
+/// Writes to a namespace which was not obtained from the host.
+#[no_mangle]
+pub extern "C" fn write_forged() {
+    let seed = Seed::new([0u8; 32]);
+    storage::write_namespaced(seed, COUNT_KEY, 0u32);
+    runtime::ret(CLValue::from_t(0u32).unwrap_or_revert())
+}
+
+fn entry_points() -> EntryPoints {
+    vec![
+        EntryPoint::new(INCREMENT_ENTRY_POINT, vec![], CLType::U32),
+        EntryPoint::new(INCREMENT_LOCAL_ENTRY_POINT, vec![], CLType::U32),
+        EntryPoint::new(WRITE_FORGED_ENTRY_POINT, vec![], CLType::U32),
+    ]
+    .into()
+}
+
+fn package_hash() -> [u8; 32] {
+    runtime::get_key(PACKAGE_NAME)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .as_hash()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant)
+}
+
+fn access_key() -> URef {
+    runtime::get_key(ACCESS_KEY_NAME)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant)
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let action: String = runtime::get_arg(Arg::Action as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    match action.as_str() {
+        ACTION_INSTALL => {
+            let (package_hash, access_key) = storage::new_contract(entry_points(), BTreeMap::new());
+            runtime::put_key(PACKAGE_NAME, Key::Hash(package_hash));
+            runtime::put_key(ACCESS_KEY_NAME, access_key.into());
+        }
+        ACTION_UPGRADE => {
+            storage::add_contract_version(
+                package_hash(),
+                access_key(),
+                entry_points(),
+                BTreeMap::new(),
+            );
+        }
+        _ => runtime::revert(Error::UnknownAction),
+    }
+}
@@ -0,0 +1,87 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLType, CLValue, ContractVersion, EntryPoint, EntryPoints, Key, URef};
+
+const PACKAGE_NAME: &str = "contract_package";
+const ACCESS_KEY_NAME: &str = "contract_package_access";
+const VERSION_NAME: &str = "contract_package_version";
+const GET_VERSION_ENTRY_POINT: &str = "get_version";
+const ACTION_UPGRADE: &str = "upgrade";
+const ACTION_DISABLE: &str = "disable";
+
+#[repr(u16)]
+enum Error {
+    UnknownAction = 1,
+}
+
+impl Into<ApiError> for Error {
+    fn into(self) -> ApiError {
+        ApiError::User(self as u16)
+    }
+}
+
+enum Arg {
+    Action = 0,
+    Version = 1,
+}
+
+#[no_mangle]
+pub extern "C" fn get_version() {
+    let version: u32 = 2;
+    runtime::ret(CLValue::from_t(version).unwrap_or_revert())
+}
+
+fn package_hash() -> [u8; 32] {
+    runtime::get_key(PACKAGE_NAME)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .as_hash()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant)
+}
+
+fn access_key() -> URef {
+    runtime::get_key(ACCESS_KEY_NAME)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant)
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let action: String = runtime::get_arg(Arg::Action as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    match action.as_str() {
+        ACTION_UPGRADE => {
+            let entry_points: EntryPoints = vec![EntryPoint::new(
+                GET_VERSION_ENTRY_POINT,
+                vec![],
+                CLType::U32,
+            )]
+            .into();
+            let version = storage::add_contract_version(
+                package_hash(),
+                access_key(),
+                entry_points,
+                BTreeMap::new(),
+            );
+            let version_uref = storage::new_turef(version);
+            runtime::put_key(VERSION_NAME, Key::from(version_uref));
+        }
+        ACTION_DISABLE => {
+            let version: ContractVersion = runtime::get_arg(Arg::Version as u32)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            storage::disable_contract_version(package_hash(), access_key(), version);
+        }
+        _ => runtime::revert(Error::UnknownAction),
+    }
+}
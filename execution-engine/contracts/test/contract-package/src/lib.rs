@@ -0,0 +1,43 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec};
+
+use contract::{
+    contract_api::{installer, runtime},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLType, CLValue, EntryPoint, Parameter};
+
+const PACKAGE_NAME: &str = "contract_package";
+const GET_VERSION_ENTRY_POINT: &str = "get_version";
+const ECHO_ENTRY_POINT: &str = "echo";
+
+#[no_mangle]
+pub extern "C" fn get_version() {
+    let version: u32 = 1;
+    runtime::ret(CLValue::from_t(version).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn echo() {
+    let message: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    runtime::ret(CLValue::from_t(message).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let entry_points = vec![
+        EntryPoint::new(GET_VERSION_ENTRY_POINT, vec![], CLType::U32),
+        EntryPoint::new(
+            ECHO_ENTRY_POINT,
+            vec![Parameter::new("message", CLType::String)],
+            CLType::String,
+        ),
+    ];
+
+    installer::install_package(PACKAGE_NAME, &entry_points, BTreeMap::new());
+}
@@ -0,0 +1,13 @@
+#![no_std]
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{ApiError, ContractRef};
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_hash: [u8; 32] = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    runtime::call_contract(ContractRef::Hash(contract_hash), ())
+}
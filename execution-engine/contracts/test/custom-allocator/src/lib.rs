@@ -0,0 +1,55 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::UnsafeCell,
+    ptr,
+};
+
+use contract::contract_api::{runtime, storage};
+
+const HEAP_SIZE: usize = 64 * 1024;
+const ALLOCATED_KEY: &str = "allocated";
+
+/// A bump allocator which never frees memory, backed by a fixed size static buffer.
+struct BumpAllocator {
+    heap: UnsafeCell<[u8; HEAP_SIZE]>,
+    next: UnsafeCell<usize>,
+}
+
+// Contracts are single threaded.
+unsafe impl Sync for BumpAllocator {}
+
+unsafe impl GlobalAlloc for BumpAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let heap_start = self.heap.get() as usize;
+        let next = &mut *self.next.get();
+        let start = (heap_start + *next + layout.align() - 1) & !(layout.align() - 1);
+        let end = start + layout.size();
+        if end > heap_start + HEAP_SIZE {
+            return ptr::null_mut();
+        }
+        *next = end - heap_start;
+        start as *mut u8
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[cfg(not(feature = "std"))]
+#[global_allocator]
+static ALLOC: BumpAllocator = BumpAllocator {
+    heap: UnsafeCell::new([0; HEAP_SIZE]),
+    next: UnsafeCell::new(0),
+};
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let values: Vec<u64> = (0..100).collect();
+    let sum: u64 = values.iter().sum();
+    let key = storage::new_turef(sum).into();
+    runtime::put_key(ALLOCATED_KEY, key);
+}
@@ -0,0 +1,36 @@
+#![no_std]
+
+use contract::{contract_api::runtime, ext_ffi, fmt_buf, unwrap_or_revert::UnwrapOrRevert};
+use types::{api_error, ApiError};
+
+const MESSAGE_BUFFER_SIZE: usize = 32;
+const INVALID_UTF8: [u8; 2] = [0xc3, 0x28];
+
+#[repr(u16)]
+enum Error {
+    PrintedInvalidUtf8 = 0,
+    Reverted = 1,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let should_revert: bool = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    runtime::print("Hello, world!").unwrap_or_revert();
+
+    let mut buf = [0u8; MESSAGE_BUFFER_SIZE];
+    let phase = runtime::get_phase();
+    let message = fmt_buf::format(&mut buf, format_args!("phase is {:?}", phase));
+    runtime::print(message).unwrap_or_revert();
+
+    let result = unsafe { ext_ffi::print(INVALID_UTF8.as_ptr(), INVALID_UTF8.len()) };
+    if api_error::result_from(result) != Err(ApiError::InvalidArgument) {
+        runtime::revert(ApiError::User(Error::PrintedInvalidUtf8 as u16));
+    }
+
+    if should_revert {
+        runtime::revert(ApiError::User(Error::Reverted as u16));
+    }
+}
@@ -0,0 +1,74 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::PurseId, ApiError, CLValue, URef, U512};
+
+const ESCROW_CONTRACT_NAME: &str = "escrow_contract";
+const ESCROW_PURSE_NAME: &str = "escrow";
+const DEPOSIT_PURSE_NAME: &str = "escrow_deposit";
+const ORIGINAL_PURSE_NAME: &str = "original_purse";
+
+#[repr(u16)]
+enum Error {
+    OwnershipTransferredFromSession = 1,
+    WithdrawnFromOriginalPurse,
+    WithdrawnFromDepositPurse,
+}
+
+impl Into<ApiError> for Error {
+    fn into(self) -> ApiError {
+        ApiError::User(self as u16)
+    }
+}
+
+/// Takes ownership of the purse passed by the caller, returning an add-only copy of the new purse.
+#[no_mangle]
+pub extern "C" fn escrow_ext() {
+    let purse_id: PurseId = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let escrow_purse_id =
+        runtime::transfer_uref_ownership(purse_id, ESCROW_PURSE_NAME).unwrap_or_revert();
+    let deposit_purse = escrow_purse_id.value().into_add();
+    runtime::ret(CLValue::from_t(deposit_purse).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let main_purse = account::get_main_purse();
+
+    let purse_id = system::create_purse();
+    system::transfer_from_purse_to_purse(main_purse, purse_id, amount).unwrap_or_revert();
+    runtime::put_key(ORIGINAL_PURSE_NAME, purse_id.value().into());
+
+    if runtime::transfer_uref_ownership(purse_id, ESCROW_PURSE_NAME)
+        != Err(ApiError::PermissionDenied)
+    {
+        runtime::revert(Error::OwnershipTransferredFromSession);
+    }
+
+    let contract_ref = storage::store_function_at_hash("escrow_ext", BTreeMap::new());
+    runtime::put_key(ESCROW_CONTRACT_NAME, contract_ref.clone().into());
+    let deposit_purse: URef = runtime::call_contract(contract_ref, (purse_id,));
+    runtime::put_key(DEPOSIT_PURSE_NAME, deposit_purse.into());
+
+    // The original purse is empty, and the deposit purse only allows adding to the escrow purse
+    if system::transfer_from_purse_to_purse(purse_id, main_purse, U512::one()).is_ok() {
+        runtime::revert(Error::WithdrawnFromOriginalPurse);
+    }
+    let deposit_purse_id = PurseId::new(deposit_purse);
+    system::transfer_from_purse_to_purse(main_purse, deposit_purse_id, amount).unwrap_or_revert();
+    if system::transfer_from_purse_to_purse(deposit_purse_id, main_purse, U512::one()).is_ok() {
+        runtime::revert(Error::WithdrawnFromDepositPurse);
+    }
+}
@@ -0,0 +1,25 @@
+#![no_std]
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::ApiError;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let known_era_id: u64 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let known_block_height: u64 = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    assert_eq!(
+        runtime::get_era_id(),
+        known_era_id,
+        "actual era id not known era id"
+    );
+    assert_eq!(
+        runtime::get_block_height(),
+        known_block_height,
+        "actual block height not known block height"
+    );
+}
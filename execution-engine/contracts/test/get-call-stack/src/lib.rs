@@ -0,0 +1,62 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLValue, CallStackElement, Key};
+
+const CONTRACT_NAME: &str = "call_stack_contract";
+const SUBCALL_CONTRACT_NAME: &str = "call_stack_subcall_contract";
+const SESSION_CALL_STACK_NAME: &str = "session_call_stack";
+const CONTRACT_CALL_STACK_NAME: &str = "contract_call_stack";
+const SUBCALL_CALL_STACK_NAME: &str = "subcall_call_stack";
+
+#[no_mangle]
+pub extern "C" fn call_stack_ext() {
+    let call_stack = runtime::get_call_stack();
+    runtime::ret(CLValue::from_t(call_stack).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn call_stack_subcall_ext() {
+    let contract_ref = runtime::get_key(CONTRACT_NAME)
+        .and_then(Key::to_contract_ref)
+        .unwrap_or_revert_with(ApiError::GetKey);
+    let call_stack: Vec<CallStackElement> = runtime::call_contract(contract_ref, ());
+    runtime::ret(CLValue::from_t(call_stack).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let session_call_stack = runtime::get_call_stack();
+    runtime::put_key(
+        SESSION_CALL_STACK_NAME,
+        storage::new_turef(session_call_stack).into(),
+    );
+
+    let contract_ref = storage::store_function_at_hash("call_stack_ext", BTreeMap::new());
+    runtime::put_key(CONTRACT_NAME, contract_ref.clone().into());
+    let contract_call_stack: Vec<CallStackElement> =
+        runtime::call_contract(contract_ref.clone(), ());
+    runtime::put_key(
+        CONTRACT_CALL_STACK_NAME,
+        storage::new_turef(contract_call_stack).into(),
+    );
+
+    let mut named_keys = BTreeMap::new();
+    named_keys.insert(CONTRACT_NAME.into(), contract_ref.into());
+    let subcall_contract_ref =
+        storage::store_function_at_hash("call_stack_subcall_ext", named_keys);
+    runtime::put_key(SUBCALL_CONTRACT_NAME, subcall_contract_ref.clone().into());
+    let subcall_call_stack: Vec<CallStackElement> =
+        runtime::call_contract(subcall_contract_ref, ());
+    runtime::put_key(
+        SUBCALL_CALL_STACK_NAME,
+        storage::new_turef(subcall_call_stack).into(),
+    );
+}
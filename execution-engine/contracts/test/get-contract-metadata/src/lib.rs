@@ -0,0 +1,32 @@
+#![no_std]
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, ContractRef};
+
+const METADATA_KEY: &str = "metadata";
+
+#[repr(u16)]
+enum Error {
+    MissingMetadata = 1,
+}
+
+impl Into<ApiError> for Error {
+    fn into(self) -> ApiError {
+        ApiError::User(self as u16)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_hash: [u8; 32] = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let metadata = runtime::get_contract_metadata(ContractRef::Hash(contract_hash))
+        .unwrap_or_revert_with(Error::MissingMetadata);
+    let metadata_uref = storage::new_turef(metadata);
+    runtime::put_key(METADATA_KEY, metadata_uref.into());
+}
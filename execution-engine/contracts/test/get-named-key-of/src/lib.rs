@@ -0,0 +1,42 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{account::PublicKey, ApiError, Key};
+
+#[repr(u16)]
+enum Error {
+    UnexpectedKey = 1,
+}
+
+impl Into<ApiError> for Error {
+    fn into(self) -> ApiError {
+        ApiError::User(self as u16)
+    }
+}
+
+enum Arg {
+    Account = 0,
+    Name,
+    Expected,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let account: PublicKey = runtime::get_arg(Arg::Account as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let name: String = runtime::get_arg(Arg::Name as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let expected: Option<Key> = runtime::get_arg(Arg::Expected as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    if runtime::get_named_key_of(account, &name) != expected {
+        runtime::revert(Error::UnexpectedKey);
+    }
+}
@@ -0,0 +1,31 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const ARG_DATA: u32 = 0;
+const BLAKE2B_DIGEST_NAME: &str = "blake2b_digest";
+const SHA256_DIGEST_NAME: &str = "sha256_digest";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let data: Vec<u8> = runtime::get_arg(ARG_DATA)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let blake2b_digest = runtime::blake2b(&data);
+    let sha256_digest = runtime::sha256(&data);
+
+    runtime::put_key(
+        BLAKE2B_DIGEST_NAME,
+        storage::new_turef(blake2b_digest).into(),
+    );
+    runtime::put_key(SHA256_DIGEST_NAME, storage::new_turef(sha256_digest).into());
+}
@@ -0,0 +1,41 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const ARG_ACTION: u32 = 0;
+const ARG_COUNT: u32 = 1;
+const ACTION_READ: &str = "read";
+const ACTION_WRITE: &str = "write";
+const ACTION_ADD: &str = "add";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let action: String = runtime::get_arg(ARG_ACTION)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let count: u32 = runtime::get_arg(ARG_COUNT)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let turef = storage::new_turef(0u64);
+    for i in 0..count {
+        match action.as_str() {
+            ACTION_READ => {
+                let _value: u64 = storage::read(turef)
+                    .unwrap_or_revert_with(ApiError::Read)
+                    .unwrap_or_revert_with(ApiError::ValueNotFound);
+            }
+            ACTION_WRITE => storage::write(turef, u64::from(i)),
+            ACTION_ADD => storage::add(turef, 1u64),
+            _ => runtime::revert(ApiError::InvalidArgument),
+        }
+    }
+}
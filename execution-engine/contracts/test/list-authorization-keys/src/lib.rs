@@ -0,0 +1,37 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::PublicKey, ApiError, CLValue};
+
+#[no_mangle]
+pub extern "C" fn list_authorization_keys_ext() {
+    let authorization_keys = runtime::list_authorization_keys();
+    let return_value = CLValue::from_t(authorization_keys).unwrap_or_revert();
+    runtime::ret(return_value)
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let known_authorization_keys: Vec<PublicKey> = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let authorization_keys = runtime::list_authorization_keys();
+    assert_eq!(
+        authorization_keys, known_authorization_keys,
+        "authorization keys were not known authorization keys"
+    );
+
+    let pointer = storage::store_function_at_hash("list_authorization_keys_ext", BTreeMap::new());
+    let subcall_authorization_keys: Vec<PublicKey> = runtime::call_contract(pointer, ());
+    assert_eq!(
+        subcall_authorization_keys, known_authorization_keys,
+        "subcall authorization keys were not known authorization keys"
+    );
+}
@@ -27,7 +27,9 @@ pub extern "C" fn call() {
 
     let mint = system::get_mint();
 
-    let balance: Option<U512> = runtime::call_contract(mint, ("balance", new_purse));
+    let result: Result<Option<U512>, mint::Error> =
+        runtime::call_contract(mint, ("balance", new_purse));
+    let balance = result.unwrap_or_revert();
 
     match balance {
         None => runtime::revert(ApiError::User(Error::BalanceNotFound as u16)),
@@ -41,8 +41,8 @@ pub fn delegate() {
             let uref: URef = runtime::get_arg(1)
                 .unwrap_or_revert_with(ApiError::MissingArgument)
                 .unwrap_or_revert_with(ApiError::InvalidArgument);
-            let balance: Option<U512> = mint.balance(uref).unwrap_or_revert();
-            let ret = CLValue::from_t(balance).unwrap_or_revert();
+            let result: Result<Option<U512>, Error> = mint.balance(uref);
+            let ret = CLValue::from_t(result).unwrap_or_revert();
             runtime::ret(ret)
         }
 
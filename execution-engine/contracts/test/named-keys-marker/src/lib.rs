@@ -0,0 +1,108 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::PurseId, ApiError, U512};
+
+const MARKER: &str = "marker";
+const MARK_ENTRY_POINT: &str = "mark";
+const GET_PAYMENT_PURSE: &str = "get_payment_purse";
+
+const COMMAND_ACCOUNT: &str = "account";
+const COMMAND_CONTRACT: &str = "contract";
+const COMMAND_PAYMENT: &str = "payment";
+const COMMAND_SESSION_AFTER_PAYMENT: &str = "session_after_payment";
+
+#[repr(u16)]
+enum Args {
+    Command = 0,
+    Amount = 1,
+}
+
+#[repr(u16)]
+enum Error {
+    UnknownCommand = 1,
+    MarkerMissing = 2,
+    MarkerPresent = 3,
+}
+
+impl Into<ApiError> for Error {
+    fn into(self) -> ApiError {
+        ApiError::User(self as u16)
+    }
+}
+
+fn assert_marker(expected: bool) {
+    match (runtime::has_key(MARKER), expected) {
+        (true, false) => runtime::revert(Error::MarkerPresent),
+        (false, true) => runtime::revert(Error::MarkerMissing),
+        _ => (),
+    }
+}
+
+fn put_marker() {
+    runtime::put_key(MARKER, storage::new_turef(()).into());
+}
+
+fn standard_payment(amount: U512) {
+    let main_purse = account::get_main_purse();
+
+    let pos_pointer = system::get_proof_of_stake();
+
+    let payment_purse: PurseId = runtime::call_contract(pos_pointer, (GET_PAYMENT_PURSE,));
+
+    system::transfer_from_purse_to_purse(main_purse, payment_purse, amount).unwrap_or_revert()
+}
+
+#[no_mangle]
+pub extern "C" fn mark() {
+    let expected: bool = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    assert_marker(expected);
+    put_marker();
+    assert_marker(true);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = runtime::get_arg(Args::Command as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    match command.as_str() {
+        COMMAND_ACCOUNT => {
+            assert_marker(false);
+            put_marker();
+            assert_marker(true);
+            runtime::remove_key(MARKER);
+            assert_marker(false);
+            put_marker();
+            assert_marker(true);
+        }
+        COMMAND_CONTRACT => {
+            let contract_ref = storage::store_function_at_hash(MARK_ENTRY_POINT, BTreeMap::new());
+            // The second call observes the marker put by the first one within the same deploy.
+            runtime::call_contract::<_, ()>(contract_ref.clone(), (false,));
+            runtime::call_contract::<_, ()>(contract_ref, (true,));
+            // Markers put by a contract live in its own named keys, not the caller's.
+            assert_marker(false);
+        }
+        COMMAND_PAYMENT => {
+            let amount: U512 = runtime::get_arg(Args::Amount as u32)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            assert_marker(false);
+            put_marker();
+            standard_payment(amount);
+        }
+        COMMAND_SESSION_AFTER_PAYMENT => assert_marker(true),
+        _ => runtime::revert(Error::UnknownCommand),
+    }
+}
@@ -0,0 +1,78 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, U512};
+
+const ACTION_CREATE: &str = "create";
+const ACTION_WITHDRAW: &str = "withdraw";
+const PURSE_NAME: &str = "savings";
+const MISSING_PURSE_NAME: &str = "missing";
+
+#[repr(u16)]
+enum Error {
+    UnknownAction = 1,
+    DuplicatePurseCreated,
+    NamedPurseNotFound,
+    UnexpectedNamedPurse,
+}
+
+impl Into<ApiError> for Error {
+    fn into(self) -> ApiError {
+        ApiError::User(self as u16)
+    }
+}
+
+enum Arg {
+    Action = 0,
+    Amount,
+}
+
+/// Creates the named purse and funds it from the main purse.
+fn create(amount: U512) {
+    let purse_id = account::create_named_purse(PURSE_NAME).unwrap_or_revert();
+
+    match account::create_named_purse(PURSE_NAME) {
+        Err(ApiError::DuplicateNamedPurse) => {}
+        _ => runtime::revert(Error::DuplicatePurseCreated),
+    }
+    if account::get_named_purse(PURSE_NAME) != Some(purse_id) {
+        runtime::revert(Error::NamedPurseNotFound);
+    }
+    if account::get_named_purse(MISSING_PURSE_NAME).is_some() {
+        runtime::revert(Error::UnexpectedNamedPurse);
+    }
+
+    system::transfer_from_purse_to_purse(account::get_main_purse(), purse_id, amount)
+        .unwrap_or_revert();
+}
+
+/// Transfers from the named purse back to the main purse.
+fn withdraw(amount: U512) {
+    let purse_id =
+        account::get_named_purse(PURSE_NAME).unwrap_or_revert_with(Error::NamedPurseNotFound);
+    system::transfer_from_purse_to_purse(purse_id, account::get_main_purse(), amount)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let action: String = runtime::get_arg(Arg::Action as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let amount: U512 = runtime::get_arg(Arg::Amount as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    match action.as_str() {
+        ACTION_CREATE => create(amount),
+        ACTION_WITHDRAW => withdraw(amount),
+        _ => runtime::revert(Error::UnknownAction),
+    }
+}
@@ -0,0 +1,42 @@
+#![no_std]
+
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::PurseId, ApiError, Key, U512};
+
+const GET_PAYMENT_PURSE: &str = "get_payment_purse";
+const NAMED_PURSE: &str = "payment_named_purse";
+const APPLICATION_STATE: &str = "payment_application_state";
+
+fn standard_payment(amount: U512) {
+    let main_purse = account::get_main_purse();
+
+    let pos_pointer = system::get_proof_of_stake();
+
+    let payment_purse: PurseId = runtime::call_contract(pos_pointer, (GET_PAYMENT_PURSE,));
+
+    system::transfer_from_purse_to_purse(main_purse, payment_purse, amount).unwrap_or_revert()
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let write_application_state: bool = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    // Keeping a purse under a name is always allowed
+    let purse = system::create_purse();
+    runtime::put_key(NAMED_PURSE, Key::from(purse.value()));
+
+    if write_application_state {
+        let state = storage::new_turef(1u64);
+        runtime::put_key(APPLICATION_STATE, Key::from(state));
+    }
+
+    standard_payment(amount);
+}
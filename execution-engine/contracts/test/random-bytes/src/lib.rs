@@ -0,0 +1,41 @@
+#![no_std]
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const ARG_USE_RANDOM_BYTES: u32 = 0;
+const UREF_NAME: &str = "uref";
+const RANDOM_BYTES_1_NAME: &str = "random_bytes_1";
+const RANDOM_BYTES_2_NAME: &str = "random_bytes_2";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let use_random_bytes: bool = runtime::get_arg(ARG_USE_RANDOM_BYTES)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let random_bytes = if use_random_bytes {
+        Some((runtime::random_bytes(), runtime::random_bytes()))
+    } else {
+        None
+    };
+
+    // Created after drawing the random bytes, so its address shows whether they were drawn from
+    // the same stream
+    let turef = storage::new_turef(());
+    runtime::put_key(UREF_NAME, turef.into());
+
+    if let Some((random_bytes_1, random_bytes_2)) = random_bytes {
+        runtime::put_key(
+            RANDOM_BYTES_1_NAME,
+            storage::new_turef(random_bytes_1).into(),
+        );
+        runtime::put_key(
+            RANDOM_BYTES_2_NAME,
+            storage::new_turef(random_bytes_2).into(),
+        );
+    }
+}
@@ -0,0 +1,43 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, Key};
+
+const CONTRACT_NAME: &str = "reentrant_contract";
+
+/// Calls itself, through the contract key passed as the first argument, until the depth given as
+/// the second argument reaches zero.
+#[no_mangle]
+pub extern "C" fn reentrant_ext() {
+    let contract_key: Key = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let depth: u32 = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    if depth > 0 {
+        let contract_ref = contract_key
+            .to_contract_ref()
+            .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+        runtime::call_contract::<_, ()>(contract_ref, (contract_key, depth - 1));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let depth: u32 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let contract_ref = storage::store_function_at_hash("reentrant_ext", BTreeMap::new());
+    let contract_key: Key = contract_ref.clone().into();
+    runtime::put_key(CONTRACT_NAME, contract_key);
+    runtime::call_contract::<_, ()>(contract_ref, (contract_key, depth));
+}
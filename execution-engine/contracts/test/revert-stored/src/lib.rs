@@ -0,0 +1,22 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use contract::contract_api::{runtime, storage};
+use types::ApiError;
+
+const CONTRACT_NAME: &str = "revert_stored";
+const ENTRY_FUNCTION_NAME: &str = "delegate";
+
+#[no_mangle]
+pub extern "C" fn delegate() {
+    runtime::revert(ApiError::User(1))
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let pointer = storage::store_function_at_hash(ENTRY_FUNCTION_NAME, BTreeMap::new());
+    runtime::put_key(CONTRACT_NAME, pointer.into());
+}
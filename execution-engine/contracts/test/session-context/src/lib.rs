@@ -0,0 +1,41 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, vec};
+
+use contract::contract_api::{account, installer, runtime, storage};
+use types::{account::PublicKey, CLType, EntryPoint, EntryPointAccess};
+
+const PACKAGE_NAME: &str = "session_context";
+const PUT_MARKER_ENTRY_POINT: &str = "put_marker";
+const GET_MAIN_PURSE_ENTRY_POINT: &str = "get_main_purse";
+const RESTRICTED_ENTRY_POINT: &str = "restricted";
+const MARKER_NAME: &str = "marker";
+const NOBODY_ADDR: [u8; 32] = [255u8; 32];
+
+#[no_mangle]
+pub extern "C" fn put_marker() {
+    runtime::put_key(MARKER_NAME, storage::new_turef(()).into());
+}
+
+#[no_mangle]
+pub extern "C" fn get_main_purse() {
+    let _ = account::get_main_purse();
+}
+
+#[no_mangle]
+pub extern "C" fn restricted() {}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let entry_points = vec![
+        EntryPoint::new(PUT_MARKER_ENTRY_POINT, vec![], CLType::Unit),
+        EntryPoint::new(GET_MAIN_PURSE_ENTRY_POINT, vec![], CLType::Unit),
+        EntryPoint::new(RESTRICTED_ENTRY_POINT, vec![], CLType::Unit).with_access(
+            EntryPointAccess::Accounts(vec![PublicKey::new(NOBODY_ADDR)]),
+        ),
+    ];
+
+    installer::install_package(PACKAGE_NAME, &entry_points, BTreeMap::new());
+}
@@ -0,0 +1,22 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const ARG_VALUE: u32 = 0;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let value: String = runtime::get_arg(ARG_VALUE)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let _turef = storage::new_turef(value);
+}
@@ -0,0 +1,23 @@
+#![no_std]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::{TaggedPublicKey, Weight},
+    ApiError, U512,
+};
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let public_key: TaggedPublicKey = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let amount: U512 = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    system::transfer_to_account(public_key.clone(), amount).unwrap_or_revert();
+    account::add_associated_key(public_key, Weight::new(1)).unwrap_or_revert();
+}
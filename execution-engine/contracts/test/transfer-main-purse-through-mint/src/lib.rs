@@ -0,0 +1,34 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{system_contract_errors::mint, ApiError, U512};
+
+const METHOD_TRANSFER: &str = "transfer";
+
+/// Transfers out of the main purse by calling the mint contract directly, rather than through
+/// `system::transfer_from_purse_to_purse`.
+#[no_mangle]
+pub extern "C" fn call() {
+    let destination_name: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let amount: U512 = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let source = account::get_main_purse();
+    let destination = system::create_purse();
+    let result: Result<(), mint::Error> = runtime::call_contract(
+        system::get_mint(),
+        (METHOD_TRANSFER, source.value(), destination.value(), amount),
+    );
+    result.unwrap_or_revert();
+    runtime::put_key(&destination_name, destination.value().into());
+}
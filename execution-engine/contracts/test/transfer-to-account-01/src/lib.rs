@@ -4,13 +4,13 @@ use contract::{
     contract_api::{runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, U512};
+use types::{account::PublicKey, ApiError, U512};
 
 const TRANSFER_AMOUNT: u32 = 250_000_000 + 1000;
 
 #[no_mangle]
 pub extern "C" fn call() {
-    let public_key = runtime::get_arg(0)
+    let public_key: PublicKey = runtime::get_arg(0)
         .unwrap_or_revert_with(ApiError::MissingArgument)
         .unwrap_or_revert_with(ApiError::InvalidArgument);
     let amount = U512::from(TRANSFER_AMOUNT);
@@ -0,0 +1,39 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use contract::{
+    contract_api::{account, runtime},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::{ActionType, KeyUpdate, PublicKey, Weight},
+    ApiError,
+};
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let updates: Vec<KeyUpdate> = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let key_management_threshold: Option<Weight> = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let expected_associated_keys: BTreeMap<PublicKey, Weight> = runtime::get_arg(2)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    account::update_associated_keys(updates).unwrap_or_revert();
+
+    if let Some(threshold) = key_management_threshold {
+        account::set_action_threshold(ActionType::KeyManagement, threshold).unwrap_or_revert();
+    }
+
+    let associated_keys = account::list_associated_keys();
+    assert_eq!(
+        associated_keys, expected_associated_keys,
+        "associated keys were not the expected associated keys"
+    );
+}
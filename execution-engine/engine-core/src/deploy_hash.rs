@@ -0,0 +1,77 @@
+use std::{array::TryFromSliceError, convert::TryFrom, fmt};
+
+use crate::DEPLOY_HASH_LENGTH;
+
+/// The hash identifying a deploy.
+///
+/// Together with the execution phase, it seeds the generation of addresses during execution, so
+/// it must be unique within an exec request.
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeployHash([u8; DEPLOY_HASH_LENGTH]);
+
+impl DeployHash {
+    pub const fn new(value: [u8; DEPLOY_HASH_LENGTH]) -> Self {
+        DeployHash(value)
+    }
+
+    pub fn value(&self) -> [u8; DEPLOY_HASH_LENGTH] {
+        self.0
+    }
+}
+
+impl From<[u8; DEPLOY_HASH_LENGTH]> for DeployHash {
+    fn from(value: [u8; DEPLOY_HASH_LENGTH]) -> Self {
+        DeployHash(value)
+    }
+}
+
+/// Fails unless the slice is exactly [`DEPLOY_HASH_LENGTH`] bytes long.
+impl TryFrom<&[u8]> for DeployHash {
+    type Error = TryFromSliceError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        <[u8; DEPLOY_HASH_LENGTH]>::try_from(bytes).map(DeployHash)
+    }
+}
+
+impl AsRef<[u8]> for DeployHash {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Display for DeployHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", base16::encode_lower(&self.0))
+    }
+}
+
+impl fmt::Debug for DeployHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DeployHash({})", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::DeployHash;
+
+    #[test]
+    fn should_convert_from_slice_of_correct_length_only() {
+        let bytes = [7u8; 33];
+        assert_eq!(
+            DeployHash::try_from(&bytes[..32]).map(|hash| hash.value()),
+            Ok([7u8; 32])
+        );
+        assert!(DeployHash::try_from(&bytes[..31]).is_err());
+        assert!(DeployHash::try_from(&bytes[..]).is_err());
+    }
+
+    #[test]
+    fn should_display_as_hex() {
+        let deploy_hash = DeployHash::new([0xab; 32]);
+        assert_eq!(deploy_hash.to_string(), "ab".repeat(32));
+    }
+}
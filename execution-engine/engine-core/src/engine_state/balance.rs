@@ -0,0 +1,41 @@
+use engine_shared::{motes::Motes, newtypes::Blake2bHash};
+use types::{ProtocolVersion, URef};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BalanceResult {
+    RootNotFound,
+    Success(Motes),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceRequest {
+    state_hash: Blake2bHash,
+    protocol_version: ProtocolVersion,
+    purse_uref: URef,
+}
+
+impl BalanceRequest {
+    pub fn new(
+        state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        purse_uref: URef,
+    ) -> Self {
+        BalanceRequest {
+            state_hash,
+            protocol_version,
+            purse_uref,
+        }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    pub fn purse_uref(&self) -> URef {
+        self.purse_uref
+    }
+}
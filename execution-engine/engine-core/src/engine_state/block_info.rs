@@ -0,0 +1,32 @@
+use types::BlockTime;
+
+/// Details of the block a deploy is executed in, which contracts can query through the
+/// `get_blocktime`, `get_era_id` and `get_block_height` host functions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BlockInfo {
+    blocktime: BlockTime,
+    era_id: u64,
+    block_height: u64,
+}
+
+impl BlockInfo {
+    pub fn new(blocktime: BlockTime, era_id: u64, block_height: u64) -> Self {
+        BlockInfo {
+            blocktime,
+            era_id,
+            block_height,
+        }
+    }
+
+    pub fn blocktime(&self) -> BlockTime {
+        self.blocktime
+    }
+
+    pub fn era_id(&self) -> u64 {
+        self.era_id
+    }
+
+    pub fn block_height(&self) -> u64 {
+        self.block_height
+    }
+}
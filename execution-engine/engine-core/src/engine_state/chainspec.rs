@@ -0,0 +1,403 @@
+//! Loading of a [`GenesisConfig`] from a chainspec, i.e. a `manifest.toml` and the files it refers
+//! to, in the format used by the node (see `node/src/main/resources/chainspec/genesis`).
+
+use std::{
+    collections::BTreeSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use failure::Fail;
+use serde::Deserialize;
+
+use engine_shared::{account::VestingSchedule, motes::Motes};
+use engine_wasm_prep::wasm_costs::{WasmCosts, WasmCostsError};
+use types::{account::PublicKey, ProtocolVersion, U512};
+
+use crate::engine_state::genesis::{GenesisAccount, GenesisConfig};
+
+const PUBLIC_KEY_LENGTH: usize = 32;
+
+#[derive(Fail, Debug)]
+pub enum Error {
+    #[fail(display = "Failed to read {}: {}", path, error)]
+    Io { path: String, error: io::Error },
+    #[fail(display = "Malformed manifest: {}", _0)]
+    Manifest(toml::de::Error),
+    #[fail(display = "Invalid protocol version: {}", _0)]
+    InvalidProtocolVersion(String),
+    #[fail(display = "Invalid wasm costs: {}", _0)]
+    InvalidWasmCosts(WasmCostsError),
+    #[fail(display = "Invalid slash percentage: {}", _0)]
+    InvalidSlashPercentage(u8),
+    #[fail(display = "Malformed account on line {}: {}", line, reason)]
+    MalformedAccount { line: usize, reason: String },
+    #[fail(display = "Duplicate account on line {}", _0)]
+    DuplicateAccount(usize),
+}
+
+impl From<WasmCostsError> for Error {
+    fn from(error: WasmCostsError) -> Self {
+        Error::InvalidWasmCosts(error)
+    }
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(error: toml::de::Error) -> Self {
+        Error::Manifest(error)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Manifest {
+    genesis: GenesisSection,
+    wasm_costs: WasmCosts,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct GenesisSection {
+    name: String,
+    timestamp: u64,
+    protocol_version: String,
+    mint_code_path: PathBuf,
+    pos_code_path: PathBuf,
+    initial_accounts_path: PathBuf,
+    #[serde(default)]
+    unbonding_delay_millis: u64,
+    #[serde(default)]
+    slash_percentage: u8,
+}
+
+/// Loads the genesis manifest at `manifest_path`, together with the installer contracts and the
+/// initial accounts it refers to.  Relative paths in the manifest are resolved against the
+/// directory containing it.
+pub fn load_genesis_config<P: AsRef<Path>>(manifest_path: P) -> Result<GenesisConfig, Error> {
+    let manifest_path = manifest_path.as_ref();
+    let base_dir = manifest_path.parent().unwrap_or_else(|| Path::new(""));
+    let manifest: Manifest = toml::from_str(&read_to_string(manifest_path)?)?;
+    let genesis = manifest.genesis;
+
+    let protocol_version = parse_protocol_version(&genesis.protocol_version)?;
+    manifest.wasm_costs.validate()?;
+    if genesis.slash_percentage > 100 {
+        return Err(Error::InvalidSlashPercentage(genesis.slash_percentage));
+    }
+    let mint_installer_bytes = read(&base_dir.join(genesis.mint_code_path))?;
+    let proof_of_stake_installer_bytes = read(&base_dir.join(genesis.pos_code_path))?;
+    let accounts = parse_accounts(&read_to_string(
+        &base_dir.join(genesis.initial_accounts_path),
+    )?)?;
+
+    Ok(GenesisConfig::new(
+        genesis.name,
+        genesis.timestamp,
+        protocol_version,
+        mint_installer_bytes,
+        proof_of_stake_installer_bytes,
+        accounts,
+        manifest.wasm_costs,
+        genesis.unbonding_delay_millis,
+        genesis.slash_percentage,
+    ))
+}
+
+/// Parses the initial accounts, given one per line as `<base64 public key>,<balance>,<bonded
+/// amount>`, optionally followed by `,<locked amount>,<cliff timestamp>,<release duration>` to
+/// lock part of the balance.  Empty lines are ignored.
+pub fn parse_accounts(csv: &str) -> Result<Vec<GenesisAccount>, Error> {
+    let mut public_keys = BTreeSet::new();
+    let mut accounts = Vec::new();
+    for (index, line) in csv.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let malformed = |reason: &str| Error::MalformedAccount {
+            line: line_number,
+            reason: reason.to_string(),
+        };
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields.len() != 3 && fields.len() != 6 {
+            return Err(malformed(
+                "expected public key, balance, bonded amount and an optional vesting schedule",
+            ));
+        }
+        let public_key = {
+            let bytes =
+                base64::decode(fields[0]).map_err(|_| malformed("public key is not base64"))?;
+            if bytes.len() != PUBLIC_KEY_LENGTH {
+                return Err(malformed("public key is not 32 bytes long"));
+            }
+            let mut key = [0u8; PUBLIC_KEY_LENGTH];
+            key.copy_from_slice(&bytes);
+            PublicKey::new(key)
+        };
+        let balance = U512::from_dec_str(fields[1]).map_err(|_| malformed("invalid balance"))?;
+        let bonded_amount =
+            U512::from_dec_str(fields[2]).map_err(|_| malformed("invalid bonded amount"))?;
+
+        let vesting_schedule = if fields.len() == 6 {
+            let locked_amount =
+                U512::from_dec_str(fields[3]).map_err(|_| malformed("invalid locked amount"))?;
+            if locked_amount > balance {
+                return Err(malformed("locked amount exceeds balance"));
+            }
+            let cliff_timestamp = fields[4]
+                .parse()
+                .map_err(|_| malformed("invalid cliff timestamp"))?;
+            let release_duration = fields[5]
+                .parse()
+                .map_err(|_| malformed("invalid release duration"))?;
+            Some(VestingSchedule::new(
+                cliff_timestamp,
+                release_duration,
+                locked_amount,
+            ))
+        } else {
+            None
+        };
+
+        if !public_keys.insert(public_key) {
+            return Err(Error::DuplicateAccount(line_number));
+        }
+        let account =
+            GenesisAccount::new(public_key, Motes::new(balance), Motes::new(bonded_amount));
+        accounts.push(match vesting_schedule {
+            Some(vesting_schedule) => account.with_vesting_schedule(vesting_schedule),
+            None => account,
+        });
+    }
+    Ok(accounts)
+}
+
+fn parse_protocol_version(version: &str) -> Result<ProtocolVersion, Error> {
+    let parts = version
+        .split('.')
+        .map(str::parse)
+        .collect::<Result<Vec<u32>, _>>()
+        .map_err(|_| Error::InvalidProtocolVersion(version.to_string()))?;
+    match parts.as_slice() {
+        [major, minor, patch] => Ok(ProtocolVersion::from_parts(*major, *minor, *patch)),
+        _ => Err(Error::InvalidProtocolVersion(version.to_string())),
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, Error> {
+    fs::read(path).map_err(|error| Error::Io {
+        path: path.display().to_string(),
+        error,
+    })
+}
+
+fn read_to_string(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|error| Error::Io {
+        path: path.display().to_string(),
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf, process};
+
+    use engine_shared::{account::VestingSchedule, motes::Motes};
+    use engine_wasm_prep::wasm_costs::WasmCostsError;
+    use types::{account::PublicKey, ProtocolVersion, U512};
+
+    use super::{load_genesis_config, parse_accounts, Error};
+
+    const MANIFEST: &str = r#"
+[genesis]
+name = "test-chain"
+timestamp = 1568805354071
+protocol-version = "1.2.3"
+mint-code-path = "mint_install.wasm"
+pos-code-path = "pos_install.wasm"
+initial-accounts-path = "accounts.csv"
+slash-percentage = 10
+
+[deploys]
+max-ttl-millis = 86400000
+max-dependencies = 10
+
+[wasm-costs]
+regular = 1
+div-multiplier = 16
+mul-multiplier = 4
+mem-multiplier = 2
+mem-initial-pages = 4096
+mem-grow-per-page = 8192
+mem-copy-per-byte = 1
+max-stack-height = 65536
+opcodes-multiplier = 3
+opcodes-divisor = 8
+"#;
+
+    const ACCOUNTS: &str = "\
+AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=,1000,0
+
+AgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgI=,2000,500
+";
+
+    fn chainspec_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("chainspec-{}-{}", name, process::id()));
+        fs::create_dir_all(&dir).expect("should create chainspec dir");
+        fs::write(dir.join("mint_install.wasm"), [1u8, 2, 3]).expect("should write mint");
+        fs::write(dir.join("pos_install.wasm"), [4u8, 5, 6]).expect("should write pos");
+        fs::write(dir.join("accounts.csv"), ACCOUNTS).expect("should write accounts");
+        dir
+    }
+
+    #[test]
+    fn should_load_genesis_config() {
+        let dir = chainspec_dir("valid");
+        let manifest_path = dir.join("manifest.toml");
+        fs::write(&manifest_path, MANIFEST).expect("should write manifest");
+
+        let genesis_config = load_genesis_config(&manifest_path).expect("should load");
+        fs::remove_dir_all(dir).expect("should clean up");
+
+        assert_eq!(genesis_config.name(), "test-chain");
+        assert_eq!(genesis_config.timestamp(), 1_568_805_354_071);
+        assert_eq!(
+            genesis_config.protocol_version(),
+            ProtocolVersion::from_parts(1, 2, 3)
+        );
+        assert_eq!(genesis_config.mint_installer_bytes(), &[1, 2, 3]);
+        assert_eq!(genesis_config.proof_of_stake_installer_bytes(), &[4, 5, 6]);
+        assert_eq!(genesis_config.accounts().len(), 2);
+        assert_eq!(genesis_config.wasm_costs().div, 16);
+        assert_eq!(genesis_config.wasm_costs().storage_write_byte, 0);
+        assert_eq!(genesis_config.wasm_costs().max_memory, 0);
+        assert_eq!(genesis_config.unbonding_delay(), 0);
+        assert_eq!(genesis_config.slash_percentage(), 10);
+    }
+
+    #[test]
+    fn should_load_wasm_limits() {
+        let dir = chainspec_dir("wasm-limits");
+        let manifest_path = dir.join("manifest.toml");
+        let manifest = format!("{}max-memory-pages = 128\nmax-table-size = 256\n", MANIFEST);
+        fs::write(&manifest_path, manifest).expect("should write manifest");
+
+        let genesis_config = load_genesis_config(&manifest_path).expect("should load");
+        fs::remove_dir_all(dir).expect("should clean up");
+
+        assert_eq!(genesis_config.wasm_costs().max_memory, 128);
+        assert_eq!(genesis_config.wasm_costs().max_table_size, 256);
+    }
+
+    #[test]
+    fn should_fail_to_load_manifest_with_missing_fields() {
+        let dir = chainspec_dir("missing-fields");
+        let manifest_path = dir.join("manifest.toml");
+        fs::write(&manifest_path, MANIFEST.replace("regular = 1", ""))
+            .expect("should write manifest");
+
+        let result = load_genesis_config(&manifest_path);
+        fs::remove_dir_all(dir).expect("should clean up");
+
+        match result {
+            Err(Error::Manifest(_)) => (),
+            other => panic!("expected a manifest error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_load_manifest_with_invalid_protocol_version() {
+        let dir = chainspec_dir("invalid-version");
+        let manifest_path = dir.join("manifest.toml");
+        fs::write(&manifest_path, MANIFEST.replace("1.2.3", "1.2")).expect("should write manifest");
+
+        let result = load_genesis_config(&manifest_path);
+        fs::remove_dir_all(dir).expect("should clean up");
+
+        match result {
+            Err(Error::InvalidProtocolVersion(version)) => assert_eq!(version, "1.2"),
+            other => panic!(
+                "expected a protocol version error, got {:?}",
+                other.map(|_| ())
+            ),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_load_manifest_with_invalid_wasm_costs() {
+        let dir = chainspec_dir("invalid-wasm-costs");
+        let manifest_path = dir.join("manifest.toml");
+        fs::write(
+            &manifest_path,
+            MANIFEST.replace("opcodes-divisor = 8", "opcodes-divisor = 0"),
+        )
+        .expect("should write manifest");
+
+        let result = load_genesis_config(&manifest_path);
+        fs::remove_dir_all(dir).expect("should clean up");
+
+        match result {
+            Err(Error::InvalidWasmCosts(WasmCostsError::ZeroOpcodesDivisor)) => (),
+            other => panic!("expected a wasm costs error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn should_parse_accounts() {
+        let accounts = parse_accounts(ACCOUNTS).expect("should parse accounts");
+        assert_eq!(accounts.len(), 2);
+        assert_eq!(accounts[1].public_key(), PublicKey::new([2; 32]));
+        assert_eq!(accounts[1].balance(), Motes::new(U512::from(2000)));
+        assert_eq!(accounts[1].bonded_amount(), Motes::new(U512::from(500)));
+        assert_eq!(accounts[1].vesting_schedule(), None);
+    }
+
+    #[test]
+    fn should_parse_account_with_vesting_schedule() {
+        let csv = "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=,1000,0,600,1568805354071,86400000";
+        let accounts = parse_accounts(csv).expect("should parse accounts");
+        assert_eq!(
+            accounts[0].vesting_schedule(),
+            Some(VestingSchedule::new(
+                1_568_805_354_071,
+                86_400_000,
+                U512::from(600)
+            ))
+        );
+    }
+
+    #[test]
+    fn should_report_line_of_malformed_account() {
+        let malformed = [
+            "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=,1000",
+            "not base64!,1000,0",
+            "AQEB,1000,0",
+            "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=,-1,0",
+            "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=,1000,x",
+            "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=,1000,0,600,1",
+            "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=,1000,0,1001,1,1",
+            "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=,1000,0,600,x,1",
+        ];
+        for line in malformed.iter() {
+            let csv = format!("{}{}\n", ACCOUNTS, line);
+            match parse_accounts(&csv) {
+                Err(Error::MalformedAccount { line, .. }) => assert_eq!(line, 4),
+                other => panic!("expected a malformed account error, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn should_reject_duplicate_accounts() {
+        let csv = format!(
+            "{}AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=,1,1\n",
+            ACCOUNTS
+        );
+        match parse_accounts(&csv) {
+            Err(Error::DuplicateAccount(line)) => assert_eq!(line, 4),
+            other => panic!("expected a duplicate account error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::{collections::BTreeSet, fmt::Debug};
+
+use engine_shared::{account::Account, newtypes::CorrelationId, stored_value::StoredValue};
+use engine_storage::global_state::StateReader;
+use types::{account::PublicKey, BlockTime, Key, ProtocolVersion};
+
+use crate::{
+    engine_state::{execution_result::ExecutionResult, Error},
+    execution,
+    tracking_copy::TrackingCopy,
+    DeployHash,
+};
+
+/// Callbacks run by [`EngineState::deploy`](super::EngineState::deploy) at fixed points of deploy
+/// execution, allowing a chain to enforce its own policies without changing the engine.
+///
+/// Every method has a no-op default, so implementors only override the points they care about.
+pub trait DeployHooks: Debug + Send + Sync {
+    /// Called once the deploy is authorized, before payment code is executed.
+    ///
+    /// Returning an error rejects the deploy as a precondition failure: nothing is executed and
+    /// nothing is charged.
+    fn pre_payment(&self, _context: &DeployHookContext) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called after payment code succeeded, before session code is executed.  The state seen
+    /// through the context includes the effects of payment code.
+    ///
+    /// Returning an error skips session code and reports the error in its place; payment is still
+    /// finalized.
+    fn pre_session(&self, _context: &DeployHookContext) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called with the result of the deploy once payment has been finalized, including when failed
+    /// payment code was charged by a forced transfer.  The state seen through the context is the
+    /// pre-state of the deploy; its effects are carried by `execution_result`.
+    fn post_finalize(&self, _context: &DeployHookContext, _execution_result: &ExecutionResult) {}
+}
+
+/// The default [`DeployHooks`], which accept every deploy.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoOpDeployHooks;
+
+impl DeployHooks for NoOpDeployHooks {}
+
+/// Details of the deploy being executed, along with read-only access to global state.
+pub struct DeployHookContext<'a> {
+    correlation_id: CorrelationId,
+    account: &'a Account,
+    authorization_keys: &'a BTreeSet<PublicKey>,
+    deploy_hash: DeployHash,
+    blocktime: BlockTime,
+    protocol_version: ProtocolVersion,
+    state: &'a dyn StateReader<Key, StoredValue, Error = execution::Error>,
+}
+
+impl<'a> DeployHookContext<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        correlation_id: CorrelationId,
+        account: &'a Account,
+        authorization_keys: &'a BTreeSet<PublicKey>,
+        deploy_hash: DeployHash,
+        blocktime: BlockTime,
+        protocol_version: ProtocolVersion,
+        state: &'a dyn StateReader<Key, StoredValue, Error = execution::Error>,
+    ) -> Self {
+        DeployHookContext {
+            correlation_id,
+            account,
+            authorization_keys,
+            deploy_hash,
+            blocktime,
+            protocol_version,
+            state,
+        }
+    }
+
+    /// The account the deploy is executed as.
+    pub fn account(&self) -> &Account {
+        self.account
+    }
+
+    pub fn authorization_keys(&self) -> &BTreeSet<PublicKey> {
+        self.authorization_keys
+    }
+
+    pub fn deploy_hash(&self) -> DeployHash {
+        self.deploy_hash
+    }
+
+    pub fn blocktime(&self) -> BlockTime {
+        self.blocktime
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    /// Reads the value stored under `key`.  Reads are not recorded in the effects of the deploy.
+    pub fn read(&self, key: &Key) -> Result<Option<StoredValue>, execution::Error> {
+        self.state.read(self.correlation_id, &key.normalize())
+    }
+}
+
+/// Read-only view of a [`TrackingCopy`] with errors converted to [`execution::Error`], so that
+/// hooks need not be generic over the global state implementation.
+pub(crate) struct TrackingCopyView<'a, R>(pub(crate) &'a TrackingCopy<R>);
+
+impl<'a, R> StateReader<Key, StoredValue> for TrackingCopyView<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    type Error = execution::Error;
+
+    fn read(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        StateReader::read(&self.0, correlation_id, key).map_err(Into::into)
+    }
+}
@@ -1,11 +1,18 @@
 use std::collections::BTreeSet;
 
-use types::account::PublicKey;
+use types::account::{PublicKey, SignatureAlgorithm, TaggedPublicKey};
 
-use crate::{engine_state::executable_deploy_item::ExecutableDeployItem, DeployHash};
+use crate::{
+    engine_state::{error::Error, executable_deploy_item::ExecutableDeployItem},
+    DeployHash,
+};
 
 type GasPrice = u64;
 
+/// A signature of a deploy hash, encoded as its algorithm's signatures usually are: 64 bytes for
+/// ed25519, DER for secp256k1.
+pub type Signature = Vec<u8>;
+
 /// Represents a deploy to be executed.  Corresponds to the similarly-named ipc protobuf message.
 #[derive(Clone, PartialEq, Eq)]
 pub struct DeployItem {
@@ -15,6 +22,14 @@ pub struct DeployItem {
     pub gas_price: GasPrice,
     pub authorization_keys: BTreeSet<PublicKey>,
     pub deploy_hash: DeployHash,
+    /// Signatures of `deploy_hash` approving this deploy, each with the algorithm-tagged public
+    /// key which made it.
+    ///
+    /// Every authorization key must have a valid signature, and every signature must be made by
+    /// one of the authorization keys.  Deploys without signatures are rejected with
+    /// [`Error::NoDeploySignatures`], unless the engine is configured to accept them, see
+    /// [`EngineConfig::with_unsigned_deploys`](super::EngineConfig::with_unsigned_deploys).
+    pub signatures: Vec<(TaggedPublicKey, Signature)>,
 }
 
 impl DeployItem {
@@ -26,6 +41,7 @@ impl DeployItem {
         gas_price: GasPrice,
         authorization_keys: BTreeSet<PublicKey>,
         deploy_hash: DeployHash,
+        signatures: Vec<(TaggedPublicKey, Signature)>,
     ) -> Self {
         DeployItem {
             address,
@@ -34,6 +50,302 @@ impl DeployItem {
             gas_price,
             authorization_keys,
             deploy_hash,
+            signatures,
+        }
+    }
+}
+
+/// Parses a secp256k1 public key in either its compressed or uncompressed SEC1 encoding.
+pub fn parse_secp256k1_public_key(bytes: &[u8]) -> Option<TaggedPublicKey> {
+    let public_key = secp256k1::PublicKey::from_slice(bytes).ok()?;
+    TaggedPublicKey::secp256k1(&public_key.serialize()).ok()
+}
+
+/// Verifies that `signatures` are valid signatures of `deploy_hash` which approve exactly the
+/// given authorization keys, i.e. that each authorization key signed the deploy.
+///
+/// Authorization keys are matched against the [`PublicKey`] derived from each signature's tagged
+/// public key.
+pub fn verify_signatures(
+    deploy_hash: &DeployHash,
+    authorization_keys: &BTreeSet<PublicKey>,
+    signatures: &[(TaggedPublicKey, Signature)],
+) -> Result<(), Error> {
+    if signatures.is_empty() {
+        return Err(Error::NoDeploySignatures);
+    }
+
+    let mut approvals = BTreeSet::new();
+    for (tagged_public_key, signature) in signatures {
+        let public_key = tagged_public_key.to_public_key();
+        if !authorization_keys.contains(&public_key) {
+            return Err(Error::InvalidDeploySignature);
+        }
+        if !verify_signature(deploy_hash, tagged_public_key, signature) {
+            return Err(Error::InvalidDeploySignature);
+        }
+        approvals.insert(public_key);
+    }
+
+    if &approvals != authorization_keys {
+        return Err(Error::InvalidDeploySignature);
+    }
+
+    Ok(())
+}
+
+/// Returns whether `signature` is a valid signature of `deploy_hash` made by `public_key`.
+///
+/// Secp256k1 signatures are made over the deploy hash itself, which is already a 32 byte digest.
+fn verify_signature(
+    deploy_hash: &DeployHash,
+    public_key: &TaggedPublicKey,
+    signature: &[u8],
+) -> bool {
+    match public_key.algorithm() {
+        SignatureAlgorithm::Ed25519 => {
+            let verifying_key = match ed25519_dalek::PublicKey::from_bytes(public_key.as_bytes()) {
+                Ok(verifying_key) => verifying_key,
+                Err(_) => return false,
+            };
+            match ed25519_dalek::Signature::from_bytes(signature) {
+                Ok(signature) => verifying_key
+                    .verify(deploy_hash.as_ref(), &signature)
+                    .is_ok(),
+                Err(_) => false,
+            }
+        }
+        SignatureAlgorithm::Secp256k1 => {
+            let verifying_key = match secp256k1::PublicKey::from_slice(public_key.as_bytes()) {
+                Ok(verifying_key) => verifying_key,
+                Err(_) => return false,
+            };
+            let message = match secp256k1::Message::from_slice(deploy_hash.as_ref()) {
+                Ok(message) => message,
+                Err(_) => return false,
+            };
+            match secp256k1::Signature::from_der(signature) {
+                Ok(signature) => secp256k1::Secp256k1::verification_only()
+                    .verify(&message, &signature, &verifying_key)
+                    .is_ok(),
+                Err(_) => false,
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use ed25519_dalek::{Keypair, PublicKey as VerifyingKey, SecretKey};
+
+    use types::account::{PublicKey, TaggedPublicKey};
+
+    use super::{parse_secp256k1_public_key, verify_signatures, Signature};
+    use crate::{engine_state::error::Error, DeployHash};
+
+    const DEPLOY_HASH: DeployHash = DeployHash::new([42; 32]);
+
+    fn keypair_from_seed(seed: u8) -> Keypair {
+        let secret = SecretKey::from_bytes(&[seed; 32]).expect("should create secret key");
+        let public = VerifyingKey::from(&secret);
+        Keypair { secret, public }
+    }
+
+    fn public_key(keypair: &Keypair) -> PublicKey {
+        PublicKey::new(keypair.public.to_bytes())
+    }
+
+    fn tagged_public_key(keypair: &Keypair) -> TaggedPublicKey {
+        TaggedPublicKey::ed25519(keypair.public.to_bytes())
+    }
+
+    fn sign(keypair: &Keypair, message: &[u8]) -> Signature {
+        keypair.sign(message).to_bytes().to_vec()
+    }
+
+    fn secp256k1_keys_from_seed(seed: u8) -> (secp256k1::SecretKey, TaggedPublicKey) {
+        let secret_key =
+            secp256k1::SecretKey::from_slice(&[seed; 32]).expect("should create secret key");
+        let public_key =
+            secp256k1::PublicKey::from_secret_key(&secp256k1::Secp256k1::new(), &secret_key);
+        let tagged_public_key = TaggedPublicKey::secp256k1(&public_key.serialize())
+            .expect("should create tagged public key");
+        (secret_key, tagged_public_key)
+    }
+
+    fn secp256k1_sign(secret_key: &secp256k1::SecretKey, message: &[u8]) -> Signature {
+        let message = secp256k1::Message::from_slice(message).expect("should create message");
+        secp256k1::Secp256k1::new()
+            .sign(&message, secret_key)
+            .serialize_der()
+            .to_vec()
+    }
+
+    #[test]
+    fn should_reject_missing_signatures() {
+        let authorization_keys: BTreeSet<PublicKey> =
+            vec![PublicKey::new([1; 32])].into_iter().collect();
+        match verify_signatures(&DEPLOY_HASH, &authorization_keys, &[]) {
+            Err(Error::NoDeploySignatures) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        match verify_signatures(&DEPLOY_HASH, &BTreeSet::new(), &[]) {
+            Err(Error::NoDeploySignatures) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_accept_signatures_of_all_authorization_keys() {
+        let keypair_1 = keypair_from_seed(1);
+        let keypair_2 = keypair_from_seed(2);
+        let authorization_keys: BTreeSet<PublicKey> =
+            vec![public_key(&keypair_1), public_key(&keypair_2)]
+                .into_iter()
+                .collect();
+        let signatures = vec![
+            (
+                tagged_public_key(&keypair_1),
+                sign(&keypair_1, DEPLOY_HASH.as_ref()),
+            ),
+            (
+                tagged_public_key(&keypair_2),
+                sign(&keypair_2, DEPLOY_HASH.as_ref()),
+            ),
+        ];
+
+        assert!(verify_signatures(&DEPLOY_HASH, &authorization_keys, &signatures).is_ok());
+    }
+
+    #[test]
+    fn should_reject_signature_of_other_message() {
+        let keypair = keypair_from_seed(1);
+        let authorization_keys: BTreeSet<PublicKey> =
+            vec![public_key(&keypair)].into_iter().collect();
+        let signatures = vec![(tagged_public_key(&keypair), sign(&keypair, &[0; 32]))];
+
+        match verify_signatures(&DEPLOY_HASH, &authorization_keys, &signatures) {
+            Err(Error::InvalidDeploySignature) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_reject_signature_by_other_key() {
+        let keypair_1 = keypair_from_seed(1);
+        let keypair_2 = keypair_from_seed(2);
+        let authorization_keys: BTreeSet<PublicKey> =
+            vec![public_key(&keypair_1)].into_iter().collect();
+        let signatures = vec![(
+            tagged_public_key(&keypair_1),
+            sign(&keypair_2, DEPLOY_HASH.as_ref()),
+        )];
+
+        match verify_signatures(&DEPLOY_HASH, &authorization_keys, &signatures) {
+            Err(Error::InvalidDeploySignature) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_reject_unsigned_authorization_key() {
+        let keypair_1 = keypair_from_seed(1);
+        let keypair_2 = keypair_from_seed(2);
+        let authorization_keys: BTreeSet<PublicKey> =
+            vec![public_key(&keypair_1), public_key(&keypair_2)]
+                .into_iter()
+                .collect();
+        let signatures = vec![(
+            tagged_public_key(&keypair_1),
+            sign(&keypair_1, DEPLOY_HASH.as_ref()),
+        )];
+
+        match verify_signatures(&DEPLOY_HASH, &authorization_keys, &signatures) {
+            Err(Error::InvalidDeploySignature) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_reject_signature_of_key_outside_authorization_keys() {
+        let keypair_1 = keypair_from_seed(1);
+        let keypair_2 = keypair_from_seed(2);
+        let authorization_keys: BTreeSet<PublicKey> =
+            vec![public_key(&keypair_1)].into_iter().collect();
+        let signatures = vec![
+            (
+                tagged_public_key(&keypair_1),
+                sign(&keypair_1, DEPLOY_HASH.as_ref()),
+            ),
+            (
+                tagged_public_key(&keypair_2),
+                sign(&keypair_2, DEPLOY_HASH.as_ref()),
+            ),
+        ];
+
+        match verify_signatures(&DEPLOY_HASH, &authorization_keys, &signatures) {
+            Err(Error::InvalidDeploySignature) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_verify_secp256k1_signatures() {
+        let (secret_key_1, tagged_public_key_1) = secp256k1_keys_from_seed(1);
+        let (secret_key_2, _) = secp256k1_keys_from_seed(2);
+        let authorization_keys: BTreeSet<PublicKey> = vec![tagged_public_key_1.to_public_key()]
+            .into_iter()
+            .collect();
+
+        let signatures = vec![(
+            tagged_public_key_1.clone(),
+            secp256k1_sign(&secret_key_1, DEPLOY_HASH.as_ref()),
+        )];
+        assert!(verify_signatures(&DEPLOY_HASH, &authorization_keys, &signatures).is_ok());
+
+        let signatures = vec![(
+            tagged_public_key_1,
+            secp256k1_sign(&secret_key_2, DEPLOY_HASH.as_ref()),
+        )];
+        match verify_signatures(&DEPLOY_HASH, &authorization_keys, &signatures) {
+            Err(Error::InvalidDeploySignature) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_not_verify_signature_of_other_algorithm() {
+        let keypair = keypair_from_seed(1);
+        let (_, secp256k1_public_key) = secp256k1_keys_from_seed(1);
+        let authorization_keys: BTreeSet<PublicKey> = vec![secp256k1_public_key.to_public_key()]
+            .into_iter()
+            .collect();
+        let signatures = vec![(secp256k1_public_key, sign(&keypair, DEPLOY_HASH.as_ref()))];
+
+        match verify_signatures(&DEPLOY_HASH, &authorization_keys, &signatures) {
+            Err(Error::InvalidDeploySignature) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_parse_compressed_and_uncompressed_secp256k1_public_keys() {
+        let secret_key =
+            secp256k1::SecretKey::from_slice(&[1; 32]).expect("should create secret key");
+        let public_key =
+            secp256k1::PublicKey::from_secret_key(&secp256k1::Secp256k1::new(), &secret_key);
+        let (_, tagged_public_key) = secp256k1_keys_from_seed(1);
+
+        assert_eq!(
+            parse_secp256k1_public_key(&public_key.serialize()),
+            Some(tagged_public_key.clone())
+        );
+        assert_eq!(
+            parse_secp256k1_public_key(&public_key.serialize_uncompressed()),
+            Some(tagged_public_key)
+        );
+        assert_eq!(parse_secp256k1_public_key(&[1; 32]), None);
+    }
+}
@@ -1,7 +1,39 @@
+use std::sync::Arc;
+
+use failure::Fail;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    deploy_hooks::{DeployHooks, NoOpDeployHooks},
+    module_cache::DEFAULT_MODULE_CACHE_CAPACITY,
+};
+
+/// Reasons for an [`EngineConfigBuilder`] to fail to load, store or build a configuration.
+#[derive(Fail, Debug)]
+pub enum EngineConfigError {
+    #[fail(display = "Malformed TOML engine config: {}", _0)]
+    TomlDeserialization(toml::de::Error),
+    #[fail(display = "Failed to serialize engine config to TOML: {}", _0)]
+    TomlSerialization(toml::ser::Error),
+    #[fail(display = "Malformed JSON engine config: {}", _0)]
+    JsonDeserialization(serde_json::Error),
+    #[fail(display = "Failed to serialize engine config to JSON: {}", _0)]
+    JsonSerialization(serde_json::Error),
+}
+
 /// The runtime configuration of the execution engine
 #[derive(Debug, Clone)]
 pub struct EngineConfig {
     // feature flags go here
+    profiling: bool,
+    deploy_hooks: Arc<dyn DeployHooks>,
+    reentrancy_guard: bool,
+    contract_logging: bool,
+    payment_write_guard: bool,
+    effects_by_phase: bool,
+    debug_print: bool,
+    unsigned_deploys: bool,
+    module_cache_capacity: usize,
 }
 
 impl EngineConfig {
@@ -9,10 +41,381 @@ impl EngineConfig {
     pub fn new() -> EngineConfig {
         Default::default()
     }
+
+    /// Returns a builder starting from the default parameters.
+    pub fn builder() -> EngineConfigBuilder {
+        EngineConfigBuilder::new()
+    }
+
+    /// Returns a builder starting from the parameters of this configuration, e.g. to serialize
+    /// them.
+    pub fn to_builder(&self) -> EngineConfigBuilder {
+        EngineConfigBuilder {
+            profiling: self.profiling,
+            deploy_hooks: Arc::clone(&self.deploy_hooks),
+            reentrancy_guard: self.reentrancy_guard,
+            contract_logging: self.contract_logging,
+            payment_write_guard: self.payment_write_guard,
+            effects_by_phase: self.effects_by_phase,
+            debug_print: self.debug_print,
+            unsigned_deploys: self.unsigned_deploys,
+            module_cache_capacity: self.module_cache_capacity,
+        }
+    }
+
+    /// Returns `true` if execution results should carry profiling data such as read stats and
+    /// host function profiles.
+    pub fn profiling_enabled(&self) -> bool {
+        self.profiling
+    }
+
+    /// Enables or disables collecting profiling data during deploy execution, see
+    /// [`ExecutionResult::read_stats`](super::execution_result::ExecutionResult::read_stats) and
+    /// [`ExecutionResult::host_function_profile`](super::execution_result::ExecutionResult::
+    /// host_function_profile).
+    pub fn with_profiling(mut self, profiling: bool) -> EngineConfig {
+        self.profiling = profiling;
+        self
+    }
+
+    /// Returns the hooks run during deploy execution.
+    pub fn deploy_hooks(&self) -> &dyn DeployHooks {
+        self.deploy_hooks.as_ref()
+    }
+
+    /// Sets the hooks run during deploy execution, replacing the default which accepts every
+    /// deploy.
+    pub fn with_deploy_hooks<H: DeployHooks + 'static>(mut self, deploy_hooks: H) -> EngineConfig {
+        self.deploy_hooks = Arc::new(deploy_hooks);
+        self
+    }
+
+    /// Returns `true` if calling a contract which is already on the call stack is an error.
+    pub fn reentrancy_guard_enabled(&self) -> bool {
+        self.reentrancy_guard
+    }
+
+    /// Enables or disables failing calls to contracts which are already on the call stack with
+    /// [`Error::Reentrancy`](crate::execution::Error::Reentrancy).
+    pub fn with_reentrancy_guard(mut self, reentrancy_guard: bool) -> EngineConfig {
+        self.reentrancy_guard = reentrancy_guard;
+        self
+    }
+
+    /// Returns `true` if messages passed to the `log` host function should be logged.
+    pub fn contract_logging_enabled(&self) -> bool {
+        self.contract_logging
+    }
+
+    /// Enables or disables logging the messages contracts pass to the `log` host function.  The
+    /// host function is free of charge either way, so this is meant for debugging and tracing
+    /// only.
+    pub fn with_contract_logging(mut self, contract_logging: bool) -> EngineConfig {
+        self.contract_logging = contract_logging;
+        self
+    }
+
+    /// Returns `true` if payment code may only write to purses.
+    pub fn payment_write_guard_enabled(&self) -> bool {
+        self.payment_write_guard
+    }
+
+    /// Enables or disables restricting the writes of payment code to purses, i.e. to named purses,
+    /// the payment purse and the balances the mint keeps for them.  Other writes, such as to the
+    /// account's named keys, local state or new URefs, fail with
+    /// [`Error::PaymentWriteForbidden`](crate::execution::Error::PaymentWriteForbidden), so that
+    /// payment code can't modify application state before the session code runs.  System contracts
+    /// called by payment code are not restricted.
+    pub fn with_payment_write_guard(mut self, payment_write_guard: bool) -> EngineConfig {
+        self.payment_write_guard = payment_write_guard;
+        self
+    }
+
+    /// Returns `true` if execution results should carry the effects of each deploy phase.
+    pub fn effects_by_phase_enabled(&self) -> bool {
+        self.effects_by_phase
+    }
+
+    /// Enables or disables keeping the effects of the payment, session and finalization phases of
+    /// each deploy, see
+    /// [`ExecutionResult::effects_by_phase`](super::execution_result::ExecutionResult::
+    /// effects_by_phase). They are meant for auditing and debugging, and are disabled by
+    /// default as they roughly double the memory held by each execution result.
+    pub fn with_effects_by_phase(mut self, effects_by_phase: bool) -> EngineConfig {
+        self.effects_by_phase = effects_by_phase;
+        self
+    }
+
+    /// Returns `true` if the text contracts pass to the `print` host function should be collected.
+    pub fn debug_print_enabled(&self) -> bool {
+        self.debug_print
+    }
+
+    /// Enables or disables collecting the text contracts pass to the `print` host function, see
+    /// [`ExecutionResult::stdout`](super::execution_result::ExecutionResult::stdout).  The host
+    /// function behaves the same either way, so this is meant for test harnesses only.
+    pub fn with_debug_print(mut self, debug_print: bool) -> EngineConfig {
+        self.debug_print = debug_print;
+        self
+    }
+
+    /// Returns `true` if deploys carrying no signatures should be accepted.
+    pub fn unsigned_deploys_enabled(&self) -> bool {
+        self.unsigned_deploys
+    }
+
+    /// Enables or disables accepting deploys which carry no signatures at all, see
+    /// [`DeployItem::signatures`](super::deploy_item::DeployItem::signatures).  Deploys carrying
+    /// signatures are verified either way.  This is meant for test harnesses only, whose accounts
+    /// have no secret keys.
+    pub fn with_unsigned_deploys(mut self, unsigned_deploys: bool) -> EngineConfig {
+        self.unsigned_deploys = unsigned_deploys;
+        self
+    }
+
+    /// Returns the number of deserialized contracts kept in the engine's module cache.
+    pub fn module_cache_capacity(&self) -> usize {
+        self.module_cache_capacity
+    }
+
+    /// Sets the number of deserialized contracts kept in the engine's module cache, see
+    /// [`ModuleCache`](super::module_cache::ModuleCache).  Calls to a cached contract skip
+    /// deserializing its Wasm bytes.  A capacity of zero disables the cache.
+    pub fn with_module_cache_capacity(mut self, module_cache_capacity: usize) -> EngineConfig {
+        self.module_cache_capacity = module_cache_capacity;
+        self
+    }
 }
 
 impl Default for EngineConfig {
     fn default() -> Self {
-        EngineConfig {}
+        EngineConfig {
+            profiling: false,
+            deploy_hooks: Arc::new(NoOpDeployHooks),
+            reentrancy_guard: false,
+            contract_logging: false,
+            payment_write_guard: false,
+            effects_by_phase: false,
+            debug_print: false,
+            unsigned_deploys: false,
+            module_cache_capacity: DEFAULT_MODULE_CACHE_CAPACITY,
+        }
+    }
+}
+
+/// A fluent builder of [`EngineConfig`], which validates the combination of options it is given.
+///
+/// The serde representation uses kebab-case keys, each of which may be omitted to keep its
+/// default, e.g. in TOML:
+///
+/// ```toml
+/// profiling = true
+/// module-cache-capacity = 64
+/// ```
+///
+/// Deploy hooks are code rather than data, so they are neither serialized nor deserialized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct EngineConfigBuilder {
+    profiling: bool,
+    #[serde(skip, default = "default_deploy_hooks")]
+    deploy_hooks: Arc<dyn DeployHooks>,
+    reentrancy_guard: bool,
+    contract_logging: bool,
+    payment_write_guard: bool,
+    effects_by_phase: bool,
+    debug_print: bool,
+    unsigned_deploys: bool,
+    module_cache_capacity: usize,
+}
+
+fn default_deploy_hooks() -> Arc<dyn DeployHooks> {
+    Arc::new(NoOpDeployHooks)
+}
+
+impl EngineConfigBuilder {
+    /// Creates a new builder with default parameters.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Deserializes the parameters of a builder from TOML.
+    pub fn from_toml(toml: &str) -> Result<Self, EngineConfigError> {
+        toml::from_str(toml).map_err(EngineConfigError::TomlDeserialization)
+    }
+
+    /// Serializes the parameters of this builder to TOML.
+    pub fn to_toml(&self) -> Result<String, EngineConfigError> {
+        toml::to_string(self).map_err(EngineConfigError::TomlSerialization)
+    }
+
+    /// Deserializes the parameters of a builder from JSON.
+    pub fn from_json(json: &str) -> Result<Self, EngineConfigError> {
+        serde_json::from_str(json).map_err(EngineConfigError::JsonDeserialization)
+    }
+
+    /// Serializes the parameters of this builder to JSON.
+    pub fn to_json(&self) -> Result<String, EngineConfigError> {
+        serde_json::to_string(self).map_err(EngineConfigError::JsonSerialization)
+    }
+
+    /// See [`EngineConfig::with_profiling`].
+    pub fn with_profiling(mut self, profiling: bool) -> Self {
+        self.profiling = profiling;
+        self
+    }
+
+    /// See [`EngineConfig::with_deploy_hooks`].
+    pub fn with_deploy_hooks<H: DeployHooks + 'static>(mut self, deploy_hooks: H) -> Self {
+        self.deploy_hooks = Arc::new(deploy_hooks);
+        self
+    }
+
+    /// See [`EngineConfig::with_reentrancy_guard`].
+    pub fn with_reentrancy_guard(mut self, reentrancy_guard: bool) -> Self {
+        self.reentrancy_guard = reentrancy_guard;
+        self
+    }
+
+    /// See [`EngineConfig::with_contract_logging`].
+    pub fn with_contract_logging(mut self, contract_logging: bool) -> Self {
+        self.contract_logging = contract_logging;
+        self
+    }
+
+    /// See [`EngineConfig::with_payment_write_guard`].
+    pub fn with_payment_write_guard(mut self, payment_write_guard: bool) -> Self {
+        self.payment_write_guard = payment_write_guard;
+        self
+    }
+
+    /// See [`EngineConfig::with_effects_by_phase`].
+    pub fn with_effects_by_phase(mut self, effects_by_phase: bool) -> Self {
+        self.effects_by_phase = effects_by_phase;
+        self
+    }
+
+    /// See [`EngineConfig::with_debug_print`].
+    pub fn with_debug_print(mut self, debug_print: bool) -> Self {
+        self.debug_print = debug_print;
+        self
+    }
+
+    /// See [`EngineConfig::with_unsigned_deploys`].
+    pub fn with_unsigned_deploys(mut self, unsigned_deploys: bool) -> Self {
+        self.unsigned_deploys = unsigned_deploys;
+        self
+    }
+
+    /// See [`EngineConfig::with_module_cache_capacity`].
+    pub fn with_module_cache_capacity(mut self, module_cache_capacity: usize) -> Self {
+        self.module_cache_capacity = module_cache_capacity;
+        self
+    }
+
+    /// Checks that the options are consistent with each other.  Every combination of the current
+    /// options is.
+    pub fn validate(&self) -> Result<(), EngineConfigError> {
+        Ok(())
+    }
+
+    /// Validates the options and builds the configuration.
+    pub fn build(self) -> Result<EngineConfig, EngineConfigError> {
+        self.validate()?;
+        Ok(EngineConfig {
+            profiling: self.profiling,
+            deploy_hooks: self.deploy_hooks,
+            reentrancy_guard: self.reentrancy_guard,
+            contract_logging: self.contract_logging,
+            payment_write_guard: self.payment_write_guard,
+            effects_by_phase: self.effects_by_phase,
+            debug_print: self.debug_print,
+            unsigned_deploys: self.unsigned_deploys,
+            module_cache_capacity: self.module_cache_capacity,
+        })
+    }
+}
+
+impl Default for EngineConfigBuilder {
+    fn default() -> Self {
+        EngineConfig::default().to_builder()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EngineConfig, EngineConfigBuilder, EngineConfigError};
+
+    #[test]
+    fn should_build_default_config() {
+        let config = EngineConfig::builder().build().expect("should build");
+        let default = EngineConfig::default();
+        assert_eq!(
+            config.reentrancy_guard_enabled(),
+            default.reentrancy_guard_enabled()
+        );
+        assert_eq!(
+            config.module_cache_capacity(),
+            default.module_cache_capacity()
+        );
+        assert!(!config.profiling_enabled());
+    }
+
+    #[test]
+    fn should_deserialize_partial_toml() {
+        let toml = r#"
+            profiling = true
+            reentrancy-guard = true
+        "#;
+        let config = EngineConfigBuilder::from_toml(toml)
+            .expect("should deserialize")
+            .build()
+            .expect("should build");
+        assert!(config.profiling_enabled());
+        assert!(config.reentrancy_guard_enabled());
+        assert!(!config.contract_logging_enabled());
+        assert_eq!(
+            config.module_cache_capacity(),
+            EngineConfig::default().module_cache_capacity()
+        );
+    }
+
+    #[test]
+    fn should_reject_unknown_keys() {
+        match EngineConfigBuilder::from_toml("turbo = true") {
+            Err(EngineConfigError::TomlDeserialization(_)) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_roundtrip_toml_and_json() {
+        let builder = EngineConfig::builder()
+            .with_profiling(true)
+            .with_effects_by_phase(true)
+            .with_module_cache_capacity(7);
+
+        let toml = builder.to_toml().expect("should serialize to TOML");
+        let from_toml = EngineConfigBuilder::from_toml(&toml).expect("should deserialize TOML");
+        let json = builder.to_json().expect("should serialize to JSON");
+        let from_json = EngineConfigBuilder::from_json(&json).expect("should deserialize JSON");
+
+        for config in vec![from_toml, from_json] {
+            let config = config.build().expect("should build");
+            assert!(config.profiling_enabled());
+            assert!(config.effects_by_phase_enabled());
+            assert_eq!(config.module_cache_capacity(), 7);
+        }
+    }
+
+    #[test]
+    fn should_reject_gas_metering_option() {
+        // Gas metering is a protocol parameter, as nodes metering differently would disagree on
+        // the gas charged for a deploy
+        let toml = r#"gas-metering = { type = "fuel", flush-threshold = 1000 }"#;
+        match EngineConfigBuilder::from_toml(toml) {
+            Err(EngineConfigError::TomlDeserialization(_)) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
     }
 }
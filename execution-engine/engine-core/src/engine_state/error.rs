@@ -1,9 +1,9 @@
 use failure::Fail;
 
-use engine_shared::newtypes::Blake2bHash;
-use types::{bytesrepr, system_contract_errors::mint};
+use engine_shared::{gas::Gas, newtypes::Blake2bHash};
+use types::{account::PublicKey, bytesrepr, system_contract_errors::mint};
 
-use crate::execution;
+use crate::{execution, DeployHash};
 use types::ProtocolVersion;
 
 #[derive(Fail, Debug)]
@@ -24,8 +24,35 @@ pub enum Error {
     ExecError(execution::Error),
     #[fail(display = "Storage error: {}", _0)]
     StorageError(engine_storage::error::Error),
-    #[fail(display = "Authorization failure: not authorized.")]
-    AuthorizationError,
+    #[fail(display = "Authorization failure: account {} not found", _0)]
+    AccountNotFound(PublicKey),
+    #[fail(display = "Authorization failure: deploy has no authorization keys")]
+    NoAuthorizationKeys,
+    #[fail(
+        display = "Authorization failure: keys {:?} are not associated with the account",
+        _0
+    )]
+    UnassociatedKeys(Vec<PublicKey>),
+    #[fail(
+        display = "Authorization failure: authorization keys of total weight {} are below the deployment threshold {}",
+        total_weight, threshold
+    )]
+    DeploymentThresholdNotMet { total_weight: u8, threshold: u8 },
+    #[fail(display = "Invalid deploy signature")]
+    InvalidDeploySignature,
+    #[fail(display = "Deploy carries no signatures")]
+    NoDeploySignatures,
+    #[fail(display = "Deploy rejected: {}", _0)]
+    DeployRejected(String),
+    #[fail(display = "Duplicate deploy hash: {}", _0)]
+    DuplicateDeployHash(DeployHash),
+    #[fail(
+        display = "Block gas limit exceeded: deploy costs {}, but only {} gas of the block remain",
+        cost, remaining
+    )]
+    BlockGasLimitExceeded { cost: Gas, remaining: Gas },
+    #[fail(display = "Invalid deploy item variant: {}", _0)]
+    InvalidDeployItemVariant(String),
     #[fail(display = "Insufficient payment")]
     InsufficientPaymentError,
     #[fail(display = "Deploy error")]
@@ -34,10 +61,17 @@ pub enum Error {
     FinalizationError,
     #[fail(display = "Missing system contract association: {}", _0)]
     MissingSystemContractError(String),
+    #[fail(display = "Missing installer of system contract: {}", _0)]
+    MissingInstaller(String),
     #[fail(display = "Serialization error: {}", _0)]
     SerializationError(bytesrepr::Error),
     #[fail(display = "Mint error: {}", _0)]
     MintError(mint::Error),
+    #[fail(
+        display = "Compacted storage has {} missing or corrupt tries under root {}",
+        tries, root
+    )]
+    CorruptCompactedStorage { root: Blake2bHash, tries: usize },
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
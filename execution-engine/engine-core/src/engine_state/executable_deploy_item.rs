@@ -1,3 +1,20 @@
+use engine_shared::account::Account;
+use types::{account::PublicKey, ContractVersion, Key, KEY_HASH_LENGTH, U512};
+
+/// The function called by deploy items which don't name an entry point.
+pub const DEFAULT_ENTRY_POINT_NAME: &str = "call";
+
+/// The context in which stored code called by a deploy item executes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutionContext {
+    /// The code executes with the base key and named keys of the deploying account, as if it had
+    /// been sent as module bytes, so it may use the account's main purse and named keys.
+    Account,
+    /// The code executes with the base key and named keys of the called contract, as if it had
+    /// been called by another contract, so it can't use the account's main purse or named keys.
+    Contract,
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub enum ExecutableDeployItem {
     ModuleBytes {
@@ -16,6 +33,32 @@ pub enum ExecutableDeployItem {
         uref: Vec<u8>,
         args: Vec<u8>,
     },
+    /// Calls `entry_point` of the given version of the contract package stored under `hash`, or
+    /// of its highest enabled version if `version` is `None`.
+    StoredVersionedContractByHash {
+        hash: Vec<u8>,
+        version: Option<ContractVersion>,
+        entry_point: String,
+        args: Vec<u8>,
+        context: ExecutionContext,
+    },
+    /// Like `StoredVersionedContractByHash`, with the hash of the package held by the named key
+    /// `name` of the deploying account.
+    StoredVersionedContractByName {
+        name: String,
+        version: Option<ContractVersion>,
+        entry_point: String,
+        args: Vec<u8>,
+        context: ExecutionContext,
+    },
+    /// Transfers `amount` motes from the main purse of the deploying account to the main purse of
+    /// the account `target`, creating that account if needed.
+    ///
+    /// Only valid as session code, where it is executed natively rather than as Wasm.
+    Transfer {
+        target: PublicKey,
+        amount: U512,
+    },
 }
 
 impl ExecutableDeployItem {
@@ -25,6 +68,48 @@ impl ExecutableDeployItem {
             ExecutableDeployItem::StoredContractByHash { args, .. } => args,
             ExecutableDeployItem::StoredContractByName { args, .. } => args,
             ExecutableDeployItem::StoredContractByURef { args, .. } => args,
+            ExecutableDeployItem::StoredVersionedContractByHash { args, .. } => args,
+            ExecutableDeployItem::StoredVersionedContractByName { args, .. } => args,
+            ExecutableDeployItem::Transfer { .. } => Vec::new(),
+        }
+    }
+
+    /// Returns the name of the function called by the deploy item.
+    pub fn entry_point_name(&self) -> &str {
+        match self {
+            ExecutableDeployItem::StoredVersionedContractByHash { entry_point, .. }
+            | ExecutableDeployItem::StoredVersionedContractByName { entry_point, .. } => {
+                entry_point
+            }
+            _ => DEFAULT_ENTRY_POINT_NAME,
+        }
+    }
+
+    /// Returns the context in which the code called by the deploy item executes.  Only calls to
+    /// versioned contracts may execute in the context of the contract.
+    pub fn execution_context(&self) -> ExecutionContext {
+        match self {
+            ExecutableDeployItem::StoredVersionedContractByHash { context, .. }
+            | ExecutableDeployItem::StoredVersionedContractByName { context, .. } => *context,
+            _ => ExecutionContext::Account,
+        }
+    }
+
+    /// Returns the hash of the stored contract called by the deploy item, if it is called by hash
+    /// or by a named key of `account` holding a [`Key::Hash`].
+    pub fn stored_contract_hash(&self, account: &Account) -> Option<[u8; KEY_HASH_LENGTH]> {
+        match self {
+            ExecutableDeployItem::StoredContractByHash { hash, .. }
+                if hash.len() == KEY_HASH_LENGTH =>
+            {
+                let mut ret = [0u8; KEY_HASH_LENGTH];
+                ret.copy_from_slice(hash);
+                Some(ret)
+            }
+            ExecutableDeployItem::StoredContractByName { name, .. } => {
+                account.named_keys().get(name).and_then(Key::as_hash)
+            }
+            _ => None,
         }
     }
 }
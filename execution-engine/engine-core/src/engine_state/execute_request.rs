@@ -8,6 +8,8 @@ use super::{deploy_item::DeployItem, execution_result::ExecutionResult};
 pub struct ExecuteRequest {
     pub parent_state_hash: Blake2bHash,
     pub block_time: u64,
+    pub era_id: u64,
+    pub block_height: u64,
     pub deploys: Vec<Result<DeployItem, ExecutionResult>>,
     pub protocol_version: ProtocolVersion,
 }
@@ -16,12 +18,16 @@ impl ExecuteRequest {
     pub fn new(
         parent_state_hash: Blake2bHash,
         block_time: u64,
+        era_id: u64,
+        block_height: u64,
         deploys: Vec<Result<DeployItem, ExecutionResult>>,
         protocol_version: ProtocolVersion,
     ) -> Self {
         Self {
             parent_state_hash,
             block_time,
+            era_id,
+            block_height,
             deploys,
             protocol_version,
         }
@@ -37,6 +43,8 @@ impl Default for ExecuteRequest {
         Self {
             parent_state_hash: [0u8; 32].into(),
             block_time: 0,
+            era_id: 0,
+            block_height: 0,
             deploys: vec![],
             protocol_version: Default::default(),
         }
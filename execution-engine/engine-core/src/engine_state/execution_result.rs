@@ -1,11 +1,14 @@
-use super::{error, execution_effect::ExecutionEffect, op::Op, CONV_RATE};
+use super::{error, execution_effect::ExecutionEffect, op::Op};
 use engine_shared::{
     additive_map::AdditiveMap, gas::Gas, motes::Motes, newtypes::CorrelationId,
     stored_value::StoredValue, transform::Transform,
 };
 use engine_storage::global_state::StateReader;
+use num_traits::Zero;
 use types::{CLValue, Key};
 
+use crate::{execution::HostFunctionProfile, tracking_copy::ReadStats};
+
 fn make_payment_error_effects(
     max_payment_cost: Motes,
     account_main_purse_balance: Motes,
@@ -32,12 +35,53 @@ fn make_payment_error_effects(
     ops.insert(rewards_purse_normalize, Op::Add);
     transforms.insert(
         rewards_purse_normalize,
-        Transform::AddUInt512(max_payment_cost.value()),
+        Transform::AddUInt512(max_payment_cost.into()),
     );
 
     ExecutionEffect::new(ops, transforms)
 }
 
+/// The effects of the payment, session and finalization phases of a deploy, as produced by each
+/// phase before they are merged into the effect of the deploy.
+///
+/// Unlike the merged effect, these include the effects of phases whose effects are discarded, e.g.
+/// of failed session code.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EffectsByPhase {
+    payment: ExecutionEffect,
+    session: ExecutionEffect,
+    finalize: ExecutionEffect,
+}
+
+impl EffectsByPhase {
+    pub fn new(
+        payment: ExecutionEffect,
+        session: ExecutionEffect,
+        finalize: ExecutionEffect,
+    ) -> Self {
+        EffectsByPhase {
+            payment,
+            session,
+            finalize,
+        }
+    }
+
+    /// Returns the effect of the payment code.
+    pub fn payment(&self) -> &ExecutionEffect {
+        &self.payment
+    }
+
+    /// Returns the effect of the session code.
+    pub fn session(&self) -> &ExecutionEffect {
+        &self.session
+    }
+
+    /// Returns the effect of finalizing the payment.
+    pub fn finalize(&self) -> &ExecutionEffect {
+        &self.finalize
+    }
+}
+
 #[derive(Debug)]
 pub enum ExecutionResult {
     /// An error condition that happened during execution
@@ -45,9 +89,20 @@ pub enum ExecutionResult {
         error: error::Error,
         effect: ExecutionEffect,
         cost: Gas,
+        read_stats: Option<ReadStats>,
+        effects_by_phase: Option<EffectsByPhase>,
+        stdout: Vec<String>,
+        host_function_profile: Option<HostFunctionProfile>,
     },
     /// Execution was finished successfully
-    Success { effect: ExecutionEffect, cost: Gas },
+    Success {
+        effect: ExecutionEffect,
+        cost: Gas,
+        read_stats: Option<ReadStats>,
+        effects_by_phase: Option<EffectsByPhase>,
+        stdout: Vec<String>,
+        host_function_profile: Option<HostFunctionProfile>,
+    },
 }
 
 pub enum ForcedTransferResult {
@@ -66,6 +121,10 @@ impl ExecutionResult {
             error,
             effect: Default::default(),
             cost: Gas::default(),
+            read_stats: None,
+            effects_by_phase: None,
+            stdout: Vec::new(),
+            host_function_profile: None,
         }
     }
 
@@ -86,7 +145,7 @@ impl ExecutionResult {
     pub fn has_precondition_failure(&self) -> bool {
         match self {
             ExecutionResult::Failure { cost, effect, .. } => {
-                cost.value() == 0.into() && *effect == Default::default()
+                cost.is_zero() && *effect == Default::default()
             }
             ExecutionResult::Success { .. } => false,
         }
@@ -106,25 +165,273 @@ impl ExecutionResult {
         }
     }
 
+    /// Returns the read stats collected while executing, if profiling was enabled.
+    pub fn read_stats(&self) -> Option<ReadStats> {
+        match self {
+            ExecutionResult::Failure { read_stats, .. } => *read_stats,
+            ExecutionResult::Success { read_stats, .. } => *read_stats,
+        }
+    }
+
     pub fn with_cost(self, cost: Gas) -> Self {
         match self {
-            ExecutionResult::Failure { error, effect, .. } => ExecutionResult::Failure {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+                ..
+            } => ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+            },
+            ExecutionResult::Success {
+                effect,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
             },
-            ExecutionResult::Success { effect, .. } => ExecutionResult::Success { effect, cost },
         }
     }
 
     pub fn with_effect(self, effect: ExecutionEffect) -> Self {
         match self {
-            ExecutionResult::Failure { error, cost, .. } => ExecutionResult::Failure {
+            ExecutionResult::Failure {
+                error,
+                cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+                ..
+            } => ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+            },
+            ExecutionResult::Success {
+                cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+            },
+        }
+    }
+
+    pub fn with_read_stats(self, read_stats: Option<ReadStats>) -> Self {
+        match self {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+                ..
+            } => ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+            },
+            ExecutionResult::Success {
+                effect,
+                cost,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+            },
+        }
+    }
+
+    /// Returns the effects of each phase of the deploy, if keeping them was enabled.
+    pub fn effects_by_phase(&self) -> Option<&EffectsByPhase> {
+        match self {
+            ExecutionResult::Failure {
+                effects_by_phase, ..
+            } => effects_by_phase.as_ref(),
+            ExecutionResult::Success {
+                effects_by_phase, ..
+            } => effects_by_phase.as_ref(),
+        }
+    }
+
+    pub fn with_effects_by_phase(self, effects_by_phase: Option<EffectsByPhase>) -> Self {
+        match self {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                read_stats,
+                stdout,
+                host_function_profile,
+                ..
+            } => ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+            },
+            ExecutionResult::Success {
+                effect,
+                cost,
+                read_stats,
+                stdout,
+                host_function_profile,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+            },
+        }
+    }
+
+    /// Returns the text printed by the executed code, in order.  Always empty unless the engine is
+    /// configured to collect it, see
+    /// [`EngineConfig::with_debug_print`](super::EngineConfig::with_debug_print).
+    pub fn stdout(&self) -> &[String] {
+        match self {
+            ExecutionResult::Failure { stdout, .. } => stdout,
+            ExecutionResult::Success { stdout, .. } => stdout,
+        }
+    }
+
+    pub fn with_stdout(self, stdout: Vec<String>) -> Self {
+        match self {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                read_stats,
+                effects_by_phase,
+                host_function_profile,
+                ..
+            } => ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+            },
+            ExecutionResult::Success {
+                effect,
+                cost,
+                read_stats,
+                effects_by_phase,
+                host_function_profile,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+            },
+        }
+    }
+
+    /// Returns the counters of the host function calls made while executing, if profiling was
+    /// enabled.
+    pub fn host_function_profile(&self) -> Option<&HostFunctionProfile> {
+        match self {
+            ExecutionResult::Failure {
+                host_function_profile,
+                ..
+            } => host_function_profile.as_ref(),
+            ExecutionResult::Success {
+                host_function_profile,
+                ..
+            } => host_function_profile.as_ref(),
+        }
+    }
+
+    pub fn with_host_function_profile(
+        self,
+        host_function_profile: Option<HostFunctionProfile>,
+    ) -> Self {
+        match self {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                ..
+            } => ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
+            },
+            ExecutionResult::Success {
+                effect,
+                cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                read_stats,
+                effects_by_phase,
+                stdout,
+                host_function_profile,
             },
-            ExecutionResult::Success { cost, .. } => ExecutionResult::Success { effect, cost },
         }
     }
 
@@ -147,10 +454,11 @@ impl ExecutionResult {
     pub fn check_forced_transfer(
         &self,
         payment_purse_balance: Motes,
+        conv_rate: u64,
     ) -> Option<ForcedTransferResult> {
-        let payment_result_cost = match Motes::from_gas(self.cost(), CONV_RATE) {
+        let payment_result_cost = match Motes::from_gas(self.cost(), conv_rate) {
             Some(cost) => cost,
-            // Multiplying cost by CONV_RATE overflowed the U512 range
+            // Multiplying cost by conv_rate overflowed the U512 range
             None => return Some(ForcedTransferResult::InsufficientPayment),
         };
         // payment_code_spec_3_b_ii: if (balance of PoS pay purse) < (gas spent during
@@ -179,6 +487,7 @@ impl ExecutionResult {
         account_main_purse_balance: Motes,
         account_main_purse: Key,
         rewards_purse: Key,
+        conv_rate: u64,
     ) -> ExecutionResult {
         let effect = make_payment_error_effects(
             max_payment_cost,
@@ -186,11 +495,15 @@ impl ExecutionResult {
             account_main_purse,
             rewards_purse,
         );
-        let cost = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
+        let cost = Gas::from_motes(max_payment_cost, conv_rate).unwrap_or_default();
         ExecutionResult::Failure {
             error,
             effect,
             cost,
+            read_stats: None,
+            effects_by_phase: None,
+            stdout: Vec::new(),
+            host_function_profile: None,
         }
     }
 }
@@ -206,6 +519,7 @@ pub struct ExecutionResultBuilder {
     payment_execution_result: Option<ExecutionResult>,
     session_execution_result: Option<ExecutionResult>,
     finalize_execution_result: Option<ExecutionResult>,
+    keep_effects_by_phase: bool,
 }
 
 impl Default for ExecutionResultBuilder {
@@ -214,6 +528,7 @@ impl Default for ExecutionResultBuilder {
             payment_execution_result: None,
             session_execution_result: None,
             finalize_execution_result: None,
+            keep_effects_by_phase: false,
         }
     }
 }
@@ -244,18 +559,19 @@ impl ExecutionResultBuilder {
         self
     }
 
+    /// Sets whether the built result should carry the effects of each phase, see
+    /// [`ExecutionResult::effects_by_phase`].
+    pub fn keep_effects_by_phase(&mut self, keep_effects_by_phase: bool) -> &mut Self {
+        self.keep_effects_by_phase = keep_effects_by_phase;
+        self
+    }
+
     pub fn total_cost(&self) -> Gas {
-        let payment_cost = self
-            .payment_execution_result
-            .as_ref()
+        self.payment_execution_result
+            .iter()
+            .chain(self.session_execution_result.iter())
             .map(ExecutionResult::cost)
-            .unwrap_or_default();
-        let session_cost = self
-            .session_execution_result
-            .as_ref()
-            .map(ExecutionResult::cost)
-            .unwrap_or_default();
-        payment_cost + session_cost
+            .sum()
     }
 
     pub fn build<R: StateReader<Key, StoredValue>>(
@@ -264,18 +580,28 @@ impl ExecutionResultBuilder {
         correlation_id: CorrelationId,
     ) -> Result<ExecutionResult, ExecutionResultBuilderError> {
         let cost = self.total_cost();
+        let effects_by_phase = self.effects_by_phase();
+        let stdout = self.stdout();
+        let host_function_profile = self.host_function_profile();
         let mut ops = AdditiveMap::new();
         let mut transforms = AdditiveMap::new();
 
         let mut ret: ExecutionResult = ExecutionResult::Success {
             effect: Default::default(),
             cost,
+            read_stats: None,
+            effects_by_phase: None,
+            stdout: Vec::new(),
+            host_function_profile: None,
         };
 
         match self.payment_execution_result {
             Some(result) => {
                 if result.is_failure() {
-                    return Ok(result);
+                    return Ok(result
+                        .with_effects_by_phase(effects_by_phase)
+                        .with_stdout(stdout)
+                        .with_host_function_profile(host_function_profile));
                 } else {
                     Self::add_effects(&mut ops, &mut transforms, result.effect());
                 }
@@ -302,7 +628,10 @@ impl ExecutionResultBuilder {
                     // payment_code_spec_5_a: FinalizationError should only ever be raised here
                     return Ok(ExecutionResult::precondition_failure(
                         error::Error::FinalizationError,
-                    ));
+                    )
+                    .with_effects_by_phase(effects_by_phase)
+                    .with_stdout(stdout)
+                    .with_host_function_profile(host_function_profile));
                 } else {
                     Self::add_effects(&mut ops, &mut transforms, result.effect());
                 }
@@ -313,7 +642,48 @@ impl ExecutionResultBuilder {
         // Remove redundant writes to allow more opportunity to commute
         let reduced_effect = Self::reduce_identity_writes(ops, transforms, reader, correlation_id);
 
-        Ok(ret.with_effect(reduced_effect))
+        Ok(ret
+            .with_effect(reduced_effect)
+            .with_effects_by_phase(effects_by_phase)
+            .with_stdout(stdout)
+            .with_host_function_profile(host_function_profile))
+    }
+
+    /// Concatenates the text printed in each phase, which is kept even if a phase fails.
+    fn stdout(&self) -> Vec<String> {
+        self.payment_execution_result
+            .iter()
+            .chain(self.session_execution_result.iter())
+            .chain(self.finalize_execution_result.iter())
+            .flat_map(|result| result.stdout().iter().cloned())
+            .collect()
+    }
+
+    /// Sums up the host function calls of each phase, including failed ones.
+    fn host_function_profile(&self) -> Option<HostFunctionProfile> {
+        self.payment_execution_result
+            .iter()
+            .chain(self.session_execution_result.iter())
+            .chain(self.finalize_execution_result.iter())
+            .filter_map(|result| result.host_function_profile().cloned())
+            .fold(None, |sum, profile| Some(sum.unwrap_or_default() + profile))
+    }
+
+    fn effects_by_phase(&self) -> Option<EffectsByPhase> {
+        if !self.keep_effects_by_phase {
+            return None;
+        }
+        let effect_of = |result: &Option<ExecutionResult>| {
+            result
+                .as_ref()
+                .map(|result| result.effect().clone())
+                .unwrap_or_default()
+        };
+        Some(EffectsByPhase::new(
+            effect_of(&self.payment_execution_result),
+            effect_of(&self.session_execution_result),
+            effect_of(&self.finalize_execution_result),
+        ))
     }
 
     fn add_effects(
@@ -6,7 +6,9 @@ use rand::{
     Rng,
 };
 
-use engine_shared::{motes::Motes, newtypes::Blake2bHash, transform::TypeMismatch};
+use engine_shared::{
+    account::VestingSchedule, motes::Motes, newtypes::Blake2bHash, transform::TypeMismatch,
+};
 use engine_storage::global_state::CommitResult;
 use engine_wasm_prep::wasm_costs::WasmCosts;
 use types::{account::PublicKey, bytesrepr, Key, ProtocolVersion, U512};
@@ -16,6 +18,8 @@ use crate::engine_state::execution_effect::ExecutionEffect;
 pub const POS_BONDING_PURSE: &str = "pos_bonding_purse";
 pub const POS_PAYMENT_PURSE: &str = "pos_payment_purse";
 pub const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
+pub const POS_UNBONDING_DELAY: &str = "pos_unbonding_delay";
+pub const POS_SLASH_PERCENTAGE: &str = "pos_slash_percentage";
 
 pub enum GenesisResult {
     RootNotFound,
@@ -65,6 +69,7 @@ pub struct GenesisAccount {
     public_key: PublicKey,
     balance: Motes,
     bonded_amount: Motes,
+    vesting_schedule: Option<VestingSchedule>,
 }
 
 impl GenesisAccount {
@@ -73,9 +78,16 @@ impl GenesisAccount {
             public_key,
             balance,
             bonded_amount,
+            vesting_schedule: None,
         }
     }
 
+    /// Locks part of the balance of the account according to the given schedule.
+    pub fn with_vesting_schedule(mut self, vesting_schedule: VestingSchedule) -> Self {
+        self.vesting_schedule = Some(vesting_schedule);
+        self
+    }
+
     pub fn public_key(&self) -> PublicKey {
         self.public_key
     }
@@ -87,6 +99,10 @@ impl GenesisAccount {
     pub fn bonded_amount(&self) -> Motes {
         self.bonded_amount
     }
+
+    pub fn vesting_schedule(&self) -> Option<VestingSchedule> {
+        self.vesting_schedule
+    }
 }
 
 impl Distribution<GenesisAccount> for Standard {
@@ -95,15 +111,36 @@ impl Distribution<GenesisAccount> for Standard {
 
         let mut u512_array = [0u8; 64];
         rng.fill_bytes(u512_array.as_mut());
-        let balance = Motes::new(U512::from(u512_array.as_ref()));
+        let first = Motes::new(U512::from(u512_array.as_ref()));
 
         rng.fill_bytes(u512_array.as_mut());
-        let bonded_amount = Motes::new(U512::from(u512_array.as_ref()));
+        let second = Motes::new(U512::from(u512_array.as_ref()));
+
+        // The bonded amount of a valid account doesn't exceed its balance
+        let (bonded_amount, balance) = if first <= second {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        // A random schedule which locks at most the whole balance
+        let vesting_schedule = if rng.gen() {
+            rng.fill_bytes(u512_array.as_mut());
+            let random = U512::from(u512_array.as_ref());
+            let locked_amount = match balance.value().checked_add(U512::one()) {
+                Some(modulus) => random % modulus,
+                None => random,
+            };
+            Some(VestingSchedule::new(rng.gen(), rng.gen(), locked_amount))
+        } else {
+            None
+        };
 
         GenesisAccount {
             public_key,
             balance,
             bonded_amount,
+            vesting_schedule,
         }
     }
 }
@@ -117,9 +154,12 @@ pub struct GenesisConfig {
     proof_of_stake_installer_bytes: Vec<u8>,
     accounts: Vec<GenesisAccount>,
     wasm_costs: WasmCosts,
+    unbonding_delay: u64,
+    slash_percentage: u8,
 }
 
 impl GenesisConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         name: String,
         timestamp: u64,
@@ -128,6 +168,8 @@ impl GenesisConfig {
         proof_of_stake_installer_bytes: Vec<u8>,
         accounts: Vec<GenesisAccount>,
         wasm_costs: WasmCosts,
+        unbonding_delay: u64,
+        slash_percentage: u8,
     ) -> Self {
         GenesisConfig {
             name,
@@ -137,6 +179,8 @@ impl GenesisConfig {
             proof_of_stake_installer_bytes,
             accounts,
             wasm_costs,
+            unbonding_delay,
+            slash_percentage,
         }
     }
 
@@ -164,6 +208,18 @@ impl GenesisConfig {
         self.wasm_costs
     }
 
+    /// Returns the time, in block time units, from an unbonding request until the stakes are paid
+    /// out by the proof-of-stake contract.
+    pub fn unbonding_delay(&self) -> u64 {
+        self.unbonding_delay
+    }
+
+    /// Returns the percentage of the stakes taken from a validator by the proof-of-stake contract
+    /// when it is slashed.
+    pub fn slash_percentage(&self) -> u8 {
+        self.slash_percentage
+    }
+
     pub fn get_bonded_validators(&self) -> impl Iterator<Item = (PublicKey, Motes)> + '_ {
         let zero = Motes::zero();
         self.accounts.iter().filter_map(move |genesis_account| {
@@ -220,8 +276,20 @@ impl Distribution<GenesisConfig> for Standard {
             max_stack_height: rng.gen(),
             opcodes_mul: rng.gen(),
             opcodes_div: rng.gen(),
+            storage_write_byte: rng.gen(),
+            read_cost: rng.gen(),
+            write_cost: rng.gen(),
+            add_cost: rng.gen(),
+            hash_base: rng.gen(),
+            hash_byte: rng.gen(),
+            max_memory: rng.gen(),
+            max_table_size: rng.gen(),
         };
 
+        let unbonding_delay = rng.gen();
+
+        let slash_percentage = rng.gen_range(0, 101);
+
         GenesisConfig {
             name,
             timestamp,
@@ -230,6 +298,8 @@ impl Distribution<GenesisConfig> for Standard {
             proof_of_stake_installer_bytes,
             accounts,
             wasm_costs,
+            unbonding_delay,
+            slash_percentage,
         }
     }
 }
@@ -1,3 +1,7 @@
+pub mod balance;
+pub mod block_info;
+pub mod chainspec;
+pub mod deploy_hooks;
 pub mod deploy_item;
 pub mod engine_config;
 mod error;
@@ -6,81 +10,158 @@ pub mod execute_request;
 pub mod execution_effect;
 pub mod execution_result;
 pub mod genesis;
+pub mod module_cache;
+pub mod native_transfer;
 pub mod op;
+pub mod protocol_version_info;
 pub mod query;
-pub mod system_contract_cache;
+#[cfg(feature = "rent")]
+pub mod rent;
+pub mod system_call_result;
 pub mod upgrade;
 pub mod utils;
 
 use std::{
     cell::RefCell,
-    collections::{BTreeMap, BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    path::Path,
     rc::Rc,
+    time::Instant,
 };
 
 use num_traits::Zero;
 use parity_wasm::elements::Module;
+use tracing::info_span;
 
 use contract::args_parser::ArgsParser;
 use engine_shared::{
     account::Account,
     additive_map::AdditiveMap,
+    contract_package::ContractHeader,
     gas::Gas,
+    metrics,
     motes::Motes,
     newtypes::{Blake2bHash, CorrelationId},
     stored_value::StoredValue,
     transform::Transform,
 };
+#[cfg(feature = "rent")]
+use engine_storage::global_state::KeysReader;
 use engine_storage::{
-    global_state::{CommitResult, StateProvider, StateReader},
-    protocol_data::ProtocolData,
+    global_state::{
+        lmdb::{LmdbGlobalState, LmdbStorageConfig},
+        CommitResult, KeysPage, StateProvider, StateReader, TrieStats, TrieStatsMode,
+    },
+    protocol_data::{
+        self, ChainParameters, ProtocolData, Sponsorship, DEFAULT_CONV_RATE, DEFAULT_MAX_PAYMENT,
+    },
+    transaction_source::lmdb::CompactionProgress,
 };
-use engine_wasm_prep::{wasm_costs::WasmCosts, Preprocessor};
+use engine_wasm_prep::{contract_metadata, wasm_costs::WasmCosts, Preprocessor};
 use types::{
     account::{PublicKey, PurseId},
-    bytesrepr::ToBytes,
+    bytesrepr::{FromBytes, ToBytes},
     system_contract_errors::mint,
-    AccessRights, BlockTime, Key, Phase, ProtocolVersion, URef, KEY_HASH_LENGTH, U512,
-    UREF_ADDR_LENGTH,
+    AccessRights, BlockTime, CLTyped, CLValue, Key, KeyTag, Phase, ProtocolVersion, URef,
+    KEY_HASH_LENGTH, U512, UREF_ADDR_LENGTH,
 };
 
 use self::{
-    deploy_item::DeployItem,
-    executable_deploy_item::ExecutableDeployItem,
-    execution_result::{ExecutionResult, ForcedTransferResult},
+    block_info::BlockInfo,
+    deploy_hooks::{DeployHookContext, TrackingCopyView},
+    deploy_item::{verify_signatures, DeployItem},
+    executable_deploy_item::{ExecutableDeployItem, ExecutionContext},
+    execution_result::{EffectsByPhase, ExecutionResult, ForcedTransferResult},
     genesis::{GenesisAccount, GenesisConfig, GenesisResult, POS_PAYMENT_PURSE, POS_REWARDS_PURSE},
-    system_contract_cache::SystemContractCache,
+    module_cache::{ModuleCache, ModuleCacheStats},
+    protocol_version_info::ProtocolVersionInfo,
 };
 pub use self::{
-    engine_config::EngineConfig,
+    engine_config::{EngineConfig, EngineConfigBuilder, EngineConfigError},
     error::{Error, RootNotFound},
 };
+#[cfg(feature = "rent")]
+use crate::engine_state::{
+    op::Op,
+    rent::{self, RentResult, RentStatus},
+};
+pub use crate::tracking_copy::ReadStats;
 use crate::{
     engine_state::{
+        balance::{BalanceRequest, BalanceResult},
         error::Error::MissingSystemContractError,
         execute_request::ExecuteRequest,
-        query::{QueryRequest, QueryResult},
+        execution_effect::ExecutionEffect,
+        query::{ContractMetadataResult, QueryRequest, QueryResult},
+        system_call_result::SystemCallResult,
         upgrade::{UpgradeConfig, UpgradeResult},
     },
     execution::{self, AddressGenerator, Executor, MINT_NAME, POS_NAME},
+    resolvers::{abi_version::GAS_FUNCTION_NAME, host_function_table},
     tracking_copy::{TrackingCopy, TrackingCopyExt},
     KnownKeys,
 };
 
-// TODO?: MAX_PAYMENT && CONV_RATE values are currently arbitrary w/ real values
-// TBD gas * CONV_RATE = motes
-pub const MAX_PAYMENT: u64 = 10_000_000;
-pub const CONV_RATE: u64 = 10;
+/// The max payment of protocol versions which don't configure [`ChainParameters`].
+pub const MAX_PAYMENT: u64 = DEFAULT_MAX_PAYMENT;
+/// The conversion rate of gas to motes of protocol versions which don't configure
+/// [`ChainParameters`].
+pub const CONV_RATE: u64 = DEFAULT_CONV_RATE;
 
 pub const SYSTEM_ACCOUNT_ADDR: [u8; 32] = [0u8; 32];
 
+/// The maximum number of keys returned by [`EngineState::keys_with_prefix`] at once.
+pub const MAX_KEYS_PAGE_SIZE: usize = 1_000;
+
 const GENESIS_INITIAL_BLOCKTIME: u64 = 0;
 const MINT_METHOD_NAME: &str = "mint";
 
+/// Writes the bytes of a system contract installer to global state under the hash of the bytes,
+/// and returns that hash.
+fn write_installer<R>(
+    tracking_copy: &mut TrackingCopy<R>,
+    bytes: &[u8],
+) -> Result<Blake2bHash, Error>
+where
+    R: StateReader<Key, StoredValue>,
+{
+    let hash = Blake2bHash::new(bytes);
+    let value = CLValue::from_t(bytes.to_vec()).map_err(execution::Error::from)?;
+    tracking_copy.write(Key::Hash(hash.into()), StoredValue::CLValue(value));
+    Ok(hash)
+}
+
+/// Reads the bytes of the installer of the system contract registered under `name`, as written by
+/// [`write_installer`] at genesis.
+fn read_installer<R>(
+    tracking_copy: &mut TrackingCopy<R>,
+    correlation_id: CorrelationId,
+    protocol_data: &ProtocolData,
+    name: &str,
+) -> Result<Vec<u8>, Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    let missing_installer = || Error::MissingInstaller(name.to_string());
+    let hash = protocol_data
+        .installer(name)
+        .ok_or_else(missing_installer)?;
+    match tracking_copy
+        .read(correlation_id, &Key::Hash(hash.into()))
+        .map_err(|error| Error::ExecError(error.into()))?
+    {
+        Some(StoredValue::CLValue(cl_value)) => cl_value
+            .into_t()
+            .map_err(|error| Error::ExecError(error.into())),
+        _ => Err(missing_installer()),
+    }
+}
+
 #[derive(Debug)]
 pub struct EngineState<S> {
     config: EngineConfig,
-    system_contract_cache: SystemContractCache,
+    module_cache: ModuleCache,
     state: S,
 }
 
@@ -90,10 +171,10 @@ where
     S::Error: Into<execution::Error>,
 {
     pub fn new(state: S, config: EngineConfig) -> EngineState<S> {
-        let system_contract_cache = Default::default();
+        let module_cache = ModuleCache::new(config.module_cache_capacity());
         EngineState {
             config,
-            system_contract_cache,
+            module_cache,
             state,
         }
     }
@@ -102,6 +183,10 @@ where
         &self.config
     }
 
+    pub fn module_cache(&self) -> &ModuleCache {
+        &self.module_cache
+    }
+
     pub fn wasm_costs(
         &self,
         protocol_version: ProtocolVersion,
@@ -112,6 +197,17 @@ where
         }
     }
 
+    /// Returns the limits on the payment and the gas of deploys in effect at `protocol_version`.
+    pub fn get_chain_parameters(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<ChainParameters>, Error> {
+        match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => Ok(Some(*protocol_data.chain_parameters())),
+            None => Ok(None),
+        }
+    }
+
     pub fn get_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -123,15 +219,29 @@ where
         }
     }
 
+    /// Lists every protocol version with stored protocol data, in ascending order.
+    pub fn list_protocol_versions(&self) -> Result<Vec<ProtocolVersionInfo>, Error> {
+        let all_protocol_data = self
+            .state
+            .list_protocol_data()
+            .map_err(|error| Error::ExecError(error.into()))?;
+        all_protocol_data
+            .iter()
+            .map(|(protocol_version, protocol_data)| {
+                ProtocolVersionInfo::new(*protocol_version, protocol_data).map_err(Error::from)
+            })
+            .collect()
+    }
+
     pub fn commit_genesis(
         &self,
         correlation_id: CorrelationId,
         genesis_config: GenesisConfig,
     ) -> Result<GenesisResult, Error> {
         // Preliminaries
-        let executor = Executor;
-        let blocktime = BlockTime::new(GENESIS_INITIAL_BLOCKTIME);
-        let gas_limit = Gas::new(std::u64::MAX.into());
+        let executor = Executor::new(self.config.clone());
+        let block_info = BlockInfo::new(BlockTime::new(GENESIS_INITIAL_BLOCKTIME), 0, 0);
+        let gas_limit = Gas::from(std::u64::MAX);
         let phase = Phase::System;
 
         let initial_base_key = Key::Account(SYSTEM_ACCOUNT_ADDR);
@@ -185,7 +295,8 @@ where
         };
 
         let address_generator = {
-            let generator = AddressGenerator::new(install_deploy_hash.into(), phase);
+            let generator =
+                AddressGenerator::new(install_deploy_hash.into(), phase, protocol_version);
             Rc::new(RefCell::new(generator))
         };
 
@@ -201,7 +312,7 @@ where
             let install_deploy_hash = install_deploy_hash.into();
             let address_generator = Rc::clone(&address_generator);
             let tracking_copy = Rc::clone(&tracking_copy);
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let module_cache = ModuleCache::clone(&self.module_cache);
 
             executor.better_exec(
                 mint_installer_module,
@@ -210,7 +321,7 @@ where
                 initial_base_key,
                 &virtual_system_account,
                 authorization_keys,
-                blocktime,
+                block_info,
                 install_deploy_hash,
                 gas_limit,
                 address_generator,
@@ -219,12 +330,12 @@ where
                 tracking_copy,
                 phase,
                 ProtocolData::default(),
-                system_contract_cache,
+                module_cache,
             )?
         };
 
-        // Spec #7: Execute pos installer wasm code, passing the initially bonded validators as an
-        // argument
+        // Spec #7: Execute pos installer wasm code, passing the initially bonded validators and the
+        // unbonding delay as arguments
         let proof_of_stake_reference: URef = {
             let proof_of_stake_installer_module = {
                 let bytes = genesis_config.proof_of_stake_installer_bytes();
@@ -237,7 +348,12 @@ where
                     .get_bonded_validators()
                     .map(|(k, v)| (k, v.value()))
                     .collect();
-                let args = (mint_reference, bonded_validators);
+                let args = (
+                    mint_reference,
+                    bonded_validators,
+                    genesis_config.unbonding_delay(),
+                    genesis_config.slash_percentage(),
+                );
                 ArgsParser::parse(args)
                     .expect("args should convert to `Vec<CLValue>`")
                     .into_bytes()
@@ -248,16 +364,15 @@ where
             let install_deploy_hash = install_deploy_hash.into();
             let address_generator = Rc::clone(&address_generator);
             let tracking_copy = Rc::clone(&tracking_copy);
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let module_cache = ModuleCache::clone(&self.module_cache);
 
             // Constructs a partial protocol data with already known urefs to pass the validation
             // step
-            let partial_protocol_data = ProtocolData::new(
-                Default::default(),
-                mint_reference,
-                // This is used as unknown key
-                URef::new([0; 32], AccessRights::READ),
-            );
+            let partial_protocol_data = {
+                let mut system_contracts = BTreeMap::new();
+                system_contracts.insert(protocol_data::MINT_NAME.to_string(), mint_reference);
+                ProtocolData::new(Default::default(), system_contracts)
+            };
 
             executor.better_exec(
                 proof_of_stake_installer_module,
@@ -266,7 +381,7 @@ where
                 initial_base_key,
                 &virtual_system_account,
                 authorization_keys,
-                blocktime,
+                block_info,
                 install_deploy_hash,
                 gas_limit,
                 address_generator,
@@ -275,12 +390,39 @@ where
                 tracking_copy,
                 phase,
                 partial_protocol_data,
-                system_contract_cache,
+                module_cache,
             )?
         };
 
+        // Persist the installers, so that upgrades can run them again without being given their
+        // bytes
+        let installers = {
+            let mut tracking_copy = tracking_copy.borrow_mut();
+            let mut ret = BTreeMap::new();
+            ret.insert(
+                protocol_data::MINT_NAME.to_string(),
+                write_installer(&mut tracking_copy, genesis_config.mint_installer_bytes())?,
+            );
+            ret.insert(
+                protocol_data::POS_NAME.to_string(),
+                write_installer(
+                    &mut tracking_copy,
+                    genesis_config.proof_of_stake_installer_bytes(),
+                )?,
+            );
+            ret
+        };
+
         // Spec #2: Associate given CostTable with given ProtocolVersion.
-        let protocol_data = ProtocolData::new(wasm_costs, mint_reference, proof_of_stake_reference);
+        let protocol_data = {
+            let mut system_contracts = BTreeMap::new();
+            system_contracts.insert(protocol_data::MINT_NAME.to_string(), mint_reference);
+            system_contracts.insert(
+                protocol_data::POS_NAME.to_string(),
+                proof_of_stake_reference,
+            );
+            ProtocolData::new(wasm_costs, system_contracts).with_installers(installers)
+        };
 
         self.state
             .put_protocol_data(protocol_version, &protocol_data)
@@ -365,10 +507,11 @@ where
                 let account_public_key = account.public_key();
                 let purse_creation_deploy_hash = account_public_key.value();
                 let address_generator = {
-                    let generator = AddressGenerator::new(purse_creation_deploy_hash, phase);
+                    let generator =
+                        AddressGenerator::new(purse_creation_deploy_hash, phase, protocol_version);
                     Rc::new(RefCell::new(generator))
                 };
-                let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+                let module_cache = ModuleCache::clone(&self.module_cache);
 
                 // ...call the Mint's "mint" endpoint to create purse with tokens...
                 let mint_result: Result<URef, mint::Error> = executor.better_exec(
@@ -378,7 +521,7 @@ where
                     base_key,
                     &virtual_system_account,
                     authorization_keys,
-                    blocktime,
+                    block_info,
                     purse_creation_deploy_hash,
                     gas_limit,
                     address_generator,
@@ -386,8 +529,8 @@ where
                     correlation_id,
                     tracking_copy_exec,
                     phase,
-                    protocol_data,
-                    system_contract_cache,
+                    protocol_data.clone(),
+                    module_cache,
                 )?;
 
                 // ...and write that account to global state...
@@ -395,11 +538,12 @@ where
                 let value = {
                     let account_main_purse = mint_result?;
                     let purse_id = PurseId::new(account_main_purse);
-                    StoredValue::Account(Account::create(
-                        account_public_key.value(),
-                        named_keys,
-                        purse_id,
-                    ))
+                    let mut genesis_account =
+                        Account::create(account_public_key.value(), named_keys, purse_id);
+                    if let Some(vesting_schedule) = account.vesting_schedule() {
+                        genesis_account.set_vesting_schedule(vesting_schedule);
+                    }
+                    StoredValue::Account(genesis_account)
                 };
 
                 tracking_copy_write.borrow_mut().write(key, value);
@@ -469,102 +613,158 @@ where
             None => *current_protocol_data.wasm_costs(),
         };
 
+        let new_rent_parameters = match upgrade_config.rent_parameters() {
+            Some(new_rent_parameters) => new_rent_parameters,
+            None => *current_protocol_data.rent_parameters(),
+        };
+
+        let new_sponsorships = upgrade_config
+            .sponsorships()
+            .unwrap_or_else(|| current_protocol_data.sponsorships())
+            .clone();
+
+        let new_host_function_costs = upgrade_config
+            .host_function_costs()
+            .unwrap_or_else(|| current_protocol_data.host_function_costs())
+            .clone();
+        if new_host_function_costs
+            .iter()
+            .any(|(name, _)| host_function_table::host_function_index(name).is_none())
+        {
+            return Err(Error::InvalidUpgradeConfig);
+        }
+
+        let new_refund_percentage = upgrade_config
+            .refund_percentage()
+            .unwrap_or_else(|| current_protocol_data.refund_percentage());
+        if new_refund_percentage > 100 {
+            return Err(Error::InvalidUpgradeConfig);
+        }
+
+        let new_chain_parameters = upgrade_config
+            .chain_parameters()
+            .unwrap_or_else(|| *current_protocol_data.chain_parameters());
+        if new_chain_parameters.conv_rate() == 0
+            || new_chain_parameters.native_transfer_cost().is_zero()
+        {
+            return Err(Error::InvalidUpgradeConfig);
+        }
+
+        let new_denied_host_functions = upgrade_config
+            .denied_host_functions()
+            .unwrap_or_else(|| current_protocol_data.denied_host_functions())
+            .clone();
+        if new_denied_host_functions
+            .iter()
+            .any(|name| host_function_table::host_function_index(name).is_none())
+        {
+            return Err(Error::InvalidUpgradeConfig);
+        }
+        // Denying `gas` would leave no module metered by host calls instantiable, and denying a
+        // host function imported by a system contract would leave it uncallable
+        if new_denied_host_functions.is_denied(GAS_FUNCTION_NAME) {
+            return Err(Error::InvalidUpgradeConfig);
+        }
+        if !new_denied_host_functions.is_empty() {
+            for system_contract in current_protocol_data.system_contracts() {
+                let contract = tracking_copy
+                    .borrow_mut()
+                    .get_contract(correlation_id, Key::URef(system_contract))?;
+                let module = self
+                    .module_cache
+                    .get_or_insert_with(contract.bytes(), engine_wasm_prep::deserialize)?;
+                if host_function_table::imported_host_functions(&module)
+                    .any(|name| new_denied_host_functions.is_denied(name))
+                {
+                    return Err(Error::InvalidUpgradeConfig);
+                }
+            }
+        }
+
+        let new_gas_metering = upgrade_config
+            .gas_metering()
+            .unwrap_or_else(|| current_protocol_data.gas_metering());
+        if new_gas_metering.validate().is_err() {
+            return Err(Error::InvalidUpgradeConfig);
+        }
+
         // 3.1.2.2 persist wasm CostTable
         let new_protocol_data = ProtocolData::new(
             new_wasm_costs,
-            current_protocol_data.mint(),
-            current_protocol_data.proof_of_stake(),
-        );
+            current_protocol_data.system_contract_registry().clone(),
+        )
+        .with_rent_parameters(new_rent_parameters)
+        .with_installers(current_protocol_data.installers().clone())
+        .with_sponsorships(new_sponsorships.clone())
+        .with_host_function_costs(new_host_function_costs.clone())
+        .with_refund_percentage(new_refund_percentage)
+        .with_chain_parameters(new_chain_parameters)
+        .with_denied_host_functions(new_denied_host_functions.clone())
+        .with_gas_metering(new_gas_metering);
+
+        if upgrade_config.upgrade_installer_bytes().is_some()
+            && upgrade_config.stored_installer().is_some()
+        {
+            return Err(Error::InvalidUpgradeConfig);
+        }
 
         self.state
             .put_protocol_data(new_protocol_version, &new_protocol_data)
             .map_err(Into::into)?;
 
         // 3.1.1.1.1.5 upgrade installer is optional except on major version upgrades
-        match upgrade_config.upgrade_installer_bytes() {
-            None if upgrade_check_result.is_code_required() => {
+        match (
+            upgrade_config.upgrade_installer_bytes(),
+            upgrade_config.stored_installer(),
+        ) {
+            (None, None) if upgrade_check_result.is_code_required() => {
                 // 3.1.1.1.1.5 code is required for major version bump
                 return Err(Error::InvalidUpgradeConfig);
             }
-            None => {
+            (None, None) => {
                 // optional for patch/minor bumps
             }
-            Some(bytes) => {
+            (Some(bytes), _) => {
                 // 3.1.2.3 execute upgrade installer if one is provided
-
-                // preprocess installer module
-                let upgrade_installer_module = {
-                    let preprocessor = Preprocessor::new(new_wasm_costs);
-                    preprocessor.preprocess(bytes)?
-                };
-
-                // currently there are no expected args for an upgrade installer but args are
-                // supported
-                let args = match upgrade_config.upgrade_installer_args() {
-                    Some(args) => args.to_vec(),
-                    None => vec![],
-                };
-
-                // execute as system account
-                let system_account = {
-                    let key = Key::Account(SYSTEM_ACCOUNT_ADDR);
-                    match tracking_copy.borrow_mut().read(correlation_id, &key) {
-                        Ok(Some(StoredValue::Account(account))) => account,
-                        Ok(_) => panic!("system account must exist"),
-                        Err(error) => return Err(Error::ExecError(error.into())),
-                    }
-                };
-
-                let mut keys = BTreeMap::new();
-
-                let initial_base_key = Key::Account(SYSTEM_ACCOUNT_ADDR);
-                let authorization_keys = {
-                    let mut ret = BTreeSet::new();
-                    ret.insert(PublicKey::new(SYSTEM_ACCOUNT_ADDR));
-                    ret
-                };
-
-                let blocktime = BlockTime::default();
-
-                let deploy_hash = {
-                    // seeds address generator w/ protocol version
-                    let bytes: Vec<u8> = upgrade_config
-                        .new_protocol_version()
-                        .value()
-                        .into_bytes()?
-                        .to_vec();
-                    Blake2bHash::new(&bytes).into()
-                };
-
-                // upgrade has no gas limit; approximating with MAX
-                let gas_limit = Gas::new(std::u64::MAX.into());
-                let phase = Phase::System;
-                let address_generator = {
-                    let generator = AddressGenerator::new(pre_state_hash.into(), phase);
-                    Rc::new(RefCell::new(generator))
-                };
-                let state = Rc::clone(&tracking_copy);
-                let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
-
-                Executor.better_exec(
-                    upgrade_installer_module,
-                    args,
-                    &mut keys,
-                    initial_base_key,
-                    &system_account,
-                    authorization_keys,
-                    blocktime,
-                    deploy_hash,
-                    gas_limit,
-                    address_generator,
-                    new_protocol_version,
+                self.exec_upgrade_installer::<()>(
                     correlation_id,
-                    state,
-                    phase,
+                    &upgrade_config,
+                    &tracking_copy,
+                    bytes,
                     new_protocol_data,
-                    system_contract_cache,
                 )?
             }
+            (None, Some(name)) => {
+                // re-run the installer persisted at genesis, and register the system contract it
+                // returns under the same name
+                let bytes = read_installer(
+                    &mut tracking_copy.borrow_mut(),
+                    correlation_id,
+                    &current_protocol_data,
+                    name,
+                )?;
+                let contract_ref: URef = self.exec_upgrade_installer(
+                    correlation_id,
+                    &upgrade_config,
+                    &tracking_copy,
+                    &bytes,
+                    new_protocol_data.clone(),
+                )?;
+                let mut system_contracts = new_protocol_data.system_contract_registry().clone();
+                system_contracts.insert(name.to_string(), contract_ref);
+                let new_protocol_data = ProtocolData::new(new_wasm_costs, system_contracts)
+                    .with_rent_parameters(new_rent_parameters)
+                    .with_installers(current_protocol_data.installers().clone())
+                    .with_sponsorships(new_sponsorships)
+                    .with_host_function_costs(new_host_function_costs)
+                    .with_refund_percentage(new_refund_percentage)
+                    .with_chain_parameters(new_chain_parameters)
+                    .with_denied_host_functions(new_denied_host_functions)
+                    .with_gas_metering(new_gas_metering);
+                self.state
+                    .put_protocol_data(new_protocol_version, &new_protocol_data)
+                    .map_err(Into::into)?;
+            }
         }
 
         let effects = tracking_copy.borrow().effect();
@@ -583,6 +783,388 @@ where
         Ok(UpgradeResult::from_commit_result(commit_result, effects))
     }
 
+    /// Executes an upgrade installer as the system account, returning the value it passes to
+    /// `ret`.
+    fn exec_upgrade_installer<T>(
+        &self,
+        correlation_id: CorrelationId,
+        upgrade_config: &UpgradeConfig,
+        tracking_copy: &Rc<RefCell<TrackingCopy<S::Reader>>>,
+        bytes: &[u8],
+        new_protocol_data: ProtocolData,
+    ) -> Result<T, Error>
+    where
+        T: FromBytes + CLTyped,
+    {
+        let pre_state_hash = upgrade_config.pre_state_hash();
+        let new_protocol_version = upgrade_config.new_protocol_version();
+        let new_wasm_costs = *new_protocol_data.wasm_costs();
+
+        // preprocess installer module
+        let upgrade_installer_module = {
+            let preprocessor = Preprocessor::new(new_wasm_costs);
+            preprocessor.preprocess(bytes)?
+        };
+
+        // currently there are no expected args for an upgrade installer but args are
+        // supported
+        let args = match upgrade_config.upgrade_installer_args() {
+            Some(args) => args.to_vec(),
+            None => vec![],
+        };
+
+        // execute as system account
+        let system_account = {
+            let key = Key::Account(SYSTEM_ACCOUNT_ADDR);
+            match tracking_copy.borrow_mut().read(correlation_id, &key) {
+                Ok(Some(StoredValue::Account(account))) => account,
+                Ok(_) => panic!("system account must exist"),
+                Err(error) => return Err(Error::ExecError(error.into())),
+            }
+        };
+
+        let mut keys = BTreeMap::new();
+
+        let initial_base_key = Key::Account(SYSTEM_ACCOUNT_ADDR);
+        let authorization_keys = {
+            let mut ret = BTreeSet::new();
+            ret.insert(PublicKey::new(SYSTEM_ACCOUNT_ADDR));
+            ret
+        };
+
+        let block_info = BlockInfo::default();
+
+        let deploy_hash = {
+            // seeds address generator w/ protocol version
+            let bytes: Vec<u8> = upgrade_config
+                .new_protocol_version()
+                .value()
+                .into_bytes()?
+                .to_vec();
+            Blake2bHash::new(&bytes).into()
+        };
+
+        // upgrade has no gas limit; approximating with MAX
+        let gas_limit = Gas::from(std::u64::MAX);
+        let phase = Phase::System;
+        let address_generator = {
+            let generator =
+                AddressGenerator::new(pre_state_hash.into(), phase, new_protocol_version);
+            Rc::new(RefCell::new(generator))
+        };
+        let state = Rc::clone(tracking_copy);
+        let module_cache = ModuleCache::clone(&self.module_cache);
+
+        Executor::new(self.config.clone())
+            .better_exec(
+                upgrade_installer_module,
+                args,
+                &mut keys,
+                initial_base_key,
+                &system_account,
+                authorization_keys,
+                block_info,
+                deploy_hash,
+                gas_limit,
+                address_generator,
+                new_protocol_version,
+                correlation_id,
+                state,
+                phase,
+                new_protocol_data,
+                module_cache,
+            )
+            .map_err(Into::into)
+    }
+
+    /// Slashes the given validators, e.g. on evidence of equivocation, by calling the `slash`
+    /// entry point of the proof-of-stake contract as the system account, and commits the effects.
+    pub fn commit_slashing(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        validators: Vec<PublicKey>,
+    ) -> Result<SystemCallResult, Error> {
+        // seeds address generator w/ the slashed validators
+        let deploy_hash = Blake2bHash::new(&validators.to_bytes()?).into();
+
+        let args = ArgsParser::parse(("slash", validators))
+            .expect("args should convert to `Vec<CLValue>`")
+            .into_bytes()
+            .expect("args should serialize");
+
+        match self.commit_proof_of_stake_system_call(
+            correlation_id,
+            protocol_version,
+            pre_state_hash,
+            deploy_hash,
+            args,
+        )? {
+            Some((commit_result, effect)) => {
+                Ok(SystemCallResult::from_commit_result(commit_result, effect))
+            }
+            None => Ok(SystemCallResult::RootNotFound),
+        }
+    }
+
+    /// Mints the seigniorage for the era `era_id`, at the rate of the [`ChainParameters`] in effect
+    /// at `protocol_version`, and distributes it to the bonded validators, by calling the
+    /// `distribute_rewards` entry point of the proof-of-stake contract as the system account, and
+    /// commits the effects.
+    pub fn distribute_rewards(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        era_id: u64,
+    ) -> Result<SystemCallResult, Error> {
+        let seigniorage_rate = match self.get_chain_parameters(protocol_version)? {
+            Some(chain_parameters) => chain_parameters.seigniorage_rate(),
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        // seeds address generator w/ the era id
+        let deploy_hash = Blake2bHash::new(&era_id.to_bytes()?).into();
+
+        let args = ArgsParser::parse(("distribute_rewards", era_id, seigniorage_rate))
+            .expect("args should convert to `Vec<CLValue>`")
+            .into_bytes()
+            .expect("args should serialize");
+
+        match self.commit_proof_of_stake_system_call(
+            correlation_id,
+            protocol_version,
+            pre_state_hash,
+            deploy_hash,
+            args,
+        )? {
+            Some((commit_result, effect)) => {
+                Ok(SystemCallResult::from_commit_result(commit_result, effect))
+            }
+            None => Ok(SystemCallResult::RootNotFound),
+        }
+    }
+
+    /// Calls the proof-of-stake contract with `args` as the system account, and commits the
+    /// effects.  Returns `None` if `pre_state_hash` is not found.
+    fn commit_proof_of_stake_system_call(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        deploy_hash: [u8; 32],
+        args: Vec<u8>,
+    ) -> Result<Option<(CommitResult, ExecutionEffect)>, Error> {
+        let tracking_copy = match self.tracking_copy(pre_state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(None),
+        };
+
+        let protocol_data = match self.state.get_protocol_data(protocol_version) {
+            Ok(Some(protocol_data)) => protocol_data,
+            Ok(None) => return Err(Error::InvalidProtocolVersion(protocol_version)),
+            Err(error) => return Err(Error::ExecError(error.into())),
+        };
+
+        let proof_of_stake_reference = protocol_data.proof_of_stake();
+        let proof_of_stake_contract = tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, Key::from(proof_of_stake_reference))?;
+        let proof_of_stake_module = self.module_cache.get_or_insert_with(
+            proof_of_stake_contract.bytes(),
+            engine_wasm_prep::deserialize,
+        )?;
+
+        // execute as system account
+        let system_account = {
+            let key = Key::Account(SYSTEM_ACCOUNT_ADDR);
+            match tracking_copy.borrow_mut().read(correlation_id, &key) {
+                Ok(Some(StoredValue::Account(account))) => account,
+                Ok(_) => panic!("system account must exist"),
+                Err(error) => return Err(Error::ExecError(error.into())),
+            }
+        };
+        let authorization_keys = {
+            let mut ret = BTreeSet::new();
+            ret.insert(PublicKey::new(SYSTEM_ACCOUNT_ADDR));
+            ret
+        };
+
+        let mut proof_of_stake_keys = proof_of_stake_contract.named_keys().to_owned();
+        let base_key = Key::from(proof_of_stake_reference);
+        // system calls have no gas limit; approximating with MAX
+        let gas_limit = Gas::from(std::u64::MAX);
+        let module_cache = ModuleCache::clone(&self.module_cache);
+
+        let effect = match Executor::new(self.config.clone()).exec_direct(
+            proof_of_stake_module,
+            args,
+            &mut proof_of_stake_keys,
+            base_key,
+            &system_account,
+            authorization_keys,
+            BlockInfo::default(),
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            Rc::clone(&tracking_copy),
+            Phase::System,
+            protocol_data,
+            module_cache,
+        ) {
+            ExecutionResult::Success { effect, .. } => effect,
+            ExecutionResult::Failure { error, .. } => return Err(error),
+        };
+
+        let commit_result = self
+            .state
+            .commit(correlation_id, pre_state_hash, effect.transforms.to_owned())
+            .map_err(Into::into)?;
+
+        Ok(Some((commit_result, effect)))
+    }
+
+    /// Tombstones the values subject to rent which expire at or before `up_to_blocktime`, i.e.
+    /// which haven't been accessed for longer than the expiry in the protocol data, along with
+    /// their rent metadata, and commits the effects.  System contracts are never tombstoned.
+    #[cfg(feature = "rent")]
+    pub fn collect_rent(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        up_to_blocktime: BlockTime,
+    ) -> Result<RentResult, Error>
+    where
+        S::Reader: KeysReader<Key, StoredValue>,
+    {
+        let protocol_data = match self.state.get_protocol_data(protocol_version) {
+            Ok(Some(protocol_data)) => protocol_data,
+            Ok(None) => return Err(Error::InvalidProtocolVersion(protocol_version)),
+            Err(error) => return Err(Error::ExecError(error.into())),
+        };
+        let rent_parameters = protocol_data.rent_parameters();
+        if !rent_parameters.is_enabled() {
+            return Ok(RentResult::Disabled);
+        }
+
+        let reader = match self.state.checkout(pre_state_hash).map_err(Into::into)? {
+            Some(reader) => reader,
+            None => return Ok(RentResult::RootNotFound),
+        };
+
+        let system_contract_keys: BTreeSet<Key> = protocol_data
+            .system_contracts()
+            .into_iter()
+            .map(|uref| Key::URef(uref).normalize())
+            .collect();
+
+        let mut ops = AdditiveMap::new();
+        let mut transforms = AdditiveMap::new();
+        let mut tombstoned = Vec::new();
+        for key in reader.keys(correlation_id).map_err(Into::into)? {
+            let value = match reader.read(correlation_id, &key).map_err(Into::into)? {
+                Some(value) => value,
+                None => continue,
+            };
+            let (tracked_key, last_accessed) = match rent::parse_metadata(&key, &value) {
+                Some(metadata) => metadata,
+                None => continue,
+            };
+            if system_contract_keys.contains(&tracked_key) {
+                continue;
+            }
+            if rent::expires_at(last_accessed, rent_parameters.expiry()) > up_to_blocktime {
+                continue;
+            }
+            if let Some(tracked_value) = reader
+                .read(correlation_id, &tracked_key)
+                .map_err(Into::into)?
+            {
+                if rent::is_purse_balance(&tracked_key, &tracked_value) {
+                    continue;
+                }
+            }
+            for key in &[tracked_key, key] {
+                ops.insert(*key, Op::Write);
+                transforms.insert(*key, Transform::Write(rent::tombstone()));
+            }
+            tombstoned.push(tracked_key);
+        }
+
+        let effect = ExecutionEffect::new(ops, transforms);
+        let commit_result = self
+            .state
+            .commit(correlation_id, pre_state_hash, effect.transforms.to_owned())
+            .map_err(Into::into)?;
+
+        Ok(RentResult::from_commit_result(
+            commit_result,
+            effect,
+            tombstoned,
+        ))
+    }
+
+    /// Returns when the value under `key` was last accessed and when it expires.
+    #[cfg(feature = "rent")]
+    pub fn get_rent_status(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        state_hash: Blake2bHash,
+        key: Key,
+    ) -> Result<RentStatus, Error> {
+        let protocol_data = match self.state.get_protocol_data(protocol_version) {
+            Ok(Some(protocol_data)) => protocol_data,
+            Ok(None) => return Err(Error::InvalidProtocolVersion(protocol_version)),
+            Err(error) => return Err(Error::ExecError(error.into())),
+        };
+        let rent_parameters = protocol_data.rent_parameters();
+        if !rent_parameters.is_enabled() {
+            return Ok(RentStatus::Disabled);
+        }
+
+        let key = key.normalize();
+        if !rent::is_subject_to_rent(&key)
+            || protocol_data
+                .system_contracts()
+                .into_iter()
+                .any(|uref| Key::URef(uref).normalize() == key)
+        {
+            return Ok(RentStatus::Exempt);
+        }
+
+        let reader = match self.state.checkout(state_hash).map_err(Into::into)? {
+            Some(reader) => reader,
+            None => return Ok(RentStatus::RootNotFound),
+        };
+
+        if let Some(value) = reader.read(correlation_id, &key).map_err(Into::into)? {
+            if rent::is_purse_balance(&key, &value) {
+                return Ok(RentStatus::Exempt);
+            }
+        }
+
+        let metadata_key = rent::metadata_key(&key);
+        let last_accessed = match reader
+            .read(correlation_id, &metadata_key)
+            .map_err(Into::into)?
+        {
+            Some(value) => match rent::parse_metadata(&metadata_key, &value) {
+                Some((_, last_accessed)) => last_accessed,
+                None => return Ok(RentStatus::Untracked),
+            },
+            None => return Ok(RentStatus::Untracked),
+        };
+
+        Ok(RentStatus::Tracked {
+            last_accessed,
+            expires_at: rent::expires_at(last_accessed, rent_parameters.expiry()),
+        })
+    }
+
     pub fn tracking_copy(
         &self,
         hash: Blake2bHash,
@@ -593,6 +1175,101 @@ where
         }
     }
 
+    /// Returns statistics of the trie under `state_hash`, or `None` if it is not a known root.
+    ///
+    /// Traversing a large trie takes long, so capacity planning on live nodes should use
+    /// [`TrieStatsMode::Sampled`].
+    pub fn trie_stats(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        mode: TrieStatsMode,
+    ) -> Result<Option<TrieStats>, Error> {
+        let stats = self
+            .state
+            .stats(correlation_id, state_hash, mode)
+            .map_err(Into::into)?;
+        Ok(stats)
+    }
+
+    /// Returns a page of the keys of the variant `key_tag` under `state_hash`, e.g. to list all the
+    /// accounts, or `None` if `state_hash` is not a known root.
+    ///
+    /// Keys are listed in the order of their serialized bytes.  A page holds at most `limit` keys,
+    /// and never more than [`MAX_KEYS_PAGE_SIZE`], following the pagination token `after` returned
+    /// with the previous page.
+    pub fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        key_tag: KeyTag,
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Option<KeysPage<Key>>, Error> {
+        let prefix = [key_tag as u8];
+        let limit = limit.min(MAX_KEYS_PAGE_SIZE);
+        let page = self
+            .state
+            .keys_with_prefix(correlation_id, state_hash, &prefix, after, limit)
+            .map_err(Into::into)?;
+        Ok(page)
+    }
+
+    /// Loads the system contracts of `protocol_version` stored under `state_hash` into the module
+    /// cache, so that the first deploys executed after the engine starts don't pay for reading and
+    /// deserializing them.
+    ///
+    /// Returns the number of contracts loaded, or `None` if `state_hash` is not a known root.
+    pub fn warm_up(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<usize>, Error> {
+        let protocol_data = self
+            .get_protocol_data(protocol_version)?
+            .ok_or_else(|| Error::InvalidProtocolVersion(protocol_version))?;
+        let mut tracking_copy = match self.tracking_copy(state_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(None),
+        };
+
+        let system_contracts = protocol_data.system_contracts();
+        for uref in &system_contracts {
+            let contract = tracking_copy.get_contract(correlation_id, Key::URef(*uref))?;
+            self.module_cache
+                .get_or_insert_with(contract.bytes(), parity_wasm::deserialize_buffer::<Module>)?;
+        }
+        Ok(Some(system_contracts.len()))
+    }
+
+    /// Returns the number of modules in the module cache, its hits and misses so far, and the
+    /// total size of the cached modules.
+    pub fn cache_stats(&self) -> ModuleCacheStats {
+        self.module_cache.stats()
+    }
+
+    /// Evicts the module of the contract stored under `uref` at the given state root from the
+    /// module cache, so that it is deserialized afresh the next time it runs.
+    ///
+    /// Returns `None` if the state root isn't known, or whether the module was cached otherwise.
+    pub fn invalidate(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        uref: URef,
+    ) -> Result<Option<bool>, Error> {
+        let mut tracking_copy = match self.tracking_copy(state_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(None),
+        };
+        let contract = tracking_copy.get_contract(correlation_id, Key::URef(uref))?;
+        let removed = self
+            .module_cache
+            .remove(&Blake2bHash::new(contract.bytes()));
+        Ok(Some(removed.is_some()))
+    }
+
     pub fn run_query(
         &self,
         correlation_id: CorrelationId,
@@ -606,40 +1283,145 @@ where
         let mut mut_tracking_copy = tracking_copy.borrow_mut();
 
         Ok(mut_tracking_copy
-            .query(correlation_id, query_request.key(), query_request.path())
+            .query_with_visited_path(correlation_id, query_request.key(), query_request.path())
             .map_err(|err| Error::ExecError(err.into()))?
             .into())
     }
 
+    /// Returns the compilation metadata embedded in the contract stored under `contract_key` at the
+    /// given state root.
+    pub fn get_contract_metadata(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        contract_key: Key,
+    ) -> Result<ContractMetadataResult, Error> {
+        let mut tracking_copy = match self.tracking_copy(state_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(ContractMetadataResult::RootNotFound),
+        };
+
+        let contract = match tracking_copy
+            .get(correlation_id, &contract_key.normalize())
+            .map_err(|err| Error::ExecError(err.into()))?
+        {
+            Some(StoredValue::Contract(contract)) => contract,
+            _ => return Ok(ContractMetadataResult::ContractNotFound),
+        };
+
+        let module = engine_wasm_prep::deserialize(contract.bytes())?;
+        let metadata = contract_metadata::get_contract_metadata(&module)?;
+
+        Ok(ContractMetadataResult::Success(metadata))
+    }
+
+    /// Returns the balance of the purse identified by the request's URef at the given state root.
+    ///
+    /// The purse's balance key is resolved through the mint associated with the request's protocol
+    /// version.
+    pub fn get_balance(
+        &self,
+        correlation_id: CorrelationId,
+        balance_request: BalanceRequest,
+    ) -> Result<BalanceResult, Error> {
+        let protocol_version = balance_request.protocol_version();
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        let mut tracking_copy = match self.tracking_copy(balance_request.state_hash())? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(BalanceResult::RootNotFound),
+        };
+
+        let purse_balance_key = tracking_copy.get_purse_balance_key(
+            correlation_id,
+            protocol_data.mint(),
+            Key::URef(balance_request.purse_uref()),
+        )?;
+        let balance = tracking_copy.get_purse_balance(correlation_id, purse_balance_key)?;
+
+        Ok(BalanceResult::Success(balance))
+    }
+
     pub fn run_execute(
         &self,
         correlation_id: CorrelationId,
         mut exec_request: ExecuteRequest,
     ) -> Result<Vec<ExecutionResult>, RootNotFound> {
         // TODO: do not unwrap
-        let wasm_costs = self
-            .wasm_costs(exec_request.protocol_version)
+        let protocol_data = self
+            .get_protocol_data(exec_request.protocol_version)
             .unwrap()
             .unwrap();
-        let executor = Executor;
-        let preprocessor = Preprocessor::new(wasm_costs);
+        let executor = Executor::new(self.config.clone());
+        let preprocessor = Preprocessor::new(*protocol_data.wasm_costs())
+            .with_gas_metering(protocol_data.gas_metering());
+        let block_gas_limit = self
+            .get_chain_parameters(exec_request.protocol_version)
+            .ok()
+            .and_then(|chain_parameters| chain_parameters?.block_gas_limit());
 
         let mut results = Vec::new();
+        let mut block_gas_used = Gas::default();
+        // Addresses are derived from the deploy hash, so deploys sharing a hash within a request
+        // would collide
+        let mut deploy_hashes = HashSet::new();
 
         for deploy_item in exec_request.take_deploys() {
             let result = match deploy_item {
-                Ok(deploy_item) => self.deploy(
-                    correlation_id,
-                    &executor,
-                    &preprocessor,
-                    exec_request.protocol_version,
-                    exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
-                    deploy_item,
-                ),
+                Ok(deploy_item) if !deploy_hashes.insert(deploy_item.deploy_hash) => {
+                    Ok(ExecutionResult::precondition_failure(
+                        Error::DuplicateDeployHash(deploy_item.deploy_hash),
+                    ))
+                }
+                Ok(deploy_item) => {
+                    let start = Instant::now();
+                    let result = self.deploy(
+                        correlation_id,
+                        &executor,
+                        &preprocessor,
+                        exec_request.protocol_version,
+                        exec_request.parent_state_hash,
+                        BlockInfo::new(
+                            BlockTime::new(exec_request.block_time),
+                            exec_request.era_id,
+                            exec_request.block_height,
+                        ),
+                        deploy_item,
+                    );
+                    if let Ok(execution_result) = &result {
+                        metrics::DEPLOY_DURATION.observe_duration(start.elapsed());
+                        metrics::DEPLOY_GAS_USED
+                            .observe(execution_result.cost().value().low_u64() as f64);
+                    }
+                    result
+                }
                 Err(exec_result) => Ok(exec_result), /* this will get pushed into the results vec
                                                       * below */
             };
+            let result = match (result, block_gas_limit) {
+                // A deploy which would take the block over its gas limit is rejected without
+                // effects, leaving the remaining gas to the deploys after it
+                (Ok(result), Some(block_gas_limit)) => {
+                    let remaining = block_gas_limit
+                        .checked_sub(block_gas_used)
+                        .unwrap_or_default();
+                    if result.cost() > remaining {
+                        Ok(ExecutionResult::precondition_failure(
+                            Error::BlockGasLimitExceeded {
+                                cost: result.cost(),
+                                remaining,
+                            },
+                        ))
+                    } else {
+                        block_gas_used += result.cost();
+                        Ok(result)
+                    }
+                }
+                (result, _) => result,
+            };
             match result {
                 Ok(result) => results.push(result),
                 Err(error) => {
@@ -648,7 +1430,44 @@ where
             };
         }
 
-        Ok(results)
+        Ok(results)
+    }
+
+    /// Executes a single deploy against the given state root without committing its effects.
+    ///
+    /// The returned `ExecutionResult` carries the same cost, effects and error as a regular
+    /// execution would, which makes it suitable for estimating gas and previewing transforms.  As
+    /// nothing is written to global state, this can be called concurrently with `run_execute`.
+    pub fn speculative_exec(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        block_info: BlockInfo,
+        protocol_version: ProtocolVersion,
+        deploy_item: DeployItem,
+    ) -> Result<ExecutionResult, RootNotFound> {
+        let protocol_data = match self.get_protocol_data(protocol_version) {
+            Ok(Some(protocol_data)) => protocol_data,
+            Ok(None) => {
+                return Ok(ExecutionResult::precondition_failure(
+                    Error::InvalidProtocolVersion(protocol_version),
+                ))
+            }
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+        };
+        let executor = Executor::new(self.config.clone());
+        let preprocessor = Preprocessor::new(*protocol_data.wasm_costs())
+            .with_gas_metering(protocol_data.gas_metering());
+
+        self.deploy(
+            correlation_id,
+            &executor,
+            &preprocessor,
+            protocol_version,
+            prestate_hash,
+            block_info,
+            deploy_item,
+        )
     }
 
     pub fn get_module(
@@ -730,11 +1549,50 @@ where
                     }
                 }
             }
+            ExecutableDeployItem::StoredVersionedContractByHash { .. }
+            | ExecutableDeployItem::StoredVersionedContractByName { .. } => {
+                let (_, contract_header) = self.get_contract_header(
+                    Rc::clone(&tracking_copy),
+                    deploy_item,
+                    account,
+                    correlation_id,
+                )?;
+                Key::Hash(contract_header.contract_hash())
+            }
+            ExecutableDeployItem::Transfer { .. } => {
+                return Err(error::Error::InvalidDeployItemVariant(
+                    "Transfer is only valid as session code".to_string(),
+                ));
+            }
         };
         let contract = tracking_copy
             .borrow_mut()
             .get_contract(correlation_id, stored_contract_key)?;
 
+        // Like calls from contracts, deploys may only call a version of a contract package through
+        // the package
+        if contract.package_hash().is_some() {
+            match deploy_item {
+                ExecutableDeployItem::StoredVersionedContractByHash { .. }
+                | ExecutableDeployItem::StoredVersionedContractByName { .. } => {}
+                _ => {
+                    return Err(error::Error::ExecError(
+                        execution::Error::ContractVersionCalledDirectly(
+                            stored_contract_key.normalize(),
+                        ),
+                    ))
+                }
+            }
+        }
+
+        // Like calls from contracts, deploys may only call a stored contract as one of its
+        // authorized callers
+        if !contract.is_authorized_caller(&PublicKey::new(account.pub_key())) {
+            return Err(error::Error::ExecError(
+                execution::Error::CallerNotAuthorized(stored_contract_key.normalize()),
+            ));
+        }
+
         // A contract may only call a stored contract that has the same protocol major version
         // number.
         let contract_version = contract.protocol_version();
@@ -746,11 +1604,138 @@ where
             return Err(error::Error::ExecError(exec_error));
         }
 
-        let (ret, _, _) = contract.destructure();
-        let module = engine_wasm_prep::deserialize(&ret)?;
+        let module = self
+            .module_cache
+            .get_or_insert_with(contract.bytes(), engine_wasm_prep::deserialize)?;
         Ok(module)
     }
 
+    /// Resolves the version of the contract package called by a `StoredVersionedContractBy*`
+    /// deploy item, and returns the hash of the package together with the version's header.
+    ///
+    /// Fails if the version is disabled, or if `account` may not call the entry point.
+    fn get_contract_header(
+        &self,
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        deploy_item: &ExecutableDeployItem,
+        account: &Account,
+        correlation_id: CorrelationId,
+    ) -> Result<([u8; KEY_HASH_LENGTH], ContractHeader), error::Error> {
+        let (package_hash, version, entry_point) = match deploy_item {
+            ExecutableDeployItem::StoredVersionedContractByHash {
+                hash,
+                version,
+                entry_point,
+                ..
+            } => {
+                let hash_len = hash.len();
+                if hash_len != KEY_HASH_LENGTH {
+                    return Err(error::Error::InvalidHashLength {
+                        expected: KEY_HASH_LENGTH,
+                        actual: hash_len,
+                    });
+                }
+                let mut arr = [0u8; KEY_HASH_LENGTH];
+                arr.copy_from_slice(&hash);
+                (arr, version, entry_point)
+            }
+            ExecutableDeployItem::StoredVersionedContractByName {
+                name,
+                version,
+                entry_point,
+                ..
+            } => {
+                let package_key = account.named_keys().get(name).ok_or_else(|| {
+                    error::Error::ExecError(execution::Error::URefNotFound(name.to_string()))
+                })?;
+                let package_hash = package_key.as_hash().ok_or_else(|| {
+                    error::Error::ExecError(execution::Error::TypeMismatch(
+                        engine_shared::transform::TypeMismatch::new(
+                            "Key::Hash".to_string(),
+                            package_key.type_string(),
+                        ),
+                    ))
+                })?;
+                (package_hash, version, entry_point)
+            }
+            _ => {
+                return Err(error::Error::InvalidDeployItemVariant(
+                    "Only calls to versioned contracts have a contract header".to_string(),
+                ))
+            }
+        };
+
+        let package_key = Key::Hash(package_hash);
+        let contract_package = match tracking_copy
+            .borrow_mut()
+            .read(correlation_id, &package_key)
+            .map_err(Into::into)?
+        {
+            Some(StoredValue::ContractPackage(contract_package)) => contract_package,
+            Some(other) => {
+                return Err(error::Error::ExecError(execution::Error::TypeMismatch(
+                    engine_shared::transform::TypeMismatch::new(
+                        "ContractPackage".to_string(),
+                        other.type_name(),
+                    ),
+                )))
+            }
+            None => {
+                return Err(error::Error::ExecError(execution::Error::KeyNotFound(
+                    package_key,
+                )))
+            }
+        };
+
+        // Deploy arguments are untyped, so unlike calls from contracts only access is checked
+        let (contract_header, _) = execution::get_entry_point(
+            &contract_package,
+            *version,
+            entry_point,
+            &PublicKey::new(account.pub_key()),
+            |uref| {
+                account
+                    .named_keys()
+                    .values()
+                    .filter_map(Key::as_uref)
+                    .any(|named_uref| named_uref.addr() == uref.addr())
+            },
+        )?;
+
+        Ok((package_hash, contract_header.clone()))
+    }
+
+    /// Returns the base key, named keys and contract package hash of the context in which the
+    /// code called by `deploy_item` executes.
+    fn get_execution_context(
+        &self,
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        deploy_item: &ExecutableDeployItem,
+        account: &Account,
+        correlation_id: CorrelationId,
+    ) -> Result<(Key, BTreeMap<String, Key>, Option<[u8; KEY_HASH_LENGTH]>), error::Error> {
+        match deploy_item.execution_context() {
+            ExecutionContext::Account => Ok((
+                Key::Account(account.pub_key()),
+                account.named_keys().clone(),
+                None,
+            )),
+            ExecutionContext::Contract => {
+                let (package_hash, contract_header) = self.get_contract_header(
+                    Rc::clone(&tracking_copy),
+                    deploy_item,
+                    account,
+                    correlation_id,
+                )?;
+                let contract_key = Key::Hash(contract_header.contract_hash());
+                let contract = tracking_copy
+                    .borrow_mut()
+                    .get_contract(correlation_id, contract_key)?;
+                Ok((contract_key, contract.take_named_keys(), Some(package_hash)))
+            }
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn deploy(
         &self,
@@ -759,23 +1744,37 @@ where
         preprocessor: &Preprocessor,
         protocol_version: ProtocolVersion,
         prestate_hash: Blake2bHash,
-        blocktime: BlockTime,
+        block_info: BlockInfo,
         deploy_item: DeployItem,
     ) -> Result<ExecutionResult, RootNotFound> {
         // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
 
+        let span = info_span!(
+            "deploy",
+            %correlation_id,
+            deploy_hash = %base16::encode_lower(&deploy_item.deploy_hash),
+        );
+        let _guard = span.enter();
+
         let session = deploy_item.session;
         let payment = deploy_item.payment;
-        let address = Key::Account(deploy_item.address.value());
+        let account_public_key = deploy_item.address;
+        let address = Key::Account(account_public_key.value());
         let authorization_keys = deploy_item.authorization_keys;
         let deploy_hash = deploy_item.deploy_hash;
+        let signatures = deploy_item.signatures;
 
         // Create tracking copy (which functions as a deploy context)
         // validation_spec_2: prestate_hash check
         let tracking_copy = match self.tracking_copy(prestate_hash) {
             Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
             Ok(None) => return Err(RootNotFound(prestate_hash)),
-            Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
+            Ok(Some(mut tracking_copy)) => {
+                if self.config.profiling_enabled() {
+                    tracking_copy.enable_read_stats();
+                }
+                Rc::new(RefCell::new(tracking_copy))
+            }
         };
 
         // Get addr bytes from `address` (which is actually a Key)
@@ -784,7 +1783,7 @@ where
             Some(account_addr) => account_addr,
             None => {
                 return Ok(ExecutionResult::precondition_failure(
-                    error::Error::AuthorizationError,
+                    error::Error::AccountNotFound(account_public_key),
                 ))
             }
         };
@@ -796,44 +1795,88 @@ where
             .get_account(correlation_id, account_addr)
         {
             Ok(account) => account,
-            Err(_) => {
+            Err(execution::Error::KeyNotFound(_)) => {
                 return Ok(ExecutionResult::precondition_failure(
-                    error::Error::AuthorizationError,
+                    error::Error::AccountNotFound(account_public_key),
                 ));
             }
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
         };
 
+        // Check that the authorization keys are backed by signatures of the deploy hash, unless the
+        // deploy carries none and the engine is configured to accept unsigned deploys
+        // validation_spec_3: account validity
+        if !(self.config.unsigned_deploys_enabled() && signatures.is_empty()) {
+            if let Err(error) = verify_signatures(&deploy_hash, &authorization_keys, &signatures) {
+                return Ok(ExecutionResult::precondition_failure(error));
+            }
+        }
+
         // Authorize using provided authorization keys
         // validation_spec_3: account validity
-        if !account.can_authorize(&authorization_keys) {
+        if authorization_keys.is_empty() {
             return Ok(ExecutionResult::precondition_failure(
-                crate::engine_state::error::Error::AuthorizationError,
+                error::Error::NoAuthorizationKeys,
+            ));
+        }
+        let unassociated_keys = account.unassociated_keys(&authorization_keys);
+        if !unassociated_keys.is_empty() {
+            return Ok(ExecutionResult::precondition_failure(
+                error::Error::UnassociatedKeys(unassociated_keys),
             ));
         }
 
         // Check total key weight against deploy threshold
         // validation_spec_4: deploy validity
-        if !account.can_deploy_with(&authorization_keys) {
+        let total_weight = account.authorization_keys_weight(&authorization_keys);
+        let threshold = *account.action_thresholds().deployment();
+        if total_weight < threshold {
             return Ok(ExecutionResult::precondition_failure(
-                // TODO?:this doesn't happen in execution any longer, should error variant be moved
-                execution::Error::DeploymentAuthorizationFailure.into(),
+                error::Error::DeploymentThresholdNotMet {
+                    total_weight: total_weight.value(),
+                    threshold: threshold.value(),
+                },
             ));
         }
 
-        // Create session code `A` from provided session bytes
+        // Let the chain's deploy hooks reject the deploy before anything is executed
+        let deploy_hooks = self.config.deploy_hooks();
+        let pre_payment_result = {
+            let tracking_copy = tracking_copy.borrow();
+            let state = TrackingCopyView(&*tracking_copy);
+            let context = DeployHookContext::new(
+                correlation_id,
+                &account,
+                &authorization_keys,
+                deploy_hash,
+                block_info.blocktime(),
+                protocol_version,
+                &state,
+            );
+            deploy_hooks.pre_payment(&context)
+        };
+        if let Err(error) = pre_payment_result {
+            return Ok(ExecutionResult::precondition_failure(error));
+        }
+
+        // Create session code `A` from provided session bytes, unless the session is a native
+        // transfer which has no module
         // validation_spec_1: valid wasm bytes
-        let session_module = match self.get_module(
-            Rc::clone(&tracking_copy),
-            &session,
-            &account,
-            correlation_id,
-            preprocessor,
-            &protocol_version,
-        ) {
-            Ok(module) => module,
-            Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error));
-            }
+        let session_module = match session {
+            ExecutableDeployItem::Transfer { .. } => None,
+            _ => match self.get_module(
+                Rc::clone(&tracking_copy),
+                &session,
+                &account,
+                correlation_id,
+                preprocessor,
+                &protocol_version,
+            ) {
+                Ok(module) => Some(module),
+                Err(error) => {
+                    return Ok(ExecutionResult::precondition_failure(error));
+                }
+            },
         };
 
         // Obtain current protocol data for given version
@@ -850,7 +1893,23 @@ where
             }
         };
 
-        let max_payment_cost: Motes = Motes::new(U512::from(MAX_PAYMENT));
+        #[cfg(feature = "rent")]
+        {
+            if protocol_data.rent_parameters().is_enabled() {
+                tracking_copy
+                    .borrow_mut()
+                    .enable_rent(block_info.blocktime());
+            }
+        }
+
+        // Deploys whose session code calls a sponsored contract are refunded during finalization
+        let sponsorship = session
+            .stored_contract_hash(&account)
+            .and_then(|contract_hash| protocol_data.sponsorship(&contract_hash));
+
+        let chain_parameters = *protocol_data.chain_parameters();
+        let conv_rate = chain_parameters.conv_rate();
+        let max_payment_cost: Motes = chain_parameters.max_payment();
 
         // Get mint system contract details
         // payment_code_spec_6: system contract validity
@@ -868,12 +1927,11 @@ where
                 Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
             };
 
-            if !self.system_contract_cache.has(&mint_reference) {
-                let module = match engine_wasm_prep::deserialize(mint_contract.bytes()) {
-                    Ok(module) => module,
-                    Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
-                };
-                self.system_contract_cache.insert(mint_reference, module);
+            if let Err(error) = self
+                .module_cache
+                .get_or_insert_with(mint_contract.bytes(), engine_wasm_prep::deserialize)
+            {
+                return Ok(ExecutionResult::precondition_failure(error.into()));
             }
 
             mint_reference
@@ -967,12 +2025,13 @@ where
 
         // [`ExecutionResultBuilder`] handles merging of multiple execution results
         let mut execution_result_builder = execution_result::ExecutionResultBuilder::new();
+        execution_result_builder.keep_effects_by_phase(self.config.effects_by_phase_enabled());
 
         // Execute provided payment code
         let payment_result = {
             // payment_code_spec_1: init pay environment w/ gas limit == (max_payment_cost /
             // conv_rate)
-            let pay_gas_limit = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
+            let pay_gas_limit = Gas::from_motes(max_payment_cost, conv_rate).unwrap_or_default();
 
             // Create payment code module from bytes
             // validation_spec_1: valid wasm bytes
@@ -989,24 +2048,39 @@ where
                     return Ok(ExecutionResult::precondition_failure(error));
                 }
             };
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let (base_key, named_keys, package_hash) = match self.get_execution_context(
+                Rc::clone(&tracking_copy),
+                &payment,
+                &account,
+                correlation_id,
+            ) {
+                Ok(execution_context) => execution_context,
+                Err(error) => {
+                    return Ok(ExecutionResult::precondition_failure(error));
+                }
+            };
+            let entry_point = payment.entry_point_name().to_string();
+            let module_cache = ModuleCache::clone(&self.module_cache);
 
             // payment_code_spec_2: execute payment code
             executor.exec(
                 payment_module,
                 payment.take_args(),
-                address,
+                &entry_point,
+                base_key,
+                named_keys,
+                package_hash,
                 &account,
                 authorization_keys.clone(),
-                blocktime,
-                deploy_hash,
+                block_info,
+                deploy_hash.value(),
                 pay_gas_limit,
                 protocol_version,
                 correlation_id,
                 Rc::clone(&tracking_copy),
                 Phase::Payment,
-                protocol_data,
-                system_contract_cache,
+                protocol_data.clone(),
+                module_cache,
             )
         };
 
@@ -1045,18 +2119,52 @@ where
             }
         };
 
-        if let Some(forced_transfer) = payment_result.check_forced_transfer(payment_purse_balance) {
+        if let Some(forced_transfer) =
+            payment_result.check_forced_transfer(payment_purse_balance, conv_rate)
+        {
+            // The payment effects are replaced by the forced transfer, and no other phase runs
+            let effects_by_phase = if self.config.effects_by_phase_enabled() {
+                Some(EffectsByPhase::new(
+                    payment_result.effect().clone(),
+                    Default::default(),
+                    Default::default(),
+                ))
+            } else {
+                None
+            };
+            let stdout = payment_result.stdout().to_vec();
+            let host_function_profile = payment_result.host_function_profile().cloned();
             let error = match forced_transfer {
                 ForcedTransferResult::InsufficientPayment => Error::InsufficientPaymentError,
                 ForcedTransferResult::PaymentFailure => payment_result.take_error().unwrap(),
             };
-            return Ok(ExecutionResult::new_payment_code_error(
+            let ret = ExecutionResult::new_payment_code_error(
                 error,
                 max_payment_cost,
                 account_main_purse_balance,
                 account_main_purse_balance_key,
                 rewards_purse_balance_key,
-            ));
+                conv_rate,
+            )
+            .with_read_stats(tracking_copy.borrow().read_stats())
+            .with_effects_by_phase(effects_by_phase)
+            .with_stdout(stdout)
+            .with_host_function_profile(host_function_profile);
+
+            // The forced transfer finalizes the payment, so the hooks see it like any other deploy
+            let tracking_copy = tracking_copy.borrow();
+            let state = TrackingCopyView(&*tracking_copy);
+            let context = DeployHookContext::new(
+                correlation_id,
+                &account,
+                &authorization_keys,
+                deploy_hash,
+                block_info.blocktime(),
+                protocol_version,
+                &state,
+            );
+            deploy_hooks.post_finalize(&context, &ret);
+            return Ok(ret);
         }
 
         execution_result_builder.set_payment_execution_result(payment_result);
@@ -1064,36 +2172,103 @@ where
         let post_payment_tc = tracking_copy.borrow();
         let session_tc = Rc::new(RefCell::new(post_payment_tc.fork()));
 
+        // Session code observes the account as left by payment code, so that named keys put or
+        // removed during the payment phase are visible to `has_key` and friends.
+        let session_account: Account = match session_tc
+            .borrow_mut()
+            .get_account(correlation_id, account_addr)
+        {
+            Ok(account) => account,
+            Err(error) => {
+                return Ok(ExecutionResult::precondition_failure(error.into()));
+            }
+        };
+
+        let pre_session_result = {
+            let session_tc = session_tc.borrow();
+            let state = TrackingCopyView(&*session_tc);
+            let context = DeployHookContext::new(
+                correlation_id,
+                &session_account,
+                &authorization_keys,
+                deploy_hash,
+                block_info.blocktime(),
+                protocol_version,
+                &state,
+            );
+            deploy_hooks.pre_session(&context)
+        };
+
         // session_code_spec_2: execute session code
-        let session_result = {
+        let session_result = if let Err(error) = pre_session_result {
+            // Session code rejected by a deploy hook is treated as failed session code
+            ExecutionResult::precondition_failure(error)
+        } else {
             // payment_code_spec_3_b_i: if (balance of PoS pay purse) >= (gas spent during
             // payment code execution) * conv_rate, yes session
             // session_code_spec_1: gas limit = ((balance of PoS payment purse) / conv_rate)
             // - (gas spent during payment execution)
-            let session_gas_limit: Gas = Gas::from_motes(payment_purse_balance, CONV_RATE)
-                .unwrap_or_default()
-                - payment_result_cost;
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let session_gas_limit: Gas = Gas::from_motes(payment_purse_balance, conv_rate)
+                .and_then(|gas| gas.checked_sub(payment_result_cost))
+                .unwrap_or_default();
 
-            executor.exec(
-                session_module,
-                session.take_args(),
-                address,
-                &account,
-                authorization_keys.clone(),
-                blocktime,
-                deploy_hash,
-                session_gas_limit,
-                protocol_version,
-                correlation_id,
-                Rc::clone(&session_tc),
-                Phase::Session,
-                protocol_data,
-                system_contract_cache,
-            )
+            match (session, session_module) {
+                (ExecutableDeployItem::Transfer { target, amount }, _) => native_transfer::exec(
+                    correlation_id,
+                    executor,
+                    ModuleCache::clone(&self.module_cache),
+                    Rc::clone(&session_tc),
+                    &session_account,
+                    authorization_keys.clone(),
+                    target,
+                    amount,
+                    session_gas_limit,
+                    block_info,
+                    deploy_hash.value(),
+                    protocol_version,
+                    &protocol_data,
+                ),
+                (session, Some(session_module)) => match self.get_execution_context(
+                    Rc::clone(&session_tc),
+                    &session,
+                    &session_account,
+                    correlation_id,
+                ) {
+                    // Session code which can't be resolved against the post-payment state is
+                    // treated as failed session code
+                    Err(error) => ExecutionResult::precondition_failure(error),
+                    Ok((base_key, named_keys, package_hash)) => {
+                        let entry_point = session.entry_point_name().to_string();
+                        let module_cache = ModuleCache::clone(&self.module_cache);
+                        executor.exec(
+                            session_module,
+                            session.take_args(),
+                            &entry_point,
+                            base_key,
+                            named_keys,
+                            package_hash,
+                            &session_account,
+                            authorization_keys.clone(),
+                            block_info,
+                            deploy_hash.value(),
+                            session_gas_limit,
+                            protocol_version,
+                            correlation_id,
+                            Rc::clone(&session_tc),
+                            Phase::Session,
+                            protocol_data.clone(),
+                            module_cache,
+                        )
+                    }
+                },
+                (_, None) => unreachable!("only native transfers have no session module"),
+            }
         };
 
-        let post_session_rc = if session_result.is_failure() {
+        let session_read_stats = session_tc.borrow().read_stats();
+        let session_succeeded = !session_result.is_failure();
+
+        let post_session_rc = if !session_succeeded {
             // If session code fails we do not include its effects,
             // so we start again from the post-payment state.
             Rc::new(RefCell::new(post_payment_tc.fork()))
@@ -1106,32 +2281,29 @@ where
         execution_result_builder.set_session_execution_result(session_result);
 
         // payment_code_spec_5: run finalize process
-        let finalize_result = {
+        let (finalize_result, finalize_read_stats) = {
             let post_session_tc = post_session_rc.borrow();
             let finalization_tc = Rc::new(RefCell::new(post_session_tc.fork()));
 
             // validation_spec_1: valid wasm bytes
-            let proof_of_stake_module =
-                match self.system_contract_cache.get(&proof_of_stake_reference) {
-                    Some(module) => module,
-                    None => {
-                        let module =
-                            match engine_wasm_prep::deserialize(proof_of_stake_contract.bytes()) {
-                                Ok(module) => module,
-                                Err(error) => {
-                                    return Ok(ExecutionResult::precondition_failure(error.into()))
-                                }
-                            };
-                        self.system_contract_cache
-                            .insert(proof_of_stake_reference, module.clone());
-                        module
-                    }
-                };
+            let proof_of_stake_module = match self.module_cache.get_or_insert_with(
+                proof_of_stake_contract.bytes(),
+                engine_wasm_prep::deserialize,
+            ) {
+                Ok(module) => module,
+                Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+            };
+
+            //((gas spent during payment code execution) + (gas spent during session code execution)) * conv_rate
+            let finalize_cost_motes: Motes = Motes::from_gas(execution_result_builder.total_cost(), conv_rate).expect("motes overflow");
 
             let proof_of_stake_args = {
-                //((gas spent during payment code execution) + (gas spent during session code execution)) * conv_rate
-                let finalize_cost_motes: Motes = Motes::from_gas(execution_result_builder.total_cost(), CONV_RATE).expect("motes overflow");
-                let args = ("finalize_payment", finalize_cost_motes.value(), account_addr);
+                let args = (
+                    "finalize_payment",
+                    finalize_cost_motes.value(),
+                    account_addr,
+                    protocol_data.refund_percentage(),
+                );
                 ArgsParser::parse(args)
                     .expect("args should convert to `Vec<CLValue>`")
                     .into_bytes()
@@ -1151,26 +2323,53 @@ where
             let mut proof_of_stake_keys = proof_of_stake_contract.named_keys().to_owned();
 
             let base_key = Key::from(proof_of_stake_reference);
-            let gas_limit = Gas::new(U512::from(std::u64::MAX));
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let gas_limit = Gas::from(std::u64::MAX);
+            let module_cache = ModuleCache::clone(&self.module_cache);
 
-            executor.exec_direct(
+            let finalize_result = executor.exec_direct(
                 proof_of_stake_module,
                 proof_of_stake_args,
                 &mut proof_of_stake_keys,
                 base_key,
                 &system_account,
-                authorization_keys,
-                blocktime,
-                deploy_hash,
+                authorization_keys.clone(),
+                block_info,
+                deploy_hash.value(),
                 gas_limit,
                 protocol_version,
                 correlation_id,
-                finalization_tc,
+                Rc::clone(&finalization_tc),
                 Phase::FinalizePayment,
-                protocol_data,
-                system_contract_cache,
-            )
+                protocol_data.clone(),
+                module_cache,
+            );
+
+            // Only successful sessions are sponsored.  If the refund fails, the deploy is charged
+            // in full rather than failed, as its payment has already been finalized.
+            let finalize_result = match sponsorship {
+                Some(sponsorship) if session_succeeded && finalize_result.is_success() => {
+                    match self.refund_sponsored_cost(
+                        correlation_id,
+                        executor,
+                        Rc::clone(&finalization_tc),
+                        sponsorship,
+                        finalize_cost_motes,
+                        &account,
+                        &system_account,
+                        block_info,
+                        deploy_hash.value(),
+                        protocol_version,
+                        protocol_data,
+                    ) {
+                        Ok(Some(effect)) => finalize_result.with_effect(effect),
+                        Ok(None) | Err(_) => finalize_result,
+                    }
+                }
+                _ => finalize_result,
+            };
+            let finalize_read_stats = finalization_tc.borrow().read_stats();
+
+            (finalize_result, finalize_read_stats)
         };
 
         execution_result_builder.set_finalize_execution_result(finalize_result);
@@ -1180,12 +2379,133 @@ where
             .build(tracking_copy.borrow().reader(), correlation_id)
             .expect("ExecutionResultBuilder not initialized properly");
 
+        // Each phase runs against its own fork, so the stats of all of them are summed up
+        let read_stats = tracking_copy.borrow().read_stats().map(|read_stats| {
+            read_stats
+                + session_read_stats.unwrap_or_default()
+                + finalize_read_stats.unwrap_or_default()
+        });
+        let ret = ret.with_read_stats(read_stats);
+
+        {
+            let tracking_copy = tracking_copy.borrow();
+            let state = TrackingCopyView(&*tracking_copy);
+            let context = DeployHookContext::new(
+                correlation_id,
+                &account,
+                &authorization_keys,
+                deploy_hash,
+                block_info.blocktime(),
+                protocol_version,
+                &state,
+            );
+            deploy_hooks.post_finalize(&context, &ret);
+        }
+
         // NOTE: payment_code_spec_5_a is enforced in execution_result_builder.build()
         // payment_code_spec_6: return properly combined set of transforms and
         // appropriate error
         Ok(ret)
     }
 
+    /// Refunds the cost of a deploy successfully calling a sponsored contract to the main purse of
+    /// `account` once its payment is finalized, by transferring up to the allowance of the
+    /// sponsorship out of the sponsorship purse on behalf of the system account.  Only what is
+    /// left in the sponsorship purse is refunded.
+    ///
+    /// Returns the effect of the finalization including the refund, or `None` if nothing was
+    /// refunded.
+    #[allow(clippy::too_many_arguments)]
+    fn refund_sponsored_cost<R>(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        finalization_tc: Rc<RefCell<TrackingCopy<R>>>,
+        sponsorship: Sponsorship,
+        cost: Motes,
+        account: &Account,
+        system_account: &Account,
+        block_info: BlockInfo,
+        deploy_hash: [u8; 32],
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+    ) -> Result<Option<ExecutionEffect>, Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
+        let mint_reference = protocol_data.mint();
+        let sponsorship_purse = sponsorship.purse();
+
+        let sponsorship_purse_balance = {
+            let mut tracking_copy = finalization_tc.borrow_mut();
+            let balance_key = tracking_copy.get_purse_balance_key(
+                correlation_id,
+                mint_reference,
+                Key::from(sponsorship_purse),
+            )?;
+            tracking_copy.get_purse_balance(correlation_id, balance_key)?
+        };
+
+        let conv_rate = protocol_data.chain_parameters().conv_rate();
+        let allowance = Motes::from_gas(sponsorship.allowance(), conv_rate).unwrap_or(cost);
+        let refund = cost.min(allowance).min(sponsorship_purse_balance);
+        if refund.value().is_zero() {
+            return Ok(None);
+        }
+
+        let mint_contract = finalization_tc
+            .borrow_mut()
+            .get_contract(correlation_id, Key::URef(mint_reference))?;
+        let mint_module = self
+            .module_cache
+            .get_or_insert_with(mint_contract.bytes(), engine_wasm_prep::deserialize)?;
+        let mut mint_keys = mint_contract.named_keys().to_owned();
+
+        let args = {
+            let args = (
+                "transfer",
+                sponsorship_purse,
+                account.purse_id().value(),
+                refund.value(),
+            );
+            ArgsParser::parse(args)
+                .expect("args should convert to `Vec<CLValue>`")
+                .into_bytes()
+                .expect("args should serialize")
+        };
+        let authorization_keys = {
+            let mut ret = BTreeSet::new();
+            ret.insert(PublicKey::new(SYSTEM_ACCOUNT_ADDR));
+            ret
+        };
+        let gas_limit = Gas::from(std::u64::MAX);
+        let module_cache = ModuleCache::clone(&self.module_cache);
+
+        let refund_result = executor.exec_direct(
+            mint_module,
+            args,
+            &mut mint_keys,
+            Key::from(mint_reference),
+            system_account,
+            authorization_keys,
+            block_info,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            finalization_tc,
+            Phase::FinalizePayment,
+            protocol_data,
+            module_cache,
+        );
+
+        match refund_result {
+            ExecutionResult::Success { effect, .. } => Ok(Some(effect)),
+            ExecutionResult::Failure { error, .. } => Err(error),
+        }
+    }
+
     pub fn apply_effect(
         &self,
         correlation_id: CorrelationId,
@@ -1209,6 +2529,50 @@ where
         }
     }
 
+    /// Commits several sets of effects, each on top of its own pre-state, in a single write
+    /// transaction of global state, e.g. the effects of all deploys of a block.
+    ///
+    /// The batch is atomic: if an entry can't be committed, the returned results end with its
+    /// failure and nothing is written.  Bonded validators are only reported once the whole batch is
+    /// committed.
+    pub fn apply_effect_batch(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        batch: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<Vec<CommitResult>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let batch_len = batch.len();
+        let commit_results = self.state.commit_batch(correlation_id, batch)?;
+        let committed = commit_results.len() == batch_len
+            && commit_results
+                .iter()
+                .all(|commit_result| match commit_result {
+                    CommitResult::Success { .. } => true,
+                    _ => false,
+                });
+        if !committed {
+            return Ok(commit_results);
+        }
+
+        commit_results
+            .into_iter()
+            .map(|commit_result| match commit_result {
+                CommitResult::Success { state_root, .. } => {
+                    let bonded_validators =
+                        self.get_bonded_validators(correlation_id, protocol_version, state_root)?;
+                    Ok(CommitResult::Success {
+                        state_root,
+                        bonded_validators,
+                    })
+                }
+                commit_result => Ok(commit_result),
+            })
+            .collect()
+    }
+
     /// Calculates bonded validators at `root_hash` state.
     ///
     /// Should only be called with a valid root hash after a successful call to
@@ -1251,3 +2615,37 @@ where
         Ok(bonded_validators)
     }
 }
+
+impl EngineState<LmdbGlobalState> {
+    /// Writes a compacted copy of the global state to `target_dir`, to replace the data directory
+    /// while the engine is stopped, e.g. once a long-running network has fragmented it.
+    ///
+    /// `storage_config` is the layout the global state was opened with.  `progress` is called as
+    /// the copy is written.  Once copied, the trie under each of `verify_roots` is checked to read
+    /// back intact from the copy.
+    pub fn compact_storage<F>(
+        &self,
+        correlation_id: CorrelationId,
+        target_dir: &Path,
+        storage_config: &LmdbStorageConfig,
+        verify_roots: &[Blake2bHash],
+        progress: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(CompactionProgress),
+    {
+        let compacted = self
+            .state
+            .copy_compacted(target_dir, storage_config, progress)?;
+        for root in verify_roots {
+            let missing_or_corrupt = compacted.missing_or_corrupt_tries(correlation_id, *root)?;
+            if !missing_or_corrupt.is_empty() {
+                return Err(Error::CorruptCompactedStorage {
+                    root: *root,
+                    tries: missing_or_corrupt.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,337 @@
+use std::sync::{Arc, Mutex};
+
+use linked_hash_map::LinkedHashMap;
+use parity_wasm::elements::Module;
+
+use engine_shared::{metrics, newtypes::Blake2bHash};
+
+/// The number of modules a [`ModuleCache`] keeps by default.
+pub const DEFAULT_MODULE_CACHE_CAPACITY: usize = 64;
+
+#[derive(Debug)]
+struct CachedModule {
+    module: Module,
+    /// The length of the Wasm bytes of the module
+    size: usize,
+}
+
+#[derive(Debug)]
+struct Modules {
+    capacity: usize,
+    entries: LinkedHashMap<Blake2bHash, CachedModule>,
+    hits: u64,
+    misses: u64,
+    bytes: usize,
+}
+
+impl Modules {
+    fn insert(&mut self, hash: Blake2bHash, module: Module, size: usize) -> Option<Module> {
+        if self.capacity == 0 {
+            return None;
+        }
+        let old_module = self.entries.insert(hash, CachedModule { module, size });
+        self.bytes += size;
+        if let Some(old_module) = old_module.as_ref() {
+            self.bytes -= old_module.size;
+        }
+        while self.entries.len() > self.capacity {
+            if let Some((_, evicted)) = self.entries.pop_front() {
+                self.bytes -= evicted.size;
+            }
+        }
+        old_module.map(|old_module| old_module.module)
+    }
+}
+
+/// A snapshot of the contents and the effectiveness of a [`ModuleCache`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ModuleCacheStats {
+    /// The number of modules held by the cache.
+    pub entries: usize,
+    /// The maximum number of modules held by the cache.
+    pub capacity: usize,
+    /// The number of lookups which found their module in the cache.
+    pub hits: u64,
+    /// The number of lookups which didn't find their module in the cache.
+    pub misses: u64,
+    /// The total length of the Wasm bytes of the modules held by the cache.
+    pub bytes: usize,
+}
+
+/// A cache of deserialized contracts, keyed by the Blake2b hash of their Wasm bytes.
+///
+/// Once the cache holds `capacity` modules, inserting another one evicts the least recently used.
+/// A capacity of zero disables caching.
+#[derive(Clone, Debug)]
+pub struct ModuleCache(Arc<Mutex<Modules>>);
+
+impl ModuleCache {
+    /// Creates an empty cache holding at most `capacity` modules.
+    pub fn new(capacity: usize) -> Self {
+        let modules = Modules {
+            capacity,
+            entries: LinkedHashMap::new(),
+            hits: 0,
+            misses: 0,
+            bytes: 0,
+        };
+        ModuleCache(Arc::new(Mutex::new(modules)))
+    }
+
+    /// Returns the maximum number of modules held by the cache.
+    pub fn capacity(&self) -> usize {
+        self.0.lock().unwrap().capacity
+    }
+
+    /// Returns the number of modules held by the cache.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().entries.len()
+    }
+
+    /// Returns `true` if the cache holds no modules.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of modules held, the number of lookups which hit and missed the cache so
+    /// far, and the total size of the modules.
+    pub fn stats(&self) -> ModuleCacheStats {
+        let modules = self.0.lock().unwrap();
+        ModuleCacheStats {
+            entries: modules.entries.len(),
+            capacity: modules.capacity,
+            hits: modules.hits,
+            misses: modules.misses,
+            bytes: modules.bytes,
+        }
+    }
+
+    /// Returns `true` if the cache has a contract corresponding to `hash`.
+    pub fn has(&self, hash: &Blake2bHash) -> bool {
+        self.0.lock().unwrap().entries.contains_key(hash)
+    }
+
+    /// Inserts `contract` into the cache under `hash`, evicting the least recently used contract
+    /// if the cache is full.
+    ///
+    /// If the cache did not have this key present, `None` is returned.
+    ///
+    /// If the cache did have this key present, the value is updated, and the old value is returned.
+    pub fn insert(&self, hash: Blake2bHash, contract: Module) -> Option<Module> {
+        let size = parity_wasm::serialize(contract.clone())
+            .map(|bytes| bytes.len())
+            .unwrap_or_default();
+        self.0.lock().unwrap().insert(hash, contract, size)
+    }
+
+    /// Removes the contract corresponding to `hash` from the cache, so that it is deserialized
+    /// again the next time it is needed.
+    ///
+    /// Returns the removed contract, if the cache held it.
+    pub fn remove(&self, hash: &Blake2bHash) -> Option<Module> {
+        let mut modules = self.0.lock().unwrap();
+        let removed = modules.entries.remove(hash)?;
+        modules.bytes -= removed.size;
+        Some(removed.module)
+    }
+
+    /// Returns a clone of the contract corresponding to `hash`, marking it as the most recently
+    /// used.
+    pub fn get(&self, hash: &Blake2bHash) -> Option<Module> {
+        let mut modules = self.0.lock().unwrap();
+        let module = modules
+            .entries
+            .get_refresh(hash)
+            .map(|cached_module| cached_module.module.clone());
+        if module.is_some() {
+            modules.hits += 1;
+        } else {
+            modules.misses += 1;
+        }
+        metrics::record_module_cache_lookup(module.is_some());
+        module
+    }
+
+    /// Returns the contract deserialized from `bytes`, calling `deserialize` and caching its
+    /// result only if the cache doesn't hold it yet.
+    pub fn get_or_insert_with<F, E>(&self, bytes: &[u8], deserialize: F) -> Result<Module, E>
+    where
+        F: FnOnce(&[u8]) -> Result<Module, E>,
+    {
+        let hash = Blake2bHash::new(bytes);
+        if let Some(module) = self.get(&hash) {
+            return Ok(module);
+        }
+        let module = deserialize(bytes)?;
+        self.0
+            .lock()
+            .unwrap()
+            .insert(hash, module.clone(), bytes.len());
+        Ok(module)
+    }
+}
+
+impl Default for ModuleCache {
+    fn default() -> Self {
+        ModuleCache::new(DEFAULT_MODULE_CACHE_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use parity_wasm::elements::{Module, ModuleNameSection, NameSection, Section};
+
+    use engine_shared::newtypes::Blake2bHash;
+
+    use super::{ModuleCache, ModuleCacheStats};
+
+    fn named_module(name: &str) -> Module {
+        let section = NameSection::Module(ModuleNameSection::new(name));
+        Module::new(vec![Section::Name(section)])
+    }
+
+    fn hash(name: &str) -> Blake2bHash {
+        Blake2bHash::new(name.as_bytes())
+    }
+
+    #[test]
+    fn should_insert_module() {
+        let cache = ModuleCache::default();
+
+        let result = cache.insert(hash("a"), Module::default());
+
+        assert!(result.is_none());
+        assert!(cache.has(&hash("a")));
+        assert!(!cache.has(&hash("b")));
+    }
+
+    #[test]
+    fn should_get_none() {
+        let cache = ModuleCache::default();
+
+        assert!(cache.get(&hash("a")).is_none())
+    }
+
+    #[test]
+    fn should_get_module() {
+        let cache = ModuleCache::default();
+        let module = named_module("a_mod");
+
+        cache.insert(hash("a"), module.clone());
+
+        assert_eq!(cache.get(&hash("a")), Some(module))
+    }
+
+    #[test]
+    fn should_update_module() {
+        let cache = ModuleCache::default();
+        let initial_module = Module::default();
+        let updated_module = named_module("a_mod");
+
+        assert_ne!(initial_module, updated_module);
+
+        let result = cache.insert(hash("a"), initial_module.clone());
+
+        assert!(result.is_none());
+
+        let result = cache.insert(hash("a"), updated_module.clone());
+
+        assert_eq!(result, Some(initial_module));
+        assert_eq!(cache.get(&hash("a")), Some(updated_module));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_module() {
+        let cache = ModuleCache::new(2);
+
+        cache.insert(hash("a"), named_module("a"));
+        cache.insert(hash("b"), named_module("b"));
+        // Refreshes "a", leaving "b" as the least recently used module
+        assert!(cache.get(&hash("a")).is_some());
+        cache.insert(hash("c"), named_module("c"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.has(&hash("a")));
+        assert!(!cache.has(&hash("b")));
+        assert!(cache.has(&hash("c")));
+    }
+
+    #[test]
+    fn should_not_cache_with_zero_capacity() {
+        let cache = ModuleCache::new(0);
+
+        let result = cache.insert(hash("a"), Module::default());
+
+        assert!(result.is_none());
+        assert!(cache.is_empty());
+        assert!(cache.get(&hash("a")).is_none());
+    }
+
+    #[test]
+    fn should_deserialize_only_on_miss() {
+        let cache = ModuleCache::default();
+        let bytes = b"module bytes";
+        let calls = Cell::new(0);
+        let deserialize = |_: &[u8]| -> Result<Module, ()> {
+            calls.set(calls.get() + 1);
+            Ok(named_module("a_mod"))
+        };
+
+        let first = cache.get_or_insert_with(bytes, deserialize);
+        let second = cache.get_or_insert_with(bytes, deserialize);
+
+        assert_eq!(calls.get(), 1);
+        assert_eq!(first, second);
+        assert!(cache.has(&Blake2bHash::new(bytes)));
+    }
+
+    #[test]
+    fn should_count_hits_misses_and_bytes() {
+        let cache = ModuleCache::new(2);
+        let deserialize = |_: &[u8]| -> Result<Module, ()> { Ok(named_module("a_mod")) };
+
+        cache.get_or_insert_with(b"a", deserialize).unwrap();
+        cache.get_or_insert_with(b"a", deserialize).unwrap();
+        cache.get_or_insert_with(b"bb", deserialize).unwrap();
+        cache.get_or_insert_with(b"ccc", deserialize).unwrap();
+
+        let expected = ModuleCacheStats {
+            entries: 2,
+            capacity: 2,
+            hits: 1,
+            misses: 3,
+            // "a" was evicted when "ccc" was inserted
+            bytes: 5,
+        };
+        assert_eq!(cache.stats(), expected);
+    }
+
+    #[test]
+    fn should_remove_module() {
+        let cache = ModuleCache::default();
+        let deserialize = |_: &[u8]| -> Result<Module, ()> { Ok(named_module("a_mod")) };
+        let bytes = b"module bytes";
+
+        cache.get_or_insert_with(bytes, deserialize).unwrap();
+        let removed = cache.remove(&Blake2bHash::new(bytes));
+
+        assert_eq!(removed, Some(named_module("a_mod")));
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats().bytes, 0);
+        assert!(cache.remove(&Blake2bHash::new(bytes)).is_none());
+    }
+
+    #[test]
+    fn should_not_cache_deserialization_errors() {
+        let cache = ModuleCache::default();
+        let bytes = b"invalid module bytes";
+
+        let result = cache.get_or_insert_with(bytes, |_| Err(()));
+
+        assert_eq!(result, Err(()));
+        assert!(cache.is_empty());
+    }
+}
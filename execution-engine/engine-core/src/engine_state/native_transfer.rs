@@ -0,0 +1,291 @@
+//! Transfers of motes between accounts which are executed by the engine itself, rather than by
+//! session code calling the mint contract.
+//!
+//! Purses are created by calling the mint contract, and balances are updated in the same layout as
+//! the mint contract keeps them, so that the mint contract can use them as any other.
+
+use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
+
+use contract::args_parser::ArgsParser;
+use engine_shared::{
+    account::Account, gas::Gas, newtypes::CorrelationId, stored_value::StoredValue,
+};
+use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
+use types::{
+    account::{PublicKey, PurseId},
+    system_contract_errors::mint,
+    AccessRights, BlockTime, CLValue, Key, Phase, ProtocolVersion, URef, U512,
+};
+
+use crate::{
+    engine_state::{
+        block_info::BlockInfo, execution_result::ExecutionResult, module_cache::ModuleCache, Error,
+    },
+    execution::{self, AddressGenerator, Executor, MINT_NAME, POS_NAME},
+    tracking_copy::{AddResult, TrackingCopy, TrackingCopyExt},
+};
+
+/// The method of the mint contract creating an empty purse.
+const MINT_CREATE_METHOD_NAME: &str = "create";
+
+/// Executes a native transfer as the session of a deploy made by `account`.
+///
+/// The transfer is charged the native transfer cost of the chain parameters, and fails without
+/// being attempted if `gas_limit` doesn't cover that.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn exec<R>(
+    correlation_id: CorrelationId,
+    executor: &Executor,
+    module_cache: ModuleCache,
+    tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+    account: &Account,
+    authorization_keys: BTreeSet<PublicKey>,
+    target: PublicKey,
+    amount: U512,
+    gas_limit: Gas,
+    block_info: BlockInfo,
+    deploy_hash: [u8; 32],
+    protocol_version: ProtocolVersion,
+    protocol_data: &ProtocolData,
+) -> ExecutionResult
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    let cost = protocol_data.chain_parameters().native_transfer_cost();
+    if gas_limit < cost {
+        return ExecutionResult::Failure {
+            error: Error::ExecError(execution::Error::GasLimit),
+            effect: Default::default(),
+            cost: gas_limit,
+            read_stats: None,
+            effects_by_phase: None,
+            stdout: Vec::new(),
+            host_function_profile: None,
+        };
+    }
+
+    let create_target_purse = || {
+        create_purse(
+            correlation_id,
+            executor,
+            module_cache,
+            Rc::clone(&tracking_copy),
+            account,
+            authorization_keys,
+            block_info,
+            deploy_hash,
+            protocol_version,
+            protocol_data,
+        )
+    };
+    let result = transfer(
+        correlation_id,
+        &tracking_copy,
+        protocol_data,
+        account,
+        target,
+        amount,
+        block_info.blocktime(),
+        create_target_purse,
+    );
+    let effect = tracking_copy.borrow().effect();
+
+    match result {
+        Ok(()) => ExecutionResult::Success {
+            effect,
+            cost,
+            read_stats: None,
+            effects_by_phase: None,
+            stdout: Vec::new(),
+            host_function_profile: None,
+        },
+        Err(error) => ExecutionResult::Failure {
+            error,
+            effect,
+            cost,
+            read_stats: None,
+            effects_by_phase: None,
+            stdout: Vec::new(),
+            host_function_profile: None,
+        },
+    }
+}
+
+/// Transfers `amount` motes from the main purse of `account` to the main purse of the account
+/// `target`, which is created with a purse from `create_purse` if it doesn't exist yet.
+///
+/// As with transfers through the mint contract, the motes still locked by the vesting schedule of
+/// `account` can't be transferred.
+#[allow(clippy::too_many_arguments)]
+fn transfer<R, F>(
+    correlation_id: CorrelationId,
+    tracking_copy: &RefCell<TrackingCopy<R>>,
+    protocol_data: &ProtocolData,
+    account: &Account,
+    target: PublicKey,
+    amount: U512,
+    blocktime: BlockTime,
+    create_purse: F,
+) -> Result<(), Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+    F: FnOnce() -> Result<PurseId, Error>,
+{
+    let mint_reference = protocol_data.mint();
+    let source = account.purse_id();
+
+    let source_balance_key = tracking_copy.borrow_mut().get_purse_balance_key(
+        correlation_id,
+        mint_reference,
+        Key::URef(source.value()),
+    )?;
+    let source_balance = tracking_copy
+        .borrow_mut()
+        .get_purse_balance(correlation_id, source_balance_key)?
+        .value();
+    if source_balance < amount {
+        return Err(Error::MintError(mint::Error::InsufficientFunds));
+    }
+    if let Some(vesting_schedule) = account.vesting_schedule() {
+        if source_balance - amount < vesting_schedule.locked_amount_at(blocktime) {
+            return Err(Error::MintError(mint::Error::FundsLocked));
+        }
+    }
+
+    let target_key = Key::Account(target.value());
+    let maybe_target = tracking_copy
+        .borrow_mut()
+        .read(correlation_id, &target_key)
+        .map_err(Into::into)?;
+    let target_purse = match maybe_target {
+        Some(StoredValue::Account(target_account)) => {
+            let target_purse = target_account.purse_id();
+            if target_purse.value().addr() == source.value().addr() {
+                return Ok(());
+            }
+            target_purse
+        }
+        Some(_) => {
+            return Err(Error::ExecError(execution::Error::AccountNotFound(
+                target_key,
+            )))
+        }
+        None => {
+            let target_purse = create_purse()?;
+            let named_keys = vec![
+                (MINT_NAME.to_string(), mint_reference),
+                (POS_NAME.to_string(), protocol_data.proof_of_stake()),
+            ]
+            .into_iter()
+            .map(|(name, uref)| (name, Key::URef(URef::new(uref.addr(), AccessRights::READ))))
+            .collect();
+            let target_account = Account::create(target.value(), named_keys, target_purse);
+            tracking_copy
+                .borrow_mut()
+                .write(target_key, StoredValue::Account(target_account));
+            target_purse
+        }
+    };
+    let target_balance_key = tracking_copy.borrow_mut().get_purse_balance_key(
+        correlation_id,
+        mint_reference,
+        Key::URef(target_purse.value()),
+    )?;
+
+    let new_source_balance =
+        CLValue::from_t(source_balance - amount).map_err(execution::Error::from)?;
+    tracking_copy
+        .borrow_mut()
+        .write(source_balance_key, StoredValue::CLValue(new_source_balance));
+    let amount = CLValue::from_t(amount).map_err(execution::Error::from)?;
+    add(
+        correlation_id,
+        &mut tracking_copy.borrow_mut(),
+        target_balance_key,
+        amount,
+    )?;
+    Ok(())
+}
+
+/// Creates an empty purse by calling the `create` method of the mint contract on behalf of
+/// `account`, just as session code would, so that purses are only ever laid out by the mint.
+#[allow(clippy::too_many_arguments)]
+fn create_purse<R>(
+    correlation_id: CorrelationId,
+    executor: &Executor,
+    module_cache: ModuleCache,
+    tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+    account: &Account,
+    authorization_keys: BTreeSet<PublicKey>,
+    block_info: BlockInfo,
+    deploy_hash: [u8; 32],
+    protocol_version: ProtocolVersion,
+    protocol_data: &ProtocolData,
+) -> Result<PurseId, Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    let mint_reference = protocol_data.mint();
+    let mint_contract = tracking_copy
+        .borrow_mut()
+        .get_contract(correlation_id, Key::from(mint_reference))?;
+    let mint_module =
+        module_cache.get_or_insert_with(mint_contract.bytes(), engine_wasm_prep::deserialize)?;
+    let mut mint_keys = mint_contract.named_keys().to_owned();
+
+    let args = ArgsParser::parse((MINT_CREATE_METHOD_NAME,))
+        .expect("args should convert to `Vec<CLValue>`")
+        .into_bytes()
+        .expect("args should serialize");
+    let address_generator = {
+        let generator = AddressGenerator::new(deploy_hash, Phase::Session, protocol_version);
+        Rc::new(RefCell::new(generator))
+    };
+    // the purse is paid for by the native transfer cost; approximating with MAX
+    let gas_limit = Gas::from(std::u64::MAX);
+
+    let purse: URef = executor.better_exec(
+        mint_module,
+        args,
+        &mut mint_keys,
+        Key::from(mint_reference),
+        account,
+        authorization_keys,
+        block_info,
+        deploy_hash,
+        gas_limit,
+        address_generator,
+        protocol_version,
+        correlation_id,
+        tracking_copy,
+        Phase::Session,
+        protocol_data.clone(),
+        module_cache,
+    )?;
+    Ok(PurseId::new(purse))
+}
+
+fn add<R>(
+    correlation_id: CorrelationId,
+    tracking_copy: &mut TrackingCopy<R>,
+    key: Key,
+    value: CLValue,
+) -> Result<(), execution::Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    let error = match tracking_copy
+        .add(correlation_id, key, StoredValue::CLValue(value))
+        .map_err(Into::into)?
+    {
+        AddResult::Success => return Ok(()),
+        AddResult::KeyNotFound(key) => execution::Error::KeyNotFound(key),
+        AddResult::TypeMismatch(type_mismatch) => execution::Error::TypeMismatch(type_mismatch),
+        AddResult::Serialization(error) => execution::Error::BytesRepr(error),
+    };
+    Err(error)
+}
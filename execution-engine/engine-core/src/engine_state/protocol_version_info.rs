@@ -0,0 +1,46 @@
+use std::collections::BTreeMap;
+
+use engine_shared::newtypes::Blake2bHash;
+use engine_storage::protocol_data::ProtocolData;
+use types::{
+    bytesrepr::{self, ToBytes},
+    ProtocolVersion, URef,
+};
+
+/// Summary of the [`ProtocolData`] stored for a protocol version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolVersionInfo {
+    protocol_version: ProtocolVersion,
+    wasm_costs_digest: Blake2bHash,
+    system_contracts: BTreeMap<String, URef>,
+}
+
+impl ProtocolVersionInfo {
+    pub fn new(
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<Self, bytesrepr::Error> {
+        let wasm_costs_digest = Blake2bHash::new(&protocol_data.wasm_costs().to_bytes()?);
+        let system_contracts = protocol_data.system_contract_registry().clone();
+        Ok(ProtocolVersionInfo {
+            protocol_version,
+            wasm_costs_digest,
+            system_contracts,
+        })
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    /// Blake2b hash of the serialized `WasmCosts`, so that cost tables can be compared at a
+    /// glance.
+    pub fn wasm_costs_digest(&self) -> Blake2bHash {
+        self.wasm_costs_digest
+    }
+
+    /// The system contracts registered for the protocol version, keyed by name.
+    pub fn system_contracts(&self) -> &BTreeMap<String, URef> {
+        &self.system_contracts
+    }
+}
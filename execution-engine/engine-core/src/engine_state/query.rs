@@ -1,12 +1,42 @@
 use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
-use types::Key;
+use types::{ContractMetadata, Key, LOCAL_SEED_LENGTH};
 
 use crate::tracking_copy::TrackingCopyQueryResult;
 
 pub enum QueryResult {
     RootNotFound,
-    ValueNotFound(String),
-    Success(StoredValue),
+    ValueNotFound {
+        message: String,
+        visited_path: Vec<QueryPathStep>,
+    },
+    Success {
+        value: StoredValue,
+        visited_path: Vec<QueryPathStep>,
+    },
+}
+
+/// A step taken while resolving the path of a query, kept to help debug queries which don't find
+/// what they are expected to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryPathStep {
+    /// The value under the key was read.
+    Read(Key),
+    /// The segment was looked up in a stored list or map.
+    Index(String),
+    /// The segment named a namespace of local state with the given seed.
+    Namespace {
+        name: String,
+        seed: [u8; LOCAL_SEED_LENGTH],
+    },
+}
+
+/// Result of looking up the [`ContractMetadata`] of a stored contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractMetadataResult {
+    RootNotFound,
+    ContractNotFound,
+    /// The contract exists; it carries no metadata if it was compiled without it.
+    Success(Option<ContractMetadata>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -38,13 +68,19 @@ impl QueryRequest {
     }
 }
 
-impl From<TrackingCopyQueryResult> for QueryResult {
-    fn from(tracking_copy_query_result: TrackingCopyQueryResult) -> Self {
+impl From<(TrackingCopyQueryResult, Vec<QueryPathStep>)> for QueryResult {
+    fn from(
+        (tracking_copy_query_result, visited_path): (TrackingCopyQueryResult, Vec<QueryPathStep>),
+    ) -> Self {
         match tracking_copy_query_result {
-            TrackingCopyQueryResult::ValueNotFound(full_path) => {
-                QueryResult::ValueNotFound(full_path)
-            }
-            TrackingCopyQueryResult::Success(value) => QueryResult::Success(value),
+            TrackingCopyQueryResult::ValueNotFound(message) => QueryResult::ValueNotFound {
+                message,
+                visited_path,
+            },
+            TrackingCopyQueryResult::Success(value) => QueryResult::Success {
+                value,
+                visited_path,
+            },
         }
     }
 }
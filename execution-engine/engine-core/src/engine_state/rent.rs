@@ -0,0 +1,404 @@
+//! Storage rent prototype, enabled by the `rent` feature.
+//!
+//! While rent is enabled in the [`ProtocolData`](engine_storage::protocol_data::ProtocolData),
+//! the block time at which each value subject to rent was last accessed is kept in global state,
+//! in a metadata entry stored under a key derived from the value's key.  Collecting rent
+//! tombstones the values which haven't been accessed for longer than the configured expiry.
+//! Global state doesn't support removing keys, so tombstoned values are overwritten with a unit
+//! [`CLValue`].  Purse balances are never tombstoned, as that would destroy the motes they hold.
+
+use std::fmt;
+
+use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue, transform::TypeMismatch};
+use engine_storage::global_state::CommitResult;
+use types::{
+    bytesrepr::{self, ToBytes},
+    BlockTime, CLTyped, CLValue, Key, U512,
+};
+
+use crate::engine_state::execution_effect::ExecutionEffect;
+
+/// Prefix of the serialized key hashed into the key of its metadata entry.
+const METADATA_KEY_PREFIX: &[u8] = b"rent_last_accessed";
+
+/// Returns whether the value under `key` is subject to rent.  Accounts and the local state of
+/// contracts are exempt.
+pub fn is_subject_to_rent(key: &Key) -> bool {
+    match key {
+        Key::URef(_) | Key::Hash(_) => true,
+        Key::Account(_) | Key::Local(_) => false,
+    }
+}
+
+/// Returns whether `value`, stored under `key`, is the balance of a purse, which the mint keeps as
+/// a [`U512`] under a [`Key::URef`].  Balances are exempt from rent.
+pub fn is_purse_balance(key: &Key, value: &StoredValue) -> bool {
+    match (key, value) {
+        (Key::URef(_), StoredValue::CLValue(cl_value)) => *cl_value.cl_type() == U512::cl_type(),
+        _ => false,
+    }
+}
+
+/// Returns the key of the metadata entry tracking when the value under `key` was last accessed.
+pub fn metadata_key(key: &Key) -> Key {
+    let mut bytes = METADATA_KEY_PREFIX.to_vec();
+    bytes.append(&mut key.normalize().to_bytes().expect("key should serialize"));
+    Key::Hash(Blake2bHash::new(&bytes).into())
+}
+
+/// Returns the metadata entry recording that the value under `key` was last accessed at
+/// `blocktime`.
+pub fn metadata_value(key: Key, blocktime: BlockTime) -> StoredValue {
+    let last_accessed: u64 = blocktime.into();
+    let cl_value =
+        CLValue::from_t((key.normalize(), last_accessed)).expect("metadata should serialize");
+    StoredValue::CLValue(cl_value)
+}
+
+/// If `value` is the metadata entry stored under `metadata_key`, returns the key it tracks and
+/// the block time at which the value under that key was last accessed.
+pub fn parse_metadata(metadata_key: &Key, value: &StoredValue) -> Option<(Key, BlockTime)> {
+    let cl_value = match value {
+        StoredValue::CLValue(cl_value) if *cl_value.cl_type() == <(Key, u64)>::cl_type() => {
+            cl_value.clone()
+        }
+        _ => return None,
+    };
+    let (key, last_accessed): (Key, u64) = cl_value.into_t().ok()?;
+    if self::metadata_key(&key) != *metadata_key {
+        return None;
+    }
+    Some((key, BlockTime::new(last_accessed)))
+}
+
+/// Returns the block time at which a value last accessed at `last_accessed` expires.
+pub fn expires_at(last_accessed: BlockTime, expiry: u64) -> BlockTime {
+    let last_accessed: u64 = last_accessed.into();
+    BlockTime::new(last_accessed.saturating_add(expiry))
+}
+
+/// Returns the value expired values are overwritten with.
+pub fn tombstone() -> StoredValue {
+    StoredValue::CLValue(CLValue::from_t(()).expect("unit should serialize"))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RentStatus {
+    RootNotFound,
+    /// Rent is not enabled in the protocol data.
+    Disabled,
+    /// The value is not subject to rent.
+    Exempt,
+    /// The value hasn't been accessed since rent was enabled, or it has been tombstoned.
+    Untracked,
+    Tracked {
+        last_accessed: BlockTime,
+        expires_at: BlockTime,
+    },
+}
+
+pub enum RentResult {
+    RootNotFound,
+    /// Rent is not enabled in the protocol data.
+    Disabled,
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Serialization(bytesrepr::Error),
+    Success {
+        post_state_hash: Blake2bHash,
+        effect: ExecutionEffect,
+        tombstoned: Vec<Key>,
+    },
+}
+
+impl fmt::Display for RentResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            RentResult::RootNotFound => write!(f, "Root not found"),
+            RentResult::Disabled => write!(f, "Rent disabled"),
+            RentResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            RentResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            RentResult::Serialization(error) => write!(f, "Serialization error: {:?}", error),
+            RentResult::Success {
+                post_state_hash,
+                effect,
+                tombstoned,
+            } => write!(
+                f,
+                "Success: {} {:?} tombstoned: {:?}",
+                post_state_hash, effect, tombstoned
+            ),
+        }
+    }
+}
+
+impl RentResult {
+    pub fn from_commit_result(
+        commit_result: CommitResult,
+        effect: ExecutionEffect,
+        tombstoned: Vec<Key>,
+    ) -> Self {
+        match commit_result {
+            CommitResult::RootNotFound => RentResult::RootNotFound,
+            CommitResult::KeyNotFound(key) => RentResult::KeyNotFound(key),
+            CommitResult::TypeMismatch(type_mismatch) => RentResult::TypeMismatch(type_mismatch),
+            CommitResult::Serialization(error) => RentResult::Serialization(error),
+            CommitResult::Success { state_root, .. } => RentResult::Success {
+                post_state_hash: state_root,
+                effect,
+                tombstoned,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_shared::{newtypes::CorrelationId, stored_value::StoredValue};
+    use engine_storage::{
+        global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader},
+        protocol_data::{ProtocolData, RentParameters},
+    };
+    use types::{AccessRights, BlockTime, CLValue, Key, ProtocolVersion, URef, U512};
+
+    use super::{RentResult, RentStatus};
+    use crate::engine_state::{EngineConfig, EngineState};
+
+    const EXPIRY: u64 = 10;
+
+    fn value(n: i32) -> StoredValue {
+        StoredValue::CLValue(CLValue::from_t(n).expect("should create CLValue"))
+    }
+
+    #[test]
+    fn should_parse_metadata() {
+        let key = Key::URef(URef::new([1; 32], AccessRights::READ_ADD_WRITE));
+        let metadata_key = super::metadata_key(&key);
+        assert_eq!(metadata_key, super::metadata_key(&key.normalize()));
+
+        let metadata_value = super::metadata_value(key, BlockTime::new(5));
+        assert_eq!(
+            super::parse_metadata(&metadata_key, &metadata_value),
+            Some((key.normalize(), BlockTime::new(5)))
+        );
+
+        let other_key = Key::Hash([2; 32]);
+        assert_eq!(
+            super::parse_metadata(&super::metadata_key(&other_key), &metadata_value),
+            None
+        );
+        assert_eq!(super::parse_metadata(&metadata_key, &value(1)), None);
+        assert_eq!(
+            super::parse_metadata(&metadata_key, &super::tombstone()),
+            None
+        );
+    }
+
+    #[test]
+    fn should_collect_rent_of_expired_keys() {
+        let correlation_id = CorrelationId::new();
+        let protocol_version = ProtocolVersion::V1_0_0;
+
+        let expired_key = Key::Hash([1; 32]);
+        let live_key = Key::Hash([2; 32]);
+        let untracked_key = Key::Hash([3; 32]);
+        let account_key = Key::Account([4; 32]);
+        let (state, root_hash) = InMemoryGlobalState::from_pairs(
+            correlation_id,
+            &[
+                (expired_key, value(1)),
+                (live_key, value(2)),
+                (untracked_key, value(3)),
+                (account_key, value(4)),
+                (
+                    super::metadata_key(&expired_key),
+                    super::metadata_value(expired_key, BlockTime::new(0)),
+                ),
+                (
+                    super::metadata_key(&live_key),
+                    super::metadata_value(live_key, BlockTime::new(5)),
+                ),
+            ],
+        )
+        .expect("should create global state");
+        let protocol_data =
+            ProtocolData::default().with_rent_parameters(RentParameters::new(true, EXPIRY));
+        state
+            .put_protocol_data(protocol_version, &protocol_data)
+            .expect("should put protocol data");
+        let engine_state = EngineState::new(state, EngineConfig::new());
+
+        assert_eq!(
+            engine_state
+                .get_rent_status(correlation_id, protocol_version, root_hash, live_key)
+                .expect("should get rent status"),
+            RentStatus::Tracked {
+                last_accessed: BlockTime::new(5),
+                expires_at: BlockTime::new(15),
+            }
+        );
+        assert_eq!(
+            engine_state
+                .get_rent_status(correlation_id, protocol_version, root_hash, untracked_key)
+                .expect("should get rent status"),
+            RentStatus::Untracked
+        );
+        assert_eq!(
+            engine_state
+                .get_rent_status(correlation_id, protocol_version, root_hash, account_key)
+                .expect("should get rent status"),
+            RentStatus::Exempt
+        );
+
+        let post_state_hash = match engine_state
+            .collect_rent(
+                correlation_id,
+                protocol_version,
+                root_hash,
+                BlockTime::new(EXPIRY),
+            )
+            .expect("should collect rent")
+        {
+            RentResult::Success {
+                post_state_hash,
+                tombstoned,
+                ..
+            } => {
+                assert_eq!(tombstoned, vec![expired_key]);
+                post_state_hash
+            }
+            other => panic!("unexpected rent result: {}", other),
+        };
+
+        let reader = engine_state
+            .state
+            .checkout(post_state_hash)
+            .expect("should checkout")
+            .expect("should have root");
+        let read = |key: &Key| reader.read(correlation_id, key).expect("should read");
+        assert_eq!(read(&expired_key), Some(super::tombstone()));
+        assert_eq!(read(&live_key), Some(value(2)));
+        assert_eq!(read(&untracked_key), Some(value(3)));
+        assert_eq!(
+            engine_state
+                .get_rent_status(
+                    correlation_id,
+                    protocol_version,
+                    post_state_hash,
+                    expired_key
+                )
+                .expect("should get rent status"),
+            RentStatus::Untracked
+        );
+    }
+
+    #[test]
+    fn should_not_collect_rent_of_purse_balances() {
+        let correlation_id = CorrelationId::new();
+        let protocol_version = ProtocolVersion::V1_0_0;
+
+        let balance_key = Key::URef(URef::new([1; 32], AccessRights::READ_ADD_WRITE));
+        let balance = StoredValue::CLValue(
+            CLValue::from_t(U512::from(1_000)).expect("should create CLValue"),
+        );
+        let (state, root_hash) = InMemoryGlobalState::from_pairs(
+            correlation_id,
+            &[
+                (balance_key, balance.clone()),
+                (
+                    super::metadata_key(&balance_key),
+                    super::metadata_value(balance_key, BlockTime::new(0)),
+                ),
+            ],
+        )
+        .expect("should create global state");
+        let protocol_data =
+            ProtocolData::default().with_rent_parameters(RentParameters::new(true, EXPIRY));
+        state
+            .put_protocol_data(protocol_version, &protocol_data)
+            .expect("should put protocol data");
+        let engine_state = EngineState::new(state, EngineConfig::new());
+
+        assert_eq!(
+            engine_state
+                .get_rent_status(correlation_id, protocol_version, root_hash, balance_key)
+                .expect("should get rent status"),
+            RentStatus::Exempt
+        );
+
+        let post_state_hash = match engine_state
+            .collect_rent(
+                correlation_id,
+                protocol_version,
+                root_hash,
+                BlockTime::new(100 * EXPIRY),
+            )
+            .expect("should collect rent")
+        {
+            RentResult::Success {
+                post_state_hash,
+                tombstoned,
+                ..
+            } => {
+                assert!(tombstoned.is_empty());
+                post_state_hash
+            }
+            other => panic!("unexpected rent result: {}", other),
+        };
+
+        let reader = engine_state
+            .state
+            .checkout(post_state_hash)
+            .expect("should checkout")
+            .expect("should have root");
+        assert_eq!(
+            reader
+                .read(correlation_id, &balance_key)
+                .expect("should read"),
+            Some(balance)
+        );
+    }
+
+    #[test]
+    fn should_not_collect_rent_if_disabled() {
+        let correlation_id = CorrelationId::new();
+        let protocol_version = ProtocolVersion::V1_0_0;
+        let key = Key::Hash([1; 32]);
+        let (state, root_hash) = InMemoryGlobalState::from_pairs(
+            correlation_id,
+            &[
+                (key, value(1)),
+                (
+                    super::metadata_key(&key),
+                    super::metadata_value(key, BlockTime::new(0)),
+                ),
+            ],
+        )
+        .expect("should create global state");
+        state
+            .put_protocol_data(protocol_version, &ProtocolData::default())
+            .expect("should put protocol data");
+        let engine_state = EngineState::new(state, EngineConfig::new());
+
+        match engine_state
+            .collect_rent(
+                correlation_id,
+                protocol_version,
+                root_hash,
+                BlockTime::new(100),
+            )
+            .expect("should collect rent")
+        {
+            RentResult::Disabled => (),
+            other => panic!("unexpected rent result: {}", other),
+        }
+        assert_eq!(
+            engine_state
+                .get_rent_status(correlation_id, protocol_version, root_hash, key)
+                .expect("should get rent status"),
+            RentStatus::Disabled
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use std::fmt;
+
+use engine_shared::{newtypes::Blake2bHash, transform::TypeMismatch};
+use engine_storage::global_state::CommitResult;
+use types::{bytesrepr, Key};
+
+use crate::engine_state::execution_effect::ExecutionEffect;
+
+/// The outcome of committing the effects of a call the engine makes as the system rather than on
+/// behalf of a deploy, such as an upgrade or the slashing of validators.
+pub enum SystemCallResult {
+    RootNotFound,
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Serialization(bytesrepr::Error),
+    Success {
+        post_state_hash: Blake2bHash,
+        effect: ExecutionEffect,
+    },
+}
+
+impl fmt::Display for SystemCallResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            SystemCallResult::RootNotFound => write!(f, "Root not found"),
+            SystemCallResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            SystemCallResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            SystemCallResult::Serialization(error) => write!(f, "Serialization error: {:?}", error),
+            SystemCallResult::Success {
+                post_state_hash,
+                effect,
+            } => write!(f, "Success: {} {:?}", post_state_hash, effect),
+        }
+    }
+}
+
+impl SystemCallResult {
+    pub fn from_commit_result(commit_result: CommitResult, effect: ExecutionEffect) -> Self {
+        match commit_result {
+            CommitResult::RootNotFound => SystemCallResult::RootNotFound,
+            CommitResult::KeyNotFound(key) => SystemCallResult::KeyNotFound(key),
+            CommitResult::TypeMismatch(type_mismatch) => {
+                SystemCallResult::TypeMismatch(type_mismatch)
+            }
+            CommitResult::Serialization(error) => SystemCallResult::Serialization(error),
+            CommitResult::Success { state_root, .. } => SystemCallResult::Success {
+                post_state_hash: state_root,
+                effect,
+            },
+        }
+    }
+}
@@ -1,56 +1,19 @@
-use std::fmt;
+use std::collections::BTreeMap;
 
-use engine_shared::{newtypes::Blake2bHash, transform::TypeMismatch};
-use engine_storage::global_state::CommitResult;
-use engine_wasm_prep::wasm_costs::WasmCosts;
-use types::{bytesrepr, Key, ProtocolVersion};
+use engine_shared::newtypes::Blake2bHash;
+use engine_storage::protocol_data::{ChainParameters, RentParameters, Sponsorship};
+use engine_wasm_prep::{
+    denied_host_functions::DeniedHostFunctions, gas_metering::GasMetering,
+    host_function_costs::HostFunctionCosts, wasm_costs::WasmCosts,
+};
+use types::ProtocolVersion;
 
-use crate::engine_state::execution_effect::ExecutionEffect;
+use crate::engine_state::system_call_result::SystemCallResult;
 
 pub type ActivationPoint = u64;
 
-pub enum UpgradeResult {
-    RootNotFound,
-    KeyNotFound(Key),
-    TypeMismatch(TypeMismatch),
-    Serialization(bytesrepr::Error),
-    Success {
-        post_state_hash: Blake2bHash,
-        effect: ExecutionEffect,
-    },
-}
-
-impl fmt::Display for UpgradeResult {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        match self {
-            UpgradeResult::RootNotFound => write!(f, "Root not found"),
-            UpgradeResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
-            UpgradeResult::TypeMismatch(type_mismatch) => {
-                write!(f, "Type mismatch: {:?}", type_mismatch)
-            }
-            UpgradeResult::Serialization(error) => write!(f, "Serialization error: {:?}", error),
-            UpgradeResult::Success {
-                post_state_hash,
-                effect,
-            } => write!(f, "Success: {} {:?}", post_state_hash, effect),
-        }
-    }
-}
-
-impl UpgradeResult {
-    pub fn from_commit_result(commit_result: CommitResult, effect: ExecutionEffect) -> Self {
-        match commit_result {
-            CommitResult::RootNotFound => UpgradeResult::RootNotFound,
-            CommitResult::KeyNotFound(key) => UpgradeResult::KeyNotFound(key),
-            CommitResult::TypeMismatch(type_mismatch) => UpgradeResult::TypeMismatch(type_mismatch),
-            CommitResult::Serialization(error) => UpgradeResult::Serialization(error),
-            CommitResult::Success { state_root, .. } => UpgradeResult::Success {
-                post_state_hash: state_root,
-                effect,
-            },
-        }
-    }
-}
+/// The outcome of committing an upgrade.
+pub type UpgradeResult = SystemCallResult;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UpgradeConfig {
@@ -61,6 +24,14 @@ pub struct UpgradeConfig {
     upgrade_installer_bytes: Option<Vec<u8>>,
     wasm_costs: Option<WasmCosts>,
     activation_point: Option<ActivationPoint>,
+    rent_parameters: Option<RentParameters>,
+    stored_installer: Option<String>,
+    sponsorships: Option<BTreeMap<[u8; 32], Sponsorship>>,
+    host_function_costs: Option<HostFunctionCosts>,
+    refund_percentage: Option<u8>,
+    chain_parameters: Option<ChainParameters>,
+    denied_host_functions: Option<DeniedHostFunctions>,
+    gas_metering: Option<GasMetering>,
 }
 
 impl UpgradeConfig {
@@ -81,9 +52,76 @@ impl UpgradeConfig {
             upgrade_installer_bytes,
             wasm_costs,
             activation_point,
+            rent_parameters: None,
+            stored_installer: None,
+            sponsorships: None,
+            host_function_costs: None,
+            refund_percentage: None,
+            chain_parameters: None,
+            denied_host_functions: None,
+            gas_metering: None,
         }
     }
 
+    /// Overrides the rent parameters of the new protocol version, which otherwise are carried
+    /// over from the current protocol version.
+    pub fn with_rent_parameters(mut self, rent_parameters: RentParameters) -> Self {
+        self.rent_parameters = Some(rent_parameters);
+        self
+    }
+
+    /// Replaces the table of sponsored contracts of the new protocol version, which otherwise is
+    /// carried over from the current protocol version.
+    pub fn with_sponsorships(mut self, sponsorships: BTreeMap<[u8; 32], Sponsorship>) -> Self {
+        self.sponsorships = Some(sponsorships);
+        self
+    }
+
+    /// Replaces the flat costs of calls to individual host functions of the new protocol version,
+    /// which otherwise are carried over from the current protocol version.
+    pub fn with_host_function_costs(mut self, host_function_costs: HostFunctionCosts) -> Self {
+        self.host_function_costs = Some(host_function_costs);
+        self
+    }
+
+    /// Overrides the percentage of the unspent payment of each deploy which is refunded in the new
+    /// protocol version, which otherwise is carried over from the current protocol version.
+    pub fn with_refund_percentage(mut self, refund_percentage: u8) -> Self {
+        self.refund_percentage = Some(refund_percentage);
+        self
+    }
+
+    /// Overrides the limits on the payment and the gas of deploys of the new protocol version,
+    /// which otherwise are carried over from the current protocol version.
+    pub fn with_chain_parameters(mut self, chain_parameters: ChainParameters) -> Self {
+        self.chain_parameters = Some(chain_parameters);
+        self
+    }
+
+    /// Replaces the host functions which modules may not import in the new protocol version,
+    /// which otherwise are carried over from the current protocol version.
+    pub fn with_denied_host_functions(
+        mut self,
+        denied_host_functions: DeniedHostFunctions,
+    ) -> Self {
+        self.denied_host_functions = Some(denied_host_functions);
+        self
+    }
+
+    /// Overrides the strategy used to charge gas for the instructions executed by modules in the
+    /// new protocol version, which otherwise is carried over from the current protocol version.
+    pub fn with_gas_metering(mut self, gas_metering: GasMetering) -> Self {
+        self.gas_metering = Some(gas_metering);
+        self
+    }
+
+    /// Runs the installer persisted at genesis for the system contract registered under `name`
+    /// as the upgrade installer.  Mutually exclusive with providing upgrade installer bytes.
+    pub fn with_stored_installer(mut self, name: &str) -> Self {
+        self.stored_installer = Some(name.to_string());
+        self
+    }
+
     pub fn pre_state_hash(&self) -> Blake2bHash {
         self.pre_state_hash
     }
@@ -113,4 +151,36 @@ impl UpgradeConfig {
     pub fn activation_point(&self) -> Option<u64> {
         self.activation_point
     }
+
+    pub fn rent_parameters(&self) -> Option<RentParameters> {
+        self.rent_parameters
+    }
+
+    pub fn sponsorships(&self) -> Option<&BTreeMap<[u8; 32], Sponsorship>> {
+        self.sponsorships.as_ref()
+    }
+
+    pub fn host_function_costs(&self) -> Option<&HostFunctionCosts> {
+        self.host_function_costs.as_ref()
+    }
+
+    pub fn refund_percentage(&self) -> Option<u8> {
+        self.refund_percentage
+    }
+
+    pub fn chain_parameters(&self) -> Option<ChainParameters> {
+        self.chain_parameters
+    }
+
+    pub fn denied_host_functions(&self) -> Option<&DeniedHostFunctions> {
+        self.denied_host_functions.as_ref()
+    }
+
+    pub fn gas_metering(&self) -> Option<GasMetering> {
+        self.gas_metering
+    }
+
+    pub fn stored_installer(&self) -> Option<&str> {
+        self.stored_installer.as_ref().map(String::as_str)
+    }
 }
@@ -5,32 +5,110 @@ use blake2::{
 use rand::{RngCore, SeedableRng};
 use rand_chacha::ChaChaRng;
 
-use types::Phase;
+use types::{Phase, ProtocolVersion, RANDOM_BYTES_COUNT};
 
 use crate::{Address, ADDRESS_LENGTH};
 
+/// The first protocol version at which addresses and random bytes are derived individually from
+/// the deploy hash, phase, a counter and a domain tag, rather than drawn from streams seeded with
+/// the deploy hash and phase.
+pub const DOMAIN_SEPARATION_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_1_0;
+
 const SEED_LENGTH: usize = 32;
+const ADDRESS_DOMAIN: &[u8] = b"address";
+// Appended to the seed data of the random bytes stream, so it differs from the address stream
+const RANDOM_BYTES_DOMAIN: &[u8] = b"random_bytes";
 
 /// An [`AddressGenerator`] generates [`URef`](contract::uref::URef) addresses
-pub struct AddressGenerator(ChaChaRng);
+///
+/// It also provides random bytes to contracts, drawn separately from the addresses, so that
+/// requesting random bytes doesn't change the addresses generated afterwards.
+pub struct AddressGenerator(Outputs);
+
+enum Outputs {
+    /// Two ChaCha streams, seeded with the hash of the seed data with and without
+    /// [`RANDOM_BYTES_DOMAIN`] appended.
+    Streams {
+        addresses: ChaChaRng,
+        random_bytes: ChaChaRng,
+    },
+    /// Each output is the hash of `deploy hash || phase || counter || domain tag`, where the
+    /// counter is the number of outputs of the same domain generated before it.
+    DomainSeparated {
+        hash: [u8; 32],
+        phase: Phase,
+        address_count: u64,
+        random_bytes_count: u64,
+    },
+}
 
 impl AddressGenerator {
-    /// Creates an [`AddressGenerator`] from a 32-byte hash digest and [`Phase`].
-    pub fn new(hash: [u8; 32], phase: Phase) -> AddressGenerator {
-        AddressGeneratorBuilder::new()
-            .seed_with(&hash)
-            .seed_with(&[phase as u8])
-            .build()
+    /// Creates an [`AddressGenerator`] from a 32-byte hash digest and [`Phase`], using the scheme
+    /// of the given protocol version.
+    pub fn new(
+        hash: [u8; 32],
+        phase: Phase,
+        protocol_version: ProtocolVersion,
+    ) -> AddressGenerator {
+        if protocol_version >= DOMAIN_SEPARATION_PROTOCOL_VERSION {
+            AddressGenerator(Outputs::DomainSeparated {
+                hash,
+                phase,
+                address_count: 0,
+                random_bytes_count: 0,
+            })
+        } else {
+            AddressGeneratorBuilder::new()
+                .seed_with(&hash)
+                .seed_with(&[phase as u8])
+                .build()
+        }
     }
 
     pub fn create_address(&mut self) -> Address {
-        let mut buff = [0u8; ADDRESS_LENGTH];
-        self.0.fill_bytes(&mut buff);
-        buff
+        match &mut self.0 {
+            Outputs::Streams { addresses, .. } => {
+                let mut buff = [0u8; ADDRESS_LENGTH];
+                addresses.fill_bytes(&mut buff);
+                buff
+            }
+            Outputs::DomainSeparated {
+                hash,
+                phase,
+                address_count,
+                ..
+            } => {
+                let address = derive(hash, *phase, *address_count, ADDRESS_DOMAIN);
+                *address_count += 1;
+                address
+            }
+        }
+    }
+
+    pub fn create_random_bytes(&mut self) -> [u8; RANDOM_BYTES_COUNT] {
+        match &mut self.0 {
+            Outputs::Streams { random_bytes, .. } => {
+                let mut buff = [0u8; RANDOM_BYTES_COUNT];
+                random_bytes.fill_bytes(&mut buff);
+                buff
+            }
+            Outputs::DomainSeparated {
+                hash,
+                phase,
+                random_bytes_count,
+                ..
+            } => {
+                let random_bytes = derive(hash, *phase, *random_bytes_count, RANDOM_BYTES_DOMAIN);
+                *random_bytes_count += 1;
+                random_bytes
+            }
+        }
     }
 }
 
-/// A builder for [`AddressGenerator`].
+/// A builder for [`AddressGenerator`]s seeded with arbitrary data.
+///
+/// The generators use the streams which predate [`DOMAIN_SEPARATION_PROTOCOL_VERSION`].
 #[derive(Default)]
 pub struct AddressGeneratorBuilder {
     data: Vec<u8>,
@@ -47,27 +125,60 @@ impl AddressGeneratorBuilder {
     }
 
     pub fn build(self) -> AddressGenerator {
-        let mut seed: [u8; SEED_LENGTH] = [0u8; SEED_LENGTH];
-        let mut hasher = VarBlake2b::new(SEED_LENGTH).unwrap();
-        hasher.input(self.data);
-        hasher.variable_result(|hash| seed.clone_from_slice(hash));
-        AddressGenerator(ChaChaRng::from_seed(seed))
+        let addresses = ChaChaRng::from_seed(hash(&self.data));
+        let random_bytes = {
+            let mut data = self.data;
+            data.extend(RANDOM_BYTES_DOMAIN);
+            ChaChaRng::from_seed(hash(&data))
+        };
+        AddressGenerator(Outputs::Streams {
+            addresses,
+            random_bytes,
+        })
     }
 }
 
+fn derive(hash_bytes: &[u8; 32], phase: Phase, counter: u64, domain: &[u8]) -> [u8; SEED_LENGTH] {
+    let mut data = Vec::with_capacity(hash_bytes.len() + 1 + 8 + domain.len());
+    data.extend_from_slice(hash_bytes);
+    data.push(phase as u8);
+    data.extend_from_slice(&counter.to_le_bytes());
+    data.extend_from_slice(domain);
+    hash(&data)
+}
+
+fn hash(data: &[u8]) -> [u8; SEED_LENGTH] {
+    let mut seed: [u8; SEED_LENGTH] = [0u8; SEED_LENGTH];
+    let mut hasher = VarBlake2b::new(SEED_LENGTH).unwrap();
+    hasher.input(data);
+    hasher.variable_result(|hash| seed.clone_from_slice(hash));
+    seed
+}
+
 #[cfg(test)]
 mod tests {
-    use types::Phase;
+    use std::collections::BTreeSet;
 
-    use super::AddressGenerator;
+    use types::{Phase, ProtocolVersion};
+
+    use super::{AddressGenerator, DOMAIN_SEPARATION_PROTOCOL_VERSION};
 
     const DEPLOY_HASH_1: [u8; 32] = [1u8; 32];
     const DEPLOY_HASH_2: [u8; 32] = [2u8; 32];
+    const PROTOCOL_VERSION: ProtocolVersion = DOMAIN_SEPARATION_PROTOCOL_VERSION;
+    const LEGACY_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
+    const PHASES: [Phase; 4] = [
+        Phase::System,
+        Phase::Payment,
+        Phase::Session,
+        Phase::FinalizePayment,
+    ];
+    const OUTPUTS_PER_PHASE: usize = 1000;
 
     #[test]
     fn should_generate_different_numbers_for_different_seeds() {
-        let mut ag_a = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session);
-        let mut ag_b = AddressGenerator::new(DEPLOY_HASH_2, Phase::Session);
+        let mut ag_a = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session, PROTOCOL_VERSION);
+        let mut ag_b = AddressGenerator::new(DEPLOY_HASH_2, Phase::Session, PROTOCOL_VERSION);
         let random_a = ag_a.create_address();
         let random_b = ag_b.create_address();
 
@@ -76,8 +187,8 @@ mod tests {
 
     #[test]
     fn should_generate_same_numbers_for_same_seed() {
-        let mut ag_a = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session);
-        let mut ag_b = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session);
+        let mut ag_a = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session, PROTOCOL_VERSION);
+        let mut ag_b = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session, PROTOCOL_VERSION);
         let random_a = ag_a.create_address();
         let random_b = ag_b.create_address();
 
@@ -86,9 +197,10 @@ mod tests {
 
     #[test]
     fn should_not_generate_same_numbers_for_different_phase() {
-        let mut ag_a = AddressGenerator::new(DEPLOY_HASH_1, Phase::Payment);
-        let mut ag_b = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session);
-        let mut ag_c = AddressGenerator::new(DEPLOY_HASH_1, Phase::FinalizePayment);
+        let mut ag_a = AddressGenerator::new(DEPLOY_HASH_1, Phase::Payment, PROTOCOL_VERSION);
+        let mut ag_b = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session, PROTOCOL_VERSION);
+        let mut ag_c =
+            AddressGenerator::new(DEPLOY_HASH_1, Phase::FinalizePayment, PROTOCOL_VERSION);
         let random_a = ag_a.create_address();
         let random_b = ag_b.create_address();
         let random_c = ag_c.create_address();
@@ -103,4 +215,99 @@ mod tests {
             "different phase should have different output"
         );
     }
+
+    #[test]
+    fn should_not_change_addresses_when_generating_random_bytes() {
+        let mut ag_a = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session, PROTOCOL_VERSION);
+        let mut ag_b = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session, PROTOCOL_VERSION);
+        let random_bytes = ag_b.create_random_bytes();
+        let address_a = ag_a.create_address();
+        let address_b = ag_b.create_address();
+
+        assert_eq!(address_a, address_b);
+        assert_ne!(address_a, random_bytes);
+    }
+
+    #[test]
+    fn should_generate_same_random_bytes_for_same_seed() {
+        let mut ag_a = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session, PROTOCOL_VERSION);
+        let mut ag_b = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session, PROTOCOL_VERSION);
+        let mut ag_c = AddressGenerator::new(DEPLOY_HASH_2, Phase::Session, PROTOCOL_VERSION);
+        ag_b.create_address();
+        let random_a = ag_a.create_random_bytes();
+        let random_b = ag_b.create_random_bytes();
+        let random_c = ag_c.create_random_bytes();
+
+        assert_eq!(random_a, random_b);
+        assert_ne!(random_a, random_c);
+    }
+
+    #[test]
+    fn should_not_collide_across_phases() {
+        let mut outputs = BTreeSet::new();
+        for phase in PHASES.iter() {
+            let mut generator = AddressGenerator::new(DEPLOY_HASH_1, *phase, PROTOCOL_VERSION);
+            for _ in 0..OUTPUTS_PER_PHASE {
+                assert!(
+                    outputs.insert(generator.create_address()),
+                    "address collision in {:?}",
+                    phase
+                );
+                assert!(
+                    outputs.insert(generator.create_random_bytes()),
+                    "random bytes collision in {:?}",
+                    phase
+                );
+            }
+        }
+        assert_eq!(outputs.len(), PHASES.len() * OUTPUTS_PER_PHASE * 2);
+    }
+
+    #[test]
+    fn should_derive_outputs_from_hash_phase_counter_and_domain() {
+        // blake2b-256(deploy hash || phase || counter as u64 LE || domain tag)
+        let mut generator = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session, PROTOCOL_VERSION);
+        let random_bytes = generator.create_random_bytes();
+        let address_0 = generator.create_address();
+        let address_1 = generator.create_address();
+
+        assert_eq!(
+            address_0,
+            [
+                252, 233, 88, 228, 24, 181, 33, 194, 63, 184, 71, 46, 187, 117, 111, 136, 240, 21,
+                7, 194, 125, 82, 94, 186, 107, 245, 22, 199, 163, 23, 43, 7,
+            ]
+        );
+        assert_eq!(
+            address_1,
+            [
+                20, 19, 102, 67, 10, 230, 103, 20, 9, 174, 251, 146, 45, 181, 218, 80, 173, 79,
+                128, 104, 104, 227, 196, 213, 177, 77, 10, 182, 39, 49, 7, 252,
+            ]
+        );
+        assert_eq!(
+            random_bytes,
+            [
+                206, 136, 14, 37, 229, 218, 214, 48, 208, 156, 207, 27, 1, 145, 167, 156, 28, 126,
+                198, 29, 158, 99, 2, 106, 42, 67, 21, 14, 94, 69, 215, 152,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_keep_legacy_outputs_before_domain_separation() {
+        let mut legacy_a =
+            AddressGenerator::new(DEPLOY_HASH_1, Phase::Session, LEGACY_PROTOCOL_VERSION);
+        let mut legacy_b =
+            AddressGenerator::new(DEPLOY_HASH_1, Phase::Session, LEGACY_PROTOCOL_VERSION);
+        let mut separated = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session, PROTOCOL_VERSION);
+        let legacy_address = legacy_a.create_address();
+
+        assert_eq!(legacy_address, legacy_b.create_address());
+        assert_ne!(legacy_address, separated.create_address());
+        assert_ne!(
+            legacy_a.create_random_bytes(),
+            separated.create_random_bytes()
+        );
+    }
 }
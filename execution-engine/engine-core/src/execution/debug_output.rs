@@ -0,0 +1,54 @@
+use std::mem;
+
+/// Maximum number of bytes of text collected from a single execution, see [`DebugOutput`].
+pub const MAX_DEBUG_OUTPUT_SIZE: usize = 64 * 1024;
+
+/// Text printed via the `print` host function by the executed contract and the contracts it
+/// calls.
+///
+/// Printing is free of charge, so at most [`MAX_DEBUG_OUTPUT_SIZE`] bytes are kept, with every
+/// line counting as at least one byte.  Lines which don't fit are dropped.
+#[derive(Debug, Default)]
+pub struct DebugOutput {
+    lines: Vec<String>,
+    size: usize,
+}
+
+impl DebugOutput {
+    /// Appends `line` to the output, unless it would exceed [`MAX_DEBUG_OUTPUT_SIZE`].
+    pub fn push(&mut self, line: String) {
+        let size = self.size + line.len().max(1);
+        if size > MAX_DEBUG_OUTPUT_SIZE {
+            return;
+        }
+        self.size = size;
+        self.lines.push(line);
+    }
+
+    /// Takes the lines collected so far.  Lines taken still count towards the size limit.
+    pub fn take(&mut self) -> Vec<String> {
+        mem::replace(&mut self.lines, Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DebugOutput, MAX_DEBUG_OUTPUT_SIZE};
+
+    #[test]
+    fn should_drop_lines_exceeding_limit() {
+        let mut debug_output = DebugOutput::default();
+        debug_output.push("a".repeat(MAX_DEBUG_OUTPUT_SIZE - 1));
+        debug_output.push("bc".to_string());
+        debug_output.push(String::new());
+        debug_output.push(String::new());
+
+        let lines = debug_output.take();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), MAX_DEBUG_OUTPUT_SIZE - 1);
+        assert_eq!(lines[1], "");
+
+        debug_output.push("d".to_string());
+        assert!(debug_output.take().is_empty());
+    }
+}
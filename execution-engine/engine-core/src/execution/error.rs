@@ -5,8 +5,10 @@ use wasmi;
 
 use engine_shared::transform::TypeMismatch;
 use types::{
-    account::{AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure},
-    bytesrepr, system_contract_errors, AccessRights, CLValueError, Key, URef,
+    account::{
+        AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure, UpdateKeysFailure,
+    },
+    bytesrepr, system_contract_errors, AccessRights, CLValueError, ContractVersion, Key, URef,
 };
 
 use crate::resolvers::error::ResolverError;
@@ -35,9 +37,9 @@ pub enum Error {
     AddKeyFailure(AddKeyFailure),
     RemoveKeyFailure(RemoveKeyFailure),
     UpdateKeyFailure(UpdateKeyFailure),
+    UpdateKeysFailure(UpdateKeysFailure),
     SetThresholdFailure(SetThresholdFailure),
     SystemContractError(system_contract_errors::Error),
-    DeploymentAuthorizationFailure,
     ExpectedReturnValue,
     UnexpectedReturnValue,
     InvalidContext,
@@ -47,6 +49,28 @@ pub enum Error {
     },
     CLValue(CLValueError),
     HostBufferEmpty,
+    /// The requested version of a contract package does not exist or is disabled.
+    InvalidContractVersion(ContractVersion),
+    /// The contract package has no enabled version.
+    NoEnabledContractVersion,
+    EntryPointNotFound(String),
+    /// The arguments do not match the parameters of the named entry point.
+    InvalidEntryPointArgs(String),
+    /// The caller does not meet the access requirements of the named entry point.
+    EntryPointAccessDenied(String),
+    /// The called contract is already on the call stack, and reentrancy is disallowed by the
+    /// engine configuration.
+    Reentrancy(Key),
+    /// The called contract only allows calls from deploys executed as other accounts.
+    CallerNotAuthorized(Key),
+    /// The called contract is a version of a contract package, and may only be called through
+    /// the package.
+    ContractVersionCalledDirectly(Key),
+    /// Payment code attempted to write to something other than a purse, and such writes are
+    /// disallowed by the engine configuration.
+    PaymentWriteForbidden,
+    /// The seed does not belong to a namespace obtained by the current contract.
+    UnknownNamespace([u8; 32]),
 }
 
 impl fmt::Display for Error {
@@ -111,6 +135,12 @@ impl From<UpdateKeyFailure> for Error {
     }
 }
 
+impl From<UpdateKeysFailure> for Error {
+    fn from(err: UpdateKeysFailure) -> Self {
+        Error::UpdateKeysFailure(err)
+    }
+}
+
 impl From<SetThresholdFailure> for Error {
     fn from(err: SetThresholdFailure) -> Self {
         Error::SetThresholdFailure(err)
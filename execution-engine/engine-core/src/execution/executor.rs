@@ -5,6 +5,7 @@ use std::{
 };
 
 use parity_wasm::elements::Module;
+use tracing::info_span;
 
 use engine_shared::{
     account::Account, gas::Gas, newtypes::CorrelationId, stored_value::StoredValue,
@@ -13,12 +14,15 @@ use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
 use types::{
     account::PublicKey,
     bytesrepr::{self, FromBytes},
-    BlockTime, CLType, CLTyped, CLValue, Key, Phase, ProtocolVersion,
+    CLType, CLTyped, CLValue, CallStackElement, Key, Phase, ProtocolVersion,
 };
 
 use super::{extract_access_rights_from_keys, instance_and_memory, Error, Runtime};
 use crate::{
-    engine_state::{execution_result::ExecutionResult, system_contract_cache::SystemContractCache},
+    engine_state::{
+        block_info::BlockInfo, execution_result::ExecutionResult, module_cache::ModuleCache,
+        EngineConfig,
+    },
     execution::{address_generator::AddressGenerator, FN_STORE_ID_INITIAL},
     runtime_context::{self, RuntimeContext},
     tracking_copy::TrackingCopy,
@@ -43,6 +47,10 @@ macro_rules! on_fail_charge {
                     error: exec_err.into(),
                     effect: Default::default(),
                     cost: $cost,
+                    read_stats: None,
+                    effects_by_phase: None,
+                    stdout: Vec::new(),
+                    host_function_profile: None,
                 };
             }
         }
@@ -56,24 +64,70 @@ macro_rules! on_fail_charge {
                     error: exec_err.into(),
                     effect: $effect,
                     cost: $cost,
+                    read_stats: None,
+                    effects_by_phase: None,
+                    stdout: Vec::new(),
+                    host_function_profile: None,
+                };
+            }
+        }
+    };
+    ($fn:expr, $cost:expr, $effect:expr, $stdout:expr, $host_function_profile:expr) => {
+        match $fn {
+            Ok(res) => res,
+            Err(e) => {
+                let exec_err: crate::execution::Error = e.into();
+                return ExecutionResult::Failure {
+                    error: exec_err.into(),
+                    effect: $effect,
+                    cost: $cost,
+                    read_stats: None,
+                    effects_by_phase: None,
+                    stdout: $stdout,
+                    host_function_profile: $host_function_profile,
                 };
             }
         }
     };
 }
 
-pub struct Executor;
+/// Returns the call stack of code executed by the engine on behalf of `account`.  When `base_key`
+/// is a stored contract rather than the account itself, the contract is the top of the stack.
+fn initial_call_stack(account: &Account, base_key: Key) -> Vec<CallStackElement> {
+    let mut call_stack = vec![CallStackElement::Account(PublicKey::new(account.pub_key()))];
+    if base_key != Key::Account(account.pub_key()) {
+        call_stack.push(CallStackElement::Contract(base_key.normalize()));
+    }
+    call_stack
+}
+
+pub struct Executor {
+    config: EngineConfig,
+}
 
 #[allow(clippy::too_many_arguments)]
 impl Executor {
+    pub fn new(config: EngineConfig) -> Self {
+        Executor { config }
+    }
+
+    /// Executes `entry_point` of `parity_module` on behalf of `account`.
+    ///
+    /// The code executes in the context of `base_key`, which is either the account itself or a
+    /// contract, in which case `named_keys` are those of the contract and `package_hash` is the
+    /// package the contract was called through.  Only `named_keys` and the system contracts are
+    /// accessible, so code executing in the context of a contract can't use the account's purse.
     pub fn exec<R>(
         &self,
         parity_module: Module,
         args: Vec<u8>,
+        entry_point: &str,
         base_key: Key,
+        mut named_keys: BTreeMap<String, Key>,
+        package_hash: Option<[u8; 32]>,
         account: &Account,
         authorized_keys: BTreeSet<PublicKey>,
-        blocktime: BlockTime,
+        block_info: BlockInfo,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
         protocol_version: ProtocolVersion,
@@ -81,16 +135,21 @@ impl Executor {
         tc: Rc<RefCell<TrackingCopy<R>>>,
         phase: Phase,
         protocol_data: ProtocolData,
-        system_contract_cache: SystemContractCache,
+        module_cache: ModuleCache,
     ) -> ExecutionResult
     where
         R: StateReader<Key, StoredValue>,
         R::Error: Into<Error>,
     {
-        let (instance, memory) =
-            on_fail_charge!(instance_and_memory(parity_module.clone(), protocol_version));
-
-        let mut named_keys = account.named_keys().clone();
+        let span = info_span!("phase", ?phase, ?base_key);
+        let _guard = span.enter();
+        let max_memory = protocol_data.wasm_costs().memory_limit();
+        let (instance, memory) = on_fail_charge!(instance_and_memory(
+            parity_module.clone(),
+            protocol_version,
+            protocol_data.denied_host_functions(),
+            max_memory
+        ));
 
         let access_rights =
             {
@@ -101,7 +160,7 @@ impl Executor {
                 extract_access_rights_from_keys(keys)
             };
 
-        let address_generator = AddressGenerator::new(deploy_hash, phase);
+        let address_generator = AddressGenerator::new(deploy_hash, phase, protocol_version);
         let gas_counter: Gas = Gas::default();
 
         // Snapshot of effects before execution, so in case of error
@@ -122,7 +181,7 @@ impl Executor {
             .map(|bytes: Vec<u8>| CLValue::from_components(CLType::Any, bytes))
             .collect();
 
-        let context = RuntimeContext::new(
+        let mut context = RuntimeContext::new(
             tc,
             &mut named_keys,
             access_rights,
@@ -130,7 +189,8 @@ impl Executor {
             authorized_keys,
             &account,
             base_key,
-            blocktime,
+            initial_call_stack(account, base_key),
+            block_info,
             deploy_hash,
             gas_limit,
             gas_counter,
@@ -141,17 +201,35 @@ impl Executor {
             phase,
             protocol_data,
         );
+        if let Some(package_hash) = package_hash {
+            context.set_package_hash(package_hash);
+        }
 
-        let mut runtime = Runtime::new(system_contract_cache, memory, parity_module, context);
+        let mut runtime = Runtime::new(
+            self.config.clone(),
+            module_cache,
+            memory,
+            parity_module,
+            context,
+        );
+        let result = instance.invoke_export(entry_point, &[], &mut runtime);
+        let stdout = runtime.take_stdout();
+        let host_function_profile = runtime.take_host_function_profile();
         on_fail_charge!(
-            instance.invoke_export("call", &[], &mut runtime),
+            result,
             runtime.context().gas_counter(),
-            effects_snapshot
+            effects_snapshot,
+            stdout,
+            host_function_profile
         );
 
         ExecutionResult::Success {
             effect: runtime.context().effect(),
             cost: runtime.context().gas_counter(),
+            read_stats: None,
+            effects_by_phase: None,
+            stdout,
+            host_function_profile,
         }
     }
 
@@ -163,7 +241,7 @@ impl Executor {
         base_key: Key,
         account: &Account,
         authorization_keys: BTreeSet<PublicKey>,
-        blocktime: BlockTime,
+        block_info: BlockInfo,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
         protocol_version: ProtocolVersion,
@@ -171,12 +249,14 @@ impl Executor {
         state: Rc<RefCell<TrackingCopy<R>>>,
         phase: Phase,
         protocol_data: ProtocolData,
-        system_contract_cache: SystemContractCache,
+        module_cache: ModuleCache,
     ) -> ExecutionResult
     where
         R: StateReader<Key, StoredValue>,
         R::Error: Into<Error>,
     {
+        let span = info_span!("phase", ?phase, ?base_key);
+        let _guard = span.enter();
         let mut named_keys = named_keys.clone();
         let access_rights =
             {
@@ -188,7 +268,7 @@ impl Executor {
             };
 
         let address_generator = {
-            let address_generator = AddressGenerator::new(deploy_hash, phase);
+            let address_generator = AddressGenerator::new(deploy_hash, phase, protocol_version);
             Rc::new(RefCell::new(address_generator))
         };
         let gas_counter = Gas::default(); // maybe const?
@@ -204,6 +284,7 @@ impl Executor {
             on_fail_charge!(bytesrepr::deserialize(args), gas, effects_snapshot)
         };
 
+        let max_memory = protocol_data.wasm_costs().memory_limit();
         let context = RuntimeContext::new(
             state,
             &mut named_keys,
@@ -212,7 +293,8 @@ impl Executor {
             authorization_keys,
             &account,
             base_key,
-            blocktime,
+            initial_call_stack(account, base_key),
+            block_info,
             deploy_hash,
             gas_limit,
             gas_counter,
@@ -224,15 +306,32 @@ impl Executor {
             protocol_data,
         );
 
-        let (instance, memory) =
-            on_fail_charge!(instance_and_memory(parity_module.clone(), protocol_version));
-
-        let mut runtime = Runtime::new(system_contract_cache, memory, parity_module, context);
+        let (instance, memory) = on_fail_charge!(instance_and_memory(
+            parity_module.clone(),
+            protocol_version,
+            context.protocol_data().denied_host_functions(),
+            max_memory
+        ));
+
+        let mut runtime = Runtime::new(
+            self.config.clone(),
+            module_cache,
+            memory,
+            parity_module,
+            context,
+        );
 
-        match instance.invoke_export("call", &[], &mut runtime) {
+        let result = instance.invoke_export("call", &[], &mut runtime);
+        let stdout = runtime.take_stdout();
+        let host_function_profile = runtime.take_host_function_profile();
+        match result {
             Ok(_) => ExecutionResult::Success {
                 effect: runtime.context().effect(),
                 cost: runtime.context().gas_counter(),
+                read_stats: None,
+                effects_by_phase: None,
+                stdout,
+                host_function_profile,
             },
             Err(e) => {
                 if let Some(host_error) = e.as_host_error() {
@@ -249,6 +348,10 @@ impl Executor {
                             return ExecutionResult::Success {
                                 effect: runtime.context().effect(),
                                 cost: runtime.context().gas_counter(),
+                                read_stats: None,
+                                effects_by_phase: None,
+                                stdout,
+                                host_function_profile,
                             };
                         }
                         Error::Revert(status) => {
@@ -258,6 +361,10 @@ impl Executor {
                                 error: Error::Revert(*status).into(),
                                 effect: effects_snapshot,
                                 cost: runtime.context().gas_counter(),
+                                read_stats: None,
+                                effects_by_phase: None,
+                                stdout,
+                                host_function_profile,
                             };
                         }
                         _ => {}
@@ -268,6 +375,10 @@ impl Executor {
                     error: Error::Interpreter(e).into(),
                     effect: effects_snapshot,
                     cost: runtime.context().gas_counter(),
+                    read_stats: None,
+                    effects_by_phase: None,
+                    stdout,
+                    host_function_profile,
                 }
             }
         }
@@ -281,7 +392,7 @@ impl Executor {
         base_key: Key,
         account: &Account,
         authorization_keys: BTreeSet<PublicKey>,
-        blocktime: BlockTime,
+        block_info: BlockInfo,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
         address_generator: Rc<RefCell<AddressGenerator>>,
@@ -290,13 +401,15 @@ impl Executor {
         state: Rc<RefCell<TrackingCopy<R>>>,
         phase: Phase,
         protocol_data: ProtocolData,
-        system_contract_cache: SystemContractCache,
+        module_cache: ModuleCache,
     ) -> Result<T, Error>
     where
         R: StateReader<Key, StoredValue>,
         R::Error: Into<Error>,
         T: FromBytes + CLTyped,
     {
+        let span = info_span!("phase", ?phase, ?base_key);
+        let _guard = span.enter();
         let access_rights =
             {
                 let mut keys: Vec<Key> = keys.values().cloned().collect();
@@ -314,6 +427,7 @@ impl Executor {
 
         let gas_counter = Gas::default();
 
+        let max_memory = protocol_data.wasm_costs().memory_limit();
         let runtime_context = RuntimeContext::new(
             state,
             keys,
@@ -322,7 +436,8 @@ impl Executor {
             authorization_keys,
             account,
             base_key,
-            blocktime,
+            initial_call_stack(account, base_key),
+            block_info,
             deploy_hash,
             gas_limit,
             gas_counter,
@@ -334,9 +449,20 @@ impl Executor {
             protocol_data,
         );
 
-        let (instance, memory) = instance_and_memory(module.clone(), protocol_version)?;
-
-        let mut runtime = Runtime::new(system_contract_cache, memory, module, runtime_context);
+        let (instance, memory) = instance_and_memory(
+            module.clone(),
+            protocol_version,
+            runtime_context.protocol_data().denied_host_functions(),
+            max_memory,
+        )?;
+
+        let mut runtime = Runtime::new(
+            self.config.clone(),
+            module_cache,
+            memory,
+            module,
+            runtime_context,
+        );
 
         let return_error: wasmi::Error = match instance.invoke_export("call", &[], &mut runtime) {
             Err(error) => error,
@@ -0,0 +1,256 @@
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fmt,
+    ops::{Add, AddAssign},
+    time::Duration,
+};
+
+use engine_shared::gas::Gas;
+use types::Key;
+
+use crate::resolvers::{host_function_table, v1_function_index::FunctionIndex};
+
+/// Counters of the calls made to one host function.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct HostFunctionStats {
+    invocations: u64,
+    gas: Gas,
+    time: Duration,
+}
+
+impl HostFunctionStats {
+    /// Number of calls made to the host function.
+    pub fn invocations(&self) -> u64 {
+        self.invocations
+    }
+
+    /// Total gas charged while the host function ran.
+    pub fn gas(&self) -> Gas {
+        self.gas
+    }
+
+    /// Total wall time spent in the host function.
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+}
+
+impl AddAssign for HostFunctionStats {
+    fn add_assign(&mut self, other: HostFunctionStats) {
+        self.invocations += other.invocations;
+        self.gas += other.gas;
+        self.time += other.time;
+    }
+}
+
+/// Counters of the host function calls made during an execution, per calling contract and host
+/// function.
+///
+/// Calls made by session or payment code are attributed to the key of the account, and calls made
+/// by stored contracts to the key the contract was called by.  The time of a call to a contract
+/// includes the time spent in the called contract, while its gas doesn't, as the gas of a call is
+/// charged by the called contract's own host function calls.  The gas charged for the executed
+/// Wasm instructions is attributed to the `gas` host function.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HostFunctionProfile {
+    stats: BTreeMap<(Key, FunctionIndex), HostFunctionStats>,
+}
+
+impl HostFunctionProfile {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records a call of the host function `function` made by `contract`.
+    pub fn record(&mut self, contract: Key, function: FunctionIndex, gas: Gas, time: Duration) {
+        *self.stats.entry((contract, function)).or_default() += HostFunctionStats {
+            invocations: 1,
+            gas,
+            time,
+        };
+    }
+
+    /// Returns the counters of the calls `contract` made to `function`, if there were any.
+    pub fn get(&self, contract: Key, function: FunctionIndex) -> Option<HostFunctionStats> {
+        self.stats.get(&(contract, function)).copied()
+    }
+
+    /// Returns the counters per calling contract and host function.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, FunctionIndex, HostFunctionStats)> + '_ {
+        self.stats
+            .iter()
+            .map(|((contract, function), stats)| (*contract, *function, *stats))
+    }
+
+    /// Returns the counters per host function, summed over all calling contracts.
+    pub fn by_function(&self) -> BTreeMap<FunctionIndex, HostFunctionStats> {
+        let mut ret = BTreeMap::<FunctionIndex, HostFunctionStats>::new();
+        for (_, function, stats) in self.iter() {
+            *ret.entry(function).or_default() += stats;
+        }
+        ret
+    }
+
+    /// Returns the counters per calling contract, summed over all host functions.
+    pub fn by_contract(&self) -> BTreeMap<Key, HostFunctionStats> {
+        let mut ret = BTreeMap::<Key, HostFunctionStats>::new();
+        for (contract, _, stats) in self.iter() {
+            *ret.entry(contract).or_default() += stats;
+        }
+        ret
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+    }
+}
+
+impl Add for HostFunctionProfile {
+    type Output = HostFunctionProfile;
+
+    fn add(mut self, other: HostFunctionProfile) -> HostFunctionProfile {
+        for (key, stats) in other.stats {
+            *self.stats.entry(key).or_default() += stats;
+        }
+        self
+    }
+}
+
+/// Writes a report with a line per calling contract and host function, with the most expensive
+/// calls in gas, then in time, first.
+impl fmt::Display for HostFunctionProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut entries = self.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|(_, _, stats)| (Reverse(stats.gas), Reverse(stats.time)));
+        for (contract, function, stats) in entries {
+            let name = host_function_table::host_function_name(function)
+                .map(String::from)
+                .unwrap_or_else(|| format!("{:?}", function));
+            writeln!(
+                f,
+                "{} {}: {} calls, {} gas, {:?}",
+                contract, name, stats.invocations, stats.gas, stats.time
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use engine_shared::gas::Gas;
+    use types::Key;
+
+    use super::HostFunctionProfile;
+    use crate::resolvers::v1_function_index::FunctionIndex;
+
+    const ACCOUNT: Key = Key::Account([1; 32]);
+    const CONTRACT: Key = Key::Hash([2; 32]);
+
+    #[test]
+    fn should_sum_calls_per_contract_and_function() {
+        let mut profile = HostFunctionProfile::new();
+        profile.record(
+            ACCOUNT,
+            FunctionIndex::ReadFuncIndex,
+            Gas::from(10u64),
+            Duration::from_micros(3),
+        );
+        profile.record(
+            ACCOUNT,
+            FunctionIndex::ReadFuncIndex,
+            Gas::from(20u64),
+            Duration::from_micros(4),
+        );
+        profile.record(
+            CONTRACT,
+            FunctionIndex::ReadFuncIndex,
+            Gas::from(5u64),
+            Duration::from_micros(1),
+        );
+        profile.record(
+            CONTRACT,
+            FunctionIndex::WriteFuncIndex,
+            Gas::from(7u64),
+            Duration::from_micros(2),
+        );
+
+        let account_reads = profile
+            .get(ACCOUNT, FunctionIndex::ReadFuncIndex)
+            .expect("should have account reads");
+        assert_eq!(account_reads.invocations(), 2);
+        assert_eq!(account_reads.gas(), Gas::from(30u64));
+        assert_eq!(account_reads.time(), Duration::from_micros(7));
+        assert_eq!(profile.get(ACCOUNT, FunctionIndex::WriteFuncIndex), None);
+
+        let by_function = profile.by_function();
+        assert_eq!(by_function[&FunctionIndex::ReadFuncIndex].invocations(), 3);
+        assert_eq!(
+            by_function[&FunctionIndex::ReadFuncIndex].gas(),
+            Gas::from(35u64)
+        );
+        assert_eq!(by_function[&FunctionIndex::WriteFuncIndex].invocations(), 1);
+
+        let by_contract = profile.by_contract();
+        assert_eq!(by_contract[&CONTRACT].invocations(), 2);
+        assert_eq!(by_contract[&CONTRACT].gas(), Gas::from(12u64));
+    }
+
+    #[test]
+    fn should_merge_profiles() {
+        let mut first = HostFunctionProfile::new();
+        first.record(
+            ACCOUNT,
+            FunctionIndex::GasFuncIndex,
+            Gas::from(100u64),
+            Duration::from_micros(1),
+        );
+        let mut second = HostFunctionProfile::new();
+        second.record(
+            ACCOUNT,
+            FunctionIndex::GasFuncIndex,
+            Gas::from(50u64),
+            Duration::from_micros(1),
+        );
+        second.record(
+            CONTRACT,
+            FunctionIndex::GasFuncIndex,
+            Gas::from(1u64),
+            Duration::from_micros(1),
+        );
+
+        let merged = first + second;
+        let gas = merged
+            .get(ACCOUNT, FunctionIndex::GasFuncIndex)
+            .expect("should have gas calls");
+        assert_eq!(gas.invocations(), 2);
+        assert_eq!(gas.gas(), Gas::from(150u64));
+        assert_eq!(merged.iter().count(), 2);
+    }
+
+    #[test]
+    fn should_report_most_expensive_calls_first() {
+        let mut profile = HostFunctionProfile::new();
+        profile.record(
+            ACCOUNT,
+            FunctionIndex::WriteFuncIndex,
+            Gas::from(1u64),
+            Duration::from_micros(1),
+        );
+        profile.record(
+            CONTRACT,
+            FunctionIndex::ReadFuncIndex,
+            Gas::from(1_000u64),
+            Duration::from_micros(1),
+        );
+
+        let report = profile.to_string();
+        let lines = report.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("read_value: 1 calls, 1,000 gas"));
+        assert!(lines[1].contains("write: 1 calls, 1 gas"));
+    }
+}
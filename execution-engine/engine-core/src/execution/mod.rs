@@ -1,18 +1,22 @@
 mod address_generator;
+mod debug_output;
 mod error;
 #[macro_use]
 mod executor;
+mod host_function_profile;
 mod runtime;
 #[cfg(test)]
 mod tests;
 
 pub use self::{
     address_generator::{AddressGenerator, AddressGeneratorBuilder},
+    debug_output::{DebugOutput, MAX_DEBUG_OUTPUT_SIZE},
     error::Error,
     executor::Executor,
+    host_function_profile::{HostFunctionProfile, HostFunctionStats},
     runtime::{
-        extract_access_rights_from_keys, extract_access_rights_from_urefs, instance_and_memory,
-        Runtime,
+        extract_access_rights_from_keys, extract_access_rights_from_urefs, get_entry_point,
+        instance_and_memory, Runtime,
     },
 };
 
@@ -1,4 +1,4 @@
-use std::convert::TryFrom;
+use std::{convert::TryFrom, rc::Rc, time::Instant};
 
 use wasmi::{Externals, RuntimeArgs, RuntimeValue, Trap};
 
@@ -26,6 +26,39 @@ where
         args: RuntimeArgs,
     ) -> Result<Option<RuntimeValue>, Trap> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
+        let host_function_profile = match self.host_function_profile.as_ref() {
+            Some(host_function_profile) => Rc::clone(host_function_profile),
+            None => return self.invoke_host_function(func, args),
+        };
+
+        let contract = self.context.base_key();
+        let gas_before = self.context.gas_counter();
+        let start = Instant::now();
+        let result = self.invoke_host_function(func, args);
+        let time = start.elapsed();
+        let gas = self
+            .context
+            .gas_counter()
+            .checked_sub(gas_before)
+            .unwrap_or_default();
+        host_function_profile
+            .borrow_mut()
+            .record(contract, func, gas, time);
+        result
+    }
+}
+
+impl<'a, R> Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<Error>,
+{
+    fn invoke_host_function(
+        &mut self,
+        func: FunctionIndex,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        self.charge_host_function(func)?;
         match func {
             FunctionIndex::ReadFuncIndex => {
                 // args(0) = pointer to key in Wasm memory
@@ -166,6 +199,35 @@ where
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
+            FunctionIndex::GetNamedKeyOfIndex => {
+                // args(0) = pointer to serialized public key of the account in Wasm memory
+                // args(1) = size of serialized public key
+                // args(2) = pointer to key name in Wasm memory
+                // args(3) = size of key name
+                // args(4) = pointer to output buffer for serialized key
+                // args(5) = size of output buffer
+                // args(6) = pointer to bytes written
+                let (
+                    public_key_ptr,
+                    public_key_size,
+                    name_ptr,
+                    name_size,
+                    output_ptr,
+                    output_size,
+                    bytes_written,
+                ): (u32, u32, u32, u32, u32, u32, u32) = Args::parse(args)?;
+                let ret = self.load_named_key_of(
+                    public_key_ptr,
+                    public_key_size,
+                    name_ptr,
+                    name_size,
+                    output_ptr,
+                    output_size as usize,
+                    bytes_written,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
             FunctionIndex::HasKeyFuncIndex => {
                 // args(0) = pointer to key name in Wasm memory
                 // args(1) = size of key name
@@ -315,6 +377,69 @@ where
                 Ok(Some(RuntimeValue::I32(0)))
             }
 
+            FunctionIndex::CreateNamedPurseIndex => {
+                // args(0) = pointer to purse name in Wasm memory
+                // args(1) = size of purse name
+                // args(2) = pointer to array for return value
+                // args(3) = length of array for return value
+                let (name_ptr, name_size, dest_ptr, dest_size): (u32, u32, u32, u32) =
+                    Args::parse(args)?;
+                let ret = self.create_named_purse(name_ptr, name_size, dest_ptr, dest_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetNamedPurseIndex => {
+                // args(0) = pointer to purse name in Wasm memory
+                // args(1) = size of purse name
+                // args(2) = pointer to array for return value
+                // args(3) = length of array for return value
+                let (name_ptr, name_size, dest_ptr, dest_size): (u32, u32, u32, u32) =
+                    Args::parse(args)?;
+                let ret = self.get_named_purse(name_ptr, name_size, dest_ptr, dest_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::LogIndex => {
+                // args(0) = pointer to UTF-8 encoded message in Wasm memory
+                // args(1) = size of message
+                let (message_ptr, message_size): (u32, u32) = Args::parse(args)?;
+                self.log(message_ptr, message_size);
+                Ok(None)
+            }
+
+            FunctionIndex::TransferURefOwnershipIndex => {
+                // args(0) = pointer to purse in Wasm memory
+                // args(1) = size of purse
+                // args(2) = pointer to name of the new purse in Wasm memory
+                // args(3) = size of name
+                // args(4) = pointer to array for return value
+                // args(5) = length of array for return value
+                let (purse_ptr, purse_size, name_ptr, name_size, dest_ptr, dest_size) =
+                    Args::parse(args)?;
+                let ret = self.transfer_uref_ownership(
+                    purse_ptr, purse_size, name_ptr, name_size, dest_ptr, dest_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::UpdateContractAclIndex => {
+                // args(0) = pointer to contract key in Wasm memory
+                // args(1) = size of contract key
+                // args(2) = pointer to serialized authorized callers in Wasm memory
+                // args(3) = size of serialized authorized callers
+                let (key_ptr, key_size, acl_ptr, acl_size) = Args::parse(args)?;
+                let ret = self.update_contract_acl(key_ptr, key_size, acl_ptr, acl_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::PrintIndex => {
+                // args(0) = pointer to UTF-8 encoded text in Wasm memory
+                // args(1) = size of text
+                let (text_ptr, text_size): (u32, u32) = Args::parse(args)?;
+                let ret = self.print(text_ptr, text_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
             FunctionIndex::TransferToAccountIndex => {
                 // args(0) = pointer to array of bytes of a public key
                 // args(1) = length of array of bytes of a public key
@@ -434,6 +559,235 @@ where
                 let ret = self.read_host_buffer(dest_ptr, dest_size as usize, bytes_written_ptr)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
+
+            FunctionIndex::GetContractMetadataIndex => {
+                // args(0) = pointer to contract key in Wasm memory
+                // args(1) = size of contract key in Wasm memory
+                // args(2) = pointer to output size (output param)
+                let (key_ptr, key_size, output_size_ptr) = Args::parse(args)?;
+                let ret = self.get_contract_metadata(key_ptr, key_size, output_size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::NewContractIndex => {
+                // args(0) = pointer to serialized entry points in Wasm memory
+                // args(1) = size of serialized entry points
+                // args(2) = pointer to named keys to be saved with the contract
+                // args(3) = size of named keys
+                // args(4) = pointer to Wasm memory where we will save the hash of the new
+                //           contract package
+                // args(5) = pointer to Wasm memory where we will save the serialized access key
+                //           of the new contract package
+                let (
+                    entry_points_ptr,
+                    entry_points_size,
+                    named_keys_ptr,
+                    named_keys_size,
+                    package_hash_ptr,
+                    access_key_ptr,
+                ): (_, u32, _, u32, _, _) = Args::parse(args)?;
+                let entry_points_bytes =
+                    self.bytes_from_mem(entry_points_ptr, entry_points_size as usize)?;
+                let entry_points =
+                    bytesrepr::deserialize(entry_points_bytes).map_err(Error::BytesRepr)?;
+                let named_keys_bytes =
+                    self.bytes_from_mem(named_keys_ptr, named_keys_size as usize)?;
+                let named_keys =
+                    bytesrepr::deserialize(named_keys_bytes).map_err(Error::BytesRepr)?;
+                let (package_hash, access_key) = self.new_contract(entry_points, named_keys)?;
+                self.function_address(package_hash, package_hash_ptr)?;
+                let access_key_bytes = access_key.into_bytes().map_err(Error::BytesRepr)?;
+                self.memory
+                    .set(access_key_ptr, &access_key_bytes)
+                    .map_err(Error::Interpreter)?;
+                Ok(None)
+            }
+
+            FunctionIndex::AddContractVersionIndex => {
+                // args(0) = pointer to contract package hash in Wasm memory
+                // args(1) = pointer to serialized access key in Wasm memory
+                // args(2) = pointer to serialized entry points in Wasm memory
+                // args(3) = size of serialized entry points
+                // args(4) = pointer to named keys to be saved with the contract
+                // args(5) = size of named keys
+                // args(6) = pointer to Wasm memory where we will save the new version
+                let (
+                    package_hash_ptr,
+                    access_key_ptr,
+                    entry_points_ptr,
+                    entry_points_size,
+                    named_keys_ptr,
+                    named_keys_size,
+                    version_ptr,
+                ): (_, _, _, u32, _, u32, _) = Args::parse(args)?;
+                let package_hash = self.package_hash_from_mem(package_hash_ptr)?;
+                let access_key = self.access_key_from_mem(access_key_ptr)?;
+                let entry_points_bytes =
+                    self.bytes_from_mem(entry_points_ptr, entry_points_size as usize)?;
+                let entry_points =
+                    bytesrepr::deserialize(entry_points_bytes).map_err(Error::BytesRepr)?;
+                let named_keys_bytes =
+                    self.bytes_from_mem(named_keys_ptr, named_keys_size as usize)?;
+                let named_keys =
+                    bytesrepr::deserialize(named_keys_bytes).map_err(Error::BytesRepr)?;
+                let version =
+                    self.add_contract_version(package_hash, access_key, entry_points, named_keys)?;
+                self.memory
+                    .set(version_ptr, &version.to_le_bytes())
+                    .map_err(Error::Interpreter)?;
+                Ok(None)
+            }
+
+            FunctionIndex::DisableContractVersionIndex => {
+                // args(0) = pointer to contract package hash in Wasm memory
+                // args(1) = pointer to serialized access key in Wasm memory
+                // args(2) = version to disable
+                let (package_hash_ptr, access_key_ptr, version) = Args::parse(args)?;
+                let package_hash = self.package_hash_from_mem(package_hash_ptr)?;
+                let access_key = self.access_key_from_mem(access_key_ptr)?;
+                self.disable_contract_version(package_hash, access_key, version)?;
+                Ok(None)
+            }
+
+            FunctionIndex::CallVersionedContractIndex => {
+                // args(0) = pointer to contract package hash in Wasm memory
+                // args(1) = version to call, or 0 to call the highest enabled version
+                // args(2) = pointer to entry point name in Wasm memory
+                // args(3) = size of entry point name
+                // args(4) = pointer to function arguments in Wasm memory
+                // args(5) = size of arguments
+                // args(6) = pointer to result size (output)
+                let (
+                    package_hash_ptr,
+                    version,
+                    entry_point_ptr,
+                    entry_point_size,
+                    args_ptr,
+                    args_size,
+                    result_size_ptr,
+                ): (_, u32, _, _, _, u32, _) = Args::parse(args)?;
+                let package_hash = self.package_hash_from_mem(package_hash_ptr)?;
+                let version = if version == 0 { None } else { Some(version) };
+                let entry_point = self.string_from_mem(entry_point_ptr, entry_point_size)?;
+                let args_bytes = self.bytes_from_mem(args_ptr, args_size as usize)?;
+                let ret = self.call_versioned_contract_host_buf(
+                    package_hash,
+                    version,
+                    entry_point,
+                    args_bytes,
+                    result_size_ptr,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetCallStackIndex => {
+                // args(0) = pointer to result size (output)
+                let output_size_ptr = Args::parse(args)?;
+                let ret = self.get_call_stack(output_size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::ListAuthorizationKeysIndex => {
+                // args(0) = pointer to result size (output)
+                let output_size_ptr = Args::parse(args)?;
+                let ret = self.list_authorization_keys(output_size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::UpdateAssociatedKeysIndex => {
+                // args(0) = pointer to serialized key updates in Wasm memory
+                // args(1) = size of serialized key updates
+                let (updates_ptr, updates_size) = Args::parse(args)?;
+                let value = self.update_associated_keys(updates_ptr, updates_size)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::ListAssociatedKeysIndex => {
+                // args(0) = pointer to result size (output)
+                let output_size_ptr = Args::parse(args)?;
+                let ret = self.list_associated_keys(output_size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::NamespaceIndex => {
+                // args(0) = pointer to namespace name in Wasm memory
+                // args(1) = size of namespace name
+                // args(2) = pointer to Wasm memory where to write the seed
+                let (name_ptr, name_size, dest_ptr) = Args::parse(args)?;
+                self.namespace(name_ptr, name_size, dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::ReadNamespacedIndex => {
+                // args(0) = pointer to namespace seed in Wasm memory
+                // args(1) = pointer to key in Wasm memory
+                // args(2) = size of key
+                // args(3) = pointer to output size (output param)
+                let (seed_ptr, key_ptr, key_size, output_size_ptr) = Args::parse(args)?;
+                let ret = self.read_namespaced(seed_ptr, key_ptr, key_size, output_size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::WriteNamespacedIndex => {
+                // args(0) = pointer to namespace seed in Wasm memory
+                // args(1) = pointer to key in Wasm memory
+                // args(2) = size of key
+                // args(3) = pointer to value
+                // args(4) = size of value
+                let (seed_ptr, key_ptr, key_size, value_ptr, value_size) = Args::parse(args)?;
+                self.write_namespaced(seed_ptr, key_ptr, key_size, value_ptr, value_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::AddNamespacedIndex => {
+                // args(0) = pointer to namespace seed in Wasm memory
+                // args(1) = pointer to key in Wasm memory
+                // args(2) = size of key
+                // args(3) = pointer to value
+                // args(4) = size of value
+                let (seed_ptr, key_ptr, key_size, value_ptr, value_size) = Args::parse(args)?;
+                self.add_namespaced(seed_ptr, key_ptr, key_size, value_ptr, value_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::GetEraIdIndex => {
+                // args(0) = pointer to Wasm memory where to write.
+                let dest_ptr = Args::parse(args)?;
+                self.get_era_id(dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::GetBlockHeightIndex => {
+                // args(0) = pointer to Wasm memory where to write.
+                let dest_ptr = Args::parse(args)?;
+                self.get_block_height(dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::RandomBytesIndex => {
+                // args(0) = pointer to Wasm memory where to write the random bytes
+                let dest_ptr = Args::parse(args)?;
+                self.random_bytes(dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::Blake2bIndex => {
+                // args(0) = pointer to data in Wasm memory
+                // args(1) = size of data in Wasm memory
+                // args(2) = pointer to Wasm memory where to write the digest
+                let (data_ptr, data_size, dest_ptr) = Args::parse(args)?;
+                self.blake2b(data_ptr, data_size, dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::Sha256Index => {
+                // args(0) = pointer to data in Wasm memory
+                // args(1) = size of data in Wasm memory
+                // args(2) = pointer to Wasm memory where to write the digest
+                let (data_ptr, data_size, dest_ptr) = Args::parse(args)?;
+                self.sha256(data_ptr, data_size, dest_ptr)?;
+                Ok(None)
+            }
         }
     }
 }
@@ -2,41 +2,79 @@ mod args;
 mod externals;
 
 use std::{
+    cell::RefCell,
     cmp,
     collections::{BTreeMap, HashMap, HashSet},
     convert::TryFrom,
     iter::IntoIterator,
+    rc::Rc,
 };
 
+use blake2::{
+    digest::{Input, VariableOutput},
+    VarBlake2b,
+};
 use itertools::Itertools;
 use parity_wasm::elements::Module;
+use sha2::{Digest, Sha256};
+use tracing::info_span;
 use wasmi::{ImportsBuilder, MemoryRef, ModuleInstance, ModuleRef, Trap, TrapKind};
 
 use contract::args_parser::ArgsParser;
-use engine_shared::{account::Account, contract::Contract, gas::Gas, stored_value::StoredValue};
+use engine_shared::{
+    account::Account,
+    contract::Contract,
+    contract_package::{ContractHeader, ContractPackage},
+    gas::Gas,
+    logging::{self, log_level::LogLevel},
+    stored_value::StoredValue,
+    transform::TypeMismatch,
+};
 use engine_storage::global_state::StateReader;
+use engine_wasm_prep::{
+    contract_metadata, denied_host_functions::DeniedHostFunctions, wasm_costs::WasmCosts,
+};
 use types::{
-    account::{ActionType, PublicKey, PurseId, Weight, PUBLIC_KEY_SERIALIZED_LENGTH},
+    account::{
+        ActionType, KeyUpdate, PublicKey, PurseId, Weight, PUBLIC_KEY_SERIALIZED_LENGTH,
+        PURSE_ID_SERIALIZED_LENGTH,
+    },
     bytesrepr::{self, ToBytes},
     system_contract_errors,
     system_contract_errors::mint,
-    AccessRights, ApiError, CLType, CLValue, Key, ProtocolVersion, SystemContractType,
-    TransferResult, TransferredTo, URef, U128, U256, U512,
+    AccessRights, ApiError, CLType, CLValue, CallStackElement, ContractVersion, EntryPoint,
+    EntryPointAccess, EntryPoints, Key, Phase, ProtocolVersion, SystemContractType, TransferResult,
+    TransferredTo, URef, BLAKE2B_DIGEST_LENGTH, LOCAL_SEED_LENGTH, U128, U256, U512,
+    UREF_SERIALIZED_LENGTH,
 };
 
-use super::{Error, MINT_NAME, POS_NAME};
+use super::{DebugOutput, Error, HostFunctionProfile, MINT_NAME, POS_NAME};
 use crate::{
-    engine_state::system_contract_cache::SystemContractCache,
-    resolvers::{create_module_resolver, memory_resolver::MemoryResolver},
+    engine_state::{module_cache::ModuleCache, EngineConfig},
+    resolvers::{
+        abi_version::{AbiVersion, LEGACY_NAMESPACE},
+        create_module_resolver, host_function_table,
+        memory_resolver::MemoryResolver,
+        v1_function_index::FunctionIndex,
+    },
     runtime_context::RuntimeContext,
     Address,
 };
 
+/// The method of the mint contract transferring motes between purses.
+const MINT_TRANSFER_METHOD_NAME: &str = "transfer";
+
 pub struct Runtime<'a, R> {
-    system_contract_cache: SystemContractCache,
+    config: EngineConfig,
+    module_cache: ModuleCache,
     memory: MemoryRef,
     module: Module,
     host_buf: Option<CLValue>,
+    /// Text printed by the executed contract and the contracts it calls, see [`Runtime::print`].
+    stdout: Rc<RefCell<DebugOutput>>,
+    /// Counters of the host function calls made by the executed contract and the contracts it
+    /// calls, if profiling is enabled.
+    host_function_profile: Option<Rc<RefCell<HostFunctionProfile>>>,
     context: RuntimeContext<'a, R>,
 }
 
@@ -59,11 +97,23 @@ pub fn rename_export_to_call(module: &mut Module, name: String) {
 pub fn instance_and_memory(
     parity_module: Module,
     protocol_version: ProtocolVersion,
+    denied_host_functions: &DeniedHostFunctions,
+    max_memory: u32,
 ) -> Result<(ModuleRef, MemoryRef), Error> {
+    let abi_version = AbiVersion::of_module(&parity_module)?;
     let module = wasmi::Module::from_parity_wasm_module(parity_module)?;
-    let resolver = create_module_resolver(protocol_version)?;
+    let resolver = create_module_resolver(
+        protocol_version,
+        abi_version,
+        denied_host_functions,
+        max_memory,
+    )?;
+    // `AbiVersion::of_module` has checked that the module imports only the injected `gas` function
+    // from the legacy namespace if it targets a versioned one
+    let abi_namespace = abi_version.namespace();
     let mut imports = ImportsBuilder::new();
-    imports.push_resolver("env", &resolver);
+    imports.push_resolver(LEGACY_NAMESPACE, &resolver);
+    imports.push_resolver(abi_namespace.as_str(), &resolver);
     let instance = ModuleInstance::new(&module, &imports)?.assert_no_start();
 
     let memory = resolver.memory_ref()?;
@@ -126,6 +176,58 @@ pub fn extract_access_rights_from_keys<I: IntoIterator<Item = Key>>(
         .collect()
 }
 
+/// Returns the header of the given version of `contract_package`, or of its current version if
+/// `version` is `None`, together with its `entry_point`, checking that the version is enabled and
+/// that `caller` meets the access requirements of the entry point.
+///
+/// `knows_uref` tells whether the caller holds a `URef`.  Group `URef`s only need to be known to
+/// the caller; their access rights are not compared.
+pub fn get_entry_point<'a, F: Fn(&URef) -> bool>(
+    contract_package: &'a ContractPackage,
+    version: Option<ContractVersion>,
+    entry_point: &str,
+    caller: &PublicKey,
+    knows_uref: F,
+) -> Result<(&'a ContractHeader, &'a EntryPoint), Error> {
+    let contract_header = match version {
+        Some(version) => contract_package
+            .get_version(version)
+            .filter(|contract_header| contract_header.is_enabled())
+            .ok_or(Error::InvalidContractVersion(version))?,
+        None => contract_package
+            .current_version()
+            .map(|(_, contract_header)| contract_header)
+            .ok_or(Error::NoEnabledContractVersion)?,
+    };
+
+    let entry_point_type = contract_header
+        .entry_points()
+        .get(entry_point)
+        .ok_or_else(|| Error::EntryPointNotFound(entry_point.to_string()))?;
+    let can_access = match entry_point_type.access() {
+        EntryPointAccess::Public => true,
+        EntryPointAccess::Groups(urefs) => urefs.iter().any(knows_uref),
+        EntryPointAccess::Accounts(public_keys) => public_keys.contains(caller),
+    };
+    if !can_access {
+        return Err(Error::EntryPointAccessDenied(entry_point.to_string()));
+    }
+
+    Ok((contract_header, entry_point_type))
+}
+
+/// Converts an error from a call to the mint's "transfer" method into the error returned to the
+/// calling contract.  Errors raised by the mint itself are passed on as [`ApiError::Mint`] so that
+/// the caller can tell e.g. insufficient funds from an invalid purse.
+fn transfer_error_to_api_error(error: Error) -> ApiError {
+    match error {
+        Error::SystemContractError(system_contract_errors::Error::MintError(mint_error)) => {
+            mint_error.into()
+        }
+        _ => ApiError::Transfer,
+    }
+}
+
 #[allow(clippy::cognitive_complexity)]
 fn extract_urefs(cl_value: &CLValue) -> Result<Vec<URef>, Error> {
     match cl_value.cl_type() {
@@ -1333,11 +1435,14 @@ fn extract_urefs(cl_value: &CLValue) -> Result<Vec<URef>, Error> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn sub_call<R>(
     parity_module: Module,
+    entry_point: &str,
     args: Vec<CLValue>,
     named_keys: &mut BTreeMap<String, Key>,
     key: Key,
+    package_hash: Option<[u8; 32]>,
     current_runtime: &mut Runtime<R>,
     // Unforgable references passed across the call boundary from caller to callee (necessary if
     // the contract takes a uref argument).
@@ -1348,7 +1453,17 @@ where
     R: StateReader<Key, StoredValue>,
     R::Error: Into<Error>,
 {
-    let (instance, memory) = instance_and_memory(parity_module.clone(), protocol_version)?;
+    let span = info_span!("sub_call", contract = ?key, entry_point);
+    let _guard = span.enter();
+
+    let protocol_data = current_runtime.context.protocol_data();
+    let max_memory = protocol_data.wasm_costs().memory_limit();
+    let (instance, memory) = instance_and_memory(
+        parity_module.clone(),
+        protocol_version,
+        protocol_data.denied_host_functions(),
+        max_memory,
+    )?;
 
     let access_rights = {
         let mut keys: Vec<Key> = named_keys.values().cloned().collect();
@@ -1358,13 +1473,19 @@ where
         extract_access_rights_from_keys(keys)
     };
 
-    let system_contract_cache = SystemContractCache::clone(&current_runtime.system_contract_cache);
+    let module_cache = ModuleCache::clone(&current_runtime.module_cache);
+
+    let mut call_stack = current_runtime.context.call_stack().to_vec();
+    call_stack.push(CallStackElement::Contract(key.normalize()));
 
     let mut runtime = Runtime {
-        system_contract_cache,
+        config: current_runtime.config.clone(),
+        module_cache,
         memory,
         module: parity_module,
         host_buf: None,
+        stdout: Rc::clone(&current_runtime.stdout),
+        host_function_profile: current_runtime.host_function_profile.clone(),
         context: RuntimeContext::new(
             current_runtime.context.state(),
             named_keys,
@@ -1373,7 +1494,8 @@ where
             current_runtime.context.authorization_keys().clone(),
             &current_runtime.context.account(),
             key,
-            current_runtime.context.get_blocktime(),
+            call_stack,
+            current_runtime.context.get_block_info(),
             current_runtime.context.get_deployhash(),
             current_runtime.context.gas_limit(),
             current_runtime.context.gas_counter(),
@@ -1382,11 +1504,14 @@ where
             protocol_version,
             current_runtime.context.correlation_id(),
             current_runtime.context.phase(),
-            current_runtime.context.protocol_data(),
+            current_runtime.context.protocol_data().clone(),
         ),
     };
+    if let Some(package_hash) = package_hash {
+        runtime.context.set_package_hash(package_hash);
+    }
 
-    let result = instance.invoke_export("call", &[], &mut runtime);
+    let result = instance.invoke_export(entry_point, &[], &mut runtime);
 
     // TODO: To account for the gas used in a subcall, we should uncomment the following lines
     // if !current_runtime.charge_gas(runtime.context.gas_counter()) {
@@ -1436,16 +1561,25 @@ where
     R::Error: Into<Error>,
 {
     pub fn new(
-        system_contract_cache: SystemContractCache,
+        config: EngineConfig,
+        module_cache: ModuleCache,
         memory: MemoryRef,
         module: Module,
         context: RuntimeContext<'a, R>,
     ) -> Self {
+        let host_function_profile = if config.profiling_enabled() {
+            Some(Default::default())
+        } else {
+            None
+        };
         Runtime {
-            system_contract_cache,
+            config,
+            module_cache,
             memory,
             module,
             host_buf: None,
+            stdout: Default::default(),
+            host_function_profile,
             context,
         }
     }
@@ -1480,6 +1614,44 @@ where
         }
     }
 
+    /// Charges gas for writing `cl_value` to global state, by the length of its serialized form.
+    ///
+    /// The per-byte rate is taken from the current protocol's `WasmCosts`; a rate of zero (the
+    /// default until enabled by a protocol upgrade) makes this a no-op.
+    fn charge_storage_write(&mut self, cl_value: &CLValue) -> Result<(), Trap> {
+        let storage_write_byte = self.context.protocol_data().wasm_costs().storage_write_byte;
+        let amount = U512::from(storage_write_byte) * U512::from(cl_value.serialized_length());
+        self.gas(Gas::new(amount))
+    }
+
+    /// Charges the flat cost of a host function accessing global state, as selected from the
+    /// current protocol's `WasmCosts` by `cost`.
+    fn charge_host_op<F: FnOnce(&WasmCosts) -> u32>(&mut self, cost: F) -> Result<(), Trap> {
+        let amount = cost(self.context.protocol_data().wasm_costs());
+        self.gas(Gas::new(U512::from(amount)))
+    }
+
+    /// Charges the flat cost of calling the host function `func`, as priced by the current
+    /// protocol's `HostFunctionCosts`.
+    fn charge_host_function(&mut self, func: FunctionIndex) -> Result<(), Trap> {
+        let host_function_costs = self.context.protocol_data().host_function_costs();
+        if host_function_costs.is_empty() {
+            return Ok(());
+        }
+        let amount = host_function_table::host_function_name(func)
+            .map(|name| host_function_costs.cost(name))
+            .unwrap_or_default();
+        self.gas(Gas::new(U512::from(amount)))
+    }
+
+    /// Charges gas for hashing `data_size` bytes with the `blake2b` or `sha256` host function.
+    fn charge_hash(&mut self, data_size: u32) -> Result<(), Trap> {
+        let wasm_costs = self.context.protocol_data().wasm_costs();
+        let amount = U512::from(wasm_costs.hash_base)
+            + U512::from(wasm_costs.hash_byte) * U512::from(data_size);
+        self.gas(Gas::new(amount))
+    }
+
     fn bytes_from_mem(&self, ptr: u32, size: usize) -> Result<Vec<u8>, Error> {
         self.memory.get(ptr, size).map_err(Into::into)
     }
@@ -1508,23 +1680,14 @@ where
     fn get_function_by_name(&mut self, name_ptr: u32, name_size: u32) -> Result<Vec<u8>, Trap> {
         let name = self.string_from_mem(name_ptr, name_size)?;
 
-        let has_name: bool = self
-            .module
-            .export_section()
-            .and_then(|export_section| {
-                export_section
-                    .entries()
-                    .iter()
-                    .find(|export_entry| export_entry.field() == name)
-            })
-            .is_some();
-
-        if has_name {
+        if self.has_export(&name) {
             let mut module = self.module.clone();
             // We only want the function exported under `name` to be callable; `optimize` removes
             // all code that is not reachable from the exports listed in the second argument.
             pwasm_utils::optimize(&mut module, vec![&name]).unwrap();
             rename_export_to_call(&mut module, name);
+            let metadata_section = contract_metadata::metadata_section(&self.module).cloned();
+            let module = contract_metadata::restore_metadata_section(module, metadata_section);
 
             parity_wasm::serialize(module).map_err(|e| Error::ParityWasm(e).into())
         } else {
@@ -1532,6 +1695,34 @@ where
         }
     }
 
+    fn has_export(&self, name: &str) -> bool {
+        self.module
+            .export_section()
+            .and_then(|export_section| {
+                export_section
+                    .entries()
+                    .iter()
+                    .find(|export_entry| export_entry.field() == name)
+            })
+            .is_some()
+    }
+
+    /// Serializes the current module, keeping only the functions exported under the names of
+    /// `entry_points` and the code reachable from them.
+    fn get_module_with_entry_points(&self, entry_points: &EntryPoints) -> Result<Vec<u8>, Error> {
+        let exports: Vec<&str> = entry_points.names().collect();
+        if let Some(missing) = exports.iter().find(|name| !self.has_export(name)) {
+            return Err(Error::FunctionNotFound(missing.to_string()));
+        }
+
+        let mut module = self.module.clone();
+        pwasm_utils::optimize(&mut module, exports).unwrap();
+        let metadata_section = contract_metadata::metadata_section(&self.module).cloned();
+        let module = contract_metadata::restore_metadata_section(module, metadata_section);
+
+        parity_wasm::serialize(module).map_err(Error::ParityWasm)
+    }
+
     fn is_valid_uref(&mut self, uref_ptr: u32, uref_size: u32) -> Result<bool, Trap> {
         let bytes = self.bytes_from_mem(uref_ptr, uref_size as usize)?;
         let uref: URef = bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?;
@@ -1595,10 +1786,60 @@ where
 
         // Get a key and serialize it
         let key = match self.context.named_keys_get(&name) {
-            Some(key) => key,
+            Some(key) => *key,
             None => return Ok(Err(ApiError::MissingKey)),
         };
 
+        self.write_key_to_mem(&key, output_ptr, output_size, bytes_written_ptr)
+    }
+
+    /// Load the key known by the given name to another account into the Wasm memory.
+    ///
+    /// The access rights of a `URef` are removed, so the caller can't use it to access the value it
+    /// refers to.
+    #[allow(clippy::too_many_arguments)]
+    fn load_named_key_of(
+        &mut self,
+        public_key_ptr: u32,
+        public_key_size: u32,
+        name_ptr: u32,
+        name_size: u32,
+        output_ptr: u32,
+        output_size: usize,
+        bytes_written_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        self.charge_host_op(|costs| costs.read_cost)?;
+        let public_key: PublicKey = {
+            let bytes = self.bytes_from_mem(public_key_ptr, public_key_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        let name = self.string_from_mem(name_ptr, name_size)?;
+
+        let account = match self
+            .context
+            .read_account(&Key::Account(public_key.value()))?
+        {
+            Some(StoredValue::Account(account)) => account,
+            Some(_) => return Ok(Err(ApiError::UnexpectedValueVariant)),
+            None => return Ok(Err(ApiError::ValueNotFound)),
+        };
+        let key = match account.named_keys().get(&name) {
+            Some(Key::URef(uref)) => Key::URef(uref.remove_access_rights()),
+            Some(key) => *key,
+            None => return Ok(Err(ApiError::MissingKey)),
+        };
+
+        self.write_key_to_mem(&key, output_ptr, output_size, bytes_written_ptr)
+    }
+
+    /// Writes the serialized `key` to `output_ptr`, and its length to `bytes_written_ptr`.
+    fn write_key_to_mem(
+        &mut self,
+        key: &Key,
+        output_ptr: u32,
+        output_size: usize,
+        bytes_written_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
         let key_bytes = match key.to_bytes() {
             Ok(bytes) => bytes,
             Err(error) => return Ok(Err(error.into())),
@@ -1642,10 +1883,12 @@ where
     ) -> Result<(), Trap> {
         let name = self.string_from_mem(name_ptr, name_size)?;
         let key = self.key_from_mem(key_ptr, key_size)?;
+        self.validate_payment_write_to(&key)?;
         self.context.put_key(name, key).map_err(Into::into)
     }
 
     fn remove_key(&mut self, name_ptr: u32, name_size: u32) -> Result<(), Trap> {
+        self.validate_payment_write()?;
         let name = self.string_from_mem(name_ptr, name_size)?;
         self.context.remove_key(&name)?;
         Ok(())
@@ -1691,6 +1934,62 @@ where
             .map_err(|e| Error::Interpreter(e).into())
     }
 
+    /// Writes the id of the current era to [dest_ptr] in Wasm memory.
+    fn get_era_id(&self, dest_ptr: u32) -> Result<(), Trap> {
+        let era_id = self
+            .context
+            .get_era_id()
+            .into_bytes()
+            .map_err(Error::BytesRepr)?;
+        self.memory
+            .set(dest_ptr, &era_id)
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// Writes the height of the current block to [dest_ptr] in Wasm memory.
+    fn get_block_height(&self, dest_ptr: u32) -> Result<(), Trap> {
+        let block_height = self
+            .context
+            .get_block_height()
+            .into_bytes()
+            .map_err(Error::BytesRepr)?;
+        self.memory
+            .set(dest_ptr, &block_height)
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// Writes [`RANDOM_BYTES_COUNT`](types::RANDOM_BYTES_COUNT) bytes from the deploy-seeded random
+    /// stream to `dest_ptr`.
+    fn random_bytes(&mut self, dest_ptr: u32) -> Result<(), Trap> {
+        let random_bytes = self.context.random_bytes();
+        self.memory
+            .set(dest_ptr, &random_bytes)
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// Writes the BLAKE2b digest of the given data to `dest_ptr`.
+    fn blake2b(&mut self, data_ptr: u32, data_size: u32, dest_ptr: u32) -> Result<(), Trap> {
+        self.charge_hash(data_size)?;
+        let data = self.bytes_from_mem(data_ptr, data_size as usize)?;
+        let mut digest = [0u8; BLAKE2B_DIGEST_LENGTH];
+        let mut hasher = VarBlake2b::new(BLAKE2B_DIGEST_LENGTH).unwrap();
+        hasher.input(data);
+        hasher.variable_result(|hash| digest.clone_from_slice(hash));
+        self.memory
+            .set(dest_ptr, &digest)
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// Writes the SHA-256 digest of the given data to `dest_ptr`.
+    fn sha256(&mut self, data_ptr: u32, data_size: u32, dest_ptr: u32) -> Result<(), Trap> {
+        self.charge_hash(data_size)?;
+        let data = self.bytes_from_mem(data_ptr, data_size as usize)?;
+        let digest = Sha256::digest(&data);
+        self.memory
+            .set(dest_ptr, digest.as_slice())
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
     /// Return some bytes from the memory and terminate the current `sub_call`. Note that the return
     /// type is `Trap`, indicating that this function will always kill the current Wasm instance.
     fn ret(&mut self, value_ptr: u32, value_size: usize) -> Trap {
@@ -1720,6 +2019,26 @@ where
 
     /// Calls contract living under a `key`, with supplied `args`.
     fn call_contract(&mut self, key: Key, args_bytes: Vec<u8>) -> Result<CLValue, Error> {
+        let args: Vec<CLValue> = bytesrepr::deserialize(args_bytes)?;
+        self.call_contract_entry_point(key, None, "call", args)
+    }
+
+    /// Calls the function exported under `entry_point` by the contract living under `key`, which
+    /// is a version of the contract package under `package_hash` if given.
+    fn call_contract_entry_point(
+        &mut self,
+        key: Key,
+        package_hash: Option<[u8; 32]>,
+        entry_point: &str,
+        args: Vec<CLValue>,
+    ) -> Result<CLValue, Error> {
+        if self.config.reentrancy_guard_enabled() {
+            let element = CallStackElement::Contract(key.normalize());
+            if self.context.call_stack().contains(&element) {
+                return Err(Error::Reentrancy(key.normalize()));
+            }
+        }
+
         let contract = match self.context.read_gs(&key)? {
             Some(StoredValue::Contract(contract)) => contract,
             Some(_) => {
@@ -1731,6 +2050,19 @@ where
             None => return Err(Error::KeyNotFound(key)),
         };
 
+        // Versions of a contract package may only be called through the package, so that disabled
+        // versions and entry point access requirements can't be bypassed
+        if package_hash.is_none() && contract.package_hash().is_some() {
+            return Err(Error::ContractVersionCalledDirectly(key.normalize()));
+        }
+
+        let caller = PublicKey::new(self.context.account().pub_key());
+        if !contract.is_authorized_caller(&caller) {
+            return Err(Error::CallerNotAuthorized(key.normalize()));
+        }
+
+        self.check_mint_transfer(key, &args)?;
+
         // Check for major version compatibility before calling
         let contract_version = contract.protocol_version();
         let current_version = self.context.protocol_version();
@@ -1741,17 +2073,9 @@ where
             });
         }
 
-        let args: Vec<CLValue> = bytesrepr::deserialize(args_bytes)?;
-
-        let maybe_module = match key {
-            Key::URef(uref) => self.system_contract_cache.get(&uref),
-            _ => None,
-        };
-
-        let module = match maybe_module {
-            Some(module) => module,
-            None => parity_wasm::deserialize_buffer(contract.bytes())?,
-        };
+        let module = self
+            .module_cache
+            .get_or_insert_with(contract.bytes(), parity_wasm::deserialize_buffer::<Module>)?;
 
         let mut extra_urefs = vec![];
         // A loop is needed to be able to use the '?' operator
@@ -1770,9 +2094,11 @@ where
 
         let result = sub_call(
             module,
+            entry_point,
             args,
             &mut refs,
             key,
+            package_hash,
             self,
             extra_urefs,
             contract_version,
@@ -1780,6 +2106,33 @@ where
         Ok(result)
     }
 
+    /// Calls `entry_point` of the given version of the contract package stored under
+    /// `package_hash`, or of its highest enabled version if `version` is `None`.
+    fn call_versioned_contract(
+        &mut self,
+        package_hash: [u8; 32],
+        version: Option<ContractVersion>,
+        entry_point: String,
+        args_bytes: Vec<u8>,
+    ) -> Result<CLValue, Error> {
+        let contract_package = self.read_contract_package(package_hash)?;
+        let caller = PublicKey::new(self.context.account().pub_key());
+        let (contract_header, entry_point_type) =
+            get_entry_point(&contract_package, version, &entry_point, &caller, |uref| {
+                self.context
+                    .validate_uref(&uref.remove_access_rights())
+                    .is_ok()
+            })?;
+
+        let args: Vec<CLValue> = bytesrepr::deserialize(args_bytes)?;
+        if !entry_point_type.accepts(&args) {
+            return Err(Error::InvalidEntryPointArgs(entry_point));
+        }
+
+        let contract_key = Key::Hash(contract_header.contract_hash());
+        self.call_contract_entry_point(contract_key, Some(package_hash), &entry_point, args)
+    }
+
     fn call_contract_host_buf(
         &mut self,
         key: Key,
@@ -1792,6 +2145,33 @@ where
         }
 
         let result = self.call_contract(key, args_bytes)?;
+        self.write_call_result(result, result_size_ptr)
+    }
+
+    fn call_versioned_contract_host_buf(
+        &mut self,
+        package_hash: [u8; 32],
+        version: Option<ContractVersion>,
+        entry_point: String,
+        args_bytes: Vec<u8>,
+        result_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        if !self.can_write_to_host_buf() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+
+        let result =
+            self.call_versioned_contract(package_hash, version, entry_point, args_bytes)?;
+        self.write_call_result(result, result_size_ptr)
+    }
+
+    /// Writes the result of a contract call to the host buffer and its size at `result_size_ptr`.
+    fn write_call_result(
+        &mut self,
+        result: CLValue,
+        result_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
         let result_size = result.inner_bytes().len() as u32; // considered to be safe
 
         if let Err(error) = self.write_host_buf(result) {
@@ -1848,6 +2228,7 @@ where
         fn_bytes: Vec<u8>,
         named_keys: BTreeMap<String, Key>,
     ) -> Result<[u8; 32], Error> {
+        self.validate_payment_write()?;
         let contract = Contract::new(fn_bytes, named_keys, self.context.protocol_version());
         let contract_addr = self
             .context
@@ -1863,6 +2244,7 @@ where
         fn_bytes: Vec<u8>,
         named_keys: BTreeMap<String, Key>,
     ) -> Result<[u8; 32], Error> {
+        self.validate_payment_write()?;
         let contract = Contract::new(fn_bytes, named_keys, self.context.protocol_version());
         let new_hash = self
             .context
@@ -1870,6 +2252,134 @@ where
         Ok(new_hash)
     }
 
+    /// Stores a contract under `contract_hash` as a version of the contract package stored under
+    /// `package_hash`.
+    fn store_contract_version(
+        &mut self,
+        contract_hash: [u8; 32],
+        package_hash: [u8; 32],
+        fn_bytes: Vec<u8>,
+        named_keys: BTreeMap<String, Key>,
+    ) -> Result<(), Error> {
+        let contract = Contract::new(fn_bytes, named_keys, self.context.protocol_version())
+            .with_package_hash(package_hash);
+        self.context
+            .store_function_at(contract_hash, StoredValue::Contract(contract))
+    }
+
+    /// Creates a contract package whose first version exposes the given entry points of the
+    /// current module, and returns the hash of the package along with its access key.
+    fn new_contract(
+        &mut self,
+        entry_points: EntryPoints,
+        named_keys: BTreeMap<String, Key>,
+    ) -> Result<([u8; 32], URef), Error> {
+        self.validate_payment_write()?;
+        let access_key = self
+            .context
+            .new_uref(StoredValue::CLValue(CLValue::from_t(())?))?
+            .into_uref()
+            .ok_or_else(|| Error::URefNotFound("contract package access key".to_string()))?;
+
+        // The contract is given the first address and its package the second, but the contract
+        // needs to know the package's hash when it is stored
+        let contract_hash = self.context.new_function_address()?;
+        let package_hash = self.context.new_function_address()?;
+
+        let module_bytes = self.get_module_with_entry_points(&entry_points)?;
+        self.store_contract_version(contract_hash, package_hash, module_bytes, named_keys)?;
+
+        let mut contract_package = ContractPackage::new(access_key, BTreeMap::new());
+        contract_package.add_version(contract_hash, entry_points);
+        self.context
+            .store_function_at(package_hash, StoredValue::ContractPackage(contract_package))?;
+
+        Ok((package_hash, access_key))
+    }
+
+    /// Adds a version exposing the given entry points of the current module to the contract
+    /// package stored under `package_hash`.
+    fn add_contract_version(
+        &mut self,
+        package_hash: [u8; 32],
+        access_key: URef,
+        entry_points: EntryPoints,
+        named_keys: BTreeMap<String, Key>,
+    ) -> Result<ContractVersion, Error> {
+        self.validate_payment_write()?;
+        let mut contract_package =
+            self.read_contract_package_for_update(package_hash, access_key)?;
+
+        let contract_hash = self.context.new_function_address()?;
+        let module_bytes = self.get_module_with_entry_points(&entry_points)?;
+        self.store_contract_version(contract_hash, package_hash, module_bytes, named_keys)?;
+
+        let version = contract_package.add_version(contract_hash, entry_points);
+        self.context
+            .update_contract_package(package_hash, contract_package)?;
+        Ok(version)
+    }
+
+    fn disable_contract_version(
+        &mut self,
+        package_hash: [u8; 32],
+        access_key: URef,
+        version: ContractVersion,
+    ) -> Result<(), Error> {
+        self.validate_payment_write()?;
+        let mut contract_package =
+            self.read_contract_package_for_update(package_hash, access_key)?;
+        if !contract_package.disable_version(version) {
+            return Err(Error::InvalidContractVersion(version));
+        }
+        self.context
+            .update_contract_package(package_hash, contract_package)
+    }
+
+    fn read_contract_package(&mut self, package_hash: [u8; 32]) -> Result<ContractPackage, Error> {
+        let key = Key::Hash(package_hash);
+        match self.context.read_gs(&key)? {
+            Some(StoredValue::ContractPackage(contract_package)) => Ok(contract_package),
+            Some(other) => Err(Error::TypeMismatch(TypeMismatch::new(
+                "ContractPackage".to_string(),
+                other.type_name(),
+            ))),
+            None => Err(Error::KeyNotFound(key)),
+        }
+    }
+
+    /// Reads the contract package stored under `package_hash`, checking that `access_key` is a
+    /// valid, writeable reference to its access key.
+    fn read_contract_package_for_update(
+        &mut self,
+        package_hash: [u8; 32],
+        access_key: URef,
+    ) -> Result<ContractPackage, Error> {
+        let contract_package = self.read_contract_package(package_hash)?;
+        self.context.validate_uref(&access_key)?;
+        if access_key.addr() != contract_package.access_key().addr() {
+            return Err(Error::ForgedReference(access_key));
+        }
+        if !access_key.is_writeable() {
+            return Err(Error::InvalidAccess {
+                required: AccessRights::WRITE,
+            });
+        }
+        Ok(contract_package)
+    }
+
+    /// Reads a contract package hash from Wasm memory.
+    fn package_hash_from_mem(&self, package_hash_ptr: u32) -> Result<[u8; 32], Error> {
+        let bytes = self.bytes_from_mem(package_hash_ptr, 32)?;
+        bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)
+    }
+
+    /// Reads a contract package access key from Wasm memory.
+    fn access_key_from_mem(&self, access_key_ptr: u32) -> Result<URef, Error> {
+        let bytes = self.bytes_from_mem(access_key_ptr, UREF_SERIALIZED_LENGTH)?;
+        bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)
+    }
+
     /// Writes function address (`hash_bytes`) into the Wasm memory (at
     /// `dest_ptr` pointer).
     fn function_address(&mut self, hash_bytes: [u8; 32], dest_ptr: u32) -> Result<(), Trap> {
@@ -1881,7 +2391,10 @@ where
     /// Generates new unforgable reference and adds it to the context's
     /// access_rights set.
     fn new_uref(&mut self, key_ptr: u32, value_ptr: u32, value_size: u32) -> Result<(), Trap> {
+        self.charge_host_op(|costs| costs.write_cost)?;
+        self.validate_payment_write()?;
         let cl_value = self.cl_value_from_mem(value_ptr, value_size)?; // read initial value from memory
+        self.charge_storage_write(&cl_value)?;
         let key = self.context.new_uref(StoredValue::CLValue(cl_value))?;
         self.memory
             .set(key_ptr, &key.into_bytes().map_err(Error::BytesRepr)?)
@@ -1896,8 +2409,11 @@ where
         value_ptr: u32,
         value_size: u32,
     ) -> Result<(), Trap> {
+        self.charge_host_op(|costs| costs.write_cost)?;
         let key = self.key_from_mem(key_ptr, key_size)?;
+        self.validate_payment_write_to(&key)?;
         let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
+        self.charge_storage_write(&cl_value)?;
         self.context
             .write_gs(key, StoredValue::CLValue(cl_value))
             .map_err(Into::into)
@@ -1912,8 +2428,11 @@ where
         value_ptr: u32,
         value_size: u32,
     ) -> Result<(), Trap> {
+        self.charge_host_op(|costs| costs.write_cost)?;
+        self.validate_payment_write()?;
         let key_bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
         let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
+        self.charge_storage_write(&cl_value)?;
         self.context
             .write_ls(&key_bytes, cl_value)
             .map_err(Into::into)
@@ -1927,7 +2446,9 @@ where
         value_ptr: u32,
         value_size: u32,
     ) -> Result<(), Trap> {
+        self.charge_host_op(|costs| costs.add_cost)?;
         let key = self.key_from_mem(key_ptr, key_size)?;
+        self.validate_payment_write_to(&key)?;
         let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
         self.context
             .add_gs(key, StoredValue::CLValue(cl_value))
@@ -1943,6 +2464,8 @@ where
         value_ptr: u32,
         value_size: u32,
     ) -> Result<(), Trap> {
+        self.charge_host_op(|costs| costs.add_cost)?;
+        self.validate_payment_write()?;
         let key_bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
         let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
         self.context
@@ -1961,6 +2484,7 @@ where
         key_size: u32,
         output_size_ptr: u32,
     ) -> Result<Result<(), ApiError>, Trap> {
+        self.charge_host_op(|costs| costs.read_cost)?;
         if !self.can_write_to_host_buf() {
             // Exit early if the host buffer is already occupied
             return Ok(Err(ApiError::HostBufferFull));
@@ -1985,6 +2509,116 @@ where
         Ok(Ok(()))
     }
 
+    /// Reads the [`ContractMetadata`](types::ContractMetadata) embedded in the contract stored
+    /// under the key, and writes it to the host buffer.
+    fn get_contract_metadata(
+        &mut self,
+        key_ptr: u32,
+        key_size: u32,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if !self.can_write_to_host_buf() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+
+        let key = self.key_from_mem(key_ptr, key_size)?;
+        let contract = match self.context.read_gs(&key)? {
+            Some(StoredValue::Contract(contract)) => contract,
+            _ => return Ok(Err(ApiError::ContractNotFound)),
+        };
+
+        let module: Module =
+            parity_wasm::deserialize_buffer(contract.bytes()).map_err(Error::from)?;
+        let metadata = match contract_metadata::get_contract_metadata(&module) {
+            Ok(Some(metadata)) => metadata,
+            Ok(None) => return Ok(Err(ApiError::ValueNotFound)),
+            Err(_) => return Ok(Err(ApiError::Deserialize)),
+        };
+
+        let cl_value = CLValue::from_t(metadata).map_err(Error::from)?;
+        let value_size = cl_value.inner_bytes().len() as u32;
+        if let Err(error) = self.write_host_buf(cl_value) {
+            return Ok(Err(error));
+        }
+
+        let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
+        if let Err(error) = self.memory.set(output_size_ptr, &value_bytes) {
+            return Err(Error::Interpreter(error).into());
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Writes the call stack of the current context to the host buffer.
+    fn get_call_stack(&mut self, output_size_ptr: u32) -> Result<Result<(), ApiError>, Trap> {
+        if !self.can_write_to_host_buf() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+
+        let call_stack = self.context.call_stack().to_vec();
+        let cl_value = CLValue::from_t(call_stack).map_err(Error::from)?;
+        let value_size = cl_value.inner_bytes().len() as u32;
+        if let Err(error) = self.write_host_buf(cl_value) {
+            return Ok(Err(error));
+        }
+
+        let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
+        if let Err(error) = self.memory.set(output_size_ptr, &value_bytes) {
+            return Err(Error::Interpreter(error).into());
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Writes the keys which signed the current deploy to the host buffer.
+    fn list_authorization_keys(
+        &mut self,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if !self.can_write_to_host_buf() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+
+        let authorization_keys: Vec<PublicKey> =
+            self.context.authorization_keys().iter().copied().collect();
+        let cl_value = CLValue::from_t(authorization_keys).map_err(Error::from)?;
+        let value_size = cl_value.inner_bytes().len() as u32;
+        if let Err(error) = self.write_host_buf(cl_value) {
+            return Ok(Err(error));
+        }
+
+        let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
+        if let Err(error) = self.memory.set(output_size_ptr, &value_bytes) {
+            return Err(Error::Interpreter(error).into());
+        }
+
+        Ok(Ok(()))
+    }
+
+    fn list_associated_keys(&mut self, output_size_ptr: u32) -> Result<Result<(), ApiError>, Trap> {
+        if !self.can_write_to_host_buf() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+
+        let associated_keys = self.context.associated_keys()?;
+        let cl_value = CLValue::from_t(associated_keys).map_err(Error::from)?;
+        let value_size = cl_value.inner_bytes().len() as u32;
+        if let Err(error) = self.write_host_buf(cl_value) {
+            return Ok(Err(error));
+        }
+
+        let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
+        if let Err(error) = self.memory.set(output_size_ptr, &value_bytes) {
+            return Err(Error::Interpreter(error).into());
+        }
+
+        Ok(Ok(()))
+    }
+
     /// Similar to `read`, this function is for reading from the "local cluster"
     /// of global state
     fn read_local(
@@ -1993,6 +2627,7 @@ where
         key_size: u32,
         output_size_ptr: u32,
     ) -> Result<Result<(), ApiError>, Trap> {
+        self.charge_host_op(|costs| costs.read_cost)?;
         if !self.can_write_to_host_buf() {
             // Exit early if the host buffer is already occupied
             return Ok(Err(ApiError::HostBufferFull));
@@ -2018,12 +2653,104 @@ where
         Ok(Ok(()))
     }
 
+    fn seed_from_mem(&self, seed_ptr: u32) -> Result<[u8; LOCAL_SEED_LENGTH], Error> {
+        let bytes = self.bytes_from_mem(seed_ptr, LOCAL_SEED_LENGTH)?;
+        let mut seed = [0u8; LOCAL_SEED_LENGTH];
+        seed.copy_from_slice(&bytes);
+        Ok(seed)
+    }
+
+    /// Writes the seed of the namespace of local state whose name is at `name_ptr` to `dest_ptr`.
+    fn namespace(&mut self, name_ptr: u32, name_size: u32, dest_ptr: u32) -> Result<(), Trap> {
+        self.charge_hash(name_size)?;
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        let seed = self.context.namespace(&name);
+        self.memory
+            .set(dest_ptr, &seed)
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// Similar to `read_local`, but reads from the namespace with the seed at `seed_ptr`.
+    fn read_namespaced(
+        &mut self,
+        seed_ptr: u32,
+        key_ptr: u32,
+        key_size: u32,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        self.charge_host_op(|costs| costs.read_cost)?;
+        if !self.can_write_to_host_buf() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+
+        let seed = self.seed_from_mem(seed_ptr)?;
+        let key_bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
+
+        let cl_value = match self.context.read_ns(seed, &key_bytes)? {
+            Some(cl_value) => cl_value,
+            None => return Ok(Err(ApiError::ValueNotFound)),
+        };
+
+        let value_size = cl_value.inner_bytes().len() as u32;
+        if let Err(error) = self.write_host_buf(cl_value) {
+            return Ok(Err(error));
+        }
+
+        let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
+        if let Err(error) = self.memory.set(output_size_ptr, &value_bytes) {
+            return Err(Error::Interpreter(error).into());
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Similar to `write_local`, but writes to the namespace with the seed at `seed_ptr`.
+    fn write_namespaced(
+        &mut self,
+        seed_ptr: u32,
+        key_ptr: u32,
+        key_size: u32,
+        value_ptr: u32,
+        value_size: u32,
+    ) -> Result<(), Trap> {
+        self.charge_host_op(|costs| costs.write_cost)?;
+        self.validate_payment_write()?;
+        let seed = self.seed_from_mem(seed_ptr)?;
+        let key_bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
+        let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
+        self.charge_storage_write(&cl_value)?;
+        self.context
+            .write_ns(seed, &key_bytes, cl_value)
+            .map_err(Into::into)
+    }
+
+    /// Similar to `add_local`, but adds to a value in the namespace with the seed at `seed_ptr`.
+    fn add_namespaced(
+        &mut self,
+        seed_ptr: u32,
+        key_ptr: u32,
+        key_size: u32,
+        value_ptr: u32,
+        value_size: u32,
+    ) -> Result<(), Trap> {
+        self.charge_host_op(|costs| costs.add_cost)?;
+        self.validate_payment_write()?;
+        let seed = self.seed_from_mem(seed_ptr)?;
+        let key_bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
+        let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
+        self.context
+            .add_ns(seed, &key_bytes, cl_value)
+            .map_err(Into::into)
+    }
+
     /// Reverts contract execution with a status specified.
     fn revert(&mut self, status: u32) -> Trap {
         Error::Revert(status).into()
     }
 
     fn add_associated_key(&mut self, public_key_ptr: u32, weight_value: u8) -> Result<i32, Trap> {
+        self.validate_payment_write()?;
         let public_key = {
             // Public key as serialized bytes
             let source_serialized =
@@ -2048,6 +2775,7 @@ where
     }
 
     fn remove_associated_key(&mut self, public_key_ptr: u32) -> Result<i32, Trap> {
+        self.validate_payment_write()?;
         let public_key = {
             // Public key as serialized bytes
             let source_serialized =
@@ -2069,6 +2797,7 @@ where
         public_key_ptr: u32,
         weight_value: u8,
     ) -> Result<i32, Trap> {
+        self.validate_payment_write()?;
         let public_key = {
             // Public key as serialized bytes
             let source_serialized =
@@ -2092,11 +2821,29 @@ where
         }
     }
 
+    fn update_associated_keys(&mut self, updates_ptr: u32, updates_size: u32) -> Result<i32, Trap> {
+        self.validate_payment_write()?;
+        let updates: Vec<KeyUpdate> = {
+            let updates_bytes = self.bytes_from_mem(updates_ptr, updates_size as usize)?;
+            bytesrepr::deserialize(updates_bytes).map_err(Error::BytesRepr)?
+        };
+
+        match self.context.update_associated_keys(&updates) {
+            Ok(_) => Ok(0),
+            // As with `UpdateKeyFailure`, variants of `UpdateKeysFailure` start from `1`, so `0`
+            // is free to signal success.
+            Err(Error::UpdateKeysFailure(e)) => Ok(e as i32),
+            // Any other variant just pass as `Trap`
+            Err(e) => Err(e.into()),
+        }
+    }
+
     fn set_action_threshold(
         &mut self,
         action_type_value: u32,
         threshold_value: u8,
     ) -> Result<i32, Trap> {
+        self.validate_payment_write()?;
         match ActionType::try_from(action_type_value) {
             Ok(action_type) => {
                 let threshold = Weight::new(threshold_value);
@@ -2145,8 +2892,212 @@ where
         self.mint_create(mint_contract_key)
     }
 
-    /// Calls the "transfer" method on the mint contract at the given mint
-    /// contract key
+    /// Creates a new purse, stores it in the account under the given name, and writes it to
+    /// `dest_ptr` in the Wasm memory.
+    fn create_named_purse(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        dest_ptr: u32,
+        dest_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        self.charge_host_op(|costs| costs.write_cost)?;
+        if (dest_size as usize) < PURSE_ID_SERIALIZED_LENGTH {
+            return Ok(Err(ApiError::BufferTooSmall));
+        }
+        let name = self.string_from_mem(name_ptr, name_size)?;
+
+        // Checked before creating the purse, so that no purse is created in vain
+        if self.context.get_named_purse(&name)?.is_some() {
+            return Ok(Err(ApiError::DuplicateNamedPurse));
+        }
+
+        let purse_id = self.create_purse()?;
+        if !self.context.add_named_purse(name, purse_id)? {
+            return Ok(Err(ApiError::DuplicateNamedPurse));
+        }
+
+        let purse_id_bytes = purse_id.into_bytes().map_err(Error::BytesRepr)?;
+        self.memory
+            .set(dest_ptr, &purse_id_bytes)
+            .map_err(|e| Error::Interpreter(e).into())
+            .map(Ok)
+    }
+
+    /// Writes the purse of the account stored under the given name to `dest_ptr` in the Wasm
+    /// memory.
+    fn get_named_purse(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        dest_ptr: u32,
+        dest_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        self.charge_host_op(|costs| costs.read_cost)?;
+        if (dest_size as usize) < PURSE_ID_SERIALIZED_LENGTH {
+            return Ok(Err(ApiError::BufferTooSmall));
+        }
+        let name = self.string_from_mem(name_ptr, name_size)?;
+
+        let purse_id = match self.context.get_named_purse(&name)? {
+            Some(purse_id) => purse_id,
+            None => return Ok(Err(ApiError::MissingKey)),
+        };
+
+        let purse_id_bytes = purse_id.into_bytes().map_err(Error::BytesRepr)?;
+        self.memory
+            .set(dest_ptr, &purse_id_bytes)
+            .map_err(|e| Error::Interpreter(e).into())
+            .map(Ok)
+    }
+
+    /// Moves the whole balance of a purse into a new purse owned by the currently executing stored
+    /// contract, stores the new purse under the given name in the contract's named keys, and
+    /// writes it to `dest_ptr` in the Wasm memory.
+    ///
+    /// Whoever else holds the original purse is left with an empty purse, and can't withdraw from
+    /// the new one unless the contract hands it out.
+    #[allow(clippy::too_many_arguments)]
+    fn transfer_uref_ownership(
+        &mut self,
+        purse_ptr: u32,
+        purse_size: u32,
+        name_ptr: u32,
+        name_size: u32,
+        dest_ptr: u32,
+        dest_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        self.charge_host_op(|costs| costs.write_cost)?;
+        if (dest_size as usize) < PURSE_ID_SERIALIZED_LENGTH {
+            return Ok(Err(ApiError::BufferTooSmall));
+        }
+        let purse_id: PurseId = {
+            let bytes = self.bytes_from_mem(purse_ptr, purse_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        let name = self.string_from_mem(name_ptr, name_size)?;
+
+        if let Key::Account(_) = self.context.base_key() {
+            return Ok(Err(ApiError::PermissionDenied));
+        }
+        self.context.validate_uref(&purse_id.value())?;
+        if !purse_id.value().is_writeable() {
+            return Ok(Err(ApiError::NoAccessRights));
+        }
+        if self.context.named_keys_contains_key(&name) {
+            return Ok(Err(ApiError::DuplicateKey));
+        }
+        let balance = match self.get_balance(purse_id)? {
+            Some(balance) => balance,
+            None => return Ok(Err(ApiError::InvalidPurse)),
+        };
+
+        let new_purse_id = self.create_purse()?;
+        let mint_contract_key = self.get_mint_contract_uref().into();
+        if let Err(error) = self.mint_transfer(mint_contract_key, purse_id, new_purse_id, balance) {
+            return Ok(Err(transfer_error_to_api_error(error)));
+        }
+        self.context.put_key(name, new_purse_id.value().into())?;
+
+        let purse_id_bytes = new_purse_id.into_bytes().map_err(Error::BytesRepr)?;
+        self.memory
+            .set(dest_ptr, &purse_id_bytes)
+            .map_err(|e| Error::Interpreter(e).into())
+            .map(Ok)
+    }
+
+    /// Logs the UTF-8 encoded message at `message_ptr` in the Wasm memory, if contract logging is
+    /// enabled in the engine configuration.
+    ///
+    /// Never charges gas and never fails, so that enabling logging can't change the outcome of a
+    /// deploy.
+    fn log(&self, message_ptr: u32, message_size: u32) {
+        if !self.config.contract_logging_enabled() {
+            return;
+        }
+        let message = match self.bytes_from_mem(message_ptr, message_size as usize) {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(_) => return,
+        };
+        let mut properties = BTreeMap::new();
+        properties.insert(
+            "base-key".to_string(),
+            format!("{}", self.context.base_key()),
+        );
+        properties.insert("message".to_string(), message);
+        logging::log_details(
+            LogLevel::Debug,
+            "contract log from {base-key}: {message}".to_owned(),
+            properties,
+        );
+    }
+
+    /// Appends the UTF-8 encoded text at `text_ptr` in the Wasm memory to the output of the deploy.
+    ///
+    /// The output is only collected if the engine is configured to, see
+    /// [`EngineConfig::with_debug_print`], but the text is read and validated either way, so that
+    /// the result doesn't depend on the configuration.  Like logging, printing never charges gas.
+    fn print(&mut self, text_ptr: u32, text_size: u32) -> Result<Result<(), ApiError>, Trap> {
+        let bytes = self.bytes_from_mem(text_ptr, text_size as usize)?;
+        let text = match String::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(_) => return Ok(Err(ApiError::InvalidArgument)),
+        };
+        if self.config.debug_print_enabled() {
+            self.stdout.borrow_mut().push(text);
+        }
+        Ok(Ok(()))
+    }
+
+    /// Checks the vesting schedule of the account before a call to the "transfer" method of the
+    /// mint contract, whether it is made through a host function or by calling the mint directly.
+    ///
+    /// Calls with malformed arguments are left for the mint to reject.
+    fn check_mint_transfer(&mut self, key: Key, args: &[CLValue]) -> Result<(), Error> {
+        let mint = self.context.protocol_data().mint();
+        if key.as_uref().map(|uref| uref.addr()) != Some(mint.addr()) {
+            return Ok(());
+        }
+        let method: Option<String> = args.get(0).and_then(|arg| arg.clone().into_t().ok());
+        if method.as_deref() != Some(MINT_TRANSFER_METHOD_NAME) {
+            return Ok(());
+        }
+        let source: Option<URef> = args.get(1).and_then(|arg| arg.clone().into_t().ok());
+        let amount: Option<U512> = args.get(3).and_then(|arg| arg.clone().into_t().ok());
+        match (source, amount) {
+            (Some(source), Some(amount)) => {
+                self.check_vesting_schedule(PurseId::new(source), amount)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that transferring `amount` out of `source` leaves at least the amount which is still
+    /// locked by the vesting schedule of the account, if `source` is the account's main purse.
+    ///
+    /// Transfers exceeding the balance of the purse are left for the mint to reject.
+    fn check_vesting_schedule(&mut self, source: PurseId, amount: U512) -> Result<(), Error> {
+        let account = self.context.account();
+        if account.purse_id().value().addr() != source.value().addr() {
+            return Ok(());
+        }
+        let locked_amount = match account.vesting_schedule() {
+            Some(vesting_schedule) => {
+                vesting_schedule.locked_amount_at(self.context.get_blocktime())
+            }
+            None => return Ok(()),
+        };
+        if locked_amount.is_zero() {
+            return Ok(());
+        }
+        let balance = self.get_balance(source)?.unwrap_or_default();
+        if balance >= amount && balance - amount < locked_amount {
+            return Err(system_contract_errors::Error::from(mint::Error::FundsLocked).into());
+        }
+        Ok(())
+    }
+
+    /// Calls the "transfer" method on the mint contract at the given mint contract key.
     fn mint_transfer(
         &mut self,
         mint_contract_key: Key,
@@ -2158,7 +3109,12 @@ where
         let target_value: URef = target.value();
 
         let args_bytes = {
-            let args = ("transfer", source_value, target_value, amount);
+            let args = (
+                MINT_TRANSFER_METHOD_NAME,
+                source_value,
+                target_value,
+                amount,
+            );
             ArgsParser::parse(args)?.into_bytes()?
         };
 
@@ -2312,13 +3268,9 @@ where
 
         let mint_contract_key = self.get_mint_contract_uref().into();
 
-        if self
-            .mint_transfer(mint_contract_key, source, target, amount)
-            .is_ok()
-        {
-            Ok(Ok(()))
-        } else {
-            Ok(Err(ApiError::Transfer))
+        match self.mint_transfer(mint_contract_key, source, target, amount) {
+            Ok(()) => Ok(Ok(())),
+            Err(error) => Ok(Err(transfer_error_to_api_error(error))),
         }
     }
 
@@ -2398,7 +3350,9 @@ where
     }
 
     /// If key is in named_keys with AccessRights::Write, processes bytes from calling contract
-    /// and writes them at the provided uref, overwriting existing value if any
+    /// and writes them at the provided uref, overwriting existing value if any.
+    ///
+    /// System contracts can only be upgraded during `Phase::System`, i.e. by an upgrade installer.
     fn upgrade_contract_at_uref(
         &mut self,
         name_ptr: u32,
@@ -2406,10 +3360,17 @@ where
         key_ptr: u32,
         key_size: u32,
     ) -> Result<Result<(), ApiError>, Trap> {
+        self.validate_payment_write()?;
         let key = self.key_from_mem(key_ptr, key_size)?;
-        let named_keys = match self.context.read_gs(&key)? {
+        if self.is_system_contract(&key) && self.context.phase() != Phase::System {
+            return Ok(Err(ApiError::PermissionDenied));
+        }
+        let (named_keys, authorized_callers) = match self.context.read_gs(&key)? {
             None => Err(Error::KeyNotFound(key)),
-            Some(StoredValue::Contract(contract)) => Ok(contract.named_keys().clone()),
+            Some(StoredValue::Contract(contract)) => Ok((
+                contract.named_keys().clone(),
+                contract.authorized_callers().map(<[PublicKey]>::to_vec),
+            )),
             Some(_) => Err(Error::FunctionNotFound(format!(
                 "Value at {:?} is not a contract",
                 key
@@ -2418,13 +3379,93 @@ where
         let bytes = self.get_function_by_name(name_ptr, name_size)?;
         match self
             .context
-            .upgrade_contract_at_uref(key, bytes, named_keys)
+            .upgrade_contract_at_uref(key, bytes, named_keys, authorized_callers)
         {
             Ok(_) => Ok(Ok(())),
             Err(_) => Ok(Err(ApiError::UpgradeContractAtURef)),
         }
     }
 
+    /// Replaces the accounts allowed to call the contract stored under the key at `key_ptr`, with
+    /// the serialized `Option<Vec<PublicKey>>` at `acl_ptr`.  `None` allows anyone to call it.
+    ///
+    /// The key has to be a writeable `URef`, and can't point at a system contract.
+    fn update_contract_acl(
+        &mut self,
+        key_ptr: u32,
+        key_size: u32,
+        acl_ptr: u32,
+        acl_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        self.charge_host_op(|costs| costs.write_cost)?;
+        self.validate_payment_write()?;
+        let key = self.key_from_mem(key_ptr, key_size)?;
+        let authorized_callers: Option<Vec<PublicKey>> = {
+            let bytes = self.bytes_from_mem(acl_ptr, acl_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        self.context.validate_key(&key)?;
+        if self.is_system_contract(&key) || !self.context.is_writeable(&key) {
+            return Ok(Err(ApiError::PermissionDenied));
+        }
+        self.context
+            .update_contract_acl(key, authorized_callers)
+            .map(Ok)
+            .map_err(Into::into)
+    }
+
+    /// Checks if the key points at one of the system contracts of the current protocol version.
+    fn is_system_contract(&self, key: &Key) -> bool {
+        match key {
+            Key::URef(uref) => self
+                .context
+                .protocol_data()
+                .system_contracts()
+                .iter()
+                .any(|system_contract| system_contract.addr() == uref.addr()),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the engine configuration restricts the writes of payment code and the
+    /// current context is payment code, rather than a system contract called by it.
+    fn is_payment_write_guarded(&self) -> bool {
+        self.config.payment_write_guard_enabled()
+            && self.context.phase() == Phase::Payment
+            && !self.is_system_contract(&self.context.base_key())
+    }
+
+    /// Fails if the current context is guarded payment code, see
+    /// [`EngineConfig::with_payment_write_guard`].
+    fn validate_payment_write(&self) -> Result<(), Error> {
+        if self.is_payment_write_guarded() {
+            Err(Error::PaymentWriteForbidden)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Fails if the current context is guarded payment code and `key` is not a purse.
+    fn validate_payment_write_to(&mut self, key: &Key) -> Result<(), Error> {
+        if self.is_payment_write_guarded() && !self.is_purse(key)? {
+            Err(Error::PaymentWriteForbidden)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns `true` if `key` is a purse known to the mint.
+    fn is_purse(&mut self, key: &Key) -> Result<bool, Error> {
+        match key {
+            Key::URef(uref) => {
+                let seed = self.get_mint_contract_uref().addr();
+                let purse_addr = uref.addr().into_bytes()?;
+                Ok(self.context.read_ls_with_seed(seed, &purse_addr)?.is_some())
+            }
+            _ => Ok(false),
+        }
+    }
+
     fn get_system_contract(
         &mut self,
         system_contract_index: u32,
@@ -2450,6 +3491,19 @@ where
         self.host_buf.take()
     }
 
+    /// Takes the text printed so far by the executed contract and the contracts it called.
+    pub fn take_stdout(&self) -> Vec<String> {
+        self.stdout.borrow_mut().take()
+    }
+
+    /// Takes the counters of the host function calls made so far by the executed contract and the
+    /// contracts it called, or returns `None` if profiling is disabled.
+    pub fn take_host_function_profile(&self) -> Option<HostFunctionProfile> {
+        self.host_function_profile
+            .as_ref()
+            .map(|profile| profile.replace(HostFunctionProfile::new()))
+    }
+
     /// Checks if a write to host buffer can happen.
     ///
     /// This will check if the host buffer is empty.
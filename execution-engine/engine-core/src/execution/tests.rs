@@ -1,9 +1,15 @@
+use parity_wasm::elements::Module;
+
 use engine_shared::{gas::Gas, transform::Transform};
-use types::{Key, U512};
+use engine_wasm_prep::{
+    denied_host_functions::DeniedHostFunctions, wasm_costs::DEFAULT_MAX_MEMORY_PAGES,
+};
+use types::{Key, ProtocolVersion, U512};
 
-use super::Error;
-use crate::engine_state::{
-    execution_effect::ExecutionEffect, execution_result::ExecutionResult, op::Op,
+use super::{instance_and_memory, Error};
+use crate::{
+    engine_state::{execution_effect::ExecutionEffect, execution_result::ExecutionResult, op::Op},
+    resolvers::{abi_version::AbiVersion, error::ResolverError},
 };
 
 fn on_fail_charge_test_helper<T>(
@@ -15,6 +21,10 @@ fn on_fail_charge_test_helper<T>(
     ExecutionResult::Success {
         effect: Default::default(),
         cost: success_cost,
+        read_stats: None,
+        effects_by_phase: None,
+        stdout: Vec::new(),
+        host_function_profile: None,
     }
 }
 
@@ -55,6 +65,10 @@ fn on_fail_charge_with_action() {
         ExecutionResult::Success {
             effect: Default::default(),
             cost: Gas::default(),
+            read_stats: None,
+            effects_by_phase: None,
+            stdout: Vec::new(),
+            host_function_profile: None,
         }
     };
     match f() {
@@ -67,3 +81,73 @@ fn on_fail_charge_with_action() {
         }
     }
 }
+
+fn module_importing_from(namespace: &str) -> Module {
+    let wat = format!(
+        r#"(module
+             (import "env" "gas" (func (param i32)))
+             (import "{0}" "ret" (func (param i32 i32)))
+             (import "{0}" "memory" (memory 1)))"#,
+        namespace
+    );
+    let wasm = wabt::wat2wasm(wat).expect("should parse wat");
+    parity_wasm::deserialize_buffer(&wasm).expect("should deserialize wasm")
+}
+
+#[test]
+fn should_instantiate_module_targeting_abi_version_1() {
+    for namespace in &["env", "env.v1"] {
+        assert!(
+            instance_and_memory(
+                module_importing_from(namespace),
+                ProtocolVersion::V1_0_0,
+                &DeniedHostFunctions::new(),
+                DEFAULT_MAX_MEMORY_PAGES
+            )
+            .is_ok(),
+            "should instantiate module importing from {}",
+            namespace
+        );
+    }
+}
+
+#[test]
+fn should_not_instantiate_module_targeting_unknown_abi_version() {
+    let module = module_importing_from("env.v2");
+    match instance_and_memory(
+        module,
+        ProtocolVersion::V1_0_0,
+        &DeniedHostFunctions::new(),
+        DEFAULT_MAX_MEMORY_PAGES,
+    ) {
+        Err(Error::ResolverError(ResolverError::UnknownAbiVersion {
+            abi_version,
+            protocol_version,
+        })) => {
+            assert_eq!(abi_version, AbiVersion::new(2));
+            assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
+        }
+        Err(error) => panic!("unexpected error: {:?}", error),
+        Ok(_) => panic!("should not instantiate module"),
+    }
+}
+
+#[test]
+fn should_not_instantiate_module_importing_denied_host_function() {
+    let denied_host_functions = DeniedHostFunctions::new().with_denied("ret");
+    for namespace in &["env", "env.v1"] {
+        match instance_and_memory(
+            module_importing_from(namespace),
+            ProtocolVersion::V1_0_0,
+            &denied_host_functions,
+            DEFAULT_MAX_MEMORY_PAGES,
+        ) {
+            Err(error) => assert!(
+                error.to_string().contains("host function ret is denied"),
+                "unexpected error: {}",
+                error
+            ),
+            Ok(_) => panic!("should not instantiate module importing from {}", namespace),
+        }
+    }
+}
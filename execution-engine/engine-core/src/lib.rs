@@ -1,5 +1,6 @@
 #![feature(never_type)]
 
+mod deploy_hash;
 pub mod engine_state;
 pub mod execution;
 pub mod resolvers;
@@ -15,6 +16,6 @@ pub const DEPLOY_HASH_LENGTH: usize = 32;
 
 pub type Address = [u8; ADDRESS_LENGTH];
 
-pub type DeployHash = [u8; DEPLOY_HASH_LENGTH];
+pub use deploy_hash::DeployHash;
 
 type KnownKeys = BTreeMap<String, Key>;
@@ -0,0 +1,179 @@
+use std::fmt::{self, Display, Formatter};
+
+use parity_wasm::elements::{External, Module};
+
+use super::error::ResolverError;
+
+/// Namespace of the host functions of ABI version 1, used by all modules which don't import from a
+/// versioned namespace.
+pub const LEGACY_NAMESPACE: &str = "env";
+/// Name of the host function charging gas.  It is imported from [`LEGACY_NAMESPACE`] by
+/// `pwasm_utils::inject_gas_counter` regardless of the ABI version the module targets.
+pub const GAS_FUNCTION_NAME: &str = "gas";
+const VERSIONED_NAMESPACE_PREFIX: &str = "env.v";
+
+/// The version of the host function table a Wasm module is linked against.
+///
+/// A module selects its ABI version through the namespace of its imports: `env.v<N>` for version
+/// `N`, or `env` for version 1.  All imports other than the `gas` function injected by the engine
+/// must use the same namespace.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AbiVersion(u32);
+
+impl AbiVersion {
+    pub const V1: AbiVersion = AbiVersion(1);
+
+    pub fn new(version: u32) -> Self {
+        AbiVersion(version)
+    }
+
+    pub fn value(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the versioned namespace of this ABI version, i.e. `env.v<N>`.
+    pub fn namespace(self) -> String {
+        format!("{}{}", VERSIONED_NAMESPACE_PREFIX, self.0)
+    }
+
+    /// Parses the ABI version from the namespace of an import.
+    pub fn from_namespace(namespace: &str) -> Option<Self> {
+        if namespace == LEGACY_NAMESPACE {
+            return Some(AbiVersion::V1);
+        }
+        if !namespace.starts_with(VERSIONED_NAMESPACE_PREFIX) {
+            return None;
+        }
+        let version = namespace[VERSIONED_NAMESPACE_PREFIX.len()..]
+            .parse()
+            .ok()
+            .map(AbiVersion)?;
+        // Only the canonical spelling is accepted, e.g. not `env.v+2` or `env.v02`
+        if version.namespace() != namespace {
+            return None;
+        }
+        Some(version)
+    }
+
+    /// Returns the ABI version targeted by `module`.
+    ///
+    /// A module without imports targets ABI version 1.
+    pub fn of_module(module: &Module) -> Result<Self, ResolverError> {
+        let imports = module
+            .import_section()
+            .map(|section| section.entries())
+            .unwrap_or_default();
+
+        let mut abi_version = None;
+        for entry in imports {
+            let is_gas_function = entry.module() == LEGACY_NAMESPACE
+                && entry.field() == GAS_FUNCTION_NAME
+                && match entry.external() {
+                    External::Function(_) => true,
+                    _ => false,
+                };
+            if is_gas_function {
+                continue;
+            }
+
+            let version = AbiVersion::from_namespace(entry.module())
+                .ok_or_else(|| ResolverError::UnknownImportModule(entry.module().to_string()))?;
+            match abi_version {
+                None => abi_version = Some(version),
+                Some(expected) if expected != version => {
+                    return Err(ResolverError::MixedAbiVersions(expected, version));
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok(abi_version.unwrap_or(AbiVersion::V1))
+    }
+}
+
+impl Display for AbiVersion {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::Module;
+
+    use super::AbiVersion;
+    use crate::resolvers::error::ResolverError;
+
+    fn module(imports: &str) -> Module {
+        let wat = format!("(module {})", imports);
+        let wasm = wabt::wat2wasm(wat).expect("should parse wat");
+        parity_wasm::deserialize_buffer(&wasm).expect("should deserialize wasm")
+    }
+
+    #[test]
+    fn should_parse_namespace() {
+        assert_eq!(AbiVersion::from_namespace("env"), Some(AbiVersion::V1));
+        assert_eq!(AbiVersion::from_namespace("env.v1"), Some(AbiVersion::V1));
+        assert_eq!(
+            AbiVersion::from_namespace("env.v2"),
+            Some(AbiVersion::new(2))
+        );
+        assert_eq!(AbiVersion::from_namespace("env.v"), None);
+        assert_eq!(AbiVersion::from_namespace("env.v+2"), None);
+        assert_eq!(AbiVersion::from_namespace("env.v02"), None);
+        assert_eq!(AbiVersion::from_namespace("env.x2"), None);
+        assert_eq!(AbiVersion::from_namespace("ext"), None);
+        assert_eq!(
+            AbiVersion::from_namespace(&AbiVersion::new(3).namespace()),
+            Some(AbiVersion::new(3))
+        );
+    }
+
+    #[test]
+    fn should_default_to_v1() {
+        assert_eq!(AbiVersion::of_module(&module("")).unwrap(), AbiVersion::V1);
+        let legacy = module(
+            r#"(import "env" "gas" (func (param i32)))
+               (import "env" "ret" (func (param i32 i32)))
+               (import "env" "memory" (memory 1))"#,
+        );
+        assert_eq!(AbiVersion::of_module(&legacy).unwrap(), AbiVersion::V1);
+    }
+
+    #[test]
+    fn should_read_version_from_namespace() {
+        let versioned = module(
+            r#"(import "env" "gas" (func (param i32)))
+               (import "env.v2" "ret" (func (param i32 i32)))
+               (import "env.v2" "memory" (memory 1))"#,
+        );
+        assert_eq!(
+            AbiVersion::of_module(&versioned).unwrap(),
+            AbiVersion::new(2)
+        );
+    }
+
+    #[test]
+    fn should_reject_mixed_versions() {
+        let mixed = module(
+            r#"(import "env.v2" "ret" (func (param i32 i32)))
+               (import "env" "memory" (memory 1))"#,
+        );
+        match AbiVersion::of_module(&mixed) {
+            Err(ResolverError::MixedAbiVersions(first, second)) => {
+                assert_eq!(first, AbiVersion::new(2));
+                assert_eq!(second, AbiVersion::V1);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_unknown_namespace() {
+        let unknown = module(r#"(import "host" "ret" (func (param i32 i32)))"#);
+        match AbiVersion::of_module(&unknown) {
+            Err(ResolverError::UnknownImportModule(namespace)) => assert_eq!(namespace, "host"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
@@ -1,7 +1,18 @@
 use types::ProtocolVersion;
 
+use super::abi_version::AbiVersion;
+
 #[derive(Debug)]
 pub enum ResolverError {
     UnknownProtocolVersion(ProtocolVersion),
     NoImportedMemory,
+    /// The module imports from a namespace which doesn't name an ABI version.
+    UnknownImportModule(String),
+    /// The module imports from the namespaces of two different ABI versions.
+    MixedAbiVersions(AbiVersion, AbiVersion),
+    /// The module targets an ABI version without host functions at the given protocol version.
+    UnknownAbiVersion {
+        abi_version: AbiVersion,
+        protocol_version: ProtocolVersion,
+    },
 }
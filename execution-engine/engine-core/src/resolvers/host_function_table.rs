@@ -0,0 +1,425 @@
+use std::fmt::Write;
+
+use parity_wasm::elements::{External, Module};
+use wasmi::{Signature, ValueType};
+
+use super::{abi_version::AbiVersion, v1_function_index::FunctionIndex};
+
+/// Path of the generated host function documentation, relative to the crate root.
+pub const HOST_FUNCTIONS_DOC: &str = "HOST_FUNCTIONS.md";
+
+/// All ABI versions with a host function table.
+pub const ABI_VERSIONS: &[AbiVersion] = &[AbiVersion::V1];
+
+/// A function exported by the host to Wasm modules.  All its parameters and its result, if any, are
+/// `i32`s.
+#[derive(Debug, Copy, Clone)]
+pub struct HostFunction {
+    name: &'static str,
+    param_count: usize,
+    has_result: bool,
+    index: FunctionIndex,
+}
+
+impl HostFunction {
+    const fn new(
+        name: &'static str,
+        param_count: usize,
+        has_result: bool,
+        index: FunctionIndex,
+    ) -> Self {
+        HostFunction {
+            name,
+            param_count,
+            has_result,
+            index,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn index(&self) -> FunctionIndex {
+        self.index
+    }
+
+    pub fn signature(&self) -> Signature {
+        let params = vec![ValueType::I32; self.param_count];
+        let result = if self.has_result {
+            Some(ValueType::I32)
+        } else {
+            None
+        };
+        Signature::new(params, result)
+    }
+
+    fn signature_doc(&self) -> String {
+        let params = vec!["i32"; self.param_count].join(", ");
+        if self.has_result {
+            format!("({}) -> i32", params)
+        } else {
+            format!("({})", params)
+        }
+    }
+}
+
+const V1_HOST_FUNCTIONS: &[HostFunction] = &[
+    HostFunction::new("read_value", 3, true, FunctionIndex::ReadFuncIndex),
+    HostFunction::new(
+        "read_value_local",
+        3,
+        true,
+        FunctionIndex::ReadLocalFuncIndex,
+    ),
+    HostFunction::new(
+        "load_named_keys",
+        2,
+        true,
+        FunctionIndex::LoadNamedKeysFuncIndex,
+    ),
+    HostFunction::new("write", 4, false, FunctionIndex::WriteFuncIndex),
+    HostFunction::new("write_local", 4, false, FunctionIndex::WriteLocalFuncIndex),
+    HostFunction::new("add", 4, false, FunctionIndex::AddFuncIndex),
+    HostFunction::new("add_local", 4, false, FunctionIndex::AddLocalFuncIndex),
+    HostFunction::new("new_uref", 3, false, FunctionIndex::NewFuncIndex),
+    HostFunction::new("get_arg_size", 2, true, FunctionIndex::GetArgSizeFuncIndex),
+    HostFunction::new("get_arg", 3, true, FunctionIndex::GetArgFuncIndex),
+    HostFunction::new("ret", 2, false, FunctionIndex::RetFuncIndex),
+    HostFunction::new(
+        "call_contract",
+        5,
+        true,
+        FunctionIndex::CallContractFuncIndex,
+    ),
+    HostFunction::new("get_key", 5, true, FunctionIndex::GetKeyFuncIndex),
+    HostFunction::new("has_key", 2, true, FunctionIndex::HasKeyFuncIndex),
+    HostFunction::new("put_key", 4, false, FunctionIndex::PutKeyFuncIndex),
+    HostFunction::new("gas", 1, false, FunctionIndex::GasFuncIndex),
+    HostFunction::new("store_function", 5, false, FunctionIndex::StoreFnIndex),
+    HostFunction::new(
+        "store_function_at_hash",
+        5,
+        false,
+        FunctionIndex::StoreFnAtHashIndex,
+    ),
+    HostFunction::new("is_valid_uref", 2, true, FunctionIndex::IsValidURefFnIndex),
+    HostFunction::new("revert", 1, false, FunctionIndex::RevertFuncIndex),
+    HostFunction::new(
+        "add_associated_key",
+        2,
+        true,
+        FunctionIndex::AddAssociatedKeyFuncIndex,
+    ),
+    HostFunction::new(
+        "remove_associated_key",
+        1,
+        true,
+        FunctionIndex::RemoveAssociatedKeyFuncIndex,
+    ),
+    HostFunction::new(
+        "update_associated_key",
+        2,
+        true,
+        FunctionIndex::UpdateAssociatedKeyFuncIndex,
+    ),
+    HostFunction::new(
+        "set_action_threshold",
+        2,
+        true,
+        FunctionIndex::SetActionThresholdFuncIndex,
+    ),
+    HostFunction::new("remove_key", 2, false, FunctionIndex::RemoveKeyFuncIndex),
+    HostFunction::new("get_caller", 1, false, FunctionIndex::GetCallerIndex),
+    HostFunction::new("get_blocktime", 1, false, FunctionIndex::GetBlocktimeIndex),
+    HostFunction::new("create_purse", 2, true, FunctionIndex::CreatePurseIndex),
+    HostFunction::new(
+        "transfer_to_account",
+        4,
+        true,
+        FunctionIndex::TransferToAccountIndex,
+    ),
+    HostFunction::new(
+        "transfer_from_purse_to_account",
+        6,
+        true,
+        FunctionIndex::TransferFromPurseToAccountIndex,
+    ),
+    HostFunction::new(
+        "transfer_from_purse_to_purse",
+        6,
+        true,
+        FunctionIndex::TransferFromPurseToPurseIndex,
+    ),
+    HostFunction::new("get_balance", 3, true, FunctionIndex::GetBalanceIndex),
+    HostFunction::new("get_phase", 1, false, FunctionIndex::GetPhaseIndex),
+    HostFunction::new(
+        "upgrade_contract_at_uref",
+        4,
+        true,
+        FunctionIndex::UpgradeContractAtURefIndex,
+    ),
+    HostFunction::new(
+        "get_system_contract",
+        3,
+        true,
+        FunctionIndex::GetSystemContractIndex,
+    ),
+    HostFunction::new("get_main_purse", 1, false, FunctionIndex::GetMainPurseIndex),
+    HostFunction::new(
+        "read_host_buffer",
+        3,
+        true,
+        FunctionIndex::ReadHostBufferIndex,
+    ),
+    HostFunction::new(
+        "get_contract_metadata",
+        3,
+        true,
+        FunctionIndex::GetContractMetadataIndex,
+    ),
+    HostFunction::new("new_contract", 6, false, FunctionIndex::NewContractIndex),
+    HostFunction::new(
+        "add_contract_version",
+        7,
+        false,
+        FunctionIndex::AddContractVersionIndex,
+    ),
+    HostFunction::new(
+        "disable_contract_version",
+        3,
+        false,
+        FunctionIndex::DisableContractVersionIndex,
+    ),
+    HostFunction::new(
+        "call_versioned_contract",
+        7,
+        true,
+        FunctionIndex::CallVersionedContractIndex,
+    ),
+    HostFunction::new("get_call_stack", 1, true, FunctionIndex::GetCallStackIndex),
+    HostFunction::new("random_bytes", 1, false, FunctionIndex::RandomBytesIndex),
+    HostFunction::new("blake2b", 3, false, FunctionIndex::Blake2bIndex),
+    HostFunction::new("sha256", 3, false, FunctionIndex::Sha256Index),
+    HostFunction::new(
+        "get_named_key_of",
+        7,
+        true,
+        FunctionIndex::GetNamedKeyOfIndex,
+    ),
+    HostFunction::new(
+        "create_named_purse",
+        4,
+        true,
+        FunctionIndex::CreateNamedPurseIndex,
+    ),
+    HostFunction::new(
+        "get_named_purse",
+        4,
+        true,
+        FunctionIndex::GetNamedPurseIndex,
+    ),
+    HostFunction::new("log", 2, false, FunctionIndex::LogIndex),
+    HostFunction::new(
+        "transfer_uref_ownership",
+        6,
+        true,
+        FunctionIndex::TransferURefOwnershipIndex,
+    ),
+    HostFunction::new(
+        "update_contract_acl",
+        4,
+        true,
+        FunctionIndex::UpdateContractAclIndex,
+    ),
+    HostFunction::new("print", 2, true, FunctionIndex::PrintIndex),
+    HostFunction::new(
+        "list_authorization_keys",
+        1,
+        true,
+        FunctionIndex::ListAuthorizationKeysIndex,
+    ),
+    HostFunction::new("namespace", 3, false, FunctionIndex::NamespaceIndex),
+    HostFunction::new(
+        "read_value_namespaced",
+        4,
+        true,
+        FunctionIndex::ReadNamespacedIndex,
+    ),
+    HostFunction::new(
+        "write_namespaced",
+        5,
+        false,
+        FunctionIndex::WriteNamespacedIndex,
+    ),
+    HostFunction::new(
+        "add_namespaced",
+        5,
+        false,
+        FunctionIndex::AddNamespacedIndex,
+    ),
+    HostFunction::new("get_era_id", 1, false, FunctionIndex::GetEraIdIndex),
+    HostFunction::new(
+        "get_block_height",
+        1,
+        false,
+        FunctionIndex::GetBlockHeightIndex,
+    ),
+    HostFunction::new(
+        "update_associated_keys",
+        2,
+        true,
+        FunctionIndex::UpdateAssociatedKeysIndex,
+    ),
+    HostFunction::new(
+        "list_associated_keys",
+        1,
+        true,
+        FunctionIndex::ListAssociatedKeysIndex,
+    ),
+];
+
+/// Returns the host functions of the given ABI version, or `None` if the version is unknown.
+pub fn host_functions(abi_version: AbiVersion) -> Option<&'static [HostFunction]> {
+    if abi_version == AbiVersion::V1 {
+        Some(V1_HOST_FUNCTIONS)
+    } else {
+        None
+    }
+}
+
+/// Returns the name the host function with the given index is imported by, in any ABI version.
+pub fn host_function_name(index: FunctionIndex) -> Option<&'static str> {
+    ABI_VERSIONS
+        .iter()
+        .filter_map(|abi_version| host_functions(*abi_version))
+        .flat_map(|functions| functions.iter())
+        .find(|function| function.index() == index)
+        .map(HostFunction::name)
+}
+
+/// Returns the index of the host function imported as `name`, in any ABI version.
+pub fn host_function_index(name: &str) -> Option<FunctionIndex> {
+    ABI_VERSIONS
+        .iter()
+        .filter_map(|abi_version| host_functions(*abi_version))
+        .flat_map(|functions| functions.iter())
+        .find(|function| function.name() == name)
+        .map(HostFunction::index)
+}
+
+/// Returns the names of the functions `module` imports, i.e. of the host functions it is linked
+/// against, whatever ABI version it targets.
+pub fn imported_host_functions(module: &Module) -> impl Iterator<Item = &str> {
+    module
+        .import_section()
+        .map(|section| section.entries())
+        .unwrap_or_default()
+        .iter()
+        .filter(|entry| match entry.external() {
+            External::Function(_) => true,
+            _ => false,
+        })
+        .map(|entry| entry.field())
+}
+
+/// Renders the host function tables of all ABI versions as markdown, as committed in
+/// [`HOST_FUNCTIONS_DOC`].
+pub fn render_markdown() -> String {
+    let mut doc = String::new();
+    // Writing to a `String` can't fail
+    writeln!(doc, "# Host functions").unwrap();
+    writeln!(doc).unwrap();
+    writeln!(
+        doc,
+        "<!-- Generated from `engine-core/src/resolvers/host_function_table.rs`; do not edit. -->"
+    )
+    .unwrap();
+    writeln!(doc).unwrap();
+    writeln!(
+        doc,
+        "A Wasm module selects the ABI version of the host functions it links against through the \
+         namespace of its imports: `env.v<N>` for version `N`, or `env` for version 1.  All \
+         imports must use the same namespace, except for the `gas` function injected by the \
+         engine, which is always imported from `env`.  Modules importing from any other namespace, \
+         or targeting an ABI version unknown to the current protocol version, are rejected."
+    )
+    .unwrap();
+
+    for abi_version in ABI_VERSIONS {
+        let functions = host_functions(*abi_version).expect("should have host functions");
+        writeln!(doc).unwrap();
+        writeln!(doc, "## ABI version {}", abi_version).unwrap();
+        writeln!(doc).unwrap();
+        writeln!(doc, "| Index | Name | Signature |").unwrap();
+        writeln!(doc, "|------:|------|-----------|").unwrap();
+        for function in functions {
+            let index: usize = function.index().into();
+            writeln!(
+                doc,
+                "| {} | `{}` | `{}` |",
+                index,
+                function.name(),
+                function.signature_doc()
+            )
+            .unwrap();
+        }
+    }
+    doc
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, convert::TryFrom, env, fs, path::Path};
+
+    use super::*;
+
+    const UPDATE_DOC_VAR: &str = "UPDATE_HOST_FUNCTIONS_DOC";
+
+    #[test]
+    fn should_export_every_function_index_once() {
+        for abi_version in ABI_VERSIONS {
+            let functions = host_functions(*abi_version).expect("should have host functions");
+            let names: BTreeSet<&str> = functions.iter().map(HostFunction::name).collect();
+            let indices: BTreeSet<usize> = functions
+                .iter()
+                .map(|function| function.index().into())
+                .collect();
+            assert_eq!(names.len(), functions.len(), "duplicate name");
+            assert_eq!(indices.len(), functions.len(), "duplicate index");
+            assert!(FunctionIndex::try_from(functions.len()).is_err());
+        }
+    }
+
+    #[test]
+    fn should_look_up_host_functions_by_name_and_index() {
+        for function in V1_HOST_FUNCTIONS {
+            assert_eq!(host_function_index(function.name()), Some(function.index()));
+            assert_eq!(host_function_name(function.index()), Some(function.name()));
+        }
+        assert_eq!(host_function_index("unknown"), None);
+    }
+
+    #[test]
+    fn should_not_have_unknown_abi_versions() {
+        assert!(host_functions(AbiVersion::new(0)).is_none());
+        assert!(host_functions(AbiVersion::new(2)).is_none());
+    }
+
+    #[test]
+    fn host_functions_doc_should_be_up_to_date() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(HOST_FUNCTIONS_DOC);
+        let expected = render_markdown();
+        if env::var_os(UPDATE_DOC_VAR).is_some() {
+            fs::write(&path, expected).expect("should write host functions doc");
+            return;
+        }
+        let actual = fs::read_to_string(&path).expect("should read host functions doc");
+        assert!(
+            actual == expected,
+            "{} is out of date; regenerate it by running this test with {}=1",
+            path.display(),
+            UPDATE_DOC_VAR
+        );
+    }
+}
@@ -1,34 +1,111 @@
+pub mod abi_version;
 pub mod error;
+pub mod host_function_table;
 pub mod memory_resolver;
 pub mod v1_function_index;
 mod v1_resolver;
 
 use wasmi::ModuleImportResolver;
 
+use engine_wasm_prep::denied_host_functions::DeniedHostFunctions;
 use types::ProtocolVersion;
 
-use self::error::ResolverError;
+use self::{abi_version::AbiVersion, error::ResolverError};
 use crate::resolvers::memory_resolver::MemoryResolver;
 
-/// Creates a module resolver for given protocol version.
+/// Creates a module resolver for given protocol version and ABI version.
 ///
 /// * `protocol_version` Version of the protocol. Can't be lower than 1.
+/// * `abi_version` Version of the host functions the module targets, see [`AbiVersion::of_module`].
+/// * `denied_host_functions` Host functions the module may not import at `protocol_version`.
+/// * `max_memory` Max number of memory pages the module may import.
 pub fn create_module_resolver(
     protocol_version: ProtocolVersion,
-) -> Result<impl ModuleImportResolver + MemoryResolver, ResolverError> {
+    abi_version: AbiVersion,
+    denied_host_functions: &DeniedHostFunctions,
+    max_memory: u32,
+) -> Result<impl ModuleImportResolver + MemoryResolver + '_, ResolverError> {
     // TODO: revisit how protocol_version check here is meant to combine with upgrade
     if protocol_version >= ProtocolVersion::V1_0_0 {
-        return Ok(v1_resolver::RuntimeModuleImportResolver::default());
+        let host_functions = host_function_table::host_functions(abi_version).ok_or(
+            ResolverError::UnknownAbiVersion {
+                abi_version,
+                protocol_version,
+            },
+        )?;
+        return Ok(v1_resolver::RuntimeModuleImportResolver::new(
+            host_functions,
+            denied_host_functions,
+            protocol_version,
+            max_memory,
+        ));
     }
     Err(ResolverError::UnknownProtocolVersion(protocol_version))
 }
 
+#[cfg(test)]
+const MAX_MEMORY: u32 = 64;
+
 #[test]
 fn resolve_invalid_module() {
-    assert!(create_module_resolver(ProtocolVersion::default()).is_err());
+    assert!(create_module_resolver(
+        ProtocolVersion::default(),
+        AbiVersion::V1,
+        &DeniedHostFunctions::new(),
+        MAX_MEMORY
+    )
+    .is_err());
 }
 
 #[test]
 fn protocol_version_1_always_resolves() {
-    assert!(create_module_resolver(ProtocolVersion::V1_0_0).is_ok());
+    assert!(create_module_resolver(
+        ProtocolVersion::V1_0_0,
+        AbiVersion::V1,
+        &DeniedHostFunctions::new(),
+        MAX_MEMORY
+    )
+    .is_ok());
+}
+
+#[test]
+fn should_not_resolve_denied_host_function() {
+    let denied_host_functions = DeniedHostFunctions::new().with_denied("get_blocktime");
+    let resolver = create_module_resolver(
+        ProtocolVersion::V1_0_0,
+        AbiVersion::V1,
+        &denied_host_functions,
+        MAX_MEMORY,
+    )
+    .expect("should create resolver");
+    let signature = wasmi::Signature::new(&[wasmi::ValueType::I32][..], None);
+    let error = resolver
+        .resolve_func("get_blocktime", &signature)
+        .expect_err("should not resolve denied host function");
+    assert!(
+        error.to_string().contains("get_blocktime"),
+        "error should name the denied host function: {}",
+        error
+    );
+    assert!(resolver.resolve_func("get_era_id", &signature).is_ok());
+}
+
+#[test]
+fn should_not_resolve_unknown_abi_version() {
+    match create_module_resolver(
+        ProtocolVersion::V1_0_0,
+        AbiVersion::new(2),
+        &DeniedHostFunctions::new(),
+        MAX_MEMORY,
+    ) {
+        Err(ResolverError::UnknownAbiVersion {
+            abi_version,
+            protocol_version,
+        }) => {
+            assert_eq!(abi_version, AbiVersion::new(2));
+            assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
+        }
+        Err(error) => panic!("unexpected error: {:?}", error),
+        Ok(_) => panic!("should not resolve unknown ABI version"),
+    }
 }
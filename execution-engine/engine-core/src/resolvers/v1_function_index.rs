@@ -3,7 +3,7 @@ use std::convert::TryFrom;
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::{FromPrimitive, ToPrimitive};
 
-#[derive(Debug, PartialEq, FromPrimitive, ToPrimitive)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, ToPrimitive)]
 #[repr(usize)]
 pub enum FunctionIndex {
     WriteFuncIndex,
@@ -43,6 +43,31 @@ pub enum FunctionIndex {
     GetMainPurseIndex,
     GetArgSizeFuncIndex,
     ReadHostBufferIndex,
+    GetContractMetadataIndex,
+    NewContractIndex,
+    AddContractVersionIndex,
+    DisableContractVersionIndex,
+    CallVersionedContractIndex,
+    GetCallStackIndex,
+    RandomBytesIndex,
+    Blake2bIndex,
+    Sha256Index,
+    GetNamedKeyOfIndex,
+    CreateNamedPurseIndex,
+    GetNamedPurseIndex,
+    LogIndex,
+    TransferURefOwnershipIndex,
+    UpdateContractAclIndex,
+    PrintIndex,
+    ListAuthorizationKeysIndex,
+    NamespaceIndex,
+    ReadNamespacedIndex,
+    WriteNamespacedIndex,
+    AddNamespacedIndex,
+    GetEraIdIndex,
+    GetBlockHeightIndex,
+    UpdateAssociatedKeysIndex,
+    ListAssociatedKeysIndex,
 }
 
 impl Into<usize> for FunctionIndex {
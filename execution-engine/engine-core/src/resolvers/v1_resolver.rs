@@ -2,28 +2,44 @@ use std::cell::RefCell;
 
 use wasmi::{
     memory_units::Pages, Error as InterpreterError, FuncInstance, FuncRef, MemoryDescriptor,
-    MemoryInstance, MemoryRef, ModuleImportResolver, Signature, ValueType,
+    MemoryInstance, MemoryRef, ModuleImportResolver, Signature,
 };
 
+use engine_wasm_prep::denied_host_functions::DeniedHostFunctions;
+use types::ProtocolVersion;
+
 use super::{
-    error::ResolverError, memory_resolver::MemoryResolver, v1_function_index::FunctionIndex,
+    error::ResolverError, host_function_table::HostFunction, memory_resolver::MemoryResolver,
 };
 
-pub struct RuntimeModuleImportResolver {
+pub struct RuntimeModuleImportResolver<'a> {
     memory: RefCell<Option<MemoryRef>>,
     max_memory: u32,
+    host_functions: &'static [HostFunction],
+    denied_host_functions: &'a DeniedHostFunctions,
+    protocol_version: ProtocolVersion,
 }
 
-impl Default for RuntimeModuleImportResolver {
-    fn default() -> Self {
+impl<'a> RuntimeModuleImportResolver<'a> {
+    /// Creates a resolver exporting the given host functions, except the ones denied at
+    /// `protocol_version`, and limiting the imported memory to `max_memory` pages.
+    pub fn new(
+        host_functions: &'static [HostFunction],
+        denied_host_functions: &'a DeniedHostFunctions,
+        protocol_version: ProtocolVersion,
+        max_memory: u32,
+    ) -> Self {
         RuntimeModuleImportResolver {
             memory: RefCell::new(None),
-            max_memory: 64,
+            max_memory,
+            host_functions,
+            denied_host_functions,
+            protocol_version,
         }
     }
 }
 
-impl MemoryResolver for RuntimeModuleImportResolver {
+impl<'a> MemoryResolver for RuntimeModuleImportResolver<'a> {
     fn memory_ref(&self) -> Result<MemoryRef, ResolverError> {
         self.memory
             .borrow()
@@ -33,169 +49,32 @@ impl MemoryResolver for RuntimeModuleImportResolver {
     }
 }
 
-impl ModuleImportResolver for RuntimeModuleImportResolver {
+impl<'a> ModuleImportResolver for RuntimeModuleImportResolver<'a> {
     fn resolve_func(
         &self,
         field_name: &str,
         _signature: &Signature,
     ) -> Result<FuncRef, InterpreterError> {
-        let func_ref = match field_name {
-            "read_value" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::ReadFuncIndex.into(),
-            ),
-            "read_value_local" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::ReadLocalFuncIndex.into(),
-            ),
-            "load_named_keys" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::LoadNamedKeysFuncIndex.into(),
-            ),
-            "write" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::WriteFuncIndex.into(),
-            ),
-            "write_local" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::WriteLocalFuncIndex.into(),
-            ),
-            "add" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::AddFuncIndex.into(),
-            ),
-            "add_local" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::AddLocalFuncIndex.into(),
-            ),
-            "new_uref" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], None),
-                FunctionIndex::NewFuncIndex.into(),
-            ),
-            "get_arg_size" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::GetArgSizeFuncIndex.into(),
-            ),
-            "get_arg" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::GetArgFuncIndex.into(),
-            ),
-            "ret" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], None),
-                FunctionIndex::RetFuncIndex.into(),
-            ),
-            "call_contract" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
-                FunctionIndex::CallContractFuncIndex.into(),
-            ),
-            "get_key" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
-                FunctionIndex::GetKeyFuncIndex.into(),
-            ),
-            "has_key" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::HasKeyFuncIndex.into(),
-            ),
-            "put_key" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::PutKeyFuncIndex.into(),
-            ),
-            "gas" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GasFuncIndex.into(),
-            ),
-            "store_function" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 5][..], None),
-                FunctionIndex::StoreFnIndex.into(),
-            ),
-            "store_function_at_hash" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 5][..], None),
-                FunctionIndex::StoreFnAtHashIndex.into(),
-            ),
-            "is_valid_uref" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::IsValidURefFnIndex.into(),
-            ),
-            "revert" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::RevertFuncIndex.into(),
-            ),
-            "add_associated_key" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::AddAssociatedKeyFuncIndex.into(),
-            ),
-            "remove_associated_key" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 1][..], Some(ValueType::I32)),
-                FunctionIndex::RemoveAssociatedKeyFuncIndex.into(),
-            ),
-            "update_associated_key" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::UpdateAssociatedKeyFuncIndex.into(),
-            ),
-            "set_action_threshold" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::SetActionThresholdFuncIndex.into(),
-            ),
-            "remove_key" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], None),
-                FunctionIndex::RemoveKeyFuncIndex.into(),
-            ),
-            "get_caller" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GetCallerIndex.into(),
-            ),
-            "get_blocktime" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GetBlocktimeIndex.into(),
-            ),
-            "create_purse" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::CreatePurseIndex.into(),
-            ),
-            "transfer_to_account" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::TransferToAccountIndex.into(),
-            ),
-            "transfer_from_purse_to_account" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
-                FunctionIndex::TransferFromPurseToAccountIndex.into(),
-            ),
-            "transfer_from_purse_to_purse" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
-                FunctionIndex::TransferFromPurseToPurseIndex.into(),
-            ),
-            "get_balance" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::GetBalanceIndex.into(),
-            ),
-            "get_phase" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GetPhaseIndex.into(),
-            ),
-            "upgrade_contract_at_uref" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::UpgradeContractAtURefIndex.into(),
-            ),
-            "get_system_contract" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::GetSystemContractIndex.into(),
-            ),
-            "get_main_purse" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GetMainPurseIndex.into(),
-            ),
-            "read_host_buffer" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::ReadHostBufferIndex.into(),
-            ),
-            _ => {
-                return Err(InterpreterError::Function(format!(
+        if self.denied_host_functions.is_denied(field_name) {
+            return Err(InterpreterError::Instantiation(format!(
+                "host function {} is denied at protocol version {}",
+                field_name, self.protocol_version
+            )));
+        }
+        let host_function = self
+            .host_functions
+            .iter()
+            .find(|host_function| host_function.name() == field_name)
+            .ok_or_else(|| {
+                InterpreterError::Function(format!(
                     "host module doesn't export function with name {}",
                     field_name
-                )));
-            }
-        };
-        Ok(func_ref)
+                ))
+            })?;
+        Ok(FuncInstance::alloc_host(
+            host_function.signature(),
+            host_function.index().into(),
+        ))
     }
 
     fn resolve_memory(
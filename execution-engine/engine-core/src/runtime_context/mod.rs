@@ -12,21 +12,21 @@ use blake2::{
 };
 
 use engine_shared::{
-    account::Account, contract::Contract, gas::Gas, newtypes::CorrelationId,
-    stored_value::StoredValue,
+    account::Account, contract::Contract, contract_package::ContractPackage, gas::Gas,
+    newtypes::CorrelationId, stored_value::StoredValue,
 };
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
 use types::{
     account::{
-        ActionType, AddKeyFailure, PublicKey, PurseId, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        ActionType, AddKeyFailure, KeyUpdate, PublicKey, PurseId, RemoveKeyFailure,
+        SetThresholdFailure, UpdateKeyFailure, UpdateKeysFailure, Weight,
     },
-    bytesrepr::{self, ToBytes},
-    AccessRights, BlockTime, CLType, CLValue, Key, Phase, ProtocolVersion, URef, LOCAL_SEED_LENGTH,
+    bytesrepr, AccessRights, BlockTime, CLType, CLValue, CallStackElement, Key, Phase,
+    ProtocolVersion, URef, LOCAL_SEED_LENGTH, RANDOM_BYTES_COUNT,
 };
 
 use crate::{
-    engine_state::{execution_effect::ExecutionEffect, SYSTEM_ACCOUNT_ADDR},
+    engine_state::{block_info::BlockInfo, execution_effect::ExecutionEffect, SYSTEM_ACCOUNT_ADDR},
     execution::{AddressGenerator, Error},
     tracking_copy::{AddResult, TrackingCopy},
     Address,
@@ -49,6 +49,27 @@ pub(crate) fn attenuate_uref_for_account(account: &Account, uref: URef) -> URef
     }
 }
 
+/// Returns the seed of the local state of the account or contract under `base_key`.
+pub(crate) fn local_seed(base_key: Key) -> [u8; LOCAL_SEED_LENGTH] {
+    match base_key {
+        Key::Account(bytes) => bytes,
+        Key::Hash(bytes) => bytes,
+        Key::URef(uref) => uref.addr(),
+        Key::Local(hash) => hash,
+    }
+}
+
+/// Returns the seed of the namespace of local state called `name`, derived from `base`: either a
+/// contract package hash or the seed of a local state.
+pub(crate) fn namespace_seed(base: [u8; LOCAL_SEED_LENGTH], name: &str) -> [u8; LOCAL_SEED_LENGTH] {
+    let mut hasher = VarBlake2b::new(LOCAL_SEED_LENGTH).unwrap();
+    hasher.input(&base);
+    hasher.input(name.as_bytes());
+    let mut seed = [0; LOCAL_SEED_LENGTH];
+    hasher.variable_result(|hash| seed.clone_from_slice(hash));
+    seed
+}
+
 /// Holds information specific to the deployed contract.
 pub struct RuntimeContext<'a, R> {
     state: Rc<RefCell<TrackingCopy<R>>>,
@@ -63,7 +84,9 @@ pub struct RuntimeContext<'a, R> {
     // Key pointing to the entity we are currently running
     //(could point at an account or contract in the global state)
     base_key: Key,
-    blocktime: BlockTime,
+    // Chain of callers which led to the current context, ending with the current context itself
+    call_stack: Vec<CallStackElement>,
+    block_info: BlockInfo,
     deploy_hash: [u8; 32],
     gas_limit: Gas,
     gas_counter: Gas,
@@ -73,6 +96,10 @@ pub struct RuntimeContext<'a, R> {
     correlation_id: CorrelationId,
     phase: Phase,
     protocol_data: ProtocolData,
+    // Hash of the contract package the current contract was called through, if any
+    package_hash: Option<[u8; 32]>,
+    // Seeds of the namespaces of local state obtained by the current context
+    namespaces: BTreeSet<[u8; LOCAL_SEED_LENGTH]>,
 }
 
 impl<'a, R> RuntimeContext<'a, R>
@@ -89,7 +116,8 @@ where
         authorization_keys: BTreeSet<PublicKey>,
         account: &'a Account,
         base_key: Key,
-        blocktime: BlockTime,
+        call_stack: Vec<CallStackElement>,
+        block_info: BlockInfo,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
         gas_counter: Gas,
@@ -107,9 +135,10 @@ where
             args,
             account,
             authorization_keys,
-            blocktime,
+            block_info,
             deploy_hash,
             base_key,
+            call_stack,
             gas_limit,
             gas_counter,
             fn_store_id,
@@ -118,6 +147,8 @@ where
             correlation_id,
             phase,
             protocol_data,
+            package_hash: None,
+            namespaces: BTreeSet::new(),
         }
     }
 
@@ -206,14 +237,36 @@ where
         self.account.pub_key().into()
     }
 
+    pub fn call_stack(&self) -> &[CallStackElement] {
+        &self.call_stack
+    }
+
+    pub fn get_block_info(&self) -> BlockInfo {
+        self.block_info
+    }
+
     pub fn get_blocktime(&self) -> BlockTime {
-        self.blocktime
+        self.block_info.blocktime()
+    }
+
+    pub fn get_era_id(&self) -> u64 {
+        self.block_info.era_id()
+    }
+
+    pub fn get_block_height(&self) -> u64 {
+        self.block_info.block_height()
     }
 
     pub fn get_deployhash(&self) -> [u8; 32] {
         self.deploy_hash
     }
 
+    /// Returns bytes drawn from the deploy-seeded random stream of the address generator, which
+    /// is separate from the stream used for new `URef` addresses.
+    pub fn random_bytes(&mut self) -> [u8; RANDOM_BYTES_COUNT] {
+        self.address_generator.borrow_mut().create_random_bytes()
+    }
+
     pub fn access_rights_extend(&mut self, access_rights: HashMap<Address, HashSet<AccessRights>>) {
         self.access_rights.extend(access_rights);
     }
@@ -255,11 +308,36 @@ where
     }
 
     pub fn seed(&self) -> [u8; LOCAL_SEED_LENGTH] {
-        match self.base_key {
-            Key::Account(bytes) => bytes,
-            Key::Hash(bytes) => bytes,
-            Key::URef(uref) => uref.addr(),
-            Key::Local(hash) => hash,
+        local_seed(self.base_key)
+    }
+
+    /// Records the hash of the contract package through which the current contract was called.
+    pub fn set_package_hash(&mut self, package_hash: [u8; 32]) {
+        self.package_hash = Some(package_hash);
+    }
+
+    pub fn package_hash(&self) -> Option<[u8; 32]> {
+        self.package_hash
+    }
+
+    /// Returns the seed of the namespace of local state called `name`, and allows the current
+    /// context to use it.
+    ///
+    /// The seed is derived from the contract package hash when the current contract was called
+    /// through its package, so that every version of the package sees the same namespace.
+    /// Otherwise it is derived from the base key, like the seed of the default local state.
+    pub fn namespace(&mut self, name: &str) -> [u8; LOCAL_SEED_LENGTH] {
+        let base = self.package_hash.unwrap_or_else(|| self.seed());
+        let seed = namespace_seed(base, name);
+        self.namespaces.insert(seed);
+        seed
+    }
+
+    fn validate_namespace(&self, seed: &[u8; LOCAL_SEED_LENGTH]) -> Result<(), Error> {
+        if self.namespaces.contains(seed) {
+            Ok(())
+        } else {
+            Err(Error::UnknownNamespace(*seed))
         }
     }
 
@@ -276,23 +354,15 @@ where
     }
 
     /// Generates new function address.
-    /// Function address is deterministic. It is a hash of public key, nonce and
-    /// `fn_store_id`, which is a counter that is being incremented after
-    /// every function generation. If function address was based only on
-    /// account's public key and deploy's nonce, then all function addresses
-    /// generated within one deploy would have been the same.
+    ///
+    /// Function address is deterministic, see [`types::contract_address`].  It is derived from the
+    /// deploy hash and `fn_store_id`, which is a counter that is being incremented after every
+    /// function generation.  If function address was based only on the deploy hash, then all
+    /// function addresses generated within one deploy would have been the same.
     pub fn new_function_address(&mut self) -> Result<[u8; 32], Error> {
-        let mut pre_hash_bytes = Vec::with_capacity(36); //32 bytes for deploy hash + 4 bytes ID
-        pre_hash_bytes.extend_from_slice(&self.deploy_hash);
-        pre_hash_bytes.append(&mut self.fn_store_id().into_bytes()?);
-
+        let address = types::contract_address(self.deploy_hash, self.fn_store_id());
         self.inc_fn_store_id();
-
-        let mut hasher = VarBlake2b::new(32).unwrap();
-        hasher.input(&pre_hash_bytes);
-        let mut hash_bytes = [0; 32];
-        hasher.variable_result(|hash| hash_bytes.clone_from_slice(hash));
-        Ok(hash_bytes)
+        Ok(address)
     }
 
     pub fn new_uref(&mut self, value: StoredValue) -> Result<Key, Error> {
@@ -343,6 +413,43 @@ where
         }
     }
 
+    /// Reads the value under `key_bytes` in the namespace with the given seed.
+    pub fn read_ns(
+        &mut self,
+        seed: [u8; LOCAL_SEED_LENGTH],
+        key_bytes: &[u8],
+    ) -> Result<Option<CLValue>, Error> {
+        self.validate_namespace(&seed)?;
+        self.read_ls_with_seed(seed, key_bytes)
+    }
+
+    /// Writes `cl_value` under `key_bytes` in the namespace with the given seed.
+    pub fn write_ns(
+        &mut self,
+        seed: [u8; LOCAL_SEED_LENGTH],
+        key_bytes: &[u8],
+        cl_value: CLValue,
+    ) -> Result<(), Error> {
+        self.validate_namespace(&seed)?;
+        let key = Key::local(seed, key_bytes);
+        self.state
+            .borrow_mut()
+            .write(key, StoredValue::CLValue(cl_value));
+        Ok(())
+    }
+
+    /// Adds `cl_value` to the value under `key_bytes` in the namespace with the given seed.
+    pub fn add_ns(
+        &mut self,
+        seed: [u8; LOCAL_SEED_LENGTH],
+        key_bytes: &[u8],
+        cl_value: CLValue,
+    ) -> Result<(), Error> {
+        self.validate_namespace(&seed)?;
+        let key = Key::local(seed, key_bytes);
+        self.add_unsafe(key, StoredValue::CLValue(cl_value))
+    }
+
     pub fn write_ls(&mut self, key_bytes: &[u8], cl_value: CLValue) -> Result<(), Error> {
         let seed = self.seed();
         let key = Key::local(seed, key_bytes);
@@ -435,10 +542,35 @@ where
 
     pub fn store_function_at_hash(&mut self, contract: StoredValue) -> Result<[u8; 32], Error> {
         let new_hash = self.new_function_address()?;
+        self.store_function_at(new_hash, contract)?;
+        Ok(new_hash)
+    }
+
+    /// Stores `contract` under a hash previously obtained from `new_function_address`.
+    pub fn store_function_at(
+        &mut self,
+        hash: [u8; 32],
+        contract: StoredValue,
+    ) -> Result<(), Error> {
         self.validate_value(&contract)?;
-        let hash_key = Key::Hash(new_hash);
+        let hash_key = Key::Hash(hash);
         self.state.borrow_mut().write(hash_key, contract);
-        Ok(new_hash)
+        Ok(())
+    }
+
+    /// Overwrites the contract package stored under `package_hash`.  The caller is responsible for
+    /// checking that the package's access key was presented.
+    pub fn update_contract_package(
+        &mut self,
+        package_hash: [u8; 32],
+        contract_package: ContractPackage,
+    ) -> Result<(), Error> {
+        let contract_package = StoredValue::ContractPackage(contract_package);
+        self.validate_value(&contract_package)?;
+        self.state
+            .borrow_mut()
+            .write(Key::Hash(package_hash), contract_package);
+        Ok(())
     }
 
     pub fn insert_key(&mut self, name: String, key: Key) {
@@ -511,6 +643,9 @@ where
                 .named_keys()
                 .values()
                 .try_for_each(|key| self.validate_key(key)),
+            StoredValue::ContractPackage(contract_package) => {
+                self.validate_uref(&contract_package.access_key())
+            }
         }
     }
 
@@ -771,6 +906,51 @@ where
         Ok(())
     }
 
+    pub fn update_associated_keys(&mut self, updates: &[KeyUpdate]) -> Result<(), Error> {
+        // Check permission to modify associated keys
+        if !self.is_valid_context() {
+            // Exit early with error to avoid mutations
+            return Err(UpdateKeysFailure::PermissionDenied.into());
+        }
+
+        if !self
+            .account()
+            .can_manage_keys_with(&self.authorization_keys)
+        {
+            // Exit early if authorization keys weight doesn't exceed required
+            // key management threshold
+            return Err(UpdateKeysFailure::PermissionDenied.into());
+        }
+
+        // Converts an account's public key into a URef
+        let key = Key::Account(self.account().pub_key());
+
+        // Take an account out of the global state
+        let mut account: Account = self.read_gs_typed(&key)?;
+
+        // Exit early in case of error without updating global state
+        account
+            .update_associated_keys(updates)
+            .map_err(Error::from)?;
+
+        let account_value = self.account_to_validated_value(account)?;
+
+        self.state.borrow_mut().write(key, account_value);
+
+        Ok(())
+    }
+
+    /// Returns the associated keys of the account as they currently are in global state, i.e.
+    /// including any changes made earlier in this deploy.
+    pub fn associated_keys(&mut self) -> Result<BTreeMap<PublicKey, Weight>, Error> {
+        let key = Key::Account(self.account().pub_key());
+        let account: Account = self.read_gs_typed(&key)?;
+        Ok(account
+            .get_associated_keys()
+            .map(|(public_key, weight)| (*public_key, *weight))
+            .collect())
+    }
+
     pub fn set_action_threshold(
         &mut self,
         action_type: ActionType,
@@ -814,9 +994,11 @@ where
         key: Key,
         bytes: Vec<u8>,
         named_keys: BTreeMap<String, Key>,
+        authorized_callers: Option<Vec<PublicKey>>,
     ) -> Result<(), Error> {
         let protocol_version = self.protocol_version();
-        let contract = Contract::new(bytes, named_keys, protocol_version);
+        let contract = Contract::new(bytes, named_keys, protocol_version)
+            .with_authorized_callers(authorized_callers);
         let contract = StoredValue::Contract(contract);
 
         self.validate_writeable(&key)?;
@@ -826,8 +1008,24 @@ where
         Ok(())
     }
 
-    pub fn protocol_data(&self) -> ProtocolData {
-        self.protocol_data
+    /// Replaces the accounts allowed to call the contract stored under `key`, which has to be
+    /// writeable.  `None` allows anyone to call the contract.
+    pub fn update_contract_acl(
+        &mut self,
+        key: Key,
+        authorized_callers: Option<Vec<PublicKey>>,
+    ) -> Result<(), Error> {
+        self.validate_writeable(&key)?;
+        let mut contract: Contract = self.read_gs_typed(&key)?;
+        contract.set_authorized_callers(authorized_callers);
+        self.state
+            .borrow_mut()
+            .write(key, StoredValue::Contract(contract));
+        Ok(())
+    }
+
+    pub fn protocol_data(&self) -> &ProtocolData {
+        &self.protocol_data
     }
 
     /// Attenuates URef for a given account.
@@ -857,4 +1055,38 @@ where
         }
         Ok(self.account().purse_id())
     }
+
+    /// Gets the purse of the account stored under the given name, and grants access to it.
+    pub fn get_named_purse(&mut self, name: &str) -> Result<Option<PurseId>, Error> {
+        if !self.is_valid_context() {
+            return Err(Error::InvalidContext);
+        }
+        let key = Key::Account(self.account().pub_key());
+        let account: Account = self.read_gs_typed(&key)?;
+        let purse_id = account.named_purse(name);
+        if let Some(purse_id) = purse_id {
+            self.insert_uref(purse_id.value());
+        }
+        Ok(purse_id)
+    }
+
+    /// Stores a purse in the account under the given name.
+    ///
+    /// Returns `Ok(false)` without modifying the account if it already has a purse with that name.
+    pub fn add_named_purse(&mut self, name: String, purse_id: PurseId) -> Result<bool, Error> {
+        if !self.is_valid_context() {
+            return Err(Error::InvalidContext);
+        }
+        self.validate_uref(&purse_id.value())?;
+
+        let key = Key::Account(self.account().pub_key());
+        let mut account: Account = self.read_gs_typed(&key)?;
+        if !account.insert_named_purse(name, purse_id) {
+            return Ok(false);
+        }
+
+        let account_value = self.account_to_validated_value(account)?;
+        self.state.borrow_mut().write(key, account_value);
+        Ok(true)
+    }
 }
@@ -25,12 +25,12 @@ use types::{
         ActionType, AddKeyFailure, PublicKey, PurseId, RemoveKeyFailure, SetThresholdFailure,
         Weight,
     },
-    AccessRights, BlockTime, CLValue, Key, Phase, ProtocolVersion, URef, LOCAL_SEED_LENGTH,
+    AccessRights, CLValue, Key, Phase, ProtocolVersion, URef, LOCAL_SEED_LENGTH,
 };
 
 use super::{attenuate_uref_for_account, Address, Error, RuntimeContext};
 use crate::{
-    engine_state::SYSTEM_ACCOUNT_ADDR,
+    engine_state::{block_info::BlockInfo, SYSTEM_ACCOUNT_ADDR},
     execution::{extract_access_rights_from_keys, AddressGenerator},
     tracking_copy::TrackingCopy,
 };
@@ -123,7 +123,8 @@ fn mock_runtime_context<'a>(
         BTreeSet::from_iter(vec![PublicKey::new([0; 32])]),
         &account,
         base_key,
-        BlockTime::new(0),
+        Vec::new(),
+        BlockInfo::default(),
         [1u8; 32],
         Gas::default(),
         Gas::default(),
@@ -163,7 +164,8 @@ where
     let deploy_hash = [1u8; 32];
     let (key, account) = mock_account(base_acc_addr);
     let mut uref_map = BTreeMap::new();
-    let address_generator = AddressGenerator::new(deploy_hash, Phase::Session);
+    let address_generator =
+        AddressGenerator::new(deploy_hash, Phase::Session, ProtocolVersion::V1_0_0);
     let runtime_context = mock_runtime_context(
         &account,
         key,
@@ -177,7 +179,7 @@ where
 #[test]
 fn use_uref_valid() {
     // Test fixture
-    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let uref = create_uref(&mut rng, AccessRights::READ_WRITE);
     let access_rights = extract_access_rights_from_keys(vec![uref]);
     // Use uref as the key to perform an action on the global state.
@@ -190,7 +192,7 @@ fn use_uref_valid() {
 #[test]
 fn use_uref_forged() {
     // Test fixture
-    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let uref = create_uref(&mut rng, AccessRights::READ_WRITE);
     let access_rights = HashMap::new();
     let value = StoredValue::CLValue(CLValue::from_t(43_i32).unwrap());
@@ -201,7 +203,7 @@ fn use_uref_forged() {
 
 #[test]
 fn store_contract_with_uref_valid() {
-    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let uref = create_uref(&mut rng, AccessRights::READ_WRITE);
     let access_rights = extract_access_rights_from_keys(vec![uref]);
 
@@ -228,7 +230,7 @@ fn store_contract_with_uref_valid() {
 
 #[test]
 fn store_contract_with_uref_forged() {
-    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let uref = create_uref(&mut rng, AccessRights::READ_WRITE);
     let contract = StoredValue::Contract(Contract::new(
         Vec::new(),
@@ -247,7 +249,7 @@ fn store_contract_with_uref_forged() {
 fn store_contract_under_uref_valid() {
     // Test that storing contract under URef that is known and has WRITE access
     // works.
-    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let contract_uref = create_uref(&mut rng, AccessRights::READ_WRITE);
     let access_rights = extract_access_rights_from_keys(vec![contract_uref]);
     let contract = StoredValue::Contract(Contract::new(
@@ -273,7 +275,7 @@ fn store_contract_under_uref_valid() {
 fn store_contract_under_uref_forged() {
     // Test that storing contract under URef that is not known fails with
     // ForgedReference error.
-    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let contract_uref = create_uref(&mut rng, AccessRights::READ_WRITE);
     let contract = StoredValue::Contract(Contract::new(
         Vec::new(),
@@ -292,7 +294,7 @@ fn store_contract_under_uref_forged() {
 fn store_contract_uref_invalid_access() {
     // Test that storing contract under URef that is known but is not writeable
     // fails.
-    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let contract_uref = create_uref(&mut rng, AccessRights::READ);
     let access_rights = extract_access_rights_from_keys(vec![contract_uref]);
     let contract = StoredValue::Contract(Contract::new(
@@ -355,7 +357,7 @@ fn account_key_readable_invalid() {
 fn account_key_addable_valid() {
     // Account key is addable if it is a "base" key - current context of the
     // execution.
-    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let uref = create_uref(&mut rng, AccessRights::READ);
     let access_rights = extract_access_rights_from_keys(vec![uref]);
     let query_result = test(access_rights, |mut rc| {
@@ -428,7 +430,7 @@ fn contract_key_addable_valid() {
     // execution.
     let base_acc_addr = [0u8; 32];
     let (account_key, account) = mock_account(base_acc_addr);
-    let mut address_generator = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut address_generator = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let mut rng = rand::thread_rng();
     let contract_key = random_contract_key(&mut rng);
     let contract = StoredValue::Contract(Contract::new(
@@ -452,7 +454,8 @@ fn contract_key_addable_valid() {
         BTreeSet::from_iter(vec![PublicKey::new(base_acc_addr)]),
         &account,
         contract_key,
-        BlockTime::new(0),
+        Vec::new(),
+        BlockInfo::default(),
         DEPLOY_HASH,
         Gas::default(),
         Gas::default(),
@@ -489,7 +492,7 @@ fn contract_key_addable_invalid() {
     // execution.
     let base_acc_addr = [0u8; 32];
     let (account_key, account) = mock_account(base_acc_addr);
-    let mut address_generator = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut address_generator = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let mut rng = rand::thread_rng();
     let contract_key = random_contract_key(&mut rng);
     let other_contract_key = random_contract_key(&mut rng);
@@ -513,7 +516,8 @@ fn contract_key_addable_invalid() {
         BTreeSet::from_iter(vec![PublicKey::new(base_acc_addr)]),
         &account,
         other_contract_key,
-        BlockTime::new(0),
+        Vec::new(),
+        BlockInfo::default(),
         DEPLOY_HASH,
         Gas::default(),
         Gas::default(),
@@ -535,7 +539,7 @@ fn contract_key_addable_invalid() {
 
 #[test]
 fn uref_key_readable_valid() {
-    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let uref_key = create_uref(&mut rng, AccessRights::READ);
     let access_rights = extract_access_rights_from_keys(vec![uref_key]);
     let query_result = test(access_rights, |mut rc| rc.read_gs(&uref_key));
@@ -544,7 +548,7 @@ fn uref_key_readable_valid() {
 
 #[test]
 fn uref_key_readable_invalid() {
-    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let uref_key = create_uref(&mut rng, AccessRights::WRITE);
     let access_rights = extract_access_rights_from_keys(vec![uref_key]);
     let query_result = test(access_rights, |mut rc| rc.read_gs(&uref_key));
@@ -553,7 +557,7 @@ fn uref_key_readable_invalid() {
 
 #[test]
 fn uref_key_writeable_valid() {
-    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let uref_key = create_uref(&mut rng, AccessRights::WRITE);
     let access_rights = extract_access_rights_from_keys(vec![uref_key]);
     let query_result = test(access_rights, |mut rc| {
@@ -567,7 +571,7 @@ fn uref_key_writeable_valid() {
 
 #[test]
 fn uref_key_writeable_invalid() {
-    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let uref_key = create_uref(&mut rng, AccessRights::READ);
     let access_rights = extract_access_rights_from_keys(vec![uref_key]);
     let query_result = test(access_rights, |mut rc| {
@@ -581,7 +585,7 @@ fn uref_key_writeable_invalid() {
 
 #[test]
 fn uref_key_addable_valid() {
-    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let uref_key = create_uref(&mut rng, AccessRights::ADD_WRITE);
     let access_rights = extract_access_rights_from_keys(vec![uref_key]);
     let query_result = test(access_rights, |mut rc| {
@@ -600,7 +604,7 @@ fn uref_key_addable_valid() {
 
 #[test]
 fn uref_key_addable_invalid() {
-    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE, ProtocolVersion::V1_0_0);
     let uref_key = create_uref(&mut rng, AccessRights::WRITE);
     let access_rights = extract_access_rights_from_keys(vec![uref_key]);
     let query_result = test(access_rights, |mut rc| {
@@ -899,7 +903,8 @@ fn remove_uref_works() {
     let base_acc_addr = [0u8; 32];
     let deploy_hash = [1u8; 32];
     let (key, account) = mock_account(base_acc_addr);
-    let mut address_generator = AddressGenerator::new(deploy_hash, Phase::Session);
+    let mut address_generator =
+        AddressGenerator::new(deploy_hash, Phase::Session, ProtocolVersion::V1_0_0);
     let uref_name = "Foo".to_owned();
     let uref_key = create_uref(&mut address_generator, AccessRights::READ);
     let mut uref_map = iter::once((uref_name.clone(), uref_key)).collect();
@@ -927,7 +932,8 @@ fn validate_valid_purse_id_of_an_account() {
     let base_acc_addr = [0u8; 32];
     let deploy_hash = [1u8; 32];
     let (key, account) = mock_account_with_purse_id(base_acc_addr, mock_purse_id);
-    let address_generator = AddressGenerator::new(deploy_hash, Phase::Session);
+    let address_generator =
+        AddressGenerator::new(deploy_hash, Phase::Session, ProtocolVersion::V1_0_0);
     let mut uref_map = BTreeMap::new();
     let runtime_context =
         mock_runtime_context(&account, key, &mut uref_map, named_keys, address_generator);
@@ -975,3 +981,50 @@ fn attenuate_uref_for_user_account() {
         .expect("should have access rights");
     assert_eq!(access_rights, AccessRights::READ);
 }
+
+#[test]
+fn new_function_address_should_match_contract_address_test_vectors() {
+    let vectors = include_str!("../../../types/test-vectors/contract_address.txt");
+    let (key, account) = mock_account([0u8; 32]);
+    for line in vectors
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let mut deploy_hash = [0u8; 32];
+        deploy_hash.copy_from_slice(&base16::decode(fields[0]).expect("should decode deploy hash"));
+        let fn_store_id: u32 = fields[1].parse().expect("should parse index");
+        let expected = base16::decode(fields[2]).expect("should decode address");
+
+        let mut named_keys = BTreeMap::new();
+        let address_generator =
+            AddressGenerator::new(deploy_hash, Phase::Session, ProtocolVersion::V1_0_0);
+        let mut runtime_context = RuntimeContext::new(
+            Rc::new(RefCell::new(mock_tc(key, account.clone()))),
+            &mut named_keys,
+            HashMap::new(),
+            Vec::new(),
+            BTreeSet::new(),
+            &account,
+            key,
+            Vec::new(),
+            BlockInfo::default(),
+            deploy_hash,
+            Gas::default(),
+            Gas::default(),
+            fn_store_id,
+            Rc::new(RefCell::new(address_generator)),
+            ProtocolVersion::V1_0_0,
+            CorrelationId::new(),
+            Phase::Session,
+            Default::default(),
+        );
+
+        let address = runtime_context
+            .new_function_address()
+            .expect("should generate address");
+        assert_eq!(address.to_vec(), expected, "{}", line);
+        assert_eq!(address, types::contract_address(deploy_hash, fn_store_id));
+        assert_eq!(runtime_context.fn_store_id(), fn_store_id + 1);
+    }
+}
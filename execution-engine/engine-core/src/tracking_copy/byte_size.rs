@@ -1,7 +1,12 @@
 use std::{collections::BTreeMap, mem};
 
-use engine_shared::{account::Account, contract::Contract, stored_value::StoredValue};
-use types::Key;
+use engine_shared::{
+    account::Account,
+    contract::Contract,
+    contract_package::{ContractHeader, ContractPackage},
+    stored_value::StoredValue,
+};
+use types::{account::PublicKey, ContractVersion, Key};
 
 /// Returns byte size of the element - both heap size and stack size.
 pub trait ByteSize {
@@ -50,6 +55,7 @@ impl ByteSize for StoredValue {
                 StoredValue::CLValue(cl_value) => cl_value.serialized_len(),
                 StoredValue::Account(account) => account.heap_size(),
                 StoredValue::Contract(contract) => contract.heap_size(),
+                StoredValue::ContractPackage(contract_package) => contract_package.heap_size(),
             }
     }
 }
@@ -76,7 +82,19 @@ impl HeapSizeOf for Account {
 // TODO: contract has other fields (re protocol version) that are not repr here...on purpose?
 impl HeapSizeOf for Contract {
     fn heap_size(&self) -> usize {
-        self.named_keys().heap_size() + self.bytes().len()
+        self.named_keys().heap_size()
+            + self.bytes().len()
+            + self.authorized_callers().map_or(0, |authorized_callers| {
+                authorized_callers.len() * mem::size_of::<PublicKey>()
+            })
+    }
+}
+
+// NOTE: We're ignoring the heap size of the versions' entry points.
+impl HeapSizeOf for ContractPackage {
+    fn heap_size(&self) -> usize {
+        self.versions().len()
+            * (mem::size_of::<ContractVersion>() + mem::size_of::<ContractHeader>())
     }
 }
 
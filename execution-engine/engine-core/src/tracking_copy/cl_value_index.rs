@@ -0,0 +1,201 @@
+//! Resolution of query path segments inside stored `CLValue` lists and maps.
+//!
+//! Items are located by walking the serialized bytes of the collection, so a single entry can be
+//! read out of a large map without deserializing the rest of it.
+
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
+    CLType, CLValue, Key, URef, U128, U256, U512,
+};
+
+const ACCOUNT_PREFIX: &str = "account-";
+const HASH_PREFIX: &str = "hash-";
+
+/// Returns the item of the list, or the value of the map, held in `cl_value` which `segment`
+/// refers to.
+///
+/// List items are referred to by their decimal index.  Map keys are written as decimal numbers,
+/// `true` or `false`, plain strings, hex-encoded byte arrays (e.g. public keys), or formatted
+/// account and hash keys.  The error is a message explaining why `segment` can't be followed.
+pub(super) fn index(cl_value: &CLValue, segment: &str) -> Result<CLValue, String> {
+    let bytes = cl_value.inner_bytes().as_slice();
+    let result = match cl_value.cl_type() {
+        CLType::List(item_type) => list_item(item_type, bytes, None, segment),
+        CLType::FixedList(item_type, len) => list_item(item_type, bytes, Some(*len), segment),
+        CLType::Map { key, value } => map_value(key, value, bytes, segment),
+        other => {
+            return Err(format!(
+                "Name {} cannot be followed into value of type {:?} because it is neither a list nor a map. Value found at path:",
+                segment, other
+            ))
+        }
+    };
+    match result {
+        Ok(Some(value)) => Ok(value),
+        Ok(None) => Err(format!("Name {} not found in CLValue at path:", segment)),
+        Err(error) => Err(format!(
+            "Name {} cannot be followed into malformed CLValue ({:?}) at path:",
+            segment, error
+        )),
+    }
+}
+
+fn list_item(
+    item_type: &CLType,
+    bytes: &[u8],
+    fixed_len: Option<u32>,
+    segment: &str,
+) -> Result<Option<CLValue>, bytesrepr::Error> {
+    let index: u32 = match segment.parse() {
+        Ok(index) => index,
+        Err(_) => return Ok(None),
+    };
+    let (len, mut rem) = match fixed_len {
+        Some(len) if is_unprefixed_byte_array(item_type, len) => (len, bytes),
+        _ => u32::from_bytes(bytes)?,
+    };
+    if index >= len {
+        return Ok(None);
+    }
+    for _ in 0..index {
+        rem = skip(item_type, rem)?;
+    }
+    Ok(Some(take(item_type, rem)?))
+}
+
+fn map_value(
+    key_type: &CLType,
+    value_type: &CLType,
+    bytes: &[u8],
+    segment: &str,
+) -> Result<Option<CLValue>, bytesrepr::Error> {
+    let key_bytes = match parse_map_key(key_type, segment) {
+        Some(key_bytes) => key_bytes,
+        None => return Ok(None),
+    };
+    let (len, mut rem) = u32::from_bytes(bytes)?;
+    for _ in 0..len {
+        let value_bytes = skip(key_type, rem)?;
+        let is_match = &rem[..rem.len() - value_bytes.len()] == key_bytes.as_slice();
+        if is_match {
+            return Ok(Some(take(value_type, value_bytes)?));
+        }
+        rem = skip(value_type, value_bytes)?;
+    }
+    Ok(None)
+}
+
+/// Serializes the map key which `segment` is written as, or returns `None` if `segment` can't be
+/// a key of the given type.
+fn parse_map_key(key_type: &CLType, segment: &str) -> Option<Vec<u8>> {
+    match key_type {
+        CLType::Bool => segment.parse::<bool>().ok()?.to_bytes().ok(),
+        CLType::I32 => segment.parse::<i32>().ok()?.to_bytes().ok(),
+        CLType::I64 => segment.parse::<i64>().ok()?.to_bytes().ok(),
+        CLType::U8 => segment.parse::<u8>().ok()?.to_bytes().ok(),
+        CLType::U32 => segment.parse::<u32>().ok()?.to_bytes().ok(),
+        CLType::U64 => segment.parse::<u64>().ok()?.to_bytes().ok(),
+        CLType::U128 => U128::from_dec_str(segment).ok()?.to_bytes().ok(),
+        CLType::U256 => U256::from_dec_str(segment).ok()?.to_bytes().ok(),
+        CLType::U512 => U512::from_dec_str(segment).ok()?.to_bytes().ok(),
+        CLType::String => segment.to_bytes().ok(),
+        CLType::FixedList(item_type, len) if **item_type == CLType::U8 => {
+            let array = base16::decode(segment).ok()?;
+            if array.len() != *len as usize {
+                return None;
+            }
+            let mut ret = Vec::with_capacity(U32_SERIALIZED_LENGTH + array.len());
+            if !is_unprefixed_byte_array(item_type, *len) {
+                ret.extend(len.to_bytes().ok()?);
+            }
+            ret.extend(array);
+            Some(ret)
+        }
+        CLType::Key => {
+            let key = if segment.starts_with(ACCOUNT_PREFIX) {
+                let hash = Key::parse_hash(&segment[ACCOUNT_PREFIX.len()..])?;
+                Key::Account(hash.as_hash()?)
+            } else if segment.starts_with(HASH_PREFIX) {
+                Key::parse_hash(&segment[HASH_PREFIX.len()..])?
+            } else {
+                return None;
+            };
+            key.to_bytes().ok()
+        }
+        _ => None,
+    }
+}
+
+/// Byte arrays of these lengths, such as public keys, are serialized without a length prefix.
+fn is_unprefixed_byte_array(item_type: &CLType, len: u32) -> bool {
+    *item_type == CLType::U8 && [4, 5, 8, 32].contains(&len)
+}
+
+/// Splits the serialized value of type `cl_type` off the start of `bytes`.
+fn take(cl_type: &CLType, bytes: &[u8]) -> Result<CLValue, bytesrepr::Error> {
+    let rem = skip(cl_type, bytes)?;
+    let value_bytes = bytes[..bytes.len() - rem.len()].to_vec();
+    Ok(CLValue::from_components(cl_type.clone(), value_bytes))
+}
+
+/// Returns what is left of `bytes` after the serialized value of type `cl_type` at its start.
+fn skip<'a>(cl_type: &CLType, bytes: &'a [u8]) -> Result<&'a [u8], bytesrepr::Error> {
+    fn rem<T: FromBytes>(bytes: &[u8]) -> Result<&[u8], bytesrepr::Error> {
+        T::from_bytes(bytes).map(|(_, rem)| rem)
+    }
+
+    match cl_type {
+        CLType::Bool => rem::<bool>(bytes),
+        CLType::I32 => rem::<i32>(bytes),
+        CLType::I64 => rem::<i64>(bytes),
+        CLType::U8 => rem::<u8>(bytes),
+        CLType::U32 => rem::<u32>(bytes),
+        CLType::U64 => rem::<u64>(bytes),
+        CLType::U128 => rem::<U128>(bytes),
+        CLType::U256 => rem::<U256>(bytes),
+        CLType::U512 => rem::<U512>(bytes),
+        CLType::Unit => Ok(bytes),
+        CLType::String => rem::<String>(bytes),
+        CLType::Key => rem::<Key>(bytes),
+        CLType::URef => rem::<URef>(bytes),
+        CLType::Option(inner_type) => match u8::from_bytes(bytes)? {
+            (0, rem) => Ok(rem),
+            (1, rem) => skip(inner_type, rem),
+            _ => Err(bytesrepr::Error::FormattingError),
+        },
+        CLType::FixedList(item_type, len) if is_unprefixed_byte_array(item_type, *len) => {
+            bytesrepr::safe_split_at(bytes, *len as usize).map(|(_, rem)| rem)
+        }
+        CLType::List(item_type) | CLType::FixedList(item_type, _) => {
+            let (len, mut rem) = u32::from_bytes(bytes)?;
+            for _ in 0..len {
+                rem = skip(item_type, rem)?;
+            }
+            Ok(rem)
+        }
+        CLType::Result { ok, err } => match u8::from_bytes(bytes)? {
+            (0, rem) => skip(err, rem),
+            (1, rem) => skip(ok, rem),
+            _ => Err(bytesrepr::Error::FormattingError),
+        },
+        CLType::Map { key, value } => {
+            let (len, mut rem) = u32::from_bytes(bytes)?;
+            for _ in 0..len {
+                rem = skip(key, rem)?;
+                rem = skip(value, rem)?;
+            }
+            Ok(rem)
+        }
+        CLType::Tuple1(types) => types
+            .iter()
+            .try_fold(bytes, |rem, cl_type| skip(cl_type, rem)),
+        CLType::Tuple2(types) => types
+            .iter()
+            .try_fold(bytes, |rem, cl_type| skip(cl_type, rem)),
+        CLType::Tuple3(types) => types
+            .iter()
+            .try_fold(bytes, |rem, cl_type| skip(cl_type, rem)),
+        // The length of values of unknown type can't be told
+        CLType::Any => Err(bytesrepr::Error::FormattingError),
+    }
+}
@@ -5,7 +5,10 @@ use engine_shared::{
     stored_value::StoredValue, transform::TypeMismatch,
 };
 use engine_storage::global_state::StateReader;
-use types::{bytesrepr::ToBytes, CLValue, Key, URef, U512};
+use types::{
+    account::{PublicKey, PurseId},
+    CLValue, Key, URef, U512,
+};
 
 use crate::{
     execution,
@@ -57,7 +60,7 @@ where
         correlation_id: CorrelationId,
         account_address: [u8; 32],
     ) -> Result<Account, Self::Error> {
-        let account_key = Key::Account(account_address);
+        let account_key = types::account_key(PublicKey::new(account_address));
         match self.get(correlation_id, &account_key).map_err(Into::into)? {
             Some(StoredValue::Account(account)) => Ok(account),
             Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
@@ -77,8 +80,7 @@ where
         let uref = outer_key
             .as_uref()
             .ok_or_else(|| execution::Error::URefNotFound("public purse balance".to_string()))?;
-        let local_key_bytes = uref.addr().into_bytes()?;
-        let balance_mapping_key = Key::local(mint_contract_uref.addr(), &local_key_bytes);
+        let balance_mapping_key = types::purse_balance_key(mint_contract_uref, PurseId::new(*uref));
         match self
             .query(correlation_id, balance_mapping_key, &[])
             .map_err(Into::into)?
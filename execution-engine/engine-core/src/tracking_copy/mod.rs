@@ -1,10 +1,18 @@
 mod byte_size;
+mod cl_value_index;
 mod ext;
 pub(self) mod meter;
 #[cfg(test)]
 mod tests;
 
-use std::{collections::HashMap, convert::From, iter};
+#[cfg(feature = "rent")]
+use std::collections::BTreeSet;
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::From,
+    iter,
+    ops::Add,
+};
 
 use linked_hash_map::LinkedHashMap;
 
@@ -15,9 +23,20 @@ use engine_shared::{
     transform::{self, Transform, TypeMismatch},
 };
 use engine_storage::global_state::StateReader;
-use types::{bytesrepr, CLType, CLValueError, Key};
+#[cfg(feature = "rent")]
+use types::BlockTime;
+use types::{
+    account::PUBLIC_KEY_SERIALIZED_LENGTH,
+    bytesrepr::{self, ToBytes},
+    CLType, CLValue, CLValueError, Key, LOCAL_SEED_LENGTH,
+};
 
-use crate::engine_state::{execution_effect::ExecutionEffect, op::Op};
+#[cfg(feature = "rent")]
+use crate::engine_state::rent;
+use crate::{
+    engine_state::{execution_effect::ExecutionEffect, op::Op, query::QueryPathStep},
+    runtime_context,
+};
 
 pub use self::ext::TrackingCopyExt;
 use self::meter::{heap_meter::HeapSize, Meter};
@@ -28,6 +47,49 @@ pub enum TrackingCopyQueryResult {
     ValueNotFound(String),
 }
 
+/// How many stored keys and contract packages in a row a query passes through before giving up.
+const MAX_QUERY_INDIRECTIONS: usize = 16;
+
+/// Where the resolution of a query path has got to.
+enum QueryPosition {
+    /// A value, along with the base from which the seeds of its namespaces derive.
+    Value {
+        value: StoredValue,
+        seed_base: [u8; LOCAL_SEED_LENGTH],
+    },
+    /// A namespace of the local state of an account or contract, whose items are looked up by
+    /// the next segment of the path.
+    Namespace {
+        name: String,
+        container: &'static str,
+        seed: [u8; LOCAL_SEED_LENGTH],
+    },
+}
+
+/// Returns the key held in `cl_value`, or `None` if it holds neither a key nor a URef.
+fn stored_key(cl_value: &CLValue) -> Option<Result<Key, CLValueError>> {
+    match cl_value.cl_type() {
+        CLType::Key => Some(cl_value.clone().into_t()),
+        CLType::URef => Some(cl_value.clone().into_t().map(Key::URef)),
+        _ => None,
+    }
+}
+
+/// Returns the serialized keys which the namespace item written as `segment` may have been
+/// stored under, in the order they are tried: the hex-encoded public key, then the string itself.
+fn namespace_item_keys(segment: &str) -> Vec<Vec<u8>> {
+    let mut ret = Vec::new();
+    if let Ok(bytes) = base16::decode(segment) {
+        if bytes.len() == PUBLIC_KEY_SERIALIZED_LENGTH {
+            ret.push(bytes);
+        }
+    }
+    if let Ok(bytes) = segment.to_bytes() {
+        ret.push(bytes);
+    }
+    ret
+}
+
 /// Keeps track of already accessed keys.
 /// We deliberately separate cached Reads from cached mutations
 /// because we want to invalidate Reads' cache so it doesn't grow too fast.
@@ -85,11 +147,55 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
     }
 }
 
+/// Counters describing how the reads performed through a `TrackingCopy` were served.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ReadStats {
+    cache_hits: u64,
+    reader_hits: u64,
+    bytes_deserialized: u64,
+}
+
+impl ReadStats {
+    /// Number of reads served from the tracking copy's own cache.
+    pub fn cache_hits(&self) -> u64 {
+        self.cache_hits
+    }
+
+    /// Number of reads which had to go through the underlying reader.
+    pub fn reader_hits(&self) -> u64 {
+        self.reader_hits
+    }
+
+    /// Total serialized size of the values returned by the underlying reader.
+    pub fn bytes_deserialized(&self) -> u64 {
+        self.bytes_deserialized
+    }
+}
+
+impl Add for ReadStats {
+    type Output = ReadStats;
+
+    fn add(self, other: ReadStats) -> ReadStats {
+        ReadStats {
+            cache_hits: self.cache_hits + other.cache_hits,
+            reader_hits: self.reader_hits + other.reader_hits,
+            bytes_deserialized: self.bytes_deserialized + other.bytes_deserialized,
+        }
+    }
+}
+
 pub struct TrackingCopy<R> {
     reader: R,
     cache: TrackingCopyCache<HeapSize>,
     ops: AdditiveMap<Key, Op>,
     fns: AdditiveMap<Key, Transform>,
+    read_stats: Option<ReadStats>,
+    /// The block time recorded as the last access of the accessed keys, if rent is enabled.
+    #[cfg(feature = "rent")]
+    rent_blocktime: Option<BlockTime>,
+    /// The accessed keys which are subject to rent.
+    #[cfg(feature = "rent")]
+    accessed_keys: BTreeSet<Key>,
 }
 
 #[derive(Debug)]
@@ -122,6 +228,11 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
                                                                  * limit? */
             ops: AdditiveMap::new(),
             fns: AdditiveMap::new(),
+            read_stats: None,
+            #[cfg(feature = "rent")]
+            rent_blocktime: None,
+            #[cfg(feature = "rent")]
+            accessed_keys: BTreeSet::new(),
         }
     }
 
@@ -129,6 +240,34 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         &self.reader
     }
 
+    /// Starts collecting [`ReadStats`] for reads performed from now on.  Forks of this
+    /// `TrackingCopy` collect their own stats as well.
+    pub fn enable_read_stats(&mut self) {
+        if self.read_stats.is_none() {
+            self.read_stats = Some(ReadStats::default());
+        }
+    }
+
+    /// Returns the collected [`ReadStats`], or `None` if collecting them was not enabled.
+    pub fn read_stats(&self) -> Option<ReadStats> {
+        self.read_stats
+    }
+
+    /// Enables rent, so that the [`ExecutionEffect`] records `blocktime` as the last access of all
+    /// the keys subject to rent which were read, written or added to.  Forks of this
+    /// `TrackingCopy` have rent enabled as well.
+    #[cfg(feature = "rent")]
+    pub fn enable_rent(&mut self, blocktime: BlockTime) {
+        self.rent_blocktime = Some(blocktime);
+    }
+
+    #[cfg(feature = "rent")]
+    fn record_access(&mut self, key: Key) {
+        if rent::is_subject_to_rent(&key) {
+            self.accessed_keys.insert(key);
+        }
+    }
+
     /// Creates a new TrackingCopy, using this one (including its mutations) as
     /// the base state to read against. The intended use case for this
     /// function is to "snapshot" the current `TrackingCopy` and produce a
@@ -142,7 +281,15 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
     /// forking, however we recognize this is sub-optimal and will revisit
     /// in the future.
     pub fn fork(&self) -> TrackingCopy<&TrackingCopy<R>> {
-        TrackingCopy::new(self)
+        let mut fork = TrackingCopy::new(self);
+        if self.read_stats.is_some() {
+            fork.enable_read_stats();
+        }
+        #[cfg(feature = "rent")]
+        {
+            fork.rent_blocktime = self.rent_blocktime;
+        }
+        fork
     }
 
     pub fn get(
@@ -151,9 +298,21 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         key: &Key,
     ) -> Result<Option<StoredValue>, R::Error> {
         if let Some(value) = self.cache.get(key) {
-            return Ok(Some(value.to_owned()));
+            let value = value.to_owned();
+            if let Some(read_stats) = self.read_stats.as_mut() {
+                read_stats.cache_hits += 1;
+            }
+            return Ok(Some(value));
         }
-        if let Some(value) = self.reader.read(correlation_id, key)? {
+        let maybe_value = self.reader.read(correlation_id, key)?;
+        if let Some(read_stats) = self.read_stats.as_mut() {
+            read_stats.reader_hits += 1;
+            if let Some(value) = maybe_value.as_ref() {
+                // The serialized size of a value matches the number of bytes it was read from
+                read_stats.bytes_deserialized += value.serialized_length() as u64;
+            }
+        }
+        if let Some(value) = maybe_value {
             self.cache.insert_read(*key, value.to_owned());
             Ok(Some(value))
         } else {
@@ -170,6 +329,8 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         if let Some(value) = self.get(correlation_id, &normalized_key)? {
             self.ops.insert_add(normalized_key, Op::Read);
             self.fns.insert_add(normalized_key, Transform::Identity);
+            #[cfg(feature = "rent")]
+            self.record_access(normalized_key);
             Ok(Some(value))
         } else {
             Ok(None)
@@ -181,6 +342,8 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         self.cache.insert_write(normalized_key, value.clone());
         self.ops.insert_add(normalized_key, Op::Write);
         self.fns.insert_add(normalized_key, Transform::Write(value));
+        #[cfg(feature = "rent")]
+        self.record_access(normalized_key);
     }
 
     /// Ok(None) represents missing key to which we want to "add" some value.
@@ -251,6 +414,8 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
                 self.cache.insert_write(normalized_key, new_value);
                 self.ops.insert_add(normalized_key, Op::Add);
                 self.fns.insert_add(normalized_key, transform);
+                #[cfg(feature = "rent")]
+                self.record_access(normalized_key);
                 Ok(AddResult::Success)
             }
             Err(transform::Error::TypeMismatch(type_mismatch)) => {
@@ -261,6 +426,22 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
     }
 
     pub fn effect(&self) -> ExecutionEffect {
+        #[cfg(feature = "rent")]
+        {
+            if let Some(blocktime) = self.rent_blocktime {
+                let mut ops = self.ops.clone();
+                let mut fns = self.fns.clone();
+                for key in &self.accessed_keys {
+                    let metadata_key = rent::metadata_key(key);
+                    ops.insert_add(metadata_key, Op::Write);
+                    fns.insert_add(
+                        metadata_key,
+                        Transform::Write(rent::metadata_value(*key, blocktime)),
+                    );
+                }
+                return ExecutionEffect::new(ops, fns);
+            }
+        }
         ExecutionEffect::new(self.ops.clone(), self.fns.clone())
     }
 
@@ -270,70 +451,236 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         base_key: Key,
         path: &[String],
     ) -> Result<TrackingCopyQueryResult, R::Error> {
-        match self.read(correlation_id, &base_key)? {
-            None => Ok(TrackingCopyQueryResult::ValueNotFound(self.error_path_msg(
-                base_key,
-                path,
-                "".to_owned(),
-                0 as usize,
-            ))),
-            Some(base_value) => {
-                let result = path.iter().enumerate().try_fold(
-                    base_value,
-                    // We encode the two possible short-circuit conditions with
-                    // Result<(usize, String), Error>, where the Ok(_) case corresponds to
-                    // QueryResult::ValueNotFound and Err(_) corresponds to
-                    // a storage-related error. The information in the Ok(_) case is used
-                    // to build an informative error message about why the query was not successful.
-                    |current_value, (i, name)| -> Result<StoredValue, Result<(usize, String), R::Error>> {
-                        match current_value {
-                            StoredValue::Account(account) => {
-                                if let Some(key) = account.named_keys().get(name) {
-                                    self.read_key_or_stop(correlation_id, *key, i)
-                                } else {
-                                    Err(Ok((i, format!("Name {} not found in Account at path:", name))))
-                                }
-                            }
+        self.query_with_visited_path(correlation_id, base_key, path)
+            .map(|(result, _visited_path)| result)
+    }
 
-                            StoredValue::Contract(contract) => {
-                                if let Some(key) = contract.named_keys().get(name) {
-                                    self.read_key_or_stop(correlation_id, *key, i)
-                                } else {
-                                    Err(Ok((i, format!("Name {} not found in Contract at path:", name))))
-                                }
-                            }
+    /// Resolves `path` from the value under `base_key` like [`query`](TrackingCopy::query), and
+    /// also returns the steps taken, up to where the value was found or went missing.
+    ///
+    /// Each segment of the path is resolved against the value reached so far:
+    /// * in an account or contract, as one of its named keys, or else as the name of one of the
+    ///   namespaces of its local state, whose items are looked up by the next segment;
+    /// * in a contract package, as one of its versions, or else against its current version;
+    /// * in a stored key or URef, against the value under it;
+    /// * in a list or map, as an index or key of it.
+    pub fn query_with_visited_path(
+        &mut self,
+        correlation_id: CorrelationId,
+        base_key: Key,
+        path: &[String],
+    ) -> Result<(TrackingCopyQueryResult, Vec<QueryPathStep>), R::Error> {
+        let mut visited_path = vec![QueryPathStep::Read(base_key)];
+        let mut position = match self.read(correlation_id, &base_key)? {
+            Some(value) => QueryPosition::Value {
+                value,
+                seed_base: runtime_context::local_seed(base_key),
+            },
+            None => {
+                let msg = self.error_path_msg(base_key, path, "".to_owned(), 0);
+                return Ok((TrackingCopyQueryResult::ValueNotFound(msg), visited_path));
+            }
+        };
+
+        for (i, segment) in path.iter().enumerate() {
+            position =
+                match self.query_step(correlation_id, position, segment, &mut visited_path)? {
+                    Ok(position) => position,
+                    Err(msg) => {
+                        let msg = self.error_path_msg(base_key, path, msg, i);
+                        return Ok((TrackingCopyQueryResult::ValueNotFound(msg), visited_path));
+                    }
+                };
+        }
+
+        let result = match position {
+            QueryPosition::Value { value, .. } => TrackingCopyQueryResult::Success(value),
+            // A namespace holds no value of its own, so the path can't end with its name
+            QueryPosition::Namespace {
+                name, container, ..
+            } => {
+                let msg = format!("Name {} not found in {} at path:", name, container);
+                let msg = self.error_path_msg(base_key, path, msg, path.len() - 1);
+                TrackingCopyQueryResult::ValueNotFound(msg)
+            }
+        };
+        Ok((result, visited_path))
+    }
 
-                            other => Err(
-                                Ok((i, format!("Name {} cannot be followed from value {:?} because it is neither an account nor contract. Value found at path:", name, other)))
-                                ),
+    /// Resolves one segment of a query path from `position`.  The inner error is a message
+    /// explaining why `segment` can't be followed.
+    fn query_step(
+        &mut self,
+        correlation_id: CorrelationId,
+        position: QueryPosition,
+        segment: &str,
+        visited_path: &mut Vec<QueryPathStep>,
+    ) -> Result<Result<QueryPosition, String>, R::Error> {
+        let (mut value, mut seed_base) = match position {
+            QueryPosition::Value { value, seed_base } => (value, seed_base),
+            QueryPosition::Namespace { name, seed, .. } => {
+                return self.query_namespace_item(
+                    correlation_id,
+                    &name,
+                    seed,
+                    segment,
+                    visited_path,
+                )
+            }
+        };
+
+        // Stored keys and contract packages are passed through without using up the segment, at
+        // most `MAX_QUERY_INDIRECTIONS` times in a row so that cycles of stored keys end
+        for _ in 0..MAX_QUERY_INDIRECTIONS {
+            let (key, next_seed_base) = match &value {
+                StoredValue::Account(account) => {
+                    return self.query_named_key(
+                        correlation_id,
+                        account.named_keys(),
+                        "Account",
+                        seed_base,
+                        segment,
+                        visited_path,
+                    )
+                }
+
+                StoredValue::Contract(contract) => {
+                    return self.query_named_key(
+                        correlation_id,
+                        contract.named_keys(),
+                        "Contract",
+                        seed_base,
+                        segment,
+                        visited_path,
+                    )
+                }
+
+                // The namespaces of a contract called through its package derive from the hash of
+                // the package rather than of the contract
+                StoredValue::ContractPackage(package) => {
+                    let requested_version = segment
+                        .parse()
+                        .ok()
+                        .and_then(|version| package.get_version(version));
+                    if let Some(header) = requested_version {
+                        let key = Key::Hash(header.contract_hash());
+                        return self.query_read(correlation_id, key, seed_base, visited_path);
+                    }
+                    match package.current_version() {
+                        Some((_, header)) => (Key::Hash(header.contract_hash()), seed_base),
+                        None => {
+                            let msg = format!(
+                                "Name {} not found in ContractPackage with no enabled version at path:",
+                                segment
+                            );
+                            return Ok(Err(msg));
                         }
-                    },
-                );
-
-                match result {
-                    Ok(value) => Ok(TrackingCopyQueryResult::Success(value)),
-                    Err(Ok((i, s))) => Ok(TrackingCopyQueryResult::ValueNotFound(
-                        self.error_path_msg(base_key, path, s, i),
-                    )),
-                    Err(Err(err)) => Err(err),
+                    }
+                }
+
+                StoredValue::CLValue(cl_value) => match stored_key(cl_value) {
+                    Some(Ok(key)) => (key, runtime_context::local_seed(key)),
+                    Some(Err(error)) => {
+                        let msg = format!(
+                            "Name {} cannot be followed through malformed key ({:?}) at path:",
+                            segment, error
+                        );
+                        return Ok(Err(msg));
+                    }
+                    // Lists and maps are indexed into without further reads
+                    None => {
+                        visited_path.push(QueryPathStep::Index(segment.to_string()));
+                        return Ok(cl_value_index::index(cl_value, segment).map(|cl_value| {
+                            QueryPosition::Value {
+                                value: StoredValue::CLValue(cl_value),
+                                seed_base,
+                            }
+                        }));
+                    }
+                },
+            };
+
+            match self.query_read(correlation_id, key, next_seed_base, visited_path)? {
+                Ok(QueryPosition::Value {
+                    value: next_value,
+                    seed_base: next_seed_base,
+                }) => {
+                    value = next_value;
+                    seed_base = next_seed_base;
                 }
+                other => return Ok(other),
+            }
+        }
+
+        Ok(Err(format!(
+            "Name {} cannot be followed through more than {} stored keys in a row at path:",
+            segment, MAX_QUERY_INDIRECTIONS
+        )))
+    }
+
+    /// Follows the named key `segment` of an account or contract, or else enters its namespace of
+    /// that name.
+    fn query_named_key(
+        &mut self,
+        correlation_id: CorrelationId,
+        named_keys: &BTreeMap<String, Key>,
+        container: &'static str,
+        seed_base: [u8; LOCAL_SEED_LENGTH],
+        segment: &str,
+        visited_path: &mut Vec<QueryPathStep>,
+    ) -> Result<Result<QueryPosition, String>, R::Error> {
+        if let Some(key) = named_keys.get(segment) {
+            let seed_base = runtime_context::local_seed(*key);
+            return self.query_read(correlation_id, *key, seed_base, visited_path);
+        }
+        let seed = runtime_context::namespace_seed(seed_base, segment);
+        visited_path.push(QueryPathStep::Namespace {
+            name: segment.to_string(),
+            seed,
+        });
+        Ok(Ok(QueryPosition::Namespace {
+            name: segment.to_string(),
+            container,
+            seed,
+        }))
+    }
+
+    /// Reads the item `segment` of the namespace `name` with the given seed.
+    fn query_namespace_item(
+        &mut self,
+        correlation_id: CorrelationId,
+        name: &str,
+        seed: [u8; LOCAL_SEED_LENGTH],
+        segment: &str,
+        visited_path: &mut Vec<QueryPathStep>,
+    ) -> Result<Result<QueryPosition, String>, R::Error> {
+        for item_key in namespace_item_keys(segment) {
+            let key = Key::local(seed, &item_key);
+            if let Some(value) = self.read(correlation_id, &key)? {
+                visited_path.push(QueryPathStep::Read(key));
+                return Ok(Ok(QueryPosition::Value {
+                    value,
+                    seed_base: runtime_context::local_seed(key),
+                }));
             }
         }
+        Ok(Err(format!(
+            "Name {} not found in namespace {} at path:",
+            segment, name
+        )))
     }
 
-    fn read_key_or_stop(
+    fn query_read(
         &mut self,
         correlation_id: CorrelationId,
         key: Key,
-        i: usize,
-    ) -> Result<StoredValue, Result<(usize, String), R::Error>> {
-        match self.read(correlation_id, &key) {
-            // continue recursion
-            Ok(Some(value)) => Ok(value),
-            // key not found in the global state; stop recursion
-            Ok(None) => Err(Ok((i, format!("Name {:?} not found: ", key)))),
-            // global state access error; stop recursion
-            Err(error) => Err(Err(error)),
+        seed_base: [u8; LOCAL_SEED_LENGTH],
+        visited_path: &mut Vec<QueryPathStep>,
+    ) -> Result<Result<QueryPosition, String>, R::Error> {
+        visited_path.push(QueryPathStep::Read(key));
+        match self.read(correlation_id, &key)? {
+            Some(value) => Ok(Ok(QueryPosition::Value { value, seed_base })),
+            None => Ok(Err(format!("Name {:?} not found: ", key))),
         }
     }
 
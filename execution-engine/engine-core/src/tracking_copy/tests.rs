@@ -6,21 +6,26 @@ use proptest::{collection::vec, prelude::*};
 use engine_shared::{
     account::{Account, AssociatedKeys},
     contract::Contract,
-    newtypes::CorrelationId,
+    contract_package::{ContractHeader, ContractPackage},
+    newtypes::{Blake2bHash, CorrelationId},
     stored_value::{gens::stored_value_arb, StoredValue},
     transform::Transform,
 };
 use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader};
 use types::{
     account::{PublicKey, PurseId, Weight, PUBLIC_KEY_LENGTH},
+    bytesrepr::ToBytes,
     gens::*,
-    AccessRights, CLValue, Key, ProtocolVersion, URef,
+    AccessRights, CLValue, EntryPoints, Key, ProtocolVersion, URef, U512,
 };
 
 use super::{
     meter::count_meter::Count, AddResult, TrackingCopy, TrackingCopyCache, TrackingCopyQueryResult,
 };
-use crate::engine_state::op::Op;
+use crate::{
+    engine_state::{op::Op, query::QueryPathStep},
+    runtime_context,
+};
 
 struct CountingDb {
     count: Rc<Cell<i32>>,
@@ -91,6 +96,98 @@ fn tracking_copy_caching() {
     assert_eq!(db_value, 1);
 }
 
+#[test]
+fn tracking_copy_read_stats_disabled_by_default() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(counter);
+    let mut tc = TrackingCopy::new(db);
+    let k = Key::Hash([0u8; 32]);
+
+    tc.read(correlation_id, &k).unwrap();
+    assert_eq!(tc.read_stats(), None);
+}
+
+#[test]
+fn tracking_copy_read_stats() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(counter);
+    let mut tc = TrackingCopy::new(db);
+    tc.enable_read_stats();
+    let k = Key::Hash([0u8; 32]);
+
+    let value = tc.read(correlation_id, &k).unwrap().unwrap();
+    let value_size = value.to_bytes().unwrap().len() as u64;
+    // second read is served from the cache
+    tc.read(correlation_id, &k).unwrap();
+
+    let read_stats = tc.read_stats().expect("should have read stats");
+    assert_eq!(read_stats.reader_hits(), 1);
+    assert_eq!(read_stats.cache_hits(), 1);
+    assert_eq!(read_stats.bytes_deserialized(), value_size);
+
+    // forks collect their own stats
+    let mut fork = tc.fork();
+    fork.read(correlation_id, &k).unwrap();
+    let fork_read_stats = fork.read_stats().expect("fork should have read stats");
+    assert_eq!(fork_read_stats.reader_hits(), 1);
+    assert_eq!(fork_read_stats.cache_hits(), 0);
+}
+
+#[cfg(feature = "rent")]
+#[test]
+fn tracking_copy_records_last_accessed_with_rent_enabled() {
+    use types::BlockTime;
+
+    use crate::engine_state::rent;
+
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(counter);
+    let mut tc = TrackingCopy::new(db);
+    let read_key = Key::Hash([0u8; 32]);
+    let account_key = Key::Account([1u8; 32]);
+
+    tc.read(correlation_id, &read_key).unwrap();
+    assert!(tc
+        .effect()
+        .transforms
+        .get(&rent::metadata_key(&read_key))
+        .is_none());
+
+    let blocktime = BlockTime::new(42);
+    tc.enable_rent(blocktime);
+    tc.read(correlation_id, &account_key).unwrap();
+
+    // forks record the accesses of their own keys
+    let mut fork = tc.fork();
+    let written_key = Key::URef(URef::new([2u8; 32], AccessRights::READ_ADD_WRITE));
+    let value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    fork.write(written_key, value);
+
+    let effect = tc.effect();
+    assert_eq!(
+        effect.transforms.get(&rent::metadata_key(&read_key)),
+        Some(&Transform::Write(rent::metadata_value(read_key, blocktime)))
+    );
+    assert!(effect
+        .transforms
+        .get(&rent::metadata_key(&account_key))
+        .is_none());
+
+    let fork_effect = fork.effect();
+    assert_eq!(
+        fork_effect
+            .transforms
+            .get(&rent::metadata_key(&written_key)),
+        Some(&Transform::Write(rent::metadata_value(
+            written_key,
+            blocktime
+        )))
+    );
+}
+
 #[test]
 fn tracking_copy_read() {
     let correlation_id = CorrelationId::new();
@@ -478,3 +575,310 @@ fn cache_writes_not_invalidated() {
     assert_eq!(tc_cache.get(&k2), Some(&v2)); // k2 and k3 should be there
     assert_eq!(tc_cache.get(&k3), Some(&v3));
 }
+
+fn query_cl_value(cl_value: CLValue, path: &[&str]) -> (Result<TrackingCopyQueryResult, !>, i32) {
+    let correlation_id = CorrelationId::new();
+    let db = CountingDb::new_init(StoredValue::CLValue(cl_value));
+    let counter = Rc::clone(&db.count);
+    let mut tc = TrackingCopy::new(db);
+    let path = path.iter().map(ToString::to_string).collect::<Vec<_>>();
+    let result = tc.query(correlation_id, Key::Hash([0u8; 32]), &path);
+    (result, counter.get())
+}
+
+#[test]
+fn query_map_value_by_public_key() {
+    let known = PublicKey::new([1u8; PUBLIC_KEY_LENGTH]);
+    let unknown = PublicKey::new([2u8; PUBLIC_KEY_LENGTH]);
+    let balances: BTreeMap<PublicKey, U512> = vec![
+        (PublicKey::new([0u8; PUBLIC_KEY_LENGTH]), U512::from(10)),
+        (known, U512::from(20)),
+        (PublicKey::new([3u8; PUBLIC_KEY_LENGTH]), U512::from(30)),
+    ]
+    .into_iter()
+    .collect();
+    let cl_value = CLValue::from_t(balances).unwrap();
+
+    let (result, reads) =
+        query_cl_value(cl_value.clone(), &[&base16::encode_lower(&known.value())]);
+    assert_matches!(
+        result,
+        Ok(TrackingCopyQueryResult::Success(StoredValue::CLValue(value)))
+            if value == CLValue::from_t(U512::from(20)).unwrap()
+    );
+    assert_eq!(reads, 1);
+
+    let (result, _) = query_cl_value(cl_value.clone(), &[&base16::encode_lower(&unknown.value())]);
+    assert_matches!(result, Ok(TrackingCopyQueryResult::ValueNotFound(_)));
+
+    let (result, _) = query_cl_value(cl_value, &["not-hex"]);
+    assert_matches!(result, Ok(TrackingCopyQueryResult::ValueNotFound(_)));
+}
+
+#[test]
+fn query_nested_collections() {
+    let registry: BTreeMap<String, Vec<(u64, Option<String>)>> = vec![
+        (
+            "alice".to_string(),
+            vec![(1, None), (2, Some("two".to_string()))],
+        ),
+        ("bob".to_string(), vec![]),
+    ]
+    .into_iter()
+    .collect();
+    let cl_value = CLValue::from_t(registry).unwrap();
+
+    let (result, reads) = query_cl_value(cl_value.clone(), &["alice", "1"]);
+    assert_matches!(
+        result,
+        Ok(TrackingCopyQueryResult::Success(StoredValue::CLValue(value)))
+            if value == CLValue::from_t((2u64, Some("two".to_string()))).unwrap()
+    );
+    assert_eq!(reads, 1);
+
+    for missing_path in &[
+        &["alice", "2"][..],
+        &["bob", "0"],
+        &["carol"],
+        &["alice", "x"],
+    ] {
+        let (result, _) = query_cl_value(cl_value.clone(), missing_path);
+        assert_matches!(result, Ok(TrackingCopyQueryResult::ValueNotFound(_)));
+    }
+
+    // Neither tuples nor other values can be indexed into
+    let (result, _) = query_cl_value(cl_value, &["alice", "1", "0"]);
+    assert_matches!(result, Ok(TrackingCopyQueryResult::ValueNotFound(_)));
+}
+
+#[test]
+fn query_map_by_key() {
+    let account = Key::Account([1u8; 32]);
+    let contract = Key::Hash([2u8; 32]);
+    let names: BTreeMap<Key, String> = vec![
+        (account, "account".to_string()),
+        (contract, "contract".to_string()),
+    ]
+    .into_iter()
+    .collect();
+    let cl_value = CLValue::from_t(names).unwrap();
+
+    for (key, name) in &[(account, "account"), (contract, "contract")] {
+        let (result, _) = query_cl_value(cl_value.clone(), &[&key.as_string()]);
+        assert_matches!(
+            result,
+            Ok(TrackingCopyQueryResult::Success(StoredValue::CLValue(value)))
+                if value == CLValue::from_t(name.to_string()).unwrap()
+        );
+    }
+}
+
+proptest! {
+    #[test]
+    fn query_every_list_item_and_map_value(
+        items in vec(any::<u64>(), 0..50),
+        entries in prop::collection::btree_map(any::<i32>(), "\\PC*", 0..50),
+    ) {
+        let list = CLValue::from_t(items.clone()).unwrap();
+        for (index, item) in items.iter().enumerate() {
+            let (result, _) = query_cl_value(list.clone(), &[&index.to_string()]);
+            assert_matches!(
+                result,
+                Ok(TrackingCopyQueryResult::Success(StoredValue::CLValue(value)))
+                    if value == CLValue::from_t(*item).unwrap()
+            );
+        }
+        let (result, _) = query_cl_value(list, &[&items.len().to_string()]);
+        assert_matches!(result, Ok(TrackingCopyQueryResult::ValueNotFound(_)));
+
+        let map = CLValue::from_t(entries.clone()).unwrap();
+        for (key, value) in entries.iter() {
+            let (result, _) = query_cl_value(map.clone(), &[&key.to_string()]);
+            assert_matches!(
+                result,
+                Ok(TrackingCopyQueryResult::Success(StoredValue::CLValue(found)))
+                    if found == CLValue::from_t(value.clone()).unwrap()
+            );
+        }
+    }
+}
+
+const TOKEN_ACCOUNT_KEY: Key = Key::Account([1u8; 32]);
+const TOKEN_PACKAGE_HASH: [u8; 32] = [2u8; 32];
+const TOKEN_CONTRACT_V1_HASH: [u8; 32] = [3u8; 32];
+const TOKEN_CONTRACT_V2_HASH: [u8; 32] = [4u8; 32];
+const TOKEN_HOLDER: [u8; PUBLIC_KEY_LENGTH] = [5u8; PUBLIC_KEY_LENGTH];
+
+/// An account which holds a URef to the hash of a contract package with two versions, each of
+/// which names its own `version` value, and shares a `balances` namespace of local state.
+fn token_state() -> (InMemoryGlobalState, Blake2bHash, URef) {
+    let correlation_id = CorrelationId::new();
+    let token_uref = URef::new([6u8; 32], AccessRights::READ);
+
+    let contract = |version: u32| {
+        let version_key = Key::URef(URef::new([10 + version as u8; 32], AccessRights::READ));
+        let named_keys = iter::once(("version".to_string(), version_key)).collect();
+        let contract = Contract::new(vec![], named_keys, ProtocolVersion::V1_0_0);
+        let version_value = StoredValue::CLValue(CLValue::from_t(version).unwrap());
+        (contract, version_key, version_value)
+    };
+    let (contract_v1, version_v1_key, version_v1) = contract(1);
+    let (contract_v2, version_v2_key, version_v2) = contract(2);
+    let versions = vec![
+        (
+            1,
+            ContractHeader::new(TOKEN_CONTRACT_V1_HASH, EntryPoints::new(), true),
+        ),
+        (
+            2,
+            ContractHeader::new(TOKEN_CONTRACT_V2_HASH, EntryPoints::new(), true),
+        ),
+    ]
+    .into_iter()
+    .collect();
+    let package = ContractPackage::new(URef::new([7u8; 32], AccessRights::READ), versions);
+
+    let balances_seed = runtime_context::namespace_seed(TOKEN_PACKAGE_HASH, "balances");
+    let balance_key = Key::local(balances_seed, &TOKEN_HOLDER);
+
+    let account = Account::new(
+        [1u8; 32],
+        iter::once(("token_contract".to_string(), Key::URef(token_uref))).collect(),
+        PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
+        AssociatedKeys::new(PublicKey::new([1u8; 32]), Weight::new(1)),
+        Default::default(),
+    );
+
+    let (gs, root_hash) = InMemoryGlobalState::from_pairs(
+        correlation_id,
+        &[
+            (TOKEN_ACCOUNT_KEY, StoredValue::Account(account)),
+            (
+                Key::URef(token_uref),
+                StoredValue::CLValue(CLValue::from_t(Key::Hash(TOKEN_PACKAGE_HASH)).unwrap()),
+            ),
+            (
+                Key::Hash(TOKEN_PACKAGE_HASH),
+                StoredValue::ContractPackage(package),
+            ),
+            (
+                Key::Hash(TOKEN_CONTRACT_V1_HASH),
+                StoredValue::Contract(contract_v1),
+            ),
+            (
+                Key::Hash(TOKEN_CONTRACT_V2_HASH),
+                StoredValue::Contract(contract_v2),
+            ),
+            (version_v1_key, version_v1),
+            (version_v2_key, version_v2),
+            (
+                balance_key,
+                StoredValue::CLValue(CLValue::from_t(U512::from(100)).unwrap()),
+            ),
+        ],
+    )
+    .unwrap();
+    (gs, root_hash, token_uref)
+}
+
+fn query_token_state(path: &[&str]) -> (TrackingCopyQueryResult, Vec<QueryPathStep>) {
+    let correlation_id = CorrelationId::new();
+    let (gs, root_hash, _) = token_state();
+    let view = gs.checkout(root_hash).unwrap().unwrap();
+    let mut tc = TrackingCopy::new(view);
+    let path = path.iter().map(ToString::to_string).collect::<Vec<_>>();
+    tc.query_with_visited_path(correlation_id, TOKEN_ACCOUNT_KEY, &path)
+        .unwrap()
+}
+
+#[test]
+fn query_namespace_item_through_stored_key_and_package() {
+    let holder = base16::encode_lower(&TOKEN_HOLDER);
+    let (result, visited_path) = query_token_state(&["token_contract", "balances", &holder]);
+    assert_matches!(
+        result,
+        TrackingCopyQueryResult::Success(StoredValue::CLValue(value))
+            if value == CLValue::from_t(U512::from(100)).unwrap()
+    );
+
+    let (_, _, token_uref) = token_state();
+    let balances_seed = runtime_context::namespace_seed(TOKEN_PACKAGE_HASH, "balances");
+    assert_eq!(
+        visited_path,
+        vec![
+            QueryPathStep::Read(TOKEN_ACCOUNT_KEY),
+            QueryPathStep::Read(Key::URef(token_uref)),
+            QueryPathStep::Read(Key::Hash(TOKEN_PACKAGE_HASH)),
+            QueryPathStep::Read(Key::Hash(TOKEN_CONTRACT_V2_HASH)),
+            QueryPathStep::Namespace {
+                name: "balances".to_string(),
+                seed: balances_seed,
+            },
+            QueryPathStep::Read(Key::local(balances_seed, &TOKEN_HOLDER)),
+        ]
+    );
+
+    let (result, visited_path) = query_token_state(&["token_contract", "balances", "nobody"]);
+    assert_matches!(result, TrackingCopyQueryResult::ValueNotFound(_));
+    assert_eq!(visited_path.len(), 5);
+
+    // A namespace holds no value of its own
+    let (result, _) = query_token_state(&["token_contract", "balances"]);
+    assert_matches!(result, TrackingCopyQueryResult::ValueNotFound(_));
+}
+
+#[test]
+fn query_contract_package_versions() {
+    for (path, expected_version) in &[
+        (vec!["token_contract", "version"], 2u32),
+        (vec!["token_contract", "1", "version"], 1),
+        (vec!["token_contract", "2", "version"], 2),
+    ] {
+        let (result, _) = query_token_state(path);
+        assert_matches!(
+            result,
+            TrackingCopyQueryResult::Success(StoredValue::CLValue(value))
+                if value == CLValue::from_t(*expected_version).unwrap()
+        );
+    }
+
+    let (result, visited_path) = query_token_state(&["token_contract", "1"]);
+    assert_matches!(
+        result,
+        TrackingCopyQueryResult::Success(StoredValue::Contract(_))
+    );
+    assert_eq!(
+        visited_path.last(),
+        Some(&QueryPathStep::Read(Key::Hash(TOKEN_CONTRACT_V1_HASH)))
+    );
+
+    // Versions which don't exist are looked up in the current version instead
+    let (result, _) = query_token_state(&["token_contract", "3", "version"]);
+    assert_matches!(result, TrackingCopyQueryResult::ValueNotFound(_));
+}
+
+#[test]
+fn query_stops_at_cycle_of_stored_keys() {
+    let correlation_id = CorrelationId::new();
+    let first = Key::URef(URef::new([1u8; 32], AccessRights::READ));
+    let second = Key::URef(URef::new([2u8; 32], AccessRights::READ));
+    let (gs, root_hash) = InMemoryGlobalState::from_pairs(
+        correlation_id,
+        &[
+            (
+                first,
+                StoredValue::CLValue(CLValue::from_t(second).unwrap()),
+            ),
+            (
+                second,
+                StoredValue::CLValue(CLValue::from_t(first).unwrap()),
+            ),
+        ],
+    )
+    .unwrap();
+    let view = gs.checkout(root_hash).unwrap().unwrap();
+    let mut tc = TrackingCopy::new(view);
+
+    let result = tc.query(correlation_id, first, &["name".to_string()]);
+    assert_matches!(result, Ok(TrackingCopyQueryResult::ValueNotFound(_)));
+}
@@ -3,10 +3,13 @@ use std::{
     convert::{TryFrom, TryInto},
 };
 
-use engine_core::engine_state::deploy_item::DeployItem;
-use types::account::PublicKey;
+use engine_core::engine_state::deploy_item::{self, DeployItem, Signature};
+use types::account::{PublicKey, SignatureAlgorithm, TaggedPublicKey};
 
-use crate::engine_server::{ipc, mappings::MappingError};
+use crate::engine_server::{
+    ipc,
+    mappings::{MappingError, ParsingError},
+};
 
 impl TryFrom<ipc::DeployItem> for DeployItem {
     type Error = MappingError;
@@ -20,13 +23,15 @@ impl TryFrom<ipc::DeployItem> for DeployItem {
         let session = pb_deploy_item
             .take_session()
             .payload
-            .map(Into::into)
+            .map(TryInto::try_into)
+            .transpose()?
             .ok_or_else(|| MappingError::MissingPayload)?;
 
         let payment = pb_deploy_item
             .take_payment()
             .payload
-            .map(Into::into)
+            .map(TryInto::try_into)
+            .transpose()?
             .ok_or_else(|| MappingError::MissingPayload)?;
 
         let gas_price = pb_deploy_item.get_gas_price();
@@ -45,6 +50,27 @@ impl TryFrom<ipc::DeployItem> for DeployItem {
             MappingError::invalid_deploy_hash_length(pb_deploy_item.deploy_hash.len())
         })?;
 
+        let signatures = pb_deploy_item
+            .get_signatures()
+            .iter()
+            .map(|pb_signature| {
+                let raw_public_key = pb_signature.get_public_key();
+                let public_key = match pb_signature.get_sig_algorithm() {
+                    // Nodes which don't send the algorithm only send ed25519 signatures
+                    "" | "ed25519" => {
+                        TaggedPublicKey::new(SignatureAlgorithm::Ed25519, raw_public_key).ok()
+                    }
+                    "secp256k1" => deploy_item::parse_secp256k1_public_key(raw_public_key),
+                    other => {
+                        let message = format!("Unsupported signature algorithm: {}", other);
+                        return Err(MappingError::from(ParsingError::from(message)));
+                    }
+                }
+                .ok_or_else(|| MappingError::invalid_public_key_length(raw_public_key.len()))?;
+                Ok((public_key, pb_signature.get_signature().to_vec()))
+            })
+            .collect::<Result<Vec<(TaggedPublicKey, Signature)>, Self::Error>>()?;
+
         Ok(DeployItem::new(
             address,
             session,
@@ -52,6 +78,7 @@ impl TryFrom<ipc::DeployItem> for DeployItem {
             gas_price,
             authorization_keys,
             deploy_hash,
+            signatures,
         ))
     }
 }
@@ -70,7 +97,20 @@ impl From<DeployItem> for ipc::DeployItem {
                 .map(|key| key.value().to_vec())
                 .collect(),
         );
-        result.set_deploy_hash(deploy_item.deploy_hash.to_vec());
+        result.set_deploy_hash(deploy_item.deploy_hash.as_ref().to_vec());
+        result.set_signatures(
+            deploy_item
+                .signatures
+                .into_iter()
+                .map(|(public_key, signature)| {
+                    let mut pb_signature = ipc::DeploySignature::new();
+                    pb_signature.set_public_key(public_key.as_bytes().to_vec());
+                    pb_signature.set_signature(signature);
+                    pb_signature.set_sig_algorithm(public_key.algorithm().to_string());
+                    pb_signature
+                })
+                .collect(),
+        );
         result
     }
 }
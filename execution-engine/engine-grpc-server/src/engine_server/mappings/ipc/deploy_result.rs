@@ -12,12 +12,22 @@ use crate::engine_server::ipc::{DeployError_OutOfGasError, DeployResult};
 impl From<ExecutionResult> for DeployResult {
     fn from(execution_result: ExecutionResult) -> DeployResult {
         match execution_result {
-            ExecutionResult::Success { effect, cost } => detail::execution_success(effect, cost),
+            ExecutionResult::Success {
+                effect,
+                cost,
+                effects_by_phase,
+                ..
+            } => detail::with_effects_by_phase(
+                detail::execution_success(effect, cost),
+                effects_by_phase,
+            ),
             ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
-            } => (error, effect, cost).into(),
+                effects_by_phase,
+                ..
+            } => detail::with_effects_by_phase((error, effect, cost).into(), effects_by_phase),
         }
     }
 }
@@ -32,12 +42,23 @@ impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
             | error @ EngineStateError::InvalidPublicKeyLength { .. }
             | error @ EngineStateError::InvalidProtocolVersion { .. }
             | error @ EngineStateError::InvalidUpgradeConfig
+            | error @ EngineStateError::MissingInstaller(_)
             | error @ EngineStateError::WasmPreprocessingError(_)
             | error @ EngineStateError::WasmSerializationError(_)
-            | error @ EngineStateError::ExecError(ExecutionError::DeploymentAuthorizationFailure)
-            | error @ EngineStateError::AuthorizationError => {
+            | error @ EngineStateError::InvalidDeploySignature
+            | error @ EngineStateError::NoDeploySignatures
+            | error @ EngineStateError::DeployRejected(_)
+            | error @ EngineStateError::DuplicateDeployHash(_)
+            | error @ EngineStateError::BlockGasLimitExceeded { .. }
+            | error @ EngineStateError::InvalidDeployItemVariant(_) => {
                 detail::precondition_error(error.to_string())
             }
+            error @ EngineStateError::AccountNotFound(_)
+            | error @ EngineStateError::NoAuthorizationKeys
+            | error @ EngineStateError::UnassociatedKeys(_)
+            | error @ EngineStateError::DeploymentThresholdNotMet { .. } => {
+                detail::authorization_failure(error)
+            }
             EngineStateError::StorageError(storage_error) => {
                 detail::execution_error(storage_error, effect, cost)
             }
@@ -48,7 +69,8 @@ impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
             | error @ EngineStateError::DeployError
             | error @ EngineStateError::FinalizationError
             | error @ EngineStateError::SerializationError(_)
-            | error @ EngineStateError::MintError(_) => {
+            | error @ EngineStateError::MintError(_)
+            | error @ EngineStateError::CorruptCompactedStorage { .. } => {
                 detail::execution_error(error, effect, cost)
             }
             EngineStateError::ExecError(exec_error) => (exec_error, effect, cost).into(),
@@ -95,7 +117,9 @@ impl From<(ExecutionError, ExecutionEffect, Gas)> for DeployResult {
 }
 
 mod detail {
-    use super::{DeployError_OutOfGasError, DeployResult, ExecutionEffect, Gas};
+    use engine_core::engine_state::execution_result::EffectsByPhase;
+
+    use super::{DeployError_OutOfGasError, DeployResult, EngineStateError, ExecutionEffect, Gas};
 
     /// Constructs an instance of `DeployResult` with no error set, i.e. a successful
     /// result.
@@ -111,6 +135,42 @@ mod detail {
         pb_deploy_result
     }
 
+    /// Constructs an instance of `DeployResult` with an error set to
+    /// `ProtobufPreconditionFailure` which explains why the account could not authorize the deploy.
+    pub(super) fn authorization_failure(error: EngineStateError) -> DeployResult {
+        let mut pb_deploy_result = precondition_error(error.to_string());
+        let pb_authorization_failure = pb_deploy_result
+            .mut_precondition_failure()
+            .mut_authorization_failure();
+        match error {
+            EngineStateError::AccountNotFound(public_key) => pb_authorization_failure
+                .mut_account_not_found()
+                .set_public_key(public_key.value().to_vec()),
+            EngineStateError::NoAuthorizationKeys => {
+                pb_authorization_failure.mut_no_authorization_keys();
+            }
+            EngineStateError::UnassociatedKeys(public_keys) => pb_authorization_failure
+                .mut_unassociated_keys()
+                .set_public_keys(
+                    public_keys
+                        .into_iter()
+                        .map(|public_key| public_key.value().to_vec())
+                        .collect(),
+                ),
+            EngineStateError::DeploymentThresholdNotMet {
+                total_weight,
+                threshold,
+            } => {
+                let pb_threshold_not_met =
+                    pb_authorization_failure.mut_deployment_threshold_not_met();
+                pb_threshold_not_met.set_total_weight(total_weight.into());
+                pb_threshold_not_met.set_threshold(threshold.into());
+            }
+            other => panic!("{:?} is not an authorization failure", other),
+        }
+        pb_deploy_result
+    }
+
     /// Constructs an instance of `DeployResult` with an error set to
     /// `ProtobufExecutionError`.
     pub(super) fn execution_error<T: ToString>(
@@ -127,6 +187,22 @@ mod detail {
         deploy_result(DeployErrorType::OutOfGas, effect, cost)
     }
 
+    /// Adds the effects of each phase to the execution result of `pb_deploy_result`, in debug
+    /// builds only.
+    pub(super) fn with_effects_by_phase(
+        mut pb_deploy_result: DeployResult,
+        effects_by_phase: Option<EffectsByPhase>,
+    ) -> DeployResult {
+        if cfg!(debug_assertions) && pb_deploy_result.has_execution_result() {
+            if let Some(effects_by_phase) = effects_by_phase {
+                pb_deploy_result
+                    .mut_execution_result()
+                    .set_effects_by_phase(effects_by_phase.into());
+            }
+        }
+        pb_deploy_result
+    }
+
     enum DeployErrorType {
         None,
         OutOfGas,
@@ -166,7 +242,9 @@ mod tests {
     use std::convert::TryInto;
 
     use engine_shared::{additive_map::AdditiveMap, transform::Transform};
-    use types::{bytesrepr::Error as BytesReprError, AccessRights, Key, URef, U512};
+    use types::{
+        account::PublicKey, bytesrepr::Error as BytesReprError, AccessRights, Key, URef, U512,
+    };
 
     use super::*;
 
@@ -185,6 +263,10 @@ mod tests {
         let execution_result = ExecutionResult::Success {
             effect: execution_effect,
             cost,
+            read_stats: None,
+            effects_by_phase: None,
+            stdout: Vec::new(),
+            host_function_profile: None,
         };
         let mut ipc_deploy_result: DeployResult = execution_result.into();
         assert!(ipc_deploy_result.has_execution_result());
@@ -210,6 +292,10 @@ mod tests {
             error: error.into(),
             effect: Default::default(),
             cost: expected_cost,
+            read_stats: None,
+            effects_by_phase: None,
+            stdout: Vec::new(),
+            host_function_profile: None,
         };
         let mut ipc_deploy_result: DeployResult = execution_failure.into();
         assert!(ipc_deploy_result.has_execution_result());
@@ -244,6 +330,43 @@ mod tests {
         assert_eq!(test_cost(cost, forged_ref_error), cost);
     }
 
+    #[test]
+    fn authorization_failures_map_to_precondition_failures() {
+        let unassociated_key = PublicKey::new([1u8; 32]);
+        let errors = vec![
+            EngineStateError::AccountNotFound(unassociated_key),
+            EngineStateError::NoAuthorizationKeys,
+            EngineStateError::UnassociatedKeys(vec![unassociated_key]),
+            EngineStateError::DeploymentThresholdNotMet {
+                total_weight: 1,
+                threshold: 2,
+            },
+        ];
+        for error in errors {
+            let message = error.to_string();
+            let mut ipc_result: DeployResult = (error, Default::default(), Gas::default()).into();
+            assert!(ipc_result.has_precondition_failure());
+            let mut precondition_failure = ipc_result.take_precondition_failure();
+            assert_eq!(precondition_failure.get_message(), message);
+            assert!(precondition_failure.has_authorization_failure());
+
+            let authorization_failure = precondition_failure.take_authorization_failure();
+            if authorization_failure.has_unassociated_keys() {
+                assert_eq!(
+                    authorization_failure
+                        .get_unassociated_keys()
+                        .get_public_keys(),
+                    &[unassociated_key.value().to_vec()]
+                );
+            }
+            if authorization_failure.has_deployment_threshold_not_met() {
+                let threshold_not_met = authorization_failure.get_deployment_threshold_not_met();
+                assert_eq!(threshold_not_met.get_total_weight(), 1);
+                assert_eq!(threshold_not_met.get_threshold(), 2);
+            }
+        }
+    }
+
     #[test]
     fn revert_error_maps_to_execution_error() {
         const REVERT: u32 = 10;
@@ -253,6 +376,10 @@ mod tests {
             error: EngineStateError::ExecError(revert_error),
             effect: Default::default(),
             cost: Gas::new(amount),
+            read_stats: None,
+            effects_by_phase: None,
+            stdout: Vec::new(),
+            host_function_profile: None,
         };
         let mut ipc_result: DeployResult = exec_result.into();
         assert!(
@@ -1,10 +1,47 @@
-use engine_core::engine_state::executable_deploy_item::ExecutableDeployItem;
+use std::convert::{TryFrom, TryInto};
 
-use crate::engine_server::ipc::{DeployPayload, DeployPayload_oneof_payload};
+use engine_core::engine_state::executable_deploy_item::{ExecutableDeployItem, ExecutionContext};
+use types::ContractVersion;
 
-impl From<DeployPayload_oneof_payload> for ExecutableDeployItem {
-    fn from(pb_deploy_payload: DeployPayload_oneof_payload) -> Self {
-        match pb_deploy_payload {
+use crate::engine_server::{
+    ipc::{self, DeployPayload, DeployPayload_oneof_payload},
+    mappings::MappingError,
+};
+
+/// Version 0 is never assigned to a contract, so it stands for the highest enabled version.
+const PB_CURRENT_VERSION: u32 = 0;
+
+impl From<ipc::ExecutionContext> for ExecutionContext {
+    fn from(pb_execution_context: ipc::ExecutionContext) -> Self {
+        match pb_execution_context {
+            ipc::ExecutionContext::ACCOUNT => ExecutionContext::Account,
+            ipc::ExecutionContext::CONTRACT => ExecutionContext::Contract,
+        }
+    }
+}
+
+impl From<ExecutionContext> for ipc::ExecutionContext {
+    fn from(execution_context: ExecutionContext) -> Self {
+        match execution_context {
+            ExecutionContext::Account => ipc::ExecutionContext::ACCOUNT,
+            ExecutionContext::Contract => ipc::ExecutionContext::CONTRACT,
+        }
+    }
+}
+
+fn version_from_pb(pb_version: u32) -> Option<ContractVersion> {
+    if pb_version == PB_CURRENT_VERSION {
+        None
+    } else {
+        Some(pb_version)
+    }
+}
+
+impl TryFrom<DeployPayload_oneof_payload> for ExecutableDeployItem {
+    type Error = MappingError;
+
+    fn try_from(pb_deploy_payload: DeployPayload_oneof_payload) -> Result<Self, Self::Error> {
+        let deploy_item = match pb_deploy_payload {
             DeployPayload_oneof_payload::deploy_code(pb_deploy_code) => {
                 ExecutableDeployItem::ModuleBytes {
                     module_bytes: pb_deploy_code.code,
@@ -29,7 +66,33 @@ impl From<DeployPayload_oneof_payload> for ExecutableDeployItem {
                     args: pb_stored_contract_uref.args,
                 }
             }
-        }
+            DeployPayload_oneof_payload::stored_versioned_contract_hash(pb_stored_contract) => {
+                ExecutableDeployItem::StoredVersionedContractByHash {
+                    hash: pb_stored_contract.hash,
+                    version: version_from_pb(pb_stored_contract.version),
+                    entry_point: pb_stored_contract.entry_point,
+                    args: pb_stored_contract.args,
+                    context: pb_stored_contract.context.into(),
+                }
+            }
+            DeployPayload_oneof_payload::stored_versioned_contract_name(pb_stored_contract) => {
+                ExecutableDeployItem::StoredVersionedContractByName {
+                    name: pb_stored_contract.stored_contract_name,
+                    version: version_from_pb(pb_stored_contract.version),
+                    entry_point: pb_stored_contract.entry_point,
+                    args: pb_stored_contract.args,
+                    context: pb_stored_contract.context.into(),
+                }
+            }
+            DeployPayload_oneof_payload::transfer(mut pb_transfer) => {
+                let target = pb_transfer.get_target().try_into().map_err(|_| {
+                    MappingError::invalid_public_key_length(pb_transfer.target.len())
+                })?;
+                let amount = pb_transfer.take_amount().try_into()?;
+                ExecutableDeployItem::Transfer { target, amount }
+            }
+        };
+        Ok(deploy_item)
     }
 }
 
@@ -57,6 +120,39 @@ impl From<ExecutableDeployItem> for DeployPayload {
                 inner.set_uref(uref);
                 inner.set_args(args);
             }
+            ExecutableDeployItem::StoredVersionedContractByHash {
+                hash,
+                version,
+                entry_point,
+                args,
+                context,
+            } => {
+                let inner = result.mut_stored_versioned_contract_hash();
+                inner.set_hash(hash);
+                inner.set_version(version.unwrap_or(PB_CURRENT_VERSION));
+                inner.set_entry_point(entry_point);
+                inner.set_args(args);
+                inner.set_context(context.into());
+            }
+            ExecutableDeployItem::StoredVersionedContractByName {
+                name,
+                version,
+                entry_point,
+                args,
+                context,
+            } => {
+                let inner = result.mut_stored_versioned_contract_name();
+                inner.set_stored_contract_name(name);
+                inner.set_version(version.unwrap_or(PB_CURRENT_VERSION));
+                inner.set_entry_point(entry_point);
+                inner.set_args(args);
+                inner.set_context(context.into());
+            }
+            ExecutableDeployItem::Transfer { target, amount } => {
+                let inner = result.mut_transfer();
+                inner.set_target(target.to_vec());
+                inner.set_amount(amount.into());
+            }
         }
         result
     }
@@ -1,4 +1,6 @@
-use engine_core::engine_state::{execution_effect::ExecutionEffect, op::Op};
+use engine_core::engine_state::{
+    execution_effect::ExecutionEffect, execution_result::EffectsByPhase, op::Op,
+};
 use types::Key;
 
 use crate::engine_server::{
@@ -40,3 +42,13 @@ impl From<ExecutionEffect> for ipc::ExecutionEffect {
         pb_execution_effect
     }
 }
+
+impl From<EffectsByPhase> for ipc::EffectsByPhase {
+    fn from(effects_by_phase: EffectsByPhase) -> ipc::EffectsByPhase {
+        let mut pb_effects_by_phase = ipc::EffectsByPhase::new();
+        pb_effects_by_phase.set_payment(effects_by_phase.payment().clone().into());
+        pb_effects_by_phase.set_session(effects_by_phase.session().clone().into());
+        pb_effects_by_phase.set_finalize(effects_by_phase.finalize().clone().into());
+        pb_effects_by_phase
+    }
+}
@@ -1,9 +1,46 @@
 use std::convert::{TryFrom, TryInto};
 
 use engine_core::engine_state::genesis::GenesisAccount;
-use engine_shared::motes::Motes;
+use engine_shared::{account::VestingSchedule, motes::Motes};
+use types::{
+    account::{PublicKey, TaggedPublicKey, PUBLIC_KEY_LENGTH},
+    bytesrepr, U512,
+};
 
-use crate::engine_server::{ipc::ChainSpec_GenesisAccount, mappings::MappingError};
+use crate::engine_server::{
+    ipc::{ChainSpec_GenesisAccount, ChainSpec_GenesisAccount_VestingSchedule},
+    mappings::MappingError,
+    state::BigInt,
+};
+
+const U512_BIT_WIDTH: u32 = 512;
+
+/// Parses an amount of motes, accepting only the canonical decimal form produced by
+/// `From<U512> for BigInt`: ASCII digits without sign, whitespace or leading zeros, and a bit
+/// width of 512.
+fn parse_motes(pb_big_int: &BigInt, field: &str) -> Result<Motes, String> {
+    let value = pb_big_int.get_value();
+    if pb_big_int.get_bit_width() != U512_BIT_WIDTH {
+        return Err(format!(
+            "{} has a bit width of {} instead of {}",
+            field,
+            pb_big_int.get_bit_width(),
+            U512_BIT_WIDTH
+        ));
+    }
+    if value.is_empty() {
+        return Err(format!("{} is empty", field));
+    }
+    if !value.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(format!("{} {:?} is not a decimal number", field, value));
+    }
+    if value.len() > 1 && value.starts_with('0') {
+        return Err(format!("{} {:?} has leading zeros", field, value));
+    }
+    U512::from_dec_str(value)
+        .map(Motes::new)
+        .map_err(|_| format!("{} {} does not fit in 512 bits", field, value))
+}
 
 impl From<GenesisAccount> for ChainSpec_GenesisAccount {
     fn from(genesis_account: GenesisAccount) -> Self {
@@ -12,6 +49,13 @@ impl From<GenesisAccount> for ChainSpec_GenesisAccount {
         pb_genesis_account.set_public_key(genesis_account.public_key().to_vec());
         pb_genesis_account.set_balance(genesis_account.balance().value().into());
         pb_genesis_account.set_bonded_amount(genesis_account.bonded_amount().value().into());
+        if let Some(vesting_schedule) = genesis_account.vesting_schedule() {
+            let mut pb_vesting_schedule = ChainSpec_GenesisAccount_VestingSchedule::new();
+            pb_vesting_schedule.set_cliff_timestamp(vesting_schedule.cliff_timestamp());
+            pb_vesting_schedule.set_release_duration(vesting_schedule.release_duration());
+            pb_vesting_schedule.set_locked_amount(vesting_schedule.locked_amount().into());
+            pb_genesis_account.set_vesting_schedule(pb_vesting_schedule);
+        }
 
         pb_genesis_account
     }
@@ -22,21 +66,52 @@ impl TryFrom<ChainSpec_GenesisAccount> for GenesisAccount {
 
     fn try_from(mut pb_genesis_account: ChainSpec_GenesisAccount) -> Result<Self, Self::Error> {
         // TODO: our TryFromSliceForPublicKeyError should convey length info
-        let public_key = pb_genesis_account
-            .get_public_key()
-            .try_into()
-            .map_err(|_| {
-                MappingError::invalid_public_key_length(pb_genesis_account.public_key.len())
-            })?;
-        let balance = pb_genesis_account
-            .take_balance()
-            .try_into()
-            .map(Motes::new)?;
-        let bonded_amount = pb_genesis_account
-            .take_bonded_amount()
-            .try_into()
-            .map(Motes::new)?;
-        Ok(GenesisAccount::new(public_key, balance, bonded_amount))
+        let public_key_length = pb_genesis_account.public_key.len();
+        let public_key: PublicKey = if public_key_length == PUBLIC_KEY_LENGTH {
+            pb_genesis_account
+                .get_public_key()
+                .try_into()
+                .map_err(|_| MappingError::invalid_public_key_length(public_key_length))?
+        } else {
+            // Accounts of algorithm-tagged keys are created at their derived address
+            bytesrepr::deserialize::<TaggedPublicKey>(pb_genesis_account.take_public_key())
+                .map(|tagged_public_key| tagged_public_key.to_public_key())
+                .map_err(|_| MappingError::invalid_public_key_length(public_key_length))?
+        };
+        let invalid = |reason: String| MappingError::InvalidGenesisAccount {
+            account: public_key.to_string(),
+            reason,
+        };
+        let balance = parse_motes(pb_genesis_account.get_balance(), "balance").map_err(invalid)?;
+        let bonded_amount = parse_motes(pb_genesis_account.get_bonded_amount(), "bonded amount")
+            .map_err(invalid)?;
+        if bonded_amount > balance {
+            return Err(invalid(format!(
+                "bonded amount {} exceeds balance {}",
+                bonded_amount.value(),
+                balance.value()
+            )));
+        }
+        let genesis_account = GenesisAccount::new(public_key, balance, bonded_amount);
+        if !pb_genesis_account.has_vesting_schedule() {
+            return Ok(genesis_account);
+        }
+        let pb_vesting_schedule = pb_genesis_account.get_vesting_schedule();
+        let locked_amount = parse_motes(pb_vesting_schedule.get_locked_amount(), "locked amount")
+            .map_err(invalid)?;
+        if locked_amount > balance {
+            return Err(invalid(format!(
+                "locked amount {} exceeds balance {}",
+                locked_amount.value(),
+                balance.value()
+            )));
+        }
+        let vesting_schedule = VestingSchedule::new(
+            pb_vesting_schedule.get_cliff_timestamp(),
+            pb_vesting_schedule.get_release_duration(),
+            locked_amount.value(),
+        );
+        Ok(genesis_account.with_vesting_schedule(vesting_schedule))
     }
 }
 
@@ -44,9 +119,127 @@ impl TryFrom<ChainSpec_GenesisAccount> for GenesisAccount {
 mod tests {
     use rand;
 
+    use types::bytesrepr::ToBytes;
+
     use super::*;
     use crate::engine_server::mappings::test_utils;
 
+    #[test]
+    fn should_map_tagged_public_keys_to_account_addresses() {
+        let tagged_public_keys = vec![
+            TaggedPublicKey::ed25519([1; 32]),
+            TaggedPublicKey::secp256k1(&[2; 33]).expect("should create public key"),
+        ];
+        for tagged_public_key in tagged_public_keys {
+            let mut pb_genesis_account = ChainSpec_GenesisAccount::new();
+            pb_genesis_account.set_public_key(
+                tagged_public_key
+                    .to_bytes()
+                    .expect("should serialize public key"),
+            );
+            let genesis_account =
+                GenesisAccount::try_from(pb_genesis_account).expect("should map genesis account");
+            assert_eq!(
+                genesis_account.public_key(),
+                tagged_public_key.to_public_key()
+            );
+        }
+    }
+
+    fn to_pb_big_int(value: &str) -> BigInt {
+        let mut pb_big_int = BigInt::new();
+        pb_big_int.set_value(value.to_string());
+        pb_big_int.set_bit_width(U512_BIT_WIDTH);
+        pb_big_int
+    }
+
+    fn pb_genesis_account(balance: &str, bonded_amount: &str) -> ChainSpec_GenesisAccount {
+        let mut pb_genesis_account = ChainSpec_GenesisAccount::new();
+        pb_genesis_account.set_public_key(vec![1; PUBLIC_KEY_LENGTH]);
+        pb_genesis_account.set_balance(to_pb_big_int(balance));
+        pb_genesis_account.set_bonded_amount(to_pb_big_int(bonded_amount));
+        pb_genesis_account
+    }
+
+    fn expect_invalid(pb_genesis_account: ChainSpec_GenesisAccount, expected_reason: &str) {
+        match GenesisAccount::try_from(pb_genesis_account) {
+            Err(MappingError::InvalidGenesisAccount { account, reason }) => {
+                assert_eq!(account, PublicKey::new([1; PUBLIC_KEY_LENGTH]).to_string());
+                assert!(
+                    reason.contains(expected_reason),
+                    "{:?} should contain {:?}",
+                    reason,
+                    expected_reason
+                );
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_map_valid_amounts() {
+        let genesis_account =
+            GenesisAccount::try_from(pb_genesis_account("1000", "1000")).expect("should map");
+        assert_eq!(genesis_account.balance(), Motes::new(U512::from(1000)));
+        assert_eq!(
+            genesis_account.bonded_amount(),
+            Motes::new(U512::from(1000))
+        );
+
+        let genesis_account =
+            GenesisAccount::try_from(pb_genesis_account("0", "0")).expect("should map");
+        assert_eq!(genesis_account.balance(), Motes::new(U512::zero()));
+    }
+
+    #[test]
+    fn should_reject_malformed_amounts() {
+        expect_invalid(pb_genesis_account("", "0"), "balance is empty");
+        expect_invalid(pb_genesis_account("-1", "0"), "is not a decimal number");
+        expect_invalid(pb_genesis_account(" 1", "0"), "is not a decimal number");
+        expect_invalid(pb_genesis_account("1.5", "0"), "is not a decimal number");
+        expect_invalid(pb_genesis_account("01", "0"), "has leading zeros");
+        expect_invalid(pb_genesis_account("10", "1e3"), "bonded amount");
+
+        let too_large = format!("{}0", U512::max_value());
+        expect_invalid(
+            pb_genesis_account(&too_large, "0"),
+            "does not fit in 512 bits",
+        );
+
+        let mut wrong_width = pb_genesis_account("1", "0");
+        wrong_width.mut_balance().set_bit_width(256);
+        expect_invalid(wrong_width, "bit width of 256");
+    }
+
+    #[test]
+    fn should_reject_bonded_amount_exceeding_balance() {
+        expect_invalid(
+            pb_genesis_account("10", "11"),
+            "bonded amount 11 exceeds balance 10",
+        );
+    }
+
+    #[test]
+    fn should_map_vesting_schedule() {
+        let mut pb_vesting_schedule = ChainSpec_GenesisAccount_VestingSchedule::new();
+        pb_vesting_schedule.set_cliff_timestamp(1_000);
+        pb_vesting_schedule.set_release_duration(500);
+        pb_vesting_schedule.set_locked_amount(to_pb_big_int("10"));
+
+        let mut vesting_account = pb_genesis_account("10", "0");
+        vesting_account.set_vesting_schedule(pb_vesting_schedule.clone());
+        let genesis_account = GenesisAccount::try_from(vesting_account).expect("should map");
+        assert_eq!(
+            genesis_account.vesting_schedule(),
+            Some(VestingSchedule::new(1_000, 500, U512::from(10)))
+        );
+
+        pb_vesting_schedule.set_locked_amount(to_pb_big_int("11"));
+        let mut overlocked_account = pb_genesis_account("10", "0");
+        overlocked_account.set_vesting_schedule(pb_vesting_schedule);
+        expect_invalid(overlocked_account, "locked amount 11 exceeds balance 10");
+    }
+
     #[test]
     fn round_trip() {
         let genesis_account = rand::random();
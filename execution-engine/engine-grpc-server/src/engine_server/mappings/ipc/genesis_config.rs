@@ -1,10 +1,13 @@
-use std::convert::{TryFrom, TryInto};
+use std::{
+    collections::BTreeSet,
+    convert::{TryFrom, TryInto},
+};
 
 use engine_core::engine_state::genesis::{GenesisAccount, GenesisConfig};
 
 use crate::engine_server::{
     ipc::{ChainSpec_GenesisAccount, ChainSpec_GenesisConfig},
-    mappings::MappingError,
+    mappings::{MappingError, ParsingError},
 };
 
 impl From<GenesisConfig> for ChainSpec_GenesisConfig {
@@ -29,6 +32,8 @@ impl From<GenesisConfig> for ChainSpec_GenesisConfig {
         pb_genesis_config
             .mut_costs()
             .set_wasm(genesis_config.wasm_costs().into());
+        pb_genesis_config.set_unbonding_delay_millis(genesis_config.unbonding_delay());
+        pb_genesis_config.set_slash_percentage(genesis_config.slash_percentage().into());
         pb_genesis_config
     }
 }
@@ -45,7 +50,23 @@ impl TryFrom<ChainSpec_GenesisConfig> for GenesisConfig {
             .into_iter()
             .map(TryInto::try_into)
             .collect::<Result<Vec<GenesisAccount>, Self::Error>>()?;
+        let mut public_keys = BTreeSet::new();
+        for account in &accounts {
+            if !public_keys.insert(account.public_key()) {
+                return Err(MappingError::DuplicateGenesisAccount(
+                    account.public_key().to_string(),
+                ));
+            }
+        }
         let wasm_costs = pb_genesis_config.take_costs().take_wasm().into();
+        let unbonding_delay = pb_genesis_config.get_unbonding_delay_millis();
+        let slash_percentage =
+            u8::try_from(pb_genesis_config.get_slash_percentage()).map_err(|_| {
+                ParsingError(format!(
+                    "Invalid slash percentage: {}",
+                    pb_genesis_config.get_slash_percentage()
+                ))
+            })?;
         let mint_initializer_bytes = pb_genesis_config.mint_installer;
         let proof_of_stake_initializer_bytes = pb_genesis_config.pos_installer;
         Ok(GenesisConfig::new(
@@ -56,6 +77,8 @@ impl TryFrom<ChainSpec_GenesisConfig> for GenesisConfig {
             proof_of_stake_initializer_bytes,
             accounts,
             wasm_costs,
+            unbonding_delay,
+            slash_percentage,
         ))
     }
 }
@@ -72,4 +95,17 @@ mod tests {
         let genesis_config = rand::random();
         test_utils::protobuf_round_trip::<GenesisConfig, ChainSpec_GenesisConfig>(genesis_config);
     }
+
+    #[test]
+    fn should_reject_duplicate_accounts() {
+        let genesis_config: GenesisConfig = rand::random();
+        let mut pb_genesis_config = ChainSpec_GenesisConfig::from(genesis_config);
+        let duplicate = pb_genesis_config.get_accounts()[0].clone();
+        pb_genesis_config.mut_accounts().push(duplicate);
+
+        match GenesisConfig::try_from(pb_genesis_config) {
+            Err(MappingError::DuplicateGenesisAccount(_)) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
 }
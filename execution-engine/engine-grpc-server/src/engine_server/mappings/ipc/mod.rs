@@ -9,6 +9,10 @@ mod execute_request;
 mod execution_effect;
 mod genesis_account;
 mod genesis_config;
+mod module_cache_stats;
+mod protocol_version_info;
+mod query_path_step;
 mod query_request;
 mod upgrade_request;
+mod upgrade_result;
 mod wasm_costs;
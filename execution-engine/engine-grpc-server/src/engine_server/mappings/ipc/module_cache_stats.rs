@@ -0,0 +1,15 @@
+use engine_core::engine_state::module_cache::ModuleCacheStats;
+
+use crate::engine_server::ipc::WarmUpResponse_ModuleCacheStats;
+
+impl From<ModuleCacheStats> for WarmUpResponse_ModuleCacheStats {
+    fn from(stats: ModuleCacheStats) -> Self {
+        let mut pb_stats = WarmUpResponse_ModuleCacheStats::new();
+        pb_stats.set_entries(stats.entries as u64);
+        pb_stats.set_capacity(stats.capacity as u64);
+        pb_stats.set_hits(stats.hits);
+        pb_stats.set_misses(stats.misses);
+        pb_stats.set_bytes(stats.bytes as u64);
+        pb_stats
+    }
+}
@@ -0,0 +1,25 @@
+use engine_core::engine_state::protocol_version_info::ProtocolVersionInfo;
+
+use crate::engine_server::ipc::{self, ProtocolVersionInfo_SystemContract};
+
+impl From<ProtocolVersionInfo> for ipc::ProtocolVersionInfo {
+    fn from(protocol_version_info: ProtocolVersionInfo) -> Self {
+        let mut pb_protocol_version_info = ipc::ProtocolVersionInfo::new();
+        pb_protocol_version_info
+            .set_protocol_version(protocol_version_info.protocol_version().into());
+        pb_protocol_version_info
+            .set_wasm_costs_digest(protocol_version_info.wasm_costs_digest().to_vec());
+        let pb_system_contracts: Vec<ProtocolVersionInfo_SystemContract> = protocol_version_info
+            .system_contracts()
+            .iter()
+            .map(|(name, uref)| {
+                let mut pb_system_contract = ProtocolVersionInfo_SystemContract::new();
+                pb_system_contract.set_name(name.clone());
+                pb_system_contract.set_uref((*uref).into());
+                pb_system_contract
+            })
+            .collect();
+        pb_protocol_version_info.set_system_contracts(pb_system_contracts.into());
+        pb_protocol_version_info
+    }
+}
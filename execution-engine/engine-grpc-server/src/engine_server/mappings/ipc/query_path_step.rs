@@ -0,0 +1,20 @@
+use engine_core::engine_state::query::QueryPathStep;
+
+use crate::engine_server::ipc::{self, QueryPathStep_Namespace};
+
+impl From<QueryPathStep> for ipc::QueryPathStep {
+    fn from(query_path_step: QueryPathStep) -> Self {
+        let mut pb_query_path_step = ipc::QueryPathStep::new();
+        match query_path_step {
+            QueryPathStep::Read(key) => pb_query_path_step.set_read(key.into()),
+            QueryPathStep::Index(segment) => pb_query_path_step.set_index(segment),
+            QueryPathStep::Namespace { name, seed } => {
+                let mut pb_namespace = QueryPathStep_Namespace::new();
+                pb_namespace.set_name(name);
+                pb_namespace.set_seed(seed.to_vec());
+                pb_query_path_step.set_namespace(pb_namespace);
+            }
+        }
+        pb_query_path_step
+    }
+}
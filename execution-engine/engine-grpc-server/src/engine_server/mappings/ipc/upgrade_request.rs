@@ -1,9 +1,203 @@
-use std::convert::{TryFrom, TryInto};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::{TryFrom, TryInto},
+};
 
 use engine_core::engine_state::upgrade::UpgradeConfig;
-use types::ProtocolVersion;
+use engine_shared::gas::Gas;
+use engine_storage::protocol_data::{ChainParameters, Sponsorship};
+use engine_wasm_prep::{
+    denied_host_functions::DeniedHostFunctions, gas_metering::GasMetering,
+    host_function_costs::HostFunctionCosts,
+};
+use types::{ProtocolVersion, URef, U512};
 
-use crate::engine_server::{ipc::UpgradeRequest, mappings::MappingError};
+use crate::engine_server::{
+    ipc::{
+        ChainSpec_ChainParameters, ChainSpec_CostTable_HostFunctionCosts,
+        ChainSpec_DeniedHostFunctions, ChainSpec_GasMetering, ChainSpec_Sponsorships,
+        ChainSpec_Sponsorships_Sponsorship, ChainSpec_UpgradePoint, DeployCode, UpgradeRequest,
+    },
+    mappings::{self, MappingError, ParsingError},
+};
+
+impl From<&BTreeMap<[u8; 32], Sponsorship>> for ChainSpec_Sponsorships {
+    fn from(sponsorships: &BTreeMap<[u8; 32], Sponsorship>) -> Self {
+        let mut pb_sponsorships = ChainSpec_Sponsorships::new();
+        for (contract_hash, sponsorship) in sponsorships {
+            let mut pb_sponsorship = ChainSpec_Sponsorships_Sponsorship::new();
+            pb_sponsorship.set_contract_hash(contract_hash.to_vec());
+            pb_sponsorship.set_allowance(sponsorship.allowance().value().into());
+            pb_sponsorship.set_purse(sponsorship.purse().into());
+            pb_sponsorships.mut_sponsorships().push(pb_sponsorship);
+        }
+        pb_sponsorships
+    }
+}
+
+impl TryFrom<ChainSpec_Sponsorships> for BTreeMap<[u8; 32], Sponsorship> {
+    type Error = MappingError;
+
+    fn try_from(mut pb_sponsorships: ChainSpec_Sponsorships) -> Result<Self, Self::Error> {
+        let mut sponsorships = BTreeMap::new();
+        for mut pb_sponsorship in pb_sponsorships.take_sponsorships().into_iter() {
+            let contract_hash =
+                mappings::vec_to_array(pb_sponsorship.take_contract_hash(), "Contract hash")?;
+            let allowance = U512::try_from(pb_sponsorship.take_allowance())?;
+            let purse = URef::try_from(pb_sponsorship.take_purse())?;
+            sponsorships.insert(contract_hash, Sponsorship::new(Gas::new(allowance), purse));
+        }
+        Ok(sponsorships)
+    }
+}
+
+impl From<&HostFunctionCosts> for ChainSpec_CostTable_HostFunctionCosts {
+    fn from(host_function_costs: &HostFunctionCosts) -> Self {
+        let mut pb_host_function_costs = ChainSpec_CostTable_HostFunctionCosts::new();
+        for (name, cost) in host_function_costs.iter() {
+            pb_host_function_costs
+                .mut_costs()
+                .insert(name.to_string(), cost);
+        }
+        pb_host_function_costs
+    }
+}
+
+impl From<ChainSpec_CostTable_HostFunctionCosts> for HostFunctionCosts {
+    fn from(mut pb_host_function_costs: ChainSpec_CostTable_HostFunctionCosts) -> Self {
+        let costs: BTreeMap<String, u32> =
+            pb_host_function_costs.take_costs().into_iter().collect();
+        costs.into()
+    }
+}
+
+impl From<&DeniedHostFunctions> for ChainSpec_DeniedHostFunctions {
+    fn from(denied_host_functions: &DeniedHostFunctions) -> Self {
+        let mut pb_denied_host_functions = ChainSpec_DeniedHostFunctions::new();
+        for name in denied_host_functions.iter() {
+            pb_denied_host_functions.mut_names().push(name.to_string());
+        }
+        pb_denied_host_functions
+    }
+}
+
+impl From<ChainSpec_DeniedHostFunctions> for DeniedHostFunctions {
+    fn from(mut pb_denied_host_functions: ChainSpec_DeniedHostFunctions) -> Self {
+        let names: BTreeSet<String> = pb_denied_host_functions.take_names().into_iter().collect();
+        names.into()
+    }
+}
+
+impl From<GasMetering> for ChainSpec_GasMetering {
+    fn from(gas_metering: GasMetering) -> Self {
+        let mut pb_gas_metering = ChainSpec_GasMetering::new();
+        if let GasMetering::Fuel { flush_threshold } = gas_metering {
+            pb_gas_metering.set_fuel_flush_threshold(flush_threshold);
+        }
+        pb_gas_metering
+    }
+}
+
+impl From<ChainSpec_GasMetering> for GasMetering {
+    fn from(pb_gas_metering: ChainSpec_GasMetering) -> Self {
+        match pb_gas_metering.fuel_flush_threshold {
+            0 => GasMetering::HostCall,
+            flush_threshold => GasMetering::Fuel { flush_threshold },
+        }
+    }
+}
+
+impl From<ChainParameters> for ChainSpec_ChainParameters {
+    fn from(chain_parameters: ChainParameters) -> Self {
+        let mut pb_chain_parameters = ChainSpec_ChainParameters::new();
+        pb_chain_parameters.set_max_payment(chain_parameters.max_payment().value().as_u64());
+        pb_chain_parameters.set_conv_rate(chain_parameters.conv_rate());
+        pb_chain_parameters.set_block_gas_limit(
+            chain_parameters
+                .block_gas_limit()
+                .map(|block_gas_limit| block_gas_limit.value().as_u64())
+                .unwrap_or_default(),
+        );
+        pb_chain_parameters.set_seigniorage_rate(chain_parameters.seigniorage_rate());
+        pb_chain_parameters
+            .set_native_transfer_cost(chain_parameters.native_transfer_cost().value().as_u64());
+        pb_chain_parameters
+    }
+}
+
+impl From<ChainSpec_ChainParameters> for ChainParameters {
+    fn from(pb_chain_parameters: ChainSpec_ChainParameters) -> Self {
+        ChainParameters::new(
+            pb_chain_parameters.max_payment,
+            pb_chain_parameters.conv_rate,
+            pb_chain_parameters.block_gas_limit,
+            pb_chain_parameters.seigniorage_rate,
+            pb_chain_parameters.native_transfer_cost,
+        )
+    }
+}
+
+impl From<UpgradeConfig> for UpgradeRequest {
+    fn from(upgrade_config: UpgradeConfig) -> Self {
+        let mut pb_upgrade_point = ChainSpec_UpgradePoint::new();
+        pb_upgrade_point.set_protocol_version(upgrade_config.new_protocol_version().into());
+        if upgrade_config.upgrade_installer_bytes().is_some()
+            || upgrade_config.upgrade_installer_args().is_some()
+        {
+            let mut pb_upgrade_installer = DeployCode::new();
+            pb_upgrade_installer.set_code(
+                upgrade_config
+                    .upgrade_installer_bytes()
+                    .unwrap_or_default()
+                    .to_vec(),
+            );
+            pb_upgrade_installer.set_args(
+                upgrade_config
+                    .upgrade_installer_args()
+                    .unwrap_or_default()
+                    .to_vec(),
+            );
+            pb_upgrade_point.set_upgrade_installer(pb_upgrade_installer);
+        }
+        if let Some(wasm_costs) = upgrade_config.wasm_costs() {
+            pb_upgrade_point.mut_new_costs().set_wasm(wasm_costs.into());
+        }
+        if let Some(host_function_costs) = upgrade_config.host_function_costs() {
+            pb_upgrade_point
+                .mut_new_costs()
+                .set_host_functions(host_function_costs.into());
+        }
+        if let Some(refund_percentage) = upgrade_config.refund_percentage() {
+            pb_upgrade_point
+                .mut_new_refund_policy()
+                .set_refund_percentage(refund_percentage.into());
+        }
+        if let Some(chain_parameters) = upgrade_config.chain_parameters() {
+            pb_upgrade_point.set_new_chain_parameters(chain_parameters.into());
+        }
+        if let Some(denied_host_functions) = upgrade_config.denied_host_functions() {
+            pb_upgrade_point.set_new_denied_host_functions(denied_host_functions.into());
+        }
+        if let Some(gas_metering) = upgrade_config.gas_metering() {
+            pb_upgrade_point.set_new_gas_metering(gas_metering.into());
+        }
+        if let Some(rank) = upgrade_config.activation_point() {
+            pb_upgrade_point.mut_activation_point().set_rank(rank);
+        }
+        if let Some(stored_installer) = upgrade_config.stored_installer() {
+            pb_upgrade_point.set_stored_installer(stored_installer.to_string());
+        }
+        if let Some(sponsorships) = upgrade_config.sponsorships() {
+            pb_upgrade_point.set_new_sponsorships(sponsorships.into());
+        }
+
+        let mut pb_upgrade_request = UpgradeRequest::new();
+        pb_upgrade_request.set_parent_state_hash(upgrade_config.pre_state_hash().to_vec());
+        pb_upgrade_request.set_upgrade_point(pb_upgrade_point);
+        pb_upgrade_request.set_protocol_version(upgrade_config.current_protocol_version().into());
+        pb_upgrade_request
+    }
+}
 
 impl TryFrom<UpgradeRequest> for UpgradeConfig {
     type Error = MappingError;
@@ -30,18 +224,58 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
                 (bytes, args)
             };
 
-        let wasm_costs = if !upgrade_point.has_new_costs() {
+        let new_costs = upgrade_point.mut_new_costs();
+        let wasm_costs = if !new_costs.has_wasm() {
             None
         } else {
-            Some(upgrade_point.mut_new_costs().take_wasm().into())
+            Some(new_costs.take_wasm().into())
+        };
+        let host_function_costs = if !new_costs.has_host_functions() {
+            None
+        } else {
+            Some(new_costs.take_host_functions().into())
         };
         let activation_point = if !upgrade_point.has_activation_point() {
             None
         } else {
             Some(upgrade_point.get_activation_point().rank)
         };
+        let stored_installer = upgrade_point.take_stored_installer();
+        let sponsorships = if !upgrade_point.has_new_sponsorships() {
+            None
+        } else {
+            Some(upgrade_point.take_new_sponsorships().try_into()?)
+        };
+
+        let refund_percentage = if !upgrade_point.has_new_refund_policy() {
+            None
+        } else {
+            let refund_percentage = upgrade_point.get_new_refund_policy().refund_percentage;
+            let refund_percentage = u8::try_from(refund_percentage).map_err(|_| {
+                ParsingError(format!("Invalid refund percentage: {}", refund_percentage))
+            })?;
+            Some(refund_percentage)
+        };
+
+        let chain_parameters = if !upgrade_point.has_new_chain_parameters() {
+            None
+        } else {
+            Some(upgrade_point.take_new_chain_parameters().into())
+        };
+
+        let denied_host_functions = if !upgrade_point.has_new_denied_host_functions() {
+            None
+        } else {
+            Some(upgrade_point.take_new_denied_host_functions().into())
+        };
+
+        let gas_metering = if !upgrade_point.has_new_gas_metering() {
+            None
+        } else {
+            Some(upgrade_point.take_new_gas_metering().into())
+        };
 
-        Ok(UpgradeConfig::new(
+        let mut upgrade_config = UpgradeConfig::new(
             pre_state_hash,
             current_protocol_version,
             new_protocol_version,
@@ -49,6 +283,129 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
             upgrade_installer_bytes,
             wasm_costs,
             activation_point,
-        ))
+        );
+        if !stored_installer.is_empty() {
+            upgrade_config = upgrade_config.with_stored_installer(&stored_installer);
+        }
+        if let Some(sponsorships) = sponsorships {
+            upgrade_config = upgrade_config.with_sponsorships(sponsorships);
+        }
+        if let Some(host_function_costs) = host_function_costs {
+            upgrade_config = upgrade_config.with_host_function_costs(host_function_costs);
+        }
+        if let Some(refund_percentage) = refund_percentage {
+            upgrade_config = upgrade_config.with_refund_percentage(refund_percentage);
+        }
+        if let Some(chain_parameters) = chain_parameters {
+            upgrade_config = upgrade_config.with_chain_parameters(chain_parameters);
+        }
+        if let Some(denied_host_functions) = denied_host_functions {
+            upgrade_config = upgrade_config.with_denied_host_functions(denied_host_functions);
+        }
+        if let Some(gas_metering) = gas_metering {
+            upgrade_config = upgrade_config.with_gas_metering(gas_metering);
+        }
+        Ok(upgrade_config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{
+        collection::{btree_map, vec},
+        option,
+        prelude::*,
+    };
+
+    use engine_wasm_prep::{
+        denied_host_functions::gens as denied_host_functions_gens,
+        gas_metering::gens as gas_metering_gens,
+        host_function_costs::gens as host_function_costs_gens, wasm_costs::gens as wasm_costs_gens,
+    };
+    use types::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(
+            pre_state_hash in gens::u8_slice_32(),
+            current_protocol_version in gens::protocol_version_arb(),
+            new_protocol_version in gens::protocol_version_arb(),
+            // Empty installer code and args are mapped to `None`
+            upgrade_installer_args in option::of(vec(any::<u8>(), 1..100)),
+            upgrade_installer_bytes in option::of(vec(any::<u8>(), 1..100)),
+            wasm_costs in option::of(wasm_costs_gens::wasm_costs_arb()),
+            activation_point in option::of(any::<u64>()),
+            // An empty stored installer name is mapped to `None`
+            stored_installer in option::of("[a-z_]{1,16}"),
+            sponsorships in option::of(btree_map(
+                gens::u8_slice_32(),
+                (gens::u512_arb(), gens::uref_arb()),
+                0..3,
+            )),
+            host_function_costs in option::of(host_function_costs_gens::host_function_costs_arb()),
+            refund_percentage in option::of(any::<u8>()),
+            chain_parameters in option::of(
+                (any::<u64>(), any::<u64>(), any::<u64>(), any::<u64>(), any::<u64>())
+            ),
+            denied_host_functions in option::of(
+                denied_host_functions_gens::denied_host_functions_arb()
+            ),
+            gas_metering in option::of(gas_metering_gens::gas_metering_arb()),
+        ) {
+            let mut upgrade_config = UpgradeConfig::new(
+                pre_state_hash.into(),
+                current_protocol_version,
+                new_protocol_version,
+                upgrade_installer_args,
+                upgrade_installer_bytes,
+                wasm_costs,
+                activation_point,
+            );
+            if let Some(stored_installer) = stored_installer {
+                upgrade_config = upgrade_config.with_stored_installer(&stored_installer);
+            }
+            if let Some(sponsorships) = sponsorships {
+                let sponsorships = sponsorships
+                    .into_iter()
+                    .map(|(contract_hash, (allowance, purse))| {
+                        (contract_hash, Sponsorship::new(Gas::new(allowance), purse))
+                    })
+                    .collect();
+                upgrade_config = upgrade_config.with_sponsorships(sponsorships);
+            }
+            if let Some(host_function_costs) = host_function_costs {
+                upgrade_config = upgrade_config.with_host_function_costs(host_function_costs);
+            }
+            if let Some(refund_percentage) = refund_percentage {
+                upgrade_config = upgrade_config.with_refund_percentage(refund_percentage);
+            }
+            if let Some((
+                max_payment,
+                conv_rate,
+                block_gas_limit,
+                seigniorage_rate,
+                native_transfer_cost,
+            )) = chain_parameters
+            {
+                let chain_parameters = ChainParameters::new(
+                    max_payment,
+                    conv_rate,
+                    block_gas_limit,
+                    seigniorage_rate,
+                    native_transfer_cost,
+                );
+                upgrade_config = upgrade_config.with_chain_parameters(chain_parameters);
+            }
+            if let Some(denied_host_functions) = denied_host_functions {
+                upgrade_config = upgrade_config.with_denied_host_functions(denied_host_functions);
+            }
+            if let Some(gas_metering) = gas_metering {
+                upgrade_config = upgrade_config.with_gas_metering(gas_metering);
+            }
+            test_utils::protobuf_round_trip::<UpgradeConfig, UpgradeRequest>(upgrade_config);
+        }
     }
 }
@@ -0,0 +1,57 @@
+use engine_core::engine_state::upgrade::UpgradeResult;
+
+use crate::engine_server::ipc::UpgradeResponse;
+
+impl From<UpgradeResult> for UpgradeResponse {
+    fn from(upgrade_result: UpgradeResult) -> Self {
+        let mut pb_upgrade_response = UpgradeResponse::new();
+        match upgrade_result {
+            UpgradeResult::Success {
+                post_state_hash,
+                effect,
+            } => {
+                let pb_upgrade_result = pb_upgrade_response.mut_success();
+                pb_upgrade_result.set_post_state_hash(post_state_hash.to_vec());
+                pb_upgrade_result.set_effect(effect.into());
+            }
+            error => pb_upgrade_response
+                .mut_failed_deploy()
+                .set_message(error.to_string()),
+        }
+        pb_upgrade_response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_core::engine_state::execution_effect::ExecutionEffect;
+    use engine_shared::newtypes::Blake2bHash;
+
+    use super::*;
+
+    #[test]
+    fn should_map_success() {
+        let post_state_hash = Blake2bHash::new(b"post state");
+        let upgrade_result = UpgradeResult::Success {
+            post_state_hash,
+            effect: ExecutionEffect::default(),
+        };
+
+        let pb_upgrade_response = UpgradeResponse::from(upgrade_result);
+        assert!(pb_upgrade_response.has_success());
+        assert_eq!(
+            pb_upgrade_response.get_success().get_post_state_hash(),
+            &post_state_hash.to_vec()[..]
+        );
+    }
+
+    #[test]
+    fn should_map_failure() {
+        let pb_upgrade_response = UpgradeResponse::from(UpgradeResult::RootNotFound);
+        assert!(pb_upgrade_response.has_failed_deploy());
+        assert_eq!(
+            pb_upgrade_response.get_failed_deploy().get_message(),
+            UpgradeResult::RootNotFound.to_string()
+        );
+    }
+}
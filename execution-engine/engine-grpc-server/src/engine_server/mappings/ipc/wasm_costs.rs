@@ -15,6 +15,14 @@ impl From<WasmCosts> for ChainSpec_CostTable_WasmCosts {
             max_stack_height: wasm_costs.max_stack_height,
             opcodes_mul: wasm_costs.opcodes_mul,
             opcodes_div: wasm_costs.opcodes_div,
+            storage_write_byte: wasm_costs.storage_write_byte,
+            read_cost: wasm_costs.read_cost,
+            write_cost: wasm_costs.write_cost,
+            add_cost: wasm_costs.add_cost,
+            hash_base: wasm_costs.hash_base,
+            hash_byte: wasm_costs.hash_byte,
+            max_memory: wasm_costs.max_memory,
+            max_table_size: wasm_costs.max_table_size,
             ..Default::default()
         }
     }
@@ -33,6 +41,14 @@ impl From<ChainSpec_CostTable_WasmCosts> for WasmCosts {
             max_stack_height: pb_wasm_costs.max_stack_height,
             opcodes_mul: pb_wasm_costs.opcodes_mul,
             opcodes_div: pb_wasm_costs.opcodes_div,
+            storage_write_byte: pb_wasm_costs.storage_write_byte,
+            read_cost: pb_wasm_costs.read_cost,
+            write_cost: pb_wasm_costs.write_cost,
+            add_cost: pb_wasm_costs.add_cost,
+            hash_base: pb_wasm_costs.hash_base,
+            hash_byte: pb_wasm_costs.hash_byte,
+            max_memory: pb_wasm_costs.max_memory,
+            max_table_size: pb_wasm_costs.max_table_size,
         }
     }
 }
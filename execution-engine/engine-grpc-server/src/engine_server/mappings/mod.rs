@@ -32,6 +32,8 @@ pub enum MappingError {
     InvalidStateHash(String),
     MissingPayload,
     TryFromSliceError,
+    InvalidGenesisAccount { account: String, reason: String },
+    DuplicateGenesisAccount(String),
 }
 
 impl MappingError {
@@ -88,6 +90,12 @@ impl Display for MappingError {
             MappingError::InvalidStateHash(message) => write!(f, "Invalid hash: {}", message),
             MappingError::MissingPayload => write!(f, "Missing payload"),
             MappingError::TryFromSliceError => write!(f, "Unable to convert from slice"),
+            MappingError::InvalidGenesisAccount { account, reason } => {
+                write!(f, "Invalid genesis account {}: {}", account, reason)
+            }
+            MappingError::DuplicateGenesisAccount(account) => {
+                write!(f, "Duplicate genesis account {}", account)
+            }
         }
     }
 }
@@ -10,7 +10,7 @@ use types::account::{PublicKey, PurseId, Weight};
 use super::NamedKeyMap;
 use crate::engine_server::{
     mappings::{self, ParsingError},
-    state::{self, Account_AssociatedKey, NamedKey},
+    state::{self, Account_AssociatedKey, Account_NamedPurse, NamedKey},
 };
 
 impl From<Account> for state::Account {
@@ -37,6 +37,18 @@ impl From<Account> for state::Account {
             pb_action_thresholds.set_key_management_threshold(key_management)
         }
 
+        let named_purses: Vec<Account_NamedPurse> = account
+            .named_purses()
+            .iter()
+            .map(|(name, purse_id)| {
+                let mut pb_named_purse = Account_NamedPurse::new();
+                pb_named_purse.set_name(name.clone());
+                pb_named_purse.set_purse_id(purse_id.value().into());
+                pb_named_purse
+            })
+            .collect();
+        pb_account.set_named_purses(named_purses.into());
+
         pb_account
     }
 }
@@ -91,13 +103,24 @@ impl TryFrom<state::Account> for Account {
             .map_err(ParsingError::from)?
         };
 
-        let account = Account::new(
+        let mut account = Account::new(
             public_key,
             named_keys.into_inner(),
             purse_id,
             associated_keys,
             action_thresholds,
         );
+        for pb_named_purse in pb_account.named_purses.into_vec() {
+            let pb_uref = pb_named_purse.purse_id.into_option().ok_or_else(|| {
+                ParsingError::from("Protobuf Account::NamedPurse missing PurseId field")
+            })?;
+            let purse_id = PurseId::new(pb_uref.try_into()?);
+            if !account.insert_named_purse(pb_named_purse.name, purse_id) {
+                return Err(ParsingError::from(
+                    "Protobuf Account::NamedPurses contains duplicate name",
+                ));
+            }
+        }
         Ok(account)
     }
 }
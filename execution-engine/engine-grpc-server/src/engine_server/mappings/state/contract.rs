@@ -1,17 +1,30 @@
 use std::convert::{TryFrom, TryInto};
 
 use engine_shared::contract::Contract;
+use types::account::PublicKey;
 
 use super::NamedKeyMap;
 use crate::engine_server::{
-    mappings::ParsingError,
+    mappings::{self, ParsingError},
     state::{self, NamedKey},
 };
 
 impl From<Contract> for state::Contract {
     fn from(contract: Contract) -> Self {
-        let (bytes, named_keys, protocol_version) = contract.destructure();
         let mut pb_contract = state::Contract::new();
+        if let Some(authorized_callers) = contract.authorized_callers() {
+            let pb_public_keys: Vec<Vec<u8>> = authorized_callers
+                .iter()
+                .map(|public_key| public_key.value().to_vec())
+                .collect();
+            pb_contract
+                .mut_authorized_callers()
+                .set_public_keys(pb_public_keys.into());
+        }
+        if let Some(package_hash) = contract.package_hash() {
+            pb_contract.set_package_hash(package_hash.to_vec());
+        }
+        let (bytes, named_keys, protocol_version) = contract.destructure();
         let named_keys: Vec<NamedKey> = NamedKeyMap::new(named_keys).into();
         pb_contract.set_body(bytes);
         pb_contract.set_named_keys(named_keys.into());
@@ -26,8 +39,31 @@ impl TryFrom<state::Contract> for Contract {
     fn try_from(mut pb_contract: state::Contract) -> Result<Self, Self::Error> {
         let named_keys: NamedKeyMap = pb_contract.take_named_keys().into_vec().try_into()?;
         let protocol_version = pb_contract.take_protocol_version().into();
-        let contract = Contract::new(pb_contract.body, named_keys.into_inner(), protocol_version);
-        Ok(contract)
+        let authorized_callers = if pb_contract.has_authorized_callers() {
+            let public_keys = pb_contract
+                .take_authorized_callers()
+                .take_public_keys()
+                .into_iter()
+                .map(|pb_public_key| {
+                    mappings::vec_to_array(
+                        pb_public_key,
+                        "Protobuf Contract::AuthorizedCallers::PublicKey",
+                    )
+                    .map(PublicKey::new)
+                })
+                .collect::<Result<Vec<PublicKey>, ParsingError>>()?;
+            Some(public_keys)
+        } else {
+            None
+        };
+        let contract = Contract::new(pb_contract.body, named_keys.into_inner(), protocol_version)
+            .with_authorized_callers(authorized_callers);
+        if pb_contract.package_hash.is_empty() {
+            return Ok(contract);
+        }
+        let package_hash =
+            mappings::vec_to_array(pb_contract.package_hash, "Protobuf Contract::PackageHash")?;
+        Ok(contract.with_package_hash(package_hash))
     }
 }
 
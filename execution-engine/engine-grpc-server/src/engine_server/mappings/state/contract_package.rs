@@ -0,0 +1,209 @@
+use std::{
+    collections::BTreeMap,
+    convert::{TryFrom, TryInto},
+};
+
+use engine_shared::contract_package::{ContractHeader, ContractPackage};
+use types::{account::PublicKey, EntryPoint, EntryPointAccess, EntryPoints, Parameter, URef};
+
+use crate::engine_server::{
+    mappings::{self, ParsingError},
+    state::{
+        self, ContractPackage_Access, ContractPackage_Access_oneof_variants,
+        ContractPackage_EntryPoint, ContractPackage_Parameter, ContractPackage_Version, Key_URef,
+    },
+};
+
+impl From<Parameter> for ContractPackage_Parameter {
+    fn from(parameter: Parameter) -> Self {
+        let mut pb_parameter = ContractPackage_Parameter::new();
+        pb_parameter.set_name(parameter.name().to_string());
+        pb_parameter.set_cl_type(parameter.cl_type().clone().into());
+        pb_parameter
+    }
+}
+
+impl TryFrom<ContractPackage_Parameter> for Parameter {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_parameter: ContractPackage_Parameter) -> Result<Self, Self::Error> {
+        let cl_type = pb_parameter.take_cl_type().try_into()?;
+        Ok(Parameter::new(pb_parameter.name, cl_type))
+    }
+}
+
+impl From<EntryPointAccess> for ContractPackage_Access {
+    fn from(access: EntryPointAccess) -> Self {
+        let mut pb_access = ContractPackage_Access::new();
+        match access {
+            EntryPointAccess::Public => {
+                let _pb_public = pb_access.mut_public();
+            }
+            EntryPointAccess::Groups(urefs) => {
+                let pb_urefs: Vec<Key_URef> = urefs.into_iter().map(Into::into).collect();
+                pb_access.mut_groups().set_urefs(pb_urefs.into());
+            }
+            EntryPointAccess::Accounts(public_keys) => {
+                let pb_public_keys: Vec<Vec<u8>> = public_keys
+                    .into_iter()
+                    .map(|public_key| public_key.value().to_vec())
+                    .collect();
+                pb_access
+                    .mut_accounts()
+                    .set_public_keys(pb_public_keys.into());
+            }
+        }
+        pb_access
+    }
+}
+
+impl TryFrom<ContractPackage_Access> for EntryPointAccess {
+    type Error = ParsingError;
+
+    fn try_from(pb_access: ContractPackage_Access) -> Result<Self, Self::Error> {
+        let pb_access = pb_access.variants.ok_or_else(|| {
+            ParsingError::from("Unable to parse Protobuf ContractPackage::Access")
+        })?;
+
+        let access = match pb_access {
+            ContractPackage_Access_oneof_variants::public(_) => EntryPointAccess::Public,
+            ContractPackage_Access_oneof_variants::groups(mut pb_groups) => {
+                let urefs = pb_groups
+                    .take_urefs()
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<Vec<URef>, ParsingError>>()?;
+                EntryPointAccess::Groups(urefs)
+            }
+            ContractPackage_Access_oneof_variants::accounts(mut pb_accounts) => {
+                let public_keys = pb_accounts
+                    .take_public_keys()
+                    .into_iter()
+                    .map(|pb_public_key| {
+                        mappings::vec_to_array(
+                            pb_public_key,
+                            "Protobuf ContractPackage::Access::PublicKey",
+                        )
+                        .map(PublicKey::new)
+                    })
+                    .collect::<Result<Vec<PublicKey>, ParsingError>>()?;
+                EntryPointAccess::Accounts(public_keys)
+            }
+        };
+
+        Ok(access)
+    }
+}
+
+impl From<EntryPoint> for ContractPackage_EntryPoint {
+    fn from(entry_point: EntryPoint) -> Self {
+        let mut pb_entry_point = ContractPackage_EntryPoint::new();
+        pb_entry_point.set_name(entry_point.name().to_string());
+        let parameters: Vec<ContractPackage_Parameter> = entry_point
+            .parameters()
+            .iter()
+            .cloned()
+            .map(Into::into)
+            .collect();
+        pb_entry_point.set_parameters(parameters.into());
+        pb_entry_point.set_ret(entry_point.ret().clone().into());
+        pb_entry_point.set_access(entry_point.access().clone().into());
+        pb_entry_point
+    }
+}
+
+impl TryFrom<ContractPackage_EntryPoint> for EntryPoint {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_entry_point: ContractPackage_EntryPoint) -> Result<Self, Self::Error> {
+        let parameters = pb_entry_point
+            .take_parameters()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<Parameter>, ParsingError>>()?;
+        let ret = pb_entry_point.take_ret().try_into()?;
+        let access = pb_entry_point.take_access().try_into()?;
+        Ok(EntryPoint::new(pb_entry_point.name, parameters, ret).with_access(access))
+    }
+}
+
+impl From<ContractPackage> for state::ContractPackage {
+    fn from(contract_package: ContractPackage) -> Self {
+        let (access_key, versions) = contract_package.destructure();
+        let mut pb_contract_package = state::ContractPackage::new();
+        pb_contract_package.set_access_key(access_key.into());
+
+        let pb_versions: Vec<ContractPackage_Version> = versions
+            .into_iter()
+            .map(|(version, contract_header)| {
+                let mut pb_version = ContractPackage_Version::new();
+                pb_version.set_version(version);
+                pb_version.set_contract_hash(contract_header.contract_hash().to_vec());
+                let entry_points: Vec<ContractPackage_EntryPoint> = contract_header
+                    .entry_points()
+                    .iter()
+                    .cloned()
+                    .map(Into::into)
+                    .collect();
+                pb_version.set_entry_points(entry_points.into());
+                pb_version.set_enabled(contract_header.is_enabled());
+                pb_version
+            })
+            .collect();
+        pb_contract_package.set_versions(pb_versions.into());
+
+        pb_contract_package
+    }
+}
+
+impl TryFrom<state::ContractPackage> for ContractPackage {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_contract_package: state::ContractPackage) -> Result<Self, Self::Error> {
+        let mut versions = BTreeMap::new();
+        for mut pb_version in pb_contract_package.take_versions().into_iter() {
+            let contract_hash = mappings::vec_to_array(
+                pb_version.take_contract_hash(),
+                "Protobuf ContractPackage::Version::ContractHash",
+            )?;
+            let entry_points = pb_version
+                .take_entry_points()
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<EntryPoint>, ParsingError>>()?;
+            let contract_header = ContractHeader::new(
+                contract_hash,
+                EntryPoints::from(entry_points),
+                pb_version.enabled,
+            );
+            versions.insert(pb_version.version, contract_header);
+        }
+
+        let access_key = pb_contract_package
+            .access_key
+            .into_option()
+            .ok_or_else(|| ParsingError::from("Protobuf ContractPackage missing AccessKey field"))?
+            .try_into()?;
+
+        Ok(ContractPackage::new(access_key, versions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_shared::contract_package::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(contract_package in gens::contract_package_arb()) {
+            test_utils::protobuf_round_trip::<ContractPackage, state::ContractPackage>(
+                contract_package
+            );
+        }
+    }
+}
@@ -15,6 +15,9 @@ impl From<StoredValue> for state::StoredValue {
             StoredValue::CLValue(cl_value) => pb_value.set_cl_value(cl_value.into()),
             StoredValue::Account(account) => pb_value.set_account(account.into()),
             StoredValue::Contract(contract) => pb_value.set_contract(contract.into()),
+            StoredValue::ContractPackage(contract_package) => {
+                pb_value.set_contract_package(contract_package.into())
+            }
         }
 
         pb_value
@@ -39,6 +42,9 @@ impl TryFrom<state::StoredValue> for StoredValue {
             StoredValue_oneof_variants::contract(pb_contract) => {
                 StoredValue::Contract(pb_contract.try_into()?)
             }
+            StoredValue_oneof_variants::contract_package(pb_contract_package) => {
+                StoredValue::ContractPackage(pb_contract_package.try_into()?)
+            }
         };
 
         Ok(value)
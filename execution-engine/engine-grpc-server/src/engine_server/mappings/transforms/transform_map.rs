@@ -63,4 +63,24 @@ mod tests {
         assert!(commit_transform.is_some());
         assert_eq!(expected_transform, *commit_transform.unwrap())
     }
+
+    #[test]
+    fn commit_effects_merges_identical_writes() {
+        // Deploys of a block writing the same value to a key result in a single write.
+        let key = Key::Hash([1u8; 32]);
+        let value = StoredValue::CLValue(CLValue::from_t(12_i32).unwrap());
+        let setup: Vec<TransformEntry> = (0..2)
+            .map(|_| {
+                let mut tmp = TransformEntry::new();
+                tmp.set_key(key.into());
+                tmp.set_transform(Transform::Write(value.clone()).into());
+                tmp
+            })
+            .collect();
+        let commit: TransformMap = setup
+            .try_into()
+            .expect("Transforming `Vec<TransformEntry>` into `TransformMap` should work.");
+        assert_eq!(commit.0.len(), 1);
+        assert_eq!(commit.0.get(&key), Some(&Transform::Write(value)));
+    }
 }
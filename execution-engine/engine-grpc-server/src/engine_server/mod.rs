@@ -11,10 +11,12 @@ use std::{
     io::ErrorKind,
     iter::FromIterator,
     marker::{Send, Sync},
+    str,
     time::Instant,
 };
 
 use grpc::{RequestOptions, ServerBuilder, SingleResponse};
+use tracing::info_span;
 
 use engine_core::engine_state::{
     execute_request::ExecuteRequest,
@@ -26,6 +28,7 @@ use engine_core::engine_state::{
 use engine_shared::{
     logging::{self, log_duration, log_info, log_level::LogLevel},
     newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
 };
 use engine_storage::global_state::{CommitResult, StateProvider};
 use types::{bytesrepr::ToBytes, ProtocolVersion};
@@ -33,7 +36,8 @@ use types::{bytesrepr::ToBytes, ProtocolVersion};
 use self::{
     ipc::{
         ChainSpec_GenesisConfig, CommitRequest, CommitResponse, ExecuteResponse, GenesisResponse,
-        QueryResponse, UpgradeRequest, UpgradeResponse,
+        ListProtocolVersionsRequest, ListProtocolVersionsResponse, QueryResponse, UpgradeRequest,
+        UpgradeResponse, WarmUpRequest, WarmUpResponse,
     },
     ipc_grpc::{ExecutionEngineService, ExecutionEngineServiceServer},
     mappings::{ParsingError, TransformMap},
@@ -44,15 +48,34 @@ const METRIC_DURATION_EXEC: &str = "exec_duration";
 const METRIC_DURATION_QUERY: &str = "query_duration";
 const METRIC_DURATION_GENESIS: &str = "genesis_duration";
 const METRIC_DURATION_UPGRADE: &str = "upgrade_duration";
+const METRIC_DURATION_LIST_PROTOCOL_VERSIONS: &str = "list_protocol_versions_duration";
+const METRIC_DURATION_WARM_UP: &str = "warm_up_duration";
 
 const TAG_RESPONSE_COMMIT: &str = "commit_response";
 const TAG_RESPONSE_EXEC: &str = "exec_response";
 const TAG_RESPONSE_QUERY: &str = "query_response";
 const TAG_RESPONSE_GENESIS: &str = "genesis_response";
 const TAG_RESPONSE_UPGRADE: &str = "upgrade_response";
+const TAG_RESPONSE_LIST_PROTOCOL_VERSIONS: &str = "list_protocol_versions_response";
+const TAG_RESPONSE_WARM_UP: &str = "warm_up_response";
 
 const DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 
+/// Key of the request metadata entry through which a client can supply the correlation id of its
+/// trace, so that the spans of the request are tagged with it.
+pub const CORRELATION_ID_METADATA_KEY: &str = "correlation-id";
+
+/// Returns the correlation id supplied by the client, or a new one if it supplied none or an
+/// invalid one.
+fn get_correlation_id(request_options: &RequestOptions) -> CorrelationId {
+    request_options
+        .metadata
+        .get(CORRELATION_ID_METADATA_KEY)
+        .and_then(|value| str::from_utf8(value).ok())
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_else(CorrelationId::new)
+}
+
 // Idea is that Engine will represent the core of the execution engine project.
 // It will act as an entry point for execution of Wasm binaries.
 // Proto definitions should be translated into domain objects when Engine's API
@@ -66,11 +89,13 @@ where
 {
     fn query(
         &self,
-        _request_options: RequestOptions,
+        request_options: RequestOptions,
         query_request: ipc::QueryRequest,
     ) -> SingleResponse<QueryResponse> {
         let start = Instant::now();
-        let correlation_id = CorrelationId::new();
+        let correlation_id = get_correlation_id(&request_options);
+        let span = info_span!("query", %correlation_id);
+        let _guard = span.enter();
 
         let request: QueryRequest = match query_request.try_into() {
             Ok(ret) => ret,
@@ -92,14 +117,21 @@ where
         let result = self.run_query(correlation_id, request);
 
         let response = match result {
-            Ok(QueryResult::Success(value)) => {
+            Ok(QueryResult::Success {
+                value,
+                visited_path,
+            }) => {
                 let mut result = ipc::QueryResponse::new();
+                result.set_visited_path(visited_path.into_iter().map(Into::into).collect());
                 match value.to_bytes() {
                     Ok(serialized_value) => {
                         let log_message =
                             format!("query successful; correlation_id: {}", correlation_id);
                         log_info(&log_message);
                         result.set_success(serialized_value);
+                        if let StoredValue::CLValue(cl_value) = value {
+                            result.set_cl_type(cl_value.cl_type().clone().into());
+                        }
                     }
                     Err(error_msg) => {
                         let log_message = format!("Failed to serialize StoredValue: {}", error_msg);
@@ -109,11 +141,15 @@ where
                 }
                 result
             }
-            Ok(QueryResult::ValueNotFound(full_path)) => {
-                let log_message = format!("Value not found: {:?}", full_path);
+            Ok(QueryResult::ValueNotFound {
+                message,
+                visited_path,
+            }) => {
+                let log_message = format!("Value not found: {:?}", message);
                 logging::log_warning(&log_message);
                 let mut result = ipc::QueryResponse::new();
                 result.set_failure(log_message);
+                result.set_visited_path(visited_path.into_iter().map(Into::into).collect());
                 result
             }
             Ok(QueryResult::RootNotFound) => {
@@ -144,11 +180,13 @@ where
 
     fn execute(
         &self,
-        _request_options: RequestOptions,
+        request_options: RequestOptions,
         exec_request: ipc::ExecuteRequest,
     ) -> SingleResponse<ExecuteResponse> {
         let start = Instant::now();
-        let correlation_id = CorrelationId::new();
+        let correlation_id = get_correlation_id(&request_options);
+        let span = info_span!("execute", %correlation_id);
+        let _guard = span.enter();
 
         let exec_request: ExecuteRequest = match exec_request.try_into() {
             Ok(ret) => ret,
@@ -191,11 +229,13 @@ where
 
     fn commit(
         &self,
-        _request_options: RequestOptions,
+        request_options: RequestOptions,
         mut commit_request: CommitRequest,
     ) -> SingleResponse<CommitResponse> {
         let start = Instant::now();
-        let correlation_id = CorrelationId::new();
+        let correlation_id = get_correlation_id(&request_options);
+        let span = info_span!("commit", %correlation_id);
+        let _guard = span.enter();
 
         // TODO
         let protocol_version = {
@@ -305,11 +345,13 @@ where
 
     fn run_genesis(
         &self,
-        _request_options: RequestOptions,
+        request_options: RequestOptions,
         genesis_config: ChainSpec_GenesisConfig,
     ) -> SingleResponse<GenesisResponse> {
         let start = Instant::now();
-        let correlation_id = CorrelationId::new();
+        let correlation_id = get_correlation_id(&request_options);
+        let span = info_span!("run_genesis", %correlation_id);
+        let _guard = span.enter();
 
         let genesis_config: GenesisConfig = match genesis_config.try_into() {
             Ok(genesis_config) => genesis_config,
@@ -367,11 +409,13 @@ where
 
     fn upgrade(
         &self,
-        _request_options: RequestOptions,
+        request_options: RequestOptions,
         upgrade_request: UpgradeRequest,
     ) -> SingleResponse<UpgradeResponse> {
         let start = Instant::now();
-        let correlation_id = CorrelationId::new();
+        let correlation_id = get_correlation_id(&request_options);
+        let span = info_span!("upgrade", %correlation_id);
+        let _guard = span.enter();
 
         let upgrade_config: UpgradeConfig = match upgrade_request.try_into() {
             Ok(upgrade_config) => upgrade_config,
@@ -394,26 +438,17 @@ where
         };
 
         let upgrade_response = match self.commit_upgrade(correlation_id, upgrade_config) {
-            Ok(UpgradeResult::Success {
-                post_state_hash,
-                effect,
-            }) => {
-                let success_message = format!("upgrade successful: {}", post_state_hash);
-                log_info(&success_message);
-
-                let mut ret = UpgradeResponse::new();
-                let upgrade_result = ret.mut_success();
-                upgrade_result.set_post_state_hash(post_state_hash.to_vec());
-                upgrade_result.set_effect(effect.into());
-                ret
-            }
             Ok(upgrade_result) => {
-                let err_msg = upgrade_result.to_string();
-                logging::log_error(&err_msg);
-
-                let mut ret = UpgradeResponse::new();
-                ret.mut_failed_deploy().set_message(err_msg);
-                ret
+                match &upgrade_result {
+                    UpgradeResult::Success {
+                        post_state_hash, ..
+                    } => {
+                        let success_message = format!("upgrade successful: {}", post_state_hash);
+                        log_info(&success_message);
+                    }
+                    error => logging::log_error(&error.to_string()),
+                }
+                upgrade_result.into()
             }
             Err(err) => {
                 let err_msg = err.to_string();
@@ -434,6 +469,94 @@ where
 
         SingleResponse::completed(upgrade_response)
     }
+
+    fn list_protocol_versions(
+        &self,
+        request_options: RequestOptions,
+        _list_protocol_versions_request: ListProtocolVersionsRequest,
+    ) -> SingleResponse<ListProtocolVersionsResponse> {
+        let start = Instant::now();
+        let correlation_id = get_correlation_id(&request_options);
+        let span = info_span!("list_protocol_versions", %correlation_id);
+        let _guard = span.enter();
+
+        let mut response = ListProtocolVersionsResponse::new();
+        match self.list_protocol_versions() {
+            Ok(protocol_versions) => {
+                let pb_protocol_versions: Vec<ipc::ProtocolVersionInfo> =
+                    protocol_versions.into_iter().map(Into::into).collect();
+                response
+                    .mut_success()
+                    .set_protocol_versions(pb_protocol_versions.into());
+            }
+            Err(error) => {
+                let err_msg = error.to_string();
+                logging::log_error(&err_msg);
+                response.set_failure(err_msg);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_LIST_PROTOCOL_VERSIONS,
+            TAG_RESPONSE_LIST_PROTOCOL_VERSIONS,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn warm_up(
+        &self,
+        request_options: RequestOptions,
+        mut warm_up_request: WarmUpRequest,
+    ) -> SingleResponse<WarmUpResponse> {
+        let start = Instant::now();
+        let correlation_id = get_correlation_id(&request_options);
+        let span = info_span!("warm_up", %correlation_id);
+        let _guard = span.enter();
+
+        let mut response = WarmUpResponse::new();
+        let protocol_version = warm_up_request.take_protocol_version().into();
+        match Blake2bHash::try_from(warm_up_request.get_state_hash()) {
+            Err(_) => {
+                let err_msg = "Could not parse state hash".to_string();
+                logging::log_error(&err_msg);
+                response.set_failure(err_msg);
+            }
+            Ok(state_hash) => match self.warm_up(correlation_id, state_hash, protocol_version) {
+                Ok(Some(system_contract_count)) => {
+                    log_info(&format!(
+                        "warmed up {} system contracts; correlation_id: {}",
+                        system_contract_count, correlation_id
+                    ));
+                    let warm_up_result = response.mut_success();
+                    warm_up_result.set_system_contract_count(system_contract_count as u32);
+                    warm_up_result.set_module_cache_stats(self.cache_stats().into());
+                }
+                Ok(None) => {
+                    logging::log_warning("RootNotFound");
+                    response
+                        .mut_missing_state()
+                        .set_hash(warm_up_request.take_state_hash());
+                }
+                Err(error) => {
+                    let err_msg = error.to_string();
+                    logging::log_error(&err_msg);
+                    response.set_failure(err_msg);
+                }
+            },
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_WARM_UP,
+            TAG_RESPONSE_WARM_UP,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
 }
 
 // Helper method which returns single DeployResult that is set to be a
@@ -1,7 +1,9 @@
 use std::{
     collections::BTreeMap,
+    convert::TryFrom,
     fs,
-    path::PathBuf,
+    net::{SocketAddr, TcpListener},
+    path::{Path, PathBuf},
     str::FromStr,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -12,25 +14,24 @@ use std::{
 
 use clap::{App, Arg, ArgMatches};
 use dirs::home_dir;
-use engine_core::engine_state::{EngineConfig, EngineState};
+use engine_core::engine_state::{EngineConfig, EngineConfigBuilder, EngineState};
 use lazy_static::lazy_static;
-use lmdb::DatabaseFlags;
 
 use engine_shared::{
     logging::{
         self, log_level,
         log_settings::{self, LogLevelFilter, LogSettings},
     },
+    newtypes::{Blake2bHash, CorrelationId},
     os::get_page_size,
     socket,
 };
 use engine_storage::{
-    global_state::lmdb::LmdbGlobalState, transaction_source::lmdb::LmdbEnvironment,
-    trie_store::lmdb::LmdbTrieStore,
+    global_state::lmdb::{LmdbGlobalState, LmdbStorageConfig},
+    transaction_source::lmdb::{LmdbEnvironment, DEFAULT_MAP_GROWTH_FACTOR},
 };
 
-use casperlabs_engine_grpc_server::engine_server;
-use engine_storage::protocol_data_store::lmdb::LmdbProtocolDataStore;
+use casperlabs_engine_grpc_server::{engine_server, metrics_server};
 
 // exe / proc
 const PROC_NAME: &str = "casperlabs-engine-grpc-server";
@@ -50,8 +51,6 @@ const GLOBAL_STATE_DIR: &str = "global_state";
 const GET_HOME_DIR_EXPECT: &str = "Could not get home directory";
 const CREATE_DATA_DIR_EXPECT: &str = "Could not create directory";
 const LMDB_ENVIRONMENT_EXPECT: &str = "Could not create LmdbEnvironment";
-const LMDB_TRIE_STORE_EXPECT: &str = "Could not create LmdbTrieStore";
-const LMDB_PROTOCOL_DATA_STORE_EXPECT: &str = "Could not create LmdbProtocolDataStore";
 const LMDB_GLOBAL_STATE_EXPECT: &str = "Could not create LmdbGlobalState";
 
 // pages / lmdb
@@ -65,6 +64,52 @@ const GET_PAGES_EXPECT: &str = "Could not parse pages argument";
 // 805306368000 / 4096 = 196608000
 const DEFAULT_PAGES: usize = 196_608_000;
 
+// map growth factor / lmdb
+const ARG_MAP_GROWTH_FACTOR: &str = "map-growth-factor";
+const ARG_MAP_GROWTH_FACTOR_VALUE: &str = "FACTOR";
+const ARG_MAP_GROWTH_FACTOR_HELP: &str =
+    "Sets the factor by which lmdb's mmap is grown when it is full [default: 2]";
+const GET_MAP_GROWTH_FACTOR_EXPECT: &str = "Could not parse map growth factor argument";
+
+// compaction / lmdb
+const ARG_COMPACT_TO: &str = "compact-to";
+const ARG_COMPACT_TO_VALUE: &str = "DIR";
+const ARG_COMPACT_TO_HELP: &str = "Writes a compacted copy of the global state to the given \
+     directory and exits instead of serving.  The copy replaces the global_state directory of the \
+     data directory while the server is stopped.";
+const ARG_VERIFY_ROOT: &str = "verify-root";
+const ARG_VERIFY_ROOT_VALUE: &str = "HASH";
+const ARG_VERIFY_ROOT_HELP: &str =
+    "Hex-encoded state root hash to check for integrity in the compacted copy, can be repeated";
+const GET_VERIFY_ROOT_EXPECT: &str = "Could not parse verify root argument";
+const COMPACTION_START_MESSAGE: &str = "compacting global state";
+const COMPACTION_PROGRESS_TEMPLATE: &str =
+    "copied {copied_bytes} of at most {total_bytes} bytes of global state";
+const COMPACTION_FINISHED_MESSAGE: &str = "finished compacting global state";
+const COMPACTION_EXPECT: &str = "Could not compact global state";
+
+// metrics
+const ARG_METRICS_ADDRESS: &str = "metrics-address";
+const ARG_METRICS_ADDRESS_VALUE: &str = "ADDRESS";
+const ARG_METRICS_ADDRESS_HELP: &str =
+    "Serves Prometheus metrics over HTTP on the given address, e.g. 127.0.0.1:9090";
+const GET_METRICS_ADDRESS_EXPECT: &str = "Could not parse metrics address argument";
+const METRICS_SERVER_START_EXPECT: &str = "failed to start metrics server";
+
+// engine config
+const ARG_CONFIG: &str = "config";
+const ARG_CONFIG_VALUE: &str = "FILE";
+const ARG_CONFIG_HELP: &str =
+    "Reads the engine configuration from a TOML file, or a JSON file if its extension is .json";
+const READ_CONFIG_EXPECT: &str = "Could not read engine config file";
+const PARSE_CONFIG_EXPECT: &str = "Could not parse engine config file";
+const BUILD_CONFIG_EXPECT: &str = "Invalid engine config";
+
+// tracing
+const ARG_TRACING: &str = "tracing";
+const ARG_TRACING_HELP: &str =
+    "Prints the tracing spans of requests, deploys and contract calls to stderr";
+
 // socket
 const ARG_SOCKET: &str = "socket";
 const ARG_SOCKET_HELP: &str =
@@ -109,6 +154,15 @@ fn main() {
 
     let matches: &clap::ArgMatches = &*ARG_MATCHES;
 
+    if matches.is_present(ARG_TRACING) {
+        init_tracing();
+    }
+
+    if let Some(target_dir) = matches.value_of(ARG_COMPACT_TO) {
+        compact_storage(matches, Path::new(target_dir));
+        return;
+    }
+
     let socket = get_socket(matches);
 
     match socket.remove_file() {
@@ -120,11 +174,25 @@ fn main() {
 
     let map_size = get_map_size(matches);
 
+    let map_growth_factor = get_map_growth_factor(matches);
+
     let thread_count = get_thread_count(matches);
 
     let engine_config: EngineConfig = get_engine_config(matches);
 
-    let _server = get_grpc_server(&socket, data_dir, map_size, thread_count, engine_config);
+    let _metrics_server = get_metrics_address(matches).map(|address| {
+        let listener = TcpListener::bind(address).expect(METRICS_SERVER_START_EXPECT);
+        metrics_server::serve(listener).expect(METRICS_SERVER_START_EXPECT)
+    });
+
+    let _server = get_grpc_server(
+        &socket,
+        data_dir,
+        map_size,
+        map_growth_factor,
+        thread_count,
+        engine_config,
+    );
 
     log_listening_message(&socket);
 
@@ -187,6 +255,49 @@ fn get_args() -> ArgMatches<'static> {
                 .help(ARG_PAGES_HELP)
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name(ARG_MAP_GROWTH_FACTOR)
+                .long(ARG_MAP_GROWTH_FACTOR)
+                .value_name(ARG_MAP_GROWTH_FACTOR_VALUE)
+                .help(ARG_MAP_GROWTH_FACTOR_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_COMPACT_TO)
+                .long(ARG_COMPACT_TO)
+                .value_name(ARG_COMPACT_TO_VALUE)
+                .help(ARG_COMPACT_TO_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_VERIFY_ROOT)
+                .long(ARG_VERIFY_ROOT)
+                .value_name(ARG_VERIFY_ROOT_VALUE)
+                .help(ARG_VERIFY_ROOT_HELP)
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .requires(ARG_COMPACT_TO),
+        )
+        .arg(
+            Arg::with_name(ARG_METRICS_ADDRESS)
+                .long(ARG_METRICS_ADDRESS)
+                .value_name(ARG_METRICS_ADDRESS_VALUE)
+                .help(ARG_METRICS_ADDRESS_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_CONFIG)
+                .long(ARG_CONFIG)
+                .value_name(ARG_CONFIG_VALUE)
+                .help(ARG_CONFIG_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_TRACING)
+                .long(ARG_TRACING)
+                .help(ARG_TRACING_HELP),
+        )
         .arg(
             Arg::with_name(ARG_THREAD_COUNT)
                 .short(ARG_THREAD_COUNT_SHORT)
@@ -198,13 +309,21 @@ fn get_args() -> ArgMatches<'static> {
         )
         .arg(
             Arg::with_name(ARG_SOCKET)
-                .required(true)
+                .required_unless(ARG_COMPACT_TO)
                 .help(ARG_SOCKET_HELP)
                 .index(1),
         )
         .get_matches()
 }
 
+/// Installs a subscriber printing tracing spans and events to stderr, leaving stdout to the
+/// structured log
+fn init_tracing() {
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
+}
+
 /// Gets SIGINT handle to allow clean exit
 fn get_sigint_handle() -> Arc<AtomicBool> {
     let handle = Arc::new(AtomicBool::new(true));
@@ -248,6 +367,34 @@ fn get_map_size(matches: &ArgMatches) -> usize {
     page_size * pages
 }
 
+/// Parses map-growth-factor argument
+fn get_map_growth_factor(matches: &ArgMatches) -> f64 {
+    matches
+        .value_of(ARG_MAP_GROWTH_FACTOR)
+        .map_or(Ok(DEFAULT_MAP_GROWTH_FACTOR), f64::from_str)
+        .expect(GET_MAP_GROWTH_FACTOR_EXPECT)
+}
+
+/// Parses metrics-address argument
+fn get_metrics_address(matches: &ArgMatches) -> Option<SocketAddr> {
+    matches
+        .value_of(ARG_METRICS_ADDRESS)
+        .map(|address| address.parse().expect(GET_METRICS_ADDRESS_EXPECT))
+}
+
+/// Parses verify-root arguments
+fn get_verify_roots(matches: &ArgMatches) -> Vec<Blake2bHash> {
+    matches
+        .values_of(ARG_VERIFY_ROOT)
+        .into_iter()
+        .flatten()
+        .map(|root| {
+            let bytes = base16::decode(root).expect(GET_VERIFY_ROOT_EXPECT);
+            Blake2bHash::try_from(bytes.as_slice()).expect(GET_VERIFY_ROOT_EXPECT)
+        })
+        .collect()
+}
+
 fn get_thread_count(matches: &ArgMatches) -> usize {
     matches
         .value_of(ARG_THREAD_COUNT)
@@ -256,10 +403,27 @@ fn get_thread_count(matches: &ArgMatches) -> usize {
         .expect(ARG_THREAD_COUNT_EXPECT)
 }
 
-/// Returns an [`EngineConfig`].
-fn get_engine_config(_matches: &ArgMatches) -> EngineConfig {
-    // feature flags go here
-    EngineConfig::new()
+/// Returns an [`EngineConfig`], read from the config argument if given.
+fn get_engine_config(matches: &ArgMatches) -> EngineConfig {
+    let builder = match matches.value_of(ARG_CONFIG).map(Path::new) {
+        Some(path) => {
+            let contents = fs::read_to_string(path)
+                .unwrap_or_else(|error| panic!("{}: {:?}: {}", READ_CONFIG_EXPECT, path, error));
+            let is_json = path
+                .extension()
+                .map_or(false, |extension| extension == "json");
+            let result = if is_json {
+                EngineConfigBuilder::from_json(&contents)
+            } else {
+                EngineConfigBuilder::from_toml(&contents)
+            };
+            result.unwrap_or_else(|error| panic!("{}: {}", PARSE_CONFIG_EXPECT, error))
+        }
+        None => EngineConfig::builder(),
+    };
+    builder
+        .build()
+        .unwrap_or_else(|error| panic!("{}: {}", BUILD_CONFIG_EXPECT, error))
 }
 
 /// Builds and returns a gRPC server.
@@ -267,10 +431,17 @@ fn get_grpc_server(
     socket: &socket::Socket,
     data_dir: PathBuf,
     map_size: usize,
+    map_growth_factor: f64,
     thread_count: usize,
     engine_config: EngineConfig,
 ) -> grpc::Server {
-    let engine_state = get_engine_state(data_dir, map_size, engine_config);
+    let engine_state = get_engine_state(
+        data_dir,
+        map_size,
+        map_growth_factor,
+        &LmdbStorageConfig::default(),
+        engine_config,
+    );
 
     engine_server::new(socket.as_str(), thread_count, engine_state)
         .build()
@@ -281,29 +452,70 @@ fn get_grpc_server(
 fn get_engine_state(
     data_dir: PathBuf,
     map_size: usize,
+    map_growth_factor: f64,
+    storage_config: &LmdbStorageConfig,
     engine_config: EngineConfig,
 ) -> EngineState<LmdbGlobalState> {
     let environment = {
-        let ret = LmdbEnvironment::new(&data_dir, map_size).expect(LMDB_ENVIRONMENT_EXPECT);
+        let ret = LmdbEnvironment::new(&data_dir, map_size)
+            .expect(LMDB_ENVIRONMENT_EXPECT)
+            .with_map_growth_factor(map_growth_factor);
         Arc::new(ret)
     };
 
-    let trie_store = {
-        let ret = LmdbTrieStore::new(&environment, None, DatabaseFlags::empty())
-            .expect(LMDB_TRIE_STORE_EXPECT);
-        Arc::new(ret)
-    };
+    let global_state =
+        LmdbGlobalState::open(environment, storage_config).expect(LMDB_GLOBAL_STATE_EXPECT);
 
-    let protocol_data_store = {
-        let ret = LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty())
-            .expect(LMDB_PROTOCOL_DATA_STORE_EXPECT);
-        Arc::new(ret)
-    };
+    EngineState::new(global_state, engine_config)
+}
 
-    let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-        .expect(LMDB_GLOBAL_STATE_EXPECT);
+/// Writes a compacted copy of the global state in the data directory to `target_dir`
+fn compact_storage(matches: &ArgMatches, target_dir: &Path) {
+    let data_dir = get_data_dir(matches);
 
-    EngineState::new(global_state, engine_config)
+    let map_size = get_map_size(matches);
+
+    let map_growth_factor = get_map_growth_factor(matches);
+
+    let engine_config: EngineConfig = get_engine_config(matches);
+
+    let verify_roots = get_verify_roots(matches);
+
+    let storage_config = LmdbStorageConfig::default();
+
+    let engine_state = get_engine_state(
+        data_dir,
+        map_size,
+        map_growth_factor,
+        &storage_config,
+        engine_config,
+    );
+
+    logging::log_info(COMPACTION_START_MESSAGE);
+
+    engine_state
+        .compact_storage(
+            CorrelationId::new(),
+            target_dir,
+            &storage_config,
+            &verify_roots,
+            |progress| {
+                let mut properties: BTreeMap<String, String> = BTreeMap::new();
+                properties.insert(
+                    "copied_bytes".to_string(),
+                    progress.copied_bytes.to_string(),
+                );
+                properties.insert("total_bytes".to_string(), progress.total_bytes.to_string());
+                logging::log_details(
+                    log_level::LogLevel::Info,
+                    COMPACTION_PROGRESS_TEMPLATE.to_string(),
+                    properties,
+                );
+            },
+        )
+        .unwrap_or_else(|error| panic!("{}: {}", COMPACTION_EXPECT, error));
+
+    logging::log_info(COMPACTION_FINISHED_MESSAGE);
 }
 
 /// Builds and returns log_settings
@@ -0,0 +1,85 @@
+//! A minimal HTTP endpoint exposing the engine's metrics to a Prometheus scraper.
+
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    thread::{self, JoinHandle},
+};
+
+use engine_shared::{logging, metrics};
+
+const METRICS_PATH: &str = "/metrics";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Serves `GET /metrics` on `listener` from a background thread until the process exits.
+pub fn serve(listener: TcpListener) -> io::Result<JoinHandle<()>> {
+    thread::Builder::new()
+        .name("metrics-server".to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let result = stream.and_then(handle_connection);
+                if let Err(error) = result {
+                    logging::log_warning(&format!("metrics server error: {}", error));
+                }
+            }
+        })
+}
+
+fn handle_connection(mut stream: TcpStream) -> io::Result<()> {
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(METRICS_PATH)) => ("200 OK", metrics::registry().render()),
+        (Some("GET"), _) => ("404 Not Found", String::new()),
+        _ => ("405 Method Not Allowed", String::new()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        CONTENT_TYPE,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+    };
+
+    use engine_shared::metrics::TRIE_STORE_READS;
+
+    fn request(address: &str, request_line: &str) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "{}\r\n\r\n", request_line).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn should_serve_metrics() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let _handle = super::serve(listener).unwrap();
+        TRIE_STORE_READS.inc();
+
+        let response = request(&address, "GET /metrics HTTP/1.1");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: text/plain; version=0.0.4\r\n"));
+        assert!(response.contains("# TYPE trie_store_reads_total counter\n"));
+
+        let response = request(&address, "GET /other HTTP/1.1");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let response = request(&address, "POST /metrics HTTP/1.1");
+        assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+    }
+}
@@ -1,19 +1,21 @@
 mod action_thresholds;
 mod associated_keys;
+mod vesting_schedule;
 
 use std::collections::{BTreeMap, BTreeSet};
 
 use types::{
     account::{
-        ActionType, AddKeyFailure, PublicKey, PurseId, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight, PUBLIC_KEY_SERIALIZED_LENGTH, WEIGHT_SERIALIZED_LENGTH,
+        ActionType, AddKeyFailure, KeyUpdate, PublicKey, PurseId, RemoveKeyFailure,
+        SetThresholdFailure, UpdateKeyFailure, UpdateKeysFailure, Weight,
     },
-    bytesrepr::{Error, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
-    AccessRights, Key, URef, KEY_UREF_SERIALIZED_LENGTH,
+    bytesrepr::{self, Error, FromBytes, ToBytes},
+    AccessRights, Key, URef,
 };
 
 pub use action_thresholds::ActionThresholds;
 pub use associated_keys::AssociatedKeys;
+pub use vesting_schedule::VestingSchedule;
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct Account {
@@ -22,6 +24,8 @@ pub struct Account {
     purse_id: PurseId,
     associated_keys: AssociatedKeys,
     action_thresholds: ActionThresholds,
+    named_purses: BTreeMap<String, PurseId>,
+    vesting_schedule: Option<VestingSchedule>,
 }
 
 impl Account {
@@ -38,6 +42,8 @@ impl Account {
             purse_id,
             associated_keys,
             action_thresholds,
+            named_purses: BTreeMap::new(),
+            vesting_schedule: None,
         }
     }
 
@@ -77,6 +83,35 @@ impl Account {
         self.purse_id
     }
 
+    /// Returns the purses of this account other than its main purse, by name.
+    pub fn named_purses(&self) -> &BTreeMap<String, PurseId> {
+        &self.named_purses
+    }
+
+    pub fn named_purse(&self, name: &str) -> Option<PurseId> {
+        self.named_purses.get(name).copied()
+    }
+
+    /// Adds a purse under the given name, unless the account already has a purse with that name.
+    ///
+    /// Returns `false` if the name is taken.
+    pub fn insert_named_purse(&mut self, name: String, purse_id: PurseId) -> bool {
+        if self.named_purses.contains_key(&name) {
+            return false;
+        }
+        self.named_purses.insert(name, purse_id);
+        true
+    }
+
+    /// Returns the lockup on the balance of the main purse, if any.
+    pub fn vesting_schedule(&self) -> Option<&VestingSchedule> {
+        self.vesting_schedule.as_ref()
+    }
+
+    pub fn set_vesting_schedule(&mut self, vesting_schedule: VestingSchedule) {
+        self.vesting_schedule = Some(vesting_schedule);
+    }
+
     /// Returns an [`AccessRights::ADD`]-only version of the [`PurseId`].
     pub fn purse_id_add_only(&self) -> PurseId {
         let purse_id_uref = self.purse_id.value();
@@ -151,6 +186,40 @@ impl Account {
         self.associated_keys.update_key(public_key, weight)
     }
 
+    /// Applies all of the given updates to the associated keys, or none of them.
+    ///
+    /// Updates are applied in order, so a key can be removed and another added in its place
+    /// even when the account is at `MAX_KEYS`.  Thresholds are only checked against the
+    /// resulting set of keys, which allows e.g. rotating every key of a multi-sig account in a
+    /// single call.
+    pub fn update_associated_keys(
+        &mut self,
+        updates: &[KeyUpdate],
+    ) -> Result<(), UpdateKeysFailure> {
+        let mut associated_keys = self.associated_keys.clone();
+        for update in updates {
+            match *update {
+                KeyUpdate::Add(public_key, weight) => {
+                    associated_keys.add_key(public_key, weight)?
+                }
+                KeyUpdate::Update(public_key, weight) => {
+                    associated_keys.update_key(public_key, weight)?
+                }
+                KeyUpdate::Remove(public_key) => associated_keys.remove_key(&public_key)?,
+            }
+        }
+
+        let total_weight = associated_keys.total_keys_weight();
+        if total_weight < *self.action_thresholds().deployment()
+            || total_weight < *self.action_thresholds().key_management()
+        {
+            return Err(UpdateKeysFailure::ThresholdViolation);
+        }
+
+        self.associated_keys = associated_keys;
+        Ok(())
+    }
+
     pub fn get_associated_key_weight(&self, public_key: PublicKey) -> Option<&Weight> {
         self.associated_keys.get(&public_key)
     }
@@ -184,6 +253,21 @@ impl Account {
                 .all(|e| self.associated_keys.contains_key(e))
     }
 
+    /// Returns the authorization keys which are not associated with this account.
+    pub fn unassociated_keys(&self, authorization_keys: &BTreeSet<PublicKey>) -> Vec<PublicKey> {
+        authorization_keys
+            .iter()
+            .filter(|key| !self.associated_keys.contains_key(key))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the sum of the weights of the authorization keys associated with this account.
+    pub fn authorization_keys_weight(&self, authorization_keys: &BTreeSet<PublicKey>) -> Weight {
+        self.associated_keys
+            .calculate_keys_weight(authorization_keys)
+    }
+
     /// Checks whether the sum of the weights of all authorization keys is
     /// greater or equal to deploy threshold.
     pub fn can_deploy_with(&self, authorization_keys: &BTreeSet<PublicKey>) -> bool {
@@ -207,29 +291,30 @@ impl Account {
 
 impl ToBytes for Account {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let action_thresholds_size = 2 * (WEIGHT_SERIALIZED_LENGTH + U8_SERIALIZED_LENGTH);
-        let associated_keys_size = self.associated_keys.len()
-            * (PUBLIC_KEY_SERIALIZED_LENGTH + WEIGHT_SERIALIZED_LENGTH)
-            + U32_SERIALIZED_LENGTH;
-        let named_keys_size =
-            KEY_UREF_SERIALIZED_LENGTH * self.named_keys.len() + U32_SERIALIZED_LENGTH;
-        let purse_id_size = KEY_UREF_SERIALIZED_LENGTH;
-        let serialized_account_size = PUBLIC_KEY_SERIALIZED_LENGTH // pub key
-            + named_keys_size
-            + purse_id_size
-            + associated_keys_size
-            + action_thresholds_size;
-        if serialized_account_size >= u32::max_value() as usize {
-            return Err(Error::OutOfMemoryError);
-        }
-        let mut result: Vec<u8> = Vec::with_capacity(serialized_account_size);
-        result.extend(&self.public_key.to_bytes()?);
-        result.append(&mut self.named_keys.to_bytes()?);
-        result.append(&mut self.purse_id.value().to_bytes()?);
-        result.append(&mut self.associated_keys.to_bytes()?);
-        result.append(&mut self.action_thresholds.to_bytes()?);
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
+
+    fn serialized_length(&self) -> usize {
+        self.public_key.serialized_length()
+            + self.named_keys.serialized_length()
+            + self.purse_id.serialized_length()
+            + self.associated_keys.serialized_length()
+            + self.action_thresholds.serialized_length()
+            + self.named_purses.serialized_length()
+            + self.vesting_schedule.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.public_key.write_bytes(writer)?;
+        self.named_keys.write_bytes(writer)?;
+        self.purse_id.write_bytes(writer)?;
+        self.associated_keys.write_bytes(writer)?;
+        self.action_thresholds.write_bytes(writer)?;
+        self.named_purses.write_bytes(writer)?;
+        self.vesting_schedule.write_bytes(writer)
+    }
 }
 
 impl FromBytes for Account {
@@ -239,6 +324,8 @@ impl FromBytes for Account {
         let (purse_id, rem): (URef, &[u8]) = FromBytes::from_bytes(rem)?;
         let (associated_keys, rem): (AssociatedKeys, &[u8]) = FromBytes::from_bytes(rem)?;
         let (action_thresholds, rem): (ActionThresholds, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (named_purses, rem): (BTreeMap<String, PurseId>, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (vesting_schedule, rem): (Option<VestingSchedule>, &[u8]) = FromBytes::from_bytes(rem)?;
         let purse_id = PurseId::new(purse_id);
         Ok((
             Account {
@@ -247,6 +334,8 @@ impl FromBytes for Account {
                 purse_id,
                 associated_keys,
                 action_thresholds,
+                named_purses,
+                vesting_schedule,
             },
             rem,
         ))
@@ -254,7 +343,7 @@ impl FromBytes for Account {
 }
 
 pub mod gens {
-    use proptest::prelude::*;
+    use proptest::{collection::btree_map, option, prelude::*};
 
     use types::{
         account::MAX_KEYS,
@@ -264,6 +353,7 @@ pub mod gens {
     use super::*;
     use crate::account::{
         action_thresholds::gens::action_thresholds_arb, associated_keys::gens::associated_keys_arb,
+        vesting_schedule::gens::vesting_schedule_arb,
     };
 
     prop_compose! {
@@ -273,16 +363,25 @@ pub mod gens {
             purse_id in uref_arb(),
             thresholds in action_thresholds_arb(),
             mut associated_keys in associated_keys_arb(MAX_KEYS - 1),
+            named_purses in btree_map("\\PC*", uref_arb(), 3),
+            vesting_schedule in option::of(vesting_schedule_arb()),
         ) -> Account {
                 let purse_id = PurseId::new(purse_id);
                 associated_keys.add_key(pub_key.into(), Weight::new(1)).unwrap();
-                Account::new(
+                let mut account = Account::new(
                     pub_key,
                     urefs,
                     purse_id,
                     associated_keys,
                     thresholds,
-                )
+                );
+                for (name, uref) in named_purses {
+                    account.insert_named_purse(name, PurseId::new(uref));
+                }
+                if let Some(vesting_schedule) = vesting_schedule {
+                    account.set_vesting_schedule(vesting_schedule);
+                }
+                account
         }
     }
 }
@@ -312,8 +411,8 @@ mod tests {
 
     use types::{
         account::{
-            ActionType, PublicKey, PurseId, RemoveKeyFailure, SetThresholdFailure,
-            UpdateKeyFailure, Weight,
+            ActionType, KeyUpdate, PublicKey, PurseId, RemoveKeyFailure, SetThresholdFailure,
+            UpdateKeyFailure, UpdateKeysFailure, Weight,
         },
         AccessRights, URef,
     };
@@ -368,6 +467,42 @@ mod tests {
         assert!(!account.can_authorize(&BTreeSet::new()));
     }
 
+    #[test]
+    fn account_should_report_unassociated_keys_and_authorization_weight() {
+        let key_1 = PublicKey::new([1; 32]);
+        let key_2 = PublicKey::new([2; 32]);
+        let unknown_key = PublicKey::new([42; 32]);
+        let mut keys = AssociatedKeys::new(key_1, Weight::new(1));
+        keys.add_key(key_2, Weight::new(2))
+            .expect("should add key_2");
+
+        let account = Account::new(
+            [0u8; 32],
+            BTreeMap::new(),
+            PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
+            keys,
+            ActionThresholds::new(Weight::new(3), Weight::new(3))
+                .expect("should create thresholds"),
+        );
+
+        let authorization_keys = BTreeSet::from_iter(vec![key_2, unknown_key]);
+        assert_eq!(
+            account.unassociated_keys(&authorization_keys),
+            vec![unknown_key]
+        );
+        assert_eq!(
+            account.authorization_keys_weight(&authorization_keys),
+            Weight::new(2)
+        );
+
+        let authorization_keys = BTreeSet::from_iter(vec![key_1, key_2]);
+        assert!(account.unassociated_keys(&authorization_keys).is_empty());
+        assert_eq!(
+            account.authorization_keys_weight(&authorization_keys),
+            Weight::new(3)
+        );
+    }
+
     #[test]
     fn account_can_deploy_with() {
         let associated_keys = {
@@ -661,4 +796,146 @@ mod tests {
             .update_associated_key(key_1, Weight::new(1))
             .expect("should work");
     }
+
+    fn three_of_five_account(keys: &[PublicKey]) -> Account {
+        let mut associated_keys = AssociatedKeys::default();
+        for key in keys {
+            associated_keys
+                .add_key(*key, Weight::new(1))
+                .expect("should add key");
+        }
+        Account::new(
+            [0u8; 32],
+            BTreeMap::new(),
+            PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
+            associated_keys,
+            ActionThresholds::new(Weight::new(3), Weight::new(3))
+                .expect("should create thresholds"),
+        )
+    }
+
+    #[test]
+    fn update_associated_keys_should_rotate_key_set() {
+        let old_keys: Vec<PublicKey> = (0..5).map(|i| PublicKey::new([i; 32])).collect();
+        let new_keys: Vec<PublicKey> = (5..10).map(|i| PublicKey::new([i; 32])).collect();
+        let mut account = three_of_five_account(&old_keys);
+
+        // Removing the old keys one at a time gets stuck once fewer than three remain.
+        let mut step_by_step = account.clone();
+        for key in &old_keys[..2] {
+            step_by_step
+                .remove_associated_key(*key)
+                .expect("should remove key");
+        }
+        assert_eq!(
+            step_by_step.remove_associated_key(old_keys[2]),
+            Err(RemoveKeyFailure::ThresholdViolation)
+        );
+
+        let updates: Vec<KeyUpdate> = old_keys
+            .iter()
+            .map(|key| KeyUpdate::Remove(*key))
+            .chain(
+                new_keys
+                    .iter()
+                    .map(|key| KeyUpdate::Add(*key, Weight::new(1))),
+            )
+            .collect();
+        account
+            .update_associated_keys(&updates)
+            .expect("should rotate keys");
+
+        let expected: BTreeSet<PublicKey> = new_keys.iter().cloned().collect();
+        let actual: BTreeSet<PublicKey> =
+            account.get_associated_keys().map(|(key, _)| *key).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn update_associated_keys_should_check_thresholds_after_batch() {
+        let keys: Vec<PublicKey> = (0..5).map(|i| PublicKey::new([i; 32])).collect();
+        let mut account = three_of_five_account(&keys);
+        let original = account.clone();
+
+        // Dropping to two keys violates the thresholds even though each step is valid on its own.
+        let updates = vec![
+            KeyUpdate::Remove(keys[0]),
+            KeyUpdate::Remove(keys[1]),
+            KeyUpdate::Remove(keys[2]),
+        ];
+        assert_eq!(
+            account.update_associated_keys(&updates),
+            Err(UpdateKeysFailure::ThresholdViolation)
+        );
+        assert_eq!(account, original);
+
+        // A transient violation is fine as long as the final set satisfies the thresholds.
+        let updates = vec![
+            KeyUpdate::Update(keys[0], Weight::new(0)),
+            KeyUpdate::Update(keys[1], Weight::new(0)),
+            KeyUpdate::Update(keys[2], Weight::new(0)),
+            KeyUpdate::Update(keys[3], Weight::new(3)),
+        ];
+        account
+            .update_associated_keys(&updates)
+            .expect("should update keys");
+        assert_eq!(
+            account.get_associated_key_weight(keys[3]),
+            Some(&Weight::new(3))
+        );
+    }
+
+    #[test]
+    fn update_associated_keys_should_not_apply_partial_batch() {
+        let keys: Vec<PublicKey> = (0..5).map(|i| PublicKey::new([i; 32])).collect();
+        let mut account = three_of_five_account(&keys);
+        let original = account.clone();
+        let missing_key = PublicKey::new([42; 32]);
+
+        let updates = vec![
+            KeyUpdate::Add(missing_key, Weight::new(1)),
+            KeyUpdate::Add(missing_key, Weight::new(1)),
+        ];
+        assert_eq!(
+            account.update_associated_keys(&updates),
+            Err(UpdateKeysFailure::DuplicateKey)
+        );
+        assert_eq!(account, original);
+
+        let updates = vec![
+            KeyUpdate::Remove(keys[0]),
+            KeyUpdate::Update(missing_key, Weight::new(1)),
+        ];
+        assert_eq!(
+            account.update_associated_keys(&updates),
+            Err(UpdateKeysFailure::MissingKey)
+        );
+        assert_eq!(account, original);
+
+        let updates: Vec<KeyUpdate> = (10..16)
+            .map(|i| KeyUpdate::Add(PublicKey::new([i; 32]), Weight::new(1)))
+            .collect();
+        assert_eq!(
+            account.update_associated_keys(&updates),
+            Err(UpdateKeysFailure::MaxKeysLimit)
+        );
+        assert_eq!(account, original);
+    }
+
+    #[test]
+    fn should_not_replace_named_purse() {
+        let mut account = Account::create(
+            [0u8; 32],
+            BTreeMap::new(),
+            PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
+        );
+        let savings = PurseId::new(URef::new([1u8; 32], AccessRights::READ_ADD_WRITE));
+        let other = PurseId::new(URef::new([2u8; 32], AccessRights::READ_ADD_WRITE));
+
+        assert_eq!(account.named_purse("savings"), None);
+        assert!(account.insert_named_purse("savings".to_string(), savings));
+        assert!(!account.insert_named_purse("savings".to_string(), other));
+        assert_eq!(account.named_purse("savings"), Some(savings));
+        assert_eq!(account.named_purses().len(), 1);
+    }
 }
@@ -92,10 +92,18 @@ impl Default for ActionThresholds {
 impl ToBytes for ActionThresholds {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         let mut result = Vec::with_capacity(2 * WEIGHT_SERIALIZED_LENGTH);
-        result.extend(&self.deployment.to_bytes()?);
-        result.extend(&self.key_management.to_bytes()?);
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
+
+    fn serialized_length(&self) -> usize {
+        2 * WEIGHT_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.deployment.write_bytes(writer)?;
+        self.key_management.write_bytes(writer)
+    }
 }
 
 impl FromBytes for ActionThresholds {
@@ -106,6 +106,14 @@ impl ToBytes for AssociatedKeys {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         ToBytes::to_bytes(&self.0)
     }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.0.write_bytes(writer)
+    }
 }
 
 impl FromBytes for AssociatedKeys {
@@ -0,0 +1,158 @@
+use types::{
+    bytesrepr::{Error, FromBytes, ToBytes, U64_SERIALIZED_LENGTH},
+    BlockTime, U512,
+};
+
+/// A lockup on part of the balance of an account's main purse.
+///
+/// The whole `locked_amount` stays locked until `cliff_timestamp`, from which point it is released
+/// linearly over `release_duration` milliseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VestingSchedule {
+    cliff_timestamp: u64,
+    release_duration: u64,
+    locked_amount: U512,
+}
+
+impl VestingSchedule {
+    pub fn new(cliff_timestamp: u64, release_duration: u64, locked_amount: U512) -> Self {
+        VestingSchedule {
+            cliff_timestamp,
+            release_duration,
+            locked_amount,
+        }
+    }
+
+    pub fn cliff_timestamp(&self) -> u64 {
+        self.cliff_timestamp
+    }
+
+    pub fn release_duration(&self) -> u64 {
+        self.release_duration
+    }
+
+    /// Returns the amount locked at the cliff.
+    pub fn locked_amount(&self) -> U512 {
+        self.locked_amount
+    }
+
+    /// Returns the timestamp at which the whole amount has been released.
+    pub fn release_end_timestamp(&self) -> u64 {
+        self.cliff_timestamp.saturating_add(self.release_duration)
+    }
+
+    /// Returns the amount which is still locked at the given block time.
+    pub fn locked_amount_at(&self, blocktime: BlockTime) -> U512 {
+        let now: u64 = blocktime.into();
+        if now < self.cliff_timestamp {
+            return self.locked_amount;
+        }
+        if now >= self.release_end_timestamp() {
+            return U512::zero();
+        }
+
+        // Computes `locked_amount * elapsed / duration` without overflowing for large amounts.
+        let elapsed = U512::from(now - self.cliff_timestamp);
+        let duration = U512::from(self.release_duration);
+        let quotient = self.locked_amount / duration;
+        let remainder = self.locked_amount % duration;
+        let released = quotient * elapsed + remainder * elapsed / duration;
+        self.locked_amount - released
+    }
+}
+
+impl ToBytes for VestingSchedule {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = Vec::with_capacity(self.serialized_length());
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        2 * U64_SERIALIZED_LENGTH + self.locked_amount.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.cliff_timestamp.write_bytes(writer)?;
+        self.release_duration.write_bytes(writer)?;
+        self.locked_amount.write_bytes(writer)
+    }
+}
+
+impl FromBytes for VestingSchedule {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (cliff_timestamp, rem): (u64, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (release_duration, rem): (u64, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (locked_amount, rem): (U512, &[u8]) = FromBytes::from_bytes(rem)?;
+        let ret = VestingSchedule {
+            cliff_timestamp,
+            release_duration,
+            locked_amount,
+        };
+        Ok((ret, rem))
+    }
+}
+
+pub mod gens {
+    use proptest::prelude::*;
+
+    use types::gens::u512_arb;
+
+    use super::VestingSchedule;
+
+    prop_compose! {
+        pub fn vesting_schedule_arb()(
+            cliff_timestamp in any::<u64>(),
+            release_duration in any::<u64>(),
+            locked_amount in u512_arb(),
+        ) -> VestingSchedule {
+            VestingSchedule::new(cliff_timestamp, release_duration, locked_amount)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{BlockTime, U512};
+
+    use super::VestingSchedule;
+
+    const CLIFF: u64 = 1_000;
+    const DURATION: u64 = 100;
+
+    fn locked_at(schedule: &VestingSchedule, timestamp: u64) -> U512 {
+        schedule.locked_amount_at(BlockTime::new(timestamp))
+    }
+
+    #[test]
+    fn should_lock_everything_before_the_cliff() {
+        let schedule = VestingSchedule::new(CLIFF, DURATION, U512::from(500));
+        assert_eq!(locked_at(&schedule, 0), U512::from(500));
+        assert_eq!(locked_at(&schedule, CLIFF - 1), U512::from(500));
+        assert_eq!(locked_at(&schedule, CLIFF), U512::from(500));
+    }
+
+    #[test]
+    fn should_release_linearly_after_the_cliff() {
+        let schedule = VestingSchedule::new(CLIFF, DURATION, U512::from(500));
+        assert_eq!(locked_at(&schedule, CLIFF + 1), U512::from(495));
+        assert_eq!(locked_at(&schedule, CLIFF + 50), U512::from(250));
+        assert_eq!(locked_at(&schedule, CLIFF + 99), U512::from(5));
+        assert_eq!(locked_at(&schedule, CLIFF + DURATION), U512::zero());
+        assert_eq!(locked_at(&schedule, u64::max_value()), U512::zero());
+    }
+
+    #[test]
+    fn should_release_everything_at_the_cliff_without_a_release_duration() {
+        let schedule = VestingSchedule::new(CLIFF, 0, U512::from(500));
+        assert_eq!(locked_at(&schedule, CLIFF - 1), U512::from(500));
+        assert_eq!(locked_at(&schedule, CLIFF), U512::zero());
+    }
+
+    #[test]
+    fn should_not_overflow_for_large_amounts() {
+        let schedule = VestingSchedule::new(CLIFF, 3, U512::max_value());
+        let locked = locked_at(&schedule, CLIFF + 1);
+        assert_eq!(locked, U512::max_value() - U512::max_value() / 3);
+    }
+}
@@ -148,8 +148,15 @@ impl<K: Eq + Hash + Debug, V: Debug, S: BuildHasher> Debug for AdditiveMap<K, V,
 
 #[cfg(test)]
 mod tests {
+    use proptest::{collection::vec, proptest};
+
     use super::AdditiveMap;
-    use crate::transform::Transform;
+    use crate::{
+        op::{gens::op_arb, Op},
+        transform::Transform,
+    };
+
+    const KEYS: u8 = 4;
 
     #[test]
     fn insert_add() {
@@ -166,4 +173,48 @@ mod tests {
         transform_map.insert_add(key, Transform::AddInt32(2));
         assert_eq!(Transform::AddInt32(3), transform_map[key]);
     }
+
+    proptest! {
+        #[test]
+        fn insert_add_should_combine_values_of_each_key(
+            entries in vec((0..KEYS, op_arb()), 0..50)
+        ) {
+            let mut map = AdditiveMap::new();
+            for (key, op) in &entries {
+                map.insert_add(*key, *op);
+            }
+            for key in 0..KEYS {
+                let expected = entries
+                    .iter()
+                    .filter(|(k, _)| *k == key)
+                    .fold(Op::NoOp, |acc, (_, op)| Op::combine(acc, *op));
+                assert_eq!(expected, map.get(&key).cloned().unwrap_or_default());
+            }
+        }
+
+        #[test]
+        fn merging_maps_should_not_depend_on_grouping(
+            first in vec((0..KEYS, op_arb()), 0..50),
+            second in vec((0..KEYS, op_arb()), 0..50)
+        ) {
+            let mut sequential = AdditiveMap::new();
+            for (key, op) in first.iter().chain(second.iter()) {
+                sequential.insert_add(*key, *op);
+            }
+
+            let mut merged = AdditiveMap::new();
+            for (key, op) in &first {
+                merged.insert_add(*key, *op);
+            }
+            let mut other = AdditiveMap::new();
+            for (key, op) in &second {
+                other.insert_add(*key, *op);
+            }
+            for (key, op) in other {
+                merged.insert_add(key, op);
+            }
+
+            assert_eq!(sequential, merged);
+        }
+    }
 }
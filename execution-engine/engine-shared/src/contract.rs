@@ -1,8 +1,9 @@
 use std::collections::BTreeMap;
 
 use types::{
-    bytesrepr::{Error, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U64_SERIALIZED_LENGTH},
-    Key, ProtocolVersion, KEY_UREF_SERIALIZED_LENGTH,
+    account::PublicKey,
+    bytesrepr::{self, Error, FromBytes, ToBytes},
+    Key, ProtocolVersion,
 };
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -10,6 +11,10 @@ pub struct Contract {
     bytes: Vec<u8>,
     named_keys: BTreeMap<String, Key>,
     protocol_version: ProtocolVersion,
+    /// The accounts whose deploys may call the contract, or `None` if anyone may call it.
+    authorized_callers: Option<Vec<PublicKey>>,
+    /// The hash of the contract package the contract is a version of, if any.
+    package_hash: Option<[u8; 32]>,
 }
 
 impl Contract {
@@ -22,9 +27,24 @@ impl Contract {
             bytes,
             named_keys,
             protocol_version,
+            authorized_callers: None,
+            package_hash: None,
         }
     }
 
+    /// Restricts calls to the contract to deploys executed as one of `authorized_callers`, or
+    /// lifts the restriction if `None`.
+    pub fn with_authorized_callers(mut self, authorized_callers: Option<Vec<PublicKey>>) -> Self {
+        self.set_authorized_callers(authorized_callers);
+        self
+    }
+
+    /// Marks the contract as a version of the contract package stored under `package_hash`.
+    pub fn with_package_hash(mut self, package_hash: [u8; 32]) -> Self {
+        self.package_hash = Some(package_hash);
+        self
+    }
+
     pub fn named_keys_append(&mut self, keys: &mut BTreeMap<String, Key>) {
         self.named_keys.append(keys);
     }
@@ -52,29 +72,50 @@ impl Contract {
     pub fn take_named_keys(self) -> BTreeMap<String, Key> {
         self.named_keys
     }
+
+    pub fn authorized_callers(&self) -> Option<&[PublicKey]> {
+        self.authorized_callers.as_deref()
+    }
+
+    pub fn set_authorized_callers(&mut self, authorized_callers: Option<Vec<PublicKey>>) {
+        self.authorized_callers = authorized_callers;
+    }
+
+    pub fn package_hash(&self) -> Option<[u8; 32]> {
+        self.package_hash
+    }
+
+    /// Returns whether a deploy executed as `caller` may call the contract.
+    pub fn is_authorized_caller(&self, caller: &PublicKey) -> bool {
+        match &self.authorized_callers {
+            Some(authorized_callers) => authorized_callers.contains(caller),
+            None => true,
+        }
+    }
 }
 
 impl ToBytes for Contract {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        if self.bytes.len()
-            + KEY_UREF_SERIALIZED_LENGTH * self.named_keys.len()
-            + U64_SERIALIZED_LENGTH
-            >= u32::max_value() as usize - U32_SERIALIZED_LENGTH * 2
-        {
-            return Err(Error::OutOfMemoryError);
-        }
-        let size: usize = U32_SERIALIZED_LENGTH +                        //size for length of bytes
-                    self.bytes.len() +                                   //size for elements of bytes
-                    U32_SERIALIZED_LENGTH +                              //size for length of named_keys
-                    KEY_UREF_SERIALIZED_LENGTH * self.named_keys.len() + //size for named_keys elements
-                    U64_SERIALIZED_LENGTH; //size for protocol_version
-
-        let mut result = Vec::with_capacity(size);
-        result.append(&mut self.bytes.to_bytes()?);
-        result.append(&mut self.named_keys.to_bytes()?);
-        result.append(&mut self.protocol_version.to_bytes()?);
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
+
+    fn serialized_length(&self) -> usize {
+        self.bytes.serialized_length()
+            + self.named_keys.serialized_length()
+            + self.protocol_version.serialized_length()
+            + self.authorized_callers.serialized_length()
+            + self.package_hash.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.bytes.write_bytes(writer)?;
+        self.named_keys.write_bytes(writer)?;
+        self.protocol_version.write_bytes(writer)?;
+        self.authorized_callers.write_bytes(writer)?;
+        self.package_hash.write_bytes(writer)
+    }
 }
 
 impl FromBytes for Contract {
@@ -82,29 +123,48 @@ impl FromBytes for Contract {
         let (bytes, rem1): (Vec<u8>, &[u8]) = FromBytes::from_bytes(bytes)?;
         let (named_keys, rem2): (BTreeMap<String, Key>, &[u8]) = FromBytes::from_bytes(rem1)?;
         let (protocol_version, rem3): (ProtocolVersion, &[u8]) = FromBytes::from_bytes(rem2)?;
+        let (authorized_callers, rem4): (Option<Vec<PublicKey>>, &[u8]) =
+            FromBytes::from_bytes(rem3)?;
+        let (package_hash, rem5): (Option<[u8; 32]>, &[u8]) = FromBytes::from_bytes(rem4)?;
         Ok((
             Contract {
                 bytes,
                 named_keys,
                 protocol_version,
+                authorized_callers,
+                package_hash,
             },
-            rem3,
+            rem5,
         ))
     }
 }
 
 pub mod gens {
-    use proptest::{collection::vec, prelude::*};
+    use proptest::{collection::vec, option, prelude::*};
 
-    use types::gens::{named_keys_arb, protocol_version_arb};
+    use types::{
+        account::PublicKey,
+        gens::{named_keys_arb, protocol_version_arb},
+    };
 
     use super::Contract;
 
     pub fn contract_arb() -> impl Strategy<Value = Contract> {
         protocol_version_arb().prop_flat_map(move |protocol_version_arb| {
             named_keys_arb(20).prop_flat_map(move |urefs| {
-                vec(any::<u8>(), 1..1000)
-                    .prop_map(move |body| Contract::new(body, urefs.clone(), protocol_version_arb))
+                (
+                    vec(any::<u8>(), 1..1000),
+                    option::of(vec(any::<[u8; 32]>().prop_map(PublicKey::new), 0..5)),
+                    option::of(any::<[u8; 32]>()),
+                )
+                    .prop_map(move |(body, authorized_callers, package_hash)| {
+                        let contract = Contract::new(body, urefs.clone(), protocol_version_arb)
+                            .with_authorized_callers(authorized_callers);
+                        match package_hash {
+                            Some(package_hash) => contract.with_package_hash(package_hash),
+                            None => contract,
+                        }
+                    })
             })
         })
     }
@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+
+use types::{
+    bytesrepr::{self, Error, FromBytes, ToBytes},
+    ContractVersion, EntryPoints, URef, CONTRACT_INITIAL_VERSION,
+};
+
+/// A single version of a [`ContractPackage`].
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ContractHeader {
+    contract_hash: [u8; 32],
+    entry_points: EntryPoints,
+    enabled: bool,
+}
+
+impl ContractHeader {
+    pub fn new(contract_hash: [u8; 32], entry_points: EntryPoints, enabled: bool) -> Self {
+        ContractHeader {
+            contract_hash,
+            entry_points,
+            enabled,
+        }
+    }
+
+    /// Hash under which the `Contract` holding the code of this version is stored.
+    pub fn contract_hash(&self) -> [u8; 32] {
+        self.contract_hash
+    }
+
+    pub fn entry_points(&self) -> &EntryPoints {
+        &self.entry_points
+    }
+
+    /// Disabled versions are kept for reference, but can no longer be called.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl ToBytes for ContractHeader {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.contract_hash.serialized_length()
+            + self.entry_points.serialized_length()
+            + self.enabled.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.contract_hash.write_bytes(writer)?;
+        self.entry_points.write_bytes(writer)?;
+        self.enabled.write_bytes(writer)
+    }
+}
+
+impl FromBytes for ContractHeader {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (contract_hash, rem): ([u8; 32], &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (entry_points, rem): (EntryPoints, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (enabled, rem): (bool, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((
+            ContractHeader {
+                contract_hash,
+                entry_points,
+                enabled,
+            },
+            rem,
+        ))
+    }
+}
+
+/// A set of versions of a contract, stored under a single hash which does not change when new
+/// versions are added.
+///
+/// Adding and disabling versions requires the package's access key.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ContractPackage {
+    access_key: URef,
+    versions: BTreeMap<ContractVersion, ContractHeader>,
+}
+
+impl ContractPackage {
+    pub fn new(access_key: URef, versions: BTreeMap<ContractVersion, ContractHeader>) -> Self {
+        ContractPackage {
+            access_key,
+            versions,
+        }
+    }
+
+    pub fn access_key(&self) -> URef {
+        self.access_key
+    }
+
+    pub fn versions(&self) -> &BTreeMap<ContractVersion, ContractHeader> {
+        &self.versions
+    }
+
+    /// Returns the given version, whether or not it is enabled.
+    pub fn get_version(&self, version: ContractVersion) -> Option<&ContractHeader> {
+        self.versions.get(&version)
+    }
+
+    /// Returns the highest enabled version.
+    pub fn current_version(&self) -> Option<(ContractVersion, &ContractHeader)> {
+        self.versions
+            .iter()
+            .rev()
+            .find(|(_, header)| header.is_enabled())
+            .map(|(version, header)| (*version, header))
+    }
+
+    /// Adds a new enabled version and returns its number.
+    pub fn add_version(
+        &mut self,
+        contract_hash: [u8; 32],
+        entry_points: EntryPoints,
+    ) -> ContractVersion {
+        let version = self
+            .versions
+            .keys()
+            .next_back()
+            .map(|version| version + 1)
+            .unwrap_or(CONTRACT_INITIAL_VERSION);
+        self.versions.insert(
+            version,
+            ContractHeader::new(contract_hash, entry_points, true),
+        );
+        version
+    }
+
+    /// Disables the given version.  Returns `false` if there is no such version.
+    pub fn disable_version(&mut self, version: ContractVersion) -> bool {
+        match self.versions.get_mut(&version) {
+            Some(header) => {
+                header.enabled = false;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn destructure(self) -> (URef, BTreeMap<ContractVersion, ContractHeader>) {
+        (self.access_key, self.versions)
+    }
+}
+
+impl ToBytes for ContractPackage {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.access_key.serialized_length() + self.versions.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.access_key.write_bytes(writer)?;
+        self.versions.write_bytes(writer)
+    }
+}
+
+impl FromBytes for ContractPackage {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (access_key, rem): (URef, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (versions, rem): (BTreeMap<ContractVersion, ContractHeader>, &[u8]) =
+            FromBytes::from_bytes(rem)?;
+        Ok((
+            ContractPackage {
+                access_key,
+                versions,
+            },
+            rem,
+        ))
+    }
+}
+
+pub mod gens {
+    use std::collections::BTreeMap;
+
+    use proptest::{collection::vec, prelude::*};
+
+    use types::{
+        account::PublicKey,
+        gens::{u8_slice_32, uref_arb},
+        CLType, EntryPoint, EntryPointAccess, EntryPoints, Parameter,
+    };
+
+    use super::ContractPackage;
+
+    fn cl_type_arb() -> impl Strategy<Value = CLType> {
+        prop_oneof![
+            Just(CLType::Bool),
+            Just(CLType::U64),
+            Just(CLType::U512),
+            Just(CLType::String),
+            Just(CLType::Key),
+            Just(CLType::Unit),
+        ]
+    }
+
+    fn entry_point_access_arb() -> impl Strategy<Value = EntryPointAccess> {
+        prop_oneof![
+            Just(EntryPointAccess::Public),
+            vec(uref_arb(), 0..3).prop_map(EntryPointAccess::Groups),
+            vec(u8_slice_32().prop_map(PublicKey::new), 0..3).prop_map(EntryPointAccess::Accounts),
+        ]
+    }
+
+    fn entry_point_arb() -> impl Strategy<Value = EntryPoint> {
+        (
+            "[a-z_]{1,16}",
+            vec(("[a-z_]{1,16}", cl_type_arb()), 0..4),
+            cl_type_arb(),
+            entry_point_access_arb(),
+        )
+            .prop_map(|(name, parameters, ret, access)| {
+                let parameters = parameters
+                    .into_iter()
+                    .map(|(name, cl_type)| Parameter::new(name, cl_type))
+                    .collect();
+                EntryPoint::new(name, parameters, ret).with_access(access)
+            })
+    }
+
+    pub fn entry_points_arb() -> impl Strategy<Value = EntryPoints> {
+        vec(entry_point_arb(), 0..5).prop_map(EntryPoints::from)
+    }
+
+    pub fn contract_package_arb() -> impl Strategy<Value = ContractPackage> {
+        (
+            uref_arb(),
+            vec((u8_slice_32(), entry_points_arb(), any::<bool>()), 0..5),
+        )
+            .prop_map(|(access_key, versions)| {
+                let mut package = ContractPackage::new(access_key, BTreeMap::new());
+                for (contract_hash, entry_points, enabled) in versions {
+                    let version = package.add_version(contract_hash, entry_points);
+                    if !enabled {
+                        package.disable_version(version);
+                    }
+                }
+                package
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use proptest::proptest;
+
+    use types::{bytesrepr, AccessRights, EntryPoints, URef, CONTRACT_INITIAL_VERSION};
+
+    use super::{gens, ContractPackage};
+
+    #[test]
+    fn should_add_and_disable_versions() {
+        let access_key = URef::new([1; 32], AccessRights::READ_ADD_WRITE);
+        let mut package = ContractPackage::new(access_key, BTreeMap::new());
+        assert_eq!(package.current_version(), None);
+
+        let version_1 = package.add_version([2; 32], EntryPoints::new());
+        let version_2 = package.add_version([3; 32], EntryPoints::new());
+        assert_eq!(version_1, CONTRACT_INITIAL_VERSION);
+        assert_eq!(version_2, CONTRACT_INITIAL_VERSION + 1);
+        assert_eq!(
+            package
+                .current_version()
+                .map(|(version, header)| (version, header.contract_hash())),
+            Some((version_2, [3; 32]))
+        );
+
+        assert!(package.disable_version(version_2));
+        assert!(!package.disable_version(version_2 + 1));
+        assert_eq!(
+            package.current_version().map(|(version, _)| version),
+            Some(version_1)
+        );
+
+        // Versions of disabled contracts are not reused
+        let version_3 = package.add_version([4; 32], EntryPoints::new());
+        assert_eq!(version_3, version_2 + 1);
+    }
+
+    proptest! {
+        #[test]
+        fn test_value_contract_package(package in gens::contract_package_arb()) {
+            bytesrepr::test_serialization_roundtrip(&package);
+        }
+    }
+}
@@ -1,10 +1,14 @@
-use std::fmt;
+use std::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Div, Mul, Sub, SubAssign},
+};
 
 use num::Zero;
 
 use types::U512;
 
-use crate::motes::Motes;
+use crate::{motes::Motes, utils};
 
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Gas(U512);
@@ -28,15 +32,29 @@ impl Gas {
     pub fn checked_add(&self, rhs: Self) -> Option<Self> {
         self.0.checked_add(rhs.value()).map(Self::new)
     }
+
+    pub fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.value()).map(Self::new)
+    }
+
+    pub fn checked_mul(&self, rhs: Self) -> Option<Self> {
+        self.0.checked_mul(rhs.value()).map(Self::new)
+    }
+
+    pub fn checked_div(&self, rhs: Self) -> Option<Self> {
+        self.0.checked_div(rhs.value()).map(Self::new)
+    }
 }
 
+/// Formats the amount in decimal with commas separating groups of thousands, e.g.
+/// `1,000,000`.
 impl fmt::Display for Gas {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self.0)
+        f.pad(&utils::with_thousands_separators(&self.0.to_string()))
     }
 }
 
-impl std::ops::Add for Gas {
+impl Add for Gas {
     type Output = Gas;
 
     fn add(self, rhs: Self) -> Self::Output {
@@ -45,7 +63,7 @@ impl std::ops::Add for Gas {
     }
 }
 
-impl std::ops::Sub for Gas {
+impl Sub for Gas {
     type Output = Gas;
 
     fn sub(self, rhs: Self) -> Self::Output {
@@ -54,7 +72,7 @@ impl std::ops::Sub for Gas {
     }
 }
 
-impl std::ops::Div for Gas {
+impl Div for Gas {
     type Output = Gas;
 
     fn div(self, rhs: Self) -> Self::Output {
@@ -63,7 +81,7 @@ impl std::ops::Div for Gas {
     }
 }
 
-impl std::ops::Mul for Gas {
+impl Mul for Gas {
     type Output = Gas;
 
     fn mul(self, rhs: Self) -> Self::Output {
@@ -72,6 +90,48 @@ impl std::ops::Mul for Gas {
     }
 }
 
+impl AddAssign for Gas {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Gas {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sum for Gas {
+    fn sum<I: Iterator<Item = Gas>>(iter: I) -> Self {
+        iter.fold(Gas::zero(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Gas> for Gas {
+    fn sum<I: Iterator<Item = &'a Gas>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+impl From<U512> for Gas {
+    fn from(value: U512) -> Self {
+        Gas::new(value)
+    }
+}
+
+impl From<u64> for Gas {
+    fn from(value: u64) -> Self {
+        Gas::new(U512::from(value))
+    }
+}
+
+impl From<Gas> for U512 {
+    fn from(gas: Gas) -> Self {
+        gas.value()
+    }
+}
+
 impl Zero for Gas {
     fn zero() -> Self {
         Gas::new(U512::zero())
@@ -84,6 +144,8 @@ impl Zero for Gas {
 
 #[cfg(test)]
 mod tests {
+    use num::Zero;
+
     use types::U512;
 
     use crate::{gas::Gas, motes::Motes};
@@ -183,4 +245,58 @@ mod tests {
         let maybe = Gas::from_motes(motes, conv_rate);
         assert!(maybe.is_none(), "should be none due to divide by zero");
     }
+
+    #[test]
+    fn should_support_checked_sub() {
+        let left_gas = Gas::new(U512::from(10));
+        let right_gas = Gas::new(U512::from(20));
+        assert_eq!(
+            right_gas.checked_sub(left_gas),
+            Some(Gas::new(U512::from(10)))
+        );
+        assert_eq!(left_gas.checked_sub(right_gas), None, "should underflow");
+    }
+
+    #[test]
+    fn should_support_checked_mul_and_div() {
+        let gas = Gas::new(U512::MAX);
+        assert_eq!(gas.checked_mul(Gas::new(U512::from(2))), None);
+        assert_eq!(gas.checked_div(Gas::zero()), None);
+        assert_eq!(gas.checked_div(Gas::new(U512::from(1))), Some(gas));
+    }
+
+    #[test]
+    fn should_add_and_sub_assign() {
+        let mut gas = Gas::new(U512::from(10));
+        gas += Gas::new(U512::from(5));
+        assert_eq!(gas, Gas::new(U512::from(15)));
+        gas -= Gas::new(U512::from(15));
+        assert!(gas.is_zero());
+    }
+
+    #[test]
+    fn should_sum() {
+        let amounts = vec![Gas::from(1u64), Gas::from(2u64), Gas::from(3u64)];
+        let expected = Gas::from(6u64);
+        assert_eq!(amounts.iter().sum::<Gas>(), expected);
+        assert_eq!(amounts.into_iter().sum::<Gas>(), expected);
+        assert_eq!(Vec::<Gas>::new().into_iter().sum::<Gas>(), Gas::zero());
+    }
+
+    #[test]
+    fn should_convert_to_and_from_u512() {
+        let value = U512::from(42);
+        assert_eq!(Gas::from(value), Gas::new(value));
+        assert_eq!(U512::from(Gas::new(value)), value);
+        assert_eq!(Gas::from(42u64), Gas::new(value));
+    }
+
+    #[test]
+    fn should_display_with_thousands_separators() {
+        assert_eq!(Gas::from(0u64).to_string(), "0");
+        assert_eq!(Gas::from(999u64).to_string(), "999");
+        assert_eq!(Gas::from(1_000u64).to_string(), "1,000");
+        assert_eq!(Gas::from(12_345_678u64).to_string(), "12,345,678");
+        assert_eq!(format!("{:>7}", Gas::from(1_000u64)), "  1,000");
+    }
 }
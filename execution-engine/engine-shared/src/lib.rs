@@ -5,9 +5,12 @@ pub mod additive_map;
 pub mod gas;
 pub mod account;
 pub mod contract;
+pub mod contract_package;
 pub mod logging;
+pub mod metrics;
 pub mod motes;
 pub mod newtypes;
+pub mod op;
 pub mod os;
 pub mod socket;
 pub mod stored_value;
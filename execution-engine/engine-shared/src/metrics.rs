@@ -0,0 +1,406 @@
+//! A registry of counters, gauges and histograms describing the operation of the engine, rendered
+//! in the Prometheus text exposition format.
+//!
+//! The metrics recorded by the engine crates are the statics of this module, e.g.
+//! [`DEPLOY_DURATION`], all of which are registered with the global [`registry`].
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+
+/// Upper bounds of the buckets of [`DEPLOY_DURATION`], in seconds.
+const DURATION_BUCKETS: &[f64] = &[
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+/// Upper bounds of the buckets of [`DEPLOY_GAS_USED`].
+const GAS_BUCKETS: &[f64] = &[1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11];
+
+lazy_static! {
+    static ref REGISTRY: Registry = Registry::default();
+
+    /// The time taken to execute a deploy, including its payment code.
+    pub static ref DEPLOY_DURATION: Arc<Histogram> = REGISTRY.histogram(
+        "deploy_duration_seconds",
+        "Time taken to execute a deploy",
+        DURATION_BUCKETS,
+    );
+
+    /// The gas used by a deploy, including its payment code.
+    pub static ref DEPLOY_GAS_USED: Arc<Histogram> =
+        REGISTRY.histogram("deploy_gas_used", "Gas used by a deploy", GAS_BUCKETS);
+
+    /// The number of values read from tries.
+    pub static ref TRIE_STORE_READS: Arc<Counter> =
+        REGISTRY.counter("trie_store_reads_total", "Number of values read from tries");
+
+    /// The number of values written to tries.
+    pub static ref TRIE_STORE_WRITES: Arc<Counter> =
+        REGISTRY.counter("trie_store_writes_total", "Number of values written to tries");
+
+    /// The number of trie nodes put into the trie store.
+    pub static ref TRIE_STORE_PUTS: Arc<Counter> =
+        REGISTRY.counter("trie_store_puts_total", "Number of trie nodes put into the store");
+
+    /// The number of lookups of the module cache which found the module.
+    pub static ref MODULE_CACHE_HITS: Arc<Counter> =
+        REGISTRY.counter("module_cache_hits_total", "Number of module cache hits");
+
+    /// The number of lookups of the module cache which didn't find the module.
+    pub static ref MODULE_CACHE_MISSES: Arc<Counter> =
+        REGISTRY.counter("module_cache_misses_total", "Number of module cache misses");
+
+    /// The fraction of lookups of the module cache which found the module.
+    pub static ref MODULE_CACHE_HIT_RATE: Arc<Gauge> =
+        REGISTRY.gauge("module_cache_hit_rate", "Fraction of module cache lookups which hit");
+
+    /// The fraction of the LMDB map taken up by the pages in use.
+    pub static ref LMDB_MAP_UTILIZATION: Arc<Gauge> =
+        REGISTRY.gauge("lmdb_map_utilization", "Fraction of the LMDB map in use");
+}
+
+/// Returns the global registry, which holds every metric of this module.
+pub fn registry() -> &'static Registry {
+    lazy_static::initialize(&DEPLOY_DURATION);
+    lazy_static::initialize(&DEPLOY_GAS_USED);
+    lazy_static::initialize(&TRIE_STORE_READS);
+    lazy_static::initialize(&TRIE_STORE_WRITES);
+    lazy_static::initialize(&TRIE_STORE_PUTS);
+    lazy_static::initialize(&MODULE_CACHE_HITS);
+    lazy_static::initialize(&MODULE_CACHE_MISSES);
+    lazy_static::initialize(&MODULE_CACHE_HIT_RATE);
+    lazy_static::initialize(&LMDB_MAP_UTILIZATION);
+    &REGISTRY
+}
+
+/// Records a hit or miss of the module cache, and updates its hit rate.
+pub fn record_module_cache_lookup(hit: bool) {
+    if hit {
+        MODULE_CACHE_HITS.inc();
+    } else {
+        MODULE_CACHE_MISSES.inc();
+    }
+    let hits = MODULE_CACHE_HITS.get() as f64;
+    let misses = MODULE_CACHE_MISSES.get() as f64;
+    MODULE_CACHE_HIT_RATE.set(hits / (hits + misses));
+}
+
+fn add_to_f64(bits: &AtomicU64, value: f64) {
+    let mut current = bits.load(Ordering::Relaxed);
+    loop {
+        let new = (f64::from_bits(current) + value).to_bits();
+        match bits.compare_exchange_weak(current, new, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => return,
+            Err(actual) => current = actual,
+        }
+    }
+}
+
+/// A value which only ever increases.
+#[derive(Debug, Default)]
+pub struct Counter(AtomicU64);
+
+impl Counter {
+    pub fn inc(&self) {
+        self.inc_by(1)
+    }
+
+    pub fn inc_by(&self, value: u64) {
+        self.0.fetch_add(value, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// A value which can go up and down.
+#[derive(Debug, Default)]
+pub struct Gauge(AtomicU64);
+
+impl Gauge {
+    pub fn set(&self, value: f64) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn add(&self, value: f64) {
+        add_to_f64(&self.0, value)
+    }
+
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.0.load(Ordering::Relaxed))
+    }
+}
+
+/// A distribution of observed values, counted in buckets with fixed upper bounds.
+#[derive(Debug)]
+pub struct Histogram {
+    upper_bounds: Vec<f64>,
+    /// The number of observations in each bucket, with a last one for those above every bound.
+    bucket_counts: Vec<AtomicU64>,
+    count: AtomicU64,
+    sum: AtomicU64,
+}
+
+impl Histogram {
+    /// Creates a histogram with the given upper bounds of its buckets, in ascending order.
+    pub fn new(upper_bounds: &[f64]) -> Self {
+        assert!(
+            upper_bounds.windows(2).all(|pair| pair[0] < pair[1]),
+            "histogram bounds must be in ascending order"
+        );
+        Histogram {
+            upper_bounds: upper_bounds.to_vec(),
+            bucket_counts: (0..=upper_bounds.len())
+                .map(|_| AtomicU64::new(0))
+                .collect(),
+            count: AtomicU64::new(0),
+            sum: AtomicU64::new(0f64.to_bits()),
+        }
+    }
+
+    pub fn observe(&self, value: f64) {
+        let bucket = self
+            .upper_bounds
+            .iter()
+            .position(|upper_bound| value <= *upper_bound)
+            .unwrap_or_else(|| self.upper_bounds.len());
+        self.bucket_counts[bucket].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        add_to_f64(&self.sum, value);
+    }
+
+    pub fn observe_duration(&self, duration: Duration) {
+        self.observe(duration.as_secs_f64())
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    pub fn sum(&self) -> f64 {
+        f64::from_bits(self.sum.load(Ordering::Relaxed))
+    }
+
+    /// Returns the upper bound of each bucket along with the number of observations up to it.
+    pub fn cumulative_counts(&self) -> Vec<(f64, u64)> {
+        let mut cumulative_count = 0;
+        self.upper_bounds
+            .iter()
+            .copied()
+            .chain(Some(std::f64::INFINITY))
+            .zip(&self.bucket_counts)
+            .map(|(upper_bound, bucket_count)| {
+                cumulative_count += bucket_count.load(Ordering::Relaxed);
+                (upper_bound, cumulative_count)
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Metric {
+    Counter(Arc<Counter>),
+    Gauge(Arc<Gauge>),
+    Histogram(Arc<Histogram>),
+}
+
+impl Metric {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Metric::Counter(_) => "counter",
+            Metric::Gauge(_) => "gauge",
+            Metric::Histogram(_) => "histogram",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Family {
+    help: String,
+    metric: Metric,
+}
+
+/// A set of metrics, each registered under a unique name.
+#[derive(Debug, Default)]
+pub struct Registry(RwLock<BTreeMap<String, Family>>);
+
+impl Registry {
+    /// Returns the counter registered under `name`, registering a new one if there is none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a metric of another type is registered under `name`.
+    pub fn counter(&self, name: &str, help: &str) -> Arc<Counter> {
+        match self.register(name, help, || Metric::Counter(Default::default())) {
+            Metric::Counter(counter) => counter,
+            other => panic!("{} is registered as a {}", name, other.type_name()),
+        }
+    }
+
+    /// Returns the gauge registered under `name`, registering a new one if there is none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a metric of another type is registered under `name`.
+    pub fn gauge(&self, name: &str, help: &str) -> Arc<Gauge> {
+        match self.register(name, help, || Metric::Gauge(Default::default())) {
+            Metric::Gauge(gauge) => gauge,
+            other => panic!("{} is registered as a {}", name, other.type_name()),
+        }
+    }
+
+    /// Returns the histogram registered under `name`, registering a new one with the given
+    /// bucket bounds if there is none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a metric of another type is registered under `name`.
+    pub fn histogram(&self, name: &str, help: &str, upper_bounds: &[f64]) -> Arc<Histogram> {
+        match self.register(name, help, || {
+            Metric::Histogram(Arc::new(Histogram::new(upper_bounds)))
+        }) {
+            Metric::Histogram(histogram) => histogram,
+            other => panic!("{} is registered as a {}", name, other.type_name()),
+        }
+    }
+
+    fn register<F: FnOnce() -> Metric>(&self, name: &str, help: &str, new_metric: F) -> Metric {
+        let mut families = self.0.write().unwrap_or_else(|error| error.into_inner());
+        families
+            .entry(name.to_string())
+            .or_insert_with(|| Family {
+                help: help.to_string(),
+                metric: new_metric(),
+            })
+            .metric
+            .clone()
+    }
+
+    /// Renders every metric in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+        self.render_into(&mut output)
+            .expect("writing to a string should not fail");
+        output
+    }
+
+    fn render_into<W: Write>(&self, output: &mut W) -> fmt::Result {
+        let families = self.0.read().unwrap_or_else(|error| error.into_inner());
+        for (name, Family { help, metric }) in families.iter() {
+            writeln!(output, "# HELP {} {}", name, help)?;
+            writeln!(output, "# TYPE {} {}", name, metric.type_name())?;
+            match metric {
+                Metric::Counter(counter) => writeln!(output, "{} {}", name, counter.get())?,
+                Metric::Gauge(gauge) => writeln!(output, "{} {}", name, gauge.get())?,
+                Metric::Histogram(histogram) => {
+                    for (upper_bound, count) in histogram.cumulative_counts() {
+                        if upper_bound.is_infinite() {
+                            writeln!(output, "{}_bucket{{le=\"+Inf\"}} {}", name, count)?;
+                        } else {
+                            writeln!(
+                                output,
+                                "{}_bucket{{le=\"{}\"}} {}",
+                                name, upper_bound, count
+                            )?;
+                        }
+                    }
+                    writeln!(output, "{}_sum {}", name, histogram.sum())?;
+                    writeln!(output, "{}_count {}", name, histogram.count())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_count_observations_in_buckets() {
+        let histogram = Histogram::new(&[1.0, 10.0]);
+        histogram.observe(0.5);
+        histogram.observe(1.0);
+        histogram.observe(5.0);
+        histogram.observe(50.0);
+
+        assert_eq!(
+            histogram.cumulative_counts(),
+            vec![(1.0, 2), (10.0, 3), (std::f64::INFINITY, 4)]
+        );
+        assert_eq!(histogram.count(), 4);
+        assert_eq!(histogram.sum(), 56.5);
+    }
+
+    #[test]
+    fn should_return_registered_metric() {
+        let registry = Registry::default();
+        registry.counter("requests_total", "Requests").inc();
+        registry.counter("requests_total", "Requests").inc_by(2);
+        assert_eq!(registry.counter("requests_total", "Requests").get(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_not_register_metric_of_another_type() {
+        let registry = Registry::default();
+        registry.counter("requests", "Requests");
+        registry.gauge("requests", "Requests");
+    }
+
+    #[test]
+    fn should_render_prometheus_text_format() {
+        let registry = Registry::default();
+        registry
+            .counter("requests_total", "Number of requests")
+            .inc();
+        registry.gauge("utilization", "Fraction in use").set(0.25);
+        registry
+            .histogram("duration_seconds", "Time taken", &[0.5, 1.0])
+            .observe(0.75);
+
+        let expected = "\
+# HELP duration_seconds Time taken
+# TYPE duration_seconds histogram
+duration_seconds_bucket{le=\"0.5\"} 0
+duration_seconds_bucket{le=\"1\"} 1
+duration_seconds_bucket{le=\"+Inf\"} 1
+duration_seconds_sum 0.75
+duration_seconds_count 1
+# HELP requests_total Number of requests
+# TYPE requests_total counter
+requests_total 1
+# HELP utilization Fraction in use
+# TYPE utilization gauge
+utilization 0.25
+";
+        assert_eq!(registry.render(), expected);
+    }
+
+    #[test]
+    fn should_register_engine_metrics_globally() {
+        let rendered = registry().render();
+        for name in &[
+            "deploy_duration_seconds",
+            "deploy_gas_used",
+            "trie_store_reads_total",
+            "module_cache_hit_rate",
+            "lmdb_map_utilization",
+        ] {
+            assert!(
+                rendered.contains(&format!("# TYPE {} ", name)),
+                "{} should be registered",
+                name
+            );
+        }
+    }
+}
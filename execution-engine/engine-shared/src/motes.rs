@@ -1,10 +1,14 @@
-use std::fmt;
+use std::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Div, Mul, Sub, SubAssign},
+};
 
 use num::Zero;
 
 use types::U512;
 
-use crate::gas::Gas;
+use crate::{gas::Gas, utils};
 
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Motes(U512);
@@ -18,6 +22,18 @@ impl Motes {
         self.0.checked_add(rhs.value()).map(Self::new)
     }
 
+    pub fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.value()).map(Self::new)
+    }
+
+    pub fn checked_mul(&self, rhs: Self) -> Option<Self> {
+        self.0.checked_mul(rhs.value()).map(Self::new)
+    }
+
+    pub fn checked_div(&self, rhs: Self) -> Option<Self> {
+        self.0.checked_div(rhs.value()).map(Self::new)
+    }
+
     pub fn value(&self) -> U512 {
         self.0
     }
@@ -29,13 +45,15 @@ impl Motes {
     }
 }
 
+/// Formats the amount in decimal with commas separating groups of thousands, e.g.
+/// `1,000,000`.
 impl fmt::Display for Motes {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self.0)
+        f.pad(&utils::with_thousands_separators(&self.0.to_string()))
     }
 }
 
-impl std::ops::Add for Motes {
+impl Add for Motes {
     type Output = Motes;
 
     fn add(self, rhs: Self) -> Self::Output {
@@ -44,7 +62,7 @@ impl std::ops::Add for Motes {
     }
 }
 
-impl std::ops::Sub for Motes {
+impl Sub for Motes {
     type Output = Motes;
 
     fn sub(self, rhs: Self) -> Self::Output {
@@ -53,7 +71,7 @@ impl std::ops::Sub for Motes {
     }
 }
 
-impl std::ops::Div for Motes {
+impl Div for Motes {
     type Output = Motes;
 
     fn div(self, rhs: Self) -> Self::Output {
@@ -62,7 +80,7 @@ impl std::ops::Div for Motes {
     }
 }
 
-impl std::ops::Mul for Motes {
+impl Mul for Motes {
     type Output = Motes;
 
     fn mul(self, rhs: Self) -> Self::Output {
@@ -71,6 +89,48 @@ impl std::ops::Mul for Motes {
     }
 }
 
+impl AddAssign for Motes {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Motes {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sum for Motes {
+    fn sum<I: Iterator<Item = Motes>>(iter: I) -> Self {
+        iter.fold(Motes::zero(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Motes> for Motes {
+    fn sum<I: Iterator<Item = &'a Motes>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+impl From<U512> for Motes {
+    fn from(value: U512) -> Self {
+        Motes::new(value)
+    }
+}
+
+impl From<u64> for Motes {
+    fn from(value: u64) -> Self {
+        Motes::new(U512::from(value))
+    }
+}
+
+impl From<Motes> for U512 {
+    fn from(motes: Motes) -> Self {
+        motes.value()
+    }
+}
+
 impl Zero for Motes {
     fn zero() -> Self {
         Motes::new(U512::zero())
@@ -83,6 +143,8 @@ impl Zero for Motes {
 
 #[cfg(test)]
 mod tests {
+    use num::Zero;
+
     use types::U512;
 
     use crate::{gas::Gas, motes::Motes};
@@ -198,4 +260,65 @@ mod tests {
         let maybe = Motes::from_gas(gas, conv_rate);
         assert!(maybe.is_none(), "should be none due to overflow");
     }
+
+    #[test]
+    fn should_support_checked_sub() {
+        let left_motes = Motes::new(U512::from(10));
+        let right_motes = Motes::new(U512::from(20));
+        assert_eq!(
+            right_motes.checked_sub(left_motes),
+            Some(Motes::new(U512::from(10)))
+        );
+        assert_eq!(
+            left_motes.checked_sub(right_motes),
+            None,
+            "should underflow"
+        );
+    }
+
+    #[test]
+    fn should_support_checked_mul_and_div() {
+        let motes = Motes::new(U512::MAX);
+        assert_eq!(motes.checked_mul(Motes::new(U512::from(2))), None);
+        assert_eq!(motes.checked_div(Motes::zero()), None);
+        assert_eq!(motes.checked_div(Motes::new(U512::from(1))), Some(motes));
+    }
+
+    #[test]
+    fn should_add_and_sub_assign() {
+        let mut motes = Motes::new(U512::from(10));
+        motes += Motes::new(U512::from(5));
+        assert_eq!(motes, Motes::new(U512::from(15)));
+        motes -= Motes::new(U512::from(15));
+        assert!(motes.is_zero());
+    }
+
+    #[test]
+    fn should_sum() {
+        let amounts = vec![Motes::from(1u64), Motes::from(2u64), Motes::from(3u64)];
+        let expected = Motes::from(6u64);
+        assert_eq!(amounts.iter().sum::<Motes>(), expected);
+        assert_eq!(amounts.into_iter().sum::<Motes>(), expected);
+        assert_eq!(
+            Vec::<Motes>::new().into_iter().sum::<Motes>(),
+            Motes::zero()
+        );
+    }
+
+    #[test]
+    fn should_convert_to_and_from_u512() {
+        let value = U512::from(42);
+        assert_eq!(Motes::from(value), Motes::new(value));
+        assert_eq!(U512::from(Motes::new(value)), value);
+        assert_eq!(Motes::from(42u64), Motes::new(value));
+    }
+
+    #[test]
+    fn should_display_with_thousands_separators() {
+        assert_eq!(Motes::from(0u64).to_string(), "0");
+        assert_eq!(Motes::from(999u64).to_string(), "999");
+        assert_eq!(Motes::from(1_000u64).to_string(), "1,000");
+        assert_eq!(Motes::from(12_345_678u64).to_string(), "12,345,678");
+        assert_eq!(format!("{:>7}", Motes::from(1_000u64)), "  1,000");
+    }
 }
@@ -102,6 +102,14 @@ macro_rules! make_array_newtype {
             fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
                 self.0.to_bytes()
             }
+
+            fn serialized_length(&self) -> usize {
+                self.0.serialized_length()
+            }
+
+            fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+                self.0.write_bytes(writer)
+            }
         }
 
         impl bytesrepr::FromBytes for $name {
@@ -2,7 +2,7 @@
 mod macros;
 
 use core::array::TryFromSliceError;
-use std::{convert::TryFrom, fmt};
+use std::{convert::TryFrom, fmt, str::FromStr};
 
 use blake2::{
     digest::{Input, VariableOutput},
@@ -94,6 +94,14 @@ impl ToBytes for Blake2bHash {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         ToBytes::to_bytes(&self.0)
     }
+
+    fn serialized_length(&self) -> usize {
+        BLAKE2B_DIGEST_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.0.write_bytes(writer)
+    }
 }
 
 impl FromBytes for Blake2bHash {
@@ -121,6 +129,15 @@ impl fmt::Display for CorrelationId {
     }
 }
 
+/// Parses a correlation id supplied by a client, e.g. to continue its trace.
+impl FromStr for CorrelationId {
+    type Err = uuid::Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(input).map(CorrelationId)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -142,6 +159,17 @@ mod tests {
         )
     }
 
+    #[test]
+    fn should_parse_displayed_correlation_id() {
+        let correlation_id = CorrelationId::new();
+        let parsed: CorrelationId = correlation_id
+            .to_string()
+            .parse()
+            .expect("should parse correlation id");
+        assert_eq!(parsed, correlation_id);
+        assert!("not a uuid".parse::<CorrelationId>().is_err());
+    }
+
     #[test]
     fn should_support_to_string() {
         let correlation_id = CorrelationId::new();
@@ -0,0 +1,112 @@
+use std::{
+    default::Default,
+    fmt::{self, Display, Formatter},
+    ops::{Add, AddAssign},
+};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Op {
+    Read,
+    Write,
+    Add,
+    NoOp,
+}
+
+impl Op {
+    /// Combines two operations on the same key into the single operation describing both.
+    ///
+    /// `Op::NoOp` is the identity.  Reads combine into a read and adds into an add; any other
+    /// combination is a write.  The combination is associative and commutative, so the order in
+    /// which the operations of several deploys are merged does not matter.
+    pub fn combine(self, other: Op) -> Op {
+        match (self, other) {
+            (a, Op::NoOp) => a,
+            (Op::NoOp, b) => b,
+            (Op::Read, Op::Read) => Op::Read,
+            (Op::Add, Op::Add) => Op::Add,
+            _ => Op::Write,
+        }
+    }
+}
+
+impl Add for Op {
+    type Output = Op;
+
+    fn add(self, other: Op) -> Op {
+        self.combine(other)
+    }
+}
+
+impl AddAssign for Op {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Default for Op {
+    fn default() -> Self {
+        Op::NoOp
+    }
+}
+
+pub mod gens {
+    use proptest::prelude::*;
+
+    use super::Op;
+
+    pub fn op_arb() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            Just(Op::Read),
+            Just(Op::Write),
+            Just(Op::Add),
+            Just(Op::NoOp),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use super::{gens, Op};
+
+    proptest! {
+        #[test]
+        fn no_op_should_be_identity(op in gens::op_arb()) {
+            assert_eq!(op, Op::combine(op, Op::NoOp));
+            assert_eq!(op, Op::combine(Op::NoOp, op));
+        }
+
+        #[test]
+        fn combine_should_be_associative(
+            a in gens::op_arb(),
+            b in gens::op_arb(),
+            c in gens::op_arb()
+        ) {
+            assert_eq!(
+                Op::combine(Op::combine(a, b), c),
+                Op::combine(a, Op::combine(b, c))
+            );
+        }
+
+        #[test]
+        fn combine_should_be_commutative(a in gens::op_arb(), b in gens::op_arb()) {
+            assert_eq!(Op::combine(a, b), Op::combine(b, a));
+        }
+    }
+
+    #[test]
+    fn should_combine_mixed_ops_into_write() {
+        assert_eq!(Op::Read, Op::combine(Op::Read, Op::Read));
+        assert_eq!(Op::Add, Op::combine(Op::Add, Op::Add));
+        assert_eq!(Op::Write, Op::combine(Op::Read, Op::Add));
+        assert_eq!(Op::Write, Op::combine(Op::Add, Op::Read));
+        assert_eq!(Op::Write, Op::combine(Op::Write, Op::Read));
+    }
+}
@@ -1,17 +1,21 @@
-use std::{convert::TryFrom, mem, u32};
+use std::{convert::TryFrom, mem};
 
 use types::{
     bytesrepr::{self, FromBytes, ToBytes},
     CLValue,
 };
 
-use crate::{account::Account, contract::Contract, transform::TypeMismatch};
+use crate::{
+    account::Account, contract::Contract, contract_package::ContractPackage,
+    transform::TypeMismatch,
+};
 
 #[repr(u8)]
 enum Tag {
     CLValue = 0,
     Account = 1,
     Contract = 2,
+    ContractPackage = 3,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -19,6 +23,7 @@ pub enum StoredValue {
     CLValue(CLValue),
     Account(Account),
     Contract(Contract),
+    ContractPackage(ContractPackage),
 }
 
 impl StoredValue {
@@ -43,11 +48,19 @@ impl StoredValue {
         }
     }
 
+    pub fn as_contract_package(&self) -> Option<&ContractPackage> {
+        match self {
+            StoredValue::ContractPackage(contract_package) => Some(contract_package),
+            _ => None,
+        }
+    }
+
     pub fn type_name(&self) -> String {
         match self {
             StoredValue::CLValue(cl_value) => format!("{:?}", cl_value.cl_type()),
             StoredValue::Account(_) => "Account".to_string(),
             StoredValue::Contract(_) => "Contract".to_string(),
+            StoredValue::ContractPackage(_) => "ContractPackage".to_string(),
         }
     }
 }
@@ -94,24 +107,56 @@ impl TryFrom<StoredValue> for Contract {
     }
 }
 
-fn to_bytes<T: ToBytes>(value: &T, tag: Tag) -> Result<Vec<u8>, bytesrepr::Error> {
-    let mut bytes = value.to_bytes()?;
-    if bytes.len() >= u32::max_value() as usize - mem::size_of::<Tag>() {
-        return Err(bytesrepr::Error::OutOfMemoryError);
+impl TryFrom<StoredValue> for ContractPackage {
+    type Error = TypeMismatch;
+
+    fn try_from(stored_value: StoredValue) -> Result<Self, Self::Error> {
+        match stored_value {
+            StoredValue::ContractPackage(contract_package) => Ok(contract_package),
+            _ => Err(TypeMismatch::new(
+                "ContractPackage".to_string(),
+                stored_value.type_name(),
+            )),
+        }
     }
+}
 
-    let mut result = Vec::with_capacity(bytes.len() + mem::size_of::<Tag>());
-    result.push(tag as u8);
-    result.append(&mut bytes);
-    Ok(result)
+fn write_bytes<T: ToBytes>(
+    value: &T,
+    tag: Tag,
+    writer: &mut Vec<u8>,
+) -> Result<(), bytesrepr::Error> {
+    writer.push(tag as u8);
+    value.write_bytes(writer)
 }
 
 impl ToBytes for StoredValue {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        mem::size_of::<Tag>()
+            + match self {
+                StoredValue::CLValue(cl_value) => cl_value.serialized_length(),
+                StoredValue::Account(account) => account.serialized_length(),
+                StoredValue::Contract(contract) => contract.serialized_length(),
+                StoredValue::ContractPackage(contract_package) => {
+                    contract_package.serialized_length()
+                }
+            }
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
         match self {
-            StoredValue::CLValue(cl_value) => to_bytes(cl_value, Tag::CLValue),
-            StoredValue::Account(account) => to_bytes(account, Tag::Account),
-            StoredValue::Contract(contract) => to_bytes(contract, Tag::Contract),
+            StoredValue::CLValue(cl_value) => write_bytes(cl_value, Tag::CLValue, writer),
+            StoredValue::Account(account) => write_bytes(account, Tag::Account, writer),
+            StoredValue::Contract(contract) => write_bytes(contract, Tag::Contract, writer),
+            StoredValue::ContractPackage(contract_package) => {
+                write_bytes(contract_package, Tag::ContractPackage, writer)
+            }
         }
     }
 }
@@ -126,6 +171,11 @@ impl FromBytes for StoredValue {
                 .map(|(account, remainder)| (StoredValue::Account(account), remainder)),
             tag if tag == Tag::Contract as u8 => Contract::from_bytes(remainder)
                 .map(|(contract, remainder)| (StoredValue::Contract(contract), remainder)),
+            tag if tag == Tag::ContractPackage as u8 => {
+                ContractPackage::from_bytes(remainder).map(|(contract_package, remainder)| {
+                    (StoredValue::ContractPackage(contract_package), remainder)
+                })
+            }
             _ => Err(bytesrepr::Error::FormattingError),
         }
     }
@@ -137,13 +187,110 @@ pub mod gens {
     use types::gens::cl_value_arb;
 
     use super::StoredValue;
-    use crate::{account::gens::account_arb, contract::gens::contract_arb};
+    use crate::{
+        account::gens::account_arb, contract::gens::contract_arb,
+        contract_package::gens::contract_package_arb,
+    };
 
     pub fn stored_value_arb() -> impl Strategy<Value = StoredValue> {
         prop_oneof![
             cl_value_arb().prop_map(StoredValue::CLValue),
             account_arb().prop_map(StoredValue::Account),
             contract_arb().prop_map(StoredValue::Contract),
+            contract_package_arb().prop_map(StoredValue::ContractPackage),
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use proptest::proptest;
+
+    use types::{
+        account::{PublicKey, PurseId, Weight},
+        bytesrepr::{self, ToBytes},
+        AccessRights, CLValue, Key, ProtocolVersion, URef,
+    };
+
+    use super::{gens, StoredValue};
+    use crate::{
+        account::{Account, ActionThresholds, AssociatedKeys},
+        contract::Contract,
+    };
+
+    /// Encodings of stored values produced by the encoders of the 1.0.0 release, i.e. before
+    /// contract packages were introduced.
+    mod v1_0_0 {
+        pub const CL_VALUE: &str = "0008000000010000000000000005";
+        pub const ACCOUNT: &str = concat!(
+            "01",
+            "0101010101010101010101010101010101010101010101010101010101010101",
+            "01000000040000006d696e7402",
+            "010101010101010101010101010101010101010101010101010101010101010101",
+            "07",
+            "020202020202020202020202020202020202020202020202020202020202020201",
+            "07",
+            "01000000",
+            "0101010101010101010101010101010101010101010101010101010101010101",
+            "010101"
+        );
+        pub const CONTRACT: &str = concat!(
+            "02",
+            "080000000061736d01000000",
+            "01000000010000006101",
+            "0303030303030303030303030303030303030303030303030303030303030303",
+            "010000000000000000000000"
+        );
+    }
+
+    fn assert_decodes(fixture: &str, expected: StoredValue) {
+        let bytes = base16::decode(fixture).expect("fixture should be valid hex");
+        let decoded: StoredValue = bytesrepr::deserialize(bytes.clone())
+            .unwrap_or_else(|error| panic!("failed to decode fixture {}: {}", fixture, error));
+        assert_eq!(decoded, expected);
+        assert_eq!(expected.to_bytes().expect("should serialize"), bytes);
+    }
+
+    #[test]
+    fn should_decode_v1_0_0_fixtures() {
+        let uref = URef::new([1; 32], AccessRights::READ_ADD_WRITE);
+
+        assert_decodes(
+            v1_0_0::CL_VALUE,
+            StoredValue::CLValue(CLValue::from_t(1u64).unwrap()),
+        );
+
+        let mut named_keys = BTreeMap::new();
+        named_keys.insert("mint".to_string(), Key::URef(uref));
+        let account = Account::new(
+            [1; 32],
+            named_keys,
+            PurseId::new(URef::new([2; 32], AccessRights::READ_ADD_WRITE)),
+            AssociatedKeys::new(PublicKey::new([1; 32]), Weight::new(1)),
+            ActionThresholds::new(Weight::new(1), Weight::new(1)).unwrap(),
+        );
+        assert_decodes(v1_0_0::ACCOUNT, StoredValue::Account(account));
+
+        let mut named_keys = BTreeMap::new();
+        named_keys.insert("a".to_string(), Key::Hash([3; 32]));
+        let contract = Contract::new(
+            vec![0, 0x61, 0x73, 0x6d, 1, 0, 0, 0],
+            named_keys,
+            ProtocolVersion::V1_0_0,
+        );
+        assert_decodes(v1_0_0::CONTRACT, StoredValue::Contract(contract));
+    }
+
+    proptest! {
+        #[test]
+        fn should_serialize_into_exactly_sized_buffer(
+            stored_value in gens::stored_value_arb()
+        ) {
+            let bytes = stored_value.to_bytes().expect("should serialize");
+            assert_eq!(bytes.len(), stored_value.serialized_length());
+            assert_eq!(bytes.capacity(), bytes.len());
+        }
+    }
+}
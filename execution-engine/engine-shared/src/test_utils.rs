@@ -40,6 +40,14 @@ pub fn wasm_costs_mock() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 3,
         opcodes_div: 8,
+        storage_write_byte: 0,
+        read_cost: 0,
+        write_cost: 0,
+        add_cost: 0,
+        hash_base: 1_000,
+        hash_byte: 1,
+        max_memory: 64,
+        max_table_size: 4096,
     }
 }
 
@@ -55,5 +63,13 @@ pub fn wasm_costs_free() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 1,
         opcodes_div: 1,
+        storage_write_byte: 0,
+        read_cost: 0,
+        write_cost: 0,
+        add_cost: 0,
+        hash_base: 0,
+        hash_byte: 0,
+        max_memory: 64,
+        max_table_size: 4096,
     }
 }
@@ -171,48 +171,24 @@ impl Transform {
                     let found = format!("{:?}", cl_value.cl_type());
                     Err(TypeMismatch::new(expected, found).into())
                 }
+                StoredValue::ContractPackage(_) => {
+                    let expected = "Contract or Account".to_string();
+                    let found = "ContractPackage".to_string();
+                    Err(TypeMismatch::new(expected, found).into())
+                }
             },
             Transform::Failure(error) => Err(error),
         }
     }
-}
-
-/// Combines numeric `Transform`s into a single `Transform`. This is done by unwrapping the
-/// `Transform` to obtain the underlying value, performing the wrapping addition then wrapping up as
-/// a `Transform` again.
-fn wrapped_transform_addition<T>(i: T, b: Transform, expected: &str) -> Transform
-where
-    T: WrappingAdd
-        + AsPrimitive<i32>
-        + From<u32>
-        + From<u64>
-        + Into<Transform>
-        + TryFrom<Transform, Error = String>,
-    i32: AsPrimitive<T>,
-{
-    if let Transform::AddInt32(j) = b {
-        i.wrapping_add(&j.as_()).into()
-    } else if let Transform::AddUInt64(j) = b {
-        i.wrapping_add(&j.into()).into()
-    } else {
-        match T::try_from(b) {
-            Err(b_type) => Transform::Failure(
-                TypeMismatch {
-                    expected: String::from(expected),
-                    found: b_type,
-                }
-                .into(),
-            ),
-
-            Ok(j) => i.wrapping_add(&j).into(),
-        }
-    }
-}
 
-impl Add for Transform {
-    type Output = Transform;
-
-    fn add(self, other: Transform) -> Transform {
+    /// Combines two transforms of the same key into the single transform which has the same
+    /// effect as applying `self` and then `other`.  This is how the effects of deploys are merged.
+    ///
+    /// `Transform::Identity` is the identity on both sides.  A `Transform::Write` overrides
+    /// everything before it, a `Transform::Failure` absorbs everything combined with it, and
+    /// numeric additions wrap around.  Combining is associative as long as no failure arises and
+    /// the additions are of the same type as each other and as any written value.
+    pub fn combine(self, other: Transform) -> Transform {
         match (self, other) {
             (a, Transform::Identity) => a,
             (Transform::Identity, b) => b,
@@ -262,6 +238,46 @@ impl Add for Transform {
     }
 }
 
+/// Combines numeric `Transform`s into a single `Transform`. This is done by unwrapping the
+/// `Transform` to obtain the underlying value, performing the wrapping addition then wrapping up as
+/// a `Transform` again.
+fn wrapped_transform_addition<T>(i: T, b: Transform, expected: &str) -> Transform
+where
+    T: WrappingAdd
+        + AsPrimitive<i32>
+        + From<u32>
+        + From<u64>
+        + Into<Transform>
+        + TryFrom<Transform, Error = String>,
+    i32: AsPrimitive<T>,
+{
+    if let Transform::AddInt32(j) = b {
+        i.wrapping_add(&j.as_()).into()
+    } else if let Transform::AddUInt64(j) = b {
+        i.wrapping_add(&j.into()).into()
+    } else {
+        match T::try_from(b) {
+            Err(b_type) => Transform::Failure(
+                TypeMismatch {
+                    expected: String::from(expected),
+                    found: b_type,
+                }
+                .into(),
+            ),
+
+            Ok(j) => i.wrapping_add(&j).into(),
+        }
+    }
+}
+
+impl Add for Transform {
+    type Output = Transform;
+
+    fn add(self, other: Transform) -> Transform {
+        self.combine(other)
+    }
+}
+
 impl AddAssign for Transform {
     fn add_assign(&mut self, other: Self) {
         *self = self.clone() + other;
@@ -310,6 +326,11 @@ pub mod gens {
 #[cfg(test)]
 mod tests {
     use num::{Bounded, Num};
+    use proptest::{
+        collection::{btree_map, vec},
+        prelude::{any, Just, Strategy},
+        prop_oneof, proptest,
+    };
 
     use types::{account::PurseId, AccessRights, ProtocolVersion, URef, U128, U256, U512};
 
@@ -317,6 +338,7 @@ mod tests {
     use crate::{
         account::{Account, ActionThresholds, AssociatedKeys},
         contract::Contract,
+        transform::gens::transform_arb,
     };
 
     const ZERO_ARRAY: [u8; 32] = [0; 32];
@@ -753,4 +775,118 @@ mod tests {
         assert_eq!(ZERO_U512, add(MAX_U512, ONE_U512));
         assert_eq!(MAX_U512 - 1, add(MAX_U512, MAX_U512));
     }
+
+    fn write_cl_value<T: CLTyped + ToBytes>(value: T) -> Transform {
+        Transform::Write(StoredValue::CLValue(CLValue::from_t(value).unwrap()))
+    }
+
+    fn i32_transform_arb() -> impl Strategy<Value = Transform> {
+        prop_oneof![
+            Just(Transform::Identity),
+            any::<i32>().prop_map(write_cl_value),
+            any::<i32>().prop_map(Transform::AddInt32),
+        ]
+    }
+
+    fn u64_transform_arb() -> impl Strategy<Value = Transform> {
+        prop_oneof![
+            Just(Transform::Identity),
+            any::<u64>().prop_map(write_cl_value),
+            any::<u64>().prop_map(Transform::AddUInt64),
+        ]
+    }
+
+    fn u512_transform_arb() -> impl Strategy<Value = Transform> {
+        let u512_arb = || {
+            vec(any::<u8>(), 64).prop_map(|u| {
+                let mut buf: [u8; 64] = [0u8; 64];
+                buf.copy_from_slice(&u);
+                U512::from(buf)
+            })
+        };
+        prop_oneof![
+            Just(Transform::Identity),
+            u512_arb().prop_map(write_cl_value),
+            u512_arb().prop_map(Transform::AddUInt512),
+        ]
+    }
+
+    fn add_keys_transform_arb() -> impl Strategy<Value = Transform> {
+        prop_oneof![
+            Just(Transform::Identity),
+            btree_map("[a-z]{1,4}", any::<[u8; 32]>().prop_map(Key::Hash), 0..5)
+                .prop_map(Transform::AddKeys),
+        ]
+    }
+
+    fn assert_associative(a: Transform, b: Transform, c: Transform) {
+        assert_eq!(
+            Transform::combine(Transform::combine(a.clone(), b.clone()), c.clone()),
+            Transform::combine(a, Transform::combine(b, c))
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn identity_should_be_neutral(transform in transform_arb()) {
+            assert_eq!(
+                transform,
+                Transform::combine(transform.clone(), Transform::Identity)
+            );
+            assert_eq!(
+                transform,
+                Transform::combine(Transform::Identity, transform.clone())
+            );
+        }
+
+
+        #[test]
+        fn combine_i32_should_be_associative(
+            a in i32_transform_arb(),
+            b in i32_transform_arb(),
+            c in i32_transform_arb()
+        ) {
+            assert_associative(a, b, c);
+        }
+
+        #[test]
+        fn combine_u64_should_be_associative(
+            a in u64_transform_arb(),
+            b in u64_transform_arb(),
+            c in u64_transform_arb()
+        ) {
+            assert_associative(a, b, c);
+        }
+
+        #[test]
+        fn combine_u512_should_be_associative(
+            a in u512_transform_arb(),
+            b in u512_transform_arb(),
+            c in u512_transform_arb()
+        ) {
+            assert_associative(a, b, c);
+        }
+
+        #[test]
+        fn combine_add_keys_should_be_associative(
+            a in add_keys_transform_arb(),
+            b in add_keys_transform_arb(),
+            c in add_keys_transform_arb()
+        ) {
+            assert_associative(a, b, c);
+        }
+
+        #[test]
+        fn combine_should_match_applying_in_sequence(
+            value in any::<u64>(),
+            a in u64_transform_arb(),
+            b in u64_transform_arb()
+        ) {
+            let stored_value = StoredValue::CLValue(CLValue::from_t(value).unwrap());
+            let applied_in_sequence = b
+                .clone()
+                .apply(a.clone().apply(stored_value.clone()).unwrap());
+            assert_eq!(applied_in_sequence, Transform::combine(a, b).apply(stored_value));
+        }
+    }
 }
@@ -19,6 +19,19 @@ where
     }
 }
 
+/// Inserts a comma between each group of three digits of the decimal number `digits`, counting
+/// from the right.
+pub(crate) fn with_thousands_separators(digits: &str) -> String {
+    let mut ret = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            ret.push(',');
+        }
+        ret.push(digit);
+    }
+    ret
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,6 +49,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn should_separate_thousands() {
+        assert_eq!(with_thousands_separators("0"), "0");
+        assert_eq!(with_thousands_separators("999"), "999");
+        assert_eq!(with_thousands_separators("1000"), "1,000");
+        assert_eq!(with_thousands_separators("123456"), "123,456");
+        assert_eq!(with_thousands_separators("1234567"), "1,234,567");
+    }
+
     #[test]
     fn should_ser_to_json() {
         let sermock = SerMock {
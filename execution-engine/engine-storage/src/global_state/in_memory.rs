@@ -1,4 +1,4 @@
-use std::{ops::Deref, sync::Arc};
+use std::{collections::BTreeMap, ops::Deref, sync::Arc};
 
 use engine_shared::{
     additive_map::AdditiveMap,
@@ -10,10 +10,13 @@ use types::{Key, ProtocolVersion};
 
 use crate::{
     error::{self, in_memory},
-    global_state::{commit, CommitResult, StateProvider, StateReader},
+    global_state::{
+        commit, commit_batch, CommitResult, KeysPage, KeysReader, StateProvider, StateReader,
+        TrieStats, TrieStatsMode,
+    },
     protocol_data::ProtocolData,
     protocol_data_store::in_memory::InMemoryProtocolDataStore,
-    store::Store,
+    store::{Store, StoreExt},
     transaction_source::{
         in_memory::{InMemoryEnvironment, InMemoryReadTransaction},
         Transaction, TransactionSource,
@@ -139,6 +142,21 @@ impl StateReader<Key, StoredValue> for InMemoryGlobalStateView {
     }
 }
 
+impl KeysReader<Key, StoredValue> for InMemoryGlobalStateView {
+    fn keys(&self, correlation_id: CorrelationId) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = operations::keys::<
+            Key,
+            StoredValue,
+            InMemoryReadTransaction,
+            InMemoryTrieStore,
+            Self::Error,
+        >(correlation_id, &txn, self.store.deref(), &self.root_hash)?;
+        txn.commit()?;
+        Ok(ret)
+    }
+}
+
 impl StateProvider for InMemoryGlobalState {
     type Error = error::Error;
 
@@ -168,11 +186,24 @@ impl StateProvider for InMemoryGlobalState {
             &self.trie_store,
             correlation_id,
             prestate_hash,
-            effects,
+            &effects,
         )?;
         Ok(commit_result)
     }
 
+    fn commit_batch(
+        &self,
+        correlation_id: CorrelationId,
+        batch: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<Vec<CommitResult>, Self::Error> {
+        commit_batch::<InMemoryEnvironment, InMemoryTrieStore, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            &batch,
+        )
+    }
+
     fn put_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -194,9 +225,68 @@ impl StateProvider for InMemoryGlobalState {
         Ok(result)
     }
 
+    fn list_protocol_data(&self) -> Result<BTreeMap<ProtocolVersion, ProtocolData>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let result = self.protocol_data_store.get_all(&txn)?;
+        txn.commit()?;
+        Ok(result.into_iter().collect())
+    }
+
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn stats(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        mode: TrieStatsMode,
+    ) -> Result<Option<TrieStats>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = operations::stats::<
+            Key,
+            StoredValue,
+            InMemoryReadTransaction,
+            InMemoryTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &state_hash,
+            mode,
+        )?;
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        prefix: &[u8],
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Option<KeysPage<Key>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = operations::keys_with_prefix::<
+            Key,
+            StoredValue,
+            InMemoryReadTransaction,
+            InMemoryTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &state_hash,
+            prefix,
+            after,
+            limit,
+        )?;
+        txn.commit()?;
+        Ok(ret)
+    }
 }
 
 #[cfg(test)]
@@ -204,6 +294,7 @@ mod tests {
     use types::CLValue;
 
     use super::*;
+    use crate::global_state::{apply_transforms, ApplyTransformsError};
 
     #[derive(Debug, Clone)]
     struct TestPair {
@@ -263,6 +354,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn keys_of_a_checkout_return_expected_keys() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        let mut keys = checkout.keys(correlation_id).unwrap();
+        keys.sort();
+        let expected: Vec<Key> = create_test_pairs()
+            .iter()
+            .map(|TestPair { key, .. }| *key)
+            .collect();
+        assert_eq!(expected, keys);
+    }
+
     #[test]
     fn checkout_fails_if_unknown_hash_is_given() {
         let (state, _) = create_test_state();
@@ -343,6 +448,90 @@ mod tests {
         );
     }
 
+    #[test]
+    fn commit_of_unchanged_values_keeps_state_root() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+        let (state, root_hash) = create_test_state();
+
+        let mut effects = AdditiveMap::new();
+        effects.insert(test_pairs[0].key, Transform::Identity);
+        effects.insert(
+            test_pairs[1].key,
+            Transform::Write(test_pairs[1].value.clone()),
+        );
+
+        match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => assert_eq!(state_root, root_hash),
+            _ => panic!("commit failed"),
+        }
+    }
+
+    #[test]
+    fn apply_transforms_matches_commit_without_changing_state() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+
+        let mut effects = AdditiveMap::new();
+        effects.insert(Key::Account([1u8; 32]), Transform::AddInt32(41));
+        effects.insert(
+            Key::Account([3u8; 32]),
+            Transform::Write(StoredValue::CLValue(CLValue::from_t(3_i32).unwrap())),
+        );
+
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        let state_delta = apply_transforms(&checkout, correlation_id, effects.clone()).unwrap();
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+
+        assert_eq!(state_delta.len(), 2);
+        for (key, value) in state_delta {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+        assert_eq!(
+            None,
+            checkout
+                .read(correlation_id, &Key::Account([3u8; 32]))
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn apply_transforms_fails_on_missing_key() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let missing_key = Key::Account([3u8; 32]);
+
+        let mut effects = AdditiveMap::new();
+        effects.insert(missing_key, Transform::AddInt32(1));
+
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        match apply_transforms(&checkout, correlation_id, effects) {
+            Err(ApplyTransformsError::KeyNotFound(key)) => assert_eq!(key, missing_key),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn checkouts_are_isolated_from_concurrent_commits() {
+        let (state, root_hash) = create_test_state();
+        let pairs = create_test_pairs()
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, value))
+            .collect();
+        crate::global_state::tests::checkouts_are_isolated_from_concurrent_commits(
+            state, root_hash, pairs,
+        );
+    }
+
     #[test]
     fn initial_state_has_the_expected_hash() {
         let correlation_id = CorrelationId::new();
@@ -353,4 +542,83 @@ mod tests {
         let (_, root_hash) = InMemoryGlobalState::from_pairs(correlation_id, &[]).unwrap();
         assert_eq!(expected_bytes, root_hash.to_vec())
     }
+
+    fn write_effects(pairs: &[TestPair]) -> AdditiveMap<Key, Transform> {
+        pairs
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect()
+    }
+
+    #[test]
+    fn commit_batch_matches_sequential_commits() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+        let (sequential_state, root_hash) = create_test_state();
+        let (batch_state, _) = create_test_state();
+
+        let first_effects = write_effects(&test_pairs_updated[..2]);
+        let second_effects = write_effects(&test_pairs_updated[2..]);
+
+        let first_root = match sequential_state
+            .commit(correlation_id, root_hash, first_effects.clone())
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+        let second_root = match sequential_state
+            .commit(correlation_id, first_root, second_effects.clone())
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let batch = vec![(root_hash, first_effects), (first_root, second_effects)];
+        let batch_roots: Vec<Blake2bHash> = batch_state
+            .commit_batch(correlation_id, batch)
+            .unwrap()
+            .into_iter()
+            .map(|commit_result| match commit_result {
+                CommitResult::Success { state_root, .. } => state_root,
+                _ => panic!("commit failed"),
+            })
+            .collect();
+        assert_eq!(batch_roots, vec![first_root, second_root]);
+
+        let checkout = batch_state.checkout(second_root).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+
+    #[test]
+    fn commit_batch_writes_nothing_if_an_entry_fails() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+
+        let missing_key = Key::Account([9u8; 32]);
+        let mut failing_effects = AdditiveMap::new();
+        failing_effects.insert(missing_key, Transform::AddInt32(1));
+
+        let batch = vec![
+            (root_hash, write_effects(&create_test_pairs_updated())),
+            (root_hash, failing_effects),
+        ];
+        let commit_results = state.commit_batch(correlation_id, batch).unwrap();
+
+        assert_eq!(commit_results.len(), 2);
+        let first_root = match commit_results[0] {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("first commit should succeed"),
+        };
+        match commit_results[1] {
+            CommitResult::KeyNotFound(key) => assert_eq!(key, missing_key),
+            _ => panic!("second commit should fail"),
+        }
+        assert!(state.checkout(first_root).unwrap().is_none());
+        assert!(state.checkout(root_hash).unwrap().is_some());
+    }
 }
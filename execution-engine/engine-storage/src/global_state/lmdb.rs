@@ -1,33 +1,117 @@
-use std::{ops::Deref, sync::Arc};
+use std::{collections::BTreeMap, ops::Deref, path::Path, sync::Arc};
 
-use lmdb;
+use lmdb::{self, DatabaseFlags, WriteFlags};
 
 use engine_shared::{
     additive_map::AdditiveMap,
-    newtypes::{Blake2bHash, CorrelationId},
+    newtypes::{Blake2bHash, CorrelationId, BLAKE2B_DIGEST_LENGTH},
     stored_value::StoredValue,
     transform::Transform,
 };
-use types::{Key, ProtocolVersion};
+use types::{bytesrepr, Key, ProtocolVersion, SEM_VER_SERIALIZED_LENGTH};
 
 use crate::{
     error,
-    global_state::{commit, CommitResult, StateProvider, StateReader},
+    global_state::{
+        commit, commit_batch, CommitResult, KeysPage, KeysReader, StateProvider, StateReader,
+        TrieStats, TrieStatsMode,
+    },
+    module_store::lmdb::LmdbModuleStore,
     protocol_data::ProtocolData,
     protocol_data_store::lmdb::LmdbProtocolDataStore,
-    store::Store,
-    transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
+    store::{Store, StoreExt},
+    transaction_source::{
+        lmdb::{CompactionProgress, LmdbEnvironment, LmdbReadTransaction},
+        Iterable, Transaction, TransactionSource,
+    },
     trie::{operations::create_hashed_empty_trie, Trie},
     trie_store::{
         lmdb::LmdbTrieStore,
-        operations::{read, ReadResult},
+        operations::{self, read, ReadResult},
     },
 };
 
+/// The name and flags of one of the named LMDB databases of a global state.
+///
+/// As for the stores, the name is appended to the default name of the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LmdbDatabaseConfig {
+    maybe_name: Option<String>,
+    flags: DatabaseFlags,
+}
+
+impl LmdbDatabaseConfig {
+    pub fn new(maybe_name: Option<&str>, flags: DatabaseFlags) -> Self {
+        LmdbDatabaseConfig {
+            maybe_name: maybe_name.map(ToString::to_string),
+            flags,
+        }
+    }
+
+    pub fn maybe_name(&self) -> Option<&str> {
+        self.maybe_name.as_deref()
+    }
+
+    pub fn flags(&self) -> DatabaseFlags {
+        self.flags
+    }
+}
+
+impl Default for LmdbDatabaseConfig {
+    fn default() -> Self {
+        LmdbDatabaseConfig::new(None, DatabaseFlags::empty())
+    }
+}
+
+/// The layout of a global state over the named LMDB databases of an environment.
+///
+/// Trie nodes, protocol data and precompiled modules are each kept in a database of their own, so
+/// that they can be tuned independently.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LmdbStorageConfig {
+    trie_store: LmdbDatabaseConfig,
+    protocol_data_store: LmdbDatabaseConfig,
+    module_store: LmdbDatabaseConfig,
+}
+
+impl LmdbStorageConfig {
+    pub fn new() -> Self {
+        LmdbStorageConfig::default()
+    }
+
+    pub fn with_trie_store(mut self, trie_store: LmdbDatabaseConfig) -> Self {
+        self.trie_store = trie_store;
+        self
+    }
+
+    pub fn with_protocol_data_store(mut self, protocol_data_store: LmdbDatabaseConfig) -> Self {
+        self.protocol_data_store = protocol_data_store;
+        self
+    }
+
+    pub fn with_module_store(mut self, module_store: LmdbDatabaseConfig) -> Self {
+        self.module_store = module_store;
+        self
+    }
+
+    pub fn trie_store(&self) -> &LmdbDatabaseConfig {
+        &self.trie_store
+    }
+
+    pub fn protocol_data_store(&self) -> &LmdbDatabaseConfig {
+        &self.protocol_data_store
+    }
+
+    pub fn module_store(&self) -> &LmdbDatabaseConfig {
+        &self.module_store
+    }
+}
+
 pub struct LmdbGlobalState {
     pub environment: Arc<LmdbEnvironment>,
     pub trie_store: Arc<LmdbTrieStore>,
     pub protocol_data_store: Arc<LmdbProtocolDataStore>,
+    pub module_store: Arc<LmdbModuleStore>,
     pub empty_root_hash: Blake2bHash,
 }
 
@@ -39,23 +123,58 @@ pub struct LmdbGlobalStateView {
 }
 
 impl LmdbGlobalState {
+    /// Opens the state held in `environment`, creating the databases laid out by `storage_config`
+    /// which don't exist yet.
+    ///
+    /// Environments in the single-database layout, which kept trie nodes and protocol data directly
+    /// in the main database of the environment, are migrated to the named databases first.
+    pub fn open(
+        environment: Arc<LmdbEnvironment>,
+        storage_config: &LmdbStorageConfig,
+    ) -> Result<Self, error::Error> {
+        let trie_store = {
+            let config = storage_config.trie_store();
+            LmdbTrieStore::new(&environment, config.maybe_name(), config.flags())?
+        };
+        let protocol_data_store = {
+            let config = storage_config.protocol_data_store();
+            LmdbProtocolDataStore::new(&environment, config.maybe_name(), config.flags())?
+        };
+        let module_store = {
+            let config = storage_config.module_store();
+            LmdbModuleStore::new(&environment, config.maybe_name(), config.flags())?
+        };
+        migrate_single_database_layout(&environment, &trie_store, &protocol_data_store)?;
+        LmdbGlobalState::empty(
+            environment,
+            Arc::new(trie_store),
+            Arc::new(protocol_data_store),
+            Arc::new(module_store),
+        )
+    }
+
     /// Creates an empty state from an existing environment and trie_store.
     pub fn empty(
         environment: Arc<LmdbEnvironment>,
         trie_store: Arc<LmdbTrieStore>,
         protocol_data_store: Arc<LmdbProtocolDataStore>,
+        module_store: Arc<LmdbModuleStore>,
     ) -> Result<Self, error::Error> {
         let root_hash: Blake2bHash = {
             let (root_hash, root) = create_hashed_empty_trie::<Key, StoredValue>()?;
-            let mut txn = environment.create_read_write_txn()?;
-            trie_store.put(&mut txn, &root_hash, &root)?;
-            txn.commit()?;
+            environment.with_map_resize(CorrelationId::new(), || {
+                let mut txn = environment.create_read_write_txn()?;
+                trie_store.put(&mut txn, &root_hash, &root)?;
+                txn.commit()?;
+                Ok(())
+            })?;
             root_hash
         };
         Ok(LmdbGlobalState::new(
             environment,
             trie_store,
             protocol_data_store,
+            module_store,
             root_hash,
         ))
     }
@@ -66,15 +185,136 @@ impl LmdbGlobalState {
         environment: Arc<LmdbEnvironment>,
         trie_store: Arc<LmdbTrieStore>,
         protocol_data_store: Arc<LmdbProtocolDataStore>,
+        module_store: Arc<LmdbModuleStore>,
         empty_root_hash: Blake2bHash,
     ) -> Self {
         LmdbGlobalState {
             environment,
             trie_store,
             protocol_data_store,
+            module_store,
             empty_root_hash,
         }
     }
+
+    /// Writes a compacted copy of the environment to `target_dir`, as
+    /// [`LmdbEnvironment::copy_compacted`] does, and returns the global state held in the copy.
+    ///
+    /// The stores of the copy are opened as laid out by `storage_config`, which should be the
+    /// layout this state was opened with.
+    pub fn copy_compacted<F>(
+        &self,
+        target_dir: &Path,
+        storage_config: &LmdbStorageConfig,
+        progress: F,
+    ) -> Result<LmdbGlobalState, error::Error>
+    where
+        F: FnMut(CompactionProgress),
+    {
+        let environment = Arc::new(self.environment.copy_compacted(target_dir, progress)?);
+        let trie_store = Arc::new(LmdbTrieStore::open(
+            &environment,
+            storage_config.trie_store().maybe_name(),
+        )?);
+        let protocol_data_store = Arc::new(LmdbProtocolDataStore::open(
+            &environment,
+            storage_config.protocol_data_store().maybe_name(),
+        )?);
+        let module_store = Arc::new(LmdbModuleStore::open(
+            &environment,
+            storage_config.module_store().maybe_name(),
+        )?);
+        Ok(LmdbGlobalState::new(
+            environment,
+            trie_store,
+            protocol_data_store,
+            module_store,
+            self.empty_root_hash,
+        ))
+    }
+
+    /// Returns the hashes of the tries under `state_hash` which are missing from the store or
+    /// corrupt.
+    pub fn missing_or_corrupt_tries(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+    ) -> Result<Vec<Blake2bHash>, error::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = operations::missing_or_corrupt_tries::<
+            Key,
+            StoredValue,
+            LmdbReadTransaction,
+            LmdbTrieStore,
+            error::Error,
+        >(correlation_id, &txn, self.trie_store.deref(), &state_hash)?;
+        txn.commit()?;
+        Ok(ret)
+    }
+}
+
+/// Moves the trie nodes and protocol data kept directly in the main database of `environment`, as
+/// in the single-database layout, to the databases of the given stores.
+///
+/// The main database otherwise only records the named databases under their names.  Trie nodes are
+/// told apart from protocol data by the lengths of their keys, and must deserialize.  Protocol data
+/// is copied as it is, since it may be in the layout of an earlier release.  Fails with
+/// [`lmdb::Error::Incompatible`], leaving the environment as it is, if other entries are found.
+fn migrate_single_database_layout(
+    environment: &LmdbEnvironment,
+    trie_store: &LmdbTrieStore,
+    protocol_data_store: &LmdbProtocolDataStore,
+) -> Result<(), error::Error> {
+    let is_database_name = |key: &[u8]| -> Result<bool, error::Error> {
+        let name = match std::str::from_utf8(key) {
+            Ok(name) if !name.contains('\0') => name,
+            _ => return Ok(false),
+        };
+        match environment.env().open_db(Some(name)) {
+            Ok(_) => Ok(true),
+            Err(lmdb::Error::Incompatible) => Ok(false),
+            Err(error) => Err(error.into()),
+        }
+    };
+
+    environment.with_map_resize(CorrelationId::new(), || {
+        let main_db = environment.env().open_db(None)?;
+        let entries = {
+            let txn = environment.create_read_txn()?;
+            let ret = txn.read_all(main_db)?;
+            txn.commit()?;
+            ret
+        };
+
+        let mut legacy_entries = Vec::new();
+        for (key, value) in entries {
+            if is_database_name(&key)? {
+                continue;
+            }
+            let handle = match key.len() {
+                BLAKE2B_DIGEST_LENGTH => {
+                    bytesrepr::deserialize::<Trie<Key, StoredValue>>(value.clone())?;
+                    <LmdbTrieStore as Store<Blake2bHash, Trie<Key, StoredValue>>>::handle(
+                        trie_store,
+                    )
+                }
+                SEM_VER_SERIALIZED_LENGTH => protocol_data_store.handle(),
+                _ => return Err(lmdb::Error::Incompatible.into()),
+            };
+            legacy_entries.push((handle, key, value));
+        }
+        if legacy_entries.is_empty() {
+            return Ok(());
+        }
+
+        let mut txn = environment.create_read_write_txn()?;
+        for (handle, key, value) in legacy_entries {
+            txn.put(handle, &key, &value, WriteFlags::empty())?;
+            txn.del(main_db, &key, None)?;
+        }
+        txn.commit()?;
+        Ok(())
+    })
 }
 
 impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
@@ -86,7 +326,7 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
         key: &Key,
     ) -> Result<Option<StoredValue>, Self::Error> {
         let txn = self.environment.create_read_txn()?;
-        let ret = match read::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore, Self::Error>(
+        let ret = match read::<Key, StoredValue, LmdbReadTransaction, LmdbTrieStore, Self::Error>(
             correlation_id,
             &txn,
             self.store.deref(),
@@ -102,6 +342,21 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
     }
 }
 
+impl KeysReader<Key, StoredValue> for LmdbGlobalStateView {
+    fn keys(&self, correlation_id: CorrelationId) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = operations::keys::<
+            Key,
+            StoredValue,
+            LmdbReadTransaction,
+            LmdbTrieStore,
+            Self::Error,
+        >(correlation_id, &txn, self.store.deref(), &self.root_hash)?;
+        txn.commit()?;
+        Ok(ret)
+    }
+}
+
 impl StateProvider for LmdbGlobalState {
     type Error = error::Error;
 
@@ -125,14 +380,30 @@ impl StateProvider for LmdbGlobalState {
         prestate_hash: Blake2bHash,
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<CommitResult, Self::Error> {
-        let commit_result = commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
-            &self.environment,
-            &self.trie_store,
-            correlation_id,
-            prestate_hash,
-            effects,
-        )?;
-        Ok(commit_result)
+        self.environment.with_map_resize(correlation_id, || {
+            commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
+                &self.environment,
+                &self.trie_store,
+                correlation_id,
+                prestate_hash,
+                &effects,
+            )
+        })
+    }
+
+    fn commit_batch(
+        &self,
+        correlation_id: CorrelationId,
+        batch: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<Vec<CommitResult>, Self::Error> {
+        self.environment.with_map_resize(correlation_id, || {
+            commit_batch::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
+                &self.environment,
+                &self.trie_store,
+                correlation_id,
+                &batch,
+            )
+        })
     }
 
     fn put_protocol_data(
@@ -140,10 +411,12 @@ impl StateProvider for LmdbGlobalState {
         protocol_version: ProtocolVersion,
         protocol_data: &ProtocolData,
     ) -> Result<(), Self::Error> {
-        let mut txn = self.environment.create_read_write_txn()?;
-        self.protocol_data_store
-            .put(&mut txn, &protocol_version, protocol_data)?;
-        txn.commit().map_err(Into::into)
+        self.environment.with_map_resize(CorrelationId::new(), || {
+            let mut txn = self.environment.create_read_write_txn()?;
+            self.protocol_data_store
+                .put(&mut txn, &protocol_version, protocol_data)?;
+            txn.commit().map_err(Into::into)
+        })
     }
 
     fn get_protocol_data(
@@ -156,25 +429,109 @@ impl StateProvider for LmdbGlobalState {
         Ok(result)
     }
 
+    fn list_protocol_data(&self) -> Result<BTreeMap<ProtocolVersion, ProtocolData>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let result = self.protocol_data_store.get_all(&txn)?;
+        txn.commit()?;
+        Ok(result.into_iter().collect())
+    }
+
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn stats(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        mode: TrieStatsMode,
+    ) -> Result<Option<TrieStats>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret =
+            operations::stats::<Key, StoredValue, LmdbReadTransaction, LmdbTrieStore, Self::Error>(
+                correlation_id,
+                &txn,
+                self.trie_store.deref(),
+                &state_hash,
+                mode,
+            )?;
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        prefix: &[u8],
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Option<KeysPage<Key>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = operations::keys_with_prefix::<
+            Key,
+            StoredValue,
+            LmdbReadTransaction,
+            LmdbTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &state_hash,
+            prefix,
+            after,
+            limit,
+        )?;
+        txn.commit()?;
+        Ok(ret)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use lmdb::DatabaseFlags;
+    use std::{convert::TryFrom, fs};
+
     use tempfile::tempdir;
 
-    use types::CLValue;
+    use types::{bytesrepr::ToBytes, AccessRights, CLValue, URef};
 
     use crate::{
+        transaction_source::Readable,
         trie_store::operations::{write, WriteResult},
         TEST_MAP_SIZE,
     };
 
     use super::*;
 
+    /// An environment written in the layout of the 1.0.0 release, loaded by `mdb_load` from
+    /// `fixtures/v1_0_0/data.dump`.  Its trie holds [`v1_0_0::account_pair`] under
+    /// [`v1_0_0::ROOT_HASH`], and the protocol data of the release is kept for
+    /// [`ProtocolVersion::V1_0_0`].
+    mod v1_0_0 {
+        use super::TestPair;
+        use engine_shared::stored_value::StoredValue;
+        use types::{CLValue, Key};
+
+        pub const DATA_FILE: &str =
+            concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/v1_0_0/data.mdb");
+        pub const ROOT_HASH: &str =
+            "42ef93e25219f8bb21d77e418f9d2f01bb84a2319417965dbe8085f0b36a0a65";
+        /// The ten `WasmCosts` of the release followed by the mint and proof of stake `URef`s.
+        pub const PROTOCOL_DATA: &str = concat!(
+            "01000000100000000400000002000000001000000020000001000000000001000300000008000000",
+            "c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c50107",
+            "c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c60107"
+        );
+
+        pub(super) fn account_pair() -> TestPair {
+            TestPair {
+                key: Key::Account([1u8; 32]),
+                value: StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()),
+            }
+        }
+    }
+
     #[derive(Debug, Clone)]
     struct TestPair {
         key: Key,
@@ -217,12 +574,7 @@ mod tests {
         let environment = Arc::new(
             LmdbEnvironment::new(&_temp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap(),
         );
-        let trie_store =
-            Arc::new(LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
-        let protocol_data_store = Arc::new(
-            LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
-        );
-        let ret = LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+        let ret = LmdbGlobalState::open(environment, &LmdbStorageConfig::default()).unwrap();
         let mut current_root = ret.empty_root_hash;
         {
             let mut txn = ret.environment.create_read_write_txn().unwrap();
@@ -261,6 +613,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn keys_of_a_checkout_return_expected_keys() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        let mut keys = checkout.keys(correlation_id).unwrap();
+        keys.sort();
+        let expected: Vec<Key> = create_test_pairs()
+            .iter()
+            .map(|TestPair { key, .. }| *key)
+            .collect();
+        assert_eq!(expected, keys);
+    }
+
     #[test]
     fn checkout_fails_if_unknown_hash_is_given() {
         let (state, _) = create_test_state();
@@ -341,4 +707,406 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn checkouts_are_isolated_from_concurrent_commits() {
+        let (state, root_hash) = create_test_state();
+        let pairs = create_test_pairs()
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, value))
+            .collect();
+        crate::global_state::tests::checkouts_are_isolated_from_concurrent_commits(
+            state, root_hash, pairs,
+        );
+    }
+
+    fn write_effects(pairs: &[TestPair]) -> AdditiveMap<Key, Transform> {
+        pairs
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect()
+    }
+
+    #[test]
+    fn commit_batch_matches_sequential_commits() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+        let (sequential_state, root_hash) = create_test_state();
+        let (batch_state, _) = create_test_state();
+
+        let first_effects = write_effects(&test_pairs_updated[..2]);
+        let second_effects = write_effects(&test_pairs_updated[2..]);
+
+        let first_root = match sequential_state
+            .commit(correlation_id, root_hash, first_effects.clone())
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+        let second_root = match sequential_state
+            .commit(correlation_id, first_root, second_effects.clone())
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let batch = vec![(root_hash, first_effects), (first_root, second_effects)];
+        let batch_roots: Vec<Blake2bHash> = batch_state
+            .commit_batch(correlation_id, batch)
+            .unwrap()
+            .into_iter()
+            .map(|commit_result| match commit_result {
+                CommitResult::Success { state_root, .. } => state_root,
+                _ => panic!("commit failed"),
+            })
+            .collect();
+        assert_eq!(batch_roots, vec![first_root, second_root]);
+
+        let checkout = batch_state.checkout(second_root).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+
+    #[test]
+    fn commit_batch_writes_nothing_if_an_entry_fails() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+
+        let missing_key = Key::Account([9u8; 32]);
+        let mut failing_effects = AdditiveMap::new();
+        failing_effects.insert(missing_key, Transform::AddInt32(1));
+
+        let batch = vec![
+            (root_hash, write_effects(&create_test_pairs_updated())),
+            (root_hash, failing_effects),
+        ];
+        let commit_results = state.commit_batch(correlation_id, batch).unwrap();
+
+        assert_eq!(commit_results.len(), 2);
+        let first_root = match commit_results[0] {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("first commit should succeed"),
+        };
+        match commit_results[1] {
+            CommitResult::KeyNotFound(key) => assert_eq!(key, missing_key),
+            _ => panic!("second commit should fail"),
+        }
+        assert!(state.checkout(first_root).unwrap().is_none());
+        assert!(state.checkout(root_hash).unwrap().is_some());
+    }
+
+    #[test]
+    fn commit_grows_a_full_map() {
+        let correlation_id = CorrelationId::new();
+        let temp_dir = tempdir().unwrap();
+        let map_size = engine_shared::os::get_page_size().unwrap() * 16;
+        let environment =
+            Arc::new(LmdbEnvironment::new(&temp_dir.path().to_path_buf(), map_size).unwrap());
+        let state =
+            LmdbGlobalState::open(Arc::clone(&environment), &LmdbStorageConfig::default()).unwrap();
+
+        let test_pairs: Vec<TestPair> = (0..256_u32)
+            .map(|index| {
+                let mut hash = [0u8; 32];
+                hash[..4].copy_from_slice(&index.to_le_bytes());
+                TestPair {
+                    key: Key::Hash(hash),
+                    value: StoredValue::CLValue(CLValue::from_t(vec![index as u8; 1024]).unwrap()),
+                }
+            })
+            .collect();
+
+        let root_hash = match state
+            .commit(
+                correlation_id,
+                state.empty_root_hash,
+                write_effects(&test_pairs),
+            )
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        assert!(environment.map_size().unwrap() > map_size);
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+
+    #[test]
+    fn copy_compacted_holds_the_same_state() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let protocol_version = ProtocolVersion::V1_0_0;
+        let protocol_data = ProtocolData::default();
+        state
+            .put_protocol_data(protocol_version, &protocol_data)
+            .unwrap();
+
+        let target_dir = tempdir().unwrap();
+        let mut progress = Vec::new();
+        let compacted = state
+            .copy_compacted(target_dir.path(), &LmdbStorageConfig::default(), |p| {
+                progress.push(p)
+            })
+            .unwrap();
+
+        let last = progress.last().expect("should report progress");
+        assert!(last.copied_bytes > 0);
+        assert!(last.copied_bytes <= last.total_bytes);
+
+        assert_eq!(compacted.empty_root_hash, state.empty_root_hash);
+        assert!(compacted
+            .missing_or_corrupt_tries(correlation_id, root_hash)
+            .unwrap()
+            .is_empty());
+        let checkout = compacted.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+        assert_eq!(
+            compacted.get_protocol_data(protocol_version).unwrap(),
+            Some(protocol_data)
+        );
+
+        // The copy is only written to a directory without an environment.  LMDB environments must
+        // not be opened twice in a process, so the copy is closed first.
+        drop(checkout);
+        drop(compacted);
+        let result = state.copy_compacted(target_dir.path(), &LmdbStorageConfig::default(), |_| ());
+        assert_eq!(
+            result.err(),
+            Some(error::Error::Lmdb(lmdb::Error::KeyExist))
+        );
+    }
+
+    #[test]
+    fn missing_tries_are_reported() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        assert!(state
+            .missing_or_corrupt_tries(correlation_id, root_hash)
+            .unwrap()
+            .is_empty());
+
+        let missing_root = Blake2bHash::new(b"missing");
+        assert_eq!(
+            state
+                .missing_or_corrupt_tries(correlation_id, missing_root)
+                .unwrap(),
+            vec![missing_root]
+        );
+    }
+
+    /// Writes the entries of the stores of `state` directly into the main database of a new
+    /// environment, as the single-database layout kept them.
+    fn single_database_copy(state: &LmdbGlobalState, dir: &Path) -> Arc<LmdbEnvironment> {
+        let environment =
+            Arc::new(LmdbEnvironment::new(&dir.to_path_buf(), *TEST_MAP_SIZE).unwrap());
+        let entries = {
+            let trie_handle = <LmdbTrieStore as Store<Blake2bHash, Trie<Key, StoredValue>>>::handle(
+                &state.trie_store,
+            );
+            let txn = state.environment.create_read_txn().unwrap();
+            let mut ret = txn.read_all(trie_handle).unwrap();
+            ret.extend(txn.read_all(state.protocol_data_store.handle()).unwrap());
+            txn.commit().unwrap();
+            ret
+        };
+        let main_db = environment.env().open_db(None).unwrap();
+        let mut txn = environment.create_read_write_txn().unwrap();
+        for (key, value) in entries {
+            txn.put(main_db, &key, &value, WriteFlags::empty()).unwrap();
+        }
+        txn.commit().unwrap();
+        environment
+    }
+
+    fn main_database_keys(environment: &LmdbEnvironment) -> Vec<Vec<u8>> {
+        let main_db = environment.env().open_db(None).unwrap();
+        let txn = environment.create_read_txn().unwrap();
+        let mut ret = txn
+            .read_all(main_db)
+            .unwrap()
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>();
+        txn.commit().unwrap();
+        ret.sort();
+        ret
+    }
+
+    #[test]
+    fn open_migrates_single_database_layout() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let protocol_version = ProtocolVersion::V1_0_0;
+        let protocol_data = ProtocolData::default();
+        state
+            .put_protocol_data(protocol_version, &protocol_data)
+            .unwrap();
+
+        let dir = tempdir().unwrap();
+        let environment = single_database_copy(&state, dir.path());
+        let migrated =
+            LmdbGlobalState::open(Arc::clone(&environment), &LmdbStorageConfig::default()).unwrap();
+
+        let checkout = migrated.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+        assert_eq!(
+            migrated.get_protocol_data(protocol_version).unwrap(),
+            Some(protocol_data)
+        );
+        assert_eq!(
+            main_database_keys(&environment),
+            vec![
+                b"MODULE_STORE".to_vec(),
+                b"PROTOCOL_DATA_STORE".to_vec(),
+                b"TRIE_STORE".to_vec()
+            ]
+        );
+    }
+
+    /// Opens a copy of the [`v1_0_0`] fixture in `dir`.
+    fn open_v1_0_0_fixture(dir: &Path) -> LmdbGlobalState {
+        fs::copy(v1_0_0::DATA_FILE, dir.join("data.mdb")).unwrap();
+        let environment =
+            Arc::new(LmdbEnvironment::new(&dir.to_path_buf(), *TEST_MAP_SIZE).unwrap());
+        LmdbGlobalState::open(environment, &LmdbStorageConfig::default()).unwrap()
+    }
+
+    fn protocol_data_bytes(state: &LmdbGlobalState, protocol_version: ProtocolVersion) -> Vec<u8> {
+        let key = protocol_version.to_bytes().unwrap();
+        let txn = state.environment.create_read_txn().unwrap();
+        let ret = txn
+            .read(state.protocol_data_store.handle(), &key)
+            .unwrap()
+            .expect("should have protocol data");
+        txn.commit().unwrap();
+        ret
+    }
+
+    fn assert_holds_v1_0_0_fixture(state: &LmdbGlobalState) {
+        let correlation_id = CorrelationId::new();
+        let root_hash = Blake2bHash::try_from(&base16::decode(v1_0_0::ROOT_HASH).unwrap()[..])
+            .expect("fixture should be a hash");
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        let TestPair { key, value } = v1_0_0::account_pair();
+        assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+
+        let protocol_version = ProtocolVersion::V1_0_0;
+        assert_eq!(
+            protocol_data_bytes(state, protocol_version),
+            base16::decode(v1_0_0::PROTOCOL_DATA).unwrap()
+        );
+        let protocol_data = state
+            .get_protocol_data(protocol_version)
+            .unwrap()
+            .expect("should decode protocol data of the release");
+        assert_eq!(
+            protocol_data.mint(),
+            URef::new([197u8; 32], AccessRights::READ_ADD_WRITE)
+        );
+        assert_eq!(
+            protocol_data.proof_of_stake(),
+            URef::new([198u8; 32], AccessRights::READ_ADD_WRITE)
+        );
+    }
+
+    #[test]
+    fn open_reads_v1_0_0_fixture() {
+        let dir = tempdir().unwrap();
+        let state = open_v1_0_0_fixture(dir.path());
+        assert_holds_v1_0_0_fixture(&state);
+    }
+
+    #[test]
+    fn open_migrates_v1_0_0_fixture_from_single_database_layout() {
+        let fixture_dir = tempdir().unwrap();
+        let fixture = open_v1_0_0_fixture(fixture_dir.path());
+
+        // The protocol data is moved in the layout of the release, not rewritten
+        let dir = tempdir().unwrap();
+        let environment = single_database_copy(&fixture, dir.path());
+        let migrated =
+            LmdbGlobalState::open(Arc::clone(&environment), &LmdbStorageConfig::default()).unwrap();
+        assert_holds_v1_0_0_fixture(&migrated);
+        assert_eq!(
+            main_database_keys(&environment),
+            vec![
+                b"MODULE_STORE".to_vec(),
+                b"PROTOCOL_DATA_STORE".to_vec(),
+                b"TRIE_STORE".to_vec()
+            ]
+        );
+    }
+
+    #[test]
+    fn open_fails_on_unknown_entries_of_main_database() {
+        let dir = tempdir().unwrap();
+        let environment =
+            Arc::new(LmdbEnvironment::new(&dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap());
+        let main_db = environment.env().open_db(None).unwrap();
+        let mut txn = environment.create_read_write_txn().unwrap();
+        txn.put(main_db, &[1u8, 2, 3], &[4u8], WriteFlags::empty())
+            .unwrap();
+        txn.commit().unwrap();
+
+        let result = LmdbGlobalState::open(Arc::clone(&environment), &LmdbStorageConfig::default());
+        assert_eq!(
+            result.err(),
+            Some(error::Error::Lmdb(lmdb::Error::Incompatible))
+        );
+        assert!(main_database_keys(&environment).contains(&vec![1u8, 2, 3]));
+    }
+
+    #[test]
+    fn open_lays_out_configured_databases() {
+        let correlation_id = CorrelationId::new();
+        let dir = tempdir().unwrap();
+        let environment =
+            Arc::new(LmdbEnvironment::new(&dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap());
+        let storage_config = LmdbStorageConfig::new()
+            .with_trie_store(LmdbDatabaseConfig::new(Some("a"), DatabaseFlags::empty()))
+            .with_module_store(LmdbDatabaseConfig::new(Some("b"), DatabaseFlags::empty()));
+        let state = LmdbGlobalState::open(Arc::clone(&environment), &storage_config).unwrap();
+        let root_hash = match state
+            .commit(
+                correlation_id,
+                state.empty_root_hash,
+                write_effects(&create_test_pairs()),
+            )
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        assert_eq!(
+            main_database_keys(&environment),
+            vec![
+                b"MODULE_STORE-b".to_vec(),
+                b"PROTOCOL_DATA_STORE".to_vec(),
+                b"TRIE_STORE-a".to_vec()
+            ]
+        );
+
+        let target_dir = tempdir().unwrap();
+        let compacted = state
+            .copy_compacted(target_dir.path(), &storage_config, |_| ())
+            .unwrap();
+        let checkout = compacted.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
 }
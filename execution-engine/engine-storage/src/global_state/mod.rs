@@ -1,7 +1,15 @@
 pub mod in_memory;
 pub mod lmdb;
-
-use std::{collections::HashMap, fmt, hash::BuildHasher, time::Instant};
+#[cfg(test)]
+mod tests;
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt,
+    hash::BuildHasher,
+    time::Instant,
+};
 
 use engine_shared::{
     additive_map::AdditiveMap,
@@ -12,9 +20,10 @@ use engine_shared::{
 };
 use types::{account::PublicKey, bytesrepr, Key, ProtocolVersion, U512};
 
+pub use crate::trie_store::operations::{KeysPage, TrieStats, TrieStatsMode};
 use crate::{
     protocol_data::ProtocolData,
-    transaction_source::{Transaction, TransactionSource},
+    transaction_source::{Readable, Transaction, TransactionSource, Writable},
     trie::Trie,
     trie_store::{
         operations::{read, write, ReadResult, WriteResult},
@@ -24,6 +33,7 @@ use crate::{
 
 const GLOBAL_STATE_COMMIT_READS: &str = "global_state_commit_reads";
 const GLOBAL_STATE_COMMIT_WRITES: &str = "global_state_commit_writes";
+const GLOBAL_STATE_COMMIT_UNCHANGED: &str = "global_state_commit_unchanged";
 const GLOBAL_STATE_COMMIT_DURATION: &str = "global_state_commit_duration";
 const GLOBAL_STATE_COMMIT_READ_DURATION: &str = "global_state_commit_read_duration";
 const GLOBAL_STATE_COMMIT_WRITE_DURATION: &str = "global_state_commit_write_duration";
@@ -38,6 +48,12 @@ pub trait StateReader<K, V> {
     fn read(&self, correlation_id: CorrelationId, key: &K) -> Result<Option<V>, Self::Error>;
 }
 
+/// A reader of state which can also list all the keys in the state
+pub trait KeysReader<K, V>: StateReader<K, V> {
+    /// Returns all the keys in the state
+    fn keys(&self, correlation_id: CorrelationId) -> Result<Vec<K>, Self::Error>;
+}
+
 #[derive(Debug)]
 pub enum CommitResult {
     RootNotFound,
@@ -82,11 +98,73 @@ impl From<transform::Error> for CommitResult {
     }
 }
 
+/// The values left under each affected key once a set of effects has been applied.
+pub type StateDelta = BTreeMap<Key, StoredValue>;
+
+/// An error which occurs when applying effects with [`apply_transforms`].
+#[derive(Debug)]
+pub enum ApplyTransformsError<E> {
+    Reader(E),
+    KeyNotFound(Key),
+    Transform(transform::Error),
+}
+
+impl<E: fmt::Debug> fmt::Display for ApplyTransformsError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ApplyTransformsError::Reader(error) => write!(f, "Reader: {:?}", error),
+            ApplyTransformsError::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            ApplyTransformsError::Transform(error) => write!(f, "Transform: {:?}", error),
+        }
+    }
+}
+
+impl<E> From<transform::Error> for ApplyTransformsError<E> {
+    fn from(error: transform::Error) -> Self {
+        ApplyTransformsError::Transform(error)
+    }
+}
+
+/// Applies `effects` to the state seen through `reader` and returns the resulting value under
+/// each affected key, without writing anything.
+///
+/// The same rules as [`commit`] apply, so a block's claimed effects can be checked against its
+/// pre-state before being committed.
+pub fn apply_transforms<R, H>(
+    reader: &R,
+    correlation_id: CorrelationId,
+    effects: AdditiveMap<Key, Transform, H>,
+) -> Result<StateDelta, ApplyTransformsError<R::Error>>
+where
+    R: StateReader<Key, StoredValue>,
+    H: BuildHasher,
+{
+    let mut state_delta = StateDelta::new();
+    for (key, transform) in effects.into_iter() {
+        let value = match (
+            reader
+                .read(correlation_id, &key)
+                .map_err(ApplyTransformsError::Reader)?,
+            transform,
+        ) {
+            (None, Transform::Write(new_value)) => new_value,
+            (None, _) => return Err(ApplyTransformsError::KeyNotFound(key)),
+            (Some(current_value), transform) => transform.apply(current_value)?,
+        };
+        state_delta.insert(key, value);
+    }
+    Ok(state_delta)
+}
+
 pub trait StateProvider {
     type Error;
     type Reader: StateReader<Key, StoredValue, Error = Self::Error>;
 
     /// Checkouts to the post state of a specific block.
+    ///
+    /// Only committed roots can be checked out.  The returned reader sees the state under
+    /// `state_hash` for as long as it is used: commits made in the meantime, or running
+    /// concurrently, are neither visible to it nor wait for its reads to finish.
     fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error>;
 
     /// Applies changes and returns a new post state hash.
@@ -98,6 +176,14 @@ pub trait StateProvider {
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<CommitResult, Self::Error>;
 
+    /// Applies each set of effects on top of its pre-state in a single write transaction, see
+    /// [`commit_batch`].
+    fn commit_batch(
+        &self,
+        correlation_id: CorrelationId,
+        batch: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<Vec<CommitResult>, Self::Error>;
+
     fn put_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -109,7 +195,31 @@ pub trait StateProvider {
         protocol_version: ProtocolVersion,
     ) -> Result<Option<ProtocolData>, Self::Error>;
 
+    /// Returns the protocol data stored for every known protocol version.
+    fn list_protocol_data(&self) -> Result<BTreeMap<ProtocolVersion, ProtocolData>, Self::Error>;
+
     fn empty_root(&self) -> Blake2bHash;
+
+    /// Returns statistics of the trie under `state_hash`, or `None` if it is not a known root,
+    /// see [`operations::stats`](crate::trie_store::operations::stats).
+    fn stats(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        mode: TrieStatsMode,
+    ) -> Result<Option<TrieStats>, Self::Error>;
+
+    /// Returns a page of the keys under `state_hash` whose serialized bytes start with `prefix`,
+    /// or `None` if it is not a known root, see
+    /// [`operations::keys_with_prefix`](crate::trie_store::operations::keys_with_prefix).
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        prefix: &[u8],
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Option<KeysPage<Key>>, Self::Error>;
 }
 
 pub fn commit<'a, R, S, H, E>(
@@ -117,7 +227,7 @@ pub fn commit<'a, R, S, H, E>(
     store: &S,
     correlation_id: CorrelationId,
     prestate_hash: Blake2bHash,
-    effects: AdditiveMap<Key, Transform, H>,
+    effects: &AdditiveMap<Key, Transform, H>,
 ) -> Result<CommitResult, E>
 where
     R: TransactionSource<'a, Handle = S::Handle>,
@@ -126,21 +236,119 @@ where
     E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
     H: BuildHasher,
 {
+    let start = Instant::now();
+    let mut txn = environment.create_read_write_txn()?;
+
+    let commit_result = commit_in_txn::<_, _, _, E>(
+        correlation_id,
+        &mut txn,
+        store,
+        prestate_hash,
+        effects,
+        start,
+    )?;
+    if let CommitResult::Success { .. } = commit_result {
+        txn.commit()?;
+        log_duration(
+            correlation_id,
+            GLOBAL_STATE_COMMIT_DURATION,
+            COMMIT,
+            start.elapsed(),
+        );
+    }
+
+    Ok(commit_result)
+}
+
+/// Applies a batch of effects, each on top of its own pre-state, in a single read-write
+/// transaction, returning the result of each commit in order.
+///
+/// A pre-state may be the state root resulting from an earlier entry of the batch, so all deploys
+/// of a block can be committed at once after they have been executed one after another.  The batch
+/// is atomic: if an entry can't be committed, the returned results end with its failure and none of
+/// the batch is written.
+pub fn commit_batch<'a, R, S, H, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    batch: &[(Blake2bHash, AdditiveMap<Key, Transform, H>)],
+) -> Result<Vec<CommitResult>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+    H: BuildHasher,
+{
+    let start = Instant::now();
     let mut txn = environment.create_read_write_txn()?;
+
+    let mut commit_results = Vec::with_capacity(batch.len());
+    for (prestate_hash, effects) in batch {
+        let commit_result = commit_in_txn::<_, _, _, E>(
+            correlation_id,
+            &mut txn,
+            store,
+            *prestate_hash,
+            effects,
+            start,
+        )?;
+        let succeeded = match commit_result {
+            CommitResult::Success { .. } => true,
+            _ => false,
+        };
+        commit_results.push(commit_result);
+        if !succeeded {
+            // Dropping the transaction aborts it
+            return Ok(commit_results);
+        }
+    }
+    txn.commit()?;
+
+    log_duration(
+        correlation_id,
+        GLOBAL_STATE_COMMIT_DURATION,
+        COMMIT,
+        start.elapsed(),
+    );
+
+    Ok(commit_results)
+}
+
+/// Applies `effects` on top of `prestate_hash` within `txn`, leaving it to the caller to commit
+/// the transaction.
+///
+/// The effects are borrowed, so that a commit can be retried with them, e.g. after the map of an
+/// LMDB environment was grown.  Only the transforms which are applied to a stored value are cloned.
+fn commit_in_txn<T, S, H, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    prestate_hash: Blake2bHash,
+    effects: &AdditiveMap<Key, Transform, H>,
+    start: Instant,
+) -> Result<CommitResult, E>
+where
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+    H: BuildHasher,
+{
     let mut state_root = prestate_hash;
 
-    let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&txn, &state_root)?;
+    let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&*txn, &state_root)?;
 
     if maybe_root.is_none() {
         return Ok(CommitResult::RootNotFound);
     };
 
-    let start = Instant::now();
     let mut reads: i32 = 0;
     let mut writes: i32 = 0;
+    let mut unchanged: i32 = 0;
 
-    for (key, transform) in effects.into_iter() {
-        let read_result = read::<_, _, _, _, E>(correlation_id, &txn, store, &state_root, &key)?;
+    for (key, transform) in effects.iter() {
+        let read_result = read::<_, _, _, _, E>(correlation_id, &*txn, store, &state_root, key)?;
 
         log_duration(
             correlation_id,
@@ -152,19 +360,34 @@ where
         reads += 1;
 
         let value = match (read_result, transform) {
-            (ReadResult::NotFound, Transform::Write(new_value)) => new_value,
+            (ReadResult::NotFound, Transform::Write(new_value)) => Cow::Borrowed(new_value),
             (ReadResult::NotFound, _) => {
-                return Ok(CommitResult::KeyNotFound(key));
+                return Ok(CommitResult::KeyNotFound(*key));
+            }
+            // Reads, and writes of the value already stored, e.g. by several deploys of a block
+            // installing the same contract, leave the trie as it is, so there's nothing to write
+            (ReadResult::Found(_), Transform::Identity) => {
+                unchanged += 1;
+                continue;
+            }
+            (ReadResult::Found(current_value), Transform::Write(new_value))
+                if *new_value == current_value =>
+            {
+                unchanged += 1;
+                continue;
+            }
+            (ReadResult::Found(_), Transform::Write(new_value)) => Cow::Borrowed(new_value),
+            (ReadResult::Found(current_value), transform) => {
+                match transform.clone().apply(current_value) {
+                    Ok(updated_value) => Cow::Owned(updated_value),
+                    Err(err) => return Ok(err.into()),
+                }
             }
-            (ReadResult::Found(current_value), transform) => match transform.apply(current_value) {
-                Ok(updated_value) => updated_value,
-                Err(err) => return Ok(err.into()),
-            },
             _x @ (ReadResult::RootNotFound, _) => panic!(stringify!(_x._1)),
         };
 
         let write_result =
-            write::<_, _, _, _, E>(correlation_id, &mut txn, store, &state_root, &key, &value)?;
+            write::<_, _, _, _, E>(correlation_id, txn, store, &state_root, key, &*value)?;
 
         log_duration(
             correlation_id,
@@ -178,20 +401,11 @@ where
                 state_root = root_hash;
                 writes += 1;
             }
-            WriteResult::AlreadyExists => (),
+            WriteResult::AlreadyExists => unchanged += 1,
             _x @ WriteResult::RootNotFound => panic!(stringify!(_x)),
         }
     }
 
-    txn.commit()?;
-
-    log_duration(
-        correlation_id,
-        GLOBAL_STATE_COMMIT_DURATION,
-        COMMIT,
-        start.elapsed(),
-    );
-
     log_metric(
         correlation_id,
         GLOBAL_STATE_COMMIT_READS,
@@ -208,6 +422,14 @@ where
         f64::from(writes),
     );
 
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_COMMIT_UNCHANGED,
+        COMMIT,
+        GAUGE,
+        f64::from(unchanged),
+    );
+
     let bonded_validators = Default::default();
 
     Ok(CommitResult::Success {
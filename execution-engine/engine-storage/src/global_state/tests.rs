@@ -0,0 +1,93 @@
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use engine_shared::{
+    additive_map::AdditiveMap,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    transform::Transform,
+};
+use types::{CLValue, Key};
+
+use crate::global_state::{CommitResult, StateProvider, StateReader};
+
+const COMMITS: u8 = 100;
+const QUERIERS: usize = 4;
+
+/// Commits to `state` while other threads keep checking out and reading from it, starting at
+/// `root_hash`, under which `pairs` are stored.  The first pair must hold the `i32` 1, which is
+/// used as a counter.
+pub(super) fn checkouts_are_isolated_from_concurrent_commits<S>(
+    state: S,
+    root_hash: Blake2bHash,
+    pairs: Vec<(Key, StoredValue)>,
+) where
+    S: StateProvider + Send + Sync + 'static,
+    S::Error: Debug,
+{
+    let correlation_id = CorrelationId::new();
+    let state = Arc::new(state);
+    let counter_key = pairs[0].0;
+    let latest_root = Arc::new(Mutex::new(root_hash));
+    let committing = Arc::new(AtomicBool::new(true));
+
+    let queriers: Vec<_> = (0..QUERIERS)
+        .map(|_| {
+            let state = Arc::clone(&state);
+            let pairs = pairs.clone();
+            let latest_root = Arc::clone(&latest_root);
+            let committing = Arc::clone(&committing);
+            thread::spawn(move || {
+                let original_checkout = state.checkout(root_hash).unwrap().unwrap();
+                while committing.load(Ordering::SeqCst) {
+                    // The original root is unaffected by the commits
+                    for (key, value) in pairs.iter().cloned() {
+                        assert_eq!(
+                            Some(value),
+                            original_checkout.read(correlation_id, &key).unwrap()
+                        );
+                    }
+
+                    // The latest root is seen as a whole: commit `n` both bumps the counter and
+                    // writes under `Key::Hash([n; 32])`
+                    let root = *latest_root.lock().unwrap();
+                    let checkout = state.checkout(root).unwrap().unwrap();
+                    let counter: i32 = match checkout.read(correlation_id, &counter_key) {
+                        Ok(Some(StoredValue::CLValue(cl_value))) => cl_value.into_t().unwrap(),
+                        other => panic!("unexpected read result: {:?}", other),
+                    };
+                    if counter > 1 {
+                        let key = Key::Hash([(counter - 2) as u8; 32]);
+                        assert!(checkout.read(correlation_id, &key).unwrap().is_some());
+                    }
+                }
+            })
+        })
+        .collect();
+
+    let mut current_root = root_hash;
+    for n in 0..COMMITS {
+        let mut effects = AdditiveMap::new();
+        effects.insert(counter_key, Transform::AddInt32(1));
+        effects.insert(
+            Key::Hash([n; 32]),
+            Transform::Write(StoredValue::CLValue(CLValue::from_t(n).unwrap())),
+        );
+        current_root = match state.commit(correlation_id, current_root, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+        *latest_root.lock().unwrap() = current_root;
+    }
+    committing.store(false, Ordering::SeqCst);
+
+    for querier in queriers {
+        querier.join().expect("querier should not panic");
+    }
+}
@@ -3,6 +3,7 @@
 // modules
 pub mod error;
 pub mod global_state;
+pub mod module_store;
 pub mod protocol_data;
 pub mod protocol_data_store;
 pub mod store;
@@ -13,7 +14,7 @@ pub mod trie_store;
 #[cfg(test)]
 use lazy_static::lazy_static;
 
-const MAX_DBS: u32 = 2;
+const MAX_DBS: u32 = 3;
 
 #[cfg(test)]
 lazy_static! {
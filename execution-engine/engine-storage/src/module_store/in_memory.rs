@@ -0,0 +1,35 @@
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::{
+    error::in_memory::Error,
+    module_store::{self, ModuleStore},
+    store::Store,
+    transaction_source::in_memory::InMemoryEnvironment,
+};
+
+/// An in-memory module store
+pub struct InMemoryModuleStore {
+    maybe_name: Option<String>,
+}
+
+impl InMemoryModuleStore {
+    pub fn new(_env: &InMemoryEnvironment, maybe_name: Option<&str>) -> Self {
+        let name = maybe_name
+            .map(|name| format!("{}-{}", module_store::NAME, name))
+            .unwrap_or_else(|| String::from(module_store::NAME));
+        InMemoryModuleStore {
+            maybe_name: Some(name),
+        }
+    }
+}
+
+impl Store<Blake2bHash, Vec<u8>> for InMemoryModuleStore {
+    type Error = Error;
+    type Handle = Option<String>;
+
+    fn handle(&self) -> Self::Handle {
+        self.maybe_name.to_owned()
+    }
+}
+
+impl ModuleStore for InMemoryModuleStore {}
@@ -0,0 +1,54 @@
+use lmdb::{Database, DatabaseFlags};
+
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::{
+    error,
+    module_store::{self, ModuleStore},
+    store::Store,
+    transaction_source::lmdb::LmdbEnvironment,
+};
+
+/// An LMDB-backed module store.
+///
+/// Wraps [`lmdb::Database`].
+#[derive(Debug, Clone)]
+pub struct LmdbModuleStore {
+    db: Database,
+}
+
+impl LmdbModuleStore {
+    pub fn new(
+        env: &LmdbEnvironment,
+        maybe_name: Option<&str>,
+        flags: DatabaseFlags,
+    ) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().create_db(Some(&name), flags)?;
+        Ok(LmdbModuleStore { db })
+    }
+
+    pub fn open(env: &LmdbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().open_db(Some(&name))?;
+        Ok(LmdbModuleStore { db })
+    }
+
+    fn name(maybe_name: Option<&str>) -> String {
+        maybe_name
+            .map(|name| format!("{}-{}", module_store::NAME, name))
+            .unwrap_or_else(|| String::from(module_store::NAME))
+    }
+}
+
+impl Store<Blake2bHash, Vec<u8>> for LmdbModuleStore {
+    type Error = error::Error;
+
+    type Handle = Database;
+
+    fn handle(&self) -> Self::Handle {
+        self.db
+    }
+}
+
+impl ModuleStore for LmdbModuleStore {}
@@ -0,0 +1,14 @@
+//! A store for persisting the bytes of precompiled Wasm modules at the hashes of their sources.
+use engine_shared::newtypes::Blake2bHash;
+
+pub mod in_memory;
+pub mod lmdb;
+#[cfg(test)]
+mod tests;
+
+use crate::store::Store;
+
+const NAME: &str = "MODULE_STORE";
+
+/// An entity which persists the bytes of precompiled modules at the hashes of their sources.
+pub trait ModuleStore: Store<Blake2bHash, Vec<u8>> {}
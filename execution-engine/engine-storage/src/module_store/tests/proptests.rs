@@ -0,0 +1,58 @@
+use std::collections::BTreeMap;
+
+use lmdb::DatabaseFlags;
+use proptest::{
+    collection::{btree_map, vec},
+    prelude::*,
+};
+use tempfile;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::gens::u8_slice_32;
+
+use crate::{
+    module_store::{in_memory::InMemoryModuleStore, lmdb::LmdbModuleStore},
+    store::tests as store_tests,
+    transaction_source::{in_memory::InMemoryEnvironment, lmdb::LmdbEnvironment},
+    TEST_MAP_SIZE,
+};
+
+const MAX_MODULES: usize = 16;
+const MAX_MODULE_LENGTH: usize = 1024;
+
+fn modules_arb() -> impl Strategy<Value = BTreeMap<Blake2bHash, Vec<u8>>> {
+    btree_map(
+        u8_slice_32().prop_map(Blake2bHash::from),
+        vec(any::<u8>(), 0..MAX_MODULE_LENGTH),
+        1..MAX_MODULES,
+    )
+}
+
+fn in_memory_roundtrip_succeeds(inputs: BTreeMap<Blake2bHash, Vec<u8>>) -> bool {
+    let env = InMemoryEnvironment::new();
+    let store = InMemoryModuleStore::new(&env, None);
+
+    store_tests::roundtrip_succeeds(&env, &store, inputs).unwrap()
+}
+
+fn lmdb_roundtrip_succeeds(inputs: BTreeMap<Blake2bHash, Vec<u8>>) -> bool {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let env = LmdbEnvironment::new(&tmp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+    let store = LmdbModuleStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+
+    let ret = store_tests::roundtrip_succeeds(&env, &store, inputs).unwrap();
+    tmp_dir.close().unwrap();
+    ret
+}
+
+proptest! {
+    #[test]
+    fn prop_in_memory_roundtrip_succeeds(m in modules_arb()) {
+        assert!(in_memory_roundtrip_succeeds(m))
+    }
+
+    #[test]
+    fn prop_lmdb_roundtrip_succeeds(m in modules_arb()) {
+        assert!(lmdb_roundtrip_succeeds(m))
+    }
+}
@@ -1,21 +1,275 @@
-use engine_wasm_prep::wasm_costs::{WasmCosts, WASM_COSTS_SERIALIZED_LENGTH};
+use std::collections::BTreeMap;
+
+use engine_shared::{gas::Gas, motes::Motes, newtypes::Blake2bHash};
+use engine_wasm_prep::{
+    denied_host_functions::DeniedHostFunctions,
+    gas_metering::GasMetering,
+    host_function_costs::HostFunctionCosts,
+    wasm_costs::{WasmCosts, LEGACY_WASM_COSTS_SERIALIZED_LENGTH},
+};
 use types::{
-    bytesrepr::{self, FromBytes, ToBytes},
-    AccessRights, URef, UREF_SERIALIZED_LENGTH,
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    AccessRights, URef, U512, UREF_SERIALIZED_LENGTH,
 };
 
-const PROTOCOL_DATA_SERIALIZED_LENGTH: usize =
-    WASM_COSTS_SERIALIZED_LENGTH + UREF_SERIALIZED_LENGTH + UREF_SERIALIZED_LENGTH;
+/// Name under which the mint contract is registered in [`ProtocolData`].
+pub const MINT_NAME: &str = "mint";
+/// Name under which the proof of stake contract is registered in [`ProtocolData`].
+pub const POS_NAME: &str = "pos";
+
+/// Percentage of the unspent payment refunded when a deploy is finalized, unless configured
+/// otherwise.
+pub const DEFAULT_REFUND_PERCENTAGE: u8 = 100;
+
+/// Motes the main purse of a deploy's account has to hold at least, which also bound the cost of
+/// its payment code, unless configured otherwise.
+pub const DEFAULT_MAX_PAYMENT: u64 = 10_000_000;
+/// Motes paid per unit of gas, unless configured otherwise.
+pub const DEFAULT_CONV_RATE: u64 = 10;
+/// Gas charged for a native transfer, unless configured otherwise.
+pub const DEFAULT_NATIVE_TRANSFER_COST: u64 = 10_000;
+
+/// Length of the serialized [`ChainParameters`].
+const CHAIN_PARAMETERS_SERIALIZED_LENGTH: usize = 5 * 8;
+
+/// Tag leading the current layout of serialized [`ProtocolData`].
+const PROTOCOL_DATA_LAYOUT_VERSION: u8 = 1;
+
+/// Length of the untagged layout of the 1.0.0 release, i.e. the [`WasmCosts`] of that release
+/// followed by the mint and proof of stake [`URef`]s.  Every value in the current layout is longer.
+const LEGACY_PROTOCOL_DATA_SERIALIZED_LENGTH: usize =
+    LEGACY_WASM_COSTS_SERIALIZED_LENGTH + UREF_SERIALIZED_LENGTH + UREF_SERIALIZED_LENGTH;
+
+/// Length of the serialized [`RentParameters`].
+const RENT_PARAMETERS_SERIALIZED_LENGTH: usize = 1 + 8;
+
+/// Placeholder returned by the typed accessors for system contracts which are not registered.
+fn unknown_system_contract() -> URef {
+    URef::new([0; 32], AccessRights::READ)
+}
+
+/// Parameters of the storage rent prototype.  Rent is only collected if the engine is built with
+/// the `rent` feature and the parameters are enabled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RentParameters {
+    enabled: bool,
+    expiry: u64,
+}
+
+impl RentParameters {
+    /// Creates new [`RentParameters`], where `expiry` is the time, in block time units, after
+    /// which a value which hasn't been accessed expires.
+    pub fn new(enabled: bool, expiry: u64) -> Self {
+        RentParameters { enabled, expiry }
+    }
+
+    /// Whether rent is collected.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// The time, in block time units, after which a value which hasn't been accessed expires.
+    pub fn expiry(&self) -> u64 {
+        self.expiry
+    }
+}
+
+impl ToBytes for RentParameters {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = self.enabled.to_bytes()?;
+        ret.append(&mut self.expiry.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        RENT_PARAMETERS_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for RentParameters {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (enabled, rem) = FromBytes::from_bytes(bytes)?;
+        let (expiry, rem) = FromBytes::from_bytes(rem)?;
+        Ok((RentParameters { enabled, expiry }, rem))
+    }
+}
+
+/// Limits on the payment and the gas of deploys, the rate at which gas is paid for, the rate at
+/// which validator rewards are minted, and the gas charged for native transfers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainParameters {
+    max_payment: u64,
+    conv_rate: u64,
+    block_gas_limit: u64,
+    seigniorage_rate: u64,
+    native_transfer_cost: u64,
+}
+
+impl ChainParameters {
+    /// Creates new [`ChainParameters`], where a `block_gas_limit` of zero leaves the gas of
+    /// blocks unlimited, and a `seigniorage_rate` of zero mints no validator rewards.
+    pub fn new(
+        max_payment: u64,
+        conv_rate: u64,
+        block_gas_limit: u64,
+        seigniorage_rate: u64,
+        native_transfer_cost: u64,
+    ) -> Self {
+        ChainParameters {
+            max_payment,
+            conv_rate,
+            block_gas_limit,
+            seigniorage_rate,
+            native_transfer_cost,
+        }
+    }
+
+    /// The motes the main purse of a deploy's account has to hold at least, the equivalent in gas
+    /// of which is the gas limit of the deploy's payment code.
+    pub fn max_payment(&self) -> Motes {
+        Motes::from(self.max_payment)
+    }
+
+    /// The motes paid per unit of gas.
+    pub fn conv_rate(&self) -> u64 {
+        self.conv_rate
+    }
+
+    /// The gas all deploys of a block may use in total, if limited.
+    pub fn block_gas_limit(&self) -> Option<Gas> {
+        if self.block_gas_limit == 0 {
+            None
+        } else {
+            Some(Gas::from(self.block_gas_limit))
+        }
+    }
+
+    /// The motes minted as rewards for the validators at the end of each era, in billionths of
+    /// the total stakes.
+    pub fn seigniorage_rate(&self) -> u64 {
+        self.seigniorage_rate
+    }
+
+    /// The gas charged for a native transfer, regardless of whether it creates the target account.
+    pub fn native_transfer_cost(&self) -> Gas {
+        Gas::from(self.native_transfer_cost)
+    }
+}
+
+impl Default for ChainParameters {
+    fn default() -> Self {
+        ChainParameters::new(
+            DEFAULT_MAX_PAYMENT,
+            DEFAULT_CONV_RATE,
+            0,
+            0,
+            DEFAULT_NATIVE_TRANSFER_COST,
+        )
+    }
+}
+
+impl ToBytes for ChainParameters {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = self.max_payment.to_bytes()?;
+        ret.append(&mut self.conv_rate.to_bytes()?);
+        ret.append(&mut self.block_gas_limit.to_bytes()?);
+        ret.append(&mut self.seigniorage_rate.to_bytes()?);
+        ret.append(&mut self.native_transfer_cost.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        CHAIN_PARAMETERS_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for ChainParameters {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (max_payment, rem) = FromBytes::from_bytes(bytes)?;
+        let (conv_rate, rem) = FromBytes::from_bytes(rem)?;
+        let (block_gas_limit, rem) = FromBytes::from_bytes(rem)?;
+        let (seigniorage_rate, rem) = FromBytes::from_bytes(rem)?;
+        let (native_transfer_cost, rem) = FromBytes::from_bytes(rem)?;
+        let chain_parameters = ChainParameters {
+            max_payment,
+            conv_rate,
+            block_gas_limit,
+            seigniorage_rate,
+            native_transfer_cost,
+        };
+        Ok((chain_parameters, rem))
+    }
+}
+
+/// Sponsorship of a whitelisted stored contract, e.g. governance voting, which makes calling it
+/// free for users.
+///
+/// When the session code of a deploy calls the sponsored contract, the cost of the deploy is
+/// refunded to the account's main purse from the sponsorship purse, up to the allowance of gas per
+/// deploy.  Nothing is refunded once the sponsorship purse runs dry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sponsorship {
+    allowance: Gas,
+    purse: URef,
+}
+
+impl Sponsorship {
+    /// Creates a new [`Sponsorship`] funded from `purse`, which has to be writeable.
+    pub fn new(allowance: Gas, purse: URef) -> Self {
+        Sponsorship { allowance, purse }
+    }
+
+    /// The gas refunded per deploy at most.
+    pub fn allowance(&self) -> Gas {
+        self.allowance
+    }
+
+    /// The purse the refunds are paid from.
+    pub fn purse(&self) -> URef {
+        self.purse
+    }
+}
+
+impl ToBytes for Sponsorship {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = self.allowance.value().to_bytes()?;
+        ret.append(&mut self.purse.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.allowance.value().serialized_length() + UREF_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for Sponsorship {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (allowance, rem): (U512, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (purse, rem) = FromBytes::from_bytes(rem)?;
+        let sponsorship = Sponsorship {
+            allowance: Gas::new(allowance),
+            purse,
+        };
+        Ok((sponsorship, rem))
+    }
+}
 
 /// Represents a protocol's data. Intended to be associated with a given protocol version.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProtocolData {
     wasm_costs: WasmCosts,
-    mint: URef,
-    proof_of_stake: URef,
+    system_contracts: BTreeMap<String, URef>,
+    rent_parameters: RentParameters,
+    installers: BTreeMap<String, Blake2bHash>,
+    sponsorships: BTreeMap<[u8; 32], Sponsorship>,
+    host_function_costs: HostFunctionCosts,
+    refund_percentage: u8,
+    chain_parameters: ChainParameters,
+    denied_host_functions: DeniedHostFunctions,
+    gas_metering: GasMetering,
 }
 
-/// Provides a default instance with non existing urefs and empty costs table.
+/// Provides a default instance with no registered system contracts and empty costs table.
 ///
 /// Used in contexts where PoS or Mint contract is not ready yet, and pos, and
 /// mint installers are ran. For use with caution.
@@ -23,93 +277,342 @@ impl Default for ProtocolData {
     fn default() -> ProtocolData {
         ProtocolData {
             wasm_costs: WasmCosts::default(),
-            mint: URef::new([0; 32], AccessRights::READ),
-            proof_of_stake: URef::new([0; 32], AccessRights::READ),
+            system_contracts: BTreeMap::new(),
+            rent_parameters: RentParameters::default(),
+            installers: BTreeMap::new(),
+            sponsorships: BTreeMap::new(),
+            host_function_costs: HostFunctionCosts::default(),
+            refund_percentage: DEFAULT_REFUND_PERCENTAGE,
+            chain_parameters: ChainParameters::default(),
+            denied_host_functions: DeniedHostFunctions::default(),
+            gas_metering: GasMetering::default(),
         }
     }
 }
 
 impl ProtocolData {
-    /// Creates a new [`ProtocolData`] value from a given [`WasmCosts`] value.
-    pub fn new(wasm_costs: WasmCosts, mint: URef, proof_of_stake: URef) -> Self {
+    /// Creates a new [`ProtocolData`] value from a given [`WasmCosts`] value and a registry of
+    /// system contracts keyed by name.  Rent is disabled.
+    pub fn new(wasm_costs: WasmCosts, system_contracts: BTreeMap<String, URef>) -> Self {
         ProtocolData {
             wasm_costs,
-            mint,
-            proof_of_stake,
+            system_contracts,
+            rent_parameters: RentParameters::default(),
+            installers: BTreeMap::new(),
+            sponsorships: BTreeMap::new(),
+            host_function_costs: HostFunctionCosts::default(),
+            refund_percentage: DEFAULT_REFUND_PERCENTAGE,
+            chain_parameters: ChainParameters::default(),
+            denied_host_functions: DeniedHostFunctions::default(),
+            gas_metering: GasMetering::default(),
         }
     }
 
+    /// Returns the [`ProtocolData`] with the given [`RentParameters`].
+    pub fn with_rent_parameters(mut self, rent_parameters: RentParameters) -> Self {
+        self.rent_parameters = rent_parameters;
+        self
+    }
+
+    /// Returns the [`ProtocolData`] with the given installers of system contracts, keyed by the
+    /// name the contract is registered under.  Each installer is referenced by the hash of its
+    /// bytes, which are persisted in global state under [`Key::Hash`](types::Key::Hash) of that
+    /// hash.
+    pub fn with_installers(mut self, installers: BTreeMap<String, Blake2bHash>) -> Self {
+        self.installers = installers;
+        self
+    }
+
+    /// Returns the [`ProtocolData`] with the given table of [`Sponsorship`]s, keyed by the hash of
+    /// the sponsored contract.
+    pub fn with_sponsorships(mut self, sponsorships: BTreeMap<[u8; 32], Sponsorship>) -> Self {
+        self.sponsorships = sponsorships;
+        self
+    }
+
+    /// Returns the [`ProtocolData`] with the given [`HostFunctionCosts`].
+    pub fn with_host_function_costs(mut self, host_function_costs: HostFunctionCosts) -> Self {
+        self.host_function_costs = host_function_costs;
+        self
+    }
+
+    /// Returns the [`ProtocolData`] refunding `refund_percentage` percent of the unspent payment of
+    /// each deploy, the rest of which is paid to the validators.
+    pub fn with_refund_percentage(mut self, refund_percentage: u8) -> Self {
+        self.refund_percentage = refund_percentage;
+        self
+    }
+
+    /// Returns the [`ProtocolData`] with the given [`ChainParameters`].
+    pub fn with_chain_parameters(mut self, chain_parameters: ChainParameters) -> Self {
+        self.chain_parameters = chain_parameters;
+        self
+    }
+
+    /// Returns the [`ProtocolData`] denying modules the import of the given host functions.
+    pub fn with_denied_host_functions(
+        mut self,
+        denied_host_functions: DeniedHostFunctions,
+    ) -> Self {
+        self.denied_host_functions = denied_host_functions;
+        self
+    }
+
+    /// Returns the [`ProtocolData`] charging gas for instructions with the given [`GasMetering`].
+    pub fn with_gas_metering(mut self, gas_metering: GasMetering) -> Self {
+        self.gas_metering = gas_metering;
+        self
+    }
+
     /// Gets the [`WasmCosts`] value from a given [`ProtocolData`] value.
     pub fn wasm_costs(&self) -> &WasmCosts {
         &self.wasm_costs
     }
 
+    /// Gets the [`RentParameters`] value from a given [`ProtocolData`] value.
+    pub fn rent_parameters(&self) -> &RentParameters {
+        &self.rent_parameters
+    }
+
+    /// Gets the flat costs of calls to individual host functions.
+    pub fn host_function_costs(&self) -> &HostFunctionCosts {
+        &self.host_function_costs
+    }
+
+    /// Gets the percentage of the unspent payment of each deploy which is refunded.
+    pub fn refund_percentage(&self) -> u8 {
+        self.refund_percentage
+    }
+
+    /// Gets the limits on the payment and the gas of deploys.
+    pub fn chain_parameters(&self) -> &ChainParameters {
+        &self.chain_parameters
+    }
+
+    /// Gets the host functions which modules may not import.
+    pub fn denied_host_functions(&self) -> &DeniedHostFunctions {
+        &self.denied_host_functions
+    }
+
+    /// Gets the strategy used to charge gas for the instructions executed by modules.
+    pub fn gas_metering(&self) -> GasMetering {
+        self.gas_metering
+    }
+
+    /// Gets the hashes of the installers of system contracts, keyed by the name the contract is
+    /// registered under.
+    pub fn installers(&self) -> &BTreeMap<String, Blake2bHash> {
+        &self.installers
+    }
+
+    /// Looks up the hash of the installer of the system contract registered under `name`.
+    pub fn installer(&self, name: &str) -> Option<Blake2bHash> {
+        self.installers.get(name).cloned()
+    }
+
+    /// Gets the table of [`Sponsorship`]s, keyed by the hash of the sponsored contract.
+    pub fn sponsorships(&self) -> &BTreeMap<[u8; 32], Sponsorship> {
+        &self.sponsorships
+    }
+
+    /// Looks up the [`Sponsorship`] of the contract stored under the given hash.
+    pub fn sponsorship(&self, contract_hash: &[u8; 32]) -> Option<Sponsorship> {
+        self.sponsorships.get(contract_hash).cloned()
+    }
+
+    /// Gets the registry of system contracts keyed by name.
+    pub fn system_contract_registry(&self) -> &BTreeMap<String, URef> {
+        &self.system_contracts
+    }
+
+    /// Looks up a system contract by the name it is registered under.
+    pub fn system_contract(&self, name: &str) -> Option<URef> {
+        self.system_contracts.get(name).cloned()
+    }
+
+    /// Gets the mint contract, or a placeholder [`URef`] with an all-zero address if it is not
+    /// registered yet.
     pub fn mint(&self) -> URef {
-        self.mint
+        self.system_contract(MINT_NAME)
+            .unwrap_or_else(unknown_system_contract)
     }
 
+    /// Gets the proof of stake contract, or a placeholder [`URef`] with an all-zero address if it
+    /// is not registered yet.
     pub fn proof_of_stake(&self) -> URef {
-        self.proof_of_stake
+        self.system_contract(POS_NAME)
+            .unwrap_or_else(unknown_system_contract)
     }
 
     /// Retrieves all valid system contracts stored in protocol version
     pub fn system_contracts(&self) -> Vec<URef> {
-        let mut vec = Vec::with_capacity(2);
-        if self.mint.addr() != [0; 32] {
-            vec.push(self.mint)
-        }
-        if self.proof_of_stake.addr() != [0; 32] {
-            vec.push(self.proof_of_stake)
-        }
-        vec
+        self.system_contracts
+            .values()
+            .filter(|uref| uref.addr() != [0; 32])
+            .cloned()
+            .collect()
+    }
+
+    /// Decodes the untagged layout of the 1.0.0 release, i.e. the [`WasmCosts`] of that release
+    /// followed by the mint and proof of stake [`URef`]s.
+    fn from_legacy_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (wasm_costs, rem) = WasmCosts::from_legacy_bytes(bytes)?;
+        let (mint_reference, rem): (URef, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (proof_of_stake_reference, rem): (URef, &[u8]) = FromBytes::from_bytes(rem)?;
+        let mut system_contracts = BTreeMap::new();
+        system_contracts.insert(MINT_NAME.to_string(), mint_reference);
+        system_contracts.insert(POS_NAME.to_string(), proof_of_stake_reference);
+        Ok((ProtocolData::new(wasm_costs, system_contracts), rem))
+    }
+
+    /// Decodes the fields following the tag of the current layout.
+    fn from_tagged_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (wasm_costs, rem) = FromBytes::from_bytes(bytes)?;
+        let (system_contracts, rem) = FromBytes::from_bytes(rem)?;
+        let (rent_parameters, rem) = FromBytes::from_bytes(rem)?;
+        let (installers, rem) = FromBytes::from_bytes(rem)?;
+        let (sponsorships, rem) = FromBytes::from_bytes(rem)?;
+        let (host_function_costs, rem) = FromBytes::from_bytes(rem)?;
+        let (refund_percentage, rem) = FromBytes::from_bytes(rem)?;
+        let (chain_parameters, rem) = FromBytes::from_bytes(rem)?;
+        let (denied_host_functions, rem) = FromBytes::from_bytes(rem)?;
+        let (gas_metering, rem) = FromBytes::from_bytes(rem)?;
+        let protocol_data = ProtocolData {
+            wasm_costs,
+            system_contracts,
+            rent_parameters,
+            installers,
+            sponsorships,
+            host_function_costs,
+            refund_percentage,
+            chain_parameters,
+            denied_host_functions,
+            gas_metering,
+        };
+        Ok((protocol_data, rem))
     }
 }
 
+/// Protocol data is written as a tag of the layout version followed by every field.
 impl ToBytes for ProtocolData {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        let mut ret: Vec<u8> = Vec::with_capacity(PROTOCOL_DATA_SERIALIZED_LENGTH);
+        let mut ret: Vec<u8> = PROTOCOL_DATA_LAYOUT_VERSION.to_bytes()?;
         ret.append(&mut self.wasm_costs.to_bytes()?);
-        ret.append(&mut self.mint.to_bytes()?);
-        ret.append(&mut self.proof_of_stake.to_bytes()?);
+        ret.append(&mut self.system_contracts.to_bytes()?);
+        ret.append(&mut self.rent_parameters.to_bytes()?);
+        ret.append(&mut self.installers.to_bytes()?);
+        ret.append(&mut self.sponsorships.to_bytes()?);
+        ret.append(&mut self.host_function_costs.to_bytes()?);
+        ret.append(&mut self.refund_percentage.to_bytes()?);
+        ret.append(&mut self.chain_parameters.to_bytes()?);
+        ret.append(&mut self.denied_host_functions.to_bytes()?);
+        ret.append(&mut self.gas_metering.to_bytes()?);
         Ok(ret)
     }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + self.wasm_costs.serialized_length()
+            + self.system_contracts.serialized_length()
+            + self.rent_parameters.serialized_length()
+            + self.installers.serialized_length()
+            + self.sponsorships.serialized_length()
+            + self.host_function_costs.serialized_length()
+            + self.refund_percentage.serialized_length()
+            + self.chain_parameters.serialized_length()
+            + self.denied_host_functions.serialized_length()
+            + self.gas_metering.serialized_length()
+    }
 }
 
+/// Protocol data is always stored as a value of its own, so values of the length of the untagged
+/// layout of the 1.0.0 release are decoded in that layout.  They are written back in the current
+/// layout the next time protocol data is stored, e.g. on upgrade.
 impl FromBytes for ProtocolData {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (wasm_costs, rem): (WasmCosts, &[u8]) = FromBytes::from_bytes(bytes)?;
-        let (mint_reference, rem): (URef, &[u8]) = FromBytes::from_bytes(rem)?;
-        let (proof_of_stake_reference, rem): (URef, &[u8]) = FromBytes::from_bytes(rem)?;
-        Ok((
-            ProtocolData {
-                wasm_costs,
-                mint: mint_reference,
-                proof_of_stake: proof_of_stake_reference,
-            },
-            rem,
-        ))
+        if bytes.len() == LEGACY_PROTOCOL_DATA_SERIALIZED_LENGTH {
+            return ProtocolData::from_legacy_bytes(bytes);
+        }
+        let (version, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        if version != PROTOCOL_DATA_LAYOUT_VERSION {
+            return Err(bytesrepr::Error::FormattingError);
+        }
+        ProtocolData::from_tagged_bytes(rem)
     }
 }
 
 #[cfg(test)]
 pub(crate) mod gens {
-    use proptest::prop_compose;
+    use proptest::{
+        collection::btree_map,
+        prelude::{any, Strategy},
+        prop_compose,
+    };
 
-    use engine_wasm_prep::wasm_costs::gens as wasm_costs_gens;
+    use engine_shared::{gas::Gas, newtypes::Blake2bHash};
+    use engine_wasm_prep::{
+        denied_host_functions::gens as denied_host_functions_gens,
+        gas_metering::gens as gas_metering_gens,
+        host_function_costs::gens as host_function_costs_gens, wasm_costs::gens as wasm_costs_gens,
+    };
     use types::gens;
 
-    use super::ProtocolData;
+    use super::{ChainParameters, ProtocolData, RentParameters, Sponsorship};
+
+    prop_compose! {
+        pub fn rent_parameters_arb()(enabled in any::<bool>(), expiry in any::<u64>()) -> RentParameters {
+            RentParameters::new(enabled, expiry)
+        }
+    }
+
+    prop_compose! {
+        pub fn sponsorship_arb()(allowance in gens::u512_arb(), purse in gens::uref_arb()) -> Sponsorship {
+            Sponsorship::new(Gas::new(allowance), purse)
+        }
+    }
+
+    prop_compose! {
+        pub fn chain_parameters_arb()(
+            max_payment in any::<u64>(),
+            conv_rate in any::<u64>(),
+            block_gas_limit in any::<u64>(),
+            seigniorage_rate in any::<u64>(),
+            native_transfer_cost in any::<u64>(),
+        ) -> ChainParameters {
+            ChainParameters::new(
+                max_payment,
+                conv_rate,
+                block_gas_limit,
+                seigniorage_rate,
+                native_transfer_cost,
+            )
+        }
+    }
 
     prop_compose! {
         pub fn protocol_data_arb()(
             wasm_costs in wasm_costs_gens::wasm_costs_arb(),
-            mint in gens::uref_arb(),
-            proof_of_stake in gens::uref_arb(),
+            system_contracts in btree_map("\\PC*", gens::uref_arb(), 0..3),
+            rent_parameters in rent_parameters_arb(),
+            installers in btree_map("\\PC*", any::<[u8; 32]>().prop_map(Blake2bHash::from), 0..3),
+            sponsorships in btree_map(any::<[u8; 32]>(), sponsorship_arb(), 0..3),
+            host_function_costs in host_function_costs_gens::host_function_costs_arb(),
+            refund_percentage in 0..=100u8,
+            chain_parameters in chain_parameters_arb(),
+            denied_host_functions in denied_host_functions_gens::denied_host_functions_arb(),
+            gas_metering in gas_metering_gens::gas_metering_arb(),
         ) -> ProtocolData {
             ProtocolData {
                 wasm_costs,
-                mint,
-                proof_of_stake,
+                system_contracts,
+                rent_parameters,
+                installers,
+                sponsorships,
+                host_function_costs,
+                refund_percentage,
+                chain_parameters,
+                denied_host_functions,
+                gas_metering,
             }
         }
     }
@@ -117,12 +620,44 @@ pub(crate) mod gens {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use proptest::proptest;
 
-    use engine_shared::test_utils;
-    use types::{bytesrepr, AccessRights, URef};
+    use engine_shared::{gas::Gas, motes::Motes, newtypes::Blake2bHash, test_utils};
+    use engine_wasm_prep::{
+        denied_host_functions::DeniedHostFunctions,
+        gas_metering::{GasMetering, DEFAULT_FUEL_FLUSH_THRESHOLD},
+        host_function_costs::HostFunctionCosts,
+        wasm_costs::WasmCosts,
+    };
+    use types::{
+        bytesrepr::{self, ToBytes},
+        AccessRights, URef, U512,
+    };
 
-    use super::{gens, ProtocolData};
+    use super::{
+        gens, ChainParameters, ProtocolData, RentParameters, Sponsorship, DEFAULT_CONV_RATE,
+        DEFAULT_MAX_PAYMENT, DEFAULT_NATIVE_TRANSFER_COST, DEFAULT_REFUND_PERCENTAGE, MINT_NAME,
+        POS_NAME,
+    };
+
+    /// Encodings produced by the encoder of the 1.0.0 release.
+    mod v1_0_0 {
+        /// The ten `WasmCosts` of the release followed by the mint and proof of stake `URef`s.
+        pub const PROTOCOL_DATA: &str = concat!(
+            "01000000100000000400000002000000001000000020000001000000000001000300000008000000",
+            "c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c50107",
+            "c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c6c60107"
+        );
+    }
+
+    fn system_contracts(mint: URef, proof_of_stake: URef) -> BTreeMap<String, URef> {
+        let mut system_contracts = BTreeMap::new();
+        system_contracts.insert(MINT_NAME.to_string(), mint);
+        system_contracts.insert(POS_NAME.to_string(), proof_of_stake);
+        system_contracts
+    }
 
     #[test]
     fn should_serialize_and_deserialize() {
@@ -130,16 +665,317 @@ mod tests {
             let costs = test_utils::wasm_costs_mock();
             let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
             let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
-            ProtocolData::new(costs, mint_reference, proof_of_stake_reference)
+            ProtocolData::new(
+                costs,
+                system_contracts(mint_reference, proof_of_stake_reference),
+            )
         };
         let free = {
             let costs = test_utils::wasm_costs_free();
             let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
             let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
-            ProtocolData::new(costs, mint_reference, proof_of_stake_reference)
+            ProtocolData::new(
+                costs,
+                system_contracts(mint_reference, proof_of_stake_reference),
+            )
         };
         bytesrepr::test_serialization_roundtrip(&mock);
         bytesrepr::test_serialization_roundtrip(&free);
+        bytesrepr::test_serialization_roundtrip(&ProtocolData::default());
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_rent_parameters() {
+        let costs = test_utils::wasm_costs_mock();
+        let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
+        let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
+        let without_rent = ProtocolData::new(
+            costs,
+            system_contracts(mint_reference, proof_of_stake_reference),
+        );
+        let with_rent = without_rent
+            .clone()
+            .with_rent_parameters(RentParameters::new(true, 1_000));
+
+        bytesrepr::test_serialization_roundtrip(&with_rent);
+        assert_eq!(
+            with_rent.rent_parameters(),
+            &RentParameters::new(true, 1_000)
+        );
+        assert_eq!(without_rent.rent_parameters(), &RentParameters::default());
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_installers() {
+        let costs = test_utils::wasm_costs_mock();
+        let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
+        let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
+        let without_installers = ProtocolData::new(
+            costs,
+            system_contracts(mint_reference, proof_of_stake_reference),
+        );
+        let installers = {
+            let mut ret = BTreeMap::new();
+            ret.insert(MINT_NAME.to_string(), Blake2bHash::new(&[2u8; 8]));
+            ret.insert(POS_NAME.to_string(), Blake2bHash::new(&[3u8; 8]));
+            ret
+        };
+        let with_installers = without_installers
+            .clone()
+            .with_installers(installers.clone());
+        let with_rent_and_installers = with_installers
+            .clone()
+            .with_rent_parameters(RentParameters::new(true, 1_000));
+
+        bytesrepr::test_serialization_roundtrip(&with_installers);
+        bytesrepr::test_serialization_roundtrip(&with_rent_and_installers);
+        assert_eq!(with_installers.installers(), &installers);
+        assert_eq!(
+            with_installers.installer(MINT_NAME),
+            Some(Blake2bHash::new(&[2u8; 8]))
+        );
+        assert_eq!(without_installers.installer(MINT_NAME), None);
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_sponsorships() {
+        let costs = test_utils::wasm_costs_mock();
+        let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
+        let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
+        let without_sponsorships = ProtocolData::new(
+            costs,
+            system_contracts(mint_reference, proof_of_stake_reference),
+        );
+        let sponsorship = Sponsorship::new(
+            Gas::new(U512::from(1_000)),
+            URef::new([4u8; 32], AccessRights::READ_ADD_WRITE),
+        );
+        let sponsorships = {
+            let mut ret = BTreeMap::new();
+            ret.insert([5u8; 32], sponsorship);
+            ret
+        };
+        let with_sponsorships = without_sponsorships
+            .clone()
+            .with_sponsorships(sponsorships.clone());
+
+        bytesrepr::test_serialization_roundtrip(&sponsorship);
+        bytesrepr::test_serialization_roundtrip(&with_sponsorships);
+        assert_eq!(with_sponsorships.sponsorships(), &sponsorships);
+        assert_eq!(with_sponsorships.sponsorship(&[5u8; 32]), Some(sponsorship));
+        assert_eq!(with_sponsorships.sponsorship(&[6u8; 32]), None);
+        assert_eq!(without_sponsorships.sponsorship(&[5u8; 32]), None);
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_host_function_costs() {
+        let costs = test_utils::wasm_costs_mock();
+        let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
+        let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
+        let without_host_function_costs = ProtocolData::new(
+            costs,
+            system_contracts(mint_reference, proof_of_stake_reference),
+        );
+        let host_function_costs = HostFunctionCosts::new()
+            .with_cost("transfer_to_account", 10_000)
+            .with_cost("store_function", 5_000);
+        let with_host_function_costs = without_host_function_costs
+            .clone()
+            .with_host_function_costs(host_function_costs.clone());
+
+        bytesrepr::test_serialization_roundtrip(&with_host_function_costs);
+        assert_eq!(
+            with_host_function_costs.host_function_costs(),
+            &host_function_costs
+        );
+        assert!(without_host_function_costs.host_function_costs().is_empty());
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_refund_percentage() {
+        let costs = test_utils::wasm_costs_mock();
+        let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
+        let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
+        let with_full_refund = ProtocolData::new(
+            costs,
+            system_contracts(mint_reference, proof_of_stake_reference),
+        );
+        let with_partial_refund = with_full_refund.clone().with_refund_percentage(25);
+
+        bytesrepr::test_serialization_roundtrip(&with_partial_refund);
+        assert_eq!(with_partial_refund.refund_percentage(), 25);
+        assert_eq!(
+            with_full_refund.refund_percentage(),
+            DEFAULT_REFUND_PERCENTAGE
+        );
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_chain_parameters() {
+        let costs = test_utils::wasm_costs_mock();
+        let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
+        let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
+        let with_default_parameters = ProtocolData::new(
+            costs,
+            system_contracts(mint_reference, proof_of_stake_reference),
+        );
+        let chain_parameters = ChainParameters::new(5_000_000, 2, 1_000_000_000, 10_000_000, 5_000);
+        let with_chain_parameters = with_default_parameters
+            .clone()
+            .with_chain_parameters(chain_parameters);
+
+        bytesrepr::test_serialization_roundtrip(&chain_parameters);
+        bytesrepr::test_serialization_roundtrip(&with_chain_parameters);
+        assert_eq!(with_chain_parameters.chain_parameters(), &chain_parameters);
+
+        let default_parameters = with_default_parameters.chain_parameters();
+        assert_eq!(
+            default_parameters.max_payment(),
+            Motes::from(DEFAULT_MAX_PAYMENT)
+        );
+        assert_eq!(default_parameters.conv_rate(), DEFAULT_CONV_RATE);
+        assert_eq!(default_parameters.block_gas_limit(), None);
+        assert_eq!(default_parameters.seigniorage_rate(), 0);
+        assert_eq!(
+            default_parameters.native_transfer_cost(),
+            Gas::from(DEFAULT_NATIVE_TRANSFER_COST)
+        );
+        assert_eq!(
+            chain_parameters.block_gas_limit(),
+            Some(Gas::from(1_000_000_000u64))
+        );
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_denied_host_functions() {
+        let costs = test_utils::wasm_costs_mock();
+        let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
+        let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
+        let without_denied_host_functions = ProtocolData::new(
+            costs,
+            system_contracts(mint_reference, proof_of_stake_reference),
+        );
+        let denied_host_functions = DeniedHostFunctions::new().with_denied("serialize_function");
+        let with_denied_host_functions = without_denied_host_functions
+            .clone()
+            .with_denied_host_functions(denied_host_functions.clone());
+
+        bytesrepr::test_serialization_roundtrip(&with_denied_host_functions);
+        assert_eq!(
+            with_denied_host_functions.denied_host_functions(),
+            &denied_host_functions
+        );
+        assert!(without_denied_host_functions
+            .denied_host_functions()
+            .is_empty());
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_gas_metering() {
+        let costs = test_utils::wasm_costs_mock();
+        let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
+        let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
+        let with_host_call_metering = ProtocolData::new(
+            costs,
+            system_contracts(mint_reference, proof_of_stake_reference),
+        );
+        let fuel_metering = GasMetering::Fuel {
+            flush_threshold: DEFAULT_FUEL_FLUSH_THRESHOLD,
+        };
+        let with_fuel_metering = with_host_call_metering
+            .clone()
+            .with_gas_metering(fuel_metering);
+
+        bytesrepr::test_serialization_roundtrip(&with_fuel_metering);
+        assert_eq!(with_fuel_metering.gas_metering(), fuel_metering);
+        assert_eq!(
+            with_host_call_metering.gas_metering(),
+            GasMetering::HostCall
+        );
+    }
+
+    #[test]
+    fn should_decode_v1_0_0_fixture() {
+        let bytes = base16::decode(v1_0_0::PROTOCOL_DATA).expect("fixture should be valid hex");
+        assert_eq!(bytes.len(), super::LEGACY_PROTOCOL_DATA_SERIALIZED_LENGTH);
+
+        let protocol_data: ProtocolData =
+            bytesrepr::deserialize(bytes).expect("should deserialize v1.0.0 layout");
+
+        let wasm_costs = WasmCosts {
+            regular: 1,
+            div: 16,
+            mul: 4,
+            mem: 2,
+            initial_mem: 4096,
+            grow_mem: 8192,
+            memcpy: 1,
+            max_stack_height: 64 * 1024,
+            opcodes_mul: 3,
+            opcodes_div: 8,
+            ..Default::default()
+        };
+        let mint_reference = URef::new([197u8; 32], AccessRights::READ_ADD_WRITE);
+        let proof_of_stake_reference = URef::new([198u8; 32], AccessRights::READ_ADD_WRITE);
+        assert_eq!(
+            protocol_data,
+            ProtocolData::new(
+                wasm_costs,
+                system_contracts(mint_reference, proof_of_stake_reference)
+            )
+        );
+
+        // Written back in the current layout
+        bytesrepr::test_serialization_roundtrip(&protocol_data);
+    }
+
+    #[test]
+    fn should_tag_current_layout() {
+        let bytes = ProtocolData::default()
+            .to_bytes()
+            .expect("should serialize");
+        assert_eq!(bytes[0], super::PROTOCOL_DATA_LAYOUT_VERSION);
+        assert!(bytes.len() > super::LEGACY_PROTOCOL_DATA_SERIALIZED_LENGTH);
+
+        let mut unknown_version = bytes.clone();
+        unknown_version[0] = super::PROTOCOL_DATA_LAYOUT_VERSION + 1;
+        assert_eq!(
+            bytesrepr::deserialize::<ProtocolData>(unknown_version),
+            Err(bytesrepr::Error::FormattingError)
+        );
+
+        let mut trailing_bytes = bytes;
+        trailing_bytes.push(0);
+        assert!(bytesrepr::deserialize::<ProtocolData>(trailing_bytes).is_err());
+    }
+
+    #[test]
+    fn should_look_up_system_contracts_by_name() {
+        let mint_reference = URef::new([197u8; 32], AccessRights::READ_ADD_WRITE);
+        let proof_of_stake_reference = URef::new([198u8; 32], AccessRights::READ_ADD_WRITE);
+        let other_reference = URef::new([199u8; 32], AccessRights::READ_ADD_WRITE);
+        let protocol_data = {
+            let costs = test_utils::wasm_costs_mock();
+            let mut system_contracts = system_contracts(mint_reference, proof_of_stake_reference);
+            system_contracts.insert("other".to_string(), other_reference);
+            ProtocolData::new(costs, system_contracts)
+        };
+
+        assert_eq!(protocol_data.mint(), mint_reference);
+        assert_eq!(protocol_data.proof_of_stake(), proof_of_stake_reference);
+        assert_eq!(
+            protocol_data.system_contract("other"),
+            Some(other_reference)
+        );
+        assert_eq!(protocol_data.system_contract("missing"), None);
+        assert_eq!(protocol_data.system_contract_registry().len(), 3);
+    }
+
+    #[test]
+    fn should_return_placeholder_for_unregistered_system_contracts() {
+        let protocol_data = ProtocolData::default();
+        assert_eq!(protocol_data.mint().addr(), [0u8; 32]);
+        assert_eq!(protocol_data.proof_of_stake().addr(), [0u8; 32]);
     }
 
     #[test]
@@ -148,7 +984,10 @@ mod tests {
         let proof_of_stake_reference = URef::new([198u8; 32], AccessRights::READ_ADD_WRITE);
         let protocol_data = {
             let costs = test_utils::wasm_costs_mock();
-            ProtocolData::new(costs, mint_reference, proof_of_stake_reference)
+            ProtocolData::new(
+                costs,
+                system_contracts(mint_reference, proof_of_stake_reference),
+            )
         };
 
         let actual = {
@@ -170,7 +1009,10 @@ mod tests {
         let proof_of_stake_reference = URef::new([0u8; 32], AccessRights::READ);
         let protocol_data = {
             let costs = test_utils::wasm_costs_mock();
-            ProtocolData::new(costs, mint_reference, proof_of_stake_reference)
+            ProtocolData::new(
+                costs,
+                system_contracts(mint_reference, proof_of_stake_reference),
+            )
         };
 
         let actual = {
@@ -44,6 +44,23 @@ fn lmdb_roundtrip_succeeds(inputs: BTreeMap<ProtocolVersion, ProtocolData>) -> b
     ret
 }
 
+fn in_memory_get_all_succeeds(inputs: BTreeMap<ProtocolVersion, ProtocolData>) -> bool {
+    let env = InMemoryEnvironment::new();
+    let store = InMemoryProtocolDataStore::new(&env, None);
+
+    store_tests::get_all_succeeds(&env, &store, inputs).unwrap()
+}
+
+fn lmdb_get_all_succeeds(inputs: BTreeMap<ProtocolVersion, ProtocolData>) -> bool {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let env = LmdbEnvironment::new(&tmp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+    let store = LmdbProtocolDataStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+
+    let ret = store_tests::get_all_succeeds(&env, &store, inputs).unwrap();
+    tmp_dir.close().unwrap();
+    ret
+}
+
 proptest! {
     #[test]
     fn prop_in_memory_roundtrip_succeeds(
@@ -58,4 +75,18 @@ proptest! {
     ) {
         assert!(lmdb_roundtrip_succeeds(m))
     }
+
+    #[test]
+    fn prop_in_memory_get_all_succeeds(
+        m in collection::btree_map(gens_ext::protocol_version_arb(), gens::protocol_data_arb(), get_range())
+    ) {
+        assert!(in_memory_get_all_succeeds(m))
+    }
+
+    #[test]
+    fn prop_lmdb_get_all_succeeds(
+        m in collection::btree_map(gens_ext::protocol_version_arb(), gens::protocol_data_arb(), get_range())
+    ) {
+        assert!(lmdb_get_all_succeeds(m))
+    }
 }
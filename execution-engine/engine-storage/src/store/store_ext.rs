@@ -1,8 +1,8 @@
-use types::bytesrepr::{FromBytes, ToBytes};
+use types::bytesrepr::{self, FromBytes, ToBytes};
 
 use crate::{
     store::Store,
-    transaction_source::{Readable, Writable},
+    transaction_source::{Iterable, Readable, Writable},
 };
 
 pub trait StoreExt<K, V>: Store<K, V> {
@@ -25,6 +25,23 @@ pub trait StoreExt<K, V>: Store<K, V> {
         Ok(ret)
     }
 
+    /// Returns every key-value pair in the store, in no particular order.
+    fn get_all<T>(&self, txn: &T) -> Result<Vec<(K, V)>, Self::Error>
+    where
+        T: Iterable<Handle = Self::Handle>,
+        K: FromBytes,
+        V: FromBytes,
+        Self::Error: From<T::Error>,
+    {
+        let mut ret: Vec<(K, V)> = Vec::new();
+        for (key_bytes, value_bytes) in txn.read_all(self.handle())? {
+            let key = bytesrepr::deserialize(key_bytes)?;
+            let value = bytesrepr::deserialize(value_bytes)?;
+            ret.push((key, value))
+        }
+        Ok(ret)
+    }
+
     fn put_many<'a, T>(
         &self,
         txn: &mut T,
@@ -5,7 +5,7 @@ use std::{
 
 use crate::{
     error::in_memory::Error,
-    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+    transaction_source::{Iterable, Readable, Transaction, TransactionSource, Writable},
 };
 
 /// A marker for use in a mutex which represents the capability to perform a
@@ -16,20 +16,33 @@ type WriteLock<'a> = MutexGuard<'a, WriteCapability>;
 
 type BytesMap = HashMap<Vec<u8>, Vec<u8>>;
 
-type PoisonError<'a> = sync::PoisonError<MutexGuard<'a, HashMap<Option<String>, BytesMap>>>;
+/// An immutable snapshot of the contents of all databases, as of the last committed write.
+type Snapshot = Arc<HashMap<Option<String>, BytesMap>>;
+
+type PoisonError<'a> = sync::PoisonError<MutexGuard<'a, Snapshot>>;
+
+fn read_all(sub_view: Option<&BytesMap>) -> Vec<(Vec<u8>, Vec<u8>)> {
+    sub_view
+        .map(|sub_view| {
+            sub_view
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
 
 /// A read transaction for the in-memory trie store.
+///
+/// Reads see the snapshot which was current when the transaction was created; later commits are
+/// not visible, and do not have to wait for the transaction to finish.
 pub struct InMemoryReadTransaction {
-    view: HashMap<Option<String>, BytesMap>,
+    view: Snapshot,
 }
 
 impl InMemoryReadTransaction {
     pub fn new(store: &InMemoryEnvironment) -> Result<InMemoryReadTransaction, Error> {
-        let view = {
-            let db_ref = Arc::clone(&store.data);
-            let view_lock = db_ref.lock()?;
-            view_lock.to_owned()
-        };
+        let view = Arc::clone(&*store.data.lock()?);
         Ok(InMemoryReadTransaction { view })
     }
 }
@@ -54,21 +67,31 @@ impl Readable for InMemoryReadTransaction {
     }
 }
 
+impl Iterable for InMemoryReadTransaction {
+    fn read_all(&self, handle: Self::Handle) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Self::Error> {
+        Ok(read_all(self.view.get(&handle)))
+    }
+}
+
 /// A read-write transaction for the in-memory trie store.
+///
+/// Writes are made to a private copy of the latest snapshot, which replaces it on commit.  Only
+/// one read-write transaction can exist at a time.
 pub struct InMemoryReadWriteTransaction<'a> {
     view: HashMap<Option<String>, BytesMap>,
-    store_ref: Arc<Mutex<HashMap<Option<String>, BytesMap>>>,
+    store_ref: Arc<Mutex<Snapshot>>,
     _write_lock: WriteLock<'a>,
 }
 
 impl<'a> InMemoryReadWriteTransaction<'a> {
     pub fn new(store: &'a InMemoryEnvironment) -> Result<InMemoryReadWriteTransaction<'a>, Error> {
+        // Taken before copying the snapshot, so that no other commit can be made in between
+        let _write_lock = store.write_mutex.lock()?;
         let store_ref = Arc::clone(&store.data);
         let view = {
-            let view_lock = store_ref.lock()?;
-            view_lock.to_owned()
+            let snapshot = Arc::clone(&*store_ref.lock()?);
+            snapshot.as_ref().to_owned()
         };
-        let _write_lock = store.write_mutex.lock()?;
         Ok(InMemoryReadWriteTransaction {
             view,
             store_ref,
@@ -83,8 +106,8 @@ impl<'a> Transaction for InMemoryReadWriteTransaction<'a> {
     type Handle = Option<String>;
 
     fn commit(self) -> Result<(), Self::Error> {
-        let mut store_ref_lock = self.store_ref.lock()?;
-        store_ref_lock.extend(self.view);
+        let snapshot = Arc::new(self.view);
+        *self.store_ref.lock()? = snapshot;
         Ok(())
     }
 }
@@ -99,6 +122,12 @@ impl<'a> Readable for InMemoryReadWriteTransaction<'a> {
     }
 }
 
+impl<'a> Iterable for InMemoryReadWriteTransaction<'a> {
+    fn read_all(&self, handle: Self::Handle) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Self::Error> {
+        Ok(read_all(self.view.get(&handle)))
+    }
+}
+
 impl<'a> Writable for InMemoryReadWriteTransaction<'a> {
     fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
         let sub_view = self.view.entry(handle).or_default();
@@ -109,7 +138,7 @@ impl<'a> Writable for InMemoryReadWriteTransaction<'a> {
 
 /// An environment for the in-memory trie store.
 pub struct InMemoryEnvironment {
-    data: Arc<Mutex<HashMap<Option<String>, BytesMap>>>,
+    data: Arc<Mutex<Snapshot>>,
     write_mutex: Arc<Mutex<WriteCapability>>,
 }
 
@@ -118,7 +147,7 @@ impl Default for InMemoryEnvironment {
         let data = {
             let mut initial_map = HashMap::new();
             initial_map.insert(None, Default::default());
-            Arc::new(Mutex::new(initial_map))
+            Arc::new(Mutex::new(Arc::new(initial_map)))
         };
         let write_mutex = Arc::new(Mutex::new(WriteCapability));
         InMemoryEnvironment { data, write_mutex }
@@ -1,26 +1,89 @@
-use std::path::PathBuf;
+use std::{
+    ffi::CString,
+    fs, mem,
+    ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use lmdb::{self, Cursor, Database, Environment, RoTransaction, RwTransaction, WriteFlags};
+use lmdb_sys as ffi;
+use parking_lot::{RwLock, RwLockReadGuard};
 
-use lmdb::{self, Database, Environment, RoTransaction, RwTransaction, WriteFlags};
+use engine_shared::{
+    logging::{log_metric, GAUGE},
+    metrics,
+    newtypes::CorrelationId,
+};
 
 use crate::{
     error,
-    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+    transaction_source::{Iterable, Readable, Transaction, TransactionSource, Writable},
     MAX_DBS,
 };
 
-impl<'a> Transaction for RoTransaction<'a> {
+/// The factor by which the map of an [`LmdbEnvironment`] is grown by default when it is full.
+pub const DEFAULT_MAP_GROWTH_FACTOR: f64 = 2.0;
+
+/// How often [`LmdbEnvironment::copy_compacted`] reports its progress.
+const COMPACTION_PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The name of the data file of an environment.
+const DATA_FILE_NAME: &str = "data.mdb";
+
+const LMDB_MAP_UTILIZATION: &str = "lmdb_map_utilization";
+const MAP_SIZE: &str = "map_size";
+
+fn lmdb_result(err_code: i32) -> Result<(), lmdb::Error> {
+    if err_code == ffi::MDB_SUCCESS {
+        Ok(())
+    } else {
+        Err(lmdb::Error::from_err_code(err_code))
+    }
+}
+
+fn read_all<T: lmdb::Transaction>(
+    txn: &T,
+    handle: Database,
+) -> Result<Vec<(Vec<u8>, Vec<u8>)>, lmdb::Error> {
+    let mut cursor = txn.open_ro_cursor(handle)?;
+    let pairs = cursor
+        .iter_start()
+        .map(|(key, value)| (key.to_vec(), value.to_vec()))
+        .collect();
+    Ok(pairs)
+}
+
+/// A raw environment handle which can be moved to the thread copying the environment.
+struct EnvPtr(*mut ffi::MDB_env);
+
+// LMDB environments are thread-safe, and the handle is only used while the copy is joined on.
+unsafe impl Send for EnvPtr {}
+
+/// A read transaction of an [`LmdbEnvironment`].
+///
+/// Holds the guard returned by [`transaction_guard`](LmdbEnvironment::transaction_guard), so the
+/// map cannot be resized while the transaction is open.
+pub struct LmdbReadTransaction<'a> {
+    txn: RoTransaction<'a>,
+    _guard: RwLockReadGuard<'a, ()>,
+}
+
+impl<'a> Transaction for LmdbReadTransaction<'a> {
     type Error = lmdb::Error;
 
     type Handle = Database;
 
     fn commit(self) -> Result<(), Self::Error> {
-        lmdb::Transaction::commit(self)
+        lmdb::Transaction::commit(self.txn)
     }
 }
 
-impl<'a> Readable for RoTransaction<'a> {
+impl<'a> Readable for LmdbReadTransaction<'a> {
     fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
-        match lmdb::Transaction::get(self, handle, &key) {
+        match lmdb::Transaction::get(&self.txn, handle, &key) {
             Ok(bytes) => Ok(Some(bytes.to_vec())),
             Err(lmdb::Error::NotFound) => Ok(None),
             Err(e) => Err(e),
@@ -28,19 +91,48 @@ impl<'a> Readable for RoTransaction<'a> {
     }
 }
 
-impl<'a> Transaction for RwTransaction<'a> {
+impl<'a> Iterable for LmdbReadTransaction<'a> {
+    fn read_all(&self, handle: Self::Handle) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Self::Error> {
+        read_all(&self.txn, handle)
+    }
+}
+
+/// A read-write transaction of an [`LmdbEnvironment`].
+///
+/// Like [`LmdbReadTransaction`], holds the guard which keeps the map from being resized.  Derefs
+/// to the underlying [`lmdb::RwTransaction`] for operations not covered by [`Writable`].
+pub struct LmdbReadWriteTransaction<'a> {
+    txn: RwTransaction<'a>,
+    _guard: RwLockReadGuard<'a, ()>,
+}
+
+impl<'a> Deref for LmdbReadWriteTransaction<'a> {
+    type Target = RwTransaction<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.txn
+    }
+}
+
+impl<'a> DerefMut for LmdbReadWriteTransaction<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.txn
+    }
+}
+
+impl<'a> Transaction for LmdbReadWriteTransaction<'a> {
     type Error = lmdb::Error;
 
     type Handle = Database;
 
     fn commit(self) -> Result<(), Self::Error> {
-        <RwTransaction<'a> as lmdb::Transaction>::commit(self)
+        lmdb::Transaction::commit(self.txn)
     }
 }
 
-impl<'a> Readable for RwTransaction<'a> {
+impl<'a> Readable for LmdbReadWriteTransaction<'a> {
     fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
-        match lmdb::Transaction::get(self, handle, &key) {
+        match lmdb::Transaction::get(&self.txn, handle, &key) {
             Ok(bytes) => Ok(Some(bytes.to_vec())),
             Err(lmdb::Error::NotFound) => Ok(None),
             Err(e) => Err(e),
@@ -48,20 +140,45 @@ impl<'a> Readable for RwTransaction<'a> {
     }
 }
 
-impl<'a> Writable for RwTransaction<'a> {
+impl<'a> Iterable for LmdbReadWriteTransaction<'a> {
+    fn read_all(&self, handle: Self::Handle) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Self::Error> {
+        read_all(&self.txn, handle)
+    }
+}
+
+impl<'a> Writable for LmdbReadWriteTransaction<'a> {
     fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
-        self.put(handle, &key, &value, WriteFlags::empty())
+        self.txn
+            .put(handle, &key, &value, WriteFlags::empty())
             .map_err(Into::into)
     }
 }
 
+/// The progress of [`LmdbEnvironment::copy_compacted`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionProgress {
+    /// The number of bytes written to the copy so far.
+    pub copied_bytes: u64,
+    /// The number of bytes taken up by the pages in use in the environment.  The copy leaves the
+    /// free pages behind, so it finishes at or below this size.
+    pub total_bytes: u64,
+}
+
 /// The environment for an LMDB-backed trie store.
 ///
 /// Wraps [`lmdb::Environment`].
+///
+/// The map of the environment is grown automatically by operations run through
+/// [`with_map_resize`](LmdbEnvironment::with_map_resize) when it is full.  As LMDB only allows the
+/// map to be resized while no transactions are open in the process, the transactions created by
+/// [`TransactionSource`] hold the guard returned by
+/// [`transaction_guard`](LmdbEnvironment::transaction_guard) until they are finished.
 #[derive(Debug)]
 pub struct LmdbEnvironment {
     path: PathBuf,
     env: Environment,
+    map_growth_factor: f64,
+    resize_lock: RwLock<()>,
 }
 
 impl LmdbEnvironment {
@@ -71,7 +188,27 @@ impl LmdbEnvironment {
             .set_map_size(map_size)
             .open(path)?;
         let path = path.to_owned();
-        Ok(LmdbEnvironment { path, env })
+        Ok(LmdbEnvironment {
+            path,
+            env,
+            map_growth_factor: DEFAULT_MAP_GROWTH_FACTOR,
+            resize_lock: RwLock::new(()),
+        })
+    }
+
+    /// Sets the factor by which the map is grown when it is full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `map_growth_factor` is not greater than 1.
+    pub fn with_map_growth_factor(mut self, map_growth_factor: f64) -> Self {
+        assert!(
+            map_growth_factor > 1.0,
+            "map growth factor must be greater than 1, got {}",
+            map_growth_factor
+        );
+        self.map_growth_factor = map_growth_factor;
+        self
     }
 
     pub fn path(&self) -> &PathBuf {
@@ -81,6 +218,159 @@ impl LmdbEnvironment {
     pub fn env(&self) -> &Environment {
         &self.env
     }
+
+    pub fn map_growth_factor(&self) -> f64 {
+        self.map_growth_factor
+    }
+
+    /// Returns the current size of the map in bytes.
+    pub fn map_size(&self) -> Result<usize, lmdb::Error> {
+        Ok(self.info()?.me_mapsize)
+    }
+
+    /// Returns the fraction of the map taken up by the pages in use, between 0 and 1.
+    pub fn map_utilization(&self) -> Result<f64, lmdb::Error> {
+        let info = self.info()?;
+        let page_size = self.env.stat()?.page_size() as usize;
+        let used = (info.me_last_pgno + 1) * page_size;
+        Ok(used as f64 / info.me_mapsize as f64)
+    }
+
+    /// Acquires a guard which keeps the map from being resized for as long as it is held.
+    pub fn transaction_guard(&self) -> RwLockReadGuard<()> {
+        self.resize_lock.read()
+    }
+
+    /// Runs `operation`, and runs it again from the start for as long as it fails because the map
+    /// is full or has been resized by another process, growing or reloading the map in between.
+    ///
+    /// `operation` must open and finish its own transactions, since the map can only be resized
+    /// once they are all closed.  The utilization of the map is emitted as a gauge once it
+    /// succeeds.
+    pub fn with_map_resize<T, F>(
+        &self,
+        correlation_id: CorrelationId,
+        mut operation: F,
+    ) -> Result<T, error::Error>
+    where
+        F: FnMut() -> Result<T, error::Error>,
+    {
+        loop {
+            let (result, map_size) = {
+                let _guard = self.transaction_guard();
+                let map_size = self.map_size()?;
+                (operation(), map_size)
+            };
+            match result {
+                Err(error::Error::Lmdb(lmdb::Error::MapFull)) => self.grow_map(map_size)?,
+                // Adopts the size set by the other process
+                Err(error::Error::Lmdb(lmdb::Error::MapResized)) => self.resize_map(0)?,
+                Ok(value) => {
+                    let map_utilization = self.map_utilization()?;
+                    metrics::LMDB_MAP_UTILIZATION.set(map_utilization);
+                    log_metric(
+                        correlation_id,
+                        LMDB_MAP_UTILIZATION,
+                        MAP_SIZE,
+                        GAUGE,
+                        map_utilization,
+                    );
+                    return Ok(value);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Grows the map from `full_map_size` by the growth factor, unless another thread has already
+    /// grown it in the meantime.
+    fn grow_map(&self, full_map_size: usize) -> Result<(), lmdb::Error> {
+        let _guard = self.resize_lock.write();
+        if self.map_size()? > full_map_size {
+            return Ok(());
+        }
+        let page_size = self.env.stat()?.page_size() as usize;
+        let grown_map_size = (full_map_size as f64 * self.map_growth_factor) as usize;
+        // LMDB expects the map size to be a multiple of the page size
+        let new_map_size = (grown_map_size + page_size - 1) / page_size * page_size;
+        // The resize lock is held exclusively, so no transactions are open in this process
+        lmdb_result(unsafe { ffi::mdb_env_set_mapsize(self.env.env(), new_map_size) })
+    }
+
+    /// Writes a compacted copy of the environment to `target_dir`, and returns the environment of
+    /// the copy.
+    ///
+    /// The copy is made by `mdb_env_copy2` with `MDB_CP_COMPACT`, which copies every database of
+    /// the environment whatever its layout, leaving the free pages behind.  While it runs,
+    /// `progress` is called periodically and once the copy is finished.  The copy has the same map
+    /// size and growth factor as this environment.  `target_dir` must exist, and fails with
+    /// [`lmdb::Error::KeyExist`] if it already holds an environment.
+    pub fn copy_compacted<F>(
+        &self,
+        target_dir: &Path,
+        mut progress: F,
+    ) -> Result<LmdbEnvironment, error::Error>
+    where
+        F: FnMut(CompactionProgress),
+    {
+        let target_file = target_dir.join(DATA_FILE_NAME);
+        if target_file.exists() {
+            return Err(lmdb::Error::KeyExist.into());
+        }
+        let target_path = target_dir
+            .to_str()
+            .and_then(|path| CString::new(path).ok())
+            .ok_or(lmdb::Error::Invalid)?;
+
+        let map_size = {
+            let _guard = self.transaction_guard();
+            let info = self.info()?;
+            let page_size = self.env.stat()?.page_size() as u64;
+            let total_bytes = (info.me_last_pgno as u64 + 1) * page_size;
+            let copied_bytes = || fs::metadata(&target_file).map_or(0, |meta| meta.len());
+
+            let env = EnvPtr(self.env.env());
+            let (sender, receiver) = mpsc::channel();
+            let copy = thread::spawn(move || {
+                let result =
+                    unsafe { ffi::mdb_env_copy2(env.0, target_path.as_ptr(), ffi::MDB_CP_COMPACT) };
+                // The receiver outlives the thread, as it is joined below
+                let _ = sender.send(result);
+            });
+            let result = loop {
+                match receiver.recv_timeout(COMPACTION_PROGRESS_INTERVAL) {
+                    Ok(result) => break result,
+                    Err(RecvTimeoutError::Timeout) => progress(CompactionProgress {
+                        copied_bytes: copied_bytes(),
+                        total_bytes,
+                    }),
+                    Err(RecvTimeoutError::Disconnected) => break ffi::MDB_PANIC,
+                }
+            };
+            copy.join().map_err(|_| lmdb::Error::Panic)?;
+            lmdb_result(result)?;
+            progress(CompactionProgress {
+                copied_bytes: copied_bytes(),
+                total_bytes,
+            });
+            info.me_mapsize
+        };
+
+        let target = LmdbEnvironment::new(&target_dir.to_path_buf(), map_size)?
+            .with_map_growth_factor(self.map_growth_factor);
+        Ok(target)
+    }
+
+    fn resize_map(&self, map_size: usize) -> Result<(), lmdb::Error> {
+        let _guard = self.resize_lock.write();
+        lmdb_result(unsafe { ffi::mdb_env_set_mapsize(self.env.env(), map_size) })
+    }
+
+    fn info(&self) -> Result<ffi::MDB_envinfo, lmdb::Error> {
+        let mut info: ffi::MDB_envinfo = unsafe { mem::zeroed() };
+        lmdb_result(unsafe { ffi::mdb_env_info(self.env.env(), &mut info) })?;
+        Ok(info)
+    }
 }
 
 impl<'a> TransactionSource<'a> for LmdbEnvironment {
@@ -88,15 +378,20 @@ impl<'a> TransactionSource<'a> for LmdbEnvironment {
 
     type Handle = Database;
 
-    type ReadTransaction = RoTransaction<'a>;
+    type ReadTransaction = LmdbReadTransaction<'a>;
 
-    type ReadWriteTransaction = RwTransaction<'a>;
+    type ReadWriteTransaction = LmdbReadWriteTransaction<'a>;
 
-    fn create_read_txn(&'a self) -> Result<RoTransaction<'a>, Self::Error> {
-        self.env.begin_ro_txn()
+    fn create_read_txn(&'a self) -> Result<LmdbReadTransaction<'a>, Self::Error> {
+        // Recursive, as `with_map_resize` holds the guard while its operation opens transactions
+        let _guard = self.resize_lock.read_recursive();
+        let txn = self.env.begin_ro_txn()?;
+        Ok(LmdbReadTransaction { txn, _guard })
     }
 
-    fn create_read_write_txn(&'a self) -> Result<RwTransaction<'a>, Self::Error> {
-        self.env.begin_rw_txn()
+    fn create_read_write_txn(&'a self) -> Result<LmdbReadWriteTransaction<'a>, Self::Error> {
+        let _guard = self.resize_lock.read_recursive();
+        let txn = self.env.begin_rw_txn()?;
+        Ok(LmdbReadWriteTransaction { txn, _guard })
     }
 }
@@ -27,6 +27,15 @@ pub trait Readable: Transaction {
     fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error>;
 }
 
+/// A transaction with the capability to read every key-value pair from a given
+/// [`Handle`](Transaction::Handle).
+///
+/// Intended for small databases only, as all entries are copied into memory.
+pub trait Iterable: Transaction {
+    /// Returns all key-value pairs in a given [`Transaction::Handle`], in no particular order.
+    fn read_all(&self, handle: Self::Handle) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Self::Error>;
+}
+
 /// A transaction with the capability to write to a given [`Handle`](Transaction::Handle).
 pub trait Writable: Transaction {
     /// Inserts a key-value pair into a given [`Transaction::Handle`].
@@ -50,12 +50,19 @@ impl Pointer {
 
 impl ToBytes for Pointer {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        let mut hash_bytes = self.hash().to_bytes()?;
-        let mut ret = Vec::with_capacity(U32_SERIALIZED_LENGTH + hash_bytes.len());
-        ret.append(&mut self.tag().to_bytes()?);
-        ret.append(&mut hash_bytes);
+        let mut ret = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut ret)?;
         Ok(ret)
     }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH + self.hash().serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.tag().write_bytes(writer)?;
+        self.hash().write_bytes(writer)
+    }
 }
 
 impl FromBytes for Pointer {
@@ -118,6 +125,14 @@ impl ToBytes for PointerBlock {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         ToBytes::to_bytes(&self.0)
     }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.0.write_bytes(writer)
+    }
 }
 
 impl FromBytes for PointerBlock {
@@ -245,45 +260,33 @@ where
     V: ToBytes,
 {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        match self {
-            Trie::Leaf { key, value } => {
-                let mut key_bytes = ToBytes::to_bytes(key)?;
-                let mut value_bytes = ToBytes::to_bytes(value)?;
-                if key_bytes.len() + value_bytes.len()
-                    > u32::max_value() as usize - U32_SERIALIZED_LENGTH
-                {
-                    return Err(bytesrepr::Error::OutOfMemoryError);
+        let mut ret = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut ret)?;
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH
+            + match self {
+                Trie::Leaf { key, value } => key.serialized_length() + value.serialized_length(),
+                Trie::Node { pointer_block } => pointer_block.deref().serialized_length(),
+                Trie::Extension { affix, pointer } => {
+                    affix.serialized_length() + pointer.serialized_length()
                 }
-                let mut ret: Vec<u8> =
-                    Vec::with_capacity(U32_SERIALIZED_LENGTH + key_bytes.len() + value_bytes.len());
-                ret.append(&mut self.tag().to_bytes()?);
-                ret.append(&mut key_bytes);
-                ret.append(&mut value_bytes);
-                Ok(ret)
             }
-            Trie::Node { pointer_block } => {
-                let mut pointer_block_bytes = ToBytes::to_bytes(pointer_block.deref())?;
-                let mut ret: Vec<u8> =
-                    Vec::with_capacity(U32_SERIALIZED_LENGTH + pointer_block_bytes.len());
-                ret.append(&mut self.tag().to_bytes()?);
-                ret.append(&mut pointer_block_bytes);
-                Ok(ret)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.tag().write_bytes(writer)?;
+        match self {
+            Trie::Leaf { key, value } => {
+                key.write_bytes(writer)?;
+                value.write_bytes(writer)
             }
+            Trie::Node { pointer_block } => pointer_block.deref().write_bytes(writer),
             Trie::Extension { affix, pointer } => {
-                let mut affix_bytes = ToBytes::to_bytes(affix)?;
-                let mut pointer_bytes = ToBytes::to_bytes(pointer)?;
-                if affix_bytes.len() + pointer_bytes.len()
-                    > u32::max_value() as usize - U32_SERIALIZED_LENGTH
-                {
-                    return Err(bytesrepr::Error::OutOfMemoryError);
-                }
-                let mut ret: Vec<u8> = Vec::with_capacity(
-                    U32_SERIALIZED_LENGTH + affix_bytes.len() + pointer_bytes.len(),
-                );
-                ret.append(&mut self.tag().to_bytes()?);
-                ret.append(&mut affix_bytes);
-                ret.append(&mut pointer_bytes);
-                Ok(ret)
+                affix.write_bytes(writer)?;
+                pointer.write_bytes(writer)
             }
         }
     }
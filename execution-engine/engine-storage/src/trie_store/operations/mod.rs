@@ -1,10 +1,11 @@
 #[cfg(test)]
 mod tests;
 
-use std::time::Instant;
+use std::{cmp, collections::VecDeque, time::Instant};
 
 use engine_shared::{
     logging::{log_duration, log_metric, GAUGE},
+    metrics,
     newtypes::{Blake2bHash, CorrelationId},
 };
 use types::bytesrepr::{self, FromBytes, ToBytes};
@@ -50,6 +51,7 @@ where
     S::Error: From<T::Error>,
     E: From<S::Error> + From<types::bytesrepr::Error>,
 {
+    metrics::TRIE_STORE_READS.inc();
     let path: Vec<u8> = key.to_bytes()?;
 
     let mut depth: usize = 0;
@@ -633,6 +635,7 @@ where
     S::Error: From<T::Error>,
     E: From<S::Error> + From<types::bytesrepr::Error>,
 {
+    metrics::TRIE_STORE_WRITES.inc();
     let start = Instant::now();
     let mut put_counter: i32 = 0;
 
@@ -709,6 +712,7 @@ where
             let mut root_hash = root.to_owned();
             for (hash, element) in new_elements.iter() {
                 put_counter += 1;
+                metrics::TRIE_STORE_PUTS.inc();
                 store.put(txn, hash, element)?;
                 root_hash = *hash;
             }
@@ -736,7 +740,6 @@ where
 /// * This should be rewritten as an Iterator in the future.
 /// * The root doesn't necessarily need to be the apex of the trie. It can be the "root" of a
 ///   sub-trie.
-#[allow(dead_code)]
 pub fn keys<K, V, T, S, E>(
     _correlation_id: CorrelationId,
     txn: &T,
@@ -805,3 +808,285 @@ where
 
     Ok(ret)
 }
+
+/// A page of the keys at a root which start with a given prefix, as returned by
+/// [`keys_with_prefix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeysPage<K> {
+    /// The keys, in the order of their serialized bytes.
+    pub keys: Vec<K>,
+    /// The pagination token to pass to [`keys_with_prefix`] for the next page, or `None` if this
+    /// is the last page.
+    pub next: Option<Vec<u8>>,
+}
+
+/// Returns up to `limit` of the keys at a given root whose serialized bytes start with `prefix`,
+/// in the order of their serialized bytes, or `None` if the root is not in the store.
+///
+/// Only keys following the pagination token `after` are returned, i.e. the serialized bytes of
+/// the last key of the previous page.  Subtries which can't hold such keys are skipped, so that
+/// paging through the keys of one kind doesn't traverse the whole trie.
+pub fn keys_with_prefix<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    prefix: &[u8],
+    after: Option<&[u8]>,
+    limit: usize,
+) -> Result<Option<KeysPage<K>>, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    // Whether the subtrie holding the keys which start with `path` can hold keys to return
+    let is_relevant = |path: &[u8]| {
+        let len = cmp::min(path.len(), prefix.len());
+        if path[..len] != prefix[..len] {
+            return false;
+        }
+        match after {
+            Some(after) => {
+                let len = cmp::min(path.len(), after.len());
+                path[..len] >= after[..len]
+            }
+            None => true,
+        }
+    };
+
+    if store.get(txn, root)?.is_none() {
+        return Ok(None);
+    }
+
+    let mut keys = Vec::new();
+    // Tries are only read once they are visited, so skipped siblings aren't read at all
+    let mut stack: Vec<(Blake2bHash, Vec<u8>)> = vec![(*root, vec![])];
+
+    while let Some((hash, mut path)) = stack.pop() {
+        let trie: Trie<K, V> = match store.get(txn, &hash)? {
+            Some(trie) => trie,
+            None => continue,
+        };
+        match trie {
+            Trie::Leaf { key, .. } => {
+                let key_bytes = key.to_bytes()?;
+                let is_after = after.map_or(true, |after| key_bytes.as_slice() > after);
+                if key_bytes.starts_with(prefix) && is_after {
+                    keys.push(key);
+                    // One more key than requested tells whether there is a next page
+                    if keys.len() > limit {
+                        break;
+                    }
+                }
+            }
+            Trie::Node { pointer_block } => {
+                // Children are pushed in reverse, so that they are visited in ascending order
+                for index in (0..RADIX).rev() {
+                    if let Some(pointer) = &pointer_block[index] {
+                        let mut child_path = path.clone();
+                        child_path.push(index as u8);
+                        if is_relevant(&child_path) {
+                            stack.push((*pointer.hash(), child_path));
+                        }
+                    }
+                }
+            }
+            Trie::Extension { affix, pointer } => {
+                path.extend(affix.iter());
+                if is_relevant(&path) {
+                    stack.push((*pointer.hash(), path));
+                }
+            }
+        }
+    }
+
+    let next = if keys.len() > limit {
+        keys.truncate(limit);
+        let token = match keys.last() {
+            Some(key) => key.to_bytes()?,
+            None => after.unwrap_or_default().to_vec(),
+        };
+        Some(token)
+    } else {
+        None
+    };
+
+    Ok(Some(KeysPage { keys, next }))
+}
+
+/// Returns the hashes of the tries under `root` which are missing from the store, or whose
+/// contents don't hash to the hash they are stored under.
+///
+/// An empty result means that the whole trie under `root` reads back intact.  Tries which can't be
+/// deserialized at all fail the check with an error instead.
+pub fn missing_or_corrupt_tries<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+) -> Result<Vec<Blake2bHash>, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut ret = Vec::new();
+    let mut stack = vec![*root];
+
+    while let Some(hash) = stack.pop() {
+        let trie: Trie<K, V> = match store.get(txn, &hash)? {
+            Some(trie) => trie,
+            None => {
+                ret.push(hash);
+                continue;
+            }
+        };
+        if Blake2bHash::new(&trie.to_bytes()?) != hash {
+            ret.push(hash);
+        }
+        match trie {
+            Trie::Leaf { .. } => {}
+            Trie::Node { pointer_block } => {
+                for index in 0..RADIX {
+                    if let Some(pointer) = &pointer_block[index] {
+                        stack.push(*pointer.hash());
+                    }
+                }
+            }
+            Trie::Extension { pointer, .. } => stack.push(*pointer.hash()),
+        }
+    }
+
+    Ok(ret)
+}
+
+/// How much of a trie [`stats`] traverses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrieStatsMode {
+    /// Every trie reachable from the root is visited.
+    Full,
+    /// At most `max_tries` tries are visited, breadth-first from the root, which bounds the time
+    /// taken on large tries.  The upper levels of the trie are counted exactly, while the leaves
+    /// visited are a sample of the rest.
+    Sampled { max_tries: usize },
+}
+
+/// Statistics of the tries reachable from a root, as returned by [`stats`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TrieStats {
+    /// Number of leaves visited.
+    pub leaf_count: u64,
+    /// Number of nodes visited.
+    pub node_count: u64,
+    /// Number of extensions visited.
+    pub extension_count: u64,
+    /// Number of leaves visited at each depth, the root being at depth zero.
+    pub depth_histogram: Vec<u64>,
+    /// Number of leaves visited by the serialized size of their values.  Bucket `0` holds values
+    /// of at most one byte, and bucket `i > 0` values of more than `2^(i - 1)` and at most `2^i`
+    /// bytes.
+    pub value_size_histogram: Vec<u64>,
+    /// Total serialized size of the values of the leaves visited.
+    pub total_value_size: u64,
+    /// `true` if the traversal stopped before visiting every trie.
+    pub truncated: bool,
+}
+
+impl TrieStats {
+    /// Returns the number of tries visited.
+    pub fn trie_count(&self) -> u64 {
+        self.leaf_count + self.node_count + self.extension_count
+    }
+
+    fn record_leaf(&mut self, depth: usize, value_size: usize) {
+        self.leaf_count += 1;
+        increment_bucket(&mut self.depth_histogram, depth);
+        increment_bucket(&mut self.value_size_histogram, size_bucket(value_size));
+        self.total_value_size += value_size as u64;
+    }
+}
+
+fn increment_bucket(histogram: &mut Vec<u64>, bucket: usize) {
+    if histogram.len() <= bucket {
+        histogram.resize(bucket + 1, 0);
+    }
+    histogram[bucket] += 1;
+}
+
+fn size_bucket(size: usize) -> usize {
+    if size <= 1 {
+        0
+    } else {
+        (0usize.leading_zeros() - (size - 1).leading_zeros()) as usize
+    }
+}
+
+/// Returns statistics of the tries reachable from a given root hash, or `None` if the root is not
+/// in the store.
+///
+/// As with [`keys`], the root doesn't need to be the apex of the trie.
+pub fn stats<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    mode: TrieStatsMode,
+) -> Result<Option<TrieStats>, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let max_tries = match mode {
+        TrieStatsMode::Full => usize::max_value(),
+        TrieStatsMode::Sampled { max_tries } => max_tries,
+    };
+
+    let mut ret = TrieStats::default();
+    let mut queue: VecDeque<(Trie<K, V>, usize)> = {
+        let root = match store.get(txn, root)? {
+            None => return Ok(None),
+            Some(root) => root,
+        };
+        vec![(root, 0)].into()
+    };
+
+    while let Some((trie, depth)) = queue.pop_front() {
+        if ret.trie_count() as usize >= max_tries {
+            ret.truncated = true;
+            break;
+        }
+
+        match trie {
+            Trie::Leaf { value, .. } => ret.record_leaf(depth, value.serialized_length()),
+            Trie::Node { pointer_block } => {
+                ret.node_count += 1;
+                for index in 0..RADIX {
+                    if let Some(pointer) = &pointer_block[index] {
+                        if let Some(child) = store.get(txn, pointer.hash())? {
+                            queue.push_back((child, depth + 1));
+                        }
+                    }
+                }
+            }
+            Trie::Extension { pointer, .. } => {
+                ret.extension_count += 1;
+                if let Some(child) = store.get(txn, pointer.hash())? {
+                    queue.push_back((child, depth + 1));
+                }
+            }
+        }
+    }
+
+    Ok(Some(ret))
+}
@@ -98,6 +98,15 @@ impl ToBytes for PublicKey {
         };
         Ok(ret)
     }
+
+    fn serialized_length(&self) -> usize {
+        1 + match self {
+            PublicKey::Basic(key) => key.serialized_length(),
+            PublicKey::Similar(key) => key.serialized_length(),
+            PublicKey::Fancy(key) => key.serialized_length(),
+            PublicKey::Long(key) => key.serialized_length(),
+        }
+    }
 }
 
 impl FromBytes for PublicKey {
@@ -174,6 +183,15 @@ impl ToBytes for TestKey {
         }
         Ok(ret)
     }
+
+    fn serialized_length(&self) -> usize {
+        1 + match self {
+            TestKey::Account(public_key) => public_key.serialized_length(),
+            TestKey::Hash(hash) => hash.serialized_length(),
+            TestKey::URef(uref) => uref.serialized_length(),
+            TestKey::Local(local) => local.serialized_length(),
+        }
+    }
 }
 
 impl FromBytes for TestKey {
@@ -0,0 +1,108 @@
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+
+use crate::{
+    error::{self, in_memory},
+    transaction_source::{Transaction, TransactionSource},
+    trie_store::operations::{
+        self,
+        tests::{
+            create_6_leaf_trie, HashedTrie, InMemoryTestContext, LmdbTestContext, TestKey,
+            TestValue, TEST_TRIE_GENERATORS,
+        },
+    },
+};
+
+fn in_memory_missing_or_corrupt_tries(
+    context: &InMemoryTestContext,
+    root_hash: &Blake2bHash,
+) -> Vec<Blake2bHash> {
+    let correlation_id = CorrelationId::new();
+    let txn = context.environment.create_read_txn().unwrap();
+    let ret = operations::missing_or_corrupt_tries::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        root_hash,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    ret
+}
+
+#[test]
+fn lmdb_intact_tries_have_no_missing_or_corrupt_tries() {
+    for generator in &TEST_TRIE_GENERATORS {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+
+        let txn = context.environment.create_read_txn().unwrap();
+        let actual =
+            operations::missing_or_corrupt_tries::<TestKey, TestValue, _, _, error::Error>(
+                correlation_id,
+                &txn,
+                &context.store,
+                &root_hash,
+            )
+            .unwrap();
+        txn.commit().unwrap();
+
+        assert!(actual.is_empty());
+    }
+}
+
+#[test]
+fn in_memory_intact_tries_have_no_missing_or_corrupt_tries() {
+    for generator in &TEST_TRIE_GENERATORS {
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        assert!(in_memory_missing_or_corrupt_tries(&context, &root_hash).is_empty());
+    }
+}
+
+#[test]
+fn in_memory_missing_tries_are_reported() {
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    for missing_index in 0..tries.len() {
+        let mut remaining = tries.clone();
+        let missing = remaining.remove(missing_index);
+        let context = InMemoryTestContext::new(&remaining).unwrap();
+
+        assert_eq!(
+            in_memory_missing_or_corrupt_tries(&context, &root_hash),
+            vec![missing.hash]
+        );
+    }
+}
+
+#[test]
+fn in_memory_corrupt_tries_are_reported() {
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let leaf_hashes = tries
+        .iter()
+        .filter(|hashed_trie| hashed_trie.trie.key().is_some())
+        .map(|hashed_trie| hashed_trie.hash)
+        .collect::<Vec<Blake2bHash>>();
+    assert!(leaf_hashes.len() >= 2);
+
+    // Stores the first leaf under the hash of the second one
+    let mut corrupted = tries.clone();
+    let first_leaf = tries
+        .iter()
+        .find(|hashed_trie| hashed_trie.hash == leaf_hashes[0])
+        .unwrap();
+    for hashed_trie in corrupted.iter_mut() {
+        if hashed_trie.hash == leaf_hashes[1] {
+            *hashed_trie = HashedTrie {
+                hash: leaf_hashes[1],
+                trie: first_leaf.trie.clone(),
+            };
+        }
+    }
+    let context = InMemoryTestContext::new(&corrupted).unwrap();
+
+    assert_eq!(
+        in_memory_missing_or_corrupt_tries(&context, &root_hash),
+        vec![leaf_hashes[1]]
+    );
+}
@@ -0,0 +1,196 @@
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+use types::bytesrepr::ToBytes;
+
+use crate::{
+    error::{self, in_memory},
+    transaction_source::{Transaction, TransactionSource},
+    trie::Trie,
+    trie_store::operations::{
+        self,
+        tests::{
+            create_6_leaf_trie, InMemoryTestContext, LmdbTestContext, TestKey, TestValue,
+            TEST_LEAVES, TEST_TRIE_GENERATORS,
+        },
+        KeysPage,
+    },
+};
+
+/// The keys of the first `num_leaves` test leaves starting with `prefix`, in the order of their
+/// serialized bytes.
+fn expected_keys(num_leaves: usize, prefix: &[u8]) -> Vec<TestKey> {
+    let mut ret = TEST_LEAVES[..num_leaves]
+        .iter()
+        .filter_map(Trie::key)
+        .filter(|key| key.0.starts_with(prefix))
+        .cloned()
+        .collect::<Vec<TestKey>>();
+    ret.sort();
+    ret
+}
+
+fn in_memory_keys_page(
+    context: &InMemoryTestContext,
+    root_hash: &Blake2bHash,
+    prefix: &[u8],
+    after: Option<&[u8]>,
+    limit: usize,
+) -> Option<KeysPage<TestKey>> {
+    let correlation_id = CorrelationId::new();
+    let txn = context.environment.create_read_txn().unwrap();
+    let ret = operations::keys_with_prefix::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        root_hash,
+        prefix,
+        after,
+        limit,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    ret
+}
+
+/// Follows the pagination tokens from the first page to the last one.
+fn in_memory_all_pages(
+    context: &InMemoryTestContext,
+    root_hash: &Blake2bHash,
+    prefix: &[u8],
+    limit: usize,
+) -> Vec<KeysPage<TestKey>> {
+    let mut pages = Vec::new();
+    let mut after: Option<Vec<u8>> = None;
+    loop {
+        let page = in_memory_keys_page(context, root_hash, prefix, after.as_deref(), limit)
+            .expect("should find root");
+        after = page.next.clone();
+        pages.push(page);
+        if after.is_none() {
+            return pages;
+        }
+    }
+}
+
+#[test]
+fn lmdb_keys_with_empty_prefix_are_all_keys() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+
+        let txn = context.environment.create_read_txn().unwrap();
+        let page = operations::keys_with_prefix::<TestKey, TestValue, _, _, error::Error>(
+            correlation_id,
+            &txn,
+            &context.store,
+            &root_hash,
+            &[],
+            None,
+            TEST_LEAVES.len(),
+        )
+        .unwrap()
+        .expect("should find root");
+        txn.commit().unwrap();
+
+        assert_eq!(page.keys, expected_keys(num_leaves, &[]));
+        assert_eq!(page.next, None);
+    }
+}
+
+#[test]
+fn in_memory_keys_with_empty_prefix_are_all_keys() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+
+        let page = in_memory_keys_page(&context, &root_hash, &[], None, TEST_LEAVES.len())
+            .expect("should find root");
+
+        assert_eq!(page.keys, expected_keys(num_leaves, &[]));
+        assert_eq!(page.next, None);
+    }
+}
+
+#[test]
+fn in_memory_keys_with_prefix_are_filtered() {
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    for prefix in &[
+        vec![0],
+        vec![0, 0],
+        vec![0, 0, 0],
+        vec![0, 0, 0, 0, 0, 0],
+        vec![0, 1],
+        vec![0, 0, 2],
+        vec![1],
+    ] {
+        let page = in_memory_keys_page(&context, &root_hash, prefix, None, TEST_LEAVES.len())
+            .expect("should find root");
+        assert_eq!(page.keys, expected_keys(TEST_LEAVES.len(), prefix));
+        assert_eq!(page.next, None);
+    }
+}
+
+#[test]
+fn in_memory_pages_concatenate_to_all_keys() {
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    for prefix in &[vec![], vec![0, 0], vec![0, 0, 0]] {
+        let expected = expected_keys(TEST_LEAVES.len(), prefix);
+        for limit in 1..=expected.len() {
+            let pages = in_memory_all_pages(&context, &root_hash, prefix, limit);
+            assert!(pages.iter().all(|page| page.keys.len() <= limit));
+            assert_eq!(pages.len(), (expected.len() + limit - 1) / limit);
+
+            let actual = pages
+                .into_iter()
+                .flat_map(|page| page.keys)
+                .collect::<Vec<TestKey>>();
+            assert_eq!(actual, expected);
+        }
+    }
+}
+
+#[test]
+fn in_memory_keys_page_token_is_last_key() {
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let expected = expected_keys(TEST_LEAVES.len(), &[]);
+
+    let page = in_memory_keys_page(&context, &root_hash, &[], None, 2).expect("should find root");
+    assert_eq!(page.keys, expected[..2].to_vec());
+    assert_eq!(page.next, Some(expected[1].to_bytes().unwrap()));
+
+    // A token which isn't a key of the trie resumes from the following key
+    let after = [0u8, 0, 0, 1];
+    let page =
+        in_memory_keys_page(&context, &root_hash, &[], Some(&after), 1).expect("should find root");
+    assert_eq!(page.keys, vec![TestKey([0u8, 0, 0, 2, 0, 0, 0])]);
+    assert!(page.next.is_some());
+}
+
+#[test]
+fn in_memory_keys_page_of_zero_keys_keeps_token() {
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let after = [0u8, 0, 0];
+
+    let page =
+        in_memory_keys_page(&context, &root_hash, &[], Some(&after), 0).expect("should find root");
+    assert!(page.keys.is_empty());
+    assert_eq!(page.next, Some(after.to_vec()));
+}
+
+#[test]
+fn in_memory_keys_with_prefix_of_missing_root_are_none() {
+    let (_, tries) = create_6_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let missing_root = Blake2bHash::new(b"missing");
+
+    assert_eq!(
+        in_memory_keys_page(&context, &missing_root, &[], None, TEST_LEAVES.len()),
+        None
+    );
+}
@@ -1,8 +1,11 @@
 mod ee_699;
+mod integrity;
 mod keys;
+mod keys_with_prefix;
 mod proptests;
 mod read;
 mod scan;
+mod stats;
 mod write;
 
 use std::{collections::HashMap, convert};
@@ -41,6 +44,10 @@ impl ToBytes for TestKey {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         Ok(self.0.to_vec())
     }
+
+    fn serialized_length(&self) -> usize {
+        TEST_KEY_LENGTH
+    }
 }
 
 impl FromBytes for TestKey {
@@ -62,6 +69,10 @@ impl ToBytes for TestValue {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         Ok(self.0.to_vec())
     }
+
+    fn serialized_length(&self) -> usize {
+        TEST_VAL_LENGTH
+    }
 }
 
 impl FromBytes for TestValue {
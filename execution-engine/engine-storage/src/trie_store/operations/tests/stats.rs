@@ -0,0 +1,126 @@
+use engine_shared::newtypes::CorrelationId;
+
+use crate::{
+    error::{self, in_memory},
+    transaction_source::{Transaction, TransactionSource},
+    trie_store::operations::{
+        self,
+        tests::{
+            create_3_leaf_trie, InMemoryTestContext, LmdbTestContext, TestKey, TestValue,
+            TEST_TRIE_GENERATORS,
+        },
+        TrieStats, TrieStatsMode,
+    },
+};
+
+fn expected_3_leaf_stats() -> TrieStats {
+    TrieStats {
+        leaf_count: 3,
+        node_count: 3,
+        extension_count: 2,
+        depth_histogram: vec![0, 0, 0, 1, 0, 2],
+        value_size_histogram: vec![0, 0, 0, 3],
+        total_value_size: 18,
+        truncated: false,
+    }
+}
+
+#[test]
+fn lmdb_stats_count_every_leaf() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+
+        let txn = context.environment.create_read_txn().unwrap();
+        let stats = operations::stats::<TestKey, TestValue, _, _, error::Error>(
+            correlation_id,
+            &txn,
+            &context.store,
+            &root_hash,
+            TrieStatsMode::Full,
+        )
+        .unwrap()
+        .expect("should find root");
+        txn.commit().unwrap();
+
+        assert_eq!(stats.leaf_count, num_leaves as u64);
+        assert_eq!(stats.trie_count(), tries.len() as u64);
+        assert_eq!(stats.depth_histogram.iter().sum::<u64>(), num_leaves as u64);
+        assert!(!stats.truncated);
+    }
+}
+
+#[test]
+fn in_memory_stats_of_3_leaf_trie() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = create_3_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let txn = context.environment.create_read_txn().unwrap();
+    let stats = operations::stats::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        &root_hash,
+        TrieStatsMode::Full,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+
+    assert_eq!(stats, Some(expected_3_leaf_stats()));
+}
+
+#[test]
+fn in_memory_sampled_stats_stop_after_max_tries() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = create_3_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let txn = context.environment.create_read_txn().unwrap();
+    let sampled = operations::stats::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        &root_hash,
+        TrieStatsMode::Sampled { max_tries: 3 },
+    )
+    .unwrap()
+    .expect("should find root");
+    let unbounded = operations::stats::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        &root_hash,
+        TrieStatsMode::Sampled { max_tries: 100 },
+    )
+    .unwrap();
+    txn.commit().unwrap();
+
+    assert!(sampled.truncated);
+    assert_eq!(sampled.trie_count(), 3);
+    assert_eq!(sampled.node_count, 2);
+    assert_eq!(sampled.extension_count, 1);
+    assert_eq!(sampled.leaf_count, 0);
+    assert_eq!(unbounded, Some(expected_3_leaf_stats()));
+}
+
+#[test]
+fn in_memory_stats_of_missing_root_is_none() {
+    let correlation_id = CorrelationId::new();
+    let (_, tries) = create_3_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let txn = context.environment.create_read_txn().unwrap();
+    let stats = operations::stats::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        &[1u8; 32].into(),
+        TrieStatsMode::Full,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+
+    assert_eq!(stats, None);
+}
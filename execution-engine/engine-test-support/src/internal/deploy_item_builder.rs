@@ -2,10 +2,17 @@ use std::{collections::BTreeSet, path::Path};
 
 use contract::args_parser::ArgsParser;
 use engine_core::{
-    engine_state::{deploy_item::DeployItem, executable_deploy_item::ExecutableDeployItem},
+    engine_state::{
+        deploy_item::{DeployItem, Signature},
+        executable_deploy_item::{ExecutableDeployItem, ExecutionContext},
+    },
     DeployHash,
 };
-use types::{account::PublicKey, bytesrepr::ToBytes, URef};
+use types::{
+    account::{PublicKey, TaggedPublicKey},
+    bytesrepr::ToBytes,
+    ContractVersion, URef, U512,
+};
 
 use crate::internal::utils;
 
@@ -17,6 +24,7 @@ struct DeployItemData {
     pub gas_price: u64,
     pub authorization_keys: BTreeSet<PublicKey>,
     pub deploy_hash: DeployHash,
+    pub signatures: Vec<(TaggedPublicKey, Signature)>,
 }
 
 pub struct DeployItemBuilder {
@@ -85,6 +93,15 @@ impl DeployItemBuilder {
         self
     }
 
+    /// Sets the payment code to a native transfer, which the engine rejects as payment.
+    pub fn with_payment_transfer(mut self, target: [u8; 32], amount: U512) -> Self {
+        self.deploy_item.payment_code = Some(ExecutableDeployItem::Transfer {
+            target: target.into(),
+            amount,
+        });
+        self
+    }
+
     pub fn with_session_code<T: AsRef<Path>>(
         mut self,
         file_name: T,
@@ -99,6 +116,13 @@ impl DeployItemBuilder {
         self
     }
 
+    pub fn with_session_bytes(mut self, module_bytes: Vec<u8>, args: impl ArgsParser) -> Self {
+        let args = Self::serialize_args(args);
+        self.deploy_item.session_code =
+            Some(ExecutableDeployItem::ModuleBytes { module_bytes, args });
+        self
+    }
+
     pub fn with_stored_session_hash(mut self, hash: Vec<u8>, args: impl ArgsParser) -> Self {
         let args = Self::serialize_args(args);
         self.deploy_item.session_code =
@@ -137,6 +161,53 @@ impl DeployItemBuilder {
         self
     }
 
+    pub fn with_stored_versioned_session_hash(
+        mut self,
+        hash: Vec<u8>,
+        version: Option<ContractVersion>,
+        entry_point: &str,
+        args: impl ArgsParser,
+        context: ExecutionContext,
+    ) -> Self {
+        let args = Self::serialize_args(args);
+        self.deploy_item.session_code = Some(ExecutableDeployItem::StoredVersionedContractByHash {
+            hash,
+            version,
+            entry_point: entry_point.to_owned(),
+            args,
+            context,
+        });
+        self
+    }
+
+    pub fn with_stored_versioned_session_named_key(
+        mut self,
+        name: &str,
+        version: Option<ContractVersion>,
+        entry_point: &str,
+        args: impl ArgsParser,
+        context: ExecutionContext,
+    ) -> Self {
+        let args = Self::serialize_args(args);
+        self.deploy_item.session_code = Some(ExecutableDeployItem::StoredVersionedContractByName {
+            name: name.to_owned(),
+            version,
+            entry_point: entry_point.to_owned(),
+            args,
+            context,
+        });
+        self
+    }
+
+    /// Sets the session code to a native transfer of `amount` motes to the account `target`.
+    pub fn with_session_transfer(mut self, target: [u8; 32], amount: U512) -> Self {
+        self.deploy_item.session_code = Some(ExecutableDeployItem::Transfer {
+            target: target.into(),
+            amount,
+        });
+        self
+    }
+
     pub fn with_authorization_keys<T: Clone + Into<PublicKey>>(
         mut self,
         authorization_keys: &[T],
@@ -149,13 +220,26 @@ impl DeployItemBuilder {
         self
     }
 
+    /// Adds an ed25519 signature of the deploy hash made by `public_key`.
+    pub fn with_signature<T: Into<PublicKey>>(self, public_key: T, signature: &[u8]) -> Self {
+        let public_key = TaggedPublicKey::ed25519(public_key.into().value());
+        self.with_tagged_signature(public_key, signature)
+    }
+
+    pub fn with_tagged_signature(mut self, public_key: TaggedPublicKey, signature: &[u8]) -> Self {
+        self.deploy_item
+            .signatures
+            .push((public_key, signature.to_vec()));
+        self
+    }
+
     pub fn with_gas_price(mut self, gas_price: u64) -> Self {
         self.deploy_item.gas_price = gas_price;
         self
     }
 
     pub fn with_deploy_hash(mut self, hash: [u8; 32]) -> Self {
-        self.deploy_item.deploy_hash = hash;
+        self.deploy_item.deploy_hash = hash.into();
         self
     }
 
@@ -167,6 +251,7 @@ impl DeployItemBuilder {
             gas_price: self.deploy_item.gas_price,
             authorization_keys: self.deploy_item.authorization_keys,
             deploy_hash: self.deploy_item.deploy_hash,
+            signatures: self.deploy_item.signatures,
         }
     }
 
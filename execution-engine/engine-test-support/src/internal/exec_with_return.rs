@@ -3,8 +3,8 @@ use std::{cell::RefCell, collections::BTreeSet, convert::TryInto, rc::Rc};
 use contract::args_parser::ArgsParser;
 use engine_core::{
     engine_state::{
-        executable_deploy_item::ExecutableDeployItem, execution_effect::ExecutionEffect,
-        EngineState,
+        block_info::BlockInfo, executable_deploy_item::ExecutableDeployItem,
+        execution_effect::ExecutionEffect, EngineConfig, EngineState,
     },
     execution::{self, AddressGenerator},
     runtime_context::RuntimeContext,
@@ -14,7 +14,8 @@ use engine_shared::{gas::Gas, newtypes::CorrelationId};
 use engine_storage::{global_state::StateProvider, protocol_data::ProtocolData};
 use engine_wasm_prep::Preprocessor;
 use types::{
-    bytesrepr::FromBytes, BlockTime, CLTyped, CLValue, Key, Phase, ProtocolVersion, URef, U512,
+    account::PublicKey, bytesrepr::FromBytes, BlockTime, CLTyped, CLValue, CallStackElement, Key,
+    Phase, ProtocolVersion, URef, U512,
 };
 
 use crate::internal::{utils, WasmTestBuilder, DEFAULT_WASM_COSTS};
@@ -54,14 +55,14 @@ where
     ));
 
     let phase = Phase::Session;
+    let protocol_version = ProtocolVersion::V1_0_0;
     let address_generator = {
-        let address_generator = AddressGenerator::new(deploy_hash, phase);
+        let address_generator = AddressGenerator::new(deploy_hash, phase, protocol_version);
         Rc::new(RefCell::new(address_generator))
     };
     let gas_counter = Gas::default();
     let fn_store_id = INIT_FN_STORE_ID;
     let gas_limit = Gas::new(U512::from(std::u64::MAX));
-    let protocol_version = ProtocolVersion::V1_0_0;
     let correlation_id = CorrelationId::new();
     let arguments: Vec<CLValue> = args.parse().expect("should be able to serialize args");
     let base_key = Key::Account(address);
@@ -85,7 +86,8 @@ where
         BTreeSet::new(),
         &account,
         base_key,
-        BlockTime::new(block_time),
+        vec![CallStackElement::Account(PublicKey::new(address))],
+        BlockInfo::new(BlockTime::new(block_time), 0, 0),
         deploy_hash,
         gas_limit,
         gas_counter,
@@ -118,11 +120,21 @@ where
         )
         .expect("should get wasm module");
 
-    let (instance, memory) =
-        execution::instance_and_memory(parity_module.clone(), protocol_version)
-            .expect("should be able to make wasm instance from module");
-
-    let mut runtime = execution::Runtime::new(Default::default(), memory, parity_module, context);
+    let (instance, memory) = execution::instance_and_memory(
+        parity_module.clone(),
+        protocol_version,
+        context.protocol_data().denied_host_functions(),
+        wasm_costs.memory_limit(),
+    )
+    .expect("should be able to make wasm instance from module");
+
+    let mut runtime = execution::Runtime::new(
+        EngineConfig::new(),
+        Default::default(),
+        memory,
+        parity_module,
+        context,
+    );
 
     match instance.invoke_export("call", &[], &mut runtime) {
         Ok(_) => None,
@@ -38,6 +38,16 @@ impl ExecuteRequestBuilder {
         self
     }
 
+    pub fn with_era_id(mut self, era_id: u64) -> Self {
+        self.execute_request.era_id = era_id;
+        self
+    }
+
+    pub fn with_block_height(mut self, block_height: u64) -> Self {
+        self.execute_request.block_height = block_height;
+        self
+    }
+
     pub fn with_protocol_version(mut self, protocol_version: ProtocolVersion) -> Self {
         self.execute_request.protocol_version = protocol_version;
         self
@@ -2,6 +2,8 @@ mod additive_map_diff;
 mod deploy_item_builder;
 pub mod exec_with_return;
 mod execute_request_builder;
+pub mod replay;
+mod transform_matcher;
 mod upgrade_request_builder;
 pub mod utils;
 mod wasm_test_builder;
@@ -18,6 +20,7 @@ use super::{DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE};
 pub use additive_map_diff::AdditiveMapDiff;
 pub use deploy_item_builder::DeployItemBuilder;
 pub use execute_request_builder::ExecuteRequestBuilder;
+pub use transform_matcher::{KeyMatcher, TransformMatcher};
 pub use upgrade_request_builder::UpgradeRequestBuilder;
 pub use wasm_test_builder::{
     InMemoryWasmTestBuilder, LmdbWasmTestBuilder, WasmTestBuilder, WasmTestResult,
@@ -30,6 +33,8 @@ pub const STANDARD_PAYMENT_CONTRACT: &str = "standard_payment.wasm";
 pub const DEFAULT_CHAIN_NAME: &str = "gerald";
 pub const DEFAULT_GENESIS_TIMESTAMP: u64 = 0;
 pub const DEFAULT_BLOCK_TIME: u64 = 0;
+pub const DEFAULT_UNBONDING_DELAY: u64 = 0;
+pub const DEFAULT_SLASH_PERCENTAGE: u8 = 0;
 pub const MOCKED_ACCOUNT_ADDRESS: [u8; 32] = [48u8; 32];
 
 lazy_static! {
@@ -58,6 +63,8 @@ lazy_static! {
             pos_installer_bytes,
             DEFAULT_ACCOUNTS.clone(),
             *DEFAULT_WASM_COSTS,
+            DEFAULT_UNBONDING_DELAY,
+            DEFAULT_SLASH_PERCENTAGE,
         )
     };
 }
@@ -0,0 +1,245 @@
+//! A journal of the requests a [`WasmTestBuilder`] sent to its engine state, which can be replayed
+//! against a fresh engine state to check that it reaches the same post-state hashes.
+//!
+//! Recording is enabled with [`WasmTestBuilder::record_journal`].  Genesis, exec, commit and
+//! upgrade requests are recorded, along with the post-state hash each state-changing request led
+//! to.
+
+use std::{
+    convert::{TryFrom, TryInto},
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+};
+
+use protobuf::Message;
+
+use engine_core::{
+    engine_state::{execute_request::ExecuteRequest, genesis::GenesisConfig, EngineState},
+    execution,
+};
+use engine_grpc_server::engine_server::{
+    ipc::{self, ChainSpec_GenesisConfig, CommitRequest, UpgradeRequest},
+    ipc_grpc::ExecutionEngineService,
+    mappings::TransformMap,
+};
+use engine_storage::global_state::StateProvider;
+use types::bytesrepr::{self, FromBytes, ToBytes};
+
+use crate::internal::WasmTestBuilder;
+
+const GENESIS_TAG: u8 = 0;
+const EXEC_TAG: u8 = 1;
+const COMMIT_TAG: u8 = 2;
+const COMMIT_EFFECTS_TAG: u8 = 3;
+const UPGRADE_TAG: u8 = 4;
+
+/// A request recorded in a [`Journal`].
+#[derive(Clone, Debug)]
+pub enum JournalEntry {
+    /// Genesis was run with the given config.
+    Genesis {
+        config: ChainSpec_GenesisConfig,
+        post_state_hash: Vec<u8>,
+    },
+    /// The given request was executed.
+    Exec { request: ipc::ExecuteRequest },
+    /// The effects of the last exec request were committed.
+    Commit { post_state_hash: Vec<u8> },
+    /// The given effects were committed.
+    CommitEffects {
+        request: CommitRequest,
+        post_state_hash: Vec<u8>,
+    },
+    /// The given upgrade was run.
+    Upgrade {
+        request: UpgradeRequest,
+        post_state_hash: Vec<u8>,
+    },
+}
+
+impl JournalEntry {
+    /// The post-state hash recorded after the request, if it changed the global state.
+    pub fn post_state_hash(&self) -> Option<&[u8]> {
+        match self {
+            JournalEntry::Genesis {
+                post_state_hash, ..
+            }
+            | JournalEntry::Commit { post_state_hash }
+            | JournalEntry::CommitEffects {
+                post_state_hash, ..
+            }
+            | JournalEntry::Upgrade {
+                post_state_hash, ..
+            } => Some(post_state_hash),
+            JournalEntry::Exec { .. } => None,
+        }
+    }
+
+    /// Appends the entry to the journal file at `path`.
+    pub(crate) fn append_to(&self, path: &Path) -> io::Result<()> {
+        let (tag, message, post_state_hash) = match self {
+            JournalEntry::Genesis {
+                config,
+                post_state_hash,
+            } => (
+                GENESIS_TAG,
+                config.write_to_bytes(),
+                post_state_hash.as_slice(),
+            ),
+            JournalEntry::Exec { request } => (EXEC_TAG, request.write_to_bytes(), &[][..]),
+            JournalEntry::Commit { post_state_hash } => {
+                (COMMIT_TAG, Ok(Vec::new()), post_state_hash.as_slice())
+            }
+            JournalEntry::CommitEffects {
+                request,
+                post_state_hash,
+            } => (
+                COMMIT_EFFECTS_TAG,
+                request.write_to_bytes(),
+                post_state_hash.as_slice(),
+            ),
+            JournalEntry::Upgrade {
+                request,
+                post_state_hash,
+            } => (
+                UPGRADE_TAG,
+                request.write_to_bytes(),
+                post_state_hash.as_slice(),
+            ),
+        };
+        let message = message.map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        let bytes = (tag, message, post_state_hash.to_vec())
+            .to_bytes()
+            .map_err(invalid_data)?;
+
+        let mut file = OpenOptions::new().append(true).create(true).open(path)?;
+        file.write_all(&bytes)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> io::Result<(Self, &[u8])> {
+        let ((tag, message, post_state_hash), rem): ((u8, Vec<u8>, Vec<u8>), &[u8]) =
+            FromBytes::from_bytes(bytes).map_err(invalid_data)?;
+        let entry = match tag {
+            GENESIS_TAG => JournalEntry::Genesis {
+                config: parse_message(&message)?,
+                post_state_hash,
+            },
+            EXEC_TAG => JournalEntry::Exec {
+                request: parse_message(&message)?,
+            },
+            COMMIT_TAG => JournalEntry::Commit { post_state_hash },
+            COMMIT_EFFECTS_TAG => JournalEntry::CommitEffects {
+                request: parse_message(&message)?,
+                post_state_hash,
+            },
+            UPGRADE_TAG => JournalEntry::Upgrade {
+                request: parse_message(&message)?,
+                post_state_hash,
+            },
+            other => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unknown journal entry tag {}", other),
+                ))
+            }
+        };
+        Ok((entry, rem))
+    }
+}
+
+/// The requests recorded by a [`WasmTestBuilder`], in the order they were sent.
+#[derive(Clone, Debug, Default)]
+pub struct Journal {
+    entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    /// Reads the journal recorded in the file at `path`.
+    pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        let mut rem = bytes.as_slice();
+        let mut entries = Vec::new();
+        while !rem.is_empty() {
+            let (entry, next) = JournalEntry::from_bytes(rem)?;
+            entries.push(entry);
+            rem = next;
+        }
+        Ok(Journal { entries })
+    }
+
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    /// Sends the recorded requests to `builder`, which should not have run genesis yet, and
+    /// panics as soon as a request leads to a different post-state hash than was recorded.
+    pub fn replay<'a, S>(&self, builder: &'a mut WasmTestBuilder<S>) -> &'a mut WasmTestBuilder<S>
+    where
+        S: StateProvider,
+        S::Error: Into<execution::Error>,
+        EngineState<S>: ExecutionEngineService,
+    {
+        for (index, entry) in self.entries.iter().enumerate() {
+            match entry.clone() {
+                JournalEntry::Genesis { config, .. } => {
+                    let genesis_config =
+                        GenesisConfig::try_from(config).expect("should parse genesis config");
+                    builder.run_genesis(&genesis_config);
+                }
+                JournalEntry::Exec { request } => {
+                    let exec_request = ExecuteRequest::try_from(request)
+                        .unwrap_or_else(|_| panic!("should parse exec request {}", index));
+                    builder.exec(exec_request);
+                }
+                JournalEntry::Commit { .. } => {
+                    builder.commit();
+                }
+                JournalEntry::CommitEffects { mut request, .. } => {
+                    let effects: TransformMap = request
+                        .take_effects()
+                        .into_vec()
+                        .try_into()
+                        .expect("should parse effects");
+                    builder.commit_effects(request.take_prestate_hash(), effects.into_inner());
+                }
+                JournalEntry::Upgrade { mut request, .. } => {
+                    builder.upgrade_with_upgrade_request(&mut request);
+                }
+            }
+
+            if let Some(expected) = entry.post_state_hash() {
+                let actual = builder.get_post_state_hash();
+                if actual.as_slice() != expected {
+                    panic!(
+                        "Replay diverged at journal entry {} ({}):\n- recorded post-state hash: {}\n+ replayed post-state hash: {}",
+                        index,
+                        entry_name(entry),
+                        base16::encode_lower(expected),
+                        base16::encode_lower(&actual),
+                    );
+                }
+            }
+        }
+        builder
+    }
+}
+
+fn entry_name(entry: &JournalEntry) -> &'static str {
+    match entry {
+        JournalEntry::Genesis { .. } => "genesis",
+        JournalEntry::Exec { .. } => "exec",
+        JournalEntry::Commit { .. } => "commit",
+        JournalEntry::CommitEffects { .. } => "commit of effects",
+        JournalEntry::Upgrade { .. } => "upgrade",
+    }
+}
+
+fn parse_message<M: Message>(bytes: &[u8]) -> io::Result<M> {
+    protobuf::parse_from_bytes(bytes)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+fn invalid_data(error: bytesrepr::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
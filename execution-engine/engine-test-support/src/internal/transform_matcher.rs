@@ -0,0 +1,195 @@
+//! Matchers for asserting on the transforms of a run of a
+//! [`WasmTestBuilder`](super::WasmTestBuilder), e.g. through
+//! [`expect_transform`](super::WasmTestBuilder::expect_transform).
+
+use std::fmt::{self, Debug, Formatter};
+
+use engine_shared::{stored_value::StoredValue, transform::Transform};
+use types::{Key, KeyTag};
+
+/// Selects the keys an expectation about transforms applies to.
+pub struct KeyMatcher {
+    description: String,
+    predicate: Box<dyn Fn(&Key) -> bool>,
+}
+
+impl KeyMatcher {
+    /// Matches the keys satisfying `predicate`, which is shown as `description` in failure
+    /// messages.
+    pub fn new<F>(description: &str, predicate: F) -> Self
+    where
+        F: Fn(&Key) -> bool + 'static,
+    {
+        KeyMatcher {
+            description: description.to_string(),
+            predicate: Box::new(predicate),
+        }
+    }
+
+    /// Matches `key`.  As transforms are recorded under normalized keys, URefs match whatever
+    /// their access rights.
+    pub fn key(key: Key) -> Self {
+        let normalized_key = key.normalize();
+        KeyMatcher::new(&format!("{:?}", normalized_key), move |other| {
+            other.normalize() == normalized_key
+        })
+    }
+
+    /// Matches every key of the given variant.
+    pub fn tag(key_tag: KeyTag) -> Self {
+        KeyMatcher::new(&format!("any {:?} key", key_tag), move |key| {
+            key.tag() == key_tag
+        })
+    }
+
+    pub fn matches(&self, key: &Key) -> bool {
+        (self.predicate)(key)
+    }
+}
+
+impl Debug for KeyMatcher {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.description)
+    }
+}
+
+/// Selects the transforms an expectation about transforms is met by.
+pub struct TransformMatcher {
+    description: String,
+    predicate: Box<dyn Fn(&Transform) -> bool>,
+}
+
+impl TransformMatcher {
+    /// Matches the transforms satisfying `predicate`, which is shown as `description` in failure
+    /// messages.
+    pub fn new<F>(description: &str, predicate: F) -> Self
+    where
+        F: Fn(&Transform) -> bool + 'static,
+    {
+        TransformMatcher {
+            description: description.to_string(),
+            predicate: Box::new(predicate),
+        }
+    }
+
+    /// Matches every transform, including reads.
+    pub fn any() -> Self {
+        TransformMatcher::new("any transform", |_| true)
+    }
+
+    /// Matches `transform` only.
+    pub fn exactly(transform: Transform) -> Self {
+        TransformMatcher::new(&format!("{:?}", transform), move |other| {
+            *other == transform
+        })
+    }
+
+    /// Matches writes of any value.
+    pub fn write() -> Self {
+        TransformMatcher::write_where("any value", |_| true)
+    }
+
+    /// Matches writes of the values satisfying `predicate`, which is shown as `description` in
+    /// failure messages.
+    pub fn write_where<F>(description: &str, predicate: F) -> Self
+    where
+        F: Fn(&StoredValue) -> bool + 'static,
+    {
+        TransformMatcher::new(
+            &format!("Write({})", description),
+            move |transform| match transform {
+                Transform::Write(value) => predicate(value),
+                _ => false,
+            },
+        )
+    }
+
+    pub fn matches(&self, transform: &Transform) -> bool {
+        (self.predicate)(transform)
+    }
+}
+
+impl Debug for TransformMatcher {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.description)
+    }
+}
+
+/// Lists the given transforms one per line, in the order of their keys.
+pub(crate) fn format_transforms<'a, I>(transforms: I) -> String
+where
+    I: IntoIterator<Item = (&'a Key, &'a Transform)>,
+{
+    let mut transforms = transforms.into_iter().collect::<Vec<_>>();
+    transforms.sort_by_key(|(key, _)| **key);
+    if transforms.is_empty() {
+        return "  (none)\n".to_string();
+    }
+    transforms
+        .into_iter()
+        .map(|(key, transform)| format!("  {:?} => {:?}\n", key, transform))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{AccessRights, CLValue, URef};
+
+    use super::*;
+
+    #[test]
+    fn key_matcher_should_ignore_access_rights() {
+        let uref = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
+        let matcher = KeyMatcher::key(Key::URef(uref));
+
+        assert!(matcher.matches(&Key::URef(uref)));
+        assert!(matcher.matches(&Key::URef(uref.remove_access_rights())));
+        assert!(!matcher.matches(&Key::URef(URef::new([2u8; 32], AccessRights::READ))));
+        assert!(!matcher.matches(&Key::Hash([1u8; 32])));
+    }
+
+    #[test]
+    fn key_matcher_should_match_tag() {
+        let matcher = KeyMatcher::tag(KeyTag::Hash);
+
+        assert!(matcher.matches(&Key::Hash([1u8; 32])));
+        assert!(!matcher.matches(&Key::Account([1u8; 32])));
+    }
+
+    #[test]
+    fn transform_matcher_should_match_writes() {
+        let value = StoredValue::CLValue(CLValue::from_t(1u64).unwrap());
+        let write = Transform::Write(value.clone());
+
+        assert!(TransformMatcher::write().matches(&write));
+        assert!(!TransformMatcher::write().matches(&Transform::Identity));
+        assert!(TransformMatcher::exactly(write.clone()).matches(&write));
+        assert!(!TransformMatcher::exactly(write).matches(&Transform::AddUInt64(1)));
+
+        let matcher = TransformMatcher::write_where("a CLValue", |value| match value {
+            StoredValue::CLValue(_) => true,
+            _ => false,
+        });
+        assert!(matcher.matches(&Transform::Write(value)));
+        assert_eq!(format!("{:?}", matcher), "Write(a CLValue)");
+    }
+
+    #[test]
+    fn should_format_transforms_in_order_of_keys() {
+        let first = Key::Account([1u8; 32]);
+        let second = Key::Account([2u8; 32]);
+        let transforms = vec![
+            (&second, &Transform::Identity),
+            (&first, &Transform::Identity),
+        ];
+
+        let formatted = format_transforms(transforms);
+        assert_eq!(formatted.lines().count(), 2);
+        assert!(formatted
+            .lines()
+            .next()
+            .unwrap()
+            .starts_with(&format!("  {:?}", first)));
+        assert_eq!(format_transforms(Vec::new()), "  (none)\n");
+    }
+}
@@ -1,11 +1,17 @@
 use engine_grpc_server::engine_server::{
     ipc::{
-        ChainSpec_ActivationPoint, ChainSpec_CostTable_WasmCosts, ChainSpec_UpgradePoint,
-        DeployCode, UpgradeRequest,
+        ChainSpec_ActivationPoint, ChainSpec_ChainParameters,
+        ChainSpec_CostTable_HostFunctionCosts, ChainSpec_CostTable_WasmCosts,
+        ChainSpec_DeniedHostFunctions, ChainSpec_GasMetering, ChainSpec_UpgradePoint, DeployCode,
+        UpgradeRequest,
     },
     state,
 };
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_storage::protocol_data::ChainParameters;
+use engine_wasm_prep::{
+    denied_host_functions::DeniedHostFunctions, gas_metering::GasMetering,
+    host_function_costs::HostFunctionCosts, wasm_costs::WasmCosts,
+};
 use types::ProtocolVersion;
 
 pub struct UpgradeRequestBuilder {
@@ -14,6 +20,11 @@ pub struct UpgradeRequestBuilder {
     new_protocol_version: state::ProtocolVersion,
     upgrade_installer: DeployCode,
     new_costs: Option<ChainSpec_CostTable_WasmCosts>,
+    new_host_function_costs: Option<ChainSpec_CostTable_HostFunctionCosts>,
+    new_refund_percentage: Option<u8>,
+    new_chain_parameters: Option<ChainSpec_ChainParameters>,
+    new_denied_host_functions: Option<ChainSpec_DeniedHostFunctions>,
+    new_gas_metering: Option<ChainSpec_GasMetering>,
     activation_point: ChainSpec_ActivationPoint,
 }
 
@@ -54,10 +65,46 @@ impl UpgradeRequestBuilder {
         new_costs.set_max_stack_height(wasm_costs.max_stack_height);
         new_costs.set_mem(wasm_costs.mem);
         new_costs.set_memcpy(wasm_costs.memcpy);
+        new_costs.set_storage_write_byte(wasm_costs.storage_write_byte);
+        new_costs.set_read_cost(wasm_costs.read_cost);
+        new_costs.set_write_cost(wasm_costs.write_cost);
+        new_costs.set_add_cost(wasm_costs.add_cost);
+        new_costs.set_hash_base(wasm_costs.hash_base);
+        new_costs.set_hash_byte(wasm_costs.hash_byte);
+        new_costs.set_max_memory(wasm_costs.max_memory);
+        new_costs.set_max_table_size(wasm_costs.max_table_size);
         self.new_costs = Some(new_costs);
         self
     }
 
+    pub fn with_new_host_function_costs(mut self, host_function_costs: &HostFunctionCosts) -> Self {
+        self.new_host_function_costs = Some(host_function_costs.into());
+        self
+    }
+
+    pub fn with_new_refund_percentage(mut self, refund_percentage: u8) -> Self {
+        self.new_refund_percentage = Some(refund_percentage);
+        self
+    }
+
+    pub fn with_new_chain_parameters(mut self, chain_parameters: ChainParameters) -> Self {
+        self.new_chain_parameters = Some(chain_parameters.into());
+        self
+    }
+
+    pub fn with_new_denied_host_functions(
+        mut self,
+        denied_host_functions: &DeniedHostFunctions,
+    ) -> Self {
+        self.new_denied_host_functions = Some(denied_host_functions.into());
+        self
+    }
+
+    pub fn with_new_gas_metering(mut self, gas_metering: GasMetering) -> Self {
+        self.new_gas_metering = Some(gas_metering.into());
+        self
+    }
+
     pub fn with_activation_point(mut self, rank: u64) -> Self {
         self.activation_point = {
             let mut ret = ChainSpec_ActivationPoint::new();
@@ -79,6 +126,25 @@ impl UpgradeRequestBuilder {
                 upgrade_point.set_new_costs(cost_table);
             }
         }
+        if let Some(new_host_function_costs) = self.new_host_function_costs {
+            upgrade_point
+                .mut_new_costs()
+                .set_host_functions(new_host_function_costs);
+        }
+        if let Some(new_refund_percentage) = self.new_refund_percentage {
+            upgrade_point
+                .mut_new_refund_policy()
+                .set_refund_percentage(new_refund_percentage.into());
+        }
+        if let Some(new_chain_parameters) = self.new_chain_parameters {
+            upgrade_point.set_new_chain_parameters(new_chain_parameters);
+        }
+        if let Some(new_denied_host_functions) = self.new_denied_host_functions {
+            upgrade_point.set_new_denied_host_functions(new_denied_host_functions);
+        }
+        if let Some(new_gas_metering) = self.new_gas_metering {
+            upgrade_point.set_new_gas_metering(new_gas_metering);
+        }
         upgrade_point.set_protocol_version(self.new_protocol_version);
         upgrade_point.set_upgrade_installer(self.upgrade_installer);
 
@@ -97,6 +163,11 @@ impl Default for UpgradeRequestBuilder {
             new_protocol_version: Default::default(),
             upgrade_installer: Default::default(),
             new_costs: None,
+            new_host_function_costs: None,
+            new_refund_percentage: None,
+            new_chain_parameters: None,
+            new_denied_host_functions: None,
+            new_gas_metering: None,
             activation_point: Default::default(),
         }
     }
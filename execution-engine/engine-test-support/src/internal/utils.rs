@@ -17,8 +17,9 @@ use engine_shared::{
 use types::Key;
 
 use crate::internal::{
-    DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_TIMESTAMP, DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS,
-    MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+    DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_TIMESTAMP, DEFAULT_PROTOCOL_VERSION,
+    DEFAULT_SLASH_PERCENTAGE, DEFAULT_UNBONDING_DELAY, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT,
+    POS_INSTALL_CONTRACT,
 };
 
 lazy_static! {
@@ -101,6 +102,8 @@ pub fn create_genesis_config(accounts: Vec<GenesisAccount>) -> GenesisConfig {
         proof_of_stake_installer_bytes,
         accounts,
         wasm_costs,
+        DEFAULT_UNBONDING_DELAY,
+        DEFAULT_SLASH_PERCENTAGE,
     )
 }
 
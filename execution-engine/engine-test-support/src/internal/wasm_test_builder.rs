@@ -2,26 +2,25 @@ use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
     ffi::OsStr,
-    fs,
-    path::PathBuf,
+    fs::{self, File},
+    path::{Path, PathBuf},
     rc::Rc,
     sync::Arc,
 };
 
 use grpc::RequestOptions;
-use lmdb::DatabaseFlags;
 
 use engine_core::{
     engine_state::{
         execute_request::ExecuteRequest, execution_result::ExecutionResult, genesis::GenesisConfig,
-        EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR,
+        system_call_result::SystemCallResult, EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR,
     },
     execution,
 };
 use engine_grpc_server::engine_server::{
     ipc::{
-        CommitRequest, CommitResponse, GenesisResponse, QueryRequest, UpgradeRequest,
-        UpgradeResponse,
+        self, CommitRequest, CommitResponse, GenesisResponse, QueryRequest, QueryResponse,
+        UpgradeRequest, UpgradeResponse,
     },
     ipc_grpc::ExecutionEngineService,
     mappings::{MappingError, TransformMap},
@@ -38,18 +37,24 @@ use engine_shared::{
     transform::Transform,
 };
 use engine_storage::{
-    global_state::{in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState, StateProvider},
-    protocol_data_store::lmdb::LmdbProtocolDataStore,
+    global_state::{
+        in_memory::InMemoryGlobalState,
+        lmdb::{LmdbGlobalState, LmdbStorageConfig},
+        StateProvider,
+    },
     transaction_source::lmdb::LmdbEnvironment,
-    trie_store::lmdb::LmdbTrieStore,
 };
 use types::{
     account::{PublicKey, PurseId},
-    bytesrepr::{self, ToBytes},
-    CLValue, Key, URef, U512,
+    bytesrepr, install_manifest_key_name, CLType, CLValue, InstallManifest, Key, ProtocolVersion,
+    URef, U512,
 };
 
-use crate::internal::utils;
+use crate::internal::{
+    replay::JournalEntry,
+    transform_matcher::{format_transforms, KeyMatcher, TransformMatcher},
+    utils,
+};
 
 /// LMDB initial map size is calculated based on DEFAULT_LMDB_PAGES and systems page size.
 ///
@@ -84,27 +89,13 @@ pub struct WasmTestBuilder<S> {
     mint_contract_uref: Option<URef>,
     /// PoS contract uref
     pos_contract_uref: Option<URef>,
+    /// File the requests sent to the engine state are recorded in, if any
+    journal_path: Option<PathBuf>,
 }
 
 impl Default for InMemoryWasmTestBuilder {
     fn default() -> Self {
-        let engine_config = EngineConfig::new();
-        let global_state = InMemoryGlobalState::empty().expect("should create global state");
-        let engine_state = EngineState::new(global_state, engine_config);
-
-        WasmTestBuilder {
-            engine_state: Rc::new(engine_state),
-            exec_responses: Vec::new(),
-            upgrade_responses: Vec::new(),
-            genesis_hash: None,
-            post_state_hash: None,
-            transforms: Vec::new(),
-            bonded_validators: Vec::new(),
-            genesis_account: None,
-            mint_contract_uref: None,
-            pos_contract_uref: None,
-            genesis_transforms: None,
-        }
+        Self::new_with_config(EngineConfig::new())
     }
 }
 
@@ -124,6 +115,7 @@ impl<S> Clone for WasmTestBuilder<S> {
             mint_contract_uref: self.mint_contract_uref,
             pos_contract_uref: self.pos_contract_uref,
             genesis_transforms: self.genesis_transforms.clone(),
+            journal_path: self.journal_path.clone(),
         }
     }
 }
@@ -137,6 +129,21 @@ impl<S> WasmTestResult<S> {
     pub fn builder(&self) -> &WasmTestBuilder<S> {
         &self.0
     }
+
+    /// Returns the text printed via `runtime::print` by the deploys of the last exec request, in
+    /// order.
+    pub fn stdout(&self) -> Vec<String> {
+        self.0
+            .exec_responses
+            .last()
+            .map(|exec_response| {
+                exec_response
+                    .iter()
+                    .flat_map(|result| result.stdout().iter().cloned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 impl InMemoryWasmTestBuilder {
@@ -145,7 +152,7 @@ impl InMemoryWasmTestBuilder {
         engine_config: EngineConfig,
         post_state_hash: Vec<u8>,
     ) -> Self {
-        let engine_state = EngineState::new(global_state, engine_config);
+        let engine_state = new_engine_state(global_state, engine_config);
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             genesis_hash: Some(post_state_hash.clone()),
@@ -153,6 +160,26 @@ impl InMemoryWasmTestBuilder {
             ..Default::default()
         }
     }
+
+    pub fn new_with_config(engine_config: EngineConfig) -> Self {
+        let global_state = InMemoryGlobalState::empty().expect("should create global state");
+        let engine_state = new_engine_state(global_state, engine_config);
+
+        WasmTestBuilder {
+            engine_state: Rc::new(engine_state),
+            exec_responses: Vec::new(),
+            upgrade_responses: Vec::new(),
+            genesis_hash: None,
+            post_state_hash: None,
+            transforms: Vec::new(),
+            bonded_validators: Vec::new(),
+            genesis_account: None,
+            mint_contract_uref: None,
+            pos_contract_uref: None,
+            genesis_transforms: None,
+            journal_path: None,
+        }
+    }
 }
 
 impl LmdbWasmTestBuilder {
@@ -160,23 +187,23 @@ impl LmdbWasmTestBuilder {
         data_dir: &T,
         engine_config: EngineConfig,
     ) -> Self {
-        let page_size = get_page_size().expect("should get page size");
+        Self::new_with_config_and_map_size(data_dir, engine_config, Self::default_map_size())
+    }
+
+    /// Creates a new instance of builder whose LMDB environment has the given map size in bytes.
+    pub fn new_with_config_and_map_size<T: AsRef<OsStr> + ?Sized>(
+        data_dir: &T,
+        engine_config: EngineConfig,
+        map_size: usize,
+    ) -> Self {
         let global_state_dir = Self::create_and_get_global_state_dir(data_dir);
         let environment = Arc::new(
-            LmdbEnvironment::new(&global_state_dir, page_size * DEFAULT_LMDB_PAGES)
+            LmdbEnvironment::new(&global_state_dir, map_size)
                 .expect("should create LmdbEnvironment"),
         );
-        let trie_store = Arc::new(
-            LmdbTrieStore::new(&environment, None, DatabaseFlags::empty())
-                .expect("should create LmdbTrieStore"),
-        );
-        let protocol_data_store = Arc::new(
-            LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty())
-                .expect("should create LmdbProtocolDataStore"),
-        );
-        let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
+        let global_state = LmdbGlobalState::open(environment, &LmdbStorageConfig::default())
             .expect("should create LmdbGlobalState");
-        let engine_state = EngineState::new(global_state, engine_config);
+        let engine_state = new_engine_state(global_state, engine_config);
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
@@ -189,6 +216,7 @@ impl LmdbWasmTestBuilder {
             mint_contract_uref: None,
             pos_contract_uref: None,
             genesis_transforms: None,
+            journal_path: None,
         }
     }
 
@@ -221,21 +249,30 @@ impl LmdbWasmTestBuilder {
         engine_config: EngineConfig,
         post_state_hash: Vec<u8>,
     ) -> Self {
-        let page_size = get_page_size().expect("should get page size");
+        Self::open_with_map_size(
+            data_dir,
+            engine_config,
+            post_state_hash,
+            Self::default_map_size(),
+        )
+    }
+
+    /// Like `open`, but with the given map size in bytes.  A map size larger than the one the LMDBs
+    /// were created with grows them.
+    pub fn open_with_map_size<T: AsRef<OsStr> + ?Sized>(
+        data_dir: &T,
+        engine_config: EngineConfig,
+        post_state_hash: Vec<u8>,
+        map_size: usize,
+    ) -> Self {
         let global_state_dir = Self::create_and_get_global_state_dir(data_dir);
         let environment = Arc::new(
-            LmdbEnvironment::new(&global_state_dir, page_size * DEFAULT_LMDB_PAGES)
+            LmdbEnvironment::new(&global_state_dir, map_size)
                 .expect("should create LmdbEnvironment"),
         );
-        let trie_store =
-            Arc::new(LmdbTrieStore::open(&environment, None).expect("should open LmdbTrieStore"));
-        let protocol_data_store = Arc::new(
-            LmdbProtocolDataStore::open(&environment, None)
-                .expect("should open LmdbProtocolDataStore"),
-        );
-        let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-            .expect("should create LmdbGlobalState");
-        let engine_state = EngineState::new(global_state, engine_config);
+        let global_state = LmdbGlobalState::open(environment, &LmdbStorageConfig::default())
+            .expect("should open LmdbGlobalState");
+        let engine_state = new_engine_state(global_state, engine_config);
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
@@ -248,9 +285,16 @@ impl LmdbWasmTestBuilder {
             mint_contract_uref: None,
             pos_contract_uref: None,
             genesis_transforms: None,
+            journal_path: None,
         }
     }
 
+    /// Returns the map size used unless one is given, i.e. [`DEFAULT_LMDB_PAGES`] pages.
+    pub fn default_map_size() -> usize {
+        let page_size = get_page_size().expect("should get page size");
+        page_size * DEFAULT_LMDB_PAGES
+    }
+
     fn create_and_get_global_state_dir<T: AsRef<OsStr> + ?Sized>(data_dir: &T) -> PathBuf {
         let global_state_path = {
             let mut path = PathBuf::from(data_dir);
@@ -283,6 +327,30 @@ where
             mint_contract_uref: result.0.mint_contract_uref,
             pos_contract_uref: result.0.pos_contract_uref,
             genesis_transforms: result.0.genesis_transforms,
+            journal_path: result.0.journal_path,
+        }
+    }
+
+    /// Records the requests sent to the engine state from now on in a [`Journal`] written to the
+    /// file at `path`, which is truncated first.
+    ///
+    /// [`Journal`]: crate::internal::replay::Journal
+    pub fn record_journal<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        let path = path.as_ref();
+        File::create(path)
+            .unwrap_or_else(|error| panic!("Expected to create {}: {}", path.display(), error));
+        self.journal_path = Some(path.to_path_buf());
+        self
+    }
+
+    /// Appends the entry made by `make_entry` to the journal, if requests are being recorded.
+    fn record<F: FnOnce() -> JournalEntry>(&self, make_entry: F) {
+        if let Some(journal_path) = self.journal_path.as_ref() {
+            make_entry()
+                .append_to(journal_path)
+                .unwrap_or_else(|error| {
+                    panic!("Expected to write to {}: {}", journal_path.display(), error)
+                });
         }
     }
 
@@ -329,15 +397,19 @@ where
         self.pos_contract_uref = Some(protocol_data.proof_of_stake());
         self.genesis_account = Some(genesis_account);
         self.genesis_transforms = Some(transforms);
+        self.record(|| JournalEntry::Genesis {
+            config: genesis_config.to_owned().into(),
+            post_state_hash: state_root_hash.to_vec(),
+        });
         self
     }
 
-    pub fn query(
+    fn query_response(
         &self,
         maybe_post_state: Option<Vec<u8>>,
         base_key: Key,
         path: &[&str],
-    ) -> Result<StoredValue, String> {
+    ) -> Result<QueryResponse, String> {
         let post_state = maybe_post_state
             .or_else(|| self.post_state_hash.clone())
             .expect("builder must have a post-state hash");
@@ -356,9 +428,36 @@ where
             return Err(query_response.take_failure());
         }
 
+        Ok(query_response)
+    }
+
+    pub fn query(
+        &self,
+        maybe_post_state: Option<Vec<u8>>,
+        base_key: Key,
+        path: &[&str],
+    ) -> Result<StoredValue, String> {
+        let mut query_response = self.query_response(maybe_post_state, base_key, path)?;
         bytesrepr::deserialize(query_response.take_success()).map_err(|err| format!("{}", err))
     }
 
+    /// Returns the type reported alongside the queried value, which is only present if the value
+    /// is a `CLValue`.
+    pub fn query_cl_type(
+        &self,
+        maybe_post_state: Option<Vec<u8>>,
+        base_key: Key,
+        path: &[&str],
+    ) -> Result<Option<CLType>, String> {
+        let mut query_response = self.query_response(maybe_post_state, base_key, path)?;
+        if !query_response.has_cl_type() {
+            return Ok(None);
+        }
+        CLType::try_from(query_response.take_cl_type())
+            .map(Some)
+            .map_err(|error| format!("{:?}", error))
+    }
+
     pub fn exec(&mut self, mut exec_request: ExecuteRequest) -> &mut Self {
         let exec_request = {
             let hash = self
@@ -369,6 +468,16 @@ where
                 hash.as_slice().try_into().expect("expected a valid hash");
             exec_request
         };
+        let exec_request = if self.journal_path.is_some() {
+            let request = ipc::ExecuteRequest::from(exec_request);
+            self.record(|| JournalEntry::Exec {
+                request: request.clone(),
+            });
+            ExecuteRequest::try_from(request)
+                .unwrap_or_else(|response| panic!("could not parse: {:?}", response))
+        } else {
+            exec_request
+        };
         let exec_response = self
             .engine_state
             .run_execute(CorrelationId::new(), exec_request);
@@ -395,7 +504,11 @@ where
 
         let effects = self.transforms.last().cloned().unwrap_or_default();
 
-        self.commit_effects(prestate_hash, effects)
+        self.apply_commit(prestate_hash, effects);
+        self.record(|| JournalEntry::Commit {
+            post_state_hash: self.get_post_state_hash(),
+        });
+        self
     }
 
     /// Sends raw commit request to the current engine response.
@@ -421,6 +534,16 @@ where
         prestate_hash: Vec<u8>,
         effects: AdditiveMap<Key, Transform>,
     ) -> &mut Self {
+        let commit_request = create_commit_request(&prestate_hash, &effects);
+        self.apply_commit(prestate_hash, effects);
+        self.record(|| JournalEntry::CommitEffects {
+            request: commit_request,
+            post_state_hash: self.get_post_state_hash(),
+        });
+        self
+    }
+
+    fn apply_commit(&mut self, prestate_hash: Vec<u8>, effects: AdditiveMap<Key, Transform>) {
         let mut commit_response = self.commit_transforms(prestate_hash, effects);
         if !commit_response.has_success() {
             panic!(
@@ -437,7 +560,6 @@ where
             .collect::<Result<HashMap<PublicKey, U512>, MappingError>>()
             .unwrap();
         self.bonded_validators.push(bonded_validators);
-        self
     }
 
     pub fn upgrade_with_upgrade_request(
@@ -462,6 +584,79 @@ where
         self.post_state_hash = Some(upgrade_success.get_post_state_hash().to_vec());
 
         self.upgrade_responses.push(upgrade_response.clone());
+        self.record(|| JournalEntry::Upgrade {
+            request: upgrade_request.clone(),
+            post_state_hash: upgrade_success.get_post_state_hash().to_vec(),
+        });
+        self
+    }
+
+    /// Slashes `validators` on the latest post-state hash, expects a success, and overwrites the
+    /// cached post state hash with the new one.
+    pub fn slash(
+        &mut self,
+        protocol_version: ProtocolVersion,
+        validators: Vec<PublicKey>,
+    ) -> &mut Self {
+        let pre_state_hash = {
+            let hash = self
+                .post_state_hash
+                .clone()
+                .expect("expected post_state_hash");
+            Blake2bHash::try_from(hash.as_slice()).expect("should convert post_state_hash")
+        };
+        let slashing_result = self
+            .engine_state
+            .commit_slashing(
+                CorrelationId::new(),
+                protocol_version,
+                pre_state_hash,
+                validators,
+            )
+            .expect("should slash");
+
+        match slashing_result {
+            SystemCallResult::Success {
+                post_state_hash, ..
+            } => self.post_state_hash = Some(post_state_hash.to_vec()),
+            other => panic!("Expected slashing success but received {}", other),
+        }
+        self
+    }
+
+    /// Distributes the rewards for `era_id` on the latest post-state hash, expects a success, and
+    /// overwrites the cached post state hash with the new one.
+    pub fn distribute_rewards(
+        &mut self,
+        protocol_version: ProtocolVersion,
+        era_id: u64,
+    ) -> &mut Self {
+        let pre_state_hash = {
+            let hash = self
+                .post_state_hash
+                .clone()
+                .expect("expected post_state_hash");
+            Blake2bHash::try_from(hash.as_slice()).expect("should convert post_state_hash")
+        };
+        let rewards_result = self
+            .engine_state
+            .distribute_rewards(
+                CorrelationId::new(),
+                protocol_version,
+                pre_state_hash,
+                era_id,
+            )
+            .expect("should distribute rewards");
+
+        match rewards_result {
+            SystemCallResult::Success {
+                post_state_hash, ..
+            } => self.post_state_hash = Some(post_state_hash.to_vec()),
+            other => panic!(
+                "Expected rewards distribution success but received {}",
+                other
+            ),
+        }
         self
     }
 
@@ -496,6 +691,65 @@ where
         exec_result.is_failure()
     }
 
+    /// Expects the transforms of the last run to include a transform matching `transform_matcher`
+    /// under a key matching `key_matcher`.
+    pub fn expect_transform(
+        &mut self,
+        key_matcher: KeyMatcher,
+        transform_matcher: TransformMatcher,
+    ) -> &mut Self {
+        let transforms = self
+            .transforms
+            .last()
+            .expect("Expected to be called after run()");
+        let matching_keys = transforms
+            .iter()
+            .filter(|(key, _)| key_matcher.matches(key))
+            .collect::<Vec<_>>();
+        if matching_keys
+            .iter()
+            .any(|(_, transform)| transform_matcher.matches(transform))
+        {
+            return self;
+        }
+
+        let found = if matching_keys.is_empty() {
+            format!(
+                "no key matches, all transforms are:\n{}",
+                format_transforms(transforms.iter())
+            )
+        } else {
+            format!("transforms are:\n{}", format_transforms(matching_keys))
+        };
+        panic!(
+            "Expected transform under {:?}\n- expected: {:?}\n+ found {}",
+            key_matcher, transform_matcher, found
+        );
+    }
+
+    /// Expects the transforms of the last run not to write to `key`.
+    pub fn expect_no_write_to(&mut self, key: Key) -> &mut Self {
+        let transforms = self
+            .transforms
+            .last()
+            .expect("Expected to be called after run()");
+        let key_matcher = KeyMatcher::key(key);
+        let writes = transforms
+            .iter()
+            .filter(|(key, transform)| {
+                key_matcher.matches(key) && TransformMatcher::write().matches(transform)
+            })
+            .collect::<Vec<_>>();
+        if !writes.is_empty() {
+            panic!(
+                "Expected no write to {:?}, but found:\n{}",
+                key_matcher,
+                format_transforms(writes)
+            );
+        }
+        self
+    }
+
     /// Gets the transform map that's cached between runs
     pub fn get_transforms(&self) -> Vec<AdditiveMap<Key, Transform>> {
         self.transforms.clone()
@@ -573,10 +827,7 @@ where
 
     pub fn get_purse_balance(&self, purse_id: PurseId) -> U512 {
         let mint = self.get_mint_contract_uref();
-        let purse_addr = purse_id.value().addr();
-        let purse_bytes =
-            ToBytes::to_bytes(&purse_addr).expect("should be able to serialize purse bytes");
-        let balance_mapping_key = Key::local(mint.addr(), &purse_bytes);
+        let balance_mapping_key = types::purse_balance_key(mint, purse_id);
         let balance_uref = self
             .query(None, balance_mapping_key, &[])
             .and_then(|v| CLValue::try_from(v).map_err(|error| format!("{:?}", error)))
@@ -601,6 +852,21 @@ where
         }
     }
 
+    /// Gets the manifest stored by an installer which installed a contract package as
+    /// `package_name` in the context of the given account, if any.
+    pub fn get_install_manifest(
+        &self,
+        account_addr: [u8; 32],
+        package_name: &str,
+    ) -> Option<InstallManifest> {
+        let manifest_key_name = install_manifest_key_name(package_name);
+        let stored_value = self
+            .query(None, Key::Account(account_addr), &[&manifest_key_name])
+            .ok()?;
+        let cl_value = CLValue::try_from(stored_value).expect("should have manifest CLValue");
+        Some(cl_value.into_t().expect("should parse install manifest"))
+    }
+
     pub fn get_contract(&self, contract_uref: URef) -> Option<Contract> {
         let contract_value: StoredValue = self
             .query(None, Key::URef(contract_uref), &[])
@@ -620,6 +886,48 @@ where
         utils::get_exec_costs(exec_response)
     }
 
+    /// Asserts that the first deploy of the `index`th call to [`exec`](Self::exec) cost at least
+    /// `min` and at most `max` gas, so that tests can pin gas usage without breaking on every
+    /// minor change to the cost table.
+    pub fn assert_cost_between(&mut self, index: usize, min: Gas, max: Gas) -> &mut Self {
+        let cost = *self
+            .exec_costs(index)
+            .first()
+            .expect("Unable to get first deploy result");
+        assert!(
+            min <= cost && cost <= max,
+            "Expected exec request {} to cost between {} and {} gas, but it cost {}",
+            index,
+            min,
+            max,
+            cost
+        );
+        self
+    }
+
+    /// Writes the costs of all deploys executed so far to the file at `path`, which is truncated
+    /// first.  Each call to [`exec`](Self::exec) gets a line listing the costs of its deploys in
+    /// gas, separated by spaces.
+    ///
+    /// Checking the file in as a baseline makes any change in costs show up as a diff.
+    pub fn record_costs_to<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        let path = path.as_ref();
+        let costs: String = self
+            .exec_responses
+            .iter()
+            .map(|exec_response| {
+                let costs: Vec<String> = utils::get_exec_costs(exec_response)
+                    .iter()
+                    .map(|cost| cost.value().to_string())
+                    .collect();
+                format!("{}\n", costs.join(" "))
+            })
+            .collect();
+        fs::write(path, costs)
+            .unwrap_or_else(|error| panic!("Expected to write to {}: {}", path.display(), error));
+        self
+    }
+
     pub fn exec_error_message(&self, index: usize) -> Option<String> {
         let response = self.get_exec_response(index)?;
         Some(utils::get_error_message(response))
@@ -633,6 +941,16 @@ where
     }
 }
 
+/// Creates the engine state of a builder.  Test accounts have no secret keys, so it accepts deploys
+/// carrying no signatures, while deploys carrying signatures are still verified.
+fn new_engine_state<S>(global_state: S, engine_config: EngineConfig) -> EngineState<S>
+where
+    S: StateProvider,
+    S::Error: Into<execution::Error>,
+{
+    EngineState::new(global_state, engine_config.with_unsigned_deploys(true))
+}
+
 fn create_query_request(post_state: Vec<u8>, base_key: Key, path: Vec<String>) -> QueryRequest {
     let mut query_request = QueryRequest::new();
 
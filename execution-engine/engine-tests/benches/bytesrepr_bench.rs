@@ -0,0 +1,96 @@
+//! Measures serialization of the values written to global state when committing effects: stored
+//! values, and the trie leaves and nodes holding them.
+//!
+//! The `write_bytes` cases serialize into a reused buffer, so the difference from the matching
+//! `to_bytes` case is the cost of allocating the output.  To compare revisions, run
+//! `cargo bench --bench bytesrepr_bench -- --save-baseline before` on the first, then
+//! `cargo bench --bench bytesrepr_bench -- --baseline before` on the second.
+
+use std::collections::BTreeMap;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use engine_shared::{
+    account::Account, contract::Contract, newtypes::Blake2bHash, stored_value::StoredValue,
+};
+use engine_storage::trie::{Pointer, Trie};
+use types::{
+    account::PurseId, bytesrepr::ToBytes, AccessRights, CLValue, Key, ProtocolVersion, URef, U512,
+};
+
+const NAMED_KEYS_COUNT: u8 = 100;
+const CONTRACT_SIZE: usize = 100 * 1024;
+
+fn named_keys() -> BTreeMap<String, Key> {
+    (0..NAMED_KEYS_COUNT)
+        .map(|i| {
+            let uref = URef::new([i; 32], AccessRights::READ_ADD_WRITE);
+            (format!("named_key_{}", i), Key::URef(uref))
+        })
+        .collect()
+}
+
+fn stored_values() -> Vec<(&'static str, StoredValue)> {
+    let purse_id = PurseId::new(URef::new([255; 32], AccessRights::READ_ADD_WRITE));
+    let account = Account::create([1; 32], named_keys(), purse_id);
+    let contract = Contract::new(
+        vec![0; CONTRACT_SIZE],
+        named_keys(),
+        ProtocolVersion::V1_0_0,
+    );
+    let balance = CLValue::from_t(U512::from(1_000_000_000u64)).expect("should create CLValue");
+
+    vec![
+        ("cl_value", StoredValue::CLValue(balance)),
+        ("account", StoredValue::Account(account)),
+        ("contract", StoredValue::Contract(contract)),
+    ]
+}
+
+fn serialize<T: ToBytes>(value: &T) -> Vec<u8> {
+    value.to_bytes().expect("should serialize")
+}
+
+pub fn stored_value_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stored_value");
+
+    for (name, stored_value) in stored_values() {
+        group.bench_function(format!("to_bytes/{}", name), |b| {
+            b.iter(|| serialize(black_box(&stored_value)))
+        });
+
+        let mut buffer = Vec::new();
+        group.bench_function(format!("write_bytes/{}", name), |b| {
+            b.iter(|| {
+                buffer.clear();
+                black_box(&stored_value)
+                    .write_bytes(&mut buffer)
+                    .expect("should serialize")
+            })
+        });
+    }
+
+    group.finish();
+}
+
+pub fn trie_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("trie");
+
+    for (name, stored_value) in stored_values() {
+        let leaf = Trie::leaf(Key::Hash([2; 32]), stored_value);
+        group.bench_function(format!("to_bytes/leaf/{}", name), |b| {
+            b.iter(|| serialize(black_box(&leaf)))
+        });
+    }
+
+    let indexed_pointers: Vec<(usize, Pointer)> = (0..=255u8)
+        .map(|i| (usize::from(i), Pointer::NodePointer(Blake2bHash::new(&[i]))))
+        .collect();
+    let node: Trie<Key, StoredValue> = Trie::node(&indexed_pointers);
+    group.bench_function("to_bytes/node", |b| b.iter(|| serialize(black_box(&node))));
+
+    group.finish();
+}
+
+criterion_group!(benches, stored_value_bench, trie_bench);
+criterion_main!(benches);
@@ -0,0 +1,61 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_GENESIS_CONFIG,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::gas_metering::{GasMetering, DEFAULT_FUEL_FLUSH_THRESHOLD};
+use types::ProtocolVersion;
+
+const CONTRACT_COMPUTE_LOOP: &str = "compute_loop.wasm";
+const ITERATIONS: u64 = 10_000;
+const NEW_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_1_0;
+
+/// Runs genesis and upgrades to protocol version 1.1.0, metering gas with `gas_metering`.
+fn bootstrap(gas_metering: GasMetering) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(ProtocolVersion::V1_0_0)
+        .with_new_protocol_version(NEW_PROTOCOL_VERSION)
+        .with_activation_point(1)
+        .with_new_gas_metering(gas_metering)
+        .build();
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+    builder
+}
+
+fn compute_loop(builder: &mut InMemoryWasmTestBuilder) {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_COMPUTE_LOOP, (ITERATIONS,))
+            .with_protocol_version(NEW_PROTOCOL_VERSION)
+            .build();
+    // Effects are not committed so that every iteration runs against the same state
+    builder.exec(exec_request).expect_success();
+}
+
+pub fn gas_metering_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gas_metering");
+
+    group.sample_size(10);
+
+    let mut host_call_builder = bootstrap(GasMetering::HostCall);
+    group.bench_function(format!("host_call/{}", ITERATIONS), |b| {
+        b.iter(|| compute_loop(&mut host_call_builder))
+    });
+
+    let mut fuel_builder = bootstrap(GasMetering::Fuel {
+        flush_threshold: DEFAULT_FUEL_FLUSH_THRESHOLD,
+    });
+    group.bench_function(format!("fuel/{}", ITERATIONS), |b| {
+        b.iter(|| compute_loop(&mut fuel_builder))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, gas_metering_bench);
+criterion_main!(benches);
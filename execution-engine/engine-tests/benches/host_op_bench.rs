@@ -0,0 +1,43 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_HOST_OP_COST: &str = "host_op_cost.wasm";
+const ACTIONS: [&str; 3] = ["read", "write", "add"];
+const COUNT: u32 = 1_000;
+
+fn host_ops(builder: &mut InMemoryWasmTestBuilder, action: &str) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_HOST_OP_COST,
+        (action.to_string(), COUNT),
+    )
+    .build();
+    // Effects are not committed so that every iteration runs against the same state
+    builder.exec(exec_request).expect_success();
+}
+
+/// Measures the `read_value`, `write` and `add` host functions, as a basis for the `read_cost`,
+/// `write_cost` and `add_cost` entries of `WasmCosts`.
+pub fn host_op_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("host_op");
+
+    group.sample_size(10);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    for action in &ACTIONS {
+        group.bench_function(format!("{}/{}", action, COUNT), |b| {
+            b.iter(|| host_ops(&mut builder, action))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, host_op_bench);
+criterion_main!(benches);
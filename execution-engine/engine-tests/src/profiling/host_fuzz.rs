@@ -0,0 +1,255 @@
+//! This executable drives random sequences of host calls through the runtime, and checks that an
+//! LMDB-backed and an in-memory global state produce identical results and effects for them.
+//!
+//! Each iteration generates a program for the `host-fuzz` contract, which interprets it as a
+//! sequence of `new_uref`, `write`, `add` and `read` calls on URefs, the same calls on local keys,
+//! and calls into the mint via `create_purse` and `get_balance`.  Programs may reference URefs
+//! which don't exist or contain invalid opcodes, in which case the contract reverts.  The same
+//! deploy is executed and committed against both global states, and the run fails as soon as the
+//! costs, error messages, transforms or post-state hashes of the two differ.
+
+use std::{path::PathBuf, str::FromStr};
+
+use clap::{crate_version, App, Arg, ArgMatches};
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+
+use engine_core::engine_state::EngineConfig;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, LmdbWasmTestBuilder,
+        DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::account::PublicKey;
+
+use casperlabs_engine_tests::profiling;
+
+const ABOUT: &str = "Executes random sequences of host calls against an LMDB-backed and an \
+                     in-memory global state, and checks that both produce identical results and \
+                     effects.";
+
+const ITERATIONS_ARG_NAME: &str = "iterations";
+const ITERATIONS_ARG_LONG: &str = "iterations";
+const ITERATIONS_ARG_VALUE_NAME: &str = "COUNT";
+const ITERATIONS_ARG_DEFAULT: &str = "1000";
+const ITERATIONS_ARG_HELP: &str = "Number of random programs to execute";
+
+const MAX_OPS_ARG_NAME: &str = "max-ops";
+const MAX_OPS_ARG_LONG: &str = "max-ops";
+const MAX_OPS_ARG_VALUE_NAME: &str = "COUNT";
+const MAX_OPS_ARG_DEFAULT: &str = "64";
+const MAX_OPS_ARG_HELP: &str = "Maximum number of host calls in a single program";
+
+const SEED_ARG_NAME: &str = "seed";
+const SEED_ARG_LONG: &str = "seed";
+const SEED_ARG_VALUE_NAME: &str = "NUMBER";
+const SEED_ARG_HELP: &str = "Seed of the generated programs [default: random]";
+
+const HOST_FUZZ_WASM: &str = "host_fuzz.wasm";
+
+/// Opcodes understood by the `host-fuzz` contract.
+const OP_NEW_UREF: u8 = 0;
+const OP_WRITE: u8 = 1;
+const OP_ADD: u8 = 2;
+const OP_READ: u8 = 3;
+const OP_WRITE_LOCAL: u8 = 4;
+const OP_ADD_LOCAL: u8 = 5;
+const OP_READ_LOCAL: u8 = 6;
+const OP_CALL: u8 = 7;
+const OP_COUNT: u8 = 8;
+
+/// One in this many generated instructions is left unconstrained, so that invalid opcodes and
+/// references to missing URefs are exercised too.
+const INVALID_INSTRUCTION_ODDS: u32 = 256;
+/// Local keys are drawn from a small range so that programs read back what they wrote.
+const LOCAL_KEY_COUNT: u8 = 8;
+
+#[derive(Debug)]
+struct Args {
+    data_dir: PathBuf,
+    iterations: u64,
+    max_ops: usize,
+    seed: u64,
+}
+
+fn numeric_arg(
+    name: &'static str,
+    long: &'static str,
+    value_name: &'static str,
+    help: &'static str,
+) -> Arg<'static, 'static> {
+    Arg::with_name(name)
+        .long(long)
+        .value_name(value_name)
+        .help(help)
+        .takes_value(true)
+}
+
+fn parse_arg<T: FromStr>(arg_matches: &ArgMatches, name: &str) -> Option<T> {
+    arg_matches.value_of(name).map(|value| {
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("Expected a valid number for '{}'", name))
+    })
+}
+
+impl Args {
+    fn new() -> Self {
+        let exe_name = profiling::exe_name();
+        let data_dir_arg = profiling::data_dir_arg();
+        let arg_matches = App::new(&exe_name)
+            .version(crate_version!())
+            .about(ABOUT)
+            .arg(data_dir_arg)
+            .arg(
+                numeric_arg(
+                    ITERATIONS_ARG_NAME,
+                    ITERATIONS_ARG_LONG,
+                    ITERATIONS_ARG_VALUE_NAME,
+                    ITERATIONS_ARG_HELP,
+                )
+                .default_value(ITERATIONS_ARG_DEFAULT),
+            )
+            .arg(
+                numeric_arg(
+                    MAX_OPS_ARG_NAME,
+                    MAX_OPS_ARG_LONG,
+                    MAX_OPS_ARG_VALUE_NAME,
+                    MAX_OPS_ARG_HELP,
+                )
+                .default_value(MAX_OPS_ARG_DEFAULT),
+            )
+            .arg(numeric_arg(
+                SEED_ARG_NAME,
+                SEED_ARG_LONG,
+                SEED_ARG_VALUE_NAME,
+                SEED_ARG_HELP,
+            ))
+            .get_matches();
+
+        let max_ops: usize = parse_arg(&arg_matches, MAX_OPS_ARG_NAME).unwrap();
+        assert!(max_ops > 0, "Expected at least one host call per program");
+        Args {
+            data_dir: profiling::data_dir(&arg_matches),
+            iterations: parse_arg(&arg_matches, ITERATIONS_ARG_NAME).unwrap(),
+            max_ops,
+            seed: parse_arg(&arg_matches, SEED_ARG_NAME).unwrap_or_else(rand::random),
+        }
+    }
+}
+
+/// Generates a program of `(opcode, operand)` pairs, mostly referring to URefs created earlier in
+/// the same program.
+fn random_program(rng: &mut StdRng, max_ops: usize) -> Vec<u8> {
+    let op_count = rng.gen_range(1, max_ops + 1);
+    let mut uref_count: u8 = 0;
+    let mut program = Vec::with_capacity(op_count * 2);
+
+    for _ in 0..op_count {
+        if rng.gen_ratio(1, INVALID_INSTRUCTION_ODDS) {
+            program.push(rng.gen());
+            program.push(rng.gen());
+            continue;
+        }
+
+        let opcode = rng.gen_range(0, OP_COUNT);
+        let operand = match opcode {
+            OP_WRITE | OP_ADD | OP_READ if uref_count > 0 => rng.gen_range(0, uref_count),
+            OP_WRITE_LOCAL | OP_ADD_LOCAL | OP_READ_LOCAL => rng.gen_range(0, LOCAL_KEY_COUNT),
+            _ => rng.gen(),
+        };
+        if opcode == OP_NEW_UREF {
+            uref_count = uref_count.saturating_add(1);
+        }
+        program.push(opcode);
+        program.push(operand);
+    }
+
+    program
+}
+
+/// Checks that the last deploy had the same outcome against both global states.
+fn verify(
+    iteration: u64,
+    program: &[u8],
+    lmdb: &LmdbWasmTestBuilder,
+    in_memory: &InMemoryWasmTestBuilder,
+) {
+    let index = lmdb.get_exec_responses_count() - 1;
+    assert_eq!(
+        lmdb.exec_costs(index),
+        in_memory.exec_costs(index),
+        "costs diverged in iteration {} for program {:?}",
+        iteration,
+        program
+    );
+    assert_eq!(
+        lmdb.exec_error_message(index),
+        in_memory.exec_error_message(index),
+        "results diverged in iteration {} for program {:?}",
+        iteration,
+        program
+    );
+    assert_eq!(
+        lmdb.get_transforms().last(),
+        in_memory.get_transforms().last(),
+        "effects diverged in iteration {} for program {:?}",
+        iteration,
+        program
+    );
+    assert_eq!(
+        lmdb.get_post_state_hash(),
+        in_memory.get_post_state_hash(),
+        "post-state hash diverged in iteration {} for program {:?}",
+        iteration,
+        program
+    );
+}
+
+fn main() {
+    let args = Args::new();
+    println!("seed: {}", args.seed);
+    let mut rng = StdRng::seed_from_u64(args.seed);
+
+    let mut lmdb = LmdbWasmTestBuilder::new_with_config(&args.data_dir, EngineConfig::new());
+    lmdb.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+    let mut in_memory = InMemoryWasmTestBuilder::default();
+    in_memory.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let account = PublicKey::new(DEFAULT_ACCOUNT_ADDR);
+    let mut failures = 0;
+    for iteration in 0..args.iterations {
+        let program = random_program(&mut rng, args.max_ops);
+        let mut deploy_hash = [0u8; 32];
+        rng.fill_bytes(&mut deploy_hash);
+
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash(deploy_hash)
+            .with_session_code(HOST_FUZZ_WASM, (program.clone(),))
+            .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+            .with_authorization_keys(&[account])
+            .build();
+        let exec_request = || {
+            ExecuteRequestBuilder::new()
+                .push_deploy(deploy.clone())
+                .build()
+        };
+
+        lmdb.exec(exec_request()).commit();
+        in_memory.exec(exec_request()).commit();
+
+        verify(iteration, &program, &lmdb, &in_memory);
+        if lmdb.is_error() {
+            failures += 1;
+        }
+    }
+
+    println!(
+        "executed {} programs, {} of which reverted; post-state hash: {}",
+        args.iterations,
+        failures,
+        base16::encode_lower(&lmdb.get_post_state_hash())
+    );
+}
@@ -34,6 +34,12 @@ const VERBOSE_ARG_SHORT: &str = "v";
 const VERBOSE_ARG_LONG: &str = "verbose";
 const VERBOSE_ARG_HELP: &str = "Display the transforms resulting from the contract execution";
 
+const PROFILE_HOST_FUNCTIONS_ARG_NAME: &str = "profile-host-functions";
+const PROFILE_HOST_FUNCTIONS_ARG_SHORT: &str = "p";
+const PROFILE_HOST_FUNCTIONS_ARG_LONG: &str = "profile-host-functions";
+const PROFILE_HOST_FUNCTIONS_ARG_HELP: &str =
+    "Display the calls, gas and time spent per contract and host function";
+
 const TRANSFER_AMOUNT: u64 = 1;
 
 const STANDARD_PAYMENT_WASM: &str = "standard_payment.wasm";
@@ -51,6 +57,13 @@ fn verbose_arg() -> Arg<'static, 'static> {
         .help(VERBOSE_ARG_HELP)
 }
 
+fn profile_host_functions_arg() -> Arg<'static, 'static> {
+    Arg::with_name(PROFILE_HOST_FUNCTIONS_ARG_NAME)
+        .short(PROFILE_HOST_FUNCTIONS_ARG_SHORT)
+        .long(PROFILE_HOST_FUNCTIONS_ARG_LONG)
+        .help(PROFILE_HOST_FUNCTIONS_ARG_HELP)
+}
+
 fn parse_hash(encoded_hash: &str) -> Vec<u8> {
     base16::decode(encoded_hash).expect("Expected a valid, hex-encoded hash")
 }
@@ -60,6 +73,7 @@ struct Args {
     root_hash: Option<Vec<u8>>,
     data_dir: PathBuf,
     verbose: bool,
+    profile_host_functions: bool,
 }
 
 impl Args {
@@ -72,14 +86,17 @@ impl Args {
             .arg(root_hash_arg())
             .arg(data_dir_arg)
             .arg(verbose_arg())
+            .arg(profile_host_functions_arg())
             .get_matches();
         let root_hash = arg_matches.value_of(ROOT_HASH_ARG_NAME).map(parse_hash);
         let data_dir = profiling::data_dir(&arg_matches);
         let verbose = arg_matches.is_present(VERBOSE_ARG_NAME);
+        let profile_host_functions = arg_matches.is_present(PROFILE_HOST_FUNCTIONS_ARG_NAME);
         Args {
             root_hash,
             data_dir,
             verbose,
+            profile_host_functions,
         }
     }
 }
@@ -113,12 +130,21 @@ fn main() {
         ExecuteRequestBuilder::new().push_deploy(deploy).build()
     };
 
-    let mut test_builder =
-        LmdbWasmTestBuilder::open(&args.data_dir, EngineConfig::new(), root_hash);
+    let engine_config = EngineConfig::new().with_profiling(args.profile_host_functions);
+    let mut test_builder = LmdbWasmTestBuilder::open(&args.data_dir, engine_config, root_hash);
 
     test_builder.exec(exec_request).expect_success().commit();
 
     if args.verbose {
         println!("{:#?}", test_builder.get_transforms());
     }
+
+    if args.profile_host_functions {
+        let response = test_builder
+            .get_exec_response(0)
+            .expect("there should be a response");
+        if let Some(profile) = response[0].host_function_profile() {
+            print!("{}", profile);
+        }
+    }
 }
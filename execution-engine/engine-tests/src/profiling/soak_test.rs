@@ -0,0 +1,357 @@
+//! This executable runs randomized deploys against an LMDB-backed global state for a long time,
+//! periodically injecting faults, in order to check that the state survives restarts of the
+//! execution engine.
+//!
+//! Every block is also executed against an in-memory global state which is never restarted, and
+//! after each block the post-state hashes of both must match.  The injected faults are:
+//!
+//! * a crash after executing a block but before committing it, after which the block is retried,
+//! * a crash right after committing a block, and
+//! * growing the LMDB map size on restart.
+//!
+//! A crash is simulated by dropping the engine state and reopening the LMDB environment from the
+//! last post-state hash returned by a commit.
+
+use std::{path::PathBuf, str::FromStr};
+
+use clap::{crate_version, App, Arg, ArgMatches};
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+
+use engine_core::engine_state::{
+    deploy_item::DeployItem,
+    execute_request::ExecuteRequest,
+    genesis::{GenesisAccount, GenesisConfig},
+    EngineConfig,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        LmdbWasmTestBuilder, DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_TIMESTAMP, DEFAULT_PAYMENT,
+        DEFAULT_PROTOCOL_VERSION, DEFAULT_SLASH_PERCENTAGE, DEFAULT_UNBONDING_DELAY,
+        DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use types::{account::PublicKey, Key, U512};
+
+use casperlabs_engine_tests::profiling;
+
+const ABOUT: &str = "Executes randomized deploys against an LMDB-backed global state, injecting \
+                     simulated crashes and LMDB map resizes, and checks the post-state hashes \
+                     against an in-memory global state after every block.";
+
+const BLOCKS_ARG_NAME: &str = "blocks";
+const BLOCKS_ARG_LONG: &str = "blocks";
+const BLOCKS_ARG_VALUE_NAME: &str = "COUNT";
+const BLOCKS_ARG_DEFAULT: &str = "1000";
+const BLOCKS_ARG_HELP: &str = "Number of blocks to execute";
+
+const ACCOUNTS_ARG_NAME: &str = "accounts";
+const ACCOUNTS_ARG_LONG: &str = "accounts";
+const ACCOUNTS_ARG_VALUE_NAME: &str = "COUNT";
+const ACCOUNTS_ARG_DEFAULT: &str = "10";
+const ACCOUNTS_ARG_HELP: &str = "Number of accounts sending and receiving transfers";
+
+const FAULT_INTERVAL_ARG_NAME: &str = "fault-interval";
+const FAULT_INTERVAL_ARG_LONG: &str = "fault-interval";
+const FAULT_INTERVAL_ARG_VALUE_NAME: &str = "BLOCKS";
+const FAULT_INTERVAL_ARG_DEFAULT: &str = "10";
+const FAULT_INTERVAL_ARG_HELP: &str = "Number of blocks between injected faults";
+
+const MAP_SIZE_ARG_NAME: &str = "map-size";
+const MAP_SIZE_ARG_LONG: &str = "map-size";
+const MAP_SIZE_ARG_VALUE_NAME: &str = "MiB";
+const MAP_SIZE_ARG_DEFAULT: &str = "1024";
+const MAP_SIZE_ARG_HELP: &str = "Initial LMDB map size, grown by a sixteenth on every resize";
+
+const SEED_ARG_NAME: &str = "seed";
+const SEED_ARG_LONG: &str = "seed";
+const SEED_ARG_VALUE_NAME: &str = "NUMBER";
+const SEED_ARG_HELP: &str = "Seed of the generated deploys and faults [default: random]";
+
+const TRANSFER_TO_ACCOUNT_WASM: &str = "transfer_to_account.wasm";
+const MAX_DEPLOYS_PER_BLOCK: usize = 5;
+const MAX_TRANSFER_AMOUNT: u64 = 1_000_000;
+const MIB: usize = 1024 * 1024;
+
+#[derive(Debug)]
+struct Args {
+    data_dir: PathBuf,
+    blocks: u64,
+    accounts: u8,
+    fault_interval: u64,
+    map_size: usize,
+    seed: u64,
+}
+
+fn numeric_arg(
+    name: &'static str,
+    long: &'static str,
+    value_name: &'static str,
+    help: &'static str,
+) -> Arg<'static, 'static> {
+    Arg::with_name(name)
+        .long(long)
+        .value_name(value_name)
+        .help(help)
+        .takes_value(true)
+}
+
+fn parse_arg<T: FromStr>(arg_matches: &ArgMatches, name: &str) -> Option<T> {
+    arg_matches.value_of(name).map(|value| {
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("Expected a valid number for '{}'", name))
+    })
+}
+
+impl Args {
+    fn new() -> Self {
+        let exe_name = profiling::exe_name();
+        let data_dir_arg = profiling::data_dir_arg();
+        let arg_matches = App::new(&exe_name)
+            .version(crate_version!())
+            .about(ABOUT)
+            .arg(data_dir_arg)
+            .arg(
+                numeric_arg(
+                    BLOCKS_ARG_NAME,
+                    BLOCKS_ARG_LONG,
+                    BLOCKS_ARG_VALUE_NAME,
+                    BLOCKS_ARG_HELP,
+                )
+                .default_value(BLOCKS_ARG_DEFAULT),
+            )
+            .arg(
+                numeric_arg(
+                    ACCOUNTS_ARG_NAME,
+                    ACCOUNTS_ARG_LONG,
+                    ACCOUNTS_ARG_VALUE_NAME,
+                    ACCOUNTS_ARG_HELP,
+                )
+                .default_value(ACCOUNTS_ARG_DEFAULT),
+            )
+            .arg(
+                numeric_arg(
+                    FAULT_INTERVAL_ARG_NAME,
+                    FAULT_INTERVAL_ARG_LONG,
+                    FAULT_INTERVAL_ARG_VALUE_NAME,
+                    FAULT_INTERVAL_ARG_HELP,
+                )
+                .default_value(FAULT_INTERVAL_ARG_DEFAULT),
+            )
+            .arg(
+                numeric_arg(
+                    MAP_SIZE_ARG_NAME,
+                    MAP_SIZE_ARG_LONG,
+                    MAP_SIZE_ARG_VALUE_NAME,
+                    MAP_SIZE_ARG_HELP,
+                )
+                .default_value(MAP_SIZE_ARG_DEFAULT),
+            )
+            .arg(numeric_arg(
+                SEED_ARG_NAME,
+                SEED_ARG_LONG,
+                SEED_ARG_VALUE_NAME,
+                SEED_ARG_HELP,
+            ))
+            .get_matches();
+
+        let fault_interval: u64 = parse_arg(&arg_matches, FAULT_INTERVAL_ARG_NAME).unwrap();
+        assert!(fault_interval > 0, "Expected a positive fault interval");
+        let accounts: u8 = parse_arg(&arg_matches, ACCOUNTS_ARG_NAME).unwrap();
+        assert!(accounts > 0, "Expected at least one account");
+        let map_size_mib: usize = parse_arg(&arg_matches, MAP_SIZE_ARG_NAME).unwrap();
+        Args {
+            data_dir: profiling::data_dir(&arg_matches),
+            blocks: parse_arg(&arg_matches, BLOCKS_ARG_NAME).unwrap(),
+            accounts,
+            fault_interval,
+            map_size: map_size_mib * MIB,
+            seed: parse_arg(&arg_matches, SEED_ARG_NAME).unwrap_or_else(rand::random),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Fault {
+    CrashBeforeCommit,
+    CrashAfterCommit,
+    MapResize,
+}
+
+impl Fault {
+    fn random(rng: &mut StdRng) -> Self {
+        match rng.gen_range(0, 3) {
+            0 => Fault::CrashBeforeCommit,
+            1 => Fault::CrashAfterCommit,
+            _ => Fault::MapResize,
+        }
+    }
+}
+
+/// Every soak account is funded at genesis, so that they can pay for deploys for a long time.
+fn genesis_config(accounts: &[PublicKey]) -> GenesisConfig {
+    let genesis_accounts = accounts
+        .iter()
+        .map(|public_key| {
+            GenesisAccount::new(
+                *public_key,
+                Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+                Motes::new(U512::zero()),
+            )
+        })
+        .collect();
+    GenesisConfig::new(
+        DEFAULT_CHAIN_NAME.to_string(),
+        DEFAULT_GENESIS_TIMESTAMP,
+        *DEFAULT_PROTOCOL_VERSION,
+        utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+        genesis_accounts,
+        *DEFAULT_WASM_COSTS,
+        DEFAULT_UNBONDING_DELAY,
+        DEFAULT_SLASH_PERCENTAGE,
+    )
+}
+
+/// Generates the deploys of a block of transfers between random accounts.
+fn random_deploys(rng: &mut StdRng, accounts: &[PublicKey]) -> Vec<DeployItem> {
+    let deploy_count = rng.gen_range(1, MAX_DEPLOYS_PER_BLOCK + 1);
+    (0..deploy_count)
+        .map(|_| {
+            let sender = accounts[rng.gen_range(0, accounts.len())];
+            let recipient = accounts[rng.gen_range(0, accounts.len())];
+            let amount = rng.gen_range(1, MAX_TRANSFER_AMOUNT);
+            let mut deploy_hash = [0u8; 32];
+            rng.fill_bytes(&mut deploy_hash);
+
+            DeployItemBuilder::new()
+                .with_address(sender.value())
+                .with_deploy_hash(deploy_hash)
+                .with_session_code(TRANSFER_TO_ACCOUNT_WASM, (recipient, amount))
+                .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+                .with_authorization_keys(&[sender])
+                .build()
+        })
+        .collect()
+}
+
+fn exec_request(deploys: &[DeployItem], block_time: u64) -> ExecuteRequest {
+    deploys
+        .iter()
+        .cloned()
+        .fold(
+            ExecuteRequestBuilder::new().with_block_time(block_time),
+            ExecuteRequestBuilder::push_deploy,
+        )
+        .build()
+}
+
+/// Simulates a crash by dropping the engine state, and reopens the LMDBs at the last committed
+/// post-state hash.
+fn restart(builder: LmdbWasmTestBuilder, args: &Args, map_size: usize) -> LmdbWasmTestBuilder {
+    let post_state_hash = builder.get_post_state_hash();
+    drop(builder);
+    LmdbWasmTestBuilder::open_with_map_size(
+        &args.data_dir,
+        EngineConfig::new(),
+        post_state_hash,
+        map_size,
+    )
+}
+
+/// Checks that the LMDB-backed state has the same post-state hash as the in-memory one, and that
+/// the accounts can be read from it.
+fn verify(
+    block: u64,
+    lmdb: &LmdbWasmTestBuilder,
+    in_memory: &InMemoryWasmTestBuilder,
+    accounts: &[PublicKey],
+) {
+    let post_state_hash = lmdb.get_post_state_hash();
+    assert_eq!(
+        post_state_hash,
+        in_memory.get_post_state_hash(),
+        "post-state hash diverged after block {}",
+        block
+    );
+    for public_key in accounts {
+        let key = Key::Account(public_key.value());
+        assert_eq!(
+            lmdb.query(None, key, &[]),
+            in_memory.query(None, key, &[]),
+            "account {} diverged after block {}",
+            public_key,
+            block
+        );
+    }
+}
+
+fn main() {
+    let args = Args::new();
+    println!("seed: {}", args.seed);
+    let mut rng = StdRng::seed_from_u64(args.seed);
+
+    let accounts: Vec<PublicKey> = (1..=args.accounts)
+        .map(|i| PublicKey::new([i; 32]))
+        .collect();
+    let genesis_config = genesis_config(&accounts);
+
+    let mut map_size = args.map_size;
+    let mut lmdb = LmdbWasmTestBuilder::new_with_config_and_map_size(
+        &args.data_dir,
+        EngineConfig::new(),
+        map_size,
+    );
+    lmdb.run_genesis(&genesis_config);
+    let mut in_memory = InMemoryWasmTestBuilder::default();
+    in_memory.run_genesis(&genesis_config);
+    verify(0, &lmdb, &in_memory, &accounts);
+
+    let mut faults = 0;
+    for block in 1..=args.blocks {
+        let deploys = random_deploys(&mut rng, &accounts);
+        let fault = if block % args.fault_interval == 0 {
+            Some(Fault::random(&mut rng))
+        } else {
+            None
+        };
+
+        match fault {
+            Some(Fault::CrashBeforeCommit) => {
+                lmdb.exec(exec_request(&deploys, block));
+                // The effects are lost, so the block is executed again after the restart
+                lmdb = restart(lmdb, &args, map_size);
+                lmdb.exec(exec_request(&deploys, block)).commit();
+            }
+            Some(Fault::CrashAfterCommit) => {
+                lmdb.exec(exec_request(&deploys, block)).commit();
+                lmdb = restart(lmdb, &args, map_size);
+            }
+            Some(Fault::MapResize) => {
+                map_size += map_size / 16;
+                lmdb = restart(lmdb, &args, map_size);
+                lmdb.exec(exec_request(&deploys, block)).commit();
+            }
+            None => {
+                lmdb.exec(exec_request(&deploys, block)).commit();
+            }
+        }
+        in_memory.exec(exec_request(&deploys, block)).commit();
+
+        verify(block, &lmdb, &in_memory, &accounts);
+        if let Some(fault) = fault {
+            faults += 1;
+            println!("block {}: recovered from {:?}", block, fault);
+        }
+    }
+
+    println!(
+        "executed {} blocks with {} faults; post-state hash: {}",
+        args.blocks,
+        faults,
+        base16::encode_lower(&lmdb.get_post_state_hash())
+    );
+}
@@ -0,0 +1,356 @@
+//! Drives a full block lifecycle through the `ExecutionEngineService` implementation, using the
+//! protobuf requests and responses a node exchanges with the engine, so that the mappings and
+//! glue of the comm layer are exercised end to end.
+//!
+//! Only compiled with the `comm-integration` feature.
+
+use std::convert::TryFrom;
+
+use grpc::RequestOptions;
+
+use engine_core::engine_state::{
+    deploy_item::DeployItem, execute_request::ExecuteRequest, genesis::GenesisAccount,
+    EngineConfig, EngineState,
+};
+use engine_grpc_server::engine_server::{
+    ipc::{
+        self, CommitRequest, DeployResult, ListProtocolVersionsRequest, QueryRequest,
+        UpgradeRequest,
+    },
+    ipc_grpc::ExecutionEngineService,
+    transforms::TransformEntry,
+};
+use engine_shared::{motes::Motes, stored_value::StoredValue};
+use engine_storage::{global_state::in_memory::InMemoryGlobalState, protocol_data::MINT_NAME};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, UpgradeRequestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_PROTOCOL_VERSION, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use types::{
+    account::{PublicKey, PurseId},
+    bytesrepr::{self, FromBytes},
+    CLTyped, CLValue, Key, ProtocolVersion, URef, U512,
+};
+
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const ACCOUNT_2_ADDR: [u8; 32] = [2u8; 32];
+const ACCOUNT_3_ADDR: [u8; 32] = [3u8; 32];
+const ACCOUNT_4_ADDR: [u8; 32] = [4u8; 32];
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+const HASH_LENGTH: usize = 32;
+
+type Service = EngineState<InMemoryGlobalState>;
+
+fn new_service() -> Service {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    EngineState::new(
+        global_state,
+        EngineConfig::new().with_unsigned_deploys(true),
+    )
+}
+
+fn genesis_config_proto() -> ipc::ChainSpec_GenesisConfig {
+    let accounts = [DEFAULT_ACCOUNT_ADDR, ACCOUNT_1_ADDR, ACCOUNT_2_ADDR]
+        .iter()
+        .map(|addr| {
+            GenesisAccount::new(
+                PublicKey::new(*addr),
+                Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+                Motes::zero(),
+            )
+        })
+        .collect();
+    utils::create_genesis_config(accounts).into()
+}
+
+/// Runs genesis, and returns the genesis post-state hash and the number of transforms made.
+fn run_genesis(service: &Service) -> (Vec<u8>, usize) {
+    let mut response = service
+        .run_genesis(RequestOptions::new(), genesis_config_proto())
+        .wait_drop_metadata()
+        .expect("should get genesis response");
+    assert!(
+        response.has_success(),
+        "genesis failed: {:?}",
+        response.get_failed_deploy()
+    );
+    let mut success = response.take_success();
+    (
+        success.take_poststate_hash(),
+        success.get_effect().get_transform_map().len(),
+    )
+}
+
+fn deploy(
+    deploy_hash: [u8; 32],
+    addr: [u8; 32],
+    make_session: impl FnOnce(DeployItemBuilder) -> DeployItemBuilder,
+) -> DeployItem {
+    let builder = DeployItemBuilder::new()
+        .with_address(addr)
+        .with_deploy_hash(deploy_hash)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(addr)]);
+    make_session(builder).build()
+}
+
+fn execute(service: &Service, exec_request: ExecuteRequest) -> Vec<DeployResult> {
+    let mut response = service
+        .execute(RequestOptions::new(), exec_request.into())
+        .wait_drop_metadata()
+        .expect("should get execute response");
+    assert!(
+        response.has_success(),
+        "missing parent: {:?}",
+        response.get_missing_parent()
+    );
+    response.take_success().take_deploy_results().into_vec()
+}
+
+fn effects(deploy_result: &DeployResult) -> Vec<TransformEntry> {
+    assert!(
+        deploy_result.has_execution_result(),
+        "precondition failure: {:?}",
+        deploy_result.get_precondition_failure()
+    );
+    deploy_result
+        .get_execution_result()
+        .get_effects()
+        .get_transform_map()
+        .to_vec()
+}
+
+fn commit(
+    service: &Service,
+    pre_state_hash: &[u8],
+    effects: Vec<TransformEntry>,
+    protocol_version: ProtocolVersion,
+) -> Vec<u8> {
+    let mut commit_request = CommitRequest::new();
+    commit_request.set_prestate_hash(pre_state_hash.to_vec());
+    commit_request.set_effects(effects.into());
+    commit_request.set_protocol_version(protocol_version.into());
+    let mut response = service
+        .commit(RequestOptions::new(), commit_request)
+        .wait_drop_metadata()
+        .expect("should get commit response");
+    assert!(response.has_success(), "commit failed: {:?}", response);
+    response.take_success().take_poststate_hash()
+}
+
+fn query(service: &Service, state_hash: &[u8], key: Key) -> Result<StoredValue, String> {
+    let mut query_request = QueryRequest::new();
+    query_request.set_state_hash(state_hash.to_vec());
+    query_request.set_base_key(key.into());
+    let mut response = service
+        .query(RequestOptions::new(), query_request)
+        .wait_drop_metadata()
+        .expect("should get query response");
+    if response.has_failure() {
+        return Err(response.take_failure());
+    }
+    bytesrepr::deserialize(response.take_success()).map_err(|error| error.to_string())
+}
+
+fn query_cl_value<T: CLTyped + FromBytes>(service: &Service, state_hash: &[u8], key: Key) -> T {
+    let cl_value = query(service, state_hash, key)
+        .and_then(|value| CLValue::try_from(value).map_err(|error| format!("{:?}", error)))
+        .expect("should query CLValue");
+    cl_value.into_t().expect("should convert CLValue")
+}
+
+/// Returns the protocol versions known to the engine, together with the mint of each.
+fn list_protocol_versions(service: &Service) -> Vec<(ProtocolVersion, URef)> {
+    let mut response = service
+        .list_protocol_versions(RequestOptions::new(), ListProtocolVersionsRequest::new())
+        .wait_drop_metadata()
+        .expect("should get protocol versions");
+    assert!(response.has_success(), "{}", response.get_failure());
+    response
+        .take_success()
+        .take_protocol_versions()
+        .into_iter()
+        .map(|mut info| {
+            let mint = info
+                .take_system_contracts()
+                .into_iter()
+                .find(|system_contract| system_contract.get_name() == MINT_NAME)
+                .expect("should have mint");
+            (
+                info.take_protocol_version().into(),
+                URef::try_from(mint.get_uref().clone()).expect("should map mint uref"),
+            )
+        })
+        .collect()
+}
+
+fn get_balance(service: &Service, state_hash: &[u8], mint: URef, addr: [u8; 32]) -> U512 {
+    let purse_id: PurseId = match query(service, state_hash, Key::Account(addr)) {
+        Ok(StoredValue::Account(account)) => account.purse_id(),
+        other => panic!("unexpected query result: {:?}", other),
+    };
+    let balance_key: Key = query_cl_value(
+        service,
+        state_hash,
+        types::purse_balance_key(mint, purse_id),
+    );
+    query_cl_value(service, state_hash, balance_key)
+}
+
+fn upgrade(service: &Service, upgrade_request: UpgradeRequest) -> Vec<u8> {
+    let mut response = service
+        .upgrade(RequestOptions::new(), upgrade_request)
+        .wait_drop_metadata()
+        .expect("should get upgrade response");
+    assert!(
+        response.has_success(),
+        "upgrade failed: {:?}",
+        response.get_failed_deploy()
+    );
+    response.take_success().take_post_state_hash()
+}
+
+#[ignore]
+#[test]
+fn should_run_block_lifecycle_through_service() {
+    let amount = U512::from(TRANSFER_AMOUNT);
+    let service = new_service();
+
+    // Genesis is deterministic
+    let (genesis_hash, genesis_transforms) = run_genesis(&service);
+    assert_eq!(genesis_hash.len(), HASH_LENGTH);
+    assert!(genesis_transforms > 0, "genesis should have effects");
+    assert_eq!(run_genesis(&new_service()).0, genesis_hash);
+
+    let protocol_versions = list_protocol_versions(&service);
+    assert_eq!(protocol_versions.len(), 1);
+    let (protocol_version, mint) = protocol_versions[0];
+    assert_eq!(protocol_version, *DEFAULT_PROTOCOL_VERSION);
+
+    // A block of independent deploys from distinct accounts, executed on the same pre-state
+    let block_1 = ExecuteRequestBuilder::new()
+        .with_pre_state_hash(&genesis_hash)
+        .with_protocol_version(protocol_version)
+        .push_deploy(deploy([1; 32], DEFAULT_ACCOUNT_ADDR, |builder| {
+            builder.with_session_code(
+                CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+                (PublicKey::new(ACCOUNT_3_ADDR), amount),
+            )
+        }))
+        .push_deploy(deploy([2; 32], ACCOUNT_1_ADDR, |builder| {
+            builder.with_session_transfer(ACCOUNT_4_ADDR, amount)
+        }))
+        .push_deploy(deploy([3; 32], ACCOUNT_2_ADDR, |builder| {
+            builder.with_session_code(
+                CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+                (
+                    PublicKey::new(ACCOUNT_3_ADDR),
+                    U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE),
+                ),
+            )
+        }))
+        .build();
+    let deploy_results = execute(&service, block_1);
+    assert_eq!(deploy_results.len(), 3);
+    for deploy_result in &deploy_results[..2] {
+        let execution_result = deploy_result.get_execution_result();
+        assert!(
+            !execution_result.has_error(),
+            "unexpected error: {:?}",
+            execution_result.get_error()
+        );
+    }
+    let failed_result = deploy_results[2].get_execution_result();
+    assert!(
+        failed_result.get_error().has_exec_error(),
+        "the overdrawing transfer should fail"
+    );
+    assert_ne!(
+        failed_result.get_cost().get_value(),
+        "0",
+        "a failed deploy should still be charged"
+    );
+
+    // Committing the block at once is the same as committing its deploys one by one
+    let block_effects: Vec<TransformEntry> = deploy_results.iter().flat_map(effects).collect();
+    let post_state_hash = commit(&service, &genesis_hash, block_effects, protocol_version);
+    assert_eq!(post_state_hash.len(), HASH_LENGTH);
+    assert_ne!(post_state_hash, genesis_hash);
+    let sequential_post_state_hash =
+        deploy_results
+            .iter()
+            .fold(genesis_hash.clone(), |state_hash, deploy_result| {
+                commit(
+                    &service,
+                    &state_hash,
+                    effects(deploy_result),
+                    protocol_version,
+                )
+            });
+    assert_eq!(sequential_post_state_hash, post_state_hash);
+
+    assert_eq!(
+        get_balance(&service, &post_state_hash, mint, ACCOUNT_3_ADDR),
+        amount
+    );
+    assert_eq!(
+        get_balance(&service, &post_state_hash, mint, ACCOUNT_4_ADDR),
+        amount
+    );
+    assert!(
+        get_balance(&service, &post_state_hash, mint, ACCOUNT_2_ADDR)
+            < U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE),
+        "the failed deploy should have been paid for"
+    );
+    // The pre-state is untouched
+    assert!(query(&service, &genesis_hash, Key::Account(ACCOUNT_3_ADDR)).is_err());
+
+    // Upgrade, then execute on the upgraded state with the new protocol version
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+    let upgrade_request = UpgradeRequestBuilder::new()
+        .with_pre_state_hash(&post_state_hash)
+        .with_current_protocol_version(protocol_version)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(1)
+        .build();
+    let upgraded_state_hash = upgrade(&service, upgrade_request);
+    let protocol_versions = list_protocol_versions(&service);
+    assert_eq!(
+        protocol_versions
+            .iter()
+            .map(|(protocol_version, _)| *protocol_version)
+            .collect::<Vec<_>>(),
+        vec![protocol_version, new_protocol_version]
+    );
+
+    let block_2 = ExecuteRequestBuilder::new()
+        .with_pre_state_hash(&upgraded_state_hash)
+        .with_protocol_version(new_protocol_version)
+        .push_deploy(deploy([4; 32], DEFAULT_ACCOUNT_ADDR, |builder| {
+            builder.with_session_transfer(ACCOUNT_3_ADDR, amount)
+        }))
+        .build();
+    let deploy_results = execute(&service, block_2);
+    assert_eq!(deploy_results.len(), 1);
+    assert!(!deploy_results[0].get_execution_result().has_error());
+    let final_state_hash = commit(
+        &service,
+        &upgraded_state_hash,
+        effects(&deploy_results[0]),
+        new_protocol_version,
+    );
+    assert_ne!(final_state_hash, upgraded_state_hash);
+
+    assert_eq!(
+        get_balance(&service, &final_state_hash, mint, ACCOUNT_3_ADDR),
+        amount * 2
+    );
+    assert_eq!(
+        get_balance(&service, &upgraded_state_hash, mint, ACCOUNT_3_ADDR),
+        amount
+    );
+}
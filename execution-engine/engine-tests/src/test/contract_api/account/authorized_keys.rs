@@ -1,4 +1,4 @@
-use engine_core::{engine_state, execution};
+use engine_core::engine_state;
 use engine_test_support::{
     internal::{
         DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
@@ -32,7 +32,7 @@ fn should_deploy_with_authorized_identity_key() {
 #[test]
 fn should_raise_auth_failure_with_invalid_key() {
     // tests that authorized keys that does not belong to account raises
-    // AuthorizationError
+    // UnassociatedKeys
     let key_1 = [254; 32];
     assert_ne!(DEFAULT_ACCOUNT_ADDR, key_1);
 
@@ -70,7 +70,10 @@ fn should_raise_auth_failure_with_invalid_key() {
 
     assert_eq!(
         message,
-        format!("{}", engine_state::Error::AuthorizationError)
+        format!(
+            "{}",
+            engine_state::Error::UnassociatedKeys(vec![PublicKey::new(key_1)])
+        )
     )
 }
 
@@ -78,7 +81,7 @@ fn should_raise_auth_failure_with_invalid_key() {
 #[test]
 fn should_raise_auth_failure_with_invalid_keys() {
     // tests that authorized keys that does not belong to account raises
-    // AuthorizationError
+    // UnassociatedKeys
     let key_1 = [254; 32];
     let key_2 = [253; 32];
     let key_3 = [252; 32];
@@ -120,7 +123,14 @@ fn should_raise_auth_failure_with_invalid_keys() {
 
     assert_eq!(
         message,
-        format!("{}", engine_state::Error::AuthorizationError)
+        format!(
+            "{}",
+            engine_state::Error::UnassociatedKeys(vec![
+                PublicKey::new(key_3),
+                PublicKey::new(key_2),
+                PublicKey::new(key_1)
+            ])
+        )
     )
 }
 
@@ -208,10 +218,16 @@ fn should_raise_deploy_authorization_failure() {
 
         assert!(deploy_result.has_precondition_failure());
         let message = format!("{}", deploy_result.error().unwrap());
-        assert!(message.contains(&format!(
-            "{}",
-            execution::Error::DeploymentAuthorizationFailure
-        )))
+        assert_eq!(
+            message,
+            format!(
+                "{}",
+                engine_state::Error::DeploymentThresholdNotMet {
+                    total_weight: 1,
+                    threshold: 3
+                }
+            )
+        )
     }
     let exec_request_6 = {
         let deploy = DeployItemBuilder::new()
@@ -260,10 +276,16 @@ fn should_raise_deploy_authorization_failure() {
 
         assert!(deploy_result.has_precondition_failure());
         let message = format!("{}", deploy_result.error().unwrap());
-        assert!(message.contains(&format!(
-            "{}",
-            execution::Error::DeploymentAuthorizationFailure
-        )))
+        assert_eq!(
+            message,
+            format!(
+                "{}",
+                engine_state::Error::DeploymentThresholdNotMet {
+                    total_weight: 1,
+                    threshold: 5
+                }
+            )
+        )
     }
 
     let exec_request_8 = {
@@ -417,8 +439,14 @@ fn should_not_authorize_deploy_with_duplicated_keys() {
         deploy_result
     );
     let message = format!("{}", deploy_result.error().unwrap());
-    assert!(message.contains(&format!(
-        "{}",
-        execution::Error::DeploymentAuthorizationFailure
-    )))
+    assert_eq!(
+        message,
+        format!(
+            "{}",
+            engine_state::Error::DeploymentThresholdNotMet {
+                total_weight: 2,
+                threshold: 3
+            }
+        )
+    )
 }
@@ -2,3 +2,5 @@ mod associated_keys;
 mod authorized_keys;
 mod key_management_thresholds;
 mod named_keys;
+mod tagged_public_keys;
+mod update_associated_keys;
@@ -0,0 +1,89 @@
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::account::{PublicKey, TaggedPublicKey, Weight};
+
+const CONTRACT_TAGGED_PUBLIC_KEY: &str = "tagged_public_key.wasm";
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+
+fn secp256k1_public_key() -> TaggedPublicKey {
+    let mut bytes = [7u8; 33];
+    bytes[0] = 2;
+    TaggedPublicKey::secp256k1(&bytes).expect("should create public key")
+}
+
+#[ignore]
+#[test]
+fn should_create_and_associate_secp256k1_account() {
+    let tagged_public_key = secp256k1_public_key();
+    let public_key = tagged_public_key.to_public_key();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TAGGED_PUBLIC_KEY,
+        (tagged_public_key, *DEFAULT_PAYMENT * 10),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    // The account is created at the address derived from the key
+    let account = builder
+        .get_account(public_key.value())
+        .expect("should create account");
+    assert_eq!(
+        account.get_associated_key_weight(public_key),
+        Some(&Weight::new(1))
+    );
+
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get default account");
+    assert_eq!(
+        default_account.get_associated_key_weight(public_key),
+        Some(&Weight::new(1))
+    );
+
+    // The new account can run deploys authorized by its derived key
+    let deploy = DeployItemBuilder::new()
+        .with_address(public_key.value())
+        .with_deploy_hash([2; 32])
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[public_key])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_keep_address_of_ed25519_account() {
+    let tagged_public_key = TaggedPublicKey::ed25519([42; 32]);
+    assert_eq!(tagged_public_key.to_public_key(), PublicKey::new([42; 32]));
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TAGGED_PUBLIC_KEY,
+        (tagged_public_key, *DEFAULT_PAYMENT * 10),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    assert!(builder.get_account([42; 32]).is_some());
+}
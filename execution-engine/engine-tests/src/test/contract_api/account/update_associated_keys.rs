@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+
+use engine_core::engine_state::execute_request::ExecuteRequest;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{KeyUpdate, PublicKey, UpdateKeysFailure, Weight},
+    ApiError,
+};
+
+const CONTRACT_UPDATE_ASSOCIATED_KEYS: &str = "update_associated_keys.wasm";
+
+fn key(i: u8) -> PublicKey {
+    PublicKey::new([i; 32])
+}
+
+fn associated_keys(keys: &[PublicKey]) -> BTreeMap<PublicKey, Weight> {
+    keys.iter().map(|key| (*key, Weight::new(1))).collect()
+}
+
+fn update_request(
+    authorization_keys: &[PublicKey],
+    deploy_hash: [u8; 32],
+    updates: Vec<KeyUpdate>,
+    key_management_threshold: Option<Weight>,
+    expected_associated_keys: BTreeMap<PublicKey, Weight>,
+) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_session_code(
+            CONTRACT_UPDATE_ASSOCIATED_KEYS,
+            (updates, key_management_threshold, expected_associated_keys),
+        )
+        .with_deploy_hash(deploy_hash)
+        .with_authorization_keys(authorization_keys)
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy).build()
+}
+
+fn assert_failed_with(builder: &InMemoryWasmTestBuilder, failure: UpdateKeysFailure) {
+    let error_message = builder
+        .exec_error_message(builder.get_exec_responses_count() - 1)
+        .expect("should have error message");
+    let code = u32::from(ApiError::from(failure));
+    assert!(
+        error_message.contains(&format!("Revert({})", code)),
+        "unexpected error: {}",
+        error_message
+    );
+}
+
+fn assert_associated_keys(builder: &InMemoryWasmTestBuilder, expected: &[PublicKey]) {
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let actual: BTreeMap<PublicKey, Weight> = account
+        .get_associated_keys()
+        .map(|(key, weight)| (*key, *weight))
+        .collect();
+    assert_eq!(actual, associated_keys(expected));
+}
+
+#[ignore]
+#[test]
+fn should_rotate_every_key_of_a_multi_sig_account_at_once() {
+    let identity_key = PublicKey::new(DEFAULT_ACCOUNT_ADDR);
+    let old_keys = vec![identity_key, key(1), key(2), key(3), key(4)];
+    let new_keys = vec![identity_key, key(5), key(6), key(7), key(8)];
+
+    // Turns the account into a 5-of-5 account for key management
+    let setup_request = update_request(
+        &[identity_key],
+        [1; 32],
+        (1..=4)
+            .map(|i| KeyUpdate::Add(key(i), Weight::new(1)))
+            .collect(),
+        Some(Weight::new(5)),
+        associated_keys(&old_keys),
+    );
+
+    // Removing any single key would leave the account unable to manage its keys
+    let remove_one_request = update_request(
+        &old_keys,
+        [2; 32],
+        vec![KeyUpdate::Remove(key(1))],
+        None,
+        associated_keys(&old_keys),
+    );
+
+    let rotate_request = update_request(
+        &old_keys,
+        [3; 32],
+        (1..=4)
+            .map(|i| KeyUpdate::Remove(key(i)))
+            .chain((5..=8).map(|i| KeyUpdate::Add(key(i), Weight::new(1))))
+            .collect(),
+        None,
+        associated_keys(&new_keys),
+    );
+
+    // The new keys are able to manage the account's keys
+    let update_request = update_request(
+        &new_keys,
+        [4; 32],
+        vec![KeyUpdate::Update(key(5), Weight::new(2))],
+        None,
+        {
+            let mut expected = associated_keys(&new_keys);
+            expected.insert(key(5), Weight::new(2));
+            expected
+        },
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(setup_request)
+        .expect_success()
+        .commit()
+        .exec(remove_one_request)
+        .commit();
+    assert_failed_with(&builder, UpdateKeysFailure::ThresholdViolation);
+    assert_associated_keys(&builder, &old_keys);
+
+    builder
+        .exec(rotate_request)
+        .expect_success()
+        .commit()
+        .exec(update_request)
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_not_apply_any_update_of_a_failing_batch() {
+    let identity_key = PublicKey::new(DEFAULT_ACCOUNT_ADDR);
+
+    let exec_request = update_request(
+        &[identity_key],
+        [1; 32],
+        vec![
+            KeyUpdate::Add(key(1), Weight::new(1)),
+            KeyUpdate::Update(key(2), Weight::new(1)),
+        ],
+        None,
+        associated_keys(&[identity_key, key(1)]),
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit();
+    assert_failed_with(&builder, UpdateKeysFailure::MissingKey);
+    assert_associated_keys(&builder, &[identity_key]);
+}
+
+#[ignore]
+#[test]
+fn should_not_update_associated_keys_without_key_management_weight() {
+    let identity_key = PublicKey::new(DEFAULT_ACCOUNT_ADDR);
+
+    let setup_request = update_request(
+        &[identity_key],
+        [1; 32],
+        vec![KeyUpdate::Add(key(1), Weight::new(1))],
+        Some(Weight::new(2)),
+        associated_keys(&[identity_key, key(1)]),
+    );
+    let exec_request = update_request(
+        &[identity_key],
+        [2; 32],
+        vec![KeyUpdate::Remove(key(1))],
+        None,
+        associated_keys(&[identity_key]),
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(setup_request)
+        .expect_success()
+        .commit()
+        .exec(exec_request)
+        .commit();
+    assert_failed_with(&builder, UpdateKeysFailure::PermissionDenied);
+    assert_associated_keys(&builder, &[identity_key, key(1)]);
+}
@@ -0,0 +1,181 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, Key};
+
+const CONTRACT_CONTRACT_ACL: &str = "contract_acl.wasm";
+const CONTRACT_STANDARD_PAYMENT: &str = "standard_payment.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT_01: &str = "transfer_to_account_01.wasm";
+const PROTECTED_CONTRACT_NAME: &str = "acl_protected";
+const PROXY_CONTRACT_NAME: &str = "acl_proxy";
+const METHOD_STORE: &str = "store";
+const METHOD_UPDATE: &str = "update";
+const METHOD_CALL: &str = "call";
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+
+/// Funds account 1, and stores a contract which only the default account may call, along with a
+/// proxy contract which anyone may call.
+fn setup() -> (InMemoryWasmTestBuilder, Key) {
+    let transfer_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT_01,
+        (ACCOUNT_1_ADDR, *DEFAULT_PAYMENT * 10),
+    )
+    .build();
+    let store_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTRACT_ACL,
+        (METHOD_STORE, vec![PublicKey::new(DEFAULT_ACCOUNT_ADDR)]),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(transfer_request)
+        .expect_success()
+        .commit()
+        .exec(store_request)
+        .expect_success()
+        .commit();
+
+    let proxy_key = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account")
+        .named_keys()
+        .get(PROXY_CONTRACT_NAME)
+        .expect("should have proxy contract");
+
+    (builder, proxy_key)
+}
+
+fn call(builder: &mut InMemoryWasmTestBuilder, account_addr: [u8; 32], proxy_key: Key) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        account_addr,
+        CONTRACT_CONTRACT_ACL,
+        (METHOD_CALL, proxy_key),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn authorized_callers(builder: &InMemoryWasmTestBuilder) -> Option<Vec<PublicKey>> {
+    let result = builder.query(
+        None,
+        Key::Account(DEFAULT_ACCOUNT_ADDR),
+        &[PROTECTED_CONTRACT_NAME],
+    );
+    match result {
+        Ok(StoredValue::Contract(contract)) => contract.authorized_callers().map(<[_]>::to_vec),
+        other => panic!("unexpected query result: {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_store_contract_with_authorized_callers() {
+    let (builder, _) = setup();
+
+    assert_eq!(
+        authorized_callers(&builder),
+        Some(vec![PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+    );
+}
+
+#[ignore]
+#[test]
+fn should_allow_authorized_caller() {
+    let (mut builder, proxy_key) = setup();
+
+    call(&mut builder, DEFAULT_ACCOUNT_ADDR, proxy_key);
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_deny_unauthorized_caller() {
+    let (mut builder, proxy_key) = setup();
+
+    call(&mut builder, ACCOUNT_1_ADDR, proxy_key);
+    let error_message = builder
+        .exec_error_message(2)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("CallerNotAuthorized"),
+        "{}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_update_contract_acl() {
+    let (mut builder, proxy_key) = setup();
+
+    let authorized_callers_after_update = vec![
+        PublicKey::new(DEFAULT_ACCOUNT_ADDR),
+        PublicKey::new(ACCOUNT_1_ADDR),
+    ];
+    let update_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTRACT_ACL,
+        (METHOD_UPDATE, Some(authorized_callers_after_update.clone())),
+    )
+    .build();
+    builder.exec(update_request).expect_success().commit();
+    assert_eq!(
+        authorized_callers(&builder),
+        Some(authorized_callers_after_update)
+    );
+
+    call(&mut builder, ACCOUNT_1_ADDR, proxy_key);
+    builder.expect_success();
+
+    let update_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTRACT_ACL,
+        (METHOD_UPDATE, Option::<Vec<PublicKey>>::None),
+    )
+    .build();
+    builder.exec(update_request).expect_success().commit();
+    assert_eq!(authorized_callers(&builder), None);
+}
+
+#[ignore]
+#[test]
+fn should_deny_unauthorized_caller_calling_stored_contract_from_deploy() {
+    let (mut builder, _) = setup();
+
+    // The default account keeps the URef of the protected contract, but is no longer authorized
+    let update_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTRACT_ACL,
+        (METHOD_UPDATE, Some(vec![PublicKey::new(ACCOUNT_1_ADDR)])),
+    )
+    .build();
+    builder.exec(update_request).expect_success().commit();
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_deploy_hash([42; 32])
+        .with_stored_session_named_key(PROTECTED_CONTRACT_NAME, ())
+        .with_payment_code(CONTRACT_STANDARD_PAYMENT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request).commit();
+
+    let error_message = builder
+        .exec_error_message(3)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("CallerNotAuthorized"),
+        "{}",
+        error_message
+    );
+}
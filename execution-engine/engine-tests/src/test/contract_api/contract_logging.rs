@@ -0,0 +1,31 @@
+use engine_core::engine_state::EngineConfig;
+use engine_shared::gas::Gas;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_CONTRACT_LOGGING: &str = "contract_logging.wasm";
+
+fn run(engine_config: EngineConfig) -> Gas {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_CONTRACT_LOGGING, ())
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    builder.exec_costs(0)[0]
+}
+
+#[ignore]
+#[test]
+fn should_log_without_affecting_execution() {
+    let cost_without_logging = run(EngineConfig::new());
+    let cost_with_logging = run(EngineConfig::new().with_contract_logging(true));
+    assert_eq!(cost_without_logging, cost_with_logging);
+}
@@ -0,0 +1,448 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    access_key_name, account::PublicKey, contract_address, ContractVersion, Key,
+    CONTRACT_INITIAL_VERSION,
+};
+
+const CONTRACT_CONTRACT_PACKAGE: &str = "contract_package.wasm";
+const CONTRACT_CONTRACT_PACKAGE_UPGRADER: &str = "contract_package_upgrader.wasm";
+const CONTRACT_CONTRACT_PACKAGE_CALLER: &str = "contract_package_caller.wasm";
+const CONTRACT_CONTRACT_PACKAGE_ACCESS: &str = "contract_package_access.wasm";
+const CONTRACT_CONTRACT_VERSION_CALLER: &str = "contract_version_caller.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT_01: &str = "transfer_to_account_01.wasm";
+const STANDARD_PAYMENT_CONTRACT: &str = "standard_payment.wasm";
+const RESTRICTED_PACKAGE_NAME: &str = "restricted_package";
+const GROUP_ONLY_ENTRY_POINT: &str = "group_only";
+const ACCOUNT_ONLY_ENTRY_POINT: &str = "account_only";
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const PACKAGE_NAME: &str = "contract_package";
+const VERSION_NAME: &str = "contract_package_version";
+const RESULT_NAME: &str = "result";
+const GET_VERSION_ENTRY_POINT: &str = "get_version";
+const ECHO_ENTRY_POINT: &str = "echo";
+const ACTION_UPGRADE: &str = "upgrade";
+const ACTION_DISABLE: &str = "disable";
+
+fn setup() -> (InMemoryWasmTestBuilder, [u8; 32]) {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_CONTRACT_PACKAGE, ())
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let package_hash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account")
+        .named_keys()
+        .get(PACKAGE_NAME)
+        .and_then(Key::as_hash)
+        .expect("should have contract package hash");
+
+    (builder, package_hash)
+}
+
+fn upgrade(builder: &mut InMemoryWasmTestBuilder) -> ContractVersion {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTRACT_PACKAGE_UPGRADER,
+        (ACTION_UPGRADE,),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[VERSION_NAME])
+        .expect("should query new version");
+    match stored_value {
+        StoredValue::CLValue(cl_value) => cl_value.into_t().expect("should convert version"),
+        other => panic!("unexpected stored value: {:?}", other),
+    }
+}
+
+fn call(
+    builder: &mut InMemoryWasmTestBuilder,
+    package_hash: [u8; 32],
+    version: Option<ContractVersion>,
+    entry_point: &str,
+) -> Result<u32, String> {
+    call_as(
+        builder,
+        DEFAULT_ACCOUNT_ADDR,
+        package_hash,
+        version,
+        entry_point,
+    )
+}
+
+fn call_as(
+    builder: &mut InMemoryWasmTestBuilder,
+    account_addr: [u8; 32],
+    package_hash: [u8; 32],
+    version: Option<ContractVersion>,
+    entry_point: &str,
+) -> Result<u32, String> {
+    let exec_request = ExecuteRequestBuilder::standard(
+        account_addr,
+        CONTRACT_CONTRACT_PACKAGE_CALLER,
+        (package_hash, version, entry_point.to_string()),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    if builder.is_error() {
+        let error_message = builder
+            .exec_error_message(builder.get_exec_responses_count() - 1)
+            .expect("should have error message");
+        return Err(error_message);
+    }
+
+    let stored_value = builder
+        .query(None, Key::Account(account_addr), &[RESULT_NAME])
+        .expect("should query result");
+    match stored_value {
+        StoredValue::CLValue(cl_value) => Ok(cl_value.into_t().expect("should convert result")),
+        other => panic!("unexpected stored value: {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_store_contract_package() {
+    let (builder, package_hash) = setup();
+
+    let contract_package = builder
+        .query(None, Key::Hash(package_hash), &[])
+        .expect("should query contract package");
+    let contract_package = match contract_package {
+        StoredValue::ContractPackage(contract_package) => contract_package,
+        other => panic!("unexpected stored value: {:?}", other),
+    };
+
+    let (version, contract_header) = contract_package
+        .current_version()
+        .expect("should have an enabled version");
+    assert_eq!(version, CONTRACT_INITIAL_VERSION);
+    let entry_points: Vec<&str> = contract_header.entry_points().names().collect();
+    assert_eq!(
+        entry_points,
+        vec![ECHO_ENTRY_POINT, GET_VERSION_ENTRY_POINT]
+    );
+}
+
+#[ignore]
+#[test]
+fn should_store_contract_package_at_predicted_address() {
+    let deploy_hash = [42u8; 32];
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_CONTRACT_PACKAGE, ())
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+        .with_deploy_hash(deploy_hash)
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    // `new_contract` stores the contract first, then its package
+    let package_hash = contract_address(deploy_hash, 1);
+    let named_package_key = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account")
+        .named_keys()
+        .get(PACKAGE_NAME)
+        .cloned();
+    assert_eq!(named_package_key, Some(Key::Hash(package_hash)));
+
+    let contract_package = match builder.query(None, Key::Hash(package_hash), &[]) {
+        Ok(StoredValue::ContractPackage(contract_package)) => contract_package,
+        other => panic!("unexpected query result: {:?}", other),
+    };
+    let (_, contract_header) = contract_package
+        .current_version()
+        .expect("should have an enabled version");
+    assert_eq!(
+        contract_header.contract_hash(),
+        contract_address(deploy_hash, 0)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_record_install_manifest() {
+    let (builder, package_hash) = setup();
+
+    let manifest = builder
+        .get_install_manifest(DEFAULT_ACCOUNT_ADDR, PACKAGE_NAME)
+        .expect("should have install manifest");
+    assert_eq!(manifest.package_hash(), package_hash);
+    assert!(manifest.named_keys().is_empty());
+
+    let access_key = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account")
+        .named_keys()
+        .get(&access_key_name(PACKAGE_NAME))
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should have access key");
+    assert_eq!(manifest.access_key(), access_key);
+
+    assert_eq!(
+        builder.get_install_manifest(DEFAULT_ACCOUNT_ADDR, RESULT_NAME),
+        None
+    );
+}
+
+#[ignore]
+#[test]
+fn should_call_current_and_specific_versions() {
+    let (mut builder, package_hash) = setup();
+
+    assert_eq!(
+        call(&mut builder, package_hash, None, GET_VERSION_ENTRY_POINT),
+        Ok(1)
+    );
+
+    let version_2 = upgrade(&mut builder);
+    assert_eq!(version_2, CONTRACT_INITIAL_VERSION + 1);
+
+    assert_eq!(
+        call(&mut builder, package_hash, None, GET_VERSION_ENTRY_POINT),
+        Ok(2)
+    );
+    assert_eq!(
+        call(
+            &mut builder,
+            package_hash,
+            Some(CONTRACT_INITIAL_VERSION),
+            GET_VERSION_ENTRY_POINT
+        ),
+        Ok(1)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_call_disabled_version() {
+    let (mut builder, package_hash) = setup();
+    let version_2 = upgrade(&mut builder);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTRACT_PACKAGE_UPGRADER,
+        (ACTION_DISABLE, version_2),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    // The highest enabled version is called by default
+    assert_eq!(
+        call(&mut builder, package_hash, None, GET_VERSION_ENTRY_POINT),
+        Ok(1)
+    );
+
+    let error_message = call(
+        &mut builder,
+        package_hash,
+        Some(version_2),
+        GET_VERSION_ENTRY_POINT,
+    )
+    .expect_err("should fail to call disabled version");
+    assert!(
+        error_message.contains(&format!("InvalidContractVersion({})", version_2)),
+        "{}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_check_entry_point_and_args() {
+    let (mut builder, package_hash) = setup();
+
+    let error_message = call(&mut builder, package_hash, None, "missing")
+        .expect_err("should fail to call missing entry point");
+    assert!(
+        error_message.contains("EntryPointNotFound"),
+        "{}",
+        error_message
+    );
+
+    // `echo` expects a string argument, but the caller passes none
+    let error_message = call(&mut builder, package_hash, None, ECHO_ENTRY_POINT)
+        .expect_err("should fail to call entry point with invalid args");
+    assert!(
+        error_message.contains("InvalidEntryPointArgs"),
+        "{}",
+        error_message
+    );
+
+    // `echo` is not an entry point of the upgraded version
+    upgrade(&mut builder);
+    let error_message = call(&mut builder, package_hash, None, ECHO_ENTRY_POINT)
+        .expect_err("should fail to call entry point removed by upgrade");
+    assert!(
+        error_message.contains("EntryPointNotFound"),
+        "{}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_call_contract_version_directly() {
+    let (mut builder, package_hash) = setup();
+
+    let contract_package = match builder.query(None, Key::Hash(package_hash), &[]) {
+        Ok(StoredValue::ContractPackage(contract_package)) => contract_package,
+        other => panic!("unexpected query result: {:?}", other),
+    };
+    let (_, contract_header) = contract_package
+        .current_version()
+        .expect("should have an enabled version");
+    let contract_hash = contract_header.contract_hash();
+
+    match builder.query(None, Key::Hash(contract_hash), &[]) {
+        Ok(StoredValue::Contract(contract)) => {
+            assert_eq!(contract.package_hash(), Some(package_hash))
+        }
+        other => panic!("unexpected query result: {:?}", other),
+    }
+
+    // Called from a contract
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTRACT_VERSION_CALLER,
+        (contract_hash,),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+    let error_message = builder
+        .exec_error_message(builder.get_exec_responses_count() - 1)
+        .expect("should fail to call contract version from contract");
+    assert!(
+        error_message.contains("ContractVersionCalledDirectly"),
+        "{}",
+        error_message
+    );
+
+    // Called from a deploy
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_stored_session_hash(contract_hash.to_vec(), ())
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+        .with_deploy_hash([43u8; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request).commit();
+    let error_message = builder
+        .exec_error_message(builder.get_exec_responses_count() - 1)
+        .expect("should fail to call contract version from deploy");
+    assert!(
+        error_message.contains("ContractVersionCalledDirectly"),
+        "{}",
+        error_message
+    );
+}
+
+fn setup_restricted() -> (InMemoryWasmTestBuilder, [u8; 32]) {
+    let transfer_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT_01,
+        (ACCOUNT_1_ADDR, *DEFAULT_PAYMENT * 10),
+    )
+    .build();
+    let install_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_CONTRACT_PACKAGE_ACCESS, ())
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(transfer_request)
+        .expect_success()
+        .commit()
+        .exec(install_request)
+        .expect_success()
+        .commit();
+
+    let package_hash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account")
+        .named_keys()
+        .get(RESTRICTED_PACKAGE_NAME)
+        .and_then(Key::as_hash)
+        .expect("should have contract package hash");
+
+    (builder, package_hash)
+}
+
+#[ignore]
+#[test]
+fn should_restrict_entry_point_to_group() {
+    let (mut builder, package_hash) = setup_restricted();
+
+    // The installing account holds the group URef in its named keys
+    assert_eq!(
+        call(&mut builder, package_hash, None, GROUP_ONLY_ENTRY_POINT),
+        Ok(1)
+    );
+
+    let error_message = call_as(
+        &mut builder,
+        ACCOUNT_1_ADDR,
+        package_hash,
+        None,
+        GROUP_ONLY_ENTRY_POINT,
+    )
+    .expect_err("should not call entry point without group URef");
+    assert!(
+        error_message.contains("EntryPointAccessDenied"),
+        "{}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_restrict_entry_point_to_accounts() {
+    let (mut builder, package_hash) = setup_restricted();
+
+    assert_eq!(
+        call(&mut builder, package_hash, None, ACCOUNT_ONLY_ENTRY_POINT),
+        Ok(2)
+    );
+
+    let error_message = call_as(
+        &mut builder,
+        ACCOUNT_1_ADDR,
+        package_hash,
+        None,
+        ACCOUNT_ONLY_ENTRY_POINT,
+    )
+    .expect_err("should not call entry point from account not on allow-list");
+    assert!(
+        error_message.contains("EntryPointAccessDenied"),
+        "{}",
+        error_message
+    );
+}
@@ -0,0 +1,141 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{ContractVersion, Key, CONTRACT_INITIAL_VERSION};
+
+const CONTRACT_CONTRACT_PACKAGE_NAMESPACE: &str = "contract_package_namespace.wasm";
+const CONTRACT_CONTRACT_PACKAGE_CALLER: &str = "contract_package_caller.wasm";
+const PACKAGE_NAME: &str = "contract_package";
+const RESULT_NAME: &str = "result";
+const INCREMENT_ENTRY_POINT: &str = "increment";
+const INCREMENT_LOCAL_ENTRY_POINT: &str = "increment_local";
+const WRITE_FORGED_ENTRY_POINT: &str = "write_forged";
+const ACTION_INSTALL: &str = "install";
+const ACTION_UPGRADE: &str = "upgrade";
+
+fn run_action(builder: &mut InMemoryWasmTestBuilder, action: &str) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTRACT_PACKAGE_NAMESPACE,
+        (action,),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+fn setup() -> (InMemoryWasmTestBuilder, [u8; 32]) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+    run_action(&mut builder, ACTION_INSTALL);
+
+    let package_hash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account")
+        .named_keys()
+        .get(PACKAGE_NAME)
+        .and_then(Key::as_hash)
+        .expect("should have contract package hash");
+
+    (builder, package_hash)
+}
+
+fn call(
+    builder: &mut InMemoryWasmTestBuilder,
+    package_hash: [u8; 32],
+    version: Option<ContractVersion>,
+    entry_point: &str,
+) -> Result<u32, String> {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTRACT_PACKAGE_CALLER,
+        (package_hash, version, entry_point.to_string()),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    if builder.is_error() {
+        let error_message = builder
+            .exec_error_message(builder.get_exec_responses_count() - 1)
+            .expect("should have error message");
+        return Err(error_message);
+    }
+
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[RESULT_NAME])
+        .expect("should query result");
+    match stored_value {
+        StoredValue::CLValue(cl_value) => Ok(cl_value.into_t().expect("should convert result")),
+        other => panic!("unexpected stored value: {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_share_namespace_between_versions() {
+    let (mut builder, package_hash) = setup();
+
+    assert_eq!(
+        call(&mut builder, package_hash, None, INCREMENT_ENTRY_POINT),
+        Ok(1)
+    );
+
+    run_action(&mut builder, ACTION_UPGRADE);
+
+    assert_eq!(
+        call(&mut builder, package_hash, None, INCREMENT_ENTRY_POINT),
+        Ok(2)
+    );
+    assert_eq!(
+        call(
+            &mut builder,
+            package_hash,
+            Some(CONTRACT_INITIAL_VERSION),
+            INCREMENT_ENTRY_POINT
+        ),
+        Ok(3)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_share_local_state_between_versions() {
+    let (mut builder, package_hash) = setup();
+
+    assert_eq!(
+        call(
+            &mut builder,
+            package_hash,
+            None,
+            INCREMENT_LOCAL_ENTRY_POINT
+        ),
+        Ok(1)
+    );
+
+    run_action(&mut builder, ACTION_UPGRADE);
+
+    assert_eq!(
+        call(
+            &mut builder,
+            package_hash,
+            None,
+            INCREMENT_LOCAL_ENTRY_POINT
+        ),
+        Ok(1)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_write_to_forged_namespace() {
+    let (mut builder, package_hash) = setup();
+
+    let error_message = call(&mut builder, package_hash, None, WRITE_FORGED_ENTRY_POINT)
+        .expect_err("should fail to write to forged namespace");
+    assert!(
+        error_message.contains("UnknownNamespace"),
+        "{}",
+        error_message
+    );
+}
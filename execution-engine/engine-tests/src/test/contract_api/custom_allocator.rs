@@ -0,0 +1,38 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::Key;
+
+const CONTRACT_CUSTOM_ALLOCATOR: &str = "custom_allocator.wasm";
+const ALLOCATED_KEY: &str = "allocated";
+const EXPECTED_SUM: u64 = 4950;
+
+#[ignore]
+#[test]
+fn should_run_contract_with_custom_global_allocator() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_CUSTOM_ALLOCATOR, ())
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[ALLOCATED_KEY])
+        .expect("should query allocated value");
+
+    let sum = if let StoredValue::CLValue(cl_value) = stored_value {
+        cl_value.into_t::<u64>().expect("should be u64")
+    } else {
+        panic!("allocated value should be a CLValue");
+    };
+
+    assert_eq!(sum, EXPECTED_SUM);
+}
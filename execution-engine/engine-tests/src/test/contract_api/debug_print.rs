@@ -0,0 +1,51 @@
+use engine_core::engine_state::EngineConfig;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_DEBUG_PRINT: &str = "debug_print.wasm";
+const EXPECTED_STDOUT: [&str; 2] = ["Hello, world!", "phase is Session"];
+
+fn run(engine_config: EngineConfig, should_revert: bool) -> (bool, Vec<String>) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DEBUG_PRINT,
+        (should_revert,),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    let result = builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit()
+        .finish();
+
+    (result.builder().is_error(), result.stdout())
+}
+
+#[ignore]
+#[test]
+fn should_capture_printed_text() {
+    let (is_error, stdout) = run(EngineConfig::new().with_debug_print(true), false);
+    assert!(!is_error);
+    assert_eq!(stdout, EXPECTED_STDOUT);
+}
+
+#[ignore]
+#[test]
+fn should_capture_printed_text_of_reverted_deploy() {
+    let (is_error, stdout) = run(EngineConfig::new().with_debug_print(true), true);
+    assert!(is_error);
+    assert_eq!(stdout, EXPECTED_STDOUT);
+}
+
+#[ignore]
+#[test]
+fn should_print_without_capturing_text_by_default() {
+    // The contract reverts if printing fails, so it succeeds whether or not text is collected
+    let (is_error, stdout) = run(EngineConfig::new(), false);
+    assert!(!is_error);
+    assert!(stdout.is_empty());
+}
@@ -0,0 +1,84 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PurseId, AccessRights, Key, U512};
+
+const CONTRACT_ESCROW_PURSE: &str = "escrow_purse.wasm";
+const ESCROW_CONTRACT_NAME: &str = "escrow_contract";
+const ESCROW_PURSE_NAME: &str = "escrow";
+const DEPOSIT_PURSE_NAME: &str = "escrow_deposit";
+const ORIGINAL_PURSE_NAME: &str = "original_purse";
+const AMOUNT: u64 = 1_000_000;
+
+#[ignore]
+#[test]
+fn should_transfer_purse_ownership_to_contract() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_ESCROW_PURSE,
+        (U512::from(AMOUNT),),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account");
+    let named_key = |name: &str| {
+        account
+            .named_keys()
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| panic!("should have named key {}", name))
+    };
+    let original_purse = named_key(ORIGINAL_PURSE_NAME)
+        .as_uref()
+        .cloned()
+        .expect("should be uref");
+    let deposit_purse = named_key(DEPOSIT_PURSE_NAME)
+        .as_uref()
+        .cloned()
+        .expect("should be uref");
+    let contract_hash = named_key(ESCROW_CONTRACT_NAME)
+        .as_hash()
+        .expect("should be hash");
+
+    let contract = match builder.query(None, Key::Hash(contract_hash), &[]) {
+        Ok(StoredValue::Contract(contract)) => contract,
+        other => panic!("unexpected query result: {:?}", other),
+    };
+    let escrow_purse = contract
+        .named_keys()
+        .get(ESCROW_PURSE_NAME)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("contract should have escrow purse");
+
+    // The account only holds an add-only copy of the escrow purse
+    assert_eq!(
+        escrow_purse.access_rights(),
+        Some(AccessRights::READ_ADD_WRITE)
+    );
+    assert_eq!(deposit_purse, escrow_purse.into_add());
+    assert!(!account
+        .named_keys()
+        .values()
+        .any(|key| *key == Key::URef(escrow_purse)));
+
+    assert_eq!(
+        builder.get_purse_balance(PurseId::new(original_purse)),
+        U512::zero()
+    );
+    assert_eq!(
+        builder.get_purse_balance(PurseId::new(escrow_purse)),
+        U512::from(2 * AMOUNT)
+    );
+}
@@ -0,0 +1,85 @@
+use std::convert::TryInto;
+
+use engine_core::engine_state::balance::{BalanceRequest, BalanceResult};
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+        DEFAULT_PROTOCOL_VERSION,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+
+#[ignore]
+#[test]
+fn should_get_balance_of_account_main_purse() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, *DEFAULT_PAYMENT),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should get account");
+
+    let state_hash: Blake2bHash = builder
+        .get_post_state_hash()
+        .as_slice()
+        .try_into()
+        .expect("should convert post state hash");
+
+    let balance_request = BalanceRequest::new(
+        state_hash,
+        *DEFAULT_PROTOCOL_VERSION,
+        account_1.purse_id().value(),
+    );
+
+    let balance_result = builder
+        .get_engine_state()
+        .get_balance(CorrelationId::new(), balance_request)
+        .expect("should get balance");
+
+    let balance = match balance_result {
+        BalanceResult::Success(motes) => motes.value(),
+        other => panic!("unexpected balance result: {:?}", other),
+    };
+
+    assert_eq!(balance, *DEFAULT_PAYMENT);
+    assert_eq!(balance, builder.get_purse_balance(account_1.purse_id()));
+}
+
+#[ignore]
+#[test]
+fn should_return_root_not_found_for_unknown_state_hash() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let purse_id = builder.get_genesis_account().purse_id();
+
+    let balance_request = BalanceRequest::new(
+        [42u8; 32].into(),
+        *DEFAULT_PROTOCOL_VERSION,
+        purse_id.value(),
+    );
+
+    let balance_result = builder
+        .get_engine_state()
+        .get_balance(CorrelationId::new(), balance_request)
+        .expect("should get balance result");
+
+    assert_eq!(balance_result, BalanceResult::RootNotFound);
+}
@@ -0,0 +1,42 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_GET_BLOCK_INFO: &str = "get_block_info.wasm";
+
+#[ignore]
+#[test]
+fn should_run_get_block_info_contract() {
+    let era_id: u64 = 7;
+    let block_height: u64 = 1234;
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_BLOCK_INFO,
+        (era_id, block_height),
+    )
+    .with_era_id(era_id)
+    .with_block_height(block_height)
+    .build();
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_default_block_info_to_zero() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_BLOCK_INFO,
+        (0u64, 0u64),
+    )
+    .build();
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
@@ -0,0 +1,62 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, CallStackElement, Key};
+
+const CONTRACT_GET_CALL_STACK: &str = "get_call_stack.wasm";
+const CONTRACT_NAME: &str = "call_stack_contract";
+const SUBCALL_CONTRACT_NAME: &str = "call_stack_subcall_contract";
+const SESSION_CALL_STACK_NAME: &str = "session_call_stack";
+const CONTRACT_CALL_STACK_NAME: &str = "contract_call_stack";
+const SUBCALL_CALL_STACK_NAME: &str = "subcall_call_stack";
+
+fn get_call_stack(builder: &InMemoryWasmTestBuilder, name: &str) -> Vec<CallStackElement> {
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name])
+        .expect("should query call stack");
+    match stored_value {
+        StoredValue::CLValue(cl_value) => cl_value.into_t().expect("should convert call stack"),
+        other => panic!("unexpected stored value: {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_get_call_stack_of_session_and_stored_contracts() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_GET_CALL_STACK, ()).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let named_keys = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account")
+        .named_keys()
+        .clone();
+    let contract_key = named_keys[CONTRACT_NAME];
+    let subcall_contract_key = named_keys[SUBCALL_CONTRACT_NAME];
+
+    let session = CallStackElement::Account(PublicKey::new(DEFAULT_ACCOUNT_ADDR));
+    let contract = CallStackElement::Contract(contract_key);
+    let subcall_contract = CallStackElement::Contract(subcall_contract_key);
+
+    assert_eq!(
+        get_call_stack(&builder, SESSION_CALL_STACK_NAME),
+        vec![session.clone()]
+    );
+    assert_eq!(
+        get_call_stack(&builder, CONTRACT_CALL_STACK_NAME),
+        vec![session.clone(), contract.clone()]
+    );
+    assert_eq!(
+        get_call_stack(&builder, SUBCALL_CALL_STACK_NAME),
+        vec![session, subcall_contract, contract]
+    );
+}
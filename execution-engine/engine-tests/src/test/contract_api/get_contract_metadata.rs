@@ -0,0 +1,161 @@
+use std::convert::TryInto;
+
+use engine_core::engine_state::query::ContractMetadataResult;
+use engine_shared::{
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::contract_metadata;
+use types::{ContractMetadata, Key};
+
+const CONTRACT_DO_NOTHING_STORED: &str = "do_nothing_stored.wasm";
+const CONTRACT_GET_CONTRACT_METADATA: &str = "get_contract_metadata.wasm";
+const CONTRACT_STANDARD_PAYMENT: &str = "standard_payment.wasm";
+const DO_NOTHING_STORED_NAME: &str = "do_nothing_stored";
+const METADATA_KEY: &str = "metadata";
+const DESTINATION_HASH: &str = "hash";
+
+fn metadata() -> ContractMetadata {
+    ContractMetadata::new(
+        "do-nothing-stored".to_string(),
+        "0.1.0".to_string(),
+        "nightly-2020-01-08".to_string(),
+    )
+}
+
+fn do_nothing_stored_with_metadata() -> Vec<u8> {
+    let module_bytes = utils::read_wasm_file_bytes(CONTRACT_DO_NOTHING_STORED);
+    let module = engine_wasm_prep::deserialize(&module_bytes).expect("should deserialize");
+    let module =
+        contract_metadata::set_contract_metadata(module, &metadata()).expect("should set metadata");
+    parity_wasm::serialize(module).expect("should serialize module")
+}
+
+/// Stores `do_nothing_stored` at a hash and returns the builder along with the contract's key.
+fn store_contract(module_bytes: Vec<u8>) -> (InMemoryWasmTestBuilder, Key) {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_deploy_hash([1; 32])
+        .with_session_bytes(module_bytes, (DESTINATION_HASH,))
+        .with_payment_code(CONTRACT_STANDARD_PAYMENT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let contract_key = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account")
+        .named_keys()
+        .get(DO_NOTHING_STORED_NAME)
+        .expect("should have stored contract");
+
+    (builder, contract_key)
+}
+
+fn query_contract_metadata(
+    builder: &InMemoryWasmTestBuilder,
+    contract_key: Key,
+) -> ContractMetadataResult {
+    let state_hash: Blake2bHash = builder
+        .get_post_state_hash()
+        .as_slice()
+        .try_into()
+        .expect("should convert post state hash");
+
+    builder
+        .get_engine_state()
+        .get_contract_metadata(CorrelationId::new(), state_hash, contract_key)
+        .expect("should get contract metadata")
+}
+
+#[ignore]
+#[test]
+fn should_query_metadata_of_stored_contract() {
+    let (builder, contract_key) = store_contract(do_nothing_stored_with_metadata());
+
+    assert_eq!(
+        query_contract_metadata(&builder, contract_key),
+        ContractMetadataResult::Success(Some(metadata()))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_query_missing_metadata_of_stored_contract() {
+    let module_bytes = utils::read_wasm_file_bytes(CONTRACT_DO_NOTHING_STORED);
+    let (builder, contract_key) = store_contract(module_bytes);
+
+    assert_eq!(
+        query_contract_metadata(&builder, contract_key),
+        ContractMetadataResult::Success(None)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_query_metadata_of_non_contract() {
+    let module_bytes = utils::read_wasm_file_bytes(CONTRACT_DO_NOTHING_STORED);
+    let (builder, _) = store_contract(module_bytes);
+
+    assert_eq!(
+        query_contract_metadata(&builder, Key::Hash([255; 32])),
+        ContractMetadataResult::ContractNotFound
+    );
+}
+
+#[ignore]
+#[test]
+fn should_get_metadata_of_stored_contract_from_contract() {
+    let (mut builder, contract_key) = store_contract(do_nothing_stored_with_metadata());
+    let contract_hash = contract_key.as_hash().expect("should be stored at a hash");
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_CONTRACT_METADATA,
+        (contract_hash,),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[METADATA_KEY])
+        .expect("should query metadata");
+    let stored_metadata: ContractMetadata = match stored_value {
+        StoredValue::CLValue(cl_value) => cl_value.into_t().expect("should convert metadata"),
+        other => panic!("unexpected stored value: {:?}", other),
+    };
+
+    assert_eq!(stored_metadata, metadata());
+}
+
+#[ignore]
+#[test]
+fn should_revert_on_missing_metadata_from_contract() {
+    let module_bytes = utils::read_wasm_file_bytes(CONTRACT_DO_NOTHING_STORED);
+    let (mut builder, contract_key) = store_contract(module_bytes);
+    let contract_hash = contract_key.as_hash().expect("should be stored at a hash");
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_CONTRACT_METADATA,
+        (contract_hash,),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    assert!(builder.is_error());
+}
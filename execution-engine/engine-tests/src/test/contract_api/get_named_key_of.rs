@@ -0,0 +1,125 @@
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, ApiError, Key};
+
+const CONTRACT_GET_NAMED_KEY_OF: &str = "get_named_key_of.wasm";
+const CONTRACT_DO_NOTHING_STORED: &str = "do_nothing_stored.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT_01: &str = "transfer_to_account_01.wasm";
+const CONTRACT_NAME: &str = "do_nothing_stored";
+const DESTINATION_HASH: &str = "hash";
+const DESTINATION_UREF: &str = "uref";
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const UNKNOWN_ACCOUNT_ADDR: [u8; 32] = [42u8; 32];
+
+/// Stores a contract under the default account's named keys, and funds account 1.
+fn setup(destination: &str) -> (InMemoryWasmTestBuilder, Key) {
+    let transfer_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT_01,
+        (ACCOUNT_1_ADDR, *DEFAULT_PAYMENT * 10),
+    )
+    .build();
+    let store_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DO_NOTHING_STORED,
+        (destination,),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(transfer_request)
+        .expect_success()
+        .commit()
+        .exec(store_request)
+        .expect_success()
+        .commit();
+
+    let key = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account")
+        .named_keys()
+        .get(CONTRACT_NAME)
+        .expect("should have stored contract");
+
+    (builder, key)
+}
+
+fn get_named_key_of(
+    builder: &mut InMemoryWasmTestBuilder,
+    account_addr: [u8; 32],
+    name: &str,
+    expected: Option<Key>,
+) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        CONTRACT_GET_NAMED_KEY_OF,
+        (PublicKey::new(account_addr), name, expected),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_get_named_key_of_another_account() {
+    let (mut builder, key) = setup(DESTINATION_HASH);
+    assert!(key.as_hash().is_some());
+
+    get_named_key_of(&mut builder, DEFAULT_ACCOUNT_ADDR, CONTRACT_NAME, Some(key));
+}
+
+#[ignore]
+#[test]
+fn should_remove_access_rights_of_uref() {
+    let (mut builder, key) = setup(DESTINATION_UREF);
+    let uref = key
+        .into_uref()
+        .expect("should have stored contract at uref");
+    assert!(uref.access_rights().is_some());
+
+    let expected = Key::URef(uref.remove_access_rights());
+    get_named_key_of(
+        &mut builder,
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_NAME,
+        Some(expected),
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_get_missing_named_key_of_another_account() {
+    let (mut builder, _key) = setup(DESTINATION_HASH);
+
+    get_named_key_of(&mut builder, DEFAULT_ACCOUNT_ADDR, "missing", None);
+    get_named_key_of(&mut builder, UNKNOWN_ACCOUNT_ADDR, CONTRACT_NAME, None);
+}
+
+#[ignore]
+#[test]
+fn should_revert_on_unexpected_named_key() {
+    let (mut builder, key) = setup(DESTINATION_HASH);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        CONTRACT_GET_NAMED_KEY_OF,
+        (PublicKey::new(DEFAULT_ACCOUNT_ADDR), "missing", Some(key)),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    let error_message = builder
+        .exec_error_message(builder.get_exec_responses_count() - 1)
+        .expect("should have error message");
+    assert!(
+        error_message.contains(&format!("Revert({})", u32::from(ApiError::User(1)))),
+        "{}",
+        error_message
+    );
+}
@@ -0,0 +1,101 @@
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::account::PublicKey;
+
+const CONTRACT_NAMED_KEYS_MARKER: &str = "named_keys_marker.wasm";
+const MARKER: &str = "marker";
+const COMMAND_ACCOUNT: &str = "account";
+const COMMAND_CONTRACT: &str = "contract";
+const COMMAND_PAYMENT: &str = "payment";
+const COMMAND_SESSION_AFTER_PAYMENT: &str = "session_after_payment";
+
+#[ignore]
+#[test]
+fn should_see_marker_put_in_account_context() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_NAMED_KEYS_MARKER,
+        (COMMAND_ACCOUNT,),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+
+    assert!(account.named_keys().contains_key(MARKER));
+}
+
+#[ignore]
+#[test]
+fn should_see_marker_put_in_contract_context() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_NAMED_KEYS_MARKER,
+        (COMMAND_CONTRACT,),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+
+    assert!(
+        !account.named_keys().contains_key(MARKER),
+        "marker put by a contract should not end up in the caller's named keys"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_see_marker_put_by_payment_code_in_session_code() {
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash([1; 32])
+            .with_payment_code(
+                CONTRACT_NAMED_KEYS_MARKER,
+                (COMMAND_PAYMENT, *DEFAULT_PAYMENT),
+            )
+            .with_session_code(CONTRACT_NAMED_KEYS_MARKER, (COMMAND_SESSION_AFTER_PAYMENT,))
+            .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+
+    assert!(account.named_keys().contains_key(MARKER));
+}
@@ -0,0 +1,92 @@
+use engine_core::engine_state::genesis::GenesisConfig;
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_TIMESTAMP, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_SLASH_PERCENTAGE, DEFAULT_UNBONDING_DELAY, DEFAULT_WASM_COSTS,
+        MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use types::{Key, U512};
+
+const CONTRACT_HASH_FUNCTIONS: &str = "hash_functions.wasm";
+const BLAKE2B_DIGEST_NAME: &str = "blake2b_digest";
+const SHA256_DIGEST_NAME: &str = "sha256_digest";
+const DATA: &[u8] = b"abc";
+const BLAKE2B_DIGEST: &str = "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319";
+const SHA256_DIGEST: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+fn genesis_config_with_costs(wasm_costs: WasmCosts) -> GenesisConfig {
+    GenesisConfig::new(
+        DEFAULT_CHAIN_NAME.to_string(),
+        DEFAULT_GENESIS_TIMESTAMP,
+        *DEFAULT_PROTOCOL_VERSION,
+        utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+        DEFAULT_ACCOUNTS.clone(),
+        wasm_costs,
+        DEFAULT_UNBONDING_DELAY,
+        DEFAULT_SLASH_PERCENTAGE,
+    )
+}
+
+fn run(wasm_costs: WasmCosts, data: &[u8]) -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_HASH_FUNCTIONS,
+        (data.to_vec(),),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&genesis_config_with_costs(wasm_costs))
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn get_digest(builder: &InMemoryWasmTestBuilder, name: &str) -> String {
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name])
+        .expect("should query digest");
+    let digest: [u8; 32] = match stored_value {
+        StoredValue::CLValue(cl_value) => cl_value.into_t().expect("should convert digest"),
+        other => panic!("unexpected stored value: {:?}", other),
+    };
+    base16::encode_lower(&digest)
+}
+
+#[ignore]
+#[test]
+fn should_hash_with_blake2b_and_sha256() {
+    let builder = run(*DEFAULT_WASM_COSTS, DATA);
+
+    assert_eq!(get_digest(&builder, BLAKE2B_DIGEST_NAME), BLAKE2B_DIGEST);
+    assert_eq!(get_digest(&builder, SHA256_DIGEST_NAME), SHA256_DIGEST);
+}
+
+#[ignore]
+#[test]
+fn should_charge_hash_costs_per_call_and_byte() {
+    let free_hashing = WasmCosts {
+        hash_base: 0,
+        hash_byte: 0,
+        ..*DEFAULT_WASM_COSTS
+    };
+
+    for data in &[DATA.to_vec(), vec![0u8; 64 * 1024]] {
+        let free_cost = run(free_hashing, data).exec_costs(0)[0].value();
+        let cost = run(*DEFAULT_WASM_COSTS, data).exec_costs(0)[0].value();
+
+        // The contract calls both `blake2b` and `sha256`
+        let expected_surcharge = U512::from(2)
+            * (U512::from(DEFAULT_WASM_COSTS.hash_base)
+                + U512::from(DEFAULT_WASM_COSTS.hash_byte) * U512::from(data.len()));
+        assert_eq!(cost - free_cost, expected_surcharge);
+    }
+}
@@ -0,0 +1,111 @@
+use engine_core::engine_state::genesis::GenesisConfig;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_TIMESTAMP, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_SLASH_PERCENTAGE, DEFAULT_UNBONDING_DELAY, DEFAULT_WASM_COSTS,
+        MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use types::U512;
+
+const CONTRACT_HOST_OP_COST: &str = "host_op_cost.wasm";
+const ACTION_READ: &str = "read";
+const ACTION_WRITE: &str = "write";
+const ACTION_ADD: &str = "add";
+const COUNT: u32 = 10;
+const HOST_OP_COST: u32 = 1_000;
+
+fn genesis_config_with_costs(wasm_costs: WasmCosts) -> GenesisConfig {
+    GenesisConfig::new(
+        DEFAULT_CHAIN_NAME.to_string(),
+        DEFAULT_GENESIS_TIMESTAMP,
+        *DEFAULT_PROTOCOL_VERSION,
+        utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+        DEFAULT_ACCOUNTS.clone(),
+        wasm_costs,
+        DEFAULT_UNBONDING_DELAY,
+        DEFAULT_SLASH_PERCENTAGE,
+    )
+}
+
+fn host_op_cost(wasm_costs: WasmCosts, action: &str) -> U512 {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_HOST_OP_COST,
+        (action.to_string(), COUNT),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&genesis_config_with_costs(wasm_costs))
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    builder.exec_costs(0)[0].value()
+}
+
+fn surcharge(wasm_costs: WasmCosts, action: &str) -> U512 {
+    host_op_cost(wasm_costs, action) - host_op_cost(*DEFAULT_WASM_COSTS, action)
+}
+
+#[ignore]
+#[test]
+fn should_not_charge_host_ops_when_disabled() {
+    assert_eq!(DEFAULT_WASM_COSTS.read_cost, 0);
+    assert_eq!(DEFAULT_WASM_COSTS.write_cost, 0);
+    assert_eq!(DEFAULT_WASM_COSTS.add_cost, 0);
+}
+
+#[ignore]
+#[test]
+fn should_charge_read_cost_per_read() {
+    let wasm_costs = WasmCosts {
+        read_cost: HOST_OP_COST,
+        ..*DEFAULT_WASM_COSTS
+    };
+
+    assert_eq!(
+        surcharge(wasm_costs, ACTION_READ),
+        U512::from(HOST_OP_COST) * U512::from(COUNT)
+    );
+    // Only reads are charged the read cost
+    assert_eq!(surcharge(wasm_costs, ACTION_WRITE), U512::zero());
+    assert_eq!(surcharge(wasm_costs, ACTION_ADD), U512::zero());
+}
+
+#[ignore]
+#[test]
+fn should_charge_write_cost_per_write() {
+    let wasm_costs = WasmCosts {
+        write_cost: HOST_OP_COST,
+        ..*DEFAULT_WASM_COSTS
+    };
+
+    // The contract creates its `TURef` with `new_uref`, which is charged as a write too
+    assert_eq!(
+        surcharge(wasm_costs, ACTION_WRITE),
+        U512::from(HOST_OP_COST) * U512::from(COUNT + 1)
+    );
+    assert_eq!(surcharge(wasm_costs, ACTION_READ), U512::from(HOST_OP_COST));
+}
+
+#[ignore]
+#[test]
+fn should_charge_add_cost_per_add() {
+    let wasm_costs = WasmCosts {
+        add_cost: HOST_OP_COST,
+        ..*DEFAULT_WASM_COSTS
+    };
+
+    assert_eq!(
+        surcharge(wasm_costs, ACTION_ADD),
+        U512::from(HOST_OP_COST) * U512::from(COUNT)
+    );
+    assert_eq!(surcharge(wasm_costs, ACTION_WRITE), U512::zero());
+}
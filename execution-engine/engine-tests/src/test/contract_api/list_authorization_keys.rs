@@ -0,0 +1,72 @@
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::account::PublicKey;
+
+const CONTRACT_ADD_UPDATE_ASSOCIATED_KEY: &str = "add_update_associated_key.wasm";
+const CONTRACT_LIST_AUTHORIZATION_KEYS: &str = "list_authorization_keys.wasm";
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+
+#[ignore]
+#[test]
+fn should_list_single_authorization_key() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_LIST_AUTHORIZATION_KEYS,
+        (vec![PublicKey::new(DEFAULT_ACCOUNT_ADDR)],),
+    )
+    .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_list_every_authorization_key_in_ascending_order() {
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_ADD_UPDATE_ASSOCIATED_KEY,
+        (PublicKey::new(ACCOUNT_1_ADDR),),
+    )
+    .build();
+
+    // Signed in descending order, but listed in ascending order
+    let authorization_keys = [
+        PublicKey::new(DEFAULT_ACCOUNT_ADDR),
+        PublicKey::new(ACCOUNT_1_ADDR),
+    ];
+    let expected_authorization_keys = vec![
+        PublicKey::new(ACCOUNT_1_ADDR),
+        PublicKey::new(DEFAULT_ACCOUNT_ADDR),
+    ];
+    let exec_request_2 = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+            .with_session_code(
+                CONTRACT_LIST_AUTHORIZATION_KEYS,
+                (expected_authorization_keys,),
+            )
+            .with_deploy_hash([2u8; 32])
+            .with_authorization_keys(&authorization_keys)
+            .build();
+        ExecuteRequestBuilder::from_deploy_item(deploy).build()
+    };
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request_1)
+        .commit()
+        .expect_success()
+        .exec(exec_request_2)
+        .commit()
+        .expect_success();
+}
@@ -1,15 +1,36 @@
 mod account;
+mod contract_acl;
+mod contract_logging;
+mod contract_package;
+mod contract_package_namespace;
 mod create_purse;
+mod custom_allocator;
+mod debug_print;
+mod escrow_purse;
 mod get_arg;
+mod get_balance;
+mod get_block_info;
 mod get_blocktime;
+mod get_call_stack;
 mod get_caller;
+mod get_contract_metadata;
+mod get_named_key_of;
 mod get_phase;
+mod has_key;
+mod hash_functions;
+mod host_op_cost;
+mod list_authorization_keys;
 mod list_named_keys;
 mod local_state;
 mod main_purse;
 mod mint_purse;
+mod named_purses;
+mod random_bytes;
+mod reentrancy;
 mod revert;
+mod storage_write_cost;
 mod subcall;
 mod transfer;
 mod transfer_purse_to_account;
 mod transfer_purse_to_purse;
+mod vesting_schedule;
@@ -0,0 +1,73 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PurseId, U512};
+
+const CONTRACT_NAMED_PURSES: &str = "named_purses.wasm";
+const ACTION_CREATE: &str = "create";
+const ACTION_WITHDRAW: &str = "withdraw";
+const PURSE_NAME: &str = "savings";
+const DEPOSIT: u64 = 1_000_000;
+const WITHDRAWAL: u64 = 400_000;
+
+fn named_purse(builder: &InMemoryWasmTestBuilder) -> PurseId {
+    builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account")
+        .named_purse(PURSE_NAME)
+        .expect("should have named purse")
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_NAMED_PURSES,
+        (ACTION_CREATE, U512::from(DEPOSIT)),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_create_and_fund_named_purse() {
+    let builder = setup();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account");
+    assert_eq!(account.named_purses().len(), 1);
+    assert!(account.named_keys().get(PURSE_NAME).is_none());
+
+    let purse_id = named_purse(&builder);
+    assert_ne!(purse_id.value().addr(), account.purse_id().value().addr());
+    assert_eq!(builder.get_purse_balance(purse_id), U512::from(DEPOSIT));
+}
+
+#[ignore]
+#[test]
+fn should_use_named_purse_in_later_deploy() {
+    let mut builder = setup();
+    let purse_id = named_purse(&builder);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_NAMED_PURSES,
+        (ACTION_WITHDRAW, U512::from(WITHDRAWAL)),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    assert_eq!(
+        builder.get_purse_balance(purse_id),
+        U512::from(DEPOSIT - WITHDRAWAL)
+    );
+}
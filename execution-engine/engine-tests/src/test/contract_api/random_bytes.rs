@@ -0,0 +1,94 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, Key, RANDOM_BYTES_COUNT};
+
+const CONTRACT_RANDOM_BYTES: &str = "random_bytes.wasm";
+const UREF_NAME: &str = "uref";
+const RANDOM_BYTES_1_NAME: &str = "random_bytes_1";
+const RANDOM_BYTES_2_NAME: &str = "random_bytes_2";
+const DEPLOY_HASH_1: [u8; 32] = [1u8; 32];
+const DEPLOY_HASH_2: [u8; 32] = [2u8; 32];
+
+fn run(deploy_hash: [u8; 32], use_random_bytes: bool) -> InMemoryWasmTestBuilder {
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash(deploy_hash)
+            .with_session_code(CONTRACT_RANDOM_BYTES, (use_random_bytes,))
+            .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+            .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn get_named_key(builder: &InMemoryWasmTestBuilder, name: &str) -> Key {
+    *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account")
+        .named_keys()
+        .get(name)
+        .expect("should have named key")
+}
+
+fn get_random_bytes(builder: &InMemoryWasmTestBuilder, name: &str) -> [u8; RANDOM_BYTES_COUNT] {
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name])
+        .expect("should query random bytes");
+    match stored_value {
+        StoredValue::CLValue(cl_value) => cl_value.into_t().expect("should convert random bytes"),
+        other => panic!("unexpected stored value: {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_generate_random_bytes_deterministically() {
+    let builder_a = run(DEPLOY_HASH_1, true);
+    let builder_b = run(DEPLOY_HASH_1, true);
+    let builder_c = run(DEPLOY_HASH_2, true);
+
+    let random_bytes_1 = get_random_bytes(&builder_a, RANDOM_BYTES_1_NAME);
+    let random_bytes_2 = get_random_bytes(&builder_a, RANDOM_BYTES_2_NAME);
+    assert_ne!(random_bytes_1, random_bytes_2);
+
+    assert_eq!(
+        get_random_bytes(&builder_b, RANDOM_BYTES_1_NAME),
+        random_bytes_1
+    );
+    assert_eq!(
+        get_random_bytes(&builder_b, RANDOM_BYTES_2_NAME),
+        random_bytes_2
+    );
+    assert_ne!(
+        get_random_bytes(&builder_c, RANDOM_BYTES_1_NAME),
+        random_bytes_1
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_change_uref_addresses_when_using_random_bytes() {
+    let with_random_bytes = run(DEPLOY_HASH_1, true);
+    let without_random_bytes = run(DEPLOY_HASH_1, false);
+
+    assert_eq!(
+        get_named_key(&with_random_bytes, UREF_NAME),
+        get_named_key(&without_random_bytes, UREF_NAME)
+    );
+}
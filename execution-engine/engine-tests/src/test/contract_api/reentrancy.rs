@@ -0,0 +1,46 @@
+use engine_core::engine_state::EngineConfig;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_REENTRANT_CONTRACT: &str = "reentrant_contract.wasm";
+
+fn run(engine_config: EngineConfig, depth: u32) -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_REENTRANT_CONTRACT,
+        (depth,),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_allow_reentrancy_by_default() {
+    run(EngineConfig::new(), 2).expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_allow_non_reentrant_call_with_reentrancy_guard() {
+    run(EngineConfig::new().with_reentrancy_guard(true), 0).expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_fail_reentrant_call_with_reentrancy_guard() {
+    let builder = run(EngineConfig::new().with_reentrancy_guard(true), 1);
+
+    let error_message = builder
+        .exec_error_message(0)
+        .expect("should have error message");
+    assert!(error_message.contains("Reentrancy"), "{}", error_message);
+}
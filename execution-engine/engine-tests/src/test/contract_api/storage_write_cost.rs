@@ -0,0 +1,94 @@
+use engine_core::engine_state::genesis::GenesisConfig;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_TIMESTAMP, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_SLASH_PERCENTAGE, DEFAULT_UNBONDING_DELAY, DEFAULT_WASM_COSTS,
+        MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use types::{bytesrepr::ToBytes, CLValue, U512};
+
+const CONTRACT_STORAGE_WRITE_COST: &str = "storage_write_cost.wasm";
+const STORAGE_WRITE_BYTE_COST: u32 = 10;
+
+fn genesis_config_with_costs(wasm_costs: WasmCosts) -> GenesisConfig {
+    GenesisConfig::new(
+        DEFAULT_CHAIN_NAME.to_string(),
+        DEFAULT_GENESIS_TIMESTAMP,
+        *DEFAULT_PROTOCOL_VERSION,
+        utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+        DEFAULT_ACCOUNTS.clone(),
+        wasm_costs,
+        DEFAULT_UNBONDING_DELAY,
+        DEFAULT_SLASH_PERCENTAGE,
+    )
+}
+
+fn new_uref_cost(wasm_costs: WasmCosts, value: &str) -> U512 {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_STORAGE_WRITE_COST,
+        (value.to_string(),),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&genesis_config_with_costs(wasm_costs))
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    builder.exec_costs(0)[0].value()
+}
+
+fn serialized_size(value: &str) -> usize {
+    CLValue::from_t(value.to_string())
+        .expect("should create CLValue")
+        .serialized_length()
+}
+
+#[ignore]
+#[test]
+fn should_not_charge_per_byte_when_disabled() {
+    assert_eq!(DEFAULT_WASM_COSTS.storage_write_byte, 0);
+
+    let value = "a".repeat(1024);
+    let cost_with_byte_cost = |storage_write_byte| {
+        let wasm_costs = WasmCosts {
+            storage_write_byte,
+            ..*DEFAULT_WASM_COSTS
+        };
+        new_uref_cost(wasm_costs, &value)
+    };
+    let disabled_cost = new_uref_cost(*DEFAULT_WASM_COSTS, &value);
+    let single_cost = cost_with_byte_cost(STORAGE_WRITE_BYTE_COST);
+    let double_cost = cost_with_byte_cost(2 * STORAGE_WRITE_BYTE_COST);
+
+    // The cost is linear in the byte cost, so a byte cost of zero leaves only the baseline
+    let baseline_cost = single_cost * U512::from(2) - double_cost;
+    assert_eq!(disabled_cost, baseline_cost);
+    assert!(disabled_cost < single_cost);
+}
+
+#[ignore]
+#[test]
+fn should_charge_new_uref_proportionally_to_value_size() {
+    let wasm_costs = WasmCosts {
+        storage_write_byte: STORAGE_WRITE_BYTE_COST,
+        ..*DEFAULT_WASM_COSTS
+    };
+
+    for value in &["abcd".to_string(), "a".repeat(64 * 1024)] {
+        let disabled_cost = new_uref_cost(*DEFAULT_WASM_COSTS, value);
+        let enabled_cost = new_uref_cost(wasm_costs, value);
+
+        let expected_surcharge =
+            U512::from(STORAGE_WRITE_BYTE_COST) * U512::from(serialized_size(value));
+        assert_eq!(enabled_cost - disabled_cost, expected_surcharge);
+    }
+}
@@ -1,5 +1,5 @@
 use engine_shared::{stored_value::StoredValue, transform::Transform};
-use types::{ApiError, Key, U512};
+use types::{system_contract_errors::mint, ApiError, Key, U512};
 
 use engine_test_support::{
     internal::{
@@ -162,7 +162,10 @@ fn should_run_purse_to_purse_transfer_with_error() {
     // Main assertion for the result of `transfer_from_purse_to_purse`
     assert_eq!(
         purse_transfer_result,
-        format!("{:?}", Result::<(), _>::Err(ApiError::Transfer)),
+        format!(
+            "{:?}",
+            Result::<(), _>::Err(ApiError::from(mint::Error::InsufficientFunds))
+        ),
     );
 
     // Obtain main purse's balance
@@ -0,0 +1,158 @@
+use engine_core::engine_state::genesis::GenesisAccount;
+use engine_shared::{account::VestingSchedule, motes::Motes};
+use engine_test_support::internal::{
+    utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+};
+use types::{account::PublicKey, system_contract_errors::mint, ApiError, U512};
+
+const CONTRACT_TRANSFER_MAIN_PURSE_TO_NEW_PURSE: &str = "transfer_main_purse_to_new_purse.wasm";
+const CONTRACT_TRANSFER_MAIN_PURSE_THROUGH_MINT: &str = "transfer_main_purse_through_mint.wasm";
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const ACCOUNT_1_BALANCE: u64 = 10_000_000_000;
+const ACCOUNT_1_LOCKED_AMOUNT: u64 = 5_000_000_000;
+const CLIFF_TIMESTAMP: u64 = 1_000;
+const RELEASE_DURATION: u64 = 1_000;
+const HALFWAY_TIMESTAMP: u64 = CLIFF_TIMESTAMP + RELEASE_DURATION / 2;
+const RELEASE_END_TIMESTAMP: u64 = CLIFF_TIMESTAMP + RELEASE_DURATION;
+const DESTINATION_PURSE: &str = "destination";
+
+fn vesting_schedule() -> VestingSchedule {
+    VestingSchedule::new(
+        CLIFF_TIMESTAMP,
+        RELEASE_DURATION,
+        U512::from(ACCOUNT_1_LOCKED_AMOUNT),
+    )
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut accounts = DEFAULT_ACCOUNTS.clone();
+    let account = GenesisAccount::new(
+        PublicKey::new(ACCOUNT_1_ADDR),
+        Motes::new(ACCOUNT_1_BALANCE.into()),
+        Motes::zero(),
+    )
+    .with_vesting_schedule(vesting_schedule());
+    accounts.push(account);
+    let genesis_config = utils::create_genesis_config(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&genesis_config);
+    builder
+}
+
+fn transfer(builder: &mut InMemoryWasmTestBuilder, amount: u64, block_time: u64) {
+    transfer_with(
+        builder,
+        CONTRACT_TRANSFER_MAIN_PURSE_TO_NEW_PURSE,
+        amount,
+        block_time,
+    );
+}
+
+fn transfer_with(
+    builder: &mut InMemoryWasmTestBuilder,
+    contract: &str,
+    amount: u64,
+    block_time: u64,
+) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        contract,
+        (DESTINATION_PURSE, U512::from(amount)),
+    )
+    .with_block_time(block_time)
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn assert_funds_locked(builder: &InMemoryWasmTestBuilder) {
+    let error_message = builder
+        .exec_error_message(builder.get_exec_responses_count() - 1)
+        .expect("should have error message");
+    let code = u32::from(ApiError::from(mint::Error::FundsLocked));
+    assert!(
+        error_message.contains(&format!("Revert({})", code)),
+        "unexpected error: {}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_store_vesting_schedule_in_genesis_account() {
+    let builder = setup();
+    let account = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should get account");
+    assert_eq!(account.vesting_schedule(), Some(&vesting_schedule()));
+    assert_eq!(
+        builder.get_purse_balance(account.purse_id()),
+        U512::from(ACCOUNT_1_BALANCE)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_allow_transferring_unlocked_funds_before_cliff() {
+    let mut builder = setup();
+    transfer(&mut builder, 1_000_000_000, 0);
+    assert!(!builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_locked_funds_before_cliff() {
+    let mut builder = setup();
+    transfer(&mut builder, 6_000_000_000, CLIFF_TIMESTAMP);
+    assert_funds_locked(&builder);
+}
+
+#[ignore]
+#[test]
+fn should_release_locked_funds_linearly_after_cliff() {
+    let mut builder = setup();
+
+    // Half of the locked amount has been released, but not enough to move the rest of the balance
+    transfer(&mut builder, 9_000_000_000, HALFWAY_TIMESTAMP);
+    assert_funds_locked(&builder);
+
+    transfer(&mut builder, 6_000_000_000, HALFWAY_TIMESTAMP);
+    assert!(!builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_transfer_all_funds_after_release() {
+    let mut builder = setup();
+    transfer(&mut builder, 9_000_000_000, RELEASE_END_TIMESTAMP);
+    assert!(!builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_locked_funds_by_calling_mint_directly() {
+    let mut builder = setup();
+
+    transfer_with(
+        &mut builder,
+        CONTRACT_TRANSFER_MAIN_PURSE_THROUGH_MINT,
+        6_000_000_000,
+        CLIFF_TIMESTAMP,
+    );
+    let error_message = builder
+        .exec_error_message(builder.get_exec_responses_count() - 1)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("FundsLocked"),
+        "unexpected error: {}",
+        error_message
+    );
+
+    transfer_with(
+        &mut builder,
+        CONTRACT_TRANSFER_MAIN_PURSE_THROUGH_MINT,
+        1_000_000_000,
+        CLIFF_TIMESTAMP,
+    );
+    assert!(!builder.is_error());
+}
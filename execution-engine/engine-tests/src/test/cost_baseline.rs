@@ -0,0 +1,82 @@
+use std::fs;
+
+use tempfile::TempDir;
+
+use engine_shared::gas::Gas;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::U512;
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const COSTS_FILE: &str = "costs";
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const ACCOUNT_1_FUNDS: u64 = 1_000_000_000;
+
+fn run_transfer_and_do_nothing() -> InMemoryWasmTestBuilder {
+    let transfer_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(ACCOUNT_1_FUNDS)),
+    )
+    .build();
+    let do_nothing_request =
+        ExecuteRequestBuilder::standard(ACCOUNT_1_ADDR, CONTRACT_DO_NOTHING, ()).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(transfer_request)
+        .expect_success()
+        .commit()
+        .exec(do_nothing_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_assert_cost_within_bounds() {
+    let mut builder = run_transfer_and_do_nothing();
+    let cost = builder.exec_costs(1)[0];
+
+    builder
+        .assert_cost_between(1, cost, cost)
+        .assert_cost_between(1, Gas::default(), cost + cost);
+}
+
+#[ignore]
+#[test]
+#[should_panic(expected = "Expected exec request 1 to cost between")]
+fn should_fail_cost_assertion_out_of_bounds() {
+    let mut builder = run_transfer_and_do_nothing();
+    let cost = builder.exec_costs(1)[0];
+
+    builder.assert_cost_between(1, cost + Gas::from(1u64), cost + cost);
+}
+
+#[ignore]
+#[test]
+fn should_record_costs() {
+    let temp_dir = TempDir::new().expect("should create temp dir");
+    let costs_path = temp_dir.path().join(COSTS_FILE);
+
+    let mut builder = run_transfer_and_do_nothing();
+    builder.record_costs_to(&costs_path);
+
+    let expected = format!(
+        "{}\n{}\n",
+        builder.exec_costs(0)[0].value(),
+        builder.exec_costs(1)[0].value()
+    );
+    let recorded = fs::read_to_string(&costs_path).expect("should read costs");
+    assert_eq!(recorded, expected);
+
+    // Recording again yields the same baseline, rather than appending to it
+    run_transfer_and_do_nothing().record_costs_to(&costs_path);
+    let rerecorded = fs::read_to_string(&costs_path).expect("should read costs");
+    assert_eq!(rerecorded, expected);
+}
@@ -0,0 +1,220 @@
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
+
+use lazy_static::lazy_static;
+
+use engine_core::engine_state::{
+    deploy_hooks::{DeployHookContext, DeployHooks},
+    execution_result::ExecutionResult,
+    EngineConfig, Error,
+};
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_KEY,
+        DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{Key, Phase, U512};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_STANDARD_PAYMENT: &str = "standard_payment.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT_01: &str = "transfer_to_account_01.wasm";
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+
+lazy_static! {
+    static ref ACCOUNT_1_INITIAL_BALANCE: U512 = *DEFAULT_PAYMENT * 10;
+}
+
+/// Only lets accounts on the allowlist run deploys, checking them before the given phase.
+#[derive(Debug)]
+struct AllowlistHooks {
+    allowed: BTreeSet<[u8; 32]>,
+    checked_phase: Phase,
+}
+
+impl AllowlistHooks {
+    fn new(allowed: &[[u8; 32]], checked_phase: Phase) -> Self {
+        AllowlistHooks {
+            allowed: allowed.iter().cloned().collect(),
+            checked_phase,
+        }
+    }
+
+    fn check(&self, phase: Phase, context: &DeployHookContext) -> Result<(), Error> {
+        if phase != self.checked_phase {
+            return Ok(());
+        }
+        let account_addr = context.account().pub_key();
+        // The account must exist in global state, as seen by the deploy
+        match context.read(&Key::Account(account_addr))? {
+            Some(StoredValue::Account(_)) => {}
+            _ => return Err(Error::DeployRejected("unknown account".to_string())),
+        }
+        if self.allowed.contains(&account_addr) {
+            Ok(())
+        } else {
+            Err(Error::DeployRejected(
+                "account not on allowlist".to_string(),
+            ))
+        }
+    }
+}
+
+impl DeployHooks for AllowlistHooks {
+    fn pre_payment(&self, context: &DeployHookContext) -> Result<(), Error> {
+        self.check(Phase::Payment, context)
+    }
+
+    fn pre_session(&self, context: &DeployHookContext) -> Result<(), Error> {
+        self.check(Phase::Session, context)
+    }
+}
+
+/// Records whether each finalized deploy succeeded.
+#[derive(Debug, Default, Clone)]
+struct RecordingHooks {
+    results: Arc<Mutex<Vec<bool>>>,
+}
+
+impl DeployHooks for RecordingHooks {
+    fn post_finalize(&self, _context: &DeployHookContext, execution_result: &ExecutionResult) {
+        self.results
+            .lock()
+            .unwrap()
+            .push(execution_result.is_success());
+    }
+}
+
+fn setup(engine_config: EngineConfig) -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT_01,
+        (ACCOUNT_1_ADDR, *ACCOUNT_1_INITIAL_BALANCE),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_before_payment() {
+    let hooks = AllowlistHooks::new(&[DEFAULT_ACCOUNT_ADDR], Phase::Payment);
+    let mut builder = setup(EngineConfig::new().with_deploy_hooks(hooks));
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account 1");
+    let balance_before = builder.get_purse_balance(account_1.purse_id());
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(ACCOUNT_1_ADDR, CONTRACT_DO_NOTHING, ()).build();
+    builder.exec(exec_request).commit();
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("DeployRejected(\"account not on allowlist\")"),
+        "{}",
+        error_message
+    );
+
+    // Nothing was executed, so nothing was charged
+    let balance_after = builder.get_purse_balance(account_1.purse_id());
+    assert_eq!(balance_before, balance_after);
+}
+
+#[ignore]
+#[test]
+fn should_skip_session_rejected_by_hook() {
+    let hooks = AllowlistHooks::new(&[DEFAULT_ACCOUNT_ADDR], Phase::Session);
+    let mut builder = setup(EngineConfig::new().with_deploy_hooks(hooks));
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account 1");
+    let balance_before = builder.get_purse_balance(account_1.purse_id());
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(ACCOUNT_1_ADDR, CONTRACT_DO_NOTHING, ()).build();
+    builder.exec(exec_request).commit();
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("DeployRejected(\"account not on allowlist\")"),
+        "{}",
+        error_message
+    );
+
+    // Payment code was executed and finalized regardless
+    let balance_after = builder.get_purse_balance(account_1.purse_id());
+    assert!(balance_after < balance_before);
+}
+
+#[ignore]
+#[test]
+fn should_accept_deploy_on_allowlist() {
+    let hooks = AllowlistHooks::new(&[DEFAULT_ACCOUNT_ADDR, ACCOUNT_1_ADDR], Phase::Payment);
+    let mut builder = setup(EngineConfig::new().with_deploy_hooks(hooks));
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(ACCOUNT_1_ADDR, CONTRACT_DO_NOTHING, ()).build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_run_post_finalize_hook() {
+    let hooks = RecordingHooks::default();
+    let mut builder = setup(EngineConfig::new().with_deploy_hooks(hooks.clone()));
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+    builder.exec(exec_request).expect_success().commit();
+
+    assert_eq!(*hooks.results.lock().unwrap(), vec![true, true]);
+}
+
+#[ignore]
+#[test]
+fn should_run_post_finalize_hook_on_forced_transfer() {
+    let hooks = RecordingHooks::default();
+    let mut builder = setup(EngineConfig::new().with_deploy_hooks(hooks.clone()));
+
+    // Payment code which doesn't pay enough is charged by a forced transfer
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash([2; 32])
+            .with_session_code(CONTRACT_DO_NOTHING, ())
+            .with_payment_code(CONTRACT_STANDARD_PAYMENT, (U512::from(1),))
+            .with_authorization_keys(&[*DEFAULT_ACCOUNT_KEY])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+    builder.exec(exec_request).commit();
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("InsufficientPaymentError"),
+        "{}",
+        error_message
+    );
+    assert_eq!(*hooks.results.lock().unwrap(), vec![true, false]);
+}
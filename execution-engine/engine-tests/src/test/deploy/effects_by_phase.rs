@@ -0,0 +1,56 @@
+use engine_core::engine_state::EngineConfig;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+
+#[ignore]
+#[test]
+fn should_not_keep_effects_by_phase_by_default() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+
+    assert_eq!(response[0].effects_by_phase(), None);
+}
+
+#[ignore]
+#[test]
+fn should_keep_effects_by_phase_when_enabled() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+
+    let engine_config = EngineConfig::new().with_effects_by_phase(true);
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+
+    let effects_by_phase = response[0]
+        .effects_by_phase()
+        .expect("should keep effects by phase when enabled");
+
+    // Payment moves funds into the payment purse and finalization pays them out again
+    assert!(!effects_by_phase.payment().transforms.is_empty());
+    assert!(!effects_by_phase.finalize().transforms.is_empty());
+}
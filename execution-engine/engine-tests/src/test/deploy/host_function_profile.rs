@@ -0,0 +1,76 @@
+use engine_core::{engine_state::EngineConfig, resolvers::v1_function_index::FunctionIndex};
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{Key, U512};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: [u8; 32] = [42u8; 32];
+
+#[ignore]
+#[test]
+fn should_not_profile_host_functions_by_default() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+
+    assert!(response[0].host_function_profile().is_none());
+}
+
+#[ignore]
+#[test]
+fn should_profile_host_functions_when_profiling() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(1)),
+    )
+    .build();
+
+    let engine_config = EngineConfig::new().with_profiling(true);
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+
+    let profile = response[0]
+        .host_function_profile()
+        .expect("should have host function profile when profiling");
+
+    let account_key = Key::Account(DEFAULT_ACCOUNT_ADDR);
+    let transfers = profile
+        .get(account_key, FunctionIndex::TransferFromPurseToAccountIndex)
+        .expect("should have profiled the transfer");
+    assert_eq!(transfers.invocations(), 1);
+
+    // The Wasm instructions of both the payment and the session code are charged through `gas`
+    let metering = profile
+        .get(account_key, FunctionIndex::GasFuncIndex)
+        .expect("should have profiled gas metering");
+    assert!(metering.invocations() > 1);
+    assert!(metering.gas() > Default::default());
+
+    // The system contracts called by the deploy are profiled separately
+    assert!(profile.by_contract().len() > 1);
+    assert!(!profile.to_string().is_empty());
+}
@@ -1,3 +1,12 @@
+mod deploy_hooks;
+mod effects_by_phase;
+mod host_function_profile;
+mod native_transfer;
 mod payment_code;
+mod payment_write_guard;
 mod preconditions;
+mod read_stats;
+mod session_context;
+mod signatures;
+mod speculative_exec;
 mod stored_contracts;
@@ -0,0 +1,243 @@
+use engine_core::engine_state::{execute_request::ExecuteRequest, Error};
+use engine_shared::gas::Gas;
+use engine_storage::protocol_data::{
+    ChainParameters, DEFAULT_CONV_RATE, DEFAULT_MAX_PAYMENT, DEFAULT_NATIVE_TRANSFER_COST,
+};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        UpgradeRequestBuilder, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION,
+        STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use types::{account::PublicKey, ProtocolVersion, U512};
+
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: [u8; 32] = [42u8; 32];
+const TRANSFER_AMOUNT: u64 = 1_000;
+const UPGRADED_NATIVE_TRANSFER_COST: u64 = 3 * DEFAULT_NATIVE_TRANSFER_COST;
+
+fn native_transfer_request(
+    deploy_hash: [u8; 32],
+    target: [u8; 32],
+    amount: U512,
+) -> ExecuteRequest {
+    native_transfer_request_at(*DEFAULT_PROTOCOL_VERSION, deploy_hash, target, amount)
+}
+
+fn native_transfer_request_at(
+    protocol_version: ProtocolVersion,
+    deploy_hash: [u8; 32],
+    target: [u8; 32],
+    amount: U512,
+) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_deploy_hash(deploy_hash)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_session_transfer(target, amount)
+        .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+        .build();
+    ExecuteRequestBuilder::new()
+        .push_deploy(deploy)
+        .with_protocol_version(protocol_version)
+        .build()
+}
+
+#[ignore]
+#[test]
+fn should_transfer_natively_to_new_and_existing_account() {
+    let amount = U512::from(TRANSFER_AMOUNT);
+    let exec_request_1 = native_transfer_request([1; 32], ACCOUNT_1_ADDR, amount);
+    let exec_request_2 = native_transfer_request([2; 32], ACCOUNT_1_ADDR, amount);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request_1)
+        .expect_success()
+        .commit();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should create account 1");
+    assert_eq!(builder.get_purse_balance(account_1.purse_id()), amount);
+
+    builder.exec(exec_request_2).expect_success().commit();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should get account 1");
+    assert_eq!(
+        builder.get_purse_balance(account_1.purse_id()),
+        amount * 2,
+        "second transfer should credit the existing purse"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_let_new_account_use_natively_created_purse() {
+    let exec_request_1 =
+        native_transfer_request([1; 32], ACCOUNT_1_ADDR, *DEFAULT_PAYMENT + TRANSFER_AMOUNT);
+    // Spends the new purse through the mint contract, both for payment and for the transfer
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (
+            PublicKey::new(DEFAULT_ACCOUNT_ADDR),
+            U512::from(TRANSFER_AMOUNT),
+        ),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request_1)
+        .expect_success()
+        .commit()
+        .exec(exec_request_2)
+        .expect_success()
+        .commit();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should get account 1");
+    assert!(builder.get_purse_balance(account_1.purse_id()) < *DEFAULT_PAYMENT);
+}
+
+#[ignore]
+#[test]
+fn should_charge_less_for_native_transfer_than_for_wasm_transfer() {
+    let amount = U512::from(TRANSFER_AMOUNT);
+    let native_request = native_transfer_request([1; 32], ACCOUNT_1_ADDR, amount);
+    let wasm_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (PublicKey::new(ACCOUNT_1_ADDR), amount),
+    )
+    .build();
+
+    let mut native_builder = InMemoryWasmTestBuilder::default();
+    native_builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(native_request)
+        .expect_success();
+    let mut wasm_builder = InMemoryWasmTestBuilder::default();
+    wasm_builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(wasm_request)
+        .expect_success();
+
+    let native_cost = native_builder.exec_costs(0)[0];
+    let wasm_cost = wasm_builder.exec_costs(0)[0];
+    assert!(
+        native_cost < wasm_cost,
+        "native transfer cost {} should be below wasm transfer cost {}",
+        native_cost,
+        wasm_cost
+    );
+}
+
+#[ignore]
+#[test]
+fn should_charge_upgraded_native_transfer_cost() {
+    let amount = U512::from(TRANSFER_AMOUNT);
+    let new_protocol_version = ProtocolVersion::V1_1_0;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(native_transfer_request([1; 32], ACCOUNT_1_ADDR, amount))
+        .expect_success()
+        .commit();
+    let default_cost = builder.exec_costs(0)[0];
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(1)
+        .with_new_chain_parameters(ChainParameters::new(
+            DEFAULT_MAX_PAYMENT,
+            DEFAULT_CONV_RATE,
+            0,
+            0,
+            UPGRADED_NATIVE_TRANSFER_COST,
+        ))
+        .build();
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let exec_request =
+        native_transfer_request_at(new_protocol_version, [2; 32], ACCOUNT_1_ADDR, amount);
+    builder.exec(exec_request).expect_success().commit();
+    let upgraded_cost = builder.exec_costs(1)[0];
+
+    // Payment is charged the same, so the costs only differ by the native transfer costs
+    assert_eq!(
+        upgraded_cost - default_cost,
+        Gas::from(UPGRADED_NATIVE_TRANSFER_COST - DEFAULT_NATIVE_TRANSFER_COST)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_native_transfer_with_insufficient_funds() {
+    let exec_request = native_transfer_request(
+        [1; 32],
+        ACCOUNT_1_ADDR,
+        U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE),
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit();
+
+    assert!(builder.is_error());
+    let error_message = builder
+        .exec_error_message(0)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("InsufficientFunds"),
+        "unexpected error: {}",
+        error_message
+    );
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_none());
+}
+
+#[ignore]
+#[test]
+fn should_reject_native_transfer_as_payment() {
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash([1; 32])
+            .with_payment_transfer(ACCOUNT_1_ADDR, *DEFAULT_PAYMENT)
+            .with_session_code("do_nothing.wasm", ())
+            .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+            .build();
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_eq!(
+        precondition_failure,
+        Error::InvalidDeployItemVariant("Transfer is only valid as session code".to_string())
+            .to_string()
+    );
+}
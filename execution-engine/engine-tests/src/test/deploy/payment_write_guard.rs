@@ -0,0 +1,81 @@
+use engine_core::engine_state::EngineConfig;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::account::PublicKey;
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_PAYMENT_WRITE_GUARD: &str = "payment_write_guard.wasm";
+const NAMED_PURSE: &str = "payment_named_purse";
+const APPLICATION_STATE: &str = "payment_application_state";
+
+fn run(engine_config: EngineConfig, write_application_state: bool) -> InMemoryWasmTestBuilder {
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash([1; 32])
+            .with_session_code(CONTRACT_DO_NOTHING, ())
+            .with_payment_code(
+                CONTRACT_PAYMENT_WRITE_GUARD,
+                (*DEFAULT_PAYMENT, write_application_state),
+            )
+            .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit();
+    builder
+}
+
+fn has_named_key(builder: &InMemoryWasmTestBuilder, name: &str) -> bool {
+    builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .contains_key(name)
+}
+
+#[ignore]
+#[test]
+fn should_allow_payment_writes_by_default() {
+    let mut builder = run(EngineConfig::new(), true);
+
+    builder.expect_success();
+    assert!(has_named_key(&builder, NAMED_PURSE));
+    assert!(has_named_key(&builder, APPLICATION_STATE));
+}
+
+#[ignore]
+#[test]
+fn should_allow_payment_writes_to_purses_with_payment_write_guard() {
+    let mut builder = run(EngineConfig::new().with_payment_write_guard(true), false);
+
+    builder.expect_success();
+    assert!(has_named_key(&builder, NAMED_PURSE));
+}
+
+#[ignore]
+#[test]
+fn should_fail_payment_writes_to_application_state_with_payment_write_guard() {
+    let builder = run(EngineConfig::new().with_payment_write_guard(true), true);
+
+    let error_message = builder
+        .exec_error_message(0)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("PaymentWriteForbidden"),
+        "{}",
+        error_message
+    );
+    assert!(!has_named_key(&builder, APPLICATION_STATE));
+}
@@ -1,7 +1,8 @@
+use engine_core::engine_state::Error;
 use engine_test_support::{
     internal::{
         utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
-        DEFAULT_GENESIS_CONFIG, STANDARD_PAYMENT_CONTRACT,
+        DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT, STANDARD_PAYMENT_CONTRACT,
     },
     DEFAULT_ACCOUNT_ADDR,
 };
@@ -46,8 +47,12 @@ fn should_raise_precondition_authorization_failure_invalid_account() {
     let precondition_failure = utils::get_precondition_failure(response);
 
     assert_eq!(
-        precondition_failure, "Authorization failure: not authorized.",
-        "expected authorization failure"
+        precondition_failure,
+        format!(
+            "{}",
+            Error::AccountNotFound(PublicKey::new(nonexistent_account_addr))
+        ),
+        "expected account not found"
     );
 }
 
@@ -81,8 +86,9 @@ fn should_raise_precondition_authorization_failure_empty_authorized_keys() {
     let precondition_failure = utils::get_precondition_failure(response);
 
     assert_eq!(
-        precondition_failure, "Authorization failure: not authorized.",
-        "expected authorization failure"
+        precondition_failure,
+        format!("{}", Error::NoAuthorizationKeys),
+        "expected no authorization keys"
     );
 }
 
@@ -123,7 +129,54 @@ fn should_raise_precondition_authorization_failure_invalid_authorized_keys() {
     let precondition_failure = utils::get_precondition_failure(response);
 
     assert_eq!(
-        precondition_failure, "Authorization failure: not authorized.",
-        "expected authorization failure"
+        precondition_failure,
+        format!(
+            "{}",
+            Error::UnassociatedKeys(vec![PublicKey::new(nonexistent_account_addr)])
+        ),
+        "expected unassociated keys"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_duplicate_deploy_hash() {
+    let deploy_hash = [7u8; 32];
+    let make_deploy = || {
+        DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash(deploy_hash)
+            .with_session_code("do_nothing.wasm", ())
+            .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+            .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+            .build()
+    };
+
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(make_deploy())
+        .push_deploy(make_deploy())
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    assert_eq!(response.len(), 2);
+
+    // The first deploy using the hash is executed as usual
+    assert!(!response[0].has_precondition_failure());
+    assert!(response[0].error().is_none());
+
+    let precondition_failure = utils::get_precondition_failure(&response[1..]);
+    assert_eq!(
+        precondition_failure,
+        format!(
+            "Duplicate deploy hash: {}",
+            base16::encode_lower(&deploy_hash)
+        )
     );
 }
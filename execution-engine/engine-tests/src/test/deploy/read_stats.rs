@@ -0,0 +1,56 @@
+use engine_core::engine_state::EngineConfig;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+
+#[ignore]
+#[test]
+fn should_not_collect_read_stats_by_default() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+
+    assert_eq!(response[0].read_stats(), None);
+}
+
+#[ignore]
+#[test]
+fn should_collect_read_stats_when_profiling() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+
+    let engine_config = EngineConfig::new().with_profiling(true);
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+
+    let read_stats = response[0]
+        .read_stats()
+        .expect("should have read stats when profiling");
+
+    // At the very least the deploying account and its main purse balance are read from state
+    assert!(read_stats.reader_hits() > 0);
+    assert!(read_stats.bytes_deserialized() > 0);
+}
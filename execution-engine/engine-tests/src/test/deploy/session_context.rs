@@ -0,0 +1,168 @@
+use engine_core::engine_state::{
+    executable_deploy_item::ExecutionContext, execute_request::ExecuteRequest,
+};
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, Key};
+
+const CONTRACT_SESSION_CONTEXT: &str = "session_context.wasm";
+const PACKAGE_NAME: &str = "session_context";
+const PUT_MARKER_ENTRY_POINT: &str = "put_marker";
+const GET_MAIN_PURSE_ENTRY_POINT: &str = "get_main_purse";
+const RESTRICTED_ENTRY_POINT: &str = "restricted";
+const MARKER_NAME: &str = "marker";
+
+fn setup() -> (InMemoryWasmTestBuilder, [u8; 32]) {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_SESSION_CONTEXT, ()).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let package_hash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account")
+        .named_keys()
+        .get(PACKAGE_NAME)
+        .and_then(Key::as_hash)
+        .expect("should have contract package hash");
+
+    (builder, package_hash)
+}
+
+fn call_request(
+    deploy_hash: [u8; 32],
+    entry_point: &str,
+    context: ExecutionContext,
+) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_deploy_hash(deploy_hash)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_stored_versioned_session_named_key(PACKAGE_NAME, None, entry_point, (), context)
+        .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+        .build();
+    ExecuteRequestBuilder::new().push_deploy(deploy).build()
+}
+
+fn contract_has_marker(builder: &InMemoryWasmTestBuilder, package_hash: [u8; 32]) -> bool {
+    let contract_package = match builder.query(None, Key::Hash(package_hash), &[]) {
+        Ok(StoredValue::ContractPackage(contract_package)) => contract_package,
+        other => panic!("unexpected query result: {:?}", other),
+    };
+    let (_, contract_header) = contract_package
+        .current_version()
+        .expect("should have an enabled version");
+    match builder.query(None, Key::Hash(contract_header.contract_hash()), &[]) {
+        Ok(StoredValue::Contract(contract)) => contract.named_keys().contains_key(MARKER_NAME),
+        other => panic!("unexpected query result: {:?}", other),
+    }
+}
+
+fn account_has_marker(builder: &InMemoryWasmTestBuilder) -> bool {
+    builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account")
+        .named_keys()
+        .contains_key(MARKER_NAME)
+}
+
+#[ignore]
+#[test]
+fn should_run_versioned_session_code_in_account_context() {
+    let (mut builder, package_hash) = setup();
+
+    builder
+        .exec(call_request(
+            [2; 32],
+            PUT_MARKER_ENTRY_POINT,
+            ExecutionContext::Account,
+        ))
+        .expect_success()
+        .commit();
+
+    assert!(account_has_marker(&builder));
+    assert!(!contract_has_marker(&builder, package_hash));
+}
+
+#[ignore]
+#[test]
+fn should_run_versioned_session_code_in_contract_context() {
+    let (mut builder, package_hash) = setup();
+
+    builder
+        .exec(call_request(
+            [2; 32],
+            PUT_MARKER_ENTRY_POINT,
+            ExecutionContext::Contract,
+        ))
+        .expect_success()
+        .commit();
+
+    assert!(!account_has_marker(&builder));
+    assert!(contract_has_marker(&builder, package_hash));
+}
+
+#[ignore]
+#[test]
+fn should_deny_main_purse_to_session_code_in_contract_context() {
+    let (mut builder, _) = setup();
+
+    builder
+        .exec(call_request(
+            [2; 32],
+            GET_MAIN_PURSE_ENTRY_POINT,
+            ExecutionContext::Account,
+        ))
+        .expect_success()
+        .commit();
+
+    builder
+        .exec(call_request(
+            [3; 32],
+            GET_MAIN_PURSE_ENTRY_POINT,
+            ExecutionContext::Contract,
+        ))
+        .commit();
+
+    let error_message = builder
+        .exec_error_message(2)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("InvalidContext"),
+        "unexpected error: {}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_check_entry_point_access_of_versioned_session_code() {
+    let (mut builder, _) = setup();
+
+    builder.exec(call_request(
+        [2; 32],
+        RESTRICTED_ENTRY_POINT,
+        ExecutionContext::Contract,
+    ));
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert!(
+        precondition_failure.contains("EntryPointAccessDenied"),
+        "unexpected error: {}",
+        precondition_failure
+    );
+}
@@ -0,0 +1,195 @@
+use ed25519_dalek::{Keypair, PublicKey as VerifyingKey, SecretKey};
+
+use engine_core::engine_state::{
+    execution_result::ExecutionResult, genesis::GenesisResult, EngineConfig, EngineState, Error,
+};
+use engine_shared::newtypes::CorrelationId;
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::account::{PublicKey, TaggedPublicKey};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_STANDARD_PAYMENT: &str = "standard_payment.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const DEPLOY_HASH: [u8; 32] = [42; 32];
+
+fn keypair_from_seed(seed: u8) -> Keypair {
+    let secret = SecretKey::from_bytes(&[seed; 32]).expect("should create secret key");
+    let public = VerifyingKey::from(&secret);
+    Keypair { secret, public }
+}
+
+/// Runs genesis and funds an account whose public key is the one of `keypair`.
+fn setup(keypair: &Keypair) -> InMemoryWasmTestBuilder {
+    setup_account(keypair.public.to_bytes())
+}
+
+/// Runs genesis and funds the account at `account_addr`.
+fn setup_account(account_addr: [u8; 32]) -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (account_addr, *DEFAULT_PAYMENT * 10),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn deploy_builder(account_addr: [u8; 32]) -> DeployItemBuilder {
+    DeployItemBuilder::new()
+        .with_address(account_addr)
+        .with_deploy_hash(DEPLOY_HASH)
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_payment_code(CONTRACT_STANDARD_PAYMENT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(account_addr)])
+}
+
+#[ignore]
+#[test]
+fn should_accept_deploy_signed_by_authorization_key() {
+    let keypair = keypair_from_seed(1);
+    let account_addr = keypair.public.to_bytes();
+    let mut builder = setup(&keypair);
+
+    let deploy = deploy_builder(account_addr)
+        .with_signature(account_addr, &keypair.sign(&DEPLOY_HASH).to_bytes())
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_accept_deploy_signed_by_secp256k1_key() {
+    let secp256k1 = secp256k1::Secp256k1::new();
+    let secret_key = secp256k1::SecretKey::from_slice(&[1; 32]).expect("should create secret key");
+    let public_key = TaggedPublicKey::secp256k1(
+        &secp256k1::PublicKey::from_secret_key(&secp256k1, &secret_key).serialize(),
+    )
+    .expect("should create public key");
+    let account_addr = public_key.to_public_key().value();
+    let mut builder = setup_account(account_addr);
+
+    let message = secp256k1::Message::from_slice(&DEPLOY_HASH).expect("should create message");
+    let signature = secp256k1.sign(&message, &secret_key).serialize_der();
+    let deploy = deploy_builder(account_addr)
+        .with_tagged_signature(public_key.clone(), &signature)
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request).expect_success().commit();
+
+    // A signature by another secp256k1 key is rejected
+    let other_secret_key =
+        secp256k1::SecretKey::from_slice(&[2; 32]).expect("should create secret key");
+    let signature = secp256k1.sign(&message, &other_secret_key).serialize_der();
+    let deploy = deploy_builder(account_addr)
+        .with_tagged_signature(public_key, &signature)
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(2)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_eq!(precondition_failure, "Invalid deploy signature");
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_with_invalid_signature() {
+    let keypair = keypair_from_seed(1);
+    let account_addr = keypair.public.to_bytes();
+    let mut builder = setup(&keypair);
+
+    let deploy = deploy_builder(account_addr)
+        .with_signature(account_addr, &keypair.sign(&[0; 32]).to_bytes())
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_eq!(precondition_failure, "Invalid deploy signature");
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_signed_by_key_not_meeting_deploy_threshold() {
+    let keypair = keypair_from_seed(1);
+    let other_keypair = keypair_from_seed(2);
+    let account_addr = keypair.public.to_bytes();
+    let other_addr = other_keypair.public.to_bytes();
+    let mut builder = setup(&keypair);
+
+    // The other key is not associated with the account, so its approval carries no weight
+    let deploy = deploy_builder(account_addr)
+        .with_authorization_keys(&[PublicKey::new(other_addr)])
+        .with_signature(other_addr, &other_keypair.sign(&DEPLOY_HASH).to_bytes())
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_eq!(
+        precondition_failure,
+        format!(
+            "{}",
+            Error::UnassociatedKeys(vec![PublicKey::new(other_addr)])
+        )
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_unsigned_deploy_unless_configured() {
+    // Test builders always accept unsigned deploys, so the engine state is driven directly
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let engine_state = EngineState::new(global_state, EngineConfig::new());
+    let post_state_hash = match engine_state
+        .commit_genesis(CorrelationId::new(), DEFAULT_GENESIS_CONFIG.clone())
+        .expect("should run genesis")
+    {
+        GenesisResult::Success {
+            post_state_hash, ..
+        } => post_state_hash,
+        result => panic!("unexpected result: {}", result),
+    };
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ())
+            .with_pre_state_hash(&post_state_hash.to_vec())
+            .build();
+    let results = engine_state
+        .run_execute(CorrelationId::new(), exec_request)
+        .expect("should find pre-state");
+
+    match results.as_slice() {
+        [ExecutionResult::Failure {
+            error: Error::NoDeploySignatures,
+            ..
+        }] => {}
+        results => panic!("unexpected results: {:?}", results),
+    }
+}
@@ -0,0 +1,71 @@
+use std::convert::TryInto;
+
+use engine_core::engine_state::block_info::BlockInfo;
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, Key, U512};
+
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: [u8; 32] = [42u8; 32];
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+
+#[ignore]
+#[test]
+fn should_speculatively_execute_without_committing() {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_deploy_hash([1; 32])
+        .with_session_code(
+            CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+            (ACCOUNT_1_ADDR, U512::from(TRANSFER_AMOUNT)),
+        )
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let pre_state_hash = builder.get_post_state_hash();
+    let prestate_hash: Blake2bHash = pre_state_hash
+        .as_slice()
+        .try_into()
+        .expect("should convert post state hash");
+
+    let speculative_result = builder
+        .get_engine_state()
+        .speculative_exec(
+            CorrelationId::new(),
+            prestate_hash,
+            BlockInfo::default(),
+            *DEFAULT_PROTOCOL_VERSION,
+            deploy.clone(),
+        )
+        .expect("should find pre-state root");
+
+    assert!(speculative_result.is_success());
+    assert_eq!(builder.get_post_state_hash(), pre_state_hash);
+    assert!(
+        builder
+            .query(None, Key::Account(ACCOUNT_1_ADDR), &[])
+            .is_err(),
+        "speculative execution should not create the target account"
+    );
+
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_result = &builder
+        .get_exec_response(0)
+        .expect("should have exec response")[0];
+
+    assert_eq!(speculative_result.cost(), exec_result.cost());
+    assert_eq!(speculative_result.effect(), exec_result.effect());
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_some());
+}
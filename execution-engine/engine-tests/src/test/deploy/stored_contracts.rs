@@ -1,6 +1,8 @@
 use std::collections::{hash_map::RandomState, BTreeMap};
 
-use engine_core::engine_state::{upgrade::ActivationPoint, CONV_RATE};
+use engine_core::engine_state::{
+    module_cache::DEFAULT_MODULE_CACHE_CAPACITY, upgrade::ActivationPoint, EngineConfig, CONV_RATE,
+};
 use engine_grpc_server::engine_server::ipc::DeployCode;
 use engine_shared::{
     additive_map::AdditiveMap, motes::Motes, stored_value::StoredValue, transform::Transform,
@@ -1460,3 +1462,61 @@ fn should_execute_stored_payment_and_session_code_with_new_major_version() {
         "calling upgraded stored payment and session code should work",
     );
 }
+
+fn exec_stored_payment_twice(engine_config: EngineConfig) -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        &format!("{}_stored.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
+        (STORE_AT_HASH.to_string(),),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder
+        .run_genesis(&*DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let stored_payment_contract_hash = builder.get_transforms()[0]
+        .iter()
+        .find_map(|(key, transform)| match (key, transform) {
+            (Key::Hash(hash), Transform::Write(StoredValue::Contract(_))) => Some(*hash),
+            _ => None,
+        })
+        .expect("stored_payment_contract_hash should exist");
+
+    for deploy_hash in &[[2; 32], [3; 32]] {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(&format!("{}.wasm", DO_NOTHING_NAME), ())
+            .with_stored_payment_hash(
+                stored_payment_contract_hash.to_vec(),
+                (U512::from(10_000_000),),
+            )
+            .with_authorization_keys(&[*DEFAULT_ACCOUNT_KEY])
+            .with_deploy_hash(*deploy_hash)
+            .build();
+        let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+        builder.exec(exec_request).expect_success().commit();
+    }
+
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_exec_stored_code_alike_with_and_without_module_cache() {
+    let cached = exec_stored_payment_twice(EngineConfig::new());
+    let uncached = exec_stored_payment_twice(EngineConfig::new().with_module_cache_capacity(0));
+
+    assert_eq!(
+        cached.get_engine_state().config().module_cache_capacity(),
+        DEFAULT_MODULE_CACHE_CAPACITY
+    );
+    for index in 1..3 {
+        assert_eq!(cached.exec_costs(index), uncached.exec_costs(index));
+    }
+    assert_eq!(cached.get_transforms(), uncached.get_transforms());
+}
@@ -10,5 +10,14 @@ pub mod counter;
 #[cfg(test)]
 pub mod mailing_list;
 
+#[cfg(test)]
+pub mod nft;
+
+#[cfg(test)]
+pub mod token;
+
+#[cfg(test)]
+pub mod token_payment;
+
 #[cfg(test)]
 pub mod vesting;
@@ -0,0 +1,328 @@
+use contract::args_parser::ArgsParser;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, bytesrepr::FromBytes, ApiError, CLTyped, Key};
+
+const CONTRACT_NFT_DEFINE: &str = "nft_define.wasm";
+const CONTRACT_NFT_CALL: &str = "nft_call.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account.wasm";
+const NFT_NAME: &str = "nft";
+const RESULT_KEY: &str = "result";
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const ACCOUNT_1_INITIAL_BALANCE: u64 = 10_000_000_000;
+const ACCOUNT_2_ADDR: [u8; 32] = [2u8; 32];
+
+const METHOD_MINT: &str = "mint";
+const METHOD_BURN: &str = "burn";
+const METHOD_TRANSFER: &str = "transfer";
+const METHOD_APPROVE: &str = "approve";
+const METHOD_OWNER_OF: &str = "owner_of";
+const METHOD_METADATA: &str = "metadata";
+const METHOD_BALANCE_OF: &str = "balance_of";
+const METHOD_TOKENS_OF: &str = "tokens_of";
+const METHOD_TOTAL_SUPPLY: &str = "total_supply";
+
+const ERROR_NOT_MINTER: u16 = 2;
+const ERROR_TOKEN_ALREADY_EXISTS: u16 = 3;
+const ERROR_TOKEN_NOT_FOUND: u16 = 4;
+const ERROR_NOT_AUTHORIZED: u16 = 5;
+
+const TOKEN_1: &str = "token-1";
+const TOKEN_2: &str = "token-2";
+const TOKEN_3: &str = "token-3";
+
+/// Funds `ACCOUNT_1_ADDR` and deploys an NFT contract with `DEFAULT_ACCOUNT_ADDR` as its minter,
+/// returning the hash of the contract.
+fn setup() -> (InMemoryWasmTestBuilder, Key) {
+    let fund_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, ACCOUNT_1_INITIAL_BALANCE),
+    )
+    .build();
+    let define_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_NFT_DEFINE, (NFT_NAME,))
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(fund_request)
+        .expect_success()
+        .commit()
+        .exec(define_request)
+        .expect_success()
+        .commit();
+
+    let nft_key = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(NFT_NAME)
+        .expect("should have nft key");
+    (builder, nft_key)
+}
+
+fn call_nft(builder: &mut InMemoryWasmTestBuilder, sender: [u8; 32], args: impl ArgsParser) {
+    let request = ExecuteRequestBuilder::standard(sender, CONTRACT_NFT_CALL, args).build();
+    builder.exec(request).commit();
+}
+
+fn metadata_of(token_id: &str) -> String {
+    format!(r#"{{"name":"{}"}}"#, token_id)
+}
+
+fn mint(builder: &mut InMemoryWasmTestBuilder, nft: Key, to: [u8; 32], token_id: &str) {
+    let args = (
+        nft,
+        METHOD_MINT,
+        PublicKey::new(to),
+        token_id,
+        metadata_of(token_id),
+    );
+    call_nft(builder, DEFAULT_ACCOUNT_ADDR, args);
+}
+
+fn query<T: CLTyped + FromBytes>(
+    builder: &mut InMemoryWasmTestBuilder,
+    args: impl ArgsParser,
+) -> T {
+    call_nft(builder, DEFAULT_ACCOUNT_ADDR, args);
+    builder.expect_success();
+    builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[RESULT_KEY])
+        .expect("should have result")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should have expected type")
+}
+
+fn owner_of(builder: &mut InMemoryWasmTestBuilder, nft: Key, token_id: &str) -> Option<[u8; 32]> {
+    let owner: Option<PublicKey> = query(builder, (nft, METHOD_OWNER_OF, token_id));
+    owner.map(PublicKey::value)
+}
+
+fn balance_of(builder: &mut InMemoryWasmTestBuilder, nft: Key, owner: [u8; 32]) -> u64 {
+    query(builder, (nft, METHOD_BALANCE_OF, PublicKey::new(owner)))
+}
+
+fn tokens_of(builder: &mut InMemoryWasmTestBuilder, nft: Key, owner: [u8; 32]) -> Vec<String> {
+    query(builder, (nft, METHOD_TOKENS_OF, PublicKey::new(owner)))
+}
+
+fn total_supply(builder: &mut InMemoryWasmTestBuilder, nft: Key) -> u64 {
+    query(builder, (nft, METHOD_TOTAL_SUPPLY))
+}
+
+fn assert_reverted_with(builder: &InMemoryWasmTestBuilder, user_error: u16) {
+    let index = builder.get_exec_responses_count() - 1;
+    let error_message = builder
+        .exec_error_message(index)
+        .expect("should have error message");
+    let expected = format!("Revert({})", u32::from(ApiError::User(user_error)));
+    assert!(error_message.contains(&expected), "{}", error_message);
+}
+
+#[ignore]
+#[test]
+fn should_mint_tokens_with_metadata() {
+    let (mut builder, nft) = setup();
+
+    mint(&mut builder, nft, ACCOUNT_1_ADDR, TOKEN_1);
+    builder.expect_success();
+    mint(&mut builder, nft, ACCOUNT_1_ADDR, TOKEN_2);
+    builder.expect_success();
+
+    assert_eq!(owner_of(&mut builder, nft, TOKEN_1), Some(ACCOUNT_1_ADDR));
+    assert_eq!(owner_of(&mut builder, nft, TOKEN_3), None);
+    let metadata: Option<String> = query(&mut builder, (nft, METHOD_METADATA, TOKEN_1));
+    assert_eq!(metadata, Some(metadata_of(TOKEN_1)));
+    assert_eq!(balance_of(&mut builder, nft, ACCOUNT_1_ADDR), 2);
+    assert_eq!(
+        tokens_of(&mut builder, nft, ACCOUNT_1_ADDR),
+        vec![TOKEN_1.to_string(), TOKEN_2.to_string()]
+    );
+    assert_eq!(total_supply(&mut builder, nft), 2);
+}
+
+#[ignore]
+#[test]
+fn should_only_allow_minter_to_mint() {
+    let (mut builder, nft) = setup();
+
+    let args = (
+        nft,
+        METHOD_MINT,
+        PublicKey::new(ACCOUNT_1_ADDR),
+        TOKEN_1,
+        metadata_of(TOKEN_1),
+    );
+    call_nft(&mut builder, ACCOUNT_1_ADDR, args);
+
+    assert_reverted_with(&builder, ERROR_NOT_MINTER);
+    assert_eq!(owner_of(&mut builder, nft, TOKEN_1), None);
+}
+
+#[ignore]
+#[test]
+fn should_not_mint_existing_or_burnt_token() {
+    let (mut builder, nft) = setup();
+
+    mint(&mut builder, nft, ACCOUNT_1_ADDR, TOKEN_1);
+    builder.expect_success();
+    mint(&mut builder, nft, ACCOUNT_2_ADDR, TOKEN_1);
+    assert_reverted_with(&builder, ERROR_TOKEN_ALREADY_EXISTS);
+
+    call_nft(&mut builder, ACCOUNT_1_ADDR, (nft, METHOD_BURN, TOKEN_1));
+    builder.expect_success();
+    mint(&mut builder, nft, ACCOUNT_2_ADDR, TOKEN_1);
+    assert_reverted_with(&builder, ERROR_TOKEN_ALREADY_EXISTS);
+}
+
+#[ignore]
+#[test]
+fn should_transfer_token_and_update_ownership() {
+    let (mut builder, nft) = setup();
+    for token_id in &[TOKEN_1, TOKEN_2, TOKEN_3] {
+        mint(&mut builder, nft, ACCOUNT_1_ADDR, token_id);
+        builder.expect_success();
+    }
+
+    let args = (
+        nft,
+        METHOD_TRANSFER,
+        PublicKey::new(ACCOUNT_2_ADDR),
+        TOKEN_1,
+    );
+    call_nft(&mut builder, ACCOUNT_1_ADDR, args);
+    builder.expect_success();
+
+    assert_eq!(owner_of(&mut builder, nft, TOKEN_1), Some(ACCOUNT_2_ADDR));
+    assert_eq!(balance_of(&mut builder, nft, ACCOUNT_1_ADDR), 2);
+    assert_eq!(balance_of(&mut builder, nft, ACCOUNT_2_ADDR), 1);
+    // The last token of the previous owner takes the place of the transferred one.
+    assert_eq!(
+        tokens_of(&mut builder, nft, ACCOUNT_1_ADDR),
+        vec![TOKEN_3.to_string(), TOKEN_2.to_string()]
+    );
+    assert_eq!(
+        tokens_of(&mut builder, nft, ACCOUNT_2_ADDR),
+        vec![TOKEN_1.to_string()]
+    );
+    assert_eq!(total_supply(&mut builder, nft), 3);
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_token_of_another_owner() {
+    let (mut builder, nft) = setup();
+    mint(&mut builder, nft, ACCOUNT_1_ADDR, TOKEN_1);
+    builder.expect_success();
+
+    let args = (
+        nft,
+        METHOD_TRANSFER,
+        PublicKey::new(ACCOUNT_2_ADDR),
+        TOKEN_1,
+    );
+    call_nft(&mut builder, DEFAULT_ACCOUNT_ADDR, args);
+    assert_reverted_with(&builder, ERROR_NOT_AUTHORIZED);
+
+    let args = (
+        nft,
+        METHOD_TRANSFER,
+        PublicKey::new(ACCOUNT_2_ADDR),
+        TOKEN_2,
+    );
+    call_nft(&mut builder, ACCOUNT_1_ADDR, args);
+    assert_reverted_with(&builder, ERROR_TOKEN_NOT_FOUND);
+
+    assert_eq!(owner_of(&mut builder, nft, TOKEN_1), Some(ACCOUNT_1_ADDR));
+}
+
+#[ignore]
+#[test]
+fn should_transfer_approved_token_once() {
+    let (mut builder, nft) = setup();
+    mint(&mut builder, nft, ACCOUNT_1_ADDR, TOKEN_1);
+    builder.expect_success();
+
+    let spender = Some(PublicKey::new(DEFAULT_ACCOUNT_ADDR));
+    call_nft(
+        &mut builder,
+        ACCOUNT_1_ADDR,
+        (nft, METHOD_APPROVE, spender, TOKEN_1),
+    );
+    builder.expect_success();
+
+    let args = (
+        nft,
+        METHOD_TRANSFER,
+        PublicKey::new(ACCOUNT_2_ADDR),
+        TOKEN_1,
+    );
+    call_nft(&mut builder, DEFAULT_ACCOUNT_ADDR, args);
+    builder.expect_success();
+    assert_eq!(owner_of(&mut builder, nft, TOKEN_1), Some(ACCOUNT_2_ADDR));
+
+    // Transfers clear the approval.
+    let args = (
+        nft,
+        METHOD_TRANSFER,
+        PublicKey::new(DEFAULT_ACCOUNT_ADDR),
+        TOKEN_1,
+    );
+    call_nft(&mut builder, DEFAULT_ACCOUNT_ADDR, args);
+    assert_reverted_with(&builder, ERROR_NOT_AUTHORIZED);
+}
+
+#[ignore]
+#[test]
+fn should_only_allow_owner_to_approve() {
+    let (mut builder, nft) = setup();
+    mint(&mut builder, nft, ACCOUNT_1_ADDR, TOKEN_1);
+    builder.expect_success();
+
+    let spender = Some(PublicKey::new(DEFAULT_ACCOUNT_ADDR));
+    call_nft(
+        &mut builder,
+        DEFAULT_ACCOUNT_ADDR,
+        (nft, METHOD_APPROVE, spender, TOKEN_1),
+    );
+    assert_reverted_with(&builder, ERROR_NOT_AUTHORIZED);
+}
+
+#[ignore]
+#[test]
+fn should_burn_token() {
+    let (mut builder, nft) = setup();
+    mint(&mut builder, nft, ACCOUNT_1_ADDR, TOKEN_1);
+    builder.expect_success();
+    mint(&mut builder, nft, ACCOUNT_1_ADDR, TOKEN_2);
+    builder.expect_success();
+
+    call_nft(
+        &mut builder,
+        DEFAULT_ACCOUNT_ADDR,
+        (nft, METHOD_BURN, TOKEN_1),
+    );
+    assert_reverted_with(&builder, ERROR_NOT_AUTHORIZED);
+    call_nft(&mut builder, ACCOUNT_1_ADDR, (nft, METHOD_BURN, TOKEN_1));
+    builder.expect_success();
+
+    assert_eq!(owner_of(&mut builder, nft, TOKEN_1), None);
+    let metadata: Option<String> = query(&mut builder, (nft, METHOD_METADATA, TOKEN_1));
+    assert_eq!(metadata, None);
+    assert_eq!(
+        tokens_of(&mut builder, nft, ACCOUNT_1_ADDR),
+        vec![TOKEN_2.to_string()]
+    );
+    assert_eq!(total_supply(&mut builder, nft), 1);
+
+    call_nft(&mut builder, ACCOUNT_1_ADDR, (nft, METHOD_BURN, TOKEN_1));
+    assert_reverted_with(&builder, ERROR_TOKEN_NOT_FOUND);
+}
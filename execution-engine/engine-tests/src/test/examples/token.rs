@@ -0,0 +1,380 @@
+use contract::args_parser::ArgsParser;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use token::{keys, methods, Error, Event};
+use types::{
+    account::PublicKey,
+    bytesrepr::{self, FromBytes, ToBytes},
+    ApiError, CLTyped, Key, U512,
+};
+
+const CONTRACT_TOKEN_DEFINE: &str = "token_define.wasm";
+const CONTRACT_TOKEN_CALL: &str = "token_call.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account.wasm";
+const TOKEN_NAME: &str = "token";
+const BALANCE_KEY: &str = "balance";
+const TOTAL_SUPPLY_KEY: &str = "total_supply";
+const INITIAL_SUPPLY: u64 = 1_000;
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const ACCOUNT_1_INITIAL_BALANCE: u64 = 10_000_000_000;
+const ACCOUNT_2_ADDR: [u8; 32] = [2u8; 32];
+
+/// Funds `ACCOUNT_1_ADDR` and deploys a token owned by `DEFAULT_ACCOUNT_ADDR`, returning the hash
+/// of the token contract.
+fn setup() -> (InMemoryWasmTestBuilder, [u8; 32]) {
+    let fund_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, ACCOUNT_1_INITIAL_BALANCE),
+    )
+    .build();
+    let define_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TOKEN_DEFINE,
+        (TOKEN_NAME, U512::from(INITIAL_SUPPLY)),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(fund_request)
+        .expect_success()
+        .commit()
+        .exec(define_request)
+        .expect_success()
+        .commit();
+
+    let token_hash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(TOKEN_NAME)
+        .and_then(Key::as_hash)
+        .expect("should have token hash");
+    (builder, token_hash)
+}
+
+fn call_token(builder: &mut InMemoryWasmTestBuilder, sender: [u8; 32], args: impl ArgsParser) {
+    let request = ExecuteRequestBuilder::standard(sender, CONTRACT_TOKEN_CALL, args).build();
+    builder.exec(request).commit();
+}
+
+fn transfer(
+    builder: &mut InMemoryWasmTestBuilder,
+    sender: [u8; 32],
+    token_hash: [u8; 32],
+    to: [u8; 32],
+    amount: u64,
+) {
+    let args = (
+        Key::Hash(token_hash),
+        methods::TRANSFER,
+        PublicKey::new(to),
+        U512::from(amount),
+    );
+    call_token(builder, sender, args);
+}
+
+fn approve(
+    builder: &mut InMemoryWasmTestBuilder,
+    sender: [u8; 32],
+    token_hash: [u8; 32],
+    spender: [u8; 32],
+    amount: u64,
+) {
+    let args = (
+        Key::Hash(token_hash),
+        methods::APPROVE,
+        PublicKey::new(spender),
+        U512::from(amount),
+    );
+    call_token(builder, sender, args);
+}
+
+fn transfer_from(
+    builder: &mut InMemoryWasmTestBuilder,
+    sender: [u8; 32],
+    token_hash: [u8; 32],
+    owner: [u8; 32],
+    to: [u8; 32],
+    amount: u64,
+) {
+    let args = (
+        Key::Hash(token_hash),
+        methods::TRANSFER_FROM,
+        PublicKey::new(owner),
+        PublicKey::new(to),
+        U512::from(amount),
+    );
+    call_token(builder, sender, args);
+}
+
+fn read_local<T: CLTyped + FromBytes>(
+    builder: &InMemoryWasmTestBuilder,
+    token_hash: [u8; 32],
+    key: Vec<u8>,
+) -> Option<T> {
+    let local_key = Key::local(token_hash, &key.to_bytes().expect("should serialize key"));
+    let stored_value = builder.query(None, local_key, &[]).ok()?;
+    let cl_value = stored_value.as_cl_value().expect("should be CLValue");
+    Some(
+        cl_value
+            .clone()
+            .into_t()
+            .expect("should have expected type"),
+    )
+}
+
+fn balance_of(builder: &InMemoryWasmTestBuilder, token_hash: [u8; 32], owner: [u8; 32]) -> U512 {
+    read_local(
+        builder,
+        token_hash,
+        keys::balance_key(&PublicKey::new(owner)),
+    )
+    .unwrap_or_default()
+}
+
+fn allowance(
+    builder: &InMemoryWasmTestBuilder,
+    token_hash: [u8; 32],
+    owner: [u8; 32],
+    spender: [u8; 32],
+) -> U512 {
+    let key = keys::allowance_key(&PublicKey::new(owner), &PublicKey::new(spender));
+    read_local(builder, token_hash, key).unwrap_or_default()
+}
+
+fn events(builder: &InMemoryWasmTestBuilder, token_hash: [u8; 32]) -> Vec<Event<PublicKey, U512>> {
+    let count: u64 = read_local(builder, token_hash, keys::event_count_key()).unwrap_or_default();
+    (0..count)
+        .map(|index| {
+            let bytes: Vec<u8> =
+                read_local(builder, token_hash, keys::event_key(index)).expect("should have event");
+            bytesrepr::deserialize(bytes).expect("should deserialize event")
+        })
+        .collect()
+}
+
+fn query_named_value(builder: &InMemoryWasmTestBuilder, account: [u8; 32], name: &str) -> U512 {
+    builder
+        .query(None, Key::Account(account), &[name])
+        .expect("should have named value")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should be U512")
+}
+
+fn assert_reverted_with(builder: &InMemoryWasmTestBuilder, error: Error) {
+    let index = builder.get_exec_responses_count() - 1;
+    let error_message = builder
+        .exec_error_message(index)
+        .expect("should have error message");
+    let expected = format!("Revert({})", u32::from(ApiError::from(error)));
+    assert!(error_message.contains(&expected), "{}", error_message);
+}
+
+#[ignore]
+#[test]
+fn should_mint_initial_supply_to_deployer() {
+    let (builder, token_hash) = setup();
+
+    let supply = U512::from(INITIAL_SUPPLY);
+    assert_eq!(
+        balance_of(&builder, token_hash, DEFAULT_ACCOUNT_ADDR),
+        supply
+    );
+    assert_eq!(
+        read_local::<U512>(&builder, token_hash, keys::total_supply_key()),
+        Some(supply)
+    );
+    assert_eq!(
+        events(&builder, token_hash),
+        vec![Event::Mint {
+            to: PublicKey::new(DEFAULT_ACCOUNT_ADDR),
+            amount: supply
+        }]
+    );
+}
+
+#[ignore]
+#[test]
+fn should_query_token_through_client() {
+    let (mut builder, token_hash) = setup();
+
+    let owner = PublicKey::new(DEFAULT_ACCOUNT_ADDR);
+    let args = (Key::Hash(token_hash), methods::BALANCE_OF, owner);
+    call_token(&mut builder, ACCOUNT_1_ADDR, args);
+    builder.expect_success();
+    let args = (Key::Hash(token_hash), methods::TOTAL_SUPPLY);
+    call_token(&mut builder, ACCOUNT_1_ADDR, args);
+    builder.expect_success();
+
+    let supply = U512::from(INITIAL_SUPPLY);
+    assert_eq!(
+        query_named_value(&builder, ACCOUNT_1_ADDR, BALANCE_KEY),
+        supply
+    );
+    assert_eq!(
+        query_named_value(&builder, ACCOUNT_1_ADDR, TOTAL_SUPPLY_KEY),
+        supply
+    );
+}
+
+#[ignore]
+#[test]
+fn should_transfer_tokens() {
+    let (mut builder, token_hash) = setup();
+
+    transfer(
+        &mut builder,
+        DEFAULT_ACCOUNT_ADDR,
+        token_hash,
+        ACCOUNT_1_ADDR,
+        300,
+    );
+    builder.expect_success();
+    transfer(
+        &mut builder,
+        ACCOUNT_1_ADDR,
+        token_hash,
+        ACCOUNT_2_ADDR,
+        100,
+    );
+    builder.expect_success();
+
+    assert_eq!(
+        balance_of(&builder, token_hash, DEFAULT_ACCOUNT_ADDR),
+        U512::from(700)
+    );
+    assert_eq!(
+        balance_of(&builder, token_hash, ACCOUNT_1_ADDR),
+        U512::from(200)
+    );
+    assert_eq!(
+        balance_of(&builder, token_hash, ACCOUNT_2_ADDR),
+        U512::from(100)
+    );
+    assert_eq!(
+        events(&builder, token_hash).last(),
+        Some(&Event::Transfer {
+            from: PublicKey::new(ACCOUNT_1_ADDR),
+            to: PublicKey::new(ACCOUNT_2_ADDR),
+            amount: U512::from(100)
+        })
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_more_than_balance() {
+    let (mut builder, token_hash) = setup();
+
+    transfer(
+        &mut builder,
+        ACCOUNT_1_ADDR,
+        token_hash,
+        DEFAULT_ACCOUNT_ADDR,
+        1,
+    );
+
+    assert_reverted_with(&builder, Error::InsufficientBalance);
+    assert_eq!(
+        balance_of(&builder, token_hash, DEFAULT_ACCOUNT_ADDR),
+        U512::from(INITIAL_SUPPLY)
+    );
+    assert_eq!(events(&builder, token_hash).len(), 1);
+}
+
+#[ignore]
+#[test]
+fn should_transfer_from_within_allowance() {
+    let (mut builder, token_hash) = setup();
+
+    approve(
+        &mut builder,
+        DEFAULT_ACCOUNT_ADDR,
+        token_hash,
+        ACCOUNT_1_ADDR,
+        300,
+    );
+    builder.expect_success();
+    transfer_from(
+        &mut builder,
+        ACCOUNT_1_ADDR,
+        token_hash,
+        DEFAULT_ACCOUNT_ADDR,
+        ACCOUNT_2_ADDR,
+        200,
+    );
+    builder.expect_success();
+
+    assert_eq!(
+        balance_of(&builder, token_hash, DEFAULT_ACCOUNT_ADDR),
+        U512::from(800)
+    );
+    assert_eq!(
+        balance_of(&builder, token_hash, ACCOUNT_1_ADDR),
+        U512::zero()
+    );
+    assert_eq!(
+        balance_of(&builder, token_hash, ACCOUNT_2_ADDR),
+        U512::from(200)
+    );
+    assert_eq!(
+        allowance(&builder, token_hash, DEFAULT_ACCOUNT_ADDR, ACCOUNT_1_ADDR),
+        U512::from(100)
+    );
+    assert_eq!(
+        events(&builder, token_hash)[1..],
+        [
+            Event::Approval {
+                owner: PublicKey::new(DEFAULT_ACCOUNT_ADDR),
+                spender: PublicKey::new(ACCOUNT_1_ADDR),
+                amount: U512::from(300)
+            },
+            Event::Transfer {
+                from: PublicKey::new(DEFAULT_ACCOUNT_ADDR),
+                to: PublicKey::new(ACCOUNT_2_ADDR),
+                amount: U512::from(200)
+            }
+        ]
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_from_beyond_allowance() {
+    let (mut builder, token_hash) = setup();
+
+    approve(
+        &mut builder,
+        DEFAULT_ACCOUNT_ADDR,
+        token_hash,
+        ACCOUNT_1_ADDR,
+        300,
+    );
+    builder.expect_success();
+    transfer_from(
+        &mut builder,
+        ACCOUNT_1_ADDR,
+        token_hash,
+        DEFAULT_ACCOUNT_ADDR,
+        ACCOUNT_1_ADDR,
+        301,
+    );
+
+    assert_reverted_with(&builder, Error::InsufficientAllowance);
+    assert_eq!(
+        balance_of(&builder, token_hash, ACCOUNT_1_ADDR),
+        U512::zero()
+    );
+    assert_eq!(
+        allowance(&builder, token_hash, DEFAULT_ACCOUNT_ADDR, ACCOUNT_1_ADDR),
+        U512::from(300)
+    );
+}
@@ -0,0 +1,175 @@
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use token::keys;
+use types::{account::PublicKey, bytesrepr::ToBytes, Key, U512};
+
+const CONTRACT_TOKEN_DEFINE: &str = "token_define.wasm";
+const CONTRACT_TOKEN_CALL: &str = "token_call.wasm";
+const CONTRACT_TOKEN_DEX_DEFINE: &str = "token_dex_define.wasm";
+const CONTRACT_TOKEN_PAYMENT: &str = "token_payment.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account.wasm";
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const TOKEN_NAME: &str = "token";
+const DEX_NAME: &str = "dex";
+const METHOD_TRANSFER: &str = "transfer";
+const INITIAL_SUPPLY: u64 = 1_000;
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const ACCOUNT_1_TOKENS: u64 = 500;
+const ACCOUNT_2_ADDR: [u8; 32] = [2u8; 32];
+const ACCOUNT_INITIAL_BALANCE: u64 = 10_000_000_000;
+const MOTES_PER_TOKEN: u64 = 1_000_000;
+
+fn named_hash(builder: &InMemoryWasmTestBuilder, name: &str) -> [u8; 32] {
+    builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(name)
+        .and_then(Key::as_hash)
+        .expect("should have contract hash")
+}
+
+/// Deploys a token and an exchange selling motes for it, and funds `ACCOUNT_1_ADDR` with tokens
+/// and `ACCOUNT_2_ADDR` with motes only.  Returns the hash of the token and of the exchange.
+fn setup() -> (InMemoryWasmTestBuilder, [u8; 32], [u8; 32]) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    for account in &[ACCOUNT_1_ADDR, ACCOUNT_2_ADDR] {
+        let fund_request = ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            (*account, ACCOUNT_INITIAL_BALANCE),
+        )
+        .build();
+        builder.exec(fund_request).expect_success().commit();
+    }
+
+    let define_token_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TOKEN_DEFINE,
+        (TOKEN_NAME, U512::from(INITIAL_SUPPLY)),
+    )
+    .build();
+    builder.exec(define_token_request).expect_success().commit();
+    let token_hash = named_hash(&builder, TOKEN_NAME);
+
+    let transfer_tokens_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TOKEN_CALL,
+        (
+            Key::Hash(token_hash),
+            METHOD_TRANSFER,
+            PublicKey::new(ACCOUNT_1_ADDR),
+            U512::from(ACCOUNT_1_TOKENS),
+        ),
+    )
+    .build();
+    builder
+        .exec(transfer_tokens_request)
+        .expect_success()
+        .commit();
+
+    let define_dex_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TOKEN_DEX_DEFINE,
+        (
+            DEX_NAME,
+            Key::Hash(token_hash),
+            U512::from(MOTES_PER_TOKEN),
+            *DEFAULT_PAYMENT * 10,
+        ),
+    )
+    .build();
+    builder.exec(define_dex_request).expect_success().commit();
+    let dex_hash = named_hash(&builder, DEX_NAME);
+
+    (builder, token_hash, dex_hash)
+}
+
+fn exec_with_token_payment(
+    builder: &mut InMemoryWasmTestBuilder,
+    sender: [u8; 32],
+    dex_hash: [u8; 32],
+) {
+    let deploy = DeployItemBuilder::new()
+        .with_address(sender)
+        .with_deploy_hash([42; 32])
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_payment_code(
+            CONTRACT_TOKEN_PAYMENT,
+            (*DEFAULT_PAYMENT, Key::Hash(dex_hash)),
+        )
+        .with_authorization_keys(&[PublicKey::new(sender)])
+        .build();
+    let request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(request).commit();
+}
+
+fn token_balance(builder: &InMemoryWasmTestBuilder, token_hash: [u8; 32], owner: [u8; 32]) -> U512 {
+    let key = keys::balance_key(&PublicKey::new(owner));
+    let local_key = Key::local(token_hash, &key.to_bytes().expect("should serialize key"));
+    builder
+        .query(None, local_key, &[])
+        .ok()
+        .map(|stored_value| {
+            stored_value
+                .as_cl_value()
+                .expect("should be CLValue")
+                .clone()
+                .into_t()
+                .expect("should be U512")
+        })
+        .unwrap_or_default()
+}
+
+fn main_purse_balance(builder: &InMemoryWasmTestBuilder, account: [u8; 32]) -> U512 {
+    let account = builder.get_account(account).expect("should have account");
+    builder.get_purse_balance(account.purse_id())
+}
+
+#[ignore]
+#[test]
+fn should_pay_for_deploy_with_tokens() {
+    let (mut builder, token_hash, dex_hash) = setup();
+    let motes_before = main_purse_balance(&builder, ACCOUNT_1_ADDR);
+
+    exec_with_token_payment(&mut builder, ACCOUNT_1_ADDR, dex_hash);
+    builder.expect_success();
+
+    let tokens_paid = *DEFAULT_PAYMENT / MOTES_PER_TOKEN;
+    assert_eq!(
+        token_balance(&builder, token_hash, ACCOUNT_1_ADDR),
+        U512::from(ACCOUNT_1_TOKENS) - tokens_paid
+    );
+    assert_eq!(
+        token_balance(&builder, token_hash, DEFAULT_ACCOUNT_ADDR),
+        U512::from(INITIAL_SUPPLY - ACCOUNT_1_TOKENS) + tokens_paid
+    );
+    // The motes were bought from the exchange, and only the unused part of the payment is
+    // refunded to the account.
+    assert!(main_purse_balance(&builder, ACCOUNT_1_ADDR) >= motes_before);
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_pay_without_enough_tokens() {
+    let (mut builder, token_hash, dex_hash) = setup();
+
+    exec_with_token_payment(&mut builder, ACCOUNT_2_ADDR, dex_hash);
+
+    assert!(builder.is_error());
+    assert_eq!(
+        token_balance(&builder, token_hash, ACCOUNT_1_ADDR),
+        U512::from(ACCOUNT_1_TOKENS)
+    );
+    assert_eq!(
+        token_balance(&builder, token_hash, DEFAULT_ACCOUNT_ADDR),
+        U512::from(INITIAL_SUPPLY - ACCOUNT_1_TOKENS)
+    );
+}
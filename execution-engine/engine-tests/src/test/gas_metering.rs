@@ -0,0 +1,120 @@
+use engine_core::engine_state::{upgrade::ActivationPoint, Error};
+use engine_shared::gas::Gas;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_GENESIS_CONFIG,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::gas_metering::{GasMetering, DEFAULT_FUEL_FLUSH_THRESHOLD};
+use types::ProtocolVersion;
+
+const CONTRACT_COMPUTE_LOOP: &str = "compute_loop.wasm";
+const CONTRACT_ENDLESS_LOOP: &str = "endless_loop.wasm";
+const ITERATIONS: u64 = 1_000;
+const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
+const NEW_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_1_0;
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+
+/// Runs genesis and upgrades to protocol version 1.1.0, metering gas with `gas_metering`.
+fn setup(gas_metering: GasMetering) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(NEW_PROTOCOL_VERSION)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_gas_metering(gas_metering)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    builder
+}
+
+fn compute_loop_cost(gas_metering: GasMetering) -> Gas {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_COMPUTE_LOOP, (ITERATIONS,))
+            .with_protocol_version(NEW_PROTOCOL_VERSION)
+            .build();
+
+    let mut builder = setup(gas_metering);
+
+    builder.exec(exec_request).expect_success().commit();
+
+    builder.exec_costs(0)[0]
+}
+
+#[ignore]
+#[test]
+fn should_charge_the_same_with_fuel_and_host_call_metering() {
+    let host_call_cost = compute_loop_cost(GasMetering::HostCall);
+    let fuel_cost = compute_loop_cost(GasMetering::Fuel {
+        flush_threshold: DEFAULT_FUEL_FLUSH_THRESHOLD,
+    });
+    let eager_fuel_cost = compute_loop_cost(GasMetering::Fuel { flush_threshold: 1 });
+
+    assert_eq!(host_call_cost, fuel_cost);
+    assert_eq!(host_call_cost, eager_fuel_cost);
+}
+
+#[ignore]
+#[test]
+fn should_run_out_of_gas_with_fuel_metering() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_ENDLESS_LOOP, ())
+            .with_protocol_version(NEW_PROTOCOL_VERSION)
+            .build();
+
+    let mut builder = setup(GasMetering::Fuel {
+        flush_threshold: DEFAULT_FUEL_FLUSH_THRESHOLD,
+    });
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+
+    let error_message = utils::get_error_message(response);
+    assert!(error_message.contains("GasLimit"), "{}", error_message);
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_with_flush_threshold_exceeding_fuel_counter() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(NEW_PROTOCOL_VERSION)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_gas_metering(GasMetering::Fuel {
+            flush_threshold: i32::max_value() as u32 + 1,
+        })
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(
+        upgrade_response.has_failed_deploy(),
+        "should have failed deploy"
+    );
+    assert_eq!(
+        upgrade_response.get_failed_deploy().message,
+        Error::InvalidUpgradeConfig.to_string()
+    );
+}
@@ -0,0 +1,80 @@
+use std::convert::TryInto;
+
+use engine_core::engine_state::{EngineState, SYSTEM_ACCOUNT_ADDR};
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+use engine_storage::global_state::{in_memory::InMemoryGlobalState, KeysPage};
+use engine_test_support::{
+    internal::{InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{bytesrepr::ToBytes, Key, KeyTag};
+
+fn keys_page(
+    engine_state: &EngineState<InMemoryGlobalState>,
+    state_hash: Blake2bHash,
+    key_tag: KeyTag,
+    after: Option<&[u8]>,
+    limit: usize,
+) -> KeysPage<Key> {
+    engine_state
+        .keys_with_prefix(CorrelationId::new(), state_hash, key_tag, after, limit)
+        .expect("should list keys")
+        .expect("should find root")
+}
+
+#[ignore]
+#[test]
+fn should_list_accounts_of_genesis_state_in_pages() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+    let state_hash: Blake2bHash = builder
+        .get_post_state_hash()
+        .as_slice()
+        .try_into()
+        .expect("should convert post state hash");
+    let engine_state = builder.get_engine_state();
+
+    let accounts = keys_page(
+        engine_state,
+        state_hash,
+        KeyTag::Account,
+        None,
+        usize::max_value(),
+    );
+    assert_eq!(accounts.next, None);
+    assert!(accounts.keys.iter().all(|key| key.tag() == KeyTag::Account));
+    assert!(accounts.keys.contains(&Key::Account(SYSTEM_ACCOUNT_ADDR)));
+    assert!(accounts.keys.contains(&Key::Account(DEFAULT_ACCOUNT_ADDR)));
+
+    for key_tag in &[KeyTag::Account, KeyTag::URef] {
+        let all_keys = keys_page(engine_state, state_hash, *key_tag, None, usize::max_value()).keys;
+        assert!(all_keys.windows(2).all(|pair| {
+            pair[0].to_bytes().expect("should serialize")
+                < pair[1].to_bytes().expect("should serialize")
+        }));
+
+        let mut paged_keys = Vec::new();
+        let mut after: Option<Vec<u8>> = None;
+        loop {
+            let page = keys_page(engine_state, state_hash, *key_tag, after.as_deref(), 1);
+            assert!(page.keys.len() <= 1);
+            paged_keys.extend(page.keys);
+            after = page.next;
+            if after.is_none() {
+                break;
+            }
+        }
+        assert_eq!(paged_keys, all_keys);
+    }
+
+    let missing = engine_state
+        .keys_with_prefix(
+            CorrelationId::new(),
+            [1u8; 32].into(),
+            KeyTag::Account,
+            None,
+            1,
+        )
+        .expect("should list keys");
+    assert_eq!(missing, None);
+}
@@ -1,7 +1,16 @@
+#[cfg(feature = "comm-integration")]
+mod comm_integration;
 mod contract_api;
+mod cost_baseline;
 mod deploy;
 mod examples;
+mod gas_metering;
+mod keys_with_prefix;
 mod metrics;
+mod query;
 mod regression;
+mod replay;
 mod system_contracts;
+mod trie_stats;
 mod upgrade;
+mod warm_up;
@@ -0,0 +1,58 @@
+use std::convert::TryFrom;
+
+use engine_test_support::{
+    internal::{InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{bytesrepr::ToBytes, purse_balance_key, CLType, CLValue, Key};
+
+#[ignore]
+#[test]
+fn should_report_cl_type_of_queried_cl_values() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let mint = builder.get_mint_contract_uref();
+    let purse_bytes = account
+        .purse_id()
+        .value()
+        .addr()
+        .to_bytes()
+        .expect("should serialize purse address");
+    let balance_mapping_key = Key::local(mint.addr(), &purse_bytes);
+    assert_eq!(
+        purse_balance_key(mint, account.purse_id()),
+        balance_mapping_key
+    );
+
+    assert_eq!(
+        builder.query_cl_type(None, balance_mapping_key, &[]),
+        Ok(Some(CLType::Key))
+    );
+
+    let balance_key: Key = builder
+        .query(None, balance_mapping_key, &[])
+        .and_then(|value| CLValue::try_from(value).map_err(|error| format!("{:?}", error)))
+        .and_then(|cl_value| cl_value.into_t().map_err(|error| format!("{:?}", error)))
+        .expect("should find balance key");
+
+    assert_eq!(
+        builder.query_cl_type(None, balance_key, &[]),
+        Ok(Some(CLType::U512))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_report_cl_type_of_queried_accounts() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    assert_eq!(
+        builder.query_cl_type(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[]),
+        Ok(None)
+    );
+}
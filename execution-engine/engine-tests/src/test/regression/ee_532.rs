@@ -2,6 +2,7 @@ use engine_core::engine_state::Error;
 use engine_test_support::internal::{
     ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
 };
+use types::account::PublicKey;
 
 const CONTRACT_EE_532_REGRESSION: &str = "ee_532_regression.wasm";
 const UNKNOWN_ADDR: [u8; 32] = [42u8; 32];
@@ -36,7 +37,10 @@ fn should_run_ee_532_get_uref_regression_test() {
     let message = deploy_result.error().map(|err| format!("{}", err));
     assert_eq!(
         message,
-        Some(format!("{}", Error::AuthorizationError)),
-        "expected AuthorizationError"
+        Some(format!(
+            "{}",
+            Error::AccountNotFound(PublicKey::new(UNKNOWN_ADDR))
+        )),
+        "expected AccountNotFound"
     )
 }
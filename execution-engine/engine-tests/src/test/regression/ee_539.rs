@@ -1,10 +1,16 @@
+use engine_shared::stored_value::StoredValue;
 use engine_test_support::{
-    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, KeyMatcher, TransformMatcher,
+        DEFAULT_GENESIS_CONFIG,
+    },
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::account::Weight;
+use types::{account::Weight, Key};
 
 const CONTRACT_EE_539_REGRESSION: &str = "ee_539_regression.wasm";
+const KEY_MANAGEMENT_THRESHOLD: u8 = 4;
+const DEPLOYMENT_THRESHOLD: u8 = 3;
 
 #[ignore]
 #[test]
@@ -14,7 +20,10 @@ fn should_run_ee_539_serialize_action_thresholds_regression() {
     let exec_request = ExecuteRequestBuilder::standard(
         DEFAULT_ACCOUNT_ADDR,
         CONTRACT_EE_539_REGRESSION,
-        (Weight::new(4), Weight::new(3)),
+        (
+            Weight::new(KEY_MANAGEMENT_THRESHOLD),
+            Weight::new(DEPLOYMENT_THRESHOLD),
+        ),
     )
     .build();
 
@@ -22,6 +31,19 @@ fn should_run_ee_539_serialize_action_thresholds_regression() {
         .run_genesis(&DEFAULT_GENESIS_CONFIG)
         .exec(exec_request)
         .expect_success()
+        .expect_transform(
+            KeyMatcher::key(Key::Account(DEFAULT_ACCOUNT_ADDR)),
+            TransformMatcher::write_where("account with updated thresholds", |value| match value {
+                StoredValue::Account(account) => {
+                    let action_thresholds = account.action_thresholds();
+                    *action_thresholds.key_management() == Weight::new(KEY_MANAGEMENT_THRESHOLD)
+                        && *action_thresholds.deployment() == Weight::new(DEPLOYMENT_THRESHOLD)
+                }
+                _ => false,
+            }),
+        )
+        // The associated key is stored in the account itself
+        .expect_no_write_to(Key::Account([123; 32]))
         .commit()
         .finish();
 }
@@ -0,0 +1,103 @@
+use std::{fs, path::Path};
+
+use tempfile::TempDir;
+
+use engine_test_support::{
+    internal::{
+        replay::{Journal, JournalEntry},
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, LmdbWasmTestBuilder,
+        DEFAULT_GENESIS_CONFIG,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::U512;
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const JOURNAL_FILE: &str = "journal";
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const ACCOUNT_1_FUNDS: u64 = 1_000_000_000;
+
+/// Runs genesis, a transfer and a call to a contract while recording them in a journal at
+/// `journal_path`, and returns the final post-state hash.
+fn record(journal_path: &Path) -> Vec<u8> {
+    let transfer_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(ACCOUNT_1_FUNDS)),
+    )
+    .build();
+    let do_nothing_request =
+        ExecuteRequestBuilder::standard(ACCOUNT_1_ADDR, CONTRACT_DO_NOTHING, ()).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .record_journal(journal_path)
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(transfer_request)
+        .expect_success()
+        .commit()
+        .exec(do_nothing_request)
+        .expect_success()
+        .commit();
+    builder.get_post_state_hash()
+}
+
+#[ignore]
+#[test]
+fn should_record_journal() {
+    let temp_dir = TempDir::new().expect("should create temp dir");
+    let journal_path = temp_dir.path().join(JOURNAL_FILE);
+    let post_state_hash = record(&journal_path);
+
+    let journal = Journal::read(&journal_path).expect("should read journal");
+    let entries = journal.entries();
+    assert_eq!(entries.len(), 5);
+    match &entries[0] {
+        JournalEntry::Genesis { .. } => (),
+        other => panic!("unexpected journal entry: {:?}", other),
+    }
+    match (&entries[1], &entries[2]) {
+        (JournalEntry::Exec { .. }, JournalEntry::Commit { .. }) => (),
+        other => panic!("unexpected journal entries: {:?}", other),
+    }
+    assert_eq!(
+        entries.last().and_then(JournalEntry::post_state_hash),
+        Some(post_state_hash.as_slice())
+    );
+}
+
+#[ignore]
+#[test]
+fn should_replay_journal_to_same_post_state_hash() {
+    let temp_dir = TempDir::new().expect("should create temp dir");
+    let journal_path = temp_dir.path().join(JOURNAL_FILE);
+    let post_state_hash = record(&journal_path);
+    let journal = Journal::read(&journal_path).expect("should read journal");
+
+    let mut in_memory_builder = InMemoryWasmTestBuilder::default();
+    journal.replay(&mut in_memory_builder);
+    assert_eq!(in_memory_builder.get_post_state_hash(), post_state_hash);
+
+    let data_dir = TempDir::new().expect("should create temp dir");
+    let mut lmdb_builder = LmdbWasmTestBuilder::new(data_dir.path());
+    journal.replay(&mut lmdb_builder);
+    assert_eq!(lmdb_builder.get_post_state_hash(), post_state_hash);
+}
+
+#[ignore]
+#[test]
+#[should_panic(expected = "Replay diverged at journal entry 4 (commit)")]
+fn should_fail_to_replay_journal_to_different_post_state_hash() {
+    let temp_dir = TempDir::new().expect("should create temp dir");
+    let journal_path = temp_dir.path().join(JOURNAL_FILE);
+    record(&journal_path);
+
+    // The journal ends with the post-state hash recorded after the last commit
+    let mut bytes = fs::read(&journal_path).expect("should read journal file");
+    *bytes.last_mut().expect("should have bytes") ^= 1;
+    fs::write(&journal_path, bytes).expect("should write journal file");
+
+    let journal = Journal::read(&journal_path).expect("should read journal");
+    journal.replay(&mut InMemoryWasmTestBuilder::default());
+}
@@ -3,7 +3,10 @@ use engine_core::engine_state::{
     SYSTEM_ACCOUNT_ADDR,
 };
 use engine_shared::{motes::Motes, stored_value::StoredValue};
-use engine_test_support::internal::{utils, InMemoryWasmTestBuilder, DEFAULT_WASM_COSTS};
+use engine_test_support::internal::{
+    utils, InMemoryWasmTestBuilder, DEFAULT_SLASH_PERCENTAGE, DEFAULT_UNBONDING_DELAY,
+    DEFAULT_WASM_COSTS,
+};
 use types::{account::PublicKey, Key, ProtocolVersion, U512};
 
 const MINT_INSTALL: &str = "mint_install.wasm";
@@ -59,6 +62,8 @@ fn should_run_genesis() {
         pos_installer_bytes,
         accounts,
         wasm_costs,
+        DEFAULT_UNBONDING_DELAY,
+        DEFAULT_SLASH_PERCENTAGE,
     );
 
     let mut builder = InMemoryWasmTestBuilder::default();
@@ -141,6 +146,8 @@ fn should_fail_if_bad_mint_install_contract_is_provided() {
             pos_installer_bytes,
             accounts,
             wasm_costs,
+            DEFAULT_UNBONDING_DELAY,
+            DEFAULT_SLASH_PERCENTAGE,
         )
     };
 
@@ -189,6 +196,8 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
             pos_installer_bytes,
             accounts,
             wasm_costs,
+            DEFAULT_UNBONDING_DELAY,
+            DEFAULT_SLASH_PERCENTAGE,
         )
     };
 
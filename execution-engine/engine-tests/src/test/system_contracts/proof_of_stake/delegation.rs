@@ -0,0 +1,167 @@
+use engine_core::engine_state::genesis::{GenesisAccount, POS_BONDING_PURSE};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_PROTOCOL_VERSION,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{PublicKey, PurseId},
+    Key, U512,
+};
+
+const CONTRACT_DELEGATING: &str = "delegating.wasm";
+const CONTRACT_UNDELEGATING: &str = "undelegating.wasm";
+const CONTRACT_REDELEGATING: &str = "redelegating.wasm";
+
+const VALIDATOR_1_ADDR: [u8; 32] = [42; 32];
+const VALIDATOR_2_ADDR: [u8; 32] = [43; 32];
+const GENESIS_VALIDATOR_STAKE: u64 = 50_000;
+const DELEGATED_AMOUNT: u64 = 50_000;
+const REDELEGATED_AMOUNT: u64 = 20_000;
+
+/// Returns the value encoded in the name of a PoS named key starting with `prefix`.
+fn get_pos_named_key_amount(builder: &InMemoryWasmTestBuilder, prefix: &str) -> Option<U512> {
+    builder
+        .get_pos_contract()
+        .named_keys()
+        .keys()
+        .find(|name| name.starts_with(prefix))
+        .map(|name| U512::from_dec_str(&name[prefix.len()..]).expect("should parse amount"))
+}
+
+fn get_stake(builder: &InMemoryWasmTestBuilder, validator: [u8; 32]) -> Option<U512> {
+    let prefix = format!("v_{}_", base16::encode_lower(&validator));
+    get_pos_named_key_amount(builder, &prefix)
+}
+
+fn get_delegation(
+    builder: &InMemoryWasmTestBuilder,
+    delegator: [u8; 32],
+    validator: [u8; 32],
+) -> Option<U512> {
+    let prefix = format!(
+        "d_{}_{}_",
+        base16::encode_lower(&delegator),
+        base16::encode_lower(&validator)
+    );
+    get_pos_named_key_amount(builder, &prefix)
+}
+
+fn get_pos_bonding_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let purse_id = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(POS_BONDING_PURSE)
+        .and_then(Key::as_uref)
+        .map(|uref| PurseId::new(*uref))
+        .expect("should find PoS bonding purse");
+    builder.get_purse_balance(purse_id)
+}
+
+/// All bonded and delegated motes are held in the bonding purse.
+fn assert_bonding_purse_holds_stakes(builder: &InMemoryWasmTestBuilder) {
+    let total_stakes = [VALIDATOR_1_ADDR, VALIDATOR_2_ADDR]
+        .iter()
+        .filter_map(|validator| get_stake(builder, *validator))
+        .fold(U512::zero(), |sum, stake| sum + stake);
+    assert_eq!(get_pos_bonding_purse_balance(builder), total_stakes);
+}
+
+#[ignore]
+#[test]
+fn should_delegate_redelegate_and_undelegate() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        for validator in &[VALIDATOR_1_ADDR, VALIDATOR_2_ADDR] {
+            tmp.push(GenesisAccount::new(
+                PublicKey::new(*validator),
+                Motes::new(GENESIS_VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+                Motes::new(GENESIS_VALIDATOR_STAKE.into()),
+            ));
+        }
+        tmp
+    };
+    let genesis_config = utils::create_genesis_config(accounts);
+
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DELEGATING,
+        (PublicKey::new(VALIDATOR_1_ADDR), DELEGATED_AMOUNT),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&genesis_config)
+        .exec(exec_request_1)
+        .expect_success()
+        .commit();
+
+    // The delegator's share of the deploy's payment is only paid out at the end of the era.
+    assert_eq!(
+        get_delegation(&builder, DEFAULT_ACCOUNT_ADDR, VALIDATOR_1_ADDR),
+        Some(U512::from(DELEGATED_AMOUNT))
+    );
+    assert_bonding_purse_holds_stakes(&builder);
+
+    builder.distribute_rewards(*DEFAULT_PROTOCOL_VERSION, 1);
+
+    // The delegation was increased by the delegator's share of the deploy's payment.
+    let delegation = get_delegation(&builder, DEFAULT_ACCOUNT_ADDR, VALIDATOR_1_ADDR)
+        .expect("should have delegation to validator 1");
+    assert!(delegation > U512::from(DELEGATED_AMOUNT));
+    assert_eq!(
+        get_stake(&builder, VALIDATOR_1_ADDR),
+        Some(U512::from(GENESIS_VALIDATOR_STAKE) + delegation)
+    );
+    assert_bonding_purse_holds_stakes(&builder);
+
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_REDELEGATING,
+        (
+            PublicKey::new(VALIDATOR_1_ADDR),
+            PublicKey::new(VALIDATOR_2_ADDR),
+            Some(REDELEGATED_AMOUNT),
+        ),
+    )
+    .build();
+
+    builder.exec(exec_request_2).expect_success().commit();
+
+    let delegation_1 = get_delegation(&builder, DEFAULT_ACCOUNT_ADDR, VALIDATOR_1_ADDR)
+        .expect("should have delegation to validator 1");
+    let delegation_2 = get_delegation(&builder, DEFAULT_ACCOUNT_ADDR, VALIDATOR_2_ADDR)
+        .expect("should have delegation to validator 2");
+    assert!(delegation_1 >= delegation - U512::from(REDELEGATED_AMOUNT));
+    assert!(delegation_2 >= U512::from(REDELEGATED_AMOUNT));
+    assert_eq!(
+        get_stake(&builder, VALIDATOR_2_ADDR),
+        Some(U512::from(GENESIS_VALIDATOR_STAKE) + delegation_2)
+    );
+    assert_bonding_purse_holds_stakes(&builder);
+
+    let exec_request_3 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_UNDELEGATING,
+        (PublicKey::new(VALIDATOR_1_ADDR), None::<u64>),
+    )
+    .build();
+
+    builder.exec(exec_request_3).expect_success().commit();
+
+    // Validator 1 is left with its own bond, which is not affected by rewards.
+    assert_eq!(
+        get_delegation(&builder, DEFAULT_ACCOUNT_ADDR, VALIDATOR_1_ADDR),
+        None
+    );
+    assert_eq!(
+        get_stake(&builder, VALIDATOR_1_ADDR),
+        Some(U512::from(GENESIS_VALIDATOR_STAKE))
+    );
+    assert!(get_delegation(&builder, DEFAULT_ACCOUNT_ADDR, VALIDATOR_2_ADDR).is_some());
+    assert_bonding_purse_holds_stakes(&builder);
+}
@@ -8,13 +8,13 @@ use engine_shared::{account::Account, motes::Motes};
 use engine_test_support::{
     internal::{
         utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
-        DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+        UpgradeRequestBuilder, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
     },
     DEFAULT_ACCOUNT_ADDR,
 };
 use types::{
     account::{PublicKey, PurseId},
-    Key, U512,
+    Key, ProtocolVersion, U512,
 };
 
 const CONTRACT_FINALIZE_PAYMENT: &str = "pos_finalize_payment.wasm";
@@ -23,6 +23,8 @@ const FINALIZE_PAYMENT: &str = "pos_finalize_payment.wasm";
 const LOCAL_REFUND_PURSE: &str = "local_refund_purse";
 const POS_REFUND_PURSE_NAME: &str = "pos_refund_purse";
 
+const REFUND_PERCENTAGE: u8 = 40;
+
 const SYSTEM_ADDR: [u8; 32] = [0u8; 32];
 const ACCOUNT_ADDR: [u8; 32] = [1u8; 32];
 
@@ -163,6 +165,78 @@ fn finalize_payment_should_refund_to_specified_purse() {
     );
 }
 
+#[ignore]
+#[test]
+fn finalize_payment_should_refund_configured_percentage_of_unspent_payment() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let payment_amount = *DEFAULT_PAYMENT;
+    let refund_purse_flag: u8 = 1;
+    let args: (U512, u8, Option<U512>, Option<PublicKey>) =
+        (payment_amount, refund_purse_flag, None, None);
+
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(ProtocolVersion::V1_0_0)
+        .with_new_protocol_version(new_protocol_version)
+        .with_new_refund_percentage(REFUND_PERCENTAGE)
+        .build();
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let rewards_pre_balance = get_pos_rewards_purse_balance(&builder);
+
+    let exec_request = {
+        let genesis_public_key = PublicKey::new(DEFAULT_ACCOUNT_ADDR);
+
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash([1; 32])
+            .with_session_code("do_nothing.wasm", ())
+            .with_payment_code(FINALIZE_PAYMENT, args)
+            .with_authorization_keys(&[genesis_public_key])
+            .build();
+
+        ExecuteRequestBuilder::new()
+            .push_deploy(deploy)
+            .with_protocol_version(new_protocol_version)
+            .build()
+    };
+    builder.exec(exec_request).expect_success().commit();
+
+    let spent_amount: U512 = {
+        let response = builder
+            .get_exec_response(0)
+            .expect("there should be a response");
+
+        let success_result = utils::get_success_result(response);
+        Motes::from_gas(success_result.cost(), CONV_RATE)
+            .expect("should have motes")
+            .value()
+    };
+    let expected_refund =
+        (payment_amount - spent_amount) * U512::from(REFUND_PERCENTAGE) / U512::from(100);
+
+    let refund_post_balance =
+        get_named_account_balance(&builder, DEFAULT_ACCOUNT_ADDR, LOCAL_REFUND_PURSE)
+            .expect("should have refund balance");
+    assert_eq!(
+        refund_post_balance, expected_refund,
+        "user should get the configured share of the unspent payment"
+    );
+
+    // validators get the amount spent and the rest of the unspent payment
+    let rewards_post_balance = get_pos_rewards_purse_balance(&builder);
+    assert_eq!(
+        rewards_post_balance,
+        rewards_pre_balance + payment_amount - expected_refund
+    );
+    assert!(
+        get_pos_payment_purse_balance(&builder).is_zero(),
+        "payment purse should ends with zero balance"
+    );
+}
+
 // ------------- utility functions -------------------- //
 
 fn get_pos_payment_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
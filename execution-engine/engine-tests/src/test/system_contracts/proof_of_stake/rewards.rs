@@ -0,0 +1,216 @@
+use std::convert::TryFrom;
+
+use base16;
+
+use engine_core::engine_state::{
+    genesis::{GenesisAccount, GenesisConfig, POS_REWARDS_PURSE},
+    upgrade::ActivationPoint,
+};
+use engine_shared::{
+    motes::Motes,
+    newtypes::{Blake2bHash, CorrelationId},
+};
+use engine_storage::protocol_data::{
+    ChainParameters, DEFAULT_CONV_RATE, DEFAULT_MAX_PAYMENT, DEFAULT_NATIVE_TRANSFER_COST,
+};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_ACCOUNTS, DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_TIMESTAMP, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_SLASH_PERCENTAGE, DEFAULT_UNBONDING_DELAY, DEFAULT_WASM_COSTS,
+        MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{PublicKey, PurseId},
+    Key, ProtocolVersion, U512,
+};
+
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
+
+const TEST_BOND: &str = "bond";
+
+const GENESIS_VALIDATOR: [u8; 32] = [42; 32];
+const GENESIS_VALIDATOR_STAKE: u64 = 50_000;
+const ACCOUNT_STAKE: u64 = 100_000;
+/// 1% of the total stakes per era.
+const SEIGNIORAGE_RATE: u64 = 10_000_000;
+const NEW_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_1_0;
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+
+fn get_pos_purse(builder: &InMemoryWasmTestBuilder, purse_name: &str) -> Option<PurseId> {
+    builder
+        .get_pos_contract()
+        .named_keys()
+        .get(purse_name)
+        .and_then(Key::as_uref)
+        .map(|uref| PurseId::new(*uref))
+}
+
+fn get_validator_reward(builder: &InMemoryWasmTestBuilder, validator: [u8; 32]) -> Option<U512> {
+    let purse_name = format!("pos_reward_purse_{}", base16::encode_lower(&validator));
+    get_pos_purse(builder, &purse_name).map(|purse_id| builder.get_purse_balance(purse_id))
+}
+
+fn get_rewards_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let purse_id = get_pos_purse(builder, POS_REWARDS_PURSE).expect("should find rewards purse");
+    builder.get_purse_balance(purse_id)
+}
+
+/// Runs genesis, which leaves the seigniorage rate at zero, and bonds the default account.
+fn setup() -> InMemoryWasmTestBuilder {
+    let genesis_config = {
+        let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        accounts.push(GenesisAccount::new(
+            PublicKey::new(GENESIS_VALIDATOR),
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()),
+        ));
+        GenesisConfig::new(
+            DEFAULT_CHAIN_NAME.to_string(),
+            DEFAULT_GENESIS_TIMESTAMP,
+            *DEFAULT_PROTOCOL_VERSION,
+            utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+            utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+            accounts,
+            *DEFAULT_WASM_COSTS,
+            DEFAULT_UNBONDING_DELAY,
+            DEFAULT_SLASH_PERCENTAGE,
+        )
+    };
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (String::from(TEST_BOND), U512::from(ACCOUNT_STAKE)),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&genesis_config)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+/// Upgrades to protocol version 1.1.0, minting `seigniorage_rate` billionths of the total stakes
+/// per era.
+fn upgrade_seigniorage_rate(builder: &mut InMemoryWasmTestBuilder, seigniorage_rate: u64) {
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(NEW_PROTOCOL_VERSION)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_chain_parameters(ChainParameters::new(
+            DEFAULT_MAX_PAYMENT,
+            DEFAULT_CONV_RATE,
+            0,
+            seigniorage_rate,
+            DEFAULT_NATIVE_TRANSFER_COST,
+        ))
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+}
+
+#[ignore]
+#[test]
+fn should_distribute_seigniorage_pro_rata_to_stakes() {
+    let mut builder = setup();
+    upgrade_seigniorage_rate(&mut builder, SEIGNIORAGE_RATE);
+
+    let rewards_before = get_rewards_purse_balance(&builder);
+
+    builder.distribute_rewards(NEW_PROTOCOL_VERSION, 1);
+
+    let total_stakes = GENESIS_VALIDATOR_STAKE + ACCOUNT_STAKE;
+    let seigniorage = total_stakes * SEIGNIORAGE_RATE / 1_000_000_000;
+    assert_eq!(
+        get_validator_reward(&builder, GENESIS_VALIDATOR),
+        Some(U512::from(
+            seigniorage * GENESIS_VALIDATOR_STAKE / total_stakes
+        ))
+    );
+    assert_eq!(
+        get_validator_reward(&builder, DEFAULT_ACCOUNT_ADDR),
+        Some(U512::from(seigniorage * ACCOUNT_STAKE / total_stakes))
+    );
+    // The shares add up to the seigniorage, so nothing is left for the rewards purse.
+    assert_eq!(get_rewards_purse_balance(&builder), rewards_before);
+
+    // The reward purses are reused in the next era.
+    builder.distribute_rewards(NEW_PROTOCOL_VERSION, 2);
+
+    assert_eq!(
+        get_validator_reward(&builder, GENESIS_VALIDATOR),
+        Some(U512::from(
+            2 * seigniorage * GENESIS_VALIDATOR_STAKE / total_stakes
+        ))
+    );
+    assert_eq!(
+        get_validator_reward(&builder, DEFAULT_ACCOUNT_ADDR),
+        Some(U512::from(2 * seigniorage * ACCOUNT_STAKE / total_stakes))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_distribute_rewards_for_the_same_era_twice() {
+    let mut builder = setup();
+    upgrade_seigniorage_rate(&mut builder, SEIGNIORAGE_RATE);
+
+    builder.distribute_rewards(NEW_PROTOCOL_VERSION, 1);
+
+    let pre_state_hash = Blake2bHash::try_from(builder.get_post_state_hash().as_slice())
+        .expect("should convert post_state_hash");
+    for era_id in 0..=1 {
+        let result = builder.get_engine_state().distribute_rewards(
+            CorrelationId::new(),
+            NEW_PROTOCOL_VERSION,
+            pre_state_hash,
+            era_id,
+        );
+        assert!(result.is_err(), "era {} should already be rewarded", era_id);
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_distribute_rewards_with_zero_seigniorage_rate() {
+    let mut builder = setup();
+
+    let rewards_before = get_rewards_purse_balance(&builder);
+
+    builder.distribute_rewards(*DEFAULT_PROTOCOL_VERSION, 1);
+
+    assert_eq!(get_validator_reward(&builder, GENESIS_VALIDATOR), None);
+    assert_eq!(get_validator_reward(&builder, DEFAULT_ACCOUNT_ADDR), None);
+    assert_eq!(get_rewards_purse_balance(&builder), rewards_before);
+}
+
+#[ignore]
+#[test]
+fn should_distribute_rewards_at_upgraded_seigniorage_rate() {
+    let mut builder = setup();
+
+    // Nothing is minted until an upgrade sets the seigniorage rate.
+    builder.distribute_rewards(*DEFAULT_PROTOCOL_VERSION, 1);
+    assert_eq!(get_validator_reward(&builder, DEFAULT_ACCOUNT_ADDR), None);
+
+    upgrade_seigniorage_rate(&mut builder, SEIGNIORAGE_RATE);
+    builder.distribute_rewards(NEW_PROTOCOL_VERSION, 2);
+
+    let total_stakes = GENESIS_VALIDATOR_STAKE + ACCOUNT_STAKE;
+    let seigniorage = total_stakes * SEIGNIORAGE_RATE / 1_000_000_000;
+    assert_eq!(
+        get_validator_reward(&builder, DEFAULT_ACCOUNT_ADDR),
+        Some(U512::from(seigniorage * ACCOUNT_STAKE / total_stakes))
+    );
+}
@@ -0,0 +1,143 @@
+use base16;
+
+use engine_core::engine_state::genesis::{
+    GenesisAccount, GenesisConfig, POS_BONDING_PURSE, POS_REWARDS_PURSE,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_TIMESTAMP, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_UNBONDING_DELAY, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{PublicKey, PurseId},
+    Key, U512,
+};
+
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
+
+const TEST_BOND: &str = "bond";
+
+const GENESIS_VALIDATOR: [u8; 32] = [42; 32];
+const GENESIS_VALIDATOR_STAKE: u64 = 50_000;
+const ACCOUNT_STAKE: u64 = 100_000;
+const SLASH_PERCENTAGE: u8 = 10;
+
+fn get_pos_purse_balance(builder: &InMemoryWasmTestBuilder, purse_name: &str) -> U512 {
+    let purse_id = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(purse_name)
+        .and_then(Key::as_uref)
+        .map(|uref| PurseId::new(*uref))
+        .expect("should find PoS purse");
+    builder.get_purse_balance(purse_id)
+}
+
+fn has_stake(builder: &InMemoryWasmTestBuilder, validator: [u8; 32], stake: u64) -> bool {
+    let lookup_key = format!("v_{}_{}", base16::encode_lower(&validator), stake);
+    builder
+        .get_pos_contract()
+        .named_keys()
+        .contains_key(&lookup_key)
+}
+
+fn setup(slash_percentage: u8) -> InMemoryWasmTestBuilder {
+    let genesis_config = {
+        let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        accounts.push(GenesisAccount::new(
+            PublicKey::new(GENESIS_VALIDATOR),
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()),
+        ));
+        GenesisConfig::new(
+            DEFAULT_CHAIN_NAME.to_string(),
+            DEFAULT_GENESIS_TIMESTAMP,
+            *DEFAULT_PROTOCOL_VERSION,
+            utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+            utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+            accounts,
+            *DEFAULT_WASM_COSTS,
+            DEFAULT_UNBONDING_DELAY,
+            slash_percentage,
+        )
+    };
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (String::from(TEST_BOND), U512::from(ACCOUNT_STAKE)),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&genesis_config)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_slash_validator_and_move_stakes_to_rewards_purse() {
+    let mut builder = setup(SLASH_PERCENTAGE);
+
+    let rewards_before = get_pos_purse_balance(&builder, POS_REWARDS_PURSE);
+    let slashed = GENESIS_VALIDATOR_STAKE * u64::from(SLASH_PERCENTAGE) / 100;
+
+    // The same evidence reported twice must not slash the validator twice.
+    builder.slash(
+        *DEFAULT_PROTOCOL_VERSION,
+        vec![
+            PublicKey::new(GENESIS_VALIDATOR),
+            PublicKey::new(GENESIS_VALIDATOR),
+        ],
+    );
+
+    assert!(has_stake(
+        &builder,
+        GENESIS_VALIDATOR,
+        GENESIS_VALIDATOR_STAKE - slashed
+    ));
+    assert!(has_stake(&builder, DEFAULT_ACCOUNT_ADDR, ACCOUNT_STAKE));
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        U512::from(GENESIS_VALIDATOR_STAKE + ACCOUNT_STAKE - slashed)
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_REWARDS_PURSE),
+        rewards_before + U512::from(slashed)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_slash_validator_with_zero_slash_percentage() {
+    let mut builder = setup(0);
+
+    let rewards_before = get_pos_purse_balance(&builder, POS_REWARDS_PURSE);
+
+    builder.slash(
+        *DEFAULT_PROTOCOL_VERSION,
+        vec![PublicKey::new(GENESIS_VALIDATOR)],
+    );
+
+    assert!(has_stake(
+        &builder,
+        GENESIS_VALIDATOR,
+        GENESIS_VALIDATOR_STAKE
+    ));
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        U512::from(GENESIS_VALIDATOR_STAKE + ACCOUNT_STAKE)
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_REWARDS_PURSE),
+        rewards_before
+    );
+}
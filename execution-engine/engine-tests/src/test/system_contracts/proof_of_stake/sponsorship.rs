@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+
+use engine_core::engine_state::{upgrade::UpgradeConfig, CONV_RATE};
+use engine_grpc_server::engine_server::ipc::UpgradeRequest;
+use engine_shared::{gas::Gas, motes::Motes};
+use engine_storage::protocol_data::Sponsorship;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PurseId, AccessRights, Key, ProtocolVersion, URef, U512};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_DO_NOTHING_STORED: &str = "do_nothing_stored.wasm";
+const CONTRACT_REVERT_STORED: &str = "revert_stored.wasm";
+const CONTRACT_TRANSFER_MAIN_PURSE_TO_NEW_PURSE: &str = "transfer_main_purse_to_new_purse.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const DO_NOTHING_STORED_NAME: &str = "do_nothing_stored";
+const REVERT_STORED_NAME: &str = "revert_stored";
+const DESTINATION_HASH: &str = "hash";
+const SPONSORSHIP_PURSE_NAME: &str = "sponsorship_purse";
+
+const ACCOUNT_ADDR: [u8; 32] = [1u8; 32];
+const ACCOUNT_FUNDS: u64 = 1_000_000_000;
+const SPONSORSHIP_FUNDS: u64 = 1_000_000_000;
+const UNLIMITED_ALLOWANCE: u64 = 1_000_000_000;
+
+const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
+
+fn sponsored_protocol_version() -> ProtocolVersion {
+    let sem_ver = PROTOCOL_VERSION.value();
+    ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1)
+}
+
+struct Setup {
+    builder: InMemoryWasmTestBuilder,
+    contract_hash: [u8; 32],
+    reverting_contract_hash: [u8; 32],
+    sponsorship_purse: URef,
+}
+
+/// Funds a sponsorship purse and a second account, stores a contract and a reverting contract by
+/// hash and sponsors calls to both with the given allowance, in gas, in the next protocol version.
+fn setup(sponsorship_funds: u64, allowance: u64) -> Setup {
+    setup_with_sponsorship_purse(sponsorship_funds, allowance, None)
+}
+
+/// Like [`setup`], but pays the refunds out of `sponsorship_purse` instead of the funded purse,
+/// if given.
+fn setup_with_sponsorship_purse(
+    sponsorship_funds: u64,
+    allowance: u64,
+    sponsorship_purse: Option<URef>,
+) -> Setup {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let exec_requests = vec![
+        ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_MAIN_PURSE_TO_NEW_PURSE,
+            (SPONSORSHIP_PURSE_NAME, U512::from(sponsorship_funds)),
+        )
+        .build(),
+        ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_DO_NOTHING_STORED,
+            (DESTINATION_HASH,),
+        )
+        .build(),
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_REVERT_STORED, ()).build(),
+        ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+            (ACCOUNT_ADDR, U512::from(ACCOUNT_FUNDS)),
+        )
+        .build(),
+    ];
+    for exec_request in exec_requests {
+        builder.exec(exec_request).expect_success().commit();
+    }
+
+    let named_keys = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .clone();
+    let contract_hash = named_keys
+        .get(DO_NOTHING_STORED_NAME)
+        .and_then(Key::as_hash)
+        .expect("should have contract hash");
+    let reverting_contract_hash = named_keys
+        .get(REVERT_STORED_NAME)
+        .and_then(Key::as_hash)
+        .expect("should have reverting contract hash");
+    let funded_purse = named_keys
+        .get(SPONSORSHIP_PURSE_NAME)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should have sponsorship purse");
+    let sponsorship_purse = sponsorship_purse.unwrap_or(funded_purse);
+
+    let sponsorships = {
+        let mut ret = BTreeMap::new();
+        let sponsorship = Sponsorship::new(Gas::new(U512::from(allowance)), sponsorship_purse);
+        ret.insert(contract_hash, sponsorship);
+        ret.insert(reverting_contract_hash, sponsorship);
+        ret
+    };
+    let mut post_state_hash = [0u8; 32];
+    post_state_hash.copy_from_slice(&builder.get_post_state_hash());
+    let upgrade_config = UpgradeConfig::new(
+        post_state_hash.into(),
+        PROTOCOL_VERSION,
+        sponsored_protocol_version(),
+        None,
+        None,
+        None,
+        None,
+    )
+    .with_sponsorships(sponsorships);
+    let mut upgrade_request = UpgradeRequest::from(upgrade_config);
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    Setup {
+        builder,
+        contract_hash,
+        reverting_contract_hash,
+        sponsorship_purse,
+    }
+}
+
+fn account_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let account = builder
+        .get_account(ACCOUNT_ADDR)
+        .expect("should have account");
+    builder.get_purse_balance(account.purse_id())
+}
+
+/// Executes the request and returns its cost in motes.
+fn exec(
+    builder: &mut InMemoryWasmTestBuilder,
+    exec_request_builder: ExecuteRequestBuilder,
+) -> U512 {
+    let exec_request = exec_request_builder
+        .with_protocol_version(sponsored_protocol_version())
+        .build();
+    builder.exec(exec_request).expect_success().commit();
+    let cost = builder.exec_costs(builder.get_exec_responses_count() - 1)[0];
+    Motes::from_gas(cost, CONV_RATE)
+        .expect("should convert")
+        .value()
+}
+
+#[ignore]
+#[test]
+fn should_refund_cost_of_calling_sponsored_contract() {
+    let Setup {
+        mut builder,
+        contract_hash,
+        sponsorship_purse,
+    } = setup(SPONSORSHIP_FUNDS, UNLIMITED_ALLOWANCE);
+    let account_balance_before = account_balance(&builder);
+
+    let cost = exec(
+        &mut builder,
+        ExecuteRequestBuilder::contract_call_by_hash(ACCOUNT_ADDR, contract_hash, ()),
+    );
+
+    assert!(!cost.is_zero());
+    assert_eq!(account_balance(&builder), account_balance_before);
+    assert_eq!(
+        builder.get_purse_balance(PurseId::new(sponsorship_purse)),
+        U512::from(SPONSORSHIP_FUNDS) - cost
+    );
+}
+
+#[ignore]
+#[test]
+fn should_refund_cost_up_to_the_allowance() {
+    let allowance = 1_000;
+    let Setup {
+        mut builder,
+        contract_hash,
+        sponsorship_purse,
+    } = setup(SPONSORSHIP_FUNDS, allowance);
+    let account_balance_before = account_balance(&builder);
+
+    let cost = exec(
+        &mut builder,
+        ExecuteRequestBuilder::contract_call_by_hash(ACCOUNT_ADDR, contract_hash, ()),
+    );
+
+    let refund = U512::from(allowance * CONV_RATE);
+    assert!(cost > refund);
+    assert_eq!(
+        account_balance(&builder),
+        account_balance_before - cost + refund
+    );
+    assert_eq!(
+        builder.get_purse_balance(PurseId::new(sponsorship_purse)),
+        U512::from(SPONSORSHIP_FUNDS) - refund
+    );
+}
+
+#[ignore]
+#[test]
+fn should_refund_only_what_is_left_in_sponsorship_purse() {
+    let sponsorship_funds = 1_000;
+    let Setup {
+        mut builder,
+        contract_hash,
+        sponsorship_purse,
+    } = setup(sponsorship_funds, UNLIMITED_ALLOWANCE);
+    let account_balance_before = account_balance(&builder);
+
+    let first_cost = exec(
+        &mut builder,
+        ExecuteRequestBuilder::contract_call_by_hash(ACCOUNT_ADDR, contract_hash, ()),
+    );
+
+    assert_eq!(
+        account_balance(&builder),
+        account_balance_before - first_cost + U512::from(sponsorship_funds)
+    );
+    assert_eq!(
+        builder.get_purse_balance(PurseId::new(sponsorship_purse)),
+        U512::zero()
+    );
+
+    // Once the sponsorship purse is empty, calls are paid in full
+    let second_cost = exec(
+        &mut builder,
+        ExecuteRequestBuilder::contract_call_by_hash(ACCOUNT_ADDR, contract_hash, ()),
+    );
+    assert_eq!(
+        account_balance(&builder),
+        account_balance_before - first_cost - second_cost + U512::from(sponsorship_funds)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_refund_cost_of_other_session_code() {
+    let Setup {
+        mut builder,
+        sponsorship_purse,
+        ..
+    } = setup(SPONSORSHIP_FUNDS, UNLIMITED_ALLOWANCE);
+    let account_balance_before = account_balance(&builder);
+
+    let cost = exec(
+        &mut builder,
+        ExecuteRequestBuilder::standard(ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()),
+    );
+
+    assert!(cost <= *DEFAULT_PAYMENT);
+    assert_eq!(account_balance(&builder), account_balance_before - cost);
+    assert_eq!(
+        builder.get_purse_balance(PurseId::new(sponsorship_purse)),
+        U512::from(SPONSORSHIP_FUNDS)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_refund_cost_of_failed_session() {
+    let Setup {
+        mut builder,
+        reverting_contract_hash,
+        sponsorship_purse,
+        ..
+    } = setup(SPONSORSHIP_FUNDS, UNLIMITED_ALLOWANCE);
+    let account_balance_before = account_balance(&builder);
+
+    let exec_request =
+        ExecuteRequestBuilder::contract_call_by_hash(ACCOUNT_ADDR, reverting_contract_hash, ())
+            .with_protocol_version(sponsored_protocol_version())
+            .build();
+    builder.exec(exec_request).commit();
+    assert!(builder.is_error());
+
+    let cost = builder.exec_costs(builder.get_exec_responses_count() - 1)[0];
+    let cost = Motes::from_gas(cost, CONV_RATE)
+        .expect("should convert")
+        .value();
+    assert!(!cost.is_zero());
+    assert_eq!(account_balance(&builder), account_balance_before - cost);
+    assert_eq!(
+        builder.get_purse_balance(PurseId::new(sponsorship_purse)),
+        U512::from(SPONSORSHIP_FUNDS)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_charge_cost_in_full_if_refund_fails() {
+    // Not a purse, so the refund fails
+    let unusable_purse = URef::new([255u8; 32], AccessRights::READ_ADD_WRITE);
+    let Setup {
+        mut builder,
+        contract_hash,
+        ..
+    } = setup_with_sponsorship_purse(SPONSORSHIP_FUNDS, UNLIMITED_ALLOWANCE, Some(unusable_purse));
+    let account_balance_before = account_balance(&builder);
+
+    let cost = exec(
+        &mut builder,
+        ExecuteRequestBuilder::contract_call_by_hash(ACCOUNT_ADDR, contract_hash, ()),
+    );
+
+    assert!(!cost.is_zero());
+    assert_eq!(account_balance(&builder), account_balance_before - cost);
+}
@@ -0,0 +1,146 @@
+use engine_core::engine_state::{
+    genesis::{GenesisAccount, GenesisConfig, POS_BONDING_PURSE},
+    CONV_RATE,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_TIMESTAMP, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_SLASH_PERCENTAGE, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{PublicKey, PurseId},
+    Key, U512,
+};
+
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+
+const TEST_BOND: &str = "bond";
+const TEST_UNBOND: &str = "unbond";
+
+const GENESIS_VALIDATOR_STAKE: u64 = 50_000;
+const ACCOUNT_STAKE: u64 = 100_000;
+const UNBONDING_DELAY: u64 = 10_000;
+const UNBOND_BLOCK_TIME: u64 = 1_000;
+
+fn get_pos_bonding_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let purse_id = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(POS_BONDING_PURSE)
+        .and_then(Key::as_uref)
+        .map(|uref| PurseId::new(*uref))
+        .expect("should find PoS bonding purse");
+    builder.get_purse_balance(purse_id)
+}
+
+fn get_default_account_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account");
+    builder.get_purse_balance(account.purse_id())
+}
+
+fn get_exec_cost(builder: &InMemoryWasmTestBuilder, index: usize) -> U512 {
+    Motes::from_gas(builder.exec_costs(index)[0], CONV_RATE)
+        .expect("should convert")
+        .value()
+}
+
+#[ignore]
+#[test]
+fn should_pay_out_unbonded_stake_only_after_unbonding_delay() {
+    let genesis_config = {
+        let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        accounts.push(GenesisAccount::new(
+            PublicKey::new([42; 32]),
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()),
+        ));
+        GenesisConfig::new(
+            DEFAULT_CHAIN_NAME.to_string(),
+            DEFAULT_GENESIS_TIMESTAMP,
+            *DEFAULT_PROTOCOL_VERSION,
+            utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+            utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+            accounts,
+            *DEFAULT_WASM_COSTS,
+            UNBONDING_DELAY,
+            DEFAULT_SLASH_PERCENTAGE,
+        )
+    };
+
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (String::from(TEST_BOND), U512::from(ACCOUNT_STAKE)),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&genesis_config)
+        .exec(exec_request_1)
+        .expect_success()
+        .commit();
+
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(GENESIS_VALIDATOR_STAKE + ACCOUNT_STAKE)
+    );
+
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (String::from(TEST_UNBOND), Some(U512::from(ACCOUNT_STAKE))),
+    )
+    .with_block_time(UNBOND_BLOCK_TIME)
+    .build();
+
+    let balance_before = get_default_account_balance(&builder);
+    builder.exec(exec_request_2).expect_success().commit();
+
+    // The stake is withdrawn, but the funds stay in the bonding purse.
+    assert_eq!(
+        get_default_account_balance(&builder),
+        balance_before - get_exec_cost(&builder, 1)
+    );
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(GENESIS_VALIDATOR_STAKE + ACCOUNT_STAKE)
+    );
+
+    let exec_request_3 =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ())
+            .with_block_time(UNBOND_BLOCK_TIME + UNBONDING_DELAY - 1)
+            .build();
+
+    builder.exec(exec_request_3).expect_success().commit();
+
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(GENESIS_VALIDATOR_STAKE + ACCOUNT_STAKE)
+    );
+
+    let exec_request_4 =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ())
+            .with_block_time(UNBOND_BLOCK_TIME + UNBONDING_DELAY)
+            .build();
+
+    let balance_before = get_default_account_balance(&builder);
+    builder.exec(exec_request_4).expect_success().commit();
+
+    // The funds are paid out when finalizing the first deploy after the delay.
+    assert_eq!(
+        get_default_account_balance(&builder),
+        balance_before - get_exec_cost(&builder, 3) + U512::from(ACCOUNT_STAKE)
+    );
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(GENESIS_VALIDATOR_STAKE)
+    );
+}
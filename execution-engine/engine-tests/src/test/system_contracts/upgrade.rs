@@ -1,21 +1,45 @@
-use engine_core::engine_state::{upgrade::ActivationPoint, Error};
-use engine_grpc_server::engine_server::ipc::DeployCode;
-use engine_shared::{stored_value::StoredValue, transform::Transform};
+use engine_core::engine_state::{
+    upgrade::{ActivationPoint, UpgradeConfig},
+    Error, CONV_RATE, MAX_PAYMENT,
+};
+use engine_grpc_server::engine_server::ipc::{DeployCode, UpgradeRequest};
+use engine_shared::{
+    gas::Gas, newtypes::Blake2bHash, stored_value::StoredValue, transform::Transform,
+};
+use engine_storage::protocol_data::{
+    ChainParameters, DEFAULT_NATIVE_TRANSFER_COST, MINT_NAME, POS_NAME,
+};
 use engine_test_support::{
     internal::{
-        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
-        DEFAULT_GENESIS_CONFIG, DEFAULT_WASM_COSTS,
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        UpgradeRequestBuilder, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT, DEFAULT_WASM_COSTS,
+        MINT_INSTALL_CONTRACT, STANDARD_PAYMENT_CONTRACT,
     },
     DEFAULT_ACCOUNT_ADDR,
 };
-use engine_wasm_prep::wasm_costs::WasmCosts;
-use types::{CLValue, Key, ProtocolVersion, U512};
+use engine_wasm_prep::{
+    denied_host_functions::DeniedHostFunctions, host_function_costs::HostFunctionCosts,
+    wasm_costs::WasmCosts,
+};
+use types::{
+    account::PublicKey, bytesrepr::ToBytes, ApiError, CLValue, Key, ProtocolVersion, U512,
+};
 
 const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
 const MODIFIED_SYSTEM_UPGRADER_CONTRACT_NAME: &str = "modified_system_upgrader.wasm";
+const MODIFIED_MINT_UPGRADER_CONTRACT_NAME: &str = "modified_mint_upgrader.wasm";
 const MODIFIED_MINT_CALLER_CONTRACT_NAME: &str = "modified_mint_caller.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account.wasm";
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_GET_BLOCK_INFO: &str = "get_block_info.wasm";
 const PAYMENT_AMOUNT: u64 = 200_000_000;
+const TRANSFER_AMOUNT: u64 = 100_000_000;
+const TRANSFER_TO_ACCOUNT_COST: u32 = 1_000_000;
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const UPGRADED_MAX_PAYMENT: u64 = 5_000_000;
+const UPGRADED_CONV_RATE: u64 = 2;
+const UPGRADED_NATIVE_TRANSFER_COST: u64 = 20_000;
 
 fn get_upgraded_wasm_costs() -> WasmCosts {
     WasmCosts {
@@ -29,9 +53,23 @@ fn get_upgraded_wasm_costs() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 3,
         opcodes_div: 8,
+        storage_write_byte: 0,
+        read_cost: 0,
+        write_cost: 0,
+        add_cost: 0,
+        hash_base: 1_000,
+        hash_byte: 1,
+        max_memory: 128,
+        max_table_size: 4096,
     }
 }
 
+fn get_post_state_hash(builder: &InMemoryWasmTestBuilder) -> Blake2bHash {
+    let mut post_state_hash = [0u8; 32];
+    post_state_hash.copy_from_slice(&builder.get_post_state_hash());
+    post_state_hash.into()
+}
+
 #[ignore]
 #[test]
 fn should_upgrade_only_protocol_version() {
@@ -146,6 +184,49 @@ fn should_upgrade_system_contract() {
     );
 }
 
+fn assert_system_contract_upgrade_denied(upgrader_contract_name: &str) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let mint_uref = builder.get_mint_contract_uref();
+    let mint_before = builder
+        .query(None, Key::URef(mint_uref), &[])
+        .expect("should query mint");
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, upgrader_contract_name, ()).build();
+    builder.exec(exec_request).commit();
+
+    let error_message = builder
+        .exec_error_message(0)
+        .expect("should have exec error");
+    assert!(
+        error_message.contains(&format!(
+            "Revert({})",
+            u32::from(ApiError::PermissionDenied)
+        )),
+        "{}",
+        error_message
+    );
+
+    let mint_after = builder
+        .query(None, Key::URef(mint_uref), &[])
+        .expect("should query mint");
+    assert_eq!(mint_before, mint_after, "mint should not be upgraded");
+}
+
+#[ignore]
+#[test]
+fn should_not_upgrade_system_contracts_outside_of_system_phase() {
+    assert_system_contract_upgrade_denied(MODIFIED_SYSTEM_UPGRADER_CONTRACT_NAME);
+}
+
+#[ignore]
+#[test]
+fn should_not_upgrade_mint_outside_of_system_phase() {
+    assert_system_contract_upgrade_denied(MODIFIED_MINT_UPGRADER_CONTRACT_NAME);
+}
+
 #[ignore]
 #[test]
 fn should_upgrade_system_contract_on_patch_bump() {
@@ -390,6 +471,55 @@ fn should_allow_only_wasm_costs_minor_version() {
     );
 }
 
+#[test]
+fn should_list_protocol_versions() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let new_costs = get_upgraded_wasm_costs();
+
+    let mut upgrade_request = {
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(PROTOCOL_VERSION)
+            .with_new_protocol_version(new_protocol_version)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_new_costs(new_costs)
+            .build()
+    };
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let protocol_versions = builder
+        .get_engine_state()
+        .list_protocol_versions()
+        .expect("should list protocol versions");
+
+    let listed_versions: Vec<ProtocolVersion> = protocol_versions
+        .iter()
+        .map(|info| info.protocol_version())
+        .collect();
+    assert_eq!(
+        listed_versions,
+        vec![PROTOCOL_VERSION, new_protocol_version]
+    );
+
+    let expected_digests = [*DEFAULT_WASM_COSTS, new_costs]
+        .iter()
+        .map(|wasm_costs| Blake2bHash::new(&wasm_costs.to_bytes().expect("should serialize")));
+    for (info, expected_digest) in protocol_versions.iter().zip(expected_digests) {
+        assert_eq!(info.wasm_costs_digest(), expected_digest);
+        assert_eq!(
+            info.system_contracts().get("mint"),
+            Some(&builder.get_mint_contract_uref())
+        );
+    }
+}
+
 #[ignore]
 #[test]
 fn should_upgrade_system_contract_and_wasm_costs_major() {
@@ -628,3 +758,752 @@ fn should_fail_major_upgrade_without_installer() {
         Error::InvalidUpgradeConfig.to_string()
     );
 }
+
+#[ignore]
+#[test]
+fn should_upgrade_wasm_costs_from_upgrade_config() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let new_costs = get_upgraded_wasm_costs();
+
+    let upgrade_config = UpgradeConfig::new(
+        get_post_state_hash(&builder),
+        PROTOCOL_VERSION,
+        new_protocol_version,
+        None,
+        None,
+        Some(new_costs),
+        Some(DEFAULT_ACTIVATION_POINT),
+    );
+
+    let mut upgrade_request = UpgradeRequest::from(upgrade_config);
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let upgraded_wasm_costs = builder
+        .get_engine_state()
+        .wasm_costs(new_protocol_version)
+        .expect("should have result")
+        .expect("should have upgraded costs");
+
+    assert_eq!(
+        new_costs, upgraded_wasm_costs,
+        "upgraded costs should equal new costs"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_upgrade_system_contract_from_upgrade_config() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = ProtocolVersion::from_parts(2, 0, 0);
+
+    let upgrade_config = UpgradeConfig::new(
+        get_post_state_hash(&builder),
+        PROTOCOL_VERSION,
+        new_protocol_version,
+        None,
+        Some(utils::read_wasm_file_bytes(
+            MODIFIED_SYSTEM_UPGRADER_CONTRACT_NAME,
+        )),
+        None,
+        Some(DEFAULT_ACTIVATION_POINT),
+    );
+
+    let mut upgrade_request = UpgradeRequest::from(upgrade_config);
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(
+        upgrade_response.has_success(),
+        "upgrade_response expected success"
+    );
+    assert_eq!(
+        upgrade_response.get_success().get_post_state_hash(),
+        builder.get_post_state_hash().as_slice()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_persist_installers_at_genesis() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let protocol_data = builder
+        .get_engine_state()
+        .get_protocol_data(PROTOCOL_VERSION)
+        .expect("should have result")
+        .expect("should have protocol data");
+
+    let mint_installer_bytes = utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT);
+    let mint_installer_hash = Blake2bHash::new(&mint_installer_bytes);
+    assert_eq!(
+        protocol_data.installer(MINT_NAME),
+        Some(mint_installer_hash)
+    );
+    assert!(protocol_data.installer(POS_NAME).is_some());
+
+    let stored_value = builder
+        .query(None, Key::Hash(mint_installer_hash.into()), &[])
+        .expect("should have stored installer");
+    let stored_bytes: Vec<u8> = match stored_value {
+        StoredValue::CLValue(cl_value) => cl_value.into_t().expect("should be bytes"),
+        other => panic!("expected a CLValue, got {:?}", other),
+    };
+    assert_eq!(stored_bytes, mint_installer_bytes);
+}
+
+#[ignore]
+#[test]
+fn should_reinstall_mint_from_stored_installer() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor + 1, sem_ver.patch);
+
+    let upgrade_config = UpgradeConfig::new(
+        get_post_state_hash(&builder),
+        PROTOCOL_VERSION,
+        new_protocol_version,
+        None,
+        None,
+        None,
+        Some(DEFAULT_ACTIVATION_POINT),
+    )
+    .with_stored_installer(MINT_NAME);
+
+    let mut upgrade_request = UpgradeRequest::from(upgrade_config);
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let engine_state = builder.get_engine_state();
+    let current_protocol_data = engine_state
+        .get_protocol_data(PROTOCOL_VERSION)
+        .expect("should have result")
+        .expect("should have current protocol data");
+    let new_protocol_data = engine_state
+        .get_protocol_data(new_protocol_version)
+        .expect("should have result")
+        .expect("should have new protocol data");
+
+    assert_ne!(
+        new_protocol_data.mint(),
+        current_protocol_data.mint(),
+        "should have registered the reinstalled mint"
+    );
+    assert_eq!(
+        new_protocol_data.proof_of_stake(),
+        current_protocol_data.proof_of_stake()
+    );
+    assert_eq!(
+        new_protocol_data.installers(),
+        current_protocol_data.installers(),
+        "should carry the installers over"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_with_unknown_stored_installer() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = ProtocolVersion::from_parts(2, 0, 0);
+
+    let upgrade_config = UpgradeConfig::new(
+        get_post_state_hash(&builder),
+        PROTOCOL_VERSION,
+        new_protocol_version,
+        None,
+        None,
+        None,
+        Some(DEFAULT_ACTIVATION_POINT),
+    )
+    .with_stored_installer("unknown");
+
+    let mut upgrade_request = UpgradeRequest::from(upgrade_config);
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(
+        upgrade_response.has_failed_deploy(),
+        "should have failed deploy"
+    );
+    assert_eq!(
+        upgrade_response.get_failed_deploy().message,
+        Error::MissingInstaller("unknown".to_string()).to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_with_both_installer_bytes_and_stored_installer() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = ProtocolVersion::from_parts(2, 0, 0);
+
+    let upgrade_config = UpgradeConfig::new(
+        get_post_state_hash(&builder),
+        PROTOCOL_VERSION,
+        new_protocol_version,
+        None,
+        Some(utils::read_wasm_file_bytes(
+            MODIFIED_SYSTEM_UPGRADER_CONTRACT_NAME,
+        )),
+        None,
+        Some(DEFAULT_ACTIVATION_POINT),
+    )
+    .with_stored_installer(MINT_NAME);
+
+    let mut upgrade_request = UpgradeRequest::from(upgrade_config);
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(
+        upgrade_response.has_failed_deploy(),
+        "should have failed deploy"
+    );
+    assert_eq!(
+        upgrade_response.get_failed_deploy().message,
+        Error::InvalidUpgradeConfig.to_string()
+    );
+}
+
+/// Upgrades to protocol version 1.1.0, pricing host function calls with `host_function_costs` if
+/// given, then runs a transfer to a new account and returns its cost.
+fn transfer_cost_after_upgrade(host_function_costs: Option<HostFunctionCosts>) -> Gas {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+
+    let mut upgrade_request_builder = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT);
+    if let Some(host_function_costs) = host_function_costs.as_ref() {
+        upgrade_request_builder =
+            upgrade_request_builder.with_new_host_function_costs(host_function_costs);
+    }
+    let mut upgrade_request = upgrade_request_builder.build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let protocol_data = builder
+        .get_engine_state()
+        .get_protocol_data(new_protocol_version)
+        .expect("should have result")
+        .expect("should have upgraded protocol data");
+    assert_eq!(
+        protocol_data.host_function_costs(),
+        &host_function_costs.unwrap_or_default()
+    );
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, TRANSFER_AMOUNT),
+    )
+    .with_protocol_version(new_protocol_version)
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    builder.exec_costs(0)[0]
+}
+
+#[ignore]
+#[test]
+fn should_charge_upgraded_host_function_costs() {
+    let host_function_costs =
+        HostFunctionCosts::new().with_cost("transfer_to_account", TRANSFER_TO_ACCOUNT_COST);
+
+    let unpriced_cost = transfer_cost_after_upgrade(None);
+    let priced_cost = transfer_cost_after_upgrade(Some(host_function_costs));
+
+    assert_eq!(
+        priced_cost,
+        unpriced_cost + Gas::from(u64::from(TRANSFER_TO_ACCOUNT_COST))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_with_costs_of_unknown_host_function() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+
+    let upgrade_config = UpgradeConfig::new(
+        get_post_state_hash(&builder),
+        PROTOCOL_VERSION,
+        new_protocol_version,
+        None,
+        None,
+        None,
+        Some(DEFAULT_ACTIVATION_POINT),
+    )
+    .with_host_function_costs(
+        HostFunctionCosts::new().with_cost("unknown", TRANSFER_TO_ACCOUNT_COST),
+    );
+
+    let mut upgrade_request = UpgradeRequest::from(upgrade_config);
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(
+        upgrade_response.has_failed_deploy(),
+        "should have failed deploy"
+    );
+    assert_eq!(
+        upgrade_response.get_failed_deploy().message,
+        Error::InvalidUpgradeConfig.to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_deny_host_functions_from_upgraded_protocol_version() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+    let denied_host_functions = DeniedHostFunctions::new().with_denied("get_era_id");
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_denied_host_functions(&denied_host_functions)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let protocol_data = builder
+        .get_engine_state()
+        .get_protocol_data(new_protocol_version)
+        .expect("should have result")
+        .expect("should have upgraded protocol data");
+    assert_eq!(
+        protocol_data.denied_host_functions(),
+        &denied_host_functions
+    );
+
+    let era_id: u64 = 7;
+    let block_height: u64 = 42;
+
+    // the deny-list does not apply to the protocol version it was not set for
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_BLOCK_INFO,
+        (era_id, block_height),
+    )
+    .with_era_id(era_id)
+    .with_block_height(block_height)
+    .with_protocol_version(PROTOCOL_VERSION)
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_BLOCK_INFO,
+        (era_id, block_height),
+    )
+    .with_era_id(era_id)
+    .with_block_height(block_height)
+    .with_protocol_version(new_protocol_version)
+    .build();
+
+    builder.exec(exec_request).commit();
+
+    let error_message = builder
+        .exec_error_message(builder.get_exec_responses_count() - 1)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("host function get_era_id is denied"),
+        "{}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_denying_unknown_host_function() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_denied_host_functions(&DeniedHostFunctions::new().with_denied("unknown"))
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(
+        upgrade_response.has_failed_deploy(),
+        "should have failed deploy"
+    );
+    assert_eq!(
+        upgrade_response.get_failed_deploy().message,
+        Error::InvalidUpgradeConfig.to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_denying_gas_host_function() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_denied_host_functions(&DeniedHostFunctions::new().with_denied("gas"))
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(
+        upgrade_response.has_failed_deploy(),
+        "should have failed deploy"
+    );
+    assert_eq!(
+        upgrade_response.get_failed_deploy().message,
+        Error::InvalidUpgradeConfig.to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_denying_host_function_imported_by_system_contract() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+
+    // imported by the proof of stake contract
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_denied_host_functions(&DeniedHostFunctions::new().with_denied("get_blocktime"))
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(
+        upgrade_response.has_failed_deploy(),
+        "should have failed deploy"
+    );
+    assert_eq!(
+        upgrade_response.get_failed_deploy().message,
+        Error::InvalidUpgradeConfig.to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_with_refund_percentage_above_100() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_refund_percentage(101)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(
+        upgrade_response.has_failed_deploy(),
+        "should have failed deploy"
+    );
+    assert_eq!(
+        upgrade_response.get_failed_deploy().message,
+        Error::InvalidUpgradeConfig.to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_get_upgraded_chain_parameters() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+    let chain_parameters = ChainParameters::new(
+        UPGRADED_MAX_PAYMENT,
+        UPGRADED_CONV_RATE,
+        0,
+        0,
+        UPGRADED_NATIVE_TRANSFER_COST,
+    );
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_chain_parameters(chain_parameters)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let engine_state = builder.get_engine_state();
+    assert_eq!(
+        engine_state
+            .get_chain_parameters(PROTOCOL_VERSION)
+            .expect("should have result"),
+        Some(ChainParameters::default())
+    );
+    assert_eq!(
+        engine_state
+            .get_chain_parameters(new_protocol_version)
+            .expect("should have result"),
+        Some(chain_parameters)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploys_exceeding_upgraded_block_gas_limit() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+    builder.exec(exec_request).expect_success();
+    let deploy_cost = builder.exec_costs(0)[0];
+
+    // Leaves room for two of the three deploys below
+    let block_gas_limit = deploy_cost.value().as_u64() * 5 / 2;
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_chain_parameters(ChainParameters::new(
+            MAX_PAYMENT,
+            CONV_RATE,
+            block_gas_limit,
+            0,
+            DEFAULT_NATIVE_TRANSFER_COST,
+        ))
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let exec_request = (1..=3u8)
+        .fold(ExecuteRequestBuilder::new(), |exec_request_builder, n| {
+            let deploy = DeployItemBuilder::new()
+                .with_address(DEFAULT_ACCOUNT_ADDR)
+                .with_session_code(CONTRACT_DO_NOTHING, ())
+                .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+                .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+                .with_deploy_hash([n; 32])
+                .build();
+            exec_request_builder.push_deploy(deploy)
+        })
+        .with_protocol_version(new_protocol_version)
+        .build();
+
+    builder.exec(exec_request);
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("should have exec response");
+    assert_eq!(response.len(), 3);
+    assert!(response[0].error().is_none(), "first deploy should fit");
+    assert!(response[1].error().is_none(), "second deploy should fit");
+    assert_eq!(response[0].cost(), deploy_cost);
+    match response[2].error() {
+        Some(Error::BlockGasLimitExceeded { cost, remaining }) => {
+            assert_eq!(*cost, deploy_cost);
+            assert_eq!(
+                *remaining,
+                Gas::from(block_gas_limit) - deploy_cost - deploy_cost
+            );
+        }
+        other => panic!("expected block gas limit to be exceeded, got {:?}", other),
+    }
+    assert_eq!(response[2].cost(), Gas::default());
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_with_zero_conv_rate() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_chain_parameters(ChainParameters::new(
+            UPGRADED_MAX_PAYMENT,
+            0,
+            0,
+            0,
+            DEFAULT_NATIVE_TRANSFER_COST,
+        ))
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(
+        upgrade_response.has_failed_deploy(),
+        "should have failed deploy"
+    );
+    assert_eq!(
+        upgrade_response.get_failed_deploy().message,
+        Error::InvalidUpgradeConfig.to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_with_zero_native_transfer_cost() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_chain_parameters(ChainParameters::new(
+            UPGRADED_MAX_PAYMENT,
+            UPGRADED_CONV_RATE,
+            0,
+            0,
+            0,
+        ))
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(
+        upgrade_response.has_failed_deploy(),
+        "should have failed deploy"
+    );
+    assert_eq!(
+        upgrade_response.get_failed_deploy().message,
+        Error::InvalidUpgradeConfig.to_string()
+    );
+}
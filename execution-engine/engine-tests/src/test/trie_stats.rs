@@ -0,0 +1,52 @@
+use std::convert::TryInto;
+
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+use engine_storage::global_state::TrieStatsMode;
+use engine_test_support::internal::{InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG};
+
+#[ignore]
+#[test]
+fn should_get_trie_stats_of_genesis_state() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+    let state_hash: Blake2bHash = builder
+        .get_post_state_hash()
+        .as_slice()
+        .try_into()
+        .expect("should convert post state hash");
+    let engine_state = builder.get_engine_state();
+
+    let stats = engine_state
+        .trie_stats(CorrelationId::new(), state_hash, TrieStatsMode::Full)
+        .expect("should get stats")
+        .expect("should find root");
+    assert!(!stats.truncated);
+    assert!(stats.leaf_count > 0);
+    assert_eq!(
+        stats.depth_histogram.iter().sum::<u64>(),
+        stats.leaf_count,
+        "every leaf should be in the depth histogram"
+    );
+    assert_eq!(
+        stats.value_size_histogram.iter().sum::<u64>(),
+        stats.leaf_count,
+        "every leaf should be in the value size histogram"
+    );
+
+    let max_tries = (stats.trie_count() / 2) as usize;
+    let sampled = engine_state
+        .trie_stats(
+            CorrelationId::new(),
+            state_hash,
+            TrieStatsMode::Sampled { max_tries },
+        )
+        .expect("should get stats")
+        .expect("should find root");
+    assert!(sampled.truncated);
+    assert_eq!(sampled.trie_count(), max_tries as u64);
+
+    let missing = engine_state
+        .trie_stats(CorrelationId::new(), [1u8; 32].into(), TrieStatsMode::Full)
+        .expect("should get stats");
+    assert_eq!(missing, None);
+}
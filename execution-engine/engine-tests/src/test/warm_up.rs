@@ -0,0 +1,153 @@
+use std::convert::TryInto;
+
+use engine_core::engine_state::Error;
+use engine_shared::{
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+};
+use engine_test_support::internal::{
+    InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG, DEFAULT_PROTOCOL_VERSION,
+};
+use types::{Key, ProtocolVersion};
+
+fn setup() -> (InMemoryWasmTestBuilder, Blake2bHash) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+    let state_hash = builder
+        .get_post_state_hash()
+        .as_slice()
+        .try_into()
+        .expect("should convert post state hash");
+    (builder, state_hash)
+}
+
+#[ignore]
+#[test]
+fn should_load_system_contracts_into_module_cache() {
+    let (builder, state_hash) = setup();
+    let engine_state = builder.get_engine_state();
+
+    let system_contract_count = engine_state
+        .warm_up(CorrelationId::new(), state_hash, *DEFAULT_PROTOCOL_VERSION)
+        .expect("should warm up")
+        .expect("should find root");
+
+    let system_contracts = engine_state
+        .get_protocol_data(*DEFAULT_PROTOCOL_VERSION)
+        .expect("should get protocol data")
+        .expect("should have protocol data")
+        .system_contracts();
+    assert_eq!(system_contract_count, system_contracts.len());
+    for uref in system_contracts {
+        let contract = match builder.query(None, Key::URef(uref), &[]) {
+            Ok(StoredValue::Contract(contract)) => contract,
+            other => panic!("expected a contract, got {:?}", other),
+        };
+        assert!(
+            engine_state
+                .module_cache()
+                .has(&Blake2bHash::new(contract.bytes())),
+            "{} should be cached",
+            uref
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_warm_up_unknown_root() {
+    let (builder, _) = setup();
+
+    let result = builder
+        .get_engine_state()
+        .warm_up(
+            CorrelationId::new(),
+            [1u8; 32].into(),
+            *DEFAULT_PROTOCOL_VERSION,
+        )
+        .expect("should warm up");
+
+    assert!(result.is_none());
+}
+
+#[ignore]
+#[test]
+fn should_not_warm_up_unknown_protocol_version() {
+    let (builder, state_hash) = setup();
+    let unknown_protocol_version = ProtocolVersion::from_parts(42, 0, 0);
+
+    let result = builder.get_engine_state().warm_up(
+        CorrelationId::new(),
+        state_hash,
+        unknown_protocol_version,
+    );
+
+    match result {
+        Err(Error::InvalidProtocolVersion(protocol_version)) => {
+            assert_eq!(protocol_version, unknown_protocol_version)
+        }
+        other => panic!(
+            "expected an invalid protocol version error, got {:?}",
+            other
+        ),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_report_module_cache_stats_after_warm_up() {
+    let (builder, state_hash) = setup();
+    let engine_state = builder.get_engine_state();
+
+    let stats_before = engine_state.cache_stats();
+    let system_contract_count = engine_state
+        .warm_up(CorrelationId::new(), state_hash, *DEFAULT_PROTOCOL_VERSION)
+        .expect("should warm up")
+        .expect("should find root");
+    let stats_after = engine_state.cache_stats();
+
+    assert!(stats_after.entries >= system_contract_count);
+    assert!(stats_after.bytes > 0);
+    assert_eq!(
+        stats_after.hits + stats_after.misses,
+        stats_before.hits + stats_before.misses + system_contract_count as u64
+    );
+}
+
+#[ignore]
+#[test]
+fn should_invalidate_cached_system_contract() {
+    let (builder, state_hash) = setup();
+    let engine_state = builder.get_engine_state();
+    engine_state
+        .warm_up(CorrelationId::new(), state_hash, *DEFAULT_PROTOCOL_VERSION)
+        .expect("should warm up")
+        .expect("should find root");
+
+    let mint_uref = builder.get_mint_contract_uref();
+    let mint_hash = match builder.query(None, Key::URef(mint_uref), &[]) {
+        Ok(StoredValue::Contract(contract)) => Blake2bHash::new(contract.bytes()),
+        other => panic!("expected a contract, got {:?}", other),
+    };
+    let stats_before = engine_state.cache_stats();
+
+    let was_cached = engine_state
+        .invalidate(CorrelationId::new(), state_hash, mint_uref)
+        .expect("should invalidate")
+        .expect("should find root");
+
+    assert!(was_cached);
+    assert!(!engine_state.module_cache().has(&mint_hash));
+    assert_eq!(engine_state.cache_stats().entries, stats_before.entries - 1);
+
+    let was_cached = engine_state
+        .invalidate(CorrelationId::new(), state_hash, mint_uref)
+        .expect("should invalidate")
+        .expect("should find root");
+    assert!(!was_cached);
+
+    let result = engine_state
+        .invalidate(CorrelationId::new(), [1u8; 32].into(), mint_uref)
+        .expect("should invalidate");
+    assert!(result.is_none());
+}
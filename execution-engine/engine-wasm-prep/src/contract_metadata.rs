@@ -0,0 +1,116 @@
+use parity_wasm::elements::{CustomSection, Module, Section};
+
+use types::{
+    bytesrepr::{self, ToBytes},
+    ContractMetadata, CONTRACT_METADATA_SECTION,
+};
+
+/// Returns the custom section holding the contract metadata of `module`, if any.
+pub fn metadata_section(module: &Module) -> Option<&CustomSection> {
+    module.sections().iter().find_map(|section| match section {
+        Section::Custom(custom) if custom.name() == CONTRACT_METADATA_SECTION => Some(custom),
+        _ => None,
+    })
+}
+
+/// Reads the [`ContractMetadata`] embedded in `module`, if any.
+pub fn get_contract_metadata(
+    module: &Module,
+) -> Result<Option<ContractMetadata>, bytesrepr::Error> {
+    metadata_section(module)
+        .map(|section| bytesrepr::deserialize(section.payload().to_vec()))
+        .transpose()
+}
+
+/// Embeds `metadata` in `module`, replacing any metadata it already contains.
+pub fn set_contract_metadata(
+    mut module: Module,
+    metadata: &ContractMetadata,
+) -> Result<Module, bytesrepr::Error> {
+    let mut section = CustomSection::default();
+    *section.name_mut() = CONTRACT_METADATA_SECTION.to_string();
+    *section.payload_mut() = metadata.to_bytes()?;
+
+    let sections = module.sections_mut();
+    sections.retain(|section| match section {
+        Section::Custom(custom) => custom.name() != CONTRACT_METADATA_SECTION,
+        _ => true,
+    });
+    sections.push(Section::Custom(section));
+    Ok(module)
+}
+
+/// Adds `section` back to `module` if a transformation of the module dropped it.
+pub fn restore_metadata_section(mut module: Module, section: Option<CustomSection>) -> Module {
+    if let Some(section) = section {
+        if metadata_section(&module).is_none() {
+            module.sections_mut().push(Section::Custom(section));
+        }
+    }
+    module
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{builder, elements::Module};
+
+    use types::ContractMetadata;
+
+    use super::{
+        get_contract_metadata, metadata_section, restore_metadata_section, set_contract_metadata,
+    };
+    use crate::{wasm_costs::WasmCosts, Preprocessor};
+
+    fn metadata() -> ContractMetadata {
+        ContractMetadata::new(
+            "counter".to_string(),
+            "0.1.0".to_string(),
+            "nightly-2020-01-08".to_string(),
+        )
+    }
+
+    fn empty_module() -> Module {
+        // The preprocessor expects the module to define its own memory
+        builder::module().memory().with_min(1).build().build()
+    }
+
+    #[test]
+    fn should_not_find_metadata_in_module_without_section() {
+        assert_eq!(get_contract_metadata(&empty_module()), Ok(None));
+    }
+
+    #[test]
+    fn should_set_and_get_metadata() {
+        let module = set_contract_metadata(empty_module(), &ContractMetadata::default())
+            .expect("should set metadata");
+        let module = set_contract_metadata(module, &metadata()).expect("should set metadata");
+
+        assert_eq!(get_contract_metadata(&module), Ok(Some(metadata())));
+    }
+
+    #[test]
+    fn should_restore_dropped_metadata() {
+        let original = set_contract_metadata(empty_module(), &metadata()).expect("should set");
+        let section = metadata_section(&original).cloned();
+        let transformed = restore_metadata_section(empty_module(), section);
+
+        assert_eq!(get_contract_metadata(&transformed), Ok(Some(metadata())));
+    }
+
+    #[test]
+    fn preprocessor_should_preserve_metadata() {
+        let module = set_contract_metadata(empty_module(), &metadata()).expect("should set");
+        let module_bytes = parity_wasm::serialize(module).expect("should serialize module");
+        let wasm_costs = WasmCosts {
+            regular: 1,
+            max_stack_height: 64 * 1024,
+            ..Default::default()
+        };
+
+        let preprocessed = Preprocessor::new(wasm_costs)
+            .preprocess(&module_bytes)
+            .expect("should preprocess");
+
+        assert_eq!(get_contract_metadata(&preprocessed), Ok(Some(metadata())));
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeSet;
+
+use types::bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
+
+/// Host functions which modules may no longer import, keyed by the name they are imported by,
+/// e.g. `serialize_function`.  Modules importing any of them fail to instantiate, so contracts
+/// relying on a deprecated host function fail cleanly rather than observing changed behavior.
+///
+/// The deny-list is only set by upgrades, which may deny neither `gas` nor the host functions
+/// imported by the system contracts.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DeniedHostFunctions(BTreeSet<String>);
+
+impl DeniedHostFunctions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the deny-list with the host function imported as `name` added to it.
+    pub fn with_denied(mut self, name: &str) -> Self {
+        self.0.insert(name.to_string());
+        self
+    }
+
+    /// Returns whether the host function imported as `name` is denied.
+    pub fn is_denied(&self, name: &str) -> bool {
+        self.0.contains(name)
+    }
+
+    /// Returns the names of the denied host functions in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<BTreeSet<String>> for DeniedHostFunctions {
+    fn from(names: BTreeSet<String>) -> Self {
+        DeniedHostFunctions(names)
+    }
+}
+
+/// Serialized like a `Vec<String>` of the names in ascending order.
+impl ToBytes for DeniedHostFunctions {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        (self.0.len() as u32).write_bytes(&mut result)?;
+        for name in &self.0 {
+            name.write_bytes(&mut result)?;
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH + self.0.iter().map(ToBytes::serialized_length).sum::<usize>()
+    }
+}
+
+impl FromBytes for DeniedHostFunctions {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (names, rem): (Vec<String>, &[u8]) = FromBytes::from_bytes(bytes)?;
+        Ok((DeniedHostFunctions(names.into_iter().collect()), rem))
+    }
+}
+
+pub mod gens {
+    use proptest::{collection::btree_set, prop_compose};
+
+    use crate::denied_host_functions::DeniedHostFunctions;
+
+    prop_compose! {
+        pub fn denied_host_functions_arb()(
+            names in btree_set("[a-z_]{1,24}", 0..5),
+        ) -> DeniedHostFunctions {
+            DeniedHostFunctions::from(names)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::bytesrepr;
+
+    use super::{gens, DeniedHostFunctions};
+
+    #[test]
+    fn should_only_deny_listed_host_functions() {
+        let denied = DeniedHostFunctions::new().with_denied("serialize_function");
+        assert!(denied.is_denied("serialize_function"));
+        assert!(!denied.is_denied("store_function"));
+        assert!(!DeniedHostFunctions::new().is_denied("serialize_function"));
+    }
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize_with_arbitrary_values(
+            denied in gens::denied_host_functions_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&denied);
+        }
+    }
+}
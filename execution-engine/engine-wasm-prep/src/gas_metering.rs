@@ -0,0 +1,517 @@
+use std::{
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+};
+
+use parity_wasm::{
+    builder,
+    elements::{
+        BlockType, External, GlobalEntry, GlobalType, InitExpr, Instruction, Internal, Module,
+        ValueType,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use types::bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH};
+
+/// Name of the host function charging gas, as imported by `pwasm_utils::inject_gas_counter`.
+const GAS_FUNCTION_MODULE: &str = "env";
+const GAS_FUNCTION_FIELD: &str = "gas";
+
+/// Amount of gas accumulated in the fuel counter after which it is flushed to the host.
+pub const DEFAULT_FUEL_FLUSH_THRESHOLD: u32 = 100_000;
+
+const HOST_CALL_TAG: u8 = 0;
+const FUEL_TAG: u8 = 1;
+
+/// Reasons for `GasMetering::validate` to reject a strategy.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GasMeteringError {
+    /// The flush threshold of fuel metering is zero.
+    ZeroFlushThreshold,
+    /// The flush threshold of fuel metering doesn't fit the `i32` fuel counter.
+    FlushThresholdTooLarge(u32),
+}
+
+impl Display for GasMeteringError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            GasMeteringError::ZeroFlushThreshold => {
+                write!(f, "Fuel gas metering requires a non-zero flush threshold")
+            }
+            GasMeteringError::FlushThresholdTooLarge(flush_threshold) => write!(
+                f,
+                "Flush threshold of {} exceeds the maximum of {}",
+                flush_threshold,
+                i32::max_value()
+            ),
+        }
+    }
+}
+
+/// Strategy used to charge gas for the instructions executed by a Wasm module.
+///
+/// The strategy is a protocol parameter kept in the protocol data, as it decides how much gas an
+/// execution which traps or runs out of gas is charged.
+///
+/// The serde representation is tagged by a `type` key, e.g. `{ type = "host-call" }` or
+/// `{ type = "fuel", flush-threshold = 100000 }` in TOML.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum GasMetering {
+    /// Every metered block calls the `gas` host function with the cost of its instructions.
+    HostCall,
+    /// Every metered block adds the cost of its instructions to a fuel counter kept in a
+    /// module-internal global, which is only flushed to the `gas` host function once it reaches
+    /// `flush_threshold`, before each call to a host function, and when an exported function
+    /// returns.
+    ///
+    /// The total cost of a successful execution is the same as with `HostCall`.  The cost of an
+    /// execution which traps may be lower by less than `flush_threshold`, and exceeding the gas
+    /// limit is detected at most `flush_threshold` later.
+    Fuel {
+        #[serde(rename = "flush-threshold")]
+        flush_threshold: u32,
+    },
+}
+
+impl GasMetering {
+    /// Checks that the flush threshold of fuel metering is non-zero and fits the fuel counter.
+    pub fn validate(&self) -> Result<(), GasMeteringError> {
+        match self {
+            GasMetering::HostCall => Ok(()),
+            GasMetering::Fuel { flush_threshold } => {
+                fuel_flush_threshold(*flush_threshold).map(|_| ())
+            }
+        }
+    }
+}
+
+impl Default for GasMetering {
+    fn default() -> Self {
+        GasMetering::HostCall
+    }
+}
+
+impl ToBytes for GasMetering {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        match self {
+            GasMetering::HostCall => HOST_CALL_TAG.to_bytes(),
+            GasMetering::Fuel { flush_threshold } => {
+                let mut ret = FUEL_TAG.to_bytes()?;
+                ret.append(&mut flush_threshold.to_bytes()?);
+                Ok(ret)
+            }
+        }
+    }
+
+    fn serialized_length(&self) -> usize {
+        match self {
+            GasMetering::HostCall => U8_SERIALIZED_LENGTH,
+            GasMetering::Fuel { flush_threshold } => {
+                U8_SERIALIZED_LENGTH + flush_threshold.serialized_length()
+            }
+        }
+    }
+}
+
+impl FromBytes for GasMetering {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        match tag {
+            HOST_CALL_TAG => Ok((GasMetering::HostCall, rem)),
+            FUEL_TAG => {
+                let (flush_threshold, rem) = FromBytes::from_bytes(rem)?;
+                Ok((GasMetering::Fuel { flush_threshold }, rem))
+            }
+            _ => Err(bytesrepr::Error::FormattingError),
+        }
+    }
+}
+
+/// Converts `flush_threshold` to the type of the fuel counter, rejecting thresholds which fuel
+/// metering can't use.
+fn fuel_flush_threshold(flush_threshold: u32) -> Result<i32, GasMeteringError> {
+    let flush_threshold = i32::try_from(flush_threshold)
+        .map_err(|_| GasMeteringError::FlushThresholdTooLarge(flush_threshold))?;
+    if flush_threshold == 0 {
+        return Err(GasMeteringError::ZeroFlushThreshold);
+    }
+    Ok(flush_threshold)
+}
+
+/// Rewrites a module instrumented by `pwasm_utils::inject_gas_counter` so that metered blocks
+/// charge a module-internal fuel counter instead of calling the `gas` host function directly.
+pub fn inject_fuel_counter(
+    module: Module,
+    flush_threshold: u32,
+) -> Result<Module, GasMeteringError> {
+    let flush_threshold = fuel_flush_threshold(flush_threshold)?;
+    let (imported_functions, imported_globals, gas_function) = {
+        let imports = module
+            .import_section()
+            .map(|section| section.entries())
+            .unwrap_or_default();
+
+        let mut imported_functions = 0u32;
+        let mut imported_globals = 0u32;
+        let mut gas_function = None;
+        for entry in imports {
+            match entry.external() {
+                External::Function(_) => {
+                    if entry.module() == GAS_FUNCTION_MODULE && entry.field() == GAS_FUNCTION_FIELD
+                    {
+                        gas_function = Some(imported_functions);
+                    }
+                    imported_functions += 1;
+                }
+                External::Global(_) => imported_globals += 1,
+                _ => {}
+            }
+        }
+        (imported_functions, imported_globals, gas_function)
+    };
+
+    // Nothing was metered, so there is nothing to rewrite
+    let gas_function = match gas_function {
+        Some(gas_function) => gas_function,
+        None => return Ok(module),
+    };
+
+    let fuel_global = imported_globals
+        + module
+            .global_section()
+            .map(|section| section.entries().len() as u32)
+            .unwrap_or_default();
+
+    let exported_functions: Vec<u32> = module
+        .export_section()
+        .map(|section| section.entries())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| match entry.internal() {
+            Internal::Function(index) => Some(*index),
+            _ => None,
+        })
+        .collect();
+
+    let mut module = builder::from_module(module)
+        .with_global(GlobalEntry::new(
+            GlobalType::new(ValueType::I32, true),
+            InitExpr::new(vec![Instruction::I32Const(0), Instruction::End]),
+        ))
+        .build();
+
+    let rewriter = FuelRewriter {
+        gas_function,
+        imported_functions,
+        fuel_global,
+        flush_threshold,
+    };
+
+    if let Some(code_section) = module.code_section_mut() {
+        for (index, body) in code_section.bodies_mut().iter_mut().enumerate() {
+            let function_index = imported_functions + index as u32;
+            let is_exported = exported_functions.contains(&function_index);
+            let instructions = body.code_mut().elements_mut();
+            *instructions = rewriter.rewrite(instructions, is_exported);
+        }
+    }
+
+    Ok(module)
+}
+
+struct FuelRewriter {
+    gas_function: u32,
+    imported_functions: u32,
+    fuel_global: u32,
+    flush_threshold: i32,
+}
+
+impl FuelRewriter {
+    fn rewrite(&self, instructions: &[Instruction], is_exported: bool) -> Vec<Instruction> {
+        let mut result = Vec::with_capacity(instructions.len());
+        // Depth of the blocks opened within the function body; branches to `depth` leave the
+        // function.
+        let mut depth = 0u32;
+        let mut iter = instructions.iter().peekable();
+
+        while let Some(instruction) = iter.next() {
+            match instruction {
+                Instruction::I32Const(cost)
+                    if iter.peek() == Some(&&Instruction::Call(self.gas_function)) =>
+                {
+                    iter.next();
+                    self.charge(&mut result, *cost);
+                    continue;
+                }
+                Instruction::Call(index) if *index < self.imported_functions => {
+                    self.flush(&mut result)
+                }
+                Instruction::Block(_) | Instruction::Loop(_) | Instruction::If(_) => depth += 1,
+                Instruction::End if depth > 0 => depth -= 1,
+                Instruction::End | Instruction::Return if is_exported => self.flush(&mut result),
+                Instruction::Br(target) | Instruction::BrIf(target)
+                    if is_exported && *target == depth =>
+                {
+                    self.flush(&mut result)
+                }
+                Instruction::BrTable(targets, default)
+                    if is_exported && (*default == depth || targets.contains(&depth)) =>
+                {
+                    self.flush(&mut result)
+                }
+                _ => {}
+            }
+            result.push(instruction.clone());
+        }
+
+        result
+    }
+
+    /// Adds `cost` to the fuel counter and flushes it once it reaches the threshold.
+    fn charge(&self, result: &mut Vec<Instruction>, cost: i32) {
+        result.extend_from_slice(&[
+            Instruction::GetGlobal(self.fuel_global),
+            Instruction::I32Const(cost),
+            Instruction::I32Add,
+            Instruction::SetGlobal(self.fuel_global),
+            Instruction::GetGlobal(self.fuel_global),
+            Instruction::I32Const(self.flush_threshold),
+            Instruction::I32GeU,
+            Instruction::If(BlockType::NoResult),
+        ]);
+        self.push_unconditional_flush(result);
+        result.push(Instruction::End);
+    }
+
+    /// Charges the accumulated fuel through the `gas` host function, if there is any.
+    fn flush(&self, result: &mut Vec<Instruction>) {
+        result.extend_from_slice(&[
+            Instruction::GetGlobal(self.fuel_global),
+            Instruction::If(BlockType::NoResult),
+        ]);
+        self.push_unconditional_flush(result);
+        result.push(Instruction::End);
+    }
+
+    fn push_unconditional_flush(&self, result: &mut Vec<Instruction>) {
+        result.extend_from_slice(&[
+            Instruction::GetGlobal(self.fuel_global),
+            Instruction::Call(self.gas_function),
+            Instruction::I32Const(0),
+            Instruction::SetGlobal(self.fuel_global),
+        ]);
+    }
+}
+
+pub mod gens {
+    use proptest::{
+        prop_oneof,
+        strategy::{Just, Strategy},
+    };
+
+    use crate::gas_metering::GasMetering;
+
+    pub fn gas_metering_arb() -> impl Strategy<Value = GasMetering> {
+        prop_oneof![
+            Just(GasMetering::HostCall),
+            (1..=i32::max_value() as u32)
+                .prop_map(|flush_threshold| GasMetering::Fuel { flush_threshold }),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{
+        builder,
+        elements::{BlockType, Instruction, Instructions, Module},
+    };
+
+    use types::bytesrepr;
+
+    use super::{GasMetering, GasMeteringError, DEFAULT_FUEL_FLUSH_THRESHOLD};
+    use crate::{wasm_costs::WasmCosts, PreprocessingError, Preprocessor};
+
+    fn module_with_loop() -> Module {
+        // The preprocessor expects the module to define its own memory
+        builder::module()
+            .memory()
+            .with_min(1)
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::Loop(BlockType::NoResult),
+                Instruction::I32Const(1),
+                Instruction::BrIf(0),
+                Instruction::End,
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .export()
+            .field("call")
+            .internal()
+            .func(0)
+            .build()
+            .build()
+    }
+
+    fn try_preprocess(gas_metering: GasMetering) -> Result<Module, PreprocessingError> {
+        try_preprocess_module(module_with_loop(), gas_metering)
+    }
+
+    fn try_preprocess_module(
+        module: Module,
+        gas_metering: GasMetering,
+    ) -> Result<Module, PreprocessingError> {
+        let module_bytes = parity_wasm::serialize(module).expect("should serialize module");
+        let wasm_costs = WasmCosts {
+            regular: 1,
+            max_stack_height: 64 * 1024,
+            ..Default::default()
+        };
+        Preprocessor::new(wasm_costs)
+            .with_gas_metering(gas_metering)
+            .preprocess(&module_bytes)
+    }
+
+    fn preprocess(gas_metering: GasMetering) -> Module {
+        try_preprocess(gas_metering).expect("should preprocess")
+    }
+
+    fn call_body(module: &Module) -> &[Instruction] {
+        // Thunks added by the stack height limiter come after the original function
+        let code_section = module.code_section().expect("should have code section");
+        code_section
+            .bodies()
+            .first()
+            .expect("should have function body")
+            .code()
+            .elements()
+    }
+
+    fn count_gas_calls(instructions: &[Instruction]) -> usize {
+        // The `gas` host function is the only function imported by the test module
+        instructions
+            .iter()
+            .filter(|instruction| **instruction == Instruction::Call(0))
+            .count()
+    }
+
+    #[test]
+    fn fuel_metering_should_charge_global_and_flush_on_return() {
+        let host_call_module = preprocess(GasMetering::HostCall);
+        let host_call_body = call_body(&host_call_module);
+        assert!(host_call_body
+            .windows(2)
+            .filter(|pair| pair[1] == Instruction::Call(0))
+            .all(|pair| match pair[0] {
+                Instruction::I32Const(_) => true,
+                _ => false,
+            }));
+
+        let fuel_module = preprocess(GasMetering::Fuel {
+            flush_threshold: DEFAULT_FUEL_FLUSH_THRESHOLD,
+        });
+        let fuel_body = call_body(&fuel_module);
+        // The fuel counter is the first global; the stack height limiter adds its own after it
+        let fuel_global = 0;
+        assert!(fuel_body
+            .windows(2)
+            .filter(|pair| pair[1] == Instruction::Call(0))
+            .all(|pair| pair[0] == Instruction::GetGlobal(fuel_global)));
+
+        // Every metered block may flush once the threshold is reached, and the exported function
+        // additionally flushes before returning
+        assert_eq!(
+            count_gas_calls(fuel_body),
+            count_gas_calls(host_call_body) + 1
+        );
+        assert_eq!(
+            &fuel_body[fuel_body.len() - 8..],
+            &[
+                Instruction::GetGlobal(fuel_global),
+                Instruction::If(BlockType::NoResult),
+                Instruction::GetGlobal(fuel_global),
+                Instruction::Call(0),
+                Instruction::I32Const(0),
+                Instruction::SetGlobal(fuel_global),
+                Instruction::End,
+                Instruction::End,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_reject_module_writing_fuel_counter() {
+        // The module declares no globals, so the fuel counter would be added as global 0
+        let module = builder::module()
+            .memory()
+            .with_min(1)
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::I32Const(0),
+                Instruction::SetGlobal(0),
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .export()
+            .field("call")
+            .internal()
+            .func(0)
+            .build()
+            .build();
+        match try_preprocess_module(
+            module,
+            GasMetering::Fuel {
+                flush_threshold: DEFAULT_FUEL_FLUSH_THRESHOLD,
+            },
+        ) {
+            Err(PreprocessingError::UndeclaredGlobal(0)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_unusable_flush_thresholds() {
+        let too_large = i32::max_value() as u32 + 1;
+        for (flush_threshold, error) in vec![
+            (0, GasMeteringError::ZeroFlushThreshold),
+            (
+                too_large,
+                GasMeteringError::FlushThresholdTooLarge(too_large),
+            ),
+        ] {
+            let gas_metering = GasMetering::Fuel { flush_threshold };
+            assert_eq!(gas_metering.validate(), Err(error));
+            match try_preprocess(gas_metering) {
+                Err(PreprocessingError::InvalidGasMetering(actual)) => assert_eq!(actual, error),
+                other => panic!("unexpected result: {:?}", other),
+            }
+        }
+
+        let largest = GasMetering::Fuel {
+            flush_threshold: i32::max_value() as u32,
+        };
+        assert_eq!(largest.validate(), Ok(()));
+        assert_eq!(GasMetering::HostCall.validate(), Ok(()));
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        bytesrepr::test_serialization_roundtrip(&GasMetering::HostCall);
+        bytesrepr::test_serialization_roundtrip(&GasMetering::Fuel {
+            flush_threshold: DEFAULT_FUEL_FLUSH_THRESHOLD,
+        });
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use types::bytesrepr::{self, FromBytes, ToBytes};
+
+/// Flat cost of each call to individual host functions, keyed by the name the host function is
+/// imported by, e.g. `transfer_to_account`.  It is charged on top of the costs priced by
+/// [`WasmCosts`](crate::wasm_costs::WasmCosts), and host functions without an entry cost nothing
+/// extra.
+///
+/// The serde representation is a table of names to costs, as in the `[host-function-costs]`
+/// section of a chainspec's `manifest.toml`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct HostFunctionCosts(BTreeMap<String, u32>);
+
+impl HostFunctionCosts {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the schedule with the cost of the host function imported as `name` set to `cost`.
+    pub fn with_cost(mut self, name: &str, cost: u32) -> Self {
+        self.0.insert(name.to_string(), cost);
+        self
+    }
+
+    /// Returns the cost of a call to the host function imported as `name`, or zero if it has no
+    /// entry.
+    pub fn cost(&self, name: &str) -> u32 {
+        self.0.get(name).copied().unwrap_or_default()
+    }
+
+    /// Returns the names of the priced host functions and their costs.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.0.iter().map(|(name, cost)| (name.as_str(), *cost))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<BTreeMap<String, u32>> for HostFunctionCosts {
+    fn from(costs: BTreeMap<String, u32>) -> Self {
+        HostFunctionCosts(costs)
+    }
+}
+
+impl ToBytes for HostFunctionCosts {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+}
+
+impl FromBytes for HostFunctionCosts {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (costs, rem) = FromBytes::from_bytes(bytes)?;
+        Ok((HostFunctionCosts(costs), rem))
+    }
+}
+
+pub mod gens {
+    use proptest::{collection::btree_map, num, prop_compose};
+
+    use crate::host_function_costs::HostFunctionCosts;
+
+    prop_compose! {
+        pub fn host_function_costs_arb()(
+            costs in btree_map("[a-z_]{1,24}", num::u32::ANY, 0..5),
+        ) -> HostFunctionCosts {
+            HostFunctionCosts::from(costs)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::bytesrepr;
+
+    use super::{gens, HostFunctionCosts};
+
+    const HOST_FUNCTION_COSTS_SECTION: &str = r#"
+transfer_to_account = 10000
+store_function = 5000
+"#;
+
+    #[test]
+    fn should_return_zero_for_unpriced_host_functions() {
+        let costs = HostFunctionCosts::new().with_cost("transfer_to_account", 10_000);
+        assert_eq!(costs.cost("transfer_to_account"), 10_000);
+        assert_eq!(costs.cost("read_value"), 0);
+        assert_eq!(HostFunctionCosts::new().cost("transfer_to_account"), 0);
+    }
+
+    #[test]
+    fn should_deserialize_chainspec_section() {
+        let costs: HostFunctionCosts =
+            toml::from_str(HOST_FUNCTION_COSTS_SECTION).expect("should deserialize");
+        let expected = HostFunctionCosts::new()
+            .with_cost("transfer_to_account", 10_000)
+            .with_cost("store_function", 5_000);
+        assert_eq!(costs, expected);
+    }
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize_with_arbitrary_values(
+            costs in gens::host_function_costs_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&costs);
+        }
+    }
+}
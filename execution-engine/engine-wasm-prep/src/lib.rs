@@ -1,25 +1,66 @@
+pub mod contract_metadata;
+pub mod denied_host_functions;
+pub mod gas_metering;
+pub mod host_function_costs;
+pub mod validation;
 pub mod wasm_costs;
 
 use std::fmt::{self, Display, Formatter};
 
-use parity_wasm::elements::{self, Module};
+use parity_wasm::elements::{self, Module, ResizableLimits};
 use pwasm_utils::{self, stack_height};
 
-use crate::wasm_costs::WasmCosts;
-
-//NOTE: size of Wasm memory page is 64 KiB
-pub const MEM_PAGES: u32 = 64;
+use crate::{
+    gas_metering::{GasMetering, GasMeteringError},
+    validation::{
+        ATOMIC_PREFIX, BULK_PREFIX, SIGN_EXTENSION_FIRST, SIGN_EXTENSION_LAST, SIMD_PREFIX,
+    },
+    wasm_costs::WasmCosts,
+};
 
 #[derive(Debug)]
 pub enum PreprocessingError {
     DeserializeError(String),
     OperationForbiddenByGasRules,
     StackLimiterError,
+    MemoryLimitExceeded,
+    TableLimitExceeded,
+    /// The function at the given index uses a floating-point instruction.
+    FloatingPointInstruction {
+        function_index: usize,
+        instruction: String,
+    },
+    /// The module uses floating-point values in a signature, local or global.
+    FloatingPointType,
+    /// The module uses a bulk memory or non-trapping float-to-int conversion instruction.
+    BulkMemoryInstruction,
+    /// The module uses a SIMD instruction.
+    SimdInstruction,
+    /// The module uses an atomic instruction.
+    AtomicInstruction,
+    /// The module uses a sign extension instruction.
+    SignExtensionInstruction,
+    /// The gas metering strategy can't be applied.
+    InvalidGasMetering(GasMeteringError),
+    /// The module refers to a global at the given index, which it doesn't declare.
+    UndeclaredGlobal(u32),
 }
 
 impl From<elements::Error> for PreprocessingError {
     fn from(error: elements::Error) -> Self {
-        PreprocessingError::DeserializeError(error.to_string())
+        match error {
+            elements::Error::UnknownOpcode(BULK_PREFIX) => {
+                PreprocessingError::BulkMemoryInstruction
+            }
+            elements::Error::UnknownOpcode(SIMD_PREFIX) => PreprocessingError::SimdInstruction,
+            elements::Error::UnknownOpcode(ATOMIC_PREFIX) => PreprocessingError::AtomicInstruction,
+            elements::Error::UnknownOpcode(opcode)
+                if opcode >= SIGN_EXTENSION_FIRST && opcode <= SIGN_EXTENSION_LAST =>
+            {
+                PreprocessingError::SignExtensionInstruction
+            }
+            error => PreprocessingError::DeserializeError(error.to_string()),
+        }
     }
 }
 
@@ -29,36 +70,185 @@ impl Display for PreprocessingError {
             PreprocessingError::DeserializeError(error) => write!(f, "Deserialization error: {}", error),
             PreprocessingError::OperationForbiddenByGasRules => write!(f, "Encountered operation forbidden by gas rules. Consult instruction -> metering config map"),
             PreprocessingError::StackLimiterError => write!(f, "Stack limiter error"),
+            PreprocessingError::MemoryLimitExceeded => write!(f, "Module declares more memory than allowed"),
+            PreprocessingError::TableLimitExceeded => write!(f, "Module declares a larger table than allowed"),
+            PreprocessingError::FloatingPointInstruction { function_index, instruction } => write!(f, "Function {} uses floating-point instruction {}", function_index, instruction),
+            PreprocessingError::FloatingPointType => write!(f, "Module uses floating-point value types"),
+            PreprocessingError::BulkMemoryInstruction => write!(f, "Bulk memory instructions are not supported"),
+            PreprocessingError::SimdInstruction => write!(f, "SIMD instructions are not supported"),
+            PreprocessingError::AtomicInstruction => write!(f, "Atomic instructions are not supported"),
+            PreprocessingError::SignExtensionInstruction => write!(f, "Sign extension instructions are not supported"),
+            PreprocessingError::InvalidGasMetering(error) => write!(f, "Invalid gas metering: {}", error),
+            PreprocessingError::UndeclaredGlobal(global_index) => write!(f, "Module refers to undeclared global {}", global_index),
         }
     }
 }
 
 pub struct Preprocessor {
     wasm_costs: WasmCosts,
-    // Number of memory pages.
-    mem_pages: u32,
+    gas_metering: GasMetering,
 }
 
 impl Preprocessor {
     pub fn new(wasm_costs: WasmCosts) -> Self {
         Self {
             wasm_costs,
-            mem_pages: MEM_PAGES,
+            gas_metering: GasMetering::default(),
         }
     }
 
+    pub fn with_gas_metering(mut self, gas_metering: GasMetering) -> Self {
+        self.gas_metering = gas_metering;
+        self
+    }
+
     pub fn preprocess(&self, module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
         let module = deserialize(module_bytes)?;
-        let module = pwasm_utils::externalize_mem(module, None, self.mem_pages);
+        let metadata_section = contract_metadata::metadata_section(&module).cloned();
+        validation::validate_module(&module)?;
+        self.check_limits(&module)?;
+        let module = pwasm_utils::externalize_mem(module, None, self.wasm_costs.memory_limit());
         let module = pwasm_utils::inject_gas_counter(module, &self.wasm_costs.to_set())
             .map_err(|_| PreprocessingError::OperationForbiddenByGasRules)?;
+        let module = match self.gas_metering {
+            GasMetering::HostCall => module,
+            GasMetering::Fuel { flush_threshold } => {
+                gas_metering::inject_fuel_counter(module, flush_threshold)
+                    .map_err(PreprocessingError::InvalidGasMetering)?
+            }
+        };
         let module = stack_height::inject_limiter(module, self.wasm_costs.max_stack_height)
             .map_err(|_| PreprocessingError::StackLimiterError)?;
-        Ok(module)
+        Ok(contract_metadata::restore_metadata_section(
+            module,
+            metadata_section,
+        ))
+    }
+
+    /// Rejects modules whose memory or function table can exceed the configured limits.
+    fn check_limits(&self, module: &Module) -> Result<(), PreprocessingError> {
+        let memory_limit = self.wasm_costs.memory_limit();
+        if let Some(memory_section) = module.memory_section() {
+            if memory_section
+                .entries()
+                .iter()
+                .any(|memory| exceeds(memory.limits(), memory_limit))
+            {
+                return Err(PreprocessingError::MemoryLimitExceeded);
+            }
+        }
+        let table_limit = self.wasm_costs.table_limit();
+        if let Some(table_section) = module.table_section() {
+            if table_section
+                .entries()
+                .iter()
+                .any(|table| exceeds(table.limits(), table_limit))
+            {
+                return Err(PreprocessingError::TableLimitExceeded);
+            }
+        }
+        Ok(())
     }
 }
 
+fn exceeds(limits: &ResizableLimits, limit: u32) -> bool {
+    limits.initial() > limit || limits.maximum().map_or(false, |maximum| maximum > limit)
+}
+
 // Returns a parity Module from bytes without making modifications or limits
 pub fn deserialize(module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
     parity_wasm::deserialize_buffer::<Module>(module_bytes).map_err(Into::into)
 }
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{
+        builder,
+        elements::{External, Module},
+    };
+
+    use super::{PreprocessingError, Preprocessor};
+    use crate::wasm_costs::{WasmCosts, DEFAULT_MAX_MEMORY_PAGES};
+
+    fn module(memory: (u32, Option<u32>), table: Option<(u32, Option<u32>)>) -> Module {
+        let builder = builder::module()
+            .memory()
+            .with_min(memory.0)
+            .with_max(memory.1)
+            .build();
+        match table {
+            Some((min, max)) => builder.table().with_min(min).with_max(max).build().build(),
+            None => builder.build(),
+        }
+    }
+
+    fn preprocess(
+        module: Module,
+        max_memory: u32,
+        max_table_size: u32,
+    ) -> Result<Module, PreprocessingError> {
+        let module_bytes = parity_wasm::serialize(module).expect("should serialize module");
+        let wasm_costs = WasmCosts {
+            regular: 1,
+            max_stack_height: 64 * 1024,
+            max_memory,
+            max_table_size,
+            ..Default::default()
+        };
+        Preprocessor::new(wasm_costs).preprocess(&module_bytes)
+    }
+
+    fn memory_maximum(module: &Module) -> Option<u32> {
+        // The preprocessor moves the memory into the import section
+        module
+            .import_section()
+            .expect("should have import section")
+            .entries()
+            .iter()
+            .find_map(|entry| match entry.external() {
+                External::Memory(memory) => memory.limits().maximum(),
+                _ => None,
+            })
+    }
+
+    #[test]
+    fn should_cap_memory_at_default_limit() {
+        let preprocessed = preprocess(module((1, None), None), 0, 0).expect("should preprocess");
+        assert_eq!(
+            memory_maximum(&preprocessed),
+            Some(DEFAULT_MAX_MEMORY_PAGES)
+        );
+    }
+
+    #[test]
+    fn should_cap_memory_at_configured_limit() {
+        let preprocessed = preprocess(module((1, None), None), 128, 0).expect("should preprocess");
+        assert_eq!(memory_maximum(&preprocessed), Some(128));
+    }
+
+    #[test]
+    fn should_reject_memory_exceeding_limit() {
+        match preprocess(module((17, None), None), 16, 0) {
+            Err(PreprocessingError::MemoryLimitExceeded) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        match preprocess(module((1, Some(17)), None), 16, 0) {
+            Err(PreprocessingError::MemoryLimitExceeded) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(preprocess(module((16, Some(16)), None), 16, 0).is_ok());
+    }
+
+    #[test]
+    fn should_reject_table_exceeding_limit() {
+        match preprocess(module((1, None), Some((9, None))), 0, 8) {
+            Err(PreprocessingError::TableLimitExceeded) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        match preprocess(module((1, None), Some((1, Some(9)))), 0, 8) {
+            Err(PreprocessingError::TableLimitExceeded) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(preprocess(module((1, None), Some((8, Some(8)))), 0, 8).is_ok());
+    }
+}
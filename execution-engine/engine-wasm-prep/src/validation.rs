@@ -0,0 +1,374 @@
+//! Rejects modules using instructions the engine can't execute deterministically.
+//!
+//! Only the instructions of the Wasm MVP are deserialized, so instructions of later proposals
+//! (bulk memory, SIMD, atomics and sign extension) are reported while deserializing the module,
+//! see [`PreprocessingError`].  What is left to check are the floating-point instructions and
+//! value types, whose results may differ between platforms, and the references to globals the
+//! module doesn't declare.
+
+use parity_wasm::elements::{
+    External, ImportCountType, Instruction, Internal, Module, Type, ValueType,
+};
+
+use crate::PreprocessingError;
+
+/// Opcode prefix of the bulk memory and non-trapping float-to-int conversion instructions.
+pub(crate) const BULK_PREFIX: u8 = 0xfc;
+/// Opcode prefix of the SIMD instructions.
+pub(crate) const SIMD_PREFIX: u8 = 0xfd;
+/// Opcode prefix of the atomic instructions.
+pub(crate) const ATOMIC_PREFIX: u8 = 0xfe;
+/// First opcode of the sign extension instructions.
+pub(crate) const SIGN_EXTENSION_FIRST: u8 = 0xc0;
+/// Last opcode of the sign extension instructions.
+pub(crate) const SIGN_EXTENSION_LAST: u8 = 0xc4;
+
+/// Checks that `module` neither uses floating-point value types nor floating-point instructions,
+/// and only refers to the globals it declares.
+pub fn validate_module(module: &Module) -> Result<(), PreprocessingError> {
+    if uses_float_types(module) {
+        return Err(PreprocessingError::FloatingPointType);
+    }
+    check_global_references(module)?;
+
+    let imported_functions = module.import_count(ImportCountType::Function);
+    let bodies = module
+        .code_section()
+        .map(|code_section| code_section.bodies())
+        .unwrap_or_default();
+    for (body_index, body) in bodies.iter().enumerate() {
+        if let Some(instruction) = body.code().elements().iter().find(|i| is_float(i)) {
+            return Err(PreprocessingError::FloatingPointInstruction {
+                function_index: imported_functions + body_index,
+                instruction: instruction.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Rejects references to globals beyond those the module imports or defines.
+///
+/// The preprocessor appends globals of its own, such as the fuel counter and the stack height
+/// counter, which a module referring to them by index could otherwise overwrite.
+fn check_global_references(module: &Module) -> Result<(), PreprocessingError> {
+    let declared_globals = module.import_count(ImportCountType::Global)
+        + module
+            .global_section()
+            .map_or(0, |global_section| global_section.entries().len());
+
+    let bodies = module
+        .code_section()
+        .map(|code_section| code_section.bodies())
+        .unwrap_or_default();
+    let globals = module
+        .global_section()
+        .map(|global_section| global_section.entries())
+        .unwrap_or_default();
+    let data_segments = module
+        .data_section()
+        .map(|data_section| data_section.entries())
+        .unwrap_or_default();
+    let element_segments = module
+        .elements_section()
+        .map(|elements_section| elements_section.entries())
+        .unwrap_or_default();
+
+    let instructions = bodies
+        .iter()
+        .flat_map(|body| body.code().elements())
+        .chain(globals.iter().flat_map(|global| global.init_expr().code()))
+        .chain(
+            data_segments
+                .iter()
+                .flat_map(|segment| segment.offset().code()),
+        )
+        .chain(
+            element_segments
+                .iter()
+                .flat_map(|segment| segment.offset().code()),
+        );
+    let exported_globals = module
+        .export_section()
+        .map(|export_section| export_section.entries())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| match entry.internal() {
+            Internal::Global(global_index) => Some(*global_index),
+            _ => None,
+        });
+
+    match instructions
+        .filter_map(|instruction| match instruction {
+            Instruction::GetGlobal(global_index) | Instruction::SetGlobal(global_index) => {
+                Some(*global_index)
+            }
+            _ => None,
+        })
+        .chain(exported_globals)
+        .find(|global_index| *global_index as usize >= declared_globals)
+    {
+        Some(global_index) => Err(PreprocessingError::UndeclaredGlobal(global_index)),
+        None => Ok(()),
+    }
+}
+
+fn is_float_type(value_type: ValueType) -> bool {
+    match value_type {
+        ValueType::F32 | ValueType::F64 => true,
+        _ => false,
+    }
+}
+
+fn uses_float_types(module: &Module) -> bool {
+    let signatures = module
+        .type_section()
+        .map(|type_section| type_section.types())
+        .unwrap_or_default();
+    let in_signatures = signatures.iter().any(|signature| match signature {
+        Type::Function(function_type) => {
+            function_type.params().iter().cloned().any(is_float_type)
+                || function_type.return_type().map_or(false, is_float_type)
+        }
+    });
+
+    let in_locals = module.code_section().map_or(false, |code_section| {
+        code_section.bodies().iter().any(|body| {
+            body.locals()
+                .iter()
+                .any(|local| is_float_type(local.value_type()))
+        })
+    });
+
+    let in_globals = module.global_section().map_or(false, |global_section| {
+        global_section
+            .entries()
+            .iter()
+            .any(|global| is_float_type(global.global_type().content_type()))
+    });
+
+    let in_imports = module.import_section().map_or(false, |import_section| {
+        import_section
+            .entries()
+            .iter()
+            .any(|entry| match entry.external() {
+                External::Global(global_type) => is_float_type(global_type.content_type()),
+                _ => false,
+            })
+    });
+
+    in_signatures || in_locals || in_globals || in_imports
+}
+
+fn is_float(instruction: &Instruction) -> bool {
+    match instruction {
+        Instruction::F32Load(..)
+        | Instruction::F64Load(..)
+        | Instruction::F32Store(..)
+        | Instruction::F64Store(..)
+        | Instruction::F32Const(_)
+        | Instruction::F64Const(_)
+        | Instruction::F32Eq
+        | Instruction::F32Ne
+        | Instruction::F32Lt
+        | Instruction::F32Gt
+        | Instruction::F32Le
+        | Instruction::F32Ge
+        | Instruction::F64Eq
+        | Instruction::F64Ne
+        | Instruction::F64Lt
+        | Instruction::F64Gt
+        | Instruction::F64Le
+        | Instruction::F64Ge
+        | Instruction::F32Abs
+        | Instruction::F32Neg
+        | Instruction::F32Ceil
+        | Instruction::F32Floor
+        | Instruction::F32Trunc
+        | Instruction::F32Nearest
+        | Instruction::F32Sqrt
+        | Instruction::F32Add
+        | Instruction::F32Sub
+        | Instruction::F32Mul
+        | Instruction::F32Div
+        | Instruction::F32Min
+        | Instruction::F32Max
+        | Instruction::F32Copysign
+        | Instruction::F64Abs
+        | Instruction::F64Neg
+        | Instruction::F64Ceil
+        | Instruction::F64Floor
+        | Instruction::F64Trunc
+        | Instruction::F64Nearest
+        | Instruction::F64Sqrt
+        | Instruction::F64Add
+        | Instruction::F64Sub
+        | Instruction::F64Mul
+        | Instruction::F64Div
+        | Instruction::F64Min
+        | Instruction::F64Max
+        | Instruction::F64Copysign
+        | Instruction::I32TruncSF32
+        | Instruction::I32TruncUF32
+        | Instruction::I32TruncSF64
+        | Instruction::I32TruncUF64
+        | Instruction::I64TruncSF32
+        | Instruction::I64TruncUF32
+        | Instruction::I64TruncSF64
+        | Instruction::I64TruncUF64
+        | Instruction::F32ConvertSI32
+        | Instruction::F32ConvertUI32
+        | Instruction::F32ConvertSI64
+        | Instruction::F32ConvertUI64
+        | Instruction::F32DemoteF64
+        | Instruction::F64ConvertSI32
+        | Instruction::F64ConvertUI32
+        | Instruction::F64ConvertSI64
+        | Instruction::F64ConvertUI64
+        | Instruction::F64PromoteF32
+        | Instruction::I32ReinterpretF32
+        | Instruction::I64ReinterpretF64
+        | Instruction::F32ReinterpretI32
+        | Instruction::F64ReinterpretI64 => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{
+        builder,
+        elements::{Instruction, Instructions, Module, ValueType},
+    };
+
+    use super::{
+        validate_module, ATOMIC_PREFIX, BULK_PREFIX, SIGN_EXTENSION_FIRST, SIGN_EXTENSION_LAST,
+        SIMD_PREFIX,
+    };
+    use crate::{deserialize, PreprocessingError};
+
+    fn module_with_instructions(instructions: Vec<Instruction>) -> Module {
+        builder::module()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(instructions))
+            .build()
+            .build()
+            .build()
+    }
+
+    /// Returns a module whose only function consists of `opcode` followed by `nop`.
+    fn module_bytes_with_opcode(opcode: u8) -> Vec<u8> {
+        vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic and version
+            0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section: `() -> ()`
+            0x03, 0x02, 0x01, 0x00, // function section
+            0x0a, 0x06, 0x01, 0x04, 0x00, opcode, 0x01, 0x0b, // code section
+        ]
+    }
+
+    #[test]
+    fn should_accept_integer_instructions() {
+        let module = module_with_instructions(vec![
+            Instruction::I64Const(1),
+            Instruction::I64Const(2),
+            Instruction::I64Add,
+            Instruction::Drop,
+            Instruction::End,
+        ]);
+        assert!(validate_module(&module).is_ok());
+    }
+
+    #[test]
+    fn should_reject_float_instructions() {
+        let module = module_with_instructions(vec![
+            Instruction::I32Const(1),
+            Instruction::F32ConvertSI32,
+            Instruction::Drop,
+            Instruction::End,
+        ]);
+        match validate_module(&module) {
+            Err(PreprocessingError::FloatingPointInstruction {
+                function_index: 0, ..
+            }) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_float_types() {
+        let module = builder::module()
+            .function()
+            .signature()
+            .with_param(ValueType::F64)
+            .build()
+            .body()
+            .build()
+            .build()
+            .build();
+        match validate_module(&module) {
+            Err(PreprocessingError::FloatingPointType) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    fn module_with_global(global_index: u32) -> Module {
+        builder::module()
+            .global()
+            .with_type(ValueType::I32)
+            .mutable()
+            .init_expr(Instruction::I32Const(0))
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::GetGlobal(global_index),
+                Instruction::SetGlobal(global_index),
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .build()
+    }
+
+    #[test]
+    fn should_reject_references_to_undeclared_globals() {
+        assert!(validate_module(&module_with_global(0)).is_ok());
+        match validate_module(&module_with_global(1)) {
+            Err(PreprocessingError::UndeclaredGlobal(1)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_deserialize_module_with_mvp_opcode() {
+        // `nop`
+        assert!(deserialize(&module_bytes_with_opcode(0x01)).is_ok());
+    }
+
+    #[test]
+    fn should_reject_instructions_of_unsupported_proposals() {
+        match deserialize(&module_bytes_with_opcode(BULK_PREFIX)) {
+            Err(PreprocessingError::BulkMemoryInstruction) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        match deserialize(&module_bytes_with_opcode(SIMD_PREFIX)) {
+            Err(PreprocessingError::SimdInstruction) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        match deserialize(&module_bytes_with_opcode(ATOMIC_PREFIX)) {
+            Err(PreprocessingError::AtomicInstruction) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        for opcode in SIGN_EXTENSION_FIRST..=SIGN_EXTENSION_LAST {
+            match deserialize(&module_bytes_with_opcode(opcode)) {
+                Err(PreprocessingError::SignExtensionInstruction) => {}
+                other => panic!("unexpected result: {:?}", other),
+            }
+        }
+    }
+}
@@ -1,41 +1,186 @@
-use std::collections::BTreeMap;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
 
 use pwasm_utils::rules::{InstructionType, Metering, Set};
+use serde::{Deserialize, Serialize};
 
 use types::bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
 
-const NUM_FIELDS: usize = 10;
+const NUM_FIELDS: usize = 18;
 pub const WASM_COSTS_SERIALIZED_LENGTH: usize = NUM_FIELDS * U32_SERIALIZED_LENGTH;
 
+/// Number of costs in the table of the 1.0.0 release, up to and including `opcodes_div`.
+const LEGACY_NUM_FIELDS: usize = 10;
+pub const LEGACY_WASM_COSTS_SERIALIZED_LENGTH: usize = LEGACY_NUM_FIELDS * U32_SERIALIZED_LENGTH;
+
+/// Memory limit (in 64 KiB pages) used when `WasmCosts::max_memory` is zero.
+pub const DEFAULT_MAX_MEMORY_PAGES: u32 = 64;
+/// Table size limit used when `WasmCosts::max_table_size` is zero.
+pub const DEFAULT_MAX_TABLE_SIZE: u32 = 4096;
+/// Largest memory limit (in 64 KiB pages) accepted by `WasmCosts::validate`, i.e. the 4 GiB
+/// addressable by 32-bit Wasm.
+pub const MAX_MEMORY_PAGES: u32 = 65_536;
+
+/// Reasons for `WasmCosts::validate` to reject a cost table.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WasmCostsError {
+    /// `opcodes_div` is zero.
+    ZeroOpcodesDivisor,
+    /// `max_stack_height` is zero, so every function call would exhaust the stack.
+    ZeroMaxStackHeight,
+    /// `max_memory` is larger than `MAX_MEMORY_PAGES`.
+    MemoryLimitTooLarge(u32),
+}
+
+impl Display for WasmCostsError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            WasmCostsError::ZeroOpcodesDivisor => write!(f, "Opcodes divisor must not be zero"),
+            WasmCostsError::ZeroMaxStackHeight => write!(f, "Max stack height must not be zero"),
+            WasmCostsError::MemoryLimitTooLarge(pages) => write!(
+                f,
+                "Memory limit of {} pages exceeds the maximum of {} pages",
+                pages, MAX_MEMORY_PAGES
+            ),
+        }
+    }
+}
+
+/// The serde representation uses the kebab-case keys of the `[wasm-costs]` section of a
+/// chainspec's `manifest.toml`.  Keys of the costs and limits added after the initial cost table
+/// may be omitted, and default to zero.
 // Taken (partially) from parity-ethereum
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct WasmCosts {
     /// Default opcode cost
     pub regular: u32,
     /// Div operations multiplier.
+    #[serde(rename = "div-multiplier")]
     pub div: u32,
     /// Mul operations multiplier.
+    #[serde(rename = "mul-multiplier")]
     pub mul: u32,
     /// Memory (load/store) operations multiplier.
+    #[serde(rename = "mem-multiplier")]
     pub mem: u32,
     /// Memory stipend. Amount of free memory (in 64kb pages) each contract can
     /// use for stack.
+    #[serde(rename = "mem-initial-pages")]
     pub initial_mem: u32,
     /// Grow memory cost, per page (64kb)
+    #[serde(rename = "mem-grow-per-page")]
     pub grow_mem: u32,
     /// Memory copy cost, per byte
+    #[serde(rename = "mem-copy-per-byte")]
     pub memcpy: u32,
     /// Max stack height (native WebAssembly stack limiter)
     pub max_stack_height: u32,
     /// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` /
     /// `opcodes_div`
+    #[serde(rename = "opcodes-multiplier")]
     pub opcodes_mul: u32,
     /// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` /
     /// `opcodes_div`
+    #[serde(rename = "opcodes-divisor")]
     pub opcodes_div: u32,
+    /// Storage write cost, per byte of the serialized value passed to `new_uref`, `write` and
+    /// `write_local`.  Zero disables per-byte charging.
+    #[serde(default, rename = "storage-write-per-byte")]
+    pub storage_write_byte: u32,
+    /// Flat cost of each call to the `read_value` and `read_value_local` host functions.
+    ///
+    /// Like `write_cost` and `add_cost`, not yet calibrated against `host_op_bench`, hence the
+    /// default of zero.
+    #[serde(default)]
+    pub read_cost: u32,
+    /// Flat cost of each call to the `write`, `write_local` and `new_uref` host functions, charged
+    /// in addition to `storage_write_byte`.
+    #[serde(default)]
+    pub write_cost: u32,
+    /// Flat cost of each call to the `add` and `add_local` host functions.
+    #[serde(default)]
+    pub add_cost: u32,
+    /// Flat cost of each call to the `blake2b` and `sha256` host functions.
+    #[serde(default, rename = "hash-base-cost")]
+    pub hash_base: u32,
+    /// Cost per byte of input to the `blake2b` and `sha256` host functions.
+    #[serde(default, rename = "hash-per-byte")]
+    pub hash_byte: u32,
+    /// Max number of memory pages (64 KiB each) a module may declare or grow to.  Zero selects
+    /// `DEFAULT_MAX_MEMORY_PAGES`.
+    #[serde(default, rename = "max-memory-pages")]
+    pub max_memory: u32,
+    /// Max number of entries in a module's function table.  Zero selects
+    /// `DEFAULT_MAX_TABLE_SIZE`.
+    #[serde(default)]
+    pub max_table_size: u32,
 }
 
 impl WasmCosts {
+    /// Returns the effective memory limit in pages.
+    pub fn memory_limit(&self) -> u32 {
+        if self.max_memory == 0 {
+            DEFAULT_MAX_MEMORY_PAGES
+        } else {
+            self.max_memory
+        }
+    }
+
+    /// Returns the effective function table size limit.
+    pub fn table_limit(&self) -> u32 {
+        if self.max_table_size == 0 {
+            DEFAULT_MAX_TABLE_SIZE
+        } else {
+            self.max_table_size
+        }
+    }
+
+    /// Checks that the divisors are non-zero and the limits are within what the engine can honour.
+    pub fn validate(&self) -> Result<(), WasmCostsError> {
+        if self.opcodes_div == 0 {
+            return Err(WasmCostsError::ZeroOpcodesDivisor);
+        }
+        if self.max_stack_height == 0 {
+            return Err(WasmCostsError::ZeroMaxStackHeight);
+        }
+        if self.max_memory > MAX_MEMORY_PAGES {
+            return Err(WasmCostsError::MemoryLimitTooLarge(self.max_memory));
+        }
+        Ok(())
+    }
+
+    /// Decodes the costs in the layout of the 1.0.0 release, which only holds the costs up to and
+    /// including `opcodes_div`.  The costs and limits added since are zero.
+    pub fn from_legacy_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (regular, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (mem, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (initial_mem, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (grow_mem, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (memcpy, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_stack_height, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (opcodes_mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (opcodes_div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let wasm_costs = WasmCosts {
+            regular,
+            div,
+            mul,
+            mem,
+            initial_mem,
+            grow_mem,
+            memcpy,
+            max_stack_height,
+            opcodes_mul,
+            opcodes_div,
+            ..Default::default()
+        };
+        Ok((wasm_costs, rem))
+    }
+
     pub(crate) fn to_set(&self) -> Set {
         let meterings = {
             let mut tmp = BTreeMap::new();
@@ -64,8 +209,20 @@ impl ToBytes for WasmCosts {
         ret.append(&mut self.max_stack_height.to_bytes()?);
         ret.append(&mut self.opcodes_mul.to_bytes()?);
         ret.append(&mut self.opcodes_div.to_bytes()?);
+        ret.append(&mut self.storage_write_byte.to_bytes()?);
+        ret.append(&mut self.read_cost.to_bytes()?);
+        ret.append(&mut self.write_cost.to_bytes()?);
+        ret.append(&mut self.add_cost.to_bytes()?);
+        ret.append(&mut self.hash_base.to_bytes()?);
+        ret.append(&mut self.hash_byte.to_bytes()?);
+        ret.append(&mut self.max_memory.to_bytes()?);
+        ret.append(&mut self.max_table_size.to_bytes()?);
         Ok(ret)
     }
+
+    fn serialized_length(&self) -> usize {
+        WASM_COSTS_SERIALIZED_LENGTH
+    }
 }
 
 impl FromBytes for WasmCosts {
@@ -80,6 +237,14 @@ impl FromBytes for WasmCosts {
         let (max_stack_height, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (opcodes_mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (opcodes_div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (storage_write_byte, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (read_cost, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (write_cost, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (add_cost, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (hash_base, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (hash_byte, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_memory, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_table_size, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let wasm_costs = WasmCosts {
             regular,
             div,
@@ -91,6 +256,14 @@ impl FromBytes for WasmCosts {
             max_stack_height,
             opcodes_mul,
             opcodes_div,
+            storage_write_byte,
+            read_cost,
+            write_cost,
+            add_cost,
+            hash_base,
+            hash_byte,
+            max_memory,
+            max_table_size,
         };
         Ok((wasm_costs, rem))
     }
@@ -113,6 +286,14 @@ pub mod gens {
             max_stack_height in num::u32::ANY,
             opcodes_mul in num::u32::ANY,
             opcodes_div in num::u32::ANY,
+            storage_write_byte in num::u32::ANY,
+            read_cost in num::u32::ANY,
+            write_cost in num::u32::ANY,
+            add_cost in num::u32::ANY,
+            hash_base in num::u32::ANY,
+            hash_byte in num::u32::ANY,
+            max_memory in num::u32::ANY,
+            max_table_size in num::u32::ANY,
         ) -> WasmCosts {
             WasmCosts {
                 regular,
@@ -125,6 +306,14 @@ pub mod gens {
                 max_stack_height,
                 opcodes_mul,
                 opcodes_div,
+                storage_write_byte,
+                read_cost,
+                write_cost,
+                add_cost,
+                hash_base,
+                hash_byte,
+                max_memory,
+                max_table_size,
             }
         }
     }
@@ -135,9 +324,27 @@ mod tests {
     use proptest::proptest;
 
     use engine_shared::test_utils;
-    use types::bytesrepr;
+    use types::bytesrepr::{self, ToBytes};
+
+    use super::{
+        gens, WasmCosts, WasmCostsError, DEFAULT_MAX_MEMORY_PAGES, DEFAULT_MAX_TABLE_SIZE,
+        MAX_MEMORY_PAGES,
+    };
 
-    use super::gens;
+    const WASM_COSTS_SECTION: &str = r#"
+regular = 1
+div-multiplier = 16
+mul-multiplier = 4
+mem-multiplier = 2
+mem-initial-pages = 4096
+mem-grow-per-page = 8192
+mem-copy-per-byte = 1
+max-stack-height = 65536
+opcodes-multiplier = 3
+opcodes-divisor = 8
+hash-per-byte = 5
+max-memory-pages = 128
+"#;
 
     #[test]
     fn should_serialize_and_deserialize() {
@@ -147,6 +354,93 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&free);
     }
 
+    #[test]
+    fn zero_limits_should_select_defaults() {
+        let wasm_costs = WasmCosts::default();
+        assert_eq!(wasm_costs.memory_limit(), DEFAULT_MAX_MEMORY_PAGES);
+        assert_eq!(wasm_costs.table_limit(), DEFAULT_MAX_TABLE_SIZE);
+
+        let wasm_costs = WasmCosts {
+            max_memory: 16,
+            max_table_size: 8,
+            ..Default::default()
+        };
+        assert_eq!(wasm_costs.memory_limit(), 16);
+        assert_eq!(wasm_costs.table_limit(), 8);
+    }
+
+    #[test]
+    fn should_deserialize_chainspec_section() {
+        let wasm_costs: WasmCosts = toml::from_str(WASM_COSTS_SECTION).expect("should deserialize");
+        let expected = WasmCosts {
+            regular: 1,
+            div: 16,
+            mul: 4,
+            mem: 2,
+            initial_mem: 4096,
+            grow_mem: 8192,
+            memcpy: 1,
+            max_stack_height: 65536,
+            opcodes_mul: 3,
+            opcodes_div: 8,
+            hash_byte: 5,
+            max_memory: 128,
+            ..Default::default()
+        };
+        assert_eq!(wasm_costs, expected);
+        assert_eq!(wasm_costs.validate(), Ok(()));
+    }
+
+    #[test]
+    fn should_fail_to_deserialize_without_initial_costs() {
+        let section = WASM_COSTS_SECTION.replace("opcodes-divisor = 8", "");
+        assert!(toml::from_str::<WasmCosts>(&section).is_err());
+    }
+
+    #[test]
+    fn should_validate_mocks() {
+        assert_eq!(test_utils::wasm_costs_mock().validate(), Ok(()));
+        assert_eq!(test_utils::wasm_costs_free().validate(), Ok(()));
+    }
+
+    #[test]
+    fn should_reject_invalid_wasm_costs() {
+        let valid = test_utils::wasm_costs_mock();
+
+        let wasm_costs = WasmCosts {
+            opcodes_div: 0,
+            ..valid
+        };
+        assert_eq!(
+            wasm_costs.validate(),
+            Err(WasmCostsError::ZeroOpcodesDivisor)
+        );
+
+        let wasm_costs = WasmCosts {
+            max_stack_height: 0,
+            ..valid
+        };
+        assert_eq!(
+            wasm_costs.validate(),
+            Err(WasmCostsError::ZeroMaxStackHeight)
+        );
+
+        let wasm_costs = WasmCosts {
+            max_memory: MAX_MEMORY_PAGES + 1,
+            ..valid
+        };
+        assert_eq!(
+            wasm_costs.validate(),
+            Err(WasmCostsError::MemoryLimitTooLarge(MAX_MEMORY_PAGES + 1))
+        );
+
+        let wasm_costs = WasmCosts {
+            max_memory: MAX_MEMORY_PAGES,
+            ..valid
+        };
+        assert_eq!(wasm_costs.validate(), Ok(()));
+    }
+
     proptest! {
         #[test]
         fn should_serialize_and_deserialize_with_arbitrary_values(
@@ -154,5 +448,23 @@ mod tests {
         ) {
             bytesrepr::test_serialization_roundtrip(&wasm_costs);
         }
+
+        #[test]
+        fn toml_roundtrip_should_preserve_binary_encoding(wasm_costs in gens::wasm_costs_arb()) {
+            let serialized = toml::to_string(&wasm_costs).expect("should serialize to TOML");
+            let deserialized: WasmCosts =
+                toml::from_str(&serialized).expect("should deserialize from TOML");
+            assert_eq!(deserialized, wasm_costs);
+            assert_eq!(deserialized.to_bytes(), wasm_costs.to_bytes());
+        }
+
+        #[test]
+        fn json_roundtrip_should_preserve_binary_encoding(wasm_costs in gens::wasm_costs_arb()) {
+            let serialized = serde_json::to_string(&wasm_costs).expect("should serialize to JSON");
+            let deserialized: WasmCosts =
+                serde_json::from_str(&serialized).expect("should deserialize from JSON");
+            assert_eq!(deserialized, wasm_costs);
+            assert_eq!(deserialized.to_bytes(), wasm_costs.to_bytes());
+        }
     }
 }
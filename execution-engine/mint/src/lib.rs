@@ -5,7 +5,7 @@ mod storage_provider;
 
 use core::convert::TryFrom;
 
-use types::{system_contract_errors::mint::Error, Key, URef, U512};
+use types::{checked_sub, system_contract_errors::mint::Error, Key, URef, U512};
 
 pub use crate::{runtime_provider::RuntimeProvider, storage_provider::StorageProvider};
 
@@ -58,14 +58,13 @@ where
             Some(source_value) => source_value,
             None => return Err(Error::SourceNotFound),
         };
-        if amount > source_value {
-            return Err(Error::InsufficientFunds);
-        }
+        let source_value =
+            checked_sub(source_value, amount).map_err(|_| Error::InsufficientFunds)?;
         let dest_bal: URef = match S::read_local(&dest.addr())? {
             Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
             None => return Err(Error::DestNotFound),
         };
-        S::write(source_bal, source_value - amount)?;
+        S::write(source_bal, source_value)?;
         S::add(dest_bal, amount)?;
         Ok(())
     }
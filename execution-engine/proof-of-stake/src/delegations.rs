@@ -0,0 +1,227 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use types::{
+    account::PublicKey,
+    checked_div, checked_mul,
+    system_contract_errors::pos::{Error, Result},
+    U512,
+};
+
+use crate::stakes::Stakes;
+
+/// The delegator ledger, assigning the amount of motes delegated by each
+/// delegator to each validator.  Keys are `(delegator, validator)` pairs.
+///
+/// Delegated motes are part of the validator's stakes, i.e. the stakes of a
+/// validator are the sum of its own bond and all delegations to it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Delegations(pub BTreeMap<(PublicKey, PublicKey), U512>);
+
+impl Delegations {
+    /// Adds `amount` to the motes `delegator` has delegated to `validator`.
+    pub fn delegate(&mut self, delegator: &PublicKey, validator: &PublicKey, amount: U512) {
+        self.0
+            .entry((*delegator, *validator))
+            .and_modify(|x| *x += amount)
+            .or_insert(amount);
+    }
+
+    /// If `maybe_amount` is `None`, removes the whole delegation of `delegator`
+    /// to `validator`, otherwise subtracts the given amount.
+    ///
+    /// Returns the amount that was actually subtracted, or an error if
+    /// * `delegator` has not delegated to `validator`,
+    /// * the specified amount is larger than the delegation.
+    pub fn undelegate(
+        &mut self,
+        delegator: &PublicKey,
+        validator: &PublicKey,
+        maybe_amount: Option<U512>,
+    ) -> Result<U512> {
+        let key = (*delegator, *validator);
+        let delegation = self.0.get_mut(&key).ok_or(Error::NotDelegated)?;
+        let amount = maybe_amount.unwrap_or(*delegation);
+        if amount > *delegation {
+            return Err(Error::UndelegateTooLarge);
+        }
+        *delegation -= amount;
+        if delegation.is_zero() {
+            self.0.remove(&key);
+        }
+        Ok(amount)
+    }
+
+    /// Returns the total amount delegated to `validator`.
+    pub fn delegated_to(&self, validator: &PublicKey) -> U512 {
+        self.0
+            .iter()
+            .filter(|((_, v), _)| v == validator)
+            .fold(U512::zero(), |sum, (_, amount)| sum.saturating_add(*amount))
+    }
+
+    /// Subtracts `percentage` percent from every delegation to `validator`, removing the
+    /// delegations which drop to zero.
+    ///
+    /// Returns the total amount subtracted.
+    pub fn slash(&mut self, validator: &PublicKey, percentage: u8) -> Result<U512> {
+        let percentage = U512::from(percentage);
+        let mut slashed = U512::zero();
+        for ((_, v), amount) in self.0.iter_mut() {
+            if v == validator {
+                let fine = checked_div(checked_mul(*amount, percentage)?, U512::from(100))?;
+                *amount -= fine;
+                slashed += fine;
+            }
+        }
+        self.0.retain(|_, amount| !amount.is_zero());
+        Ok(slashed)
+    }
+
+    /// Splits `reward` between the delegators, pro rata to their share of the
+    /// total `stakes`.  The shares are added to the delegations and to the
+    /// validators' stakes, i.e. delegator rewards are re-staked.
+    ///
+    /// Returns the total amount distributed.  Because shares are rounded down,
+    /// this is at most `reward`; the rest belongs to the validators.
+    pub fn distribute_rewards(&mut self, stakes: &mut Stakes, reward: U512) -> Result<U512> {
+        let total_stakes = stakes.sum();
+        if total_stakes.is_zero() {
+            return Ok(U512::zero());
+        }
+        let mut shares: Vec<((PublicKey, PublicKey), U512)> = Vec::new();
+        for (key, amount) in self.0.iter() {
+            let share = checked_div(checked_mul(reward, *amount)?, total_stakes)?;
+            if !share.is_zero() {
+                shares.push((*key, share));
+            }
+        }
+
+        let mut distributed = U512::zero();
+        for ((delegator, validator), share) in shares {
+            self.delegate(&delegator, &validator, share);
+            stakes.bond(&validator, share);
+            distributed += share;
+        }
+        Ok(distributed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{account::PublicKey, system_contract_errors::pos::Error, U512};
+
+    use super::Delegations;
+    use crate::stakes::Stakes;
+
+    const KEY1: [u8; 32] = [1; 32];
+    const KEY2: [u8; 32] = [2; 32];
+    const KEY3: [u8; 32] = [3; 32];
+
+    fn new_delegations(delegations: &[([u8; 32], [u8; 32], u64)]) -> Delegations {
+        Delegations(
+            delegations
+                .iter()
+                .map(|&(delegator, validator, amount)| {
+                    (
+                        (PublicKey::new(delegator), PublicKey::new(validator)),
+                        U512::from(amount),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn new_stakes(stakes: &[([u8; 32], u64)]) -> Stakes {
+        Stakes(
+            stakes
+                .iter()
+                .map(|&(key, amount)| (PublicKey::new(key), U512::from(amount)))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_delegate() {
+        let mut delegations = Delegations::default();
+        delegations.delegate(&PublicKey::new(KEY1), &PublicKey::new(KEY2), U512::from(5));
+        delegations.delegate(&PublicKey::new(KEY1), &PublicKey::new(KEY2), U512::from(7));
+        delegations.delegate(&PublicKey::new(KEY3), &PublicKey::new(KEY2), U512::from(1));
+        assert_eq!(
+            new_delegations(&[(KEY1, KEY2, 12), (KEY3, KEY2, 1)]),
+            delegations
+        );
+        assert_eq!(
+            U512::from(13),
+            delegations.delegated_to(&PublicKey::new(KEY2))
+        );
+        assert_eq!(
+            U512::zero(),
+            delegations.delegated_to(&PublicKey::new(KEY1))
+        );
+    }
+
+    #[test]
+    fn test_undelegate() {
+        let mut delegations = new_delegations(&[(KEY1, KEY2, 12)]);
+        assert_eq!(
+            Err(Error::UndelegateTooLarge),
+            delegations.undelegate(
+                &PublicKey::new(KEY1),
+                &PublicKey::new(KEY2),
+                Some(U512::from(13))
+            )
+        );
+        assert_eq!(
+            Ok(U512::from(2)),
+            delegations.undelegate(
+                &PublicKey::new(KEY1),
+                &PublicKey::new(KEY2),
+                Some(U512::from(2))
+            )
+        );
+        assert_eq!(new_delegations(&[(KEY1, KEY2, 10)]), delegations);
+        assert_eq!(
+            Ok(U512::from(10)),
+            delegations.undelegate(&PublicKey::new(KEY1), &PublicKey::new(KEY2), None)
+        );
+        assert_eq!(Delegations::default(), delegations);
+        assert_eq!(
+            Err(Error::NotDelegated),
+            delegations.undelegate(&PublicKey::new(KEY1), &PublicKey::new(KEY2), None)
+        );
+    }
+
+    #[test]
+    fn test_slash() {
+        let mut delegations =
+            new_delegations(&[(KEY1, KEY2, 100), (KEY3, KEY2, 1), (KEY3, KEY1, 100)]);
+        assert_eq!(
+            Ok(U512::from(50)),
+            delegations.slash(&PublicKey::new(KEY2), 50)
+        );
+        assert_eq!(
+            new_delegations(&[(KEY1, KEY2, 50), (KEY3, KEY2, 1), (KEY3, KEY1, 100)]),
+            delegations
+        );
+        assert_eq!(
+            Ok(U512::from(51)),
+            delegations.slash(&PublicKey::new(KEY2), 100)
+        );
+        assert_eq!(new_delegations(&[(KEY3, KEY1, 100)]), delegations);
+    }
+
+    #[test]
+    fn test_distribute_rewards() {
+        let mut delegations = new_delegations(&[(KEY1, KEY2, 100), (KEY3, KEY2, 300)]);
+        let mut stakes = new_stakes(&[(KEY2, 1_000)]);
+
+        // The delegators hold 10% and 30% of the stakes.  Shares are rounded down.
+        let distributed = delegations.distribute_rewards(&mut stakes, U512::from(109));
+        assert_eq!(Ok(U512::from(10 + 32)), distributed);
+        assert_eq!(
+            new_delegations(&[(KEY1, KEY2, 110), (KEY3, KEY2, 332)]),
+            delegations
+        );
+        assert_eq!(new_stakes(&[(KEY2, 1_042)]), stakes);
+    }
+}
@@ -0,0 +1,14 @@
+use types::U512;
+
+use crate::{delegations::Delegations, Result};
+
+pub trait DelegationsProvider {
+    fn read() -> Result<Delegations>;
+
+    fn write(delegations: &Delegations);
+
+    /// Reads the fees paid since the last era whose rewards were distributed to the delegators.
+    fn read_undistributed_fees() -> Result<U512>;
+
+    fn write_undistributed_fees(fees: U512);
+}
@@ -2,6 +2,8 @@
 
 extern crate alloc;
 
+mod delegations;
+mod delegations_provider;
 mod mint_provider;
 mod queue;
 mod queue_provider;
@@ -9,23 +11,28 @@ mod runtime_provider;
 mod stakes;
 mod stakes_provider;
 
+use alloc::vec::Vec;
+
 use types::{
     account::{PublicKey, PurseId},
+    checked_add, checked_div, checked_mul, checked_sub,
     system_contract_errors::pos::{Error, Result},
-    AccessRights, TransferredTo, URef, U512,
+    AccessRights, URef, U512,
 };
 
 pub use crate::{
+    delegations::Delegations, delegations_provider::DelegationsProvider,
     mint_provider::MintProvider, queue::Queue, queue_provider::QueueProvider,
     runtime_provider::RuntimeProvider, stakes::Stakes, stakes_provider::StakesProvider,
 };
 
-pub trait ProofOfStake<M, Q, R, S>
+pub trait ProofOfStake<M, Q, R, S, D>
 where
     M: MintProvider,
     Q: QueueProvider,
     R: RuntimeProvider,
     S: StakesProvider,
+    D: DelegationsProvider,
 {
     fn bond(&self, validator: PublicKey, amount: U512, source_uref: URef) -> Result<()> {
         if amount.is_zero() {
@@ -40,28 +47,53 @@ where
             .map_err(|_| Error::BondTransferFailed)?;
         internal::bond::<Q, S>(amount, validator, timestamp)?;
 
-        // TODO: Remove this and set nonzero delays once the system calls `step` in each block.
-        let unbonds = internal::step::<Q, S>(timestamp)?;
-        for entry in unbonds {
-            let _: TransferredTo =
-                M::transfer_from_purse_to_account(pos_purse, entry.validator, entry.amount)
-                    .map_err(|_| Error::BondTransferFailed)?;
-        }
-        Ok(())
+        // TODO: Remove this and set a nonzero delay once the system calls `step` in each block.
+        internal::process_bond_requests::<Q, S>(timestamp)
     }
 
     fn unbond(&self, validator: PublicKey, maybe_amount: Option<U512>) -> Result<()> {
-        let pos_purse = internal::get_bonding_purse::<R>()?;
         let timestamp = R::get_block_time();
-        internal::unbond::<Q, S>(maybe_amount, validator, timestamp)?;
+        // The funds are paid out by `process_unbond_requests` once the unbonding delay is over.
+        internal::unbond::<Q, S, D>(maybe_amount, validator, timestamp)
+    }
 
-        // TODO: Remove this and set nonzero delays once the system calls `step` in each block.
-        let unbonds = internal::step::<Q, S>(timestamp)?;
-        for entry in unbonds {
-            M::transfer_from_purse_to_account(pos_purse, entry.validator, entry.amount)
-                .map_err(|_| Error::UnbondTransferFailed)?;
+    fn delegate(
+        &self,
+        delegator: PublicKey,
+        validator: PublicKey,
+        amount: U512,
+        source_uref: URef,
+    ) -> Result<()> {
+        if amount.is_zero() {
+            return Err(Error::BondTooSmall);
         }
-        Ok(())
+        let source = PurseId::new(source_uref);
+        let pos_purse = internal::get_bonding_purse::<R>()?;
+        // Delegated motes are held in the bonding purse, together with the validators' bonds.
+        M::transfer_from_purse_to_purse(source, pos_purse, amount)
+            .map_err(|_| Error::DelegateTransferFailed)?;
+        internal::delegate::<S, D>(amount, delegator, validator)
+    }
+
+    fn undelegate(
+        &self,
+        delegator: PublicKey,
+        validator: PublicKey,
+        maybe_amount: Option<U512>,
+    ) -> Result<()> {
+        let timestamp = R::get_block_time();
+        // The funds are paid out by `process_unbond_requests` once the unbonding delay is over.
+        internal::undelegate::<Q, S, D>(maybe_amount, delegator, validator, timestamp)
+    }
+
+    fn redelegate(
+        &self,
+        delegator: PublicKey,
+        from_validator: PublicKey,
+        to_validator: PublicKey,
+        maybe_amount: Option<U512>,
+    ) -> Result<()> {
+        internal::redelegate::<S, D>(maybe_amount, delegator, from_validator, to_validator)
     }
 
     fn step(&self) -> Result<()> {
@@ -81,6 +113,40 @@ where
         Ok(())
     }
 
+    fn process_unbond_requests(&self) -> Result<()> {
+        let pos_purse = internal::get_bonding_purse::<R>()?;
+        let timestamp = R::get_block_time();
+        // This is called by the system when finalizing each deploy.
+        let unbonds = internal::process_unbond_requests::<Q, R>(timestamp)?;
+
+        // Requests which can't be paid out are kept for the next call rather than failing the
+        // finalization of the deploy.
+        let unpaid = unbonds
+            .into_iter()
+            .filter(|entry| {
+                M::transfer_from_purse_to_account(pos_purse, entry.validator, entry.amount).is_err()
+            })
+            .collect();
+        internal::requeue_unbond_requests::<Q>(unpaid);
+        Ok(())
+    }
+
+    fn slash(&self, validators: Vec<PublicKey>) -> Result<()> {
+        let slashed = internal::slash::<Q, R, S, D>(&validators)?;
+        if slashed.is_zero() {
+            return Ok(());
+        }
+        let bonding_purse = internal::get_bonding_purse::<R>()?;
+        let rewards_purse = internal::get_rewards_purse::<R>()?;
+        // The mint can't burn motes, so the slashed stakes are redistributed to the validators.
+        M::transfer_from_purse_to_purse(bonding_purse, rewards_purse, slashed)
+            .map_err(|_| Error::FailedTransferToRewardsPurse)
+    }
+
+    fn distribute_rewards(&self, era_id: u64, seigniorage_rate: u64) -> Result<()> {
+        internal::distribute_era_rewards::<M, R, S, D>(era_id, seigniorage_rate)
+    }
+
     fn get_payment_purse(&self) -> Result<PurseId> {
         let purse = internal::get_payment_purse::<R>()?;
         // Limit the access rights so only balance query and deposit are allowed.
@@ -102,13 +168,20 @@ where
         Ok(maybe_purse.map(|p| PurseId::new(p.value().remove_access_rights())))
     }
 
-    fn finalize_payment(&self, amount_spent: U512, account: PublicKey) -> Result<()> {
-        internal::finalize_payment::<M, R>(amount_spent, account)
+    fn finalize_payment(
+        &self,
+        amount_spent: U512,
+        account: PublicKey,
+        refund_percentage: u8,
+    ) -> Result<()> {
+        internal::finalize_payment::<M, R, D>(amount_spent, account, refund_percentage)?;
+        self.process_unbond_requests()
     }
 }
 
 mod internal {
-    use alloc::vec::Vec;
+    use alloc::{collections::BTreeSet, string::String, vec::Vec};
+    use core::fmt::Write;
 
     use types::{
         account::{PublicKey, PurseId},
@@ -118,8 +191,9 @@ mod internal {
     };
 
     use crate::{
-        mint_provider::MintProvider, queue::QueueEntry, queue_provider::QueueProvider,
-        runtime_provider::RuntimeProvider, stakes_provider::StakesProvider,
+        delegations_provider::DelegationsProvider, mint_provider::MintProvider, queue::QueueEntry,
+        queue_provider::QueueProvider, runtime_provider::RuntimeProvider,
+        stakes_provider::StakesProvider,
     };
 
     /// Account used to run system functions (in particular `finalize_payment`).
@@ -139,12 +213,16 @@ mod internal {
     /// corresponds to is set by the user.
     const REFUND_PURSE_KEY: &str = "pos_refund_purse";
 
+    /// The prefix of the uref names where the PoS holds each validator's share of the seigniorage.
+    /// It is followed by the validator's public key in hex.
+    const VALIDATOR_REWARD_PURSE_KEY_PREFIX: &str = "pos_reward_purse_";
+
+    /// The denominator of the seigniorage rate, which is given in billionths of the total stakes.
+    const SEIGNIORAGE_RATE_DENOMINATOR: u64 = 1_000_000_000;
+
     /// The time from a bonding request until the bond becomes effective and part of the stake.
     const BOND_DELAY: u64 = 0;
 
-    /// The time from an unbonding request until the stakes are paid out.
-    const UNBOND_DELAY: u64 = 0;
-
     /// The maximum number of pending bonding requests.
     const MAX_BOND_LEN: usize = 100;
 
@@ -178,7 +256,10 @@ mod internal {
     /// Enqueues the deploy's creator for unbonding. Their vote weight as a validator is decreased
     /// immediately, but the funds will only be released after a delay. If `maybe_amount` is `None`,
     /// all funds are enqueued for withdrawal, terminating the validator status.
-    pub fn unbond<Q: QueueProvider, S: StakesProvider>(
+    ///
+    /// A validator can only unbond its own bond, not the stakes delegated to it.  While there are
+    /// delegations to it, it can't terminate its validator status.
+    pub fn unbond<Q: QueueProvider, S: StakesProvider, D: DelegationsProvider>(
         maybe_amount: Option<U512>,
         validator: PublicKey,
         timestamp: BlockTime,
@@ -189,6 +270,14 @@ mod internal {
         }
 
         let mut stakes = S::read()?;
+        let delegated = D::read()?.delegated_to(&validator);
+        if !delegated.is_zero() {
+            let stake = stakes.0.get(&validator).cloned().unwrap_or_default();
+            match maybe_amount {
+                Some(amount) if amount <= stake.saturating_sub(delegated) => (),
+                _ => return Err(Error::DelegatedStakes),
+            }
+        }
         let payout = stakes.unbond(&validator, maybe_amount)?;
         S::write(&stakes);
         // TODO: Make sure the destination is valid and the amount can be paid. The actual payment
@@ -199,20 +288,100 @@ mod internal {
         Ok(())
     }
 
+    /// Delegates `amount` from `delegator` to the bonded `validator`. Unlike a bond, which is
+    /// enqueued, the validator's stakes are increased immediately.
+    pub fn delegate<S: StakesProvider, D: DelegationsProvider>(
+        amount: U512,
+        delegator: PublicKey,
+        validator: PublicKey,
+    ) -> Result<()> {
+        if delegator == validator {
+            return Err(Error::SelfDelegation);
+        }
+
+        let mut stakes = S::read()?;
+        if !stakes.0.contains_key(&validator) {
+            return Err(Error::NotBonded);
+        }
+        stakes.validate_bonding(&validator, amount)?;
+        stakes.bond(&validator, amount);
+
+        let mut delegations = D::read()?;
+        delegations.delegate(&delegator, &validator, amount);
+
+        S::write(&stakes);
+        D::write(&delegations);
+        Ok(())
+    }
+
+    /// Enqueues the delegation of `delegator` to `validator` for withdrawal. The validator's stakes
+    /// are decreased immediately, but the funds will only be released after a delay. If
+    /// `maybe_amount` is `None`, the whole delegation is withdrawn.
+    pub fn undelegate<Q: QueueProvider, S: StakesProvider, D: DelegationsProvider>(
+        maybe_amount: Option<U512>,
+        delegator: PublicKey,
+        validator: PublicKey,
+        timestamp: BlockTime,
+    ) -> Result<()> {
+        let mut queue = Q::read_unbonding();
+        if queue.0.len() >= MAX_UNBOND_LEN {
+            return Err(Error::TooManyEventsInQueue);
+        }
+
+        let mut delegations = D::read()?;
+        let amount = delegations.undelegate(&delegator, &validator, maybe_amount)?;
+        let mut stakes = S::read()?;
+        let payout = stakes.unbond(&validator, Some(amount))?;
+        S::write(&stakes);
+        D::write(&delegations);
+        // The unbonding queue pays out to the delegator, just like to an unbonding validator.
+        queue.push(delegator, payout, timestamp)?;
+        Q::write_unbonding(queue);
+        Ok(())
+    }
+
+    /// Moves the delegation of `delegator` from `from_validator` to the bonded `to_validator`,
+    /// without paying out the funds. If `maybe_amount` is `None`, the whole delegation is moved.
+    pub fn redelegate<S: StakesProvider, D: DelegationsProvider>(
+        maybe_amount: Option<U512>,
+        delegator: PublicKey,
+        from_validator: PublicKey,
+        to_validator: PublicKey,
+    ) -> Result<()> {
+        if delegator == to_validator {
+            return Err(Error::SelfDelegation);
+        }
+
+        let mut delegations = D::read()?;
+        let amount = delegations.undelegate(&delegator, &from_validator, maybe_amount)?;
+        let mut stakes = S::read()?;
+        if !stakes.0.contains_key(&to_validator) {
+            return Err(Error::NotBonded);
+        }
+        let moved = stakes.unbond(&from_validator, Some(amount))?;
+        stakes.validate_bonding(&to_validator, moved)?;
+        stakes.bond(&to_validator, moved);
+        delegations.delegate(&delegator, &to_validator, moved);
+
+        S::write(&stakes);
+        D::write(&delegations);
+        Ok(())
+    }
+
     /// Removes all due requests from the queues and applies them.
     pub fn step<Q: QueueProvider, S: StakesProvider>(
         timestamp: BlockTime,
     ) -> Result<Vec<QueueEntry>> {
-        let mut bonding_queue = Q::read_bonding();
-        let mut unbonding_queue = Q::read_unbonding();
+        process_bond_requests::<Q, S>(timestamp)?;
+        pop_due_unbond_requests::<Q>(timestamp)
+    }
 
+    /// Removes all due requests from the bonding queue and adds them to the stakes.
+    pub fn process_bond_requests<Q: QueueProvider, S: StakesProvider>(
+        timestamp: BlockTime,
+    ) -> Result<()> {
+        let mut bonding_queue = Q::read_bonding();
         let bonds = bonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(BOND_DELAY)));
-        let unbonds =
-            unbonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(UNBOND_DELAY)));
-
-        if !unbonds.is_empty() {
-            Q::write_unbonding(unbonding_queue);
-        }
 
         if !bonds.is_empty() {
             Q::write_bonding(bonding_queue);
@@ -222,10 +391,233 @@ mod internal {
             }
             S::write(&stakes);
         }
+        Ok(())
+    }
+
+    /// Removes all requests from the unbonding queue which are older than the unbonding delay, and
+    /// returns them to be paid out.  Can only be called by the system account.
+    pub fn process_unbond_requests<Q: QueueProvider, R: RuntimeProvider>(
+        timestamp: BlockTime,
+    ) -> Result<Vec<QueueEntry>> {
+        let caller = R::get_caller();
+        if caller.value() != SYSTEM_ACCOUNT {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+        pop_due_unbond_requests::<Q>(timestamp)
+    }
+
+    /// Puts unbonding requests which couldn't be paid out back at the front of the unbonding
+    /// queue, so that they are paid out by a later call to `process_unbond_requests`.
+    pub fn requeue_unbond_requests<Q: QueueProvider>(unpaid: Vec<QueueEntry>) {
+        if unpaid.is_empty() {
+            return;
+        }
+        let mut unbonding_queue = Q::read_unbonding();
+        unbonding_queue.requeue(unpaid);
+        Q::write_unbonding(unbonding_queue);
+    }
+
+    fn pop_due_unbond_requests<Q: QueueProvider>(timestamp: BlockTime) -> Result<Vec<QueueEntry>> {
+        let unbonding_delay = Q::read_unbonding_delay()?;
+        let now: u64 = timestamp.into();
+        let due = match now.checked_sub(unbonding_delay) {
+            Some(due) => BlockTime::new(due),
+            // No request can be older than the delay yet.
+            None => return Ok(Vec::new()),
+        };
 
+        let mut unbonding_queue = Q::read_unbonding();
+        let unbonds = unbonding_queue.pop_due(due);
+
+        if !unbonds.is_empty() {
+            Q::write_unbonding(unbonding_queue);
+        }
         Ok(unbonds)
     }
 
+    /// Slashes the given validators, taking the configured percentage of their own bonds, of the
+    /// delegations to them and of their pending unbonding requests.  Can only be called by the
+    /// system account.
+    ///
+    /// Returns the total amount slashed, which is still held in the bonding purse.
+    pub fn slash<
+        Q: QueueProvider,
+        R: RuntimeProvider,
+        S: StakesProvider,
+        D: DelegationsProvider,
+    >(
+        validators: &[PublicKey],
+    ) -> Result<U512> {
+        let caller = R::get_caller();
+        if caller.value() != SYSTEM_ACCOUNT {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+        slash_validators::<Q, S, D>(validators)
+    }
+
+    fn slash_validators<Q: QueueProvider, S: StakesProvider, D: DelegationsProvider>(
+        validators: &[PublicKey],
+    ) -> Result<U512> {
+        let percentage = S::read_slash_percentage()?;
+        if percentage == 0 {
+            return Ok(U512::zero());
+        }
+
+        let mut stakes = S::read()?;
+        let mut delegations = D::read()?;
+        let mut unbonding_queue = Q::read_unbonding();
+        let mut slashed = U512::zero();
+        // Evidence against the same validator may be reported more than once.
+        let validators: BTreeSet<PublicKey> = validators.iter().cloned().collect();
+        for validator in &validators {
+            let stake = stakes.0.get(validator).cloned().unwrap_or_default();
+            let own_bond = stake.saturating_sub(delegations.delegated_to(validator));
+            let own_fine = checked_div(
+                checked_mul(own_bond, U512::from(percentage))?,
+                U512::from(100),
+            )?;
+            let fine = checked_add(own_fine, delegations.slash(validator, percentage)?)?;
+            slashed = checked_add(slashed, stakes.slash(validator, fine))?;
+            slashed = checked_add(slashed, unbonding_queue.slash(validator, percentage)?)?;
+        }
+
+        if !slashed.is_zero() {
+            S::write(&stakes);
+            D::write(&delegations);
+            Q::write_unbonding(unbonding_queue);
+        }
+        Ok(slashed)
+    }
+
+    /// Mints the seigniorage for the era `era_id`, `seigniorage_rate` billionths of the total
+    /// stakes, and splits it between the validators pro rata to their stakes, paying each share
+    /// into the validator's reward purse.  The remainder left by rounding goes to the rewards
+    /// purse.  The fees paid since the last rewarded era are then split between the delegators.
+    /// Can only be called by the system account, once per era.
+    pub fn distribute_era_rewards<
+        M: MintProvider,
+        R: RuntimeProvider,
+        S: StakesProvider,
+        D: DelegationsProvider,
+    >(
+        era_id: u64,
+        seigniorage_rate: u64,
+    ) -> Result<()> {
+        let caller = R::get_caller();
+        if caller.value() != SYSTEM_ACCOUNT {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+        let (seigniorage, shares) = era_seigniorage::<S>(era_id, seigniorage_rate)?;
+        if !seigniorage.is_zero() {
+            pay_seigniorage::<M, R>(seigniorage, shares)?;
+        }
+        distribute_fees::<M, R, S, D>()
+    }
+
+    /// Mints `seigniorage` and pays the validators' `shares` of it into their reward purses.
+    fn pay_seigniorage<M: MintProvider, R: RuntimeProvider>(
+        seigniorage: U512,
+        shares: Vec<(PublicKey, U512)>,
+    ) -> Result<()> {
+        let source = M::mint(seigniorage).map_err(|_| Error::MintSeigniorageFailed)?;
+        let mut distributed = U512::zero();
+        for (validator, share) in shares {
+            let reward_purse = get_or_create_validator_reward_purse::<M, R>(&validator)?;
+            M::transfer_from_purse_to_purse(source, reward_purse, share)
+                .map_err(|_| Error::FailedTransferToValidatorRewardPurse)?;
+            distributed += share;
+        }
+
+        let remainder = checked_sub(seigniorage, distributed)?;
+        if remainder.is_zero() {
+            return Ok(());
+        }
+        let rewards_purse = get_rewards_purse::<R>()?;
+        M::transfer_from_purse_to_purse(source, rewards_purse, remainder)
+            .map_err(|_| Error::FailedTransferToRewardsPurse)
+    }
+
+    /// Splits the fees paid into the rewards purse since the last rewarded era between the
+    /// delegators, and moves their shares to the bonding purse.
+    fn distribute_fees<
+        M: MintProvider,
+        R: RuntimeProvider,
+        S: StakesProvider,
+        D: DelegationsProvider,
+    >() -> Result<()> {
+        let fees = D::read_undistributed_fees()?;
+        if fees.is_zero() {
+            return Ok(());
+        }
+        D::write_undistributed_fees(U512::zero());
+
+        let delegators_reward = distribute_rewards::<S, D>(fees)?;
+        if delegators_reward.is_zero() {
+            return Ok(());
+        }
+        let rewards_purse = get_rewards_purse::<R>()?;
+        let bonding_purse = get_bonding_purse::<R>()?;
+        M::transfer_from_purse_to_purse(rewards_purse, bonding_purse, delegators_reward)
+            .map_err(|_| Error::FailedTransferToBondingPurse)
+    }
+
+    /// Marks the era `era_id` as rewarded and returns its seigniorage at `seigniorage_rate`,
+    /// together with the validators' shares of it.
+    fn era_seigniorage<S: StakesProvider>(
+        era_id: u64,
+        seigniorage_rate: u64,
+    ) -> Result<(U512, Vec<(PublicKey, U512)>)> {
+        if let Some(last_rewarded_era) = S::read_last_rewarded_era()? {
+            if era_id <= last_rewarded_era {
+                return Err(Error::EraAlreadyRewarded);
+            }
+        }
+        let stakes = S::read()?;
+        S::write_last_rewarded_era(era_id);
+
+        let seigniorage = checked_div(
+            checked_mul(stakes.sum(), U512::from(seigniorage_rate))?,
+            U512::from(SEIGNIORAGE_RATE_DENOMINATOR),
+        )?;
+        let shares = stakes.split_reward(seigniorage)?;
+        Ok((seigniorage, shares))
+    }
+
+    fn validator_reward_purse_key(validator: &PublicKey) -> String {
+        let mut name = String::from(VALIDATOR_REWARD_PURSE_KEY_PREFIX);
+        for byte in &validator.value()[..] {
+            write!(name, "{:02x}", byte).expect("Writing to a string cannot fail");
+        }
+        name
+    }
+
+    /// Returns the purse holding the validator's share of the seigniorage, if any was paid yet.
+    fn get_validator_reward_purse<R: RuntimeProvider>(
+        validator: &PublicKey,
+    ) -> Result<Option<PurseId>> {
+        match get_purse_id::<R>(&validator_reward_purse_key(validator)) {
+            Ok(purse_id) => Ok(Some(purse_id)),
+            Err(PurseLookupError::KeyNotFound) => Ok(None),
+            Err(PurseLookupError::KeyUnexpectedType) => {
+                Err(Error::ValidatorRewardPurseKeyUnexpectedType)
+            }
+        }
+    }
+
+    fn get_or_create_validator_reward_purse<M: MintProvider, R: RuntimeProvider>(
+        validator: &PublicKey,
+    ) -> Result<PurseId> {
+        if let Some(purse_id) = get_validator_reward_purse::<R>(validator)? {
+            return Ok(purse_id);
+        }
+        let purse_id = M::mint(U512::zero()).map_err(|_| Error::MintSeigniorageFailed)?;
+        R::put_key(
+            &validator_reward_purse_key(validator),
+            Key::URef(purse_id.value()),
+        );
+        Ok(purse_id)
+    }
+
     /// Attempts to look up a purse from the named_keys
     fn get_purse_id<R: RuntimeProvider>(
         name: &str,
@@ -273,13 +665,34 @@ mod internal {
         }
     }
 
+    /// Splits `reward` between the delegators pro rata to their stakes, and re-stakes their shares.
+    /// Returns the total amount distributed to delegators.
+    fn distribute_rewards<S: StakesProvider, D: DelegationsProvider>(reward: U512) -> Result<U512> {
+        let mut delegations = D::read()?;
+        if delegations.0.is_empty() {
+            return Ok(U512::zero());
+        }
+        let mut stakes = S::read()?;
+        let distributed = delegations.distribute_rewards(&mut stakes, reward)?;
+        if !distributed.is_zero() {
+            S::write(&stakes);
+            D::write(&delegations);
+        }
+        Ok(distributed)
+    }
+
     /// Transfers funds from the payment purse to the validator rewards purse, as well as to the
-    /// refund purse, depending on how much was spent on the computation. This function maintains
-    /// the invariant that the balance of the payment purse is zero at the beginning and end of each
+    /// refund purse, depending on how much was spent on the computation. Only `refund_percentage`
+    /// percent of the unspent payment is refunded, and the rest is paid to the validators along
+    /// with the amount spent. The amount paid is also added to the undistributed fees, whose
+    /// delegators' share is moved to their delegations by `distribute_era_rewards` once per era,
+    /// rather than rewriting the delegations on every deploy. This function maintains the
+    /// invariant that the balance of the payment purse is zero at the beginning and end of each
     /// deploy and that the refund purse is unset at the beginning and end of each deploy.
-    pub fn finalize_payment<M: MintProvider, R: RuntimeProvider>(
+    pub fn finalize_payment<M: MintProvider, R: RuntimeProvider, D: DelegationsProvider>(
         amount_spent: U512,
         account: PublicKey,
+        refund_percentage: u8,
     ) -> Result<()> {
         let caller = R::get_caller();
         if caller.value() != SYSTEM_ACCOUNT {
@@ -291,18 +704,28 @@ mod internal {
             Some(balance) => balance,
             None => return Err(Error::PaymentPurseBalanceNotFound),
         };
-        if total < amount_spent {
-            return Err(Error::InsufficientPaymentForAmountSpent);
+        let unspent = checked_sub(total, amount_spent)
+            .map_err(|_| Error::InsufficientPaymentForAmountSpent)?;
+        if refund_percentage > 100 {
+            return Err(Error::InvalidArgument);
         }
-        let refund_amount = total - amount_spent;
+        let refund_amount = checked_div(
+            checked_mul(unspent, U512::from(refund_percentage))?,
+            U512::from(100),
+        )?;
+        let amount_paid = checked_sub(total, refund_amount)?;
 
         let rewards_purse = get_rewards_purse::<R>()?;
         let refund_purse = get_refund_purse::<R>()?;
         R::remove_key(REFUND_PURSE_KEY); //unset refund purse after reading it
 
-        // pay validators
-        M::transfer_from_purse_to_purse(payment_purse, rewards_purse, amount_spent)
+        // pay validators, the delegators' share is settled at the end of the era
+        M::transfer_from_purse_to_purse(payment_purse, rewards_purse, amount_paid)
             .map_err(|_| Error::FailedTransferToRewardsPurse)?;
+        if !amount_paid.is_zero() {
+            let fees = checked_add(D::read_undistributed_fees()?, amount_paid)?;
+            D::write_undistributed_fees(fees);
+        }
 
         if refund_amount.is_zero() {
             return Ok(());
@@ -337,25 +760,41 @@ mod internal {
     mod tests {
         extern crate std;
 
-        use std::{cell::RefCell, iter, thread_local};
+        use std::{
+            cell::{Cell, RefCell},
+            iter, thread_local, vec,
+        };
 
-        use types::{account::PublicKey, system_contract_errors::pos::Result, BlockTime, U512};
+        use types::{
+            account::PublicKey,
+            system_contract_errors::pos::{Error, Result},
+            BlockTime, U512,
+        };
 
-        use super::{bond, step, unbond, BOND_DELAY, UNBOND_DELAY};
+        use super::{
+            bond, delegate, era_seigniorage, redelegate, requeue_unbond_requests, slash_validators,
+            step, unbond, undelegate, BOND_DELAY,
+        };
         use crate::{
-            queue::Queue, queue_provider::QueueProvider, stakes::Stakes,
-            stakes_provider::StakesProvider,
+            delegations::Delegations, delegations_provider::DelegationsProvider, queue::Queue,
+            queue_provider::QueueProvider, stakes::Stakes, stakes_provider::StakesProvider,
         };
 
         const KEY1: [u8; 32] = [1; 32];
         const KEY2: [u8; 32] = [2; 32];
+        const KEY3: [u8; 32] = [3; 32];
 
         thread_local! {
             static BONDING: RefCell<Queue> = RefCell::new(Queue(Default::default()));
             static UNBONDING: RefCell<Queue> = RefCell::new(Queue(Default::default()));
+            static UNBONDING_DELAY: Cell<u64> = Cell::new(0);
+            static SLASH_PERCENTAGE: Cell<u8> = Cell::new(0);
+            static LAST_REWARDED_ERA: Cell<Option<u64>> = Cell::new(None);
             static STAKES: RefCell<Stakes> = RefCell::new(
                 Stakes(iter::once((PublicKey::new(KEY1), U512::from(1_000))).collect())
             );
+            static DELEGATIONS: RefCell<Delegations> = RefCell::new(Delegations::default());
+            static UNDISTRIBUTED_FEES: Cell<U512> = Cell::new(U512::zero());
         }
 
         struct TestQueues;
@@ -376,6 +815,10 @@ mod internal {
             fn write_unbonding(queue: Queue) {
                 UNBONDING.with(|ub| ub.replace(queue));
             }
+
+            fn read_unbonding_delay() -> Result<u64> {
+                Ok(UNBONDING_DELAY.with(Cell::get))
+            }
         }
 
         struct TestStakes;
@@ -388,6 +831,38 @@ mod internal {
             fn write(stakes: &Stakes) {
                 STAKES.with(|s| s.replace(stakes.clone()));
             }
+
+            fn read_slash_percentage() -> Result<u8> {
+                Ok(SLASH_PERCENTAGE.with(Cell::get))
+            }
+
+            fn read_last_rewarded_era() -> Result<Option<u64>> {
+                Ok(LAST_REWARDED_ERA.with(Cell::get))
+            }
+
+            fn write_last_rewarded_era(era_id: u64) {
+                LAST_REWARDED_ERA.with(|era| era.set(Some(era_id)));
+            }
+        }
+
+        struct TestDelegations;
+
+        impl DelegationsProvider for TestDelegations {
+            fn read() -> Result<Delegations> {
+                DELEGATIONS.with(|d| Ok(d.borrow().clone()))
+            }
+
+            fn write(delegations: &Delegations) {
+                DELEGATIONS.with(|d| d.replace(delegations.clone()));
+            }
+
+            fn read_undistributed_fees() -> Result<U512> {
+                Ok(UNDISTRIBUTED_FEES.with(Cell::get))
+            }
+
+            fn write_undistributed_fees(fees: U512) {
+                UNDISTRIBUTED_FEES.with(|f| f.set(fees));
+            }
         }
 
         fn assert_stakes(stakes: &[([u8; 32], usize)]) {
@@ -416,7 +891,7 @@ mod internal {
             step::<TestQueues, TestStakes>(BlockTime::new(1 + BOND_DELAY)).expect("step 2");
             assert_stakes(&[(KEY1, 1_000), (KEY2, 500)]);
 
-            unbond::<TestQueues, TestStakes>(
+            unbond::<TestQueues, TestStakes, TestDelegations>(
                 Some(U512::from(500)),
                 PublicKey::new(KEY1),
                 BlockTime::new(2),
@@ -425,8 +900,224 @@ mod internal {
 
             // Unbonding becomes effective immediately.
             assert_stakes(&[(KEY1, 500), (KEY2, 500)]);
-            step::<TestQueues, TestStakes>(BlockTime::new(2 + UNBOND_DELAY)).expect("step 3");
+            step::<TestQueues, TestStakes>(BlockTime::new(2)).expect("step 3");
             assert_stakes(&[(KEY1, 500), (KEY2, 500)]);
         }
+
+        #[test]
+        fn test_unbonding_delay() {
+            UNBONDING_DELAY.with(|delay| delay.set(10));
+
+            unbond::<TestQueues, TestStakes, TestDelegations>(
+                Some(U512::from(500)),
+                PublicKey::new(KEY1),
+                BlockTime::new(5),
+            )
+            .expect("partly unbond validator 1");
+            assert_stakes(&[(KEY1, 500)]);
+
+            // The funds are only released once the delay is over.
+            let unbonds = step::<TestQueues, TestStakes>(BlockTime::new(5)).expect("step 1");
+            assert!(unbonds.is_empty());
+            let unbonds = step::<TestQueues, TestStakes>(BlockTime::new(14)).expect("step 2");
+            assert!(unbonds.is_empty());
+            let unbonds = step::<TestQueues, TestStakes>(BlockTime::new(15)).expect("step 3");
+            assert_eq!(1, unbonds.len());
+            assert_eq!(PublicKey::new(KEY1), unbonds[0].validator);
+            assert_eq!(U512::from(500), unbonds[0].amount);
+            assert!(TestQueues::read_unbonding().0.is_empty());
+        }
+
+        #[test]
+        fn test_requeue_unpaid_unbond_requests() {
+            unbond::<TestQueues, TestStakes, TestDelegations>(
+                Some(U512::from(500)),
+                PublicKey::new(KEY1),
+                BlockTime::new(5),
+            )
+            .expect("partly unbond validator 1");
+
+            let unbonds = step::<TestQueues, TestStakes>(BlockTime::new(5)).expect("step 1");
+            assert_eq!(1, unbonds.len());
+            assert!(TestQueues::read_unbonding().0.is_empty());
+
+            // A request whose payout failed is due again on the next call.
+            requeue_unbond_requests::<TestQueues>(unbonds.clone());
+            assert_eq!(unbonds, TestQueues::read_unbonding().0);
+            let retried = step::<TestQueues, TestStakes>(BlockTime::new(6)).expect("step 2");
+            assert_eq!(unbonds, retried);
+        }
+
+        #[test]
+        fn test_delegate_redelegate_undelegate() {
+            bond::<TestQueues, TestStakes>(
+                U512::from(500),
+                PublicKey::new(KEY2),
+                BlockTime::new(1),
+            )
+            .expect("bond validator 2");
+            step::<TestQueues, TestStakes>(BlockTime::new(1 + BOND_DELAY)).expect("step 1");
+
+            assert_eq!(
+                Err(Error::SelfDelegation),
+                delegate::<TestStakes, TestDelegations>(
+                    U512::from(200),
+                    PublicKey::new(KEY2),
+                    PublicKey::new(KEY2),
+                )
+            );
+            assert_eq!(
+                Err(Error::NotBonded),
+                delegate::<TestStakes, TestDelegations>(
+                    U512::from(200),
+                    PublicKey::new(KEY2),
+                    PublicKey::new(KEY3),
+                )
+            );
+
+            // Delegations become effective immediately.
+            delegate::<TestStakes, TestDelegations>(
+                U512::from(200),
+                PublicKey::new(KEY3),
+                PublicKey::new(KEY1),
+            )
+            .expect("delegate to validator 1");
+            assert_stakes(&[(KEY1, 1_200), (KEY2, 500)]);
+
+            // The validator can't unbond the delegated stakes.
+            assert_eq!(
+                Err(Error::DelegatedStakes),
+                unbond::<TestQueues, TestStakes, TestDelegations>(
+                    None,
+                    PublicKey::new(KEY1),
+                    BlockTime::new(2),
+                )
+            );
+            assert_eq!(
+                Err(Error::DelegatedStakes),
+                unbond::<TestQueues, TestStakes, TestDelegations>(
+                    Some(U512::from(1_001)),
+                    PublicKey::new(KEY1),
+                    BlockTime::new(2),
+                )
+            );
+
+            redelegate::<TestStakes, TestDelegations>(
+                Some(U512::from(50)),
+                PublicKey::new(KEY3),
+                PublicKey::new(KEY1),
+                PublicKey::new(KEY2),
+            )
+            .expect("redelegate to validator 2");
+            assert_stakes(&[(KEY1, 1_150), (KEY2, 550)]);
+
+            undelegate::<TestQueues, TestStakes, TestDelegations>(
+                None,
+                PublicKey::new(KEY3),
+                PublicKey::new(KEY1),
+                BlockTime::new(2),
+            )
+            .expect("undelegate from validator 1");
+            assert_stakes(&[(KEY1, 1_000), (KEY2, 550)]);
+            assert_eq!(
+                Ok(Delegations(
+                    iter::once(((PublicKey::new(KEY3), PublicKey::new(KEY2)), U512::from(50)))
+                        .collect()
+                )),
+                TestDelegations::read()
+            );
+
+            // The undelegated funds are paid out to the delegator.
+            let unbonds = step::<TestQueues, TestStakes>(BlockTime::new(2)).expect("step 2");
+            assert_eq!(1, unbonds.len());
+            assert_eq!(PublicKey::new(KEY3), unbonds[0].validator);
+            assert_eq!(U512::from(150), unbonds[0].amount);
+        }
+
+        #[test]
+        fn test_slash() {
+            SLASH_PERCENTAGE.with(|percentage| percentage.set(10));
+            UNBONDING_DELAY.with(|delay| delay.set(10));
+
+            bond::<TestQueues, TestStakes>(
+                U512::from(500),
+                PublicKey::new(KEY2),
+                BlockTime::new(1),
+            )
+            .expect("bond validator 2");
+            step::<TestQueues, TestStakes>(BlockTime::new(1 + BOND_DELAY)).expect("step 1");
+            delegate::<TestStakes, TestDelegations>(
+                U512::from(200),
+                PublicKey::new(KEY3),
+                PublicKey::new(KEY1),
+            )
+            .expect("delegate to validator 1");
+            unbond::<TestQueues, TestStakes, TestDelegations>(
+                Some(U512::from(300)),
+                PublicKey::new(KEY1),
+                BlockTime::new(2),
+            )
+            .expect("partly unbond validator 1");
+            assert_stakes(&[(KEY1, 900), (KEY2, 500)]);
+
+            // Validator 1 loses 10% of its own bond, of the delegation and of the pending unbond.
+            let slashed = slash_validators::<TestQueues, TestStakes, TestDelegations>(&[
+                PublicKey::new(KEY1),
+                PublicKey::new(KEY1),
+            ])
+            .expect("slash validator 1");
+            assert_eq!(U512::from(70 + 20 + 30), slashed);
+            assert_stakes(&[(KEY1, 810), (KEY2, 500)]);
+            assert_eq!(
+                Ok(Delegations(
+                    iter::once((
+                        (PublicKey::new(KEY3), PublicKey::new(KEY1)),
+                        U512::from(180)
+                    ))
+                    .collect()
+                )),
+                TestDelegations::read()
+            );
+            let unbonds = step::<TestQueues, TestStakes>(BlockTime::new(12)).expect("step 2");
+            assert_eq!(1, unbonds.len());
+            assert_eq!(U512::from(270), unbonds[0].amount);
+        }
+
+        #[test]
+        fn test_era_seigniorage() {
+            // 1% of the total stakes is minted per era.
+            const SEIGNIORAGE_RATE: u64 = 10_000_000;
+
+            bond::<TestQueues, TestStakes>(
+                U512::from(3_000),
+                PublicKey::new(KEY2),
+                BlockTime::new(1),
+            )
+            .expect("bond validator 2");
+            step::<TestQueues, TestStakes>(BlockTime::new(1 + BOND_DELAY)).expect("step 1");
+
+            let (seigniorage, shares) =
+                era_seigniorage::<TestStakes>(1, SEIGNIORAGE_RATE).expect("reward era 1");
+            assert_eq!(U512::from(40), seigniorage);
+            assert_eq!(
+                vec![
+                    (PublicKey::new(KEY1), U512::from(10)),
+                    (PublicKey::new(KEY2), U512::from(30))
+                ],
+                shares
+            );
+            assert_eq!(Ok(Some(1)), TestStakes::read_last_rewarded_era());
+
+            // Each era is rewarded only once.
+            assert_eq!(
+                Err(Error::EraAlreadyRewarded),
+                era_seigniorage::<TestStakes>(1, SEIGNIORAGE_RATE)
+            );
+            assert_eq!(
+                Err(Error::EraAlreadyRewarded),
+                era_seigniorage::<TestStakes>(0, SEIGNIORAGE_RATE)
+            );
+            assert!(era_seigniorage::<TestStakes>(2, SEIGNIORAGE_RATE).is_ok());
+        }
     }
 }
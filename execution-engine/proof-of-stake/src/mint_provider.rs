@@ -17,4 +17,7 @@ pub trait MintProvider {
     ) -> Result<(), ()>;
 
     fn get_balance(purse: PurseId) -> Option<U512>;
+
+    /// Creates a new purse holding `amount` newly minted motes.
+    fn mint(amount: U512) -> Result<PurseId, ()>;
 }
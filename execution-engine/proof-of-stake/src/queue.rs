@@ -3,7 +3,8 @@ use core::result;
 
 use types::{
     account::PublicKey,
-    bytesrepr::{self, FromBytes, ToBytes},
+    bytesrepr::{self, FromBytes, ToBytes, U64_SERIALIZED_LENGTH},
+    checked_div, checked_mul,
     system_contract_errors::pos::{Error, Result},
     BlockTime, CLType, CLTyped, U512,
 };
@@ -51,6 +52,12 @@ impl ToBytes for QueueEntry {
             .chain(self.timestamp.to_bytes()?)
             .collect())
     }
+
+    fn serialized_length(&self) -> usize {
+        self.validator.serialized_length()
+            + self.amount.serialized_length()
+            + self.timestamp.serialized_length()
+    }
 }
 
 impl CLTyped for QueueEntry {
@@ -89,6 +96,31 @@ impl Queue {
         self.0 = rest;
         older_than
     }
+
+    /// Puts entries previously returned by `pop_due` back at the front of the queue, as they are
+    /// older than the entries still in it.
+    pub fn requeue(&mut self, mut entries: Vec<QueueEntry>) {
+        entries.append(&mut self.0);
+        self.0 = entries;
+    }
+
+    /// Subtracts `percentage` percent from the validator's pending requests, removing the ones
+    /// which drop to zero.
+    ///
+    /// Returns the total amount subtracted.
+    pub fn slash(&mut self, validator: &PublicKey, percentage: u8) -> Result<U512> {
+        let percentage = U512::from(percentage);
+        let mut slashed = U512::zero();
+        for entry in self.0.iter_mut() {
+            if entry.validator == *validator {
+                let fine = checked_div(checked_mul(entry.amount, percentage)?, U512::from(100))?;
+                entry.amount -= fine;
+                slashed += fine;
+            }
+        }
+        self.0.retain(|entry| !entry.amount.is_zero());
+        Ok(slashed)
+    }
 }
 
 impl FromBytes for Queue {
@@ -112,6 +144,10 @@ impl ToBytes for Queue {
         }
         Ok(bytes)
     }
+
+    fn serialized_length(&self) -> usize {
+        U64_SERIALIZED_LENGTH + self.0.iter().map(ToBytes::serialized_length).sum::<usize>()
+    }
 }
 
 impl CLTyped for Queue {
@@ -124,7 +160,9 @@ impl CLTyped for Queue {
 mod tests {
     use alloc::vec;
 
-    use types::{account::PublicKey, system_contract_errors::pos::Error, BlockTime, U512};
+    use types::{
+        account::PublicKey, bytesrepr, system_contract_errors::pos::Error, BlockTime, U512,
+    };
 
     use super::{Queue, QueueEntry};
 
@@ -150,6 +188,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_serialization_roundtrip() {
+        let val1 = PublicKey::new(KEY1);
+        let val2 = PublicKey::new(KEY2);
+        let mut queue: Queue = Default::default();
+        assert_eq!(Ok(()), queue.push(val1, U512::from(5), BlockTime::new(100)));
+        assert_eq!(
+            Ok(()),
+            queue.push(val2, U512::max_value(), BlockTime::new(101))
+        );
+        bytesrepr::test_serialization_roundtrip(&queue.0[0]);
+        let bytes = bytesrepr::ToBytes::to_bytes(&queue).expect("should serialize");
+        assert_eq!(bytes.len(), bytesrepr::ToBytes::serialized_length(&queue));
+        let parsed: Queue = bytesrepr::deserialize(bytes).expect("should deserialize");
+        assert_eq!(parsed.0, queue.0);
+    }
+
     #[test]
     fn test_pop_due() {
         let val1 = PublicKey::new(KEY1);
@@ -171,4 +226,50 @@ mod tests {
             queue.pop_due(BlockTime::new(105))
         );
     }
+
+    #[test]
+    fn test_requeue() {
+        let val1 = PublicKey::new(KEY1);
+        let val2 = PublicKey::new(KEY2);
+        let val3 = PublicKey::new(KEY3);
+        let mut queue: Queue = Default::default();
+        assert_eq!(Ok(()), queue.push(val1, U512::from(5), BlockTime::new(100)));
+        assert_eq!(Ok(()), queue.push(val2, U512::from(6), BlockTime::new(101)));
+        assert_eq!(Ok(()), queue.push(val3, U512::from(7), BlockTime::new(102)));
+        let due = queue.pop_due(BlockTime::new(101));
+        queue.requeue(due);
+        assert_eq!(
+            vec![
+                QueueEntry::new(val1, U512::from(5), BlockTime::new(100)),
+                QueueEntry::new(val2, U512::from(6), BlockTime::new(101)),
+                QueueEntry::new(val3, U512::from(7), BlockTime::new(102)),
+            ],
+            queue.0
+        );
+    }
+
+    #[test]
+    fn test_slash() {
+        let val1 = PublicKey::new(KEY1);
+        let val2 = PublicKey::new(KEY2);
+        let mut queue: Queue = Default::default();
+        assert_eq!(
+            Ok(()),
+            queue.push(val1, U512::from(10), BlockTime::new(100))
+        );
+        assert_eq!(Ok(()), queue.push(val2, U512::from(6), BlockTime::new(101)));
+        assert_eq!(Ok(U512::from(3)), queue.slash(&val1, 30));
+        assert_eq!(
+            vec![
+                QueueEntry::new(val1, U512::from(7), BlockTime::new(100)),
+                QueueEntry::new(val2, U512::from(6), BlockTime::new(101)),
+            ],
+            queue.0
+        );
+        assert_eq!(Ok(U512::from(7)), queue.slash(&val1, 100));
+        assert_eq!(
+            vec![QueueEntry::new(val2, U512::from(6), BlockTime::new(101))],
+            queue.0
+        );
+    }
 }
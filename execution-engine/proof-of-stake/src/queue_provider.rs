@@ -1,4 +1,4 @@
-use crate::queue::Queue;
+use crate::{queue::Queue, Result};
 
 pub trait QueueProvider {
     /// Reads bonding queue.
@@ -12,4 +12,7 @@ pub trait QueueProvider {
 
     /// Writes unbonding queue.
     fn write_unbonding(queue: Queue);
+
+    /// Reads the time from an unbonding request until the stakes are paid out.
+    fn read_unbonding_delay() -> Result<u64>;
 }
@@ -1,7 +1,8 @@
-use alloc::collections::BTreeMap;
+use alloc::{collections::BTreeMap, vec::Vec};
 
 use types::{
     account::PublicKey,
+    checked_add, checked_div, checked_mul,
     system_contract_errors::pos::{Error, Result},
     U512,
 };
@@ -43,7 +44,7 @@ impl Stakes {
             .max_without(validator)
             .unwrap_or_else(U512::zero)
             .saturating_sub(MAX_SPREAD);
-        let max_decrease = MAX_DECREASE.min(self.sum() * MAX_REL_DECREASE / 1_000_000);
+        let max_decrease = MAX_DECREASE.min(self.relative_amount(MAX_REL_DECREASE)?);
 
         if let Some(amount) = maybe_amount {
             // The minimum stake value to not violate the maximum spread.
@@ -86,6 +87,40 @@ impl Stakes {
             .or_insert(amount);
     }
 
+    /// Subtracts `amount` from the validator's stakes, removing the validator if nothing is left.
+    /// Unlike unbonding, slashing is not subject to any limits.
+    ///
+    /// Returns the amount that was actually subtracted.
+    pub fn slash(&mut self, validator: &PublicKey, amount: U512) -> U512 {
+        let stake = match self.0.get_mut(validator) {
+            Some(stake) => stake,
+            None => return U512::zero(),
+        };
+        let slashed = amount.min(*stake);
+        *stake -= slashed;
+        if stake.is_zero() {
+            self.0.remove(validator);
+        }
+        slashed
+    }
+
+    /// Splits `reward` between the validators pro rata to their stakes.  Shares are rounded down,
+    /// so they may add up to less than `reward`.  Validators whose share is zero are omitted.
+    pub fn split_reward(&self, reward: U512) -> Result<Vec<(PublicKey, U512)>> {
+        let total_stakes = self.sum();
+        if total_stakes.is_zero() {
+            return Ok(Vec::new());
+        }
+        let mut shares = Vec::new();
+        for (validator, stake) in self.0.iter() {
+            let share = checked_div(checked_mul(reward, *stake)?, total_stakes)?;
+            if !share.is_zero() {
+                shares.push((*validator, share));
+            }
+        }
+        Ok(shares)
+    }
+
     /// Returns an error if bonding the specified amount is not allowed.
     pub fn validate_bonding(&self, validator: &PublicKey, amount: U512) -> Result<()> {
         let max = self
@@ -96,11 +131,14 @@ impl Stakes {
             .max_without(validator)
             .unwrap_or_else(U512::zero)
             .saturating_sub(MAX_SPREAD);
-        let stake = self.0.get(validator).map(|s| *s + amount).unwrap_or(amount);
+        let stake = match self.0.get(validator) {
+            Some(stake) => checked_add(*stake, amount)?,
+            None => amount,
+        };
         if stake > max || stake < min {
             return Err(Error::SpreadTooHigh);
         }
-        let max_increase = MAX_INCREASE.min(self.sum() * MAX_REL_INCREASE / 1_000_000);
+        let max_increase = MAX_INCREASE.min(self.relative_amount(MAX_REL_INCREASE)?);
         if (stake.is_zero() && amount > min.saturating_add(max_increase))
             || (!stake.is_zero() && amount > max_increase)
         {
@@ -109,6 +147,12 @@ impl Stakes {
         Ok(())
     }
 
+    /// Returns the given number of millionths of the total stakes.
+    fn relative_amount(&self, millionths: u64) -> Result<U512> {
+        let amount = checked_mul(self.sum(), U512::from(millionths))?;
+        Ok(checked_div(amount, U512::from(1_000_000))?)
+    }
+
     /// Returns the minimum stake of the _other_ validators.
     fn min_without(&self, validator: &PublicKey) -> Option<U512> {
         self.0
@@ -130,7 +174,7 @@ impl Stakes {
     }
 
     /// Returns the total stakes.
-    fn sum(&self) -> U512 {
+    pub(crate) fn sum(&self) -> U512 {
         self.0
             .values()
             .fold(U512::zero(), |sum, s| sum.saturating_add(*s))
@@ -139,6 +183,8 @@ impl Stakes {
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec;
+
     use types::{account::PublicKey, system_contract_errors::pos::Error, U512};
 
     use super::Stakes;
@@ -228,6 +274,60 @@ mod tests {
         assert_eq!(new_stakes(&[(KEY1, 46)]), stakes);
     }
 
+    #[test]
+    fn test_slash() {
+        let mut stakes = new_stakes(&[(KEY1, 50), (KEY2, 100)]);
+        assert_eq!(
+            U512::from(20),
+            stakes.slash(&PublicKey::new(KEY1), U512::from(20))
+        );
+        assert_eq!(new_stakes(&[(KEY1, 30), (KEY2, 100)]), stakes);
+        assert_eq!(
+            U512::from(30),
+            stakes.slash(&PublicKey::new(KEY1), U512::from(40))
+        );
+        assert_eq!(new_stakes(&[(KEY2, 100)]), stakes);
+        assert_eq!(
+            U512::zero(),
+            stakes.slash(&PublicKey::new(KEY1), U512::from(40))
+        );
+    }
+
+    #[test]
+    fn test_split_reward() {
+        let stakes = new_stakes(&[(KEY1, 100), (KEY2, 300)]);
+        assert_eq!(
+            Ok(vec![
+                (PublicKey::new(KEY1), U512::from(25)),
+                (PublicKey::new(KEY2), U512::from(76))
+            ]),
+            stakes.split_reward(U512::from(102))
+        );
+        assert_eq!(Ok(vec![]), stakes.split_reward(U512::from(3)));
+    }
+
+    #[test]
+    fn test_split_reward_overflow() {
+        let stakes = new_stakes(&[(KEY1, 100), (KEY2, 300)]);
+        assert_eq!(
+            Err(Error::ArithmeticOverflow),
+            stakes.split_reward(U512::MAX)
+        );
+    }
+
+    #[test]
+    fn test_bond_overflow() {
+        let stakes = Stakes(
+            vec![(PublicKey::new(KEY1), U512::MAX)]
+                .into_iter()
+                .collect(),
+        );
+        assert_eq!(
+            Err(Error::ArithmeticOverflow),
+            stakes.validate_bonding(&PublicKey::new(KEY1), U512::one())
+        );
+    }
+
     #[test]
     fn test_unbond_too_much_rel() {
         let mut stakes = new_stakes(&[(KEY1, 999), (KEY2, 1)]);
@@ -4,4 +4,12 @@ pub trait StakesProvider {
     fn read() -> Result<Stakes>;
 
     fn write(stakes: &Stakes);
+
+    /// Reads the percentage of the stakes taken from a validator when it is slashed.
+    fn read_slash_percentage() -> Result<u8>;
+
+    /// Reads the last era whose rewards were distributed, if any.
+    fn read_last_rewarded_era() -> Result<Option<u64>>;
+
+    fn write_last_rewarded_era(era_id: u64);
 }
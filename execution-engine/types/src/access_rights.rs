@@ -55,6 +55,15 @@ impl bytesrepr::ToBytes for AccessRights {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         self.bits.to_bytes()
     }
+
+    fn serialized_length(&self) -> usize {
+        ACCESS_RIGHTS_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        writer.push(self.bits);
+        Ok(())
+    }
 }
 
 impl bytesrepr::FromBytes for AccessRights {
@@ -4,11 +4,15 @@ use core::{
     fmt::{Debug, Display, Formatter},
 };
 
+use blake2::{
+    digest::{Input, VariableOutput},
+    VarBlake2b,
+};
 use failure::Fail;
 use hex_fmt::HexFmt;
 
 use crate::{
-    bytesrepr::{Error, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    bytesrepr::{self, Error, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
     CLType, CLTyped, URef, UREF_SERIALIZED_LENGTH,
 };
 
@@ -43,6 +47,14 @@ impl ToBytes for PurseId {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         ToBytes::to_bytes(&self.0)
     }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.0.write_bytes(writer)
+    }
 }
 
 impl FromBytes for PurseId {
@@ -158,6 +170,14 @@ impl ToBytes for Weight {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         ToBytes::to_bytes(&self.0)
     }
+
+    fn serialized_length(&self) -> usize {
+        WEIGHT_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.0.write_bytes(writer)
+    }
 }
 
 impl FromBytes for Weight {
@@ -239,6 +259,14 @@ impl ToBytes for PublicKey {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         ToBytes::to_bytes(&self.0)
     }
+
+    fn serialized_length(&self) -> usize {
+        PUBLIC_KEY_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.0.write_bytes(writer)
+    }
 }
 
 impl FromBytes for PublicKey {
@@ -248,6 +276,329 @@ impl FromBytes for PublicKey {
     }
 }
 
+impl FromBytes for Vec<PublicKey> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (size, rest): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let mut result = Vec::new();
+        result.try_reserve_exact(size as usize)?;
+        let mut stream = rest;
+        for _ in 0..size {
+            let (public_key, rem): (PublicKey, &[u8]) = FromBytes::from_bytes(stream)?;
+            result.push(public_key);
+            stream = rem;
+        }
+        Ok((result, stream))
+    }
+}
+
+impl ToBytes for Vec<PublicKey> {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH + self.len() * PUBLIC_KEY_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        (self.len() as u32).write_bytes(writer)?;
+        for public_key in self.iter() {
+            public_key.write_bytes(writer)?;
+        }
+        Ok(())
+    }
+}
+
+const KEY_UPDATE_ADD_TAG: u8 = 0;
+const KEY_UPDATE_UPDATE_TAG: u8 = 1;
+const KEY_UPDATE_REMOVE_TAG: u8 = 2;
+
+/// A single change to the associated keys of an account, applied as part of a batch by
+/// `update_associated_keys`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum KeyUpdate {
+    /// Associates a new key with the given weight.
+    Add(PublicKey, Weight),
+    /// Changes the weight of an already associated key.
+    Update(PublicKey, Weight),
+    /// Removes an associated key.
+    Remove(PublicKey),
+}
+
+impl ToBytes for KeyUpdate {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                KeyUpdate::Add(..) | KeyUpdate::Update(..) => {
+                    PUBLIC_KEY_SERIALIZED_LENGTH + WEIGHT_SERIALIZED_LENGTH
+                }
+                KeyUpdate::Remove(_) => PUBLIC_KEY_SERIALIZED_LENGTH,
+            }
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        match self {
+            KeyUpdate::Add(public_key, weight) => {
+                writer.push(KEY_UPDATE_ADD_TAG);
+                public_key.write_bytes(writer)?;
+                weight.write_bytes(writer)
+            }
+            KeyUpdate::Update(public_key, weight) => {
+                writer.push(KEY_UPDATE_UPDATE_TAG);
+                public_key.write_bytes(writer)?;
+                weight.write_bytes(writer)
+            }
+            KeyUpdate::Remove(public_key) => {
+                writer.push(KEY_UPDATE_REMOVE_TAG);
+                public_key.write_bytes(writer)
+            }
+        }
+    }
+}
+
+impl FromBytes for KeyUpdate {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        match tag {
+            KEY_UPDATE_ADD_TAG => {
+                let (public_key, rem) = PublicKey::from_bytes(rem)?;
+                let (weight, rem) = Weight::from_bytes(rem)?;
+                Ok((KeyUpdate::Add(public_key, weight), rem))
+            }
+            KEY_UPDATE_UPDATE_TAG => {
+                let (public_key, rem) = PublicKey::from_bytes(rem)?;
+                let (weight, rem) = Weight::from_bytes(rem)?;
+                Ok((KeyUpdate::Update(public_key, weight), rem))
+            }
+            KEY_UPDATE_REMOVE_TAG => {
+                let (public_key, rem) = PublicKey::from_bytes(rem)?;
+                Ok((KeyUpdate::Remove(public_key), rem))
+            }
+            _ => Err(Error::FormattingError),
+        }
+    }
+}
+
+/// Has no equivalent `CLType`, so is represented as [`CLType::Any`].
+impl CLTyped for KeyUpdate {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl FromBytes for Vec<KeyUpdate> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (size, rest): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let mut result = Vec::new();
+        result.try_reserve_exact(size as usize)?;
+        let mut stream = rest;
+        for _ in 0..size {
+            let (key_update, rem): (KeyUpdate, &[u8]) = FromBytes::from_bytes(stream)?;
+            result.push(key_update);
+            stream = rem;
+        }
+        Ok((result, stream))
+    }
+}
+
+impl ToBytes for Vec<KeyUpdate> {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH + self.iter().map(ToBytes::serialized_length).sum::<usize>()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        (self.len() as u32).write_bytes(writer)?;
+        for key_update in self.iter() {
+            key_update.write_bytes(writer)?;
+        }
+        Ok(())
+    }
+}
+
+pub const ED25519_PUBLIC_KEY_LENGTH: usize = PUBLIC_KEY_LENGTH;
+
+/// Length of a secp256k1 public key in its compressed SEC1 encoding.
+pub const SECP256K1_PUBLIC_KEY_LENGTH: usize = 33;
+
+const ED25519_TAG: u8 = 0;
+const SECP256K1_TAG: u8 = 1;
+
+/// The signature scheme a [`TaggedPublicKey`] belongs to.
+#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum SignatureAlgorithm {
+    Ed25519,
+    Secp256k1,
+}
+
+impl SignatureAlgorithm {
+    /// Length in bytes of the public keys of this algorithm.
+    pub fn public_key_length(self) -> usize {
+        match self {
+            SignatureAlgorithm::Ed25519 => ED25519_PUBLIC_KEY_LENGTH,
+            SignatureAlgorithm::Secp256k1 => SECP256K1_PUBLIC_KEY_LENGTH,
+        }
+    }
+
+    fn tag(self) -> u8 {
+        match self {
+            SignatureAlgorithm::Ed25519 => ED25519_TAG,
+            SignatureAlgorithm::Secp256k1 => SECP256K1_TAG,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            ED25519_TAG => Some(SignatureAlgorithm::Ed25519),
+            SECP256K1_TAG => Some(SignatureAlgorithm::Secp256k1),
+            _ => None,
+        }
+    }
+}
+
+impl Display for SignatureAlgorithm {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            SignatureAlgorithm::Ed25519 => write!(f, "ed25519"),
+            SignatureAlgorithm::Secp256k1 => write!(f, "secp256k1"),
+        }
+    }
+}
+
+/// The public key of a user's key pair, tagged with its [`SignatureAlgorithm`].
+///
+/// Accounts and associated keys are identified by a [`PublicKey`], which is derived from a
+/// tagged key by [`TaggedPublicKey::to_public_key`].  Ed25519 keys are used as they are, so
+/// existing accounts keep their addresses, while secp256k1 keys are hashed down to 32 bytes.
+#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone)]
+pub struct TaggedPublicKey {
+    algorithm: SignatureAlgorithm,
+    bytes: Vec<u8>,
+}
+
+impl TaggedPublicKey {
+    /// Creates a key of the given algorithm, checking the length of `bytes`.  Secp256k1 keys
+    /// must be in compressed form.
+    pub fn new(
+        algorithm: SignatureAlgorithm,
+        bytes: &[u8],
+    ) -> Result<Self, TryFromSliceForPublicKeyError> {
+        if bytes.len() != algorithm.public_key_length() {
+            return Err(TryFromSliceForPublicKeyError(()));
+        }
+        if algorithm == SignatureAlgorithm::Secp256k1 && bytes[0] != 2 && bytes[0] != 3 {
+            return Err(TryFromSliceForPublicKeyError(()));
+        }
+        Ok(TaggedPublicKey {
+            algorithm,
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    pub fn ed25519(key: [u8; ED25519_PUBLIC_KEY_LENGTH]) -> Self {
+        TaggedPublicKey {
+            algorithm: SignatureAlgorithm::Ed25519,
+            bytes: key.to_vec(),
+        }
+    }
+
+    pub fn secp256k1(bytes: &[u8]) -> Result<Self, TryFromSliceForPublicKeyError> {
+        TaggedPublicKey::new(SignatureAlgorithm::Secp256k1, bytes)
+    }
+
+    pub fn algorithm(&self) -> SignatureAlgorithm {
+        self.algorithm
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the [`PublicKey`] identifying the account of this key.
+    pub fn to_public_key(&self) -> PublicKey {
+        let mut ret = [0u8; PUBLIC_KEY_LENGTH];
+        match self.algorithm {
+            SignatureAlgorithm::Ed25519 => ret.copy_from_slice(&self.bytes),
+            SignatureAlgorithm::Secp256k1 => {
+                // Safe to unwrap here because our digest length is constant and valid
+                let mut hasher = VarBlake2b::new(PUBLIC_KEY_LENGTH).unwrap();
+                hasher.input(&[self.algorithm.tag()]);
+                hasher.input(&self.bytes);
+                hasher.variable_result(|hash| ret.clone_from_slice(hash));
+            }
+        }
+        PublicKey::new(ret)
+    }
+}
+
+impl Display for TaggedPublicKey {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "PublicKey({}, {})", self.algorithm, HexFmt(&self.bytes))
+    }
+}
+
+impl Debug for TaggedPublicKey {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+/// Has no equivalent `CLType`, so is represented as [`CLType::Any`].
+impl CLTyped for TaggedPublicKey {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl From<TaggedPublicKey> for PublicKey {
+    fn from(tagged_public_key: TaggedPublicKey) -> Self {
+        tagged_public_key.to_public_key()
+    }
+}
+
+/// Serialized as the algorithm tag followed by the raw key bytes.
+impl ToBytes for TaggedPublicKey {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH + self.bytes.len()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        writer.push(self.algorithm.tag());
+        writer.extend_from_slice(&self.bytes);
+        Ok(())
+    }
+}
+
+impl FromBytes for TaggedPublicKey {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let algorithm = SignatureAlgorithm::from_tag(tag).ok_or(Error::FormattingError)?;
+        let (key_bytes, rem) = bytesrepr::safe_split_at(rem, algorithm.public_key_length())?;
+        let tagged_public_key =
+            TaggedPublicKey::new(algorithm, key_bytes).map_err(|_| Error::FormattingError)?;
+        Ok((tagged_public_key, rem))
+    }
+}
+
 /// Represents an error that happens when trying to add a new associated key
 /// on an account.
 ///
@@ -369,11 +720,89 @@ impl TryFrom<i32> for UpdateKeyFailure {
     }
 }
 
+/// Represents an error that happens when trying to apply a batch of [`KeyUpdate`]s to the
+/// associated keys of an account.
+///
+/// It is represented by `i32` to be easily able to transform this value in and
+/// out through FFI boundaries as a number.
+///
+/// For backwards compatibility, the variants are explicitly ordered and will
+/// not be reordered; variants added in future versions will be appended to
+/// extend the enum and in the event that a variant is removed its ordinal will
+/// not be reused.
+#[derive(PartialEq, Eq, Fail, Debug)]
+#[repr(i32)]
+pub enum UpdateKeysFailure {
+    #[fail(display = "Unable to update associated keys because maximum amount of keys is reached")]
+    MaxKeysLimit = 1,
+    #[fail(display = "Unable to add new associated key because given key already exists")]
+    DuplicateKey = 2,
+    #[fail(display = "Unable to update or remove an associated key that does not exist")]
+    MissingKey = 3,
+    #[fail(display = "Unable to update associated keys due to insufficient permissions")]
+    PermissionDenied = 4,
+    #[fail(display = "Unable to update associated keys which would violate action thresholds")]
+    ThresholdViolation = 5,
+}
+
+/// convert from i32 representation of [`UpdateKeysFailure`]
+impl TryFrom<i32> for UpdateKeysFailure {
+    type Error = TryFromIntError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            d if d == UpdateKeysFailure::MaxKeysLimit as i32 => Ok(UpdateKeysFailure::MaxKeysLimit),
+            d if d == UpdateKeysFailure::DuplicateKey as i32 => Ok(UpdateKeysFailure::DuplicateKey),
+            d if d == UpdateKeysFailure::MissingKey as i32 => Ok(UpdateKeysFailure::MissingKey),
+            d if d == UpdateKeysFailure::PermissionDenied as i32 => {
+                Ok(UpdateKeysFailure::PermissionDenied)
+            }
+            d if d == UpdateKeysFailure::ThresholdViolation as i32 => {
+                Ok(UpdateKeysFailure::ThresholdViolation)
+            }
+            _ => Err(TryFromIntError(())),
+        }
+    }
+}
+
+impl From<AddKeyFailure> for UpdateKeysFailure {
+    fn from(error: AddKeyFailure) -> Self {
+        match error {
+            AddKeyFailure::MaxKeysLimit => UpdateKeysFailure::MaxKeysLimit,
+            AddKeyFailure::DuplicateKey => UpdateKeysFailure::DuplicateKey,
+            AddKeyFailure::PermissionDenied => UpdateKeysFailure::PermissionDenied,
+        }
+    }
+}
+
+impl From<UpdateKeyFailure> for UpdateKeysFailure {
+    fn from(error: UpdateKeyFailure) -> Self {
+        match error {
+            UpdateKeyFailure::MissingKey => UpdateKeysFailure::MissingKey,
+            UpdateKeyFailure::PermissionDenied => UpdateKeysFailure::PermissionDenied,
+            UpdateKeyFailure::ThresholdViolation => UpdateKeysFailure::ThresholdViolation,
+        }
+    }
+}
+
+impl From<RemoveKeyFailure> for UpdateKeysFailure {
+    fn from(error: RemoveKeyFailure) -> Self {
+        match error {
+            RemoveKeyFailure::MissingKey => UpdateKeysFailure::MissingKey,
+            RemoveKeyFailure::PermissionDenied => UpdateKeysFailure::PermissionDenied,
+            RemoveKeyFailure::ThresholdViolation => UpdateKeysFailure::ThresholdViolation,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{convert::TryFrom, vec::Vec};
 
-    use super::PublicKey;
+    use super::{
+        KeyUpdate, PublicKey, SignatureAlgorithm, TaggedPublicKey, UpdateKeysFailure, Weight,
+    };
+    use crate::bytesrepr;
 
     #[test]
     fn public_key_from_slice() {
@@ -392,4 +821,70 @@ mod tests {
         let _public_key =
             PublicKey::try_from(&[0u8; 33][..]).expect_err("should not create public key");
     }
+
+    #[test]
+    fn public_keys_serialization_roundtrip() {
+        bytesrepr::test_serialization_roundtrip(&Vec::<PublicKey>::new());
+        bytesrepr::test_serialization_roundtrip(&vec![
+            PublicKey::new([1; 32]),
+            PublicKey::new([2; 32]),
+        ]);
+    }
+
+    #[test]
+    fn key_updates_serialization_roundtrip() {
+        let key_updates = vec![
+            KeyUpdate::Add(PublicKey::new([1; 32]), Weight::new(2)),
+            KeyUpdate::Update(PublicKey::new([2; 32]), Weight::new(3)),
+            KeyUpdate::Remove(PublicKey::new([3; 32])),
+        ];
+        for key_update in &key_updates {
+            bytesrepr::test_serialization_roundtrip(key_update);
+        }
+        bytesrepr::test_serialization_roundtrip(&Vec::<KeyUpdate>::new());
+        bytesrepr::test_serialization_roundtrip(&key_updates);
+    }
+
+    #[test]
+    fn update_keys_failure_from_i32() {
+        for value in 1..=5 {
+            let failure = UpdateKeysFailure::try_from(value).expect("should convert");
+            assert_eq!(failure as i32, value);
+        }
+        UpdateKeysFailure::try_from(0).expect_err("should not convert 0");
+        UpdateKeysFailure::try_from(6).expect_err("should not convert 6");
+    }
+
+    #[test]
+    fn tagged_public_key_serialization_roundtrip() {
+        let ed25519 = TaggedPublicKey::ed25519([1; 32]);
+        let secp256k1 = TaggedPublicKey::secp256k1(&[3; 33]).expect("should create public key");
+        bytesrepr::test_serialization_roundtrip(&ed25519);
+        bytesrepr::test_serialization_roundtrip(&secp256k1);
+    }
+
+    #[test]
+    fn tagged_public_key_from_invalid_bytes() {
+        TaggedPublicKey::new(SignatureAlgorithm::Ed25519, &[1; 33])
+            .expect_err("should not create ed25519 key of wrong length");
+        TaggedPublicKey::secp256k1(&[3; 32])
+            .expect_err("should not create secp256k1 key of wrong length");
+        // Only the compressed encoding, prefixed with 2 or 3, is accepted
+        TaggedPublicKey::secp256k1(&[4; 33])
+            .expect_err("should not create uncompressed secp256k1 key");
+    }
+
+    #[test]
+    fn tagged_public_key_to_public_key() {
+        let ed25519 = TaggedPublicKey::ed25519([1; 32]);
+        assert_eq!(ed25519.to_public_key(), PublicKey::new([1; 32]));
+
+        let secp256k1_1 = TaggedPublicKey::secp256k1(&[2; 33]).expect("should create public key");
+        let secp256k1_2 = TaggedPublicKey::secp256k1(&[3; 33]).expect("should create public key");
+        assert_ne!(secp256k1_1.to_public_key(), secp256k1_2.to_public_key());
+        assert_eq!(
+            secp256k1_1.to_public_key(),
+            PublicKey::from(secp256k1_1.clone())
+        );
+    }
 }
@@ -6,11 +6,11 @@ use core::{
 use crate::{
     account::{
         AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, TryFromIntError,
-        TryFromSliceForPublicKeyError, UpdateKeyFailure,
+        TryFromSliceForPublicKeyError, UpdateKeyFailure, UpdateKeysFailure,
     },
     bytesrepr,
     system_contract_errors::{mint, pos},
-    CLValueError,
+    ArithmeticError, CLValueError,
 };
 
 /// All `Error` variants defined in this library other than `Error::User` will convert to a `u32`
@@ -128,6 +128,11 @@ pub enum ApiError {
     HostBufferEmpty,
     /// Data in the host buffer is full and should be consumed first by read operation
     HostBufferFull,
+    /// The account already has a named purse with the given name.
+    DuplicateNamedPurse,
+    /// Checked arithmetic on a `U128`, `U256` or `U512` overflowed, underflowed or divided by
+    /// zero.
+    ArithmeticOverflow,
     /// Error specific to Mint contract.
     Mint(u8),
     /// Error specific to Proof of Stake contract.
@@ -168,6 +173,18 @@ impl From<UpdateKeyFailure> for ApiError {
     }
 }
 
+impl From<UpdateKeysFailure> for ApiError {
+    fn from(error: UpdateKeysFailure) -> Self {
+        match error {
+            UpdateKeysFailure::MaxKeysLimit => ApiError::MaxKeysLimit,
+            UpdateKeysFailure::DuplicateKey => ApiError::DuplicateKey,
+            UpdateKeysFailure::MissingKey => ApiError::MissingKey,
+            UpdateKeysFailure::PermissionDenied => ApiError::PermissionDenied,
+            UpdateKeysFailure::ThresholdViolation => ApiError::ThresholdViolation,
+        }
+    }
+}
+
 impl From<RemoveKeyFailure> for ApiError {
     fn from(error: RemoveKeyFailure) -> Self {
         match error {
@@ -212,6 +229,12 @@ impl From<TryFromSliceForPublicKeyError> for ApiError {
     }
 }
 
+impl From<ArithmeticError> for ApiError {
+    fn from(_error: ArithmeticError) -> Self {
+        ApiError::ArithmeticOverflow
+    }
+}
+
 impl From<mint::Error> for ApiError {
     fn from(error: mint::Error) -> Self {
         ApiError::Mint(error as u8)
@@ -264,6 +287,8 @@ impl From<ApiError> for u32 {
             ApiError::BufferTooSmall => 35,
             ApiError::HostBufferEmpty => 36,
             ApiError::HostBufferFull => 37,
+            ApiError::DuplicateNamedPurse => 38,
+            ApiError::ArithmeticOverflow => 39,
             ApiError::Mint(value) => MINT_ERROR_OFFSET + u32::from(value),
             ApiError::ProofOfStake(value) => POS_ERROR_OFFSET + u32::from(value),
             ApiError::User(value) => RESERVED_ERROR_MAX + 1 + u32::from(value),
@@ -315,6 +340,8 @@ impl Debug for ApiError {
             ApiError::BufferTooSmall => write!(f, "ApiError::BufferTooSmall")?,
             ApiError::HostBufferEmpty => write!(f, "ApiError::HostBufferEmpty")?,
             ApiError::HostBufferFull => write!(f, "ApiError::HostBufferFull")?,
+            ApiError::DuplicateNamedPurse => write!(f, "ApiError::DuplicateNamedPurse")?,
+            ApiError::ArithmeticOverflow => write!(f, "ApiError::ArithmeticOverflow")?,
             ApiError::Mint(value) => write!(f, "ApiError::Mint({})", value)?,
             ApiError::ProofOfStake(value) => write!(f, "ApiError::ProofOfStake({})", value)?,
             ApiError::User(value) => write!(f, "ApiError::User({})", value)?,
@@ -370,6 +397,8 @@ pub fn result_from(value: i32) -> Result<(), ApiError> {
         35 => Err(ApiError::BufferTooSmall),
         36 => Err(ApiError::HostBufferEmpty),
         37 => Err(ApiError::HostBufferFull),
+        38 => Err(ApiError::DuplicateNamedPurse),
+        39 => Err(ApiError::ArithmeticOverflow),
         _ => {
             if value > RESERVED_ERROR_MAX as i32 && value <= (2 * RESERVED_ERROR_MAX + 1) as i32 {
                 Err(ApiError::User(value as u16))
@@ -476,6 +505,8 @@ mod tests {
         round_trip(Err(ApiError::BufferTooSmall));
         round_trip(Err(ApiError::HostBufferEmpty));
         round_trip(Err(ApiError::HostBufferFull));
+        round_trip(Err(ApiError::DuplicateNamedPurse));
+        round_trip(Err(ApiError::ArithmeticOverflow));
         round_trip(Err(ApiError::Mint(0)));
         round_trip(Err(ApiError::Mint(u8::MAX)));
         round_trip(Err(ApiError::ProofOfStake(0)));
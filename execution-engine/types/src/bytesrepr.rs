@@ -4,23 +4,35 @@
 #[rustfmt::skip]
 use alloc::vec;
 use alloc::{
+    boxed::Box,
     collections::{BTreeMap, TryReserveError},
     string::String,
     vec::Vec,
 };
 use core::mem::{size_of, MaybeUninit};
 
+use blake2::{
+    digest::{Input, VariableOutput},
+    VarBlake2b,
+};
 use failure::Fail;
 
+use crate::{
+    account::PublicKey, AccessRights, CLType, CLTyped, CLValue, CallStackElement, EntryPoint,
+    EntryPointAccess, EntryPoints, Key, Parameter, Phase, ProtocolVersion, URef, U128, U256, U512,
+};
+
 pub const I32_SERIALIZED_LENGTH: usize = size_of::<i32>();
 pub const U8_SERIALIZED_LENGTH: usize = size_of::<u8>();
 pub const U16_SERIALIZED_LENGTH: usize = size_of::<u16>();
 pub const U32_SERIALIZED_LENGTH: usize = size_of::<u32>();
 pub const U64_SERIALIZED_LENGTH: usize = size_of::<u64>();
+pub const I64_SERIALIZED_LENGTH: usize = size_of::<i64>();
 pub const U128_SERIALIZED_LENGTH: usize = size_of::<u128>();
 pub const U256_SERIALIZED_LENGTH: usize = U128_SERIALIZED_LENGTH * 2;
 pub const U512_SERIALIZED_LENGTH: usize = U256_SERIALIZED_LENGTH * 2;
 pub const OPTION_TAG_SERIALIZED_LENGTH: usize = 1;
+pub const SCHEMA_DIGEST_LENGTH: usize = 32;
 
 pub trait ToBytes {
     fn to_bytes(&self) -> Result<Vec<u8>, Error>;
@@ -30,6 +42,16 @@ pub trait ToBytes {
     {
         self.to_bytes()
     }
+    /// Returns the length of the serialized form of `self`, which must always equal the length of
+    /// the output of [`ToBytes::to_bytes`].
+    ///
+    /// This is the size values are charged and limited by, so it is computed without serializing.
+    fn serialized_length(&self) -> usize;
+    /// Appends the serialized form of `self` to `writer`.
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        writer.extend(self.to_bytes()?);
+        Ok(())
+    }
 }
 
 pub trait FromBytes: Sized {
@@ -74,6 +96,21 @@ pub fn serialize(t: impl ToBytes) -> Result<Vec<u8>, Error> {
     t.into_bytes()
 }
 
+/// Returns an empty buffer with exactly enough capacity to hold the serialized form of `t`.
+///
+/// Implementations of [`ToBytes::to_bytes`] can pass the returned buffer to
+/// [`ToBytes::write_bytes`] so that serialization allocates only once.  Types using it must
+/// override [`ToBytes::serialized_length`], as the default implementation calls `to_bytes`.
+pub fn allocate_buffer<T: ToBytes + ?Sized>(t: &T) -> Result<Vec<u8>, Error> {
+    let serialized_length = t.serialized_length();
+    if serialized_length > u32::max_value() as usize {
+        return Err(Error::OutOfMemoryError);
+    }
+    let mut buffer = Vec::new();
+    buffer.try_reserve_exact(serialized_length)?;
+    Ok(buffer)
+}
+
 pub fn safe_split_at(bytes: &[u8], n: usize) -> Result<(&[u8], &[u8]), Error> {
     if n > bytes.len() {
         Err(Error::EarlyEndOfStream)
@@ -86,6 +123,15 @@ impl ToBytes for bool {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         u8::from(*self).to_bytes()
     }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        writer.push(u8::from(*self));
+        Ok(())
+    }
 }
 
 impl FromBytes for bool {
@@ -105,6 +151,15 @@ impl ToBytes for u8 {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         Ok(vec![*self])
     }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        writer.push(*self);
+        Ok(())
+    }
 }
 
 impl FromBytes for u8 {
@@ -120,6 +175,15 @@ impl ToBytes for i32 {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         Ok(self.to_le_bytes().to_vec())
     }
+
+    fn serialized_length(&self) -> usize {
+        I32_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        writer.extend_from_slice(&self.to_le_bytes());
+        Ok(())
+    }
 }
 
 impl FromBytes for i32 {
@@ -135,6 +199,15 @@ impl ToBytes for u32 {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         Ok(self.to_le_bytes().to_vec())
     }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        writer.extend_from_slice(&self.to_le_bytes());
+        Ok(())
+    }
 }
 
 impl FromBytes for u32 {
@@ -150,6 +223,15 @@ impl ToBytes for u64 {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         Ok(self.to_le_bytes().to_vec())
     }
+
+    fn serialized_length(&self) -> usize {
+        U64_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        writer.extend_from_slice(&self.to_le_bytes());
+        Ok(())
+    }
 }
 
 impl FromBytes for u64 {
@@ -165,6 +247,15 @@ impl ToBytes for i64 {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         Ok(self.to_le_bytes().to_vec())
     }
+
+    fn serialized_length(&self) -> usize {
+        I64_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        writer.extend_from_slice(&self.to_le_bytes());
+        Ok(())
+    }
 }
 
 impl FromBytes for i64 {
@@ -186,16 +277,38 @@ impl FromBytes for Vec<u8> {
 
 impl ToBytes for Vec<u8> {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.as_slice().to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.as_slice().serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.as_slice().write_bytes(writer)
+    }
+}
+
+impl ToBytes for [u8] {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH + self.len()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
         // Return error if size of serialized vector would exceed limit for
         // 32-bit architecture.
         if self.len() >= u32::max_value() as usize - U32_SERIALIZED_LENGTH {
             return Err(Error::OutOfMemoryError);
         }
-        let size = self.len() as u32;
-        let mut result: Vec<u8> = Vec::with_capacity(U32_SERIALIZED_LENGTH + size as usize);
-        result.extend(size.to_bytes()?);
-        result.extend(self);
-        Ok(result)
+        (self.len() as u32).write_bytes(writer)?;
+        writer.extend_from_slice(self);
+        Ok(())
     }
 }
 
@@ -215,20 +328,27 @@ impl FromBytes for Vec<i32> {
 
 impl<T: ToBytes> ToBytes for Option<T> {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        OPTION_TAG_SERIALIZED_LENGTH + self.as_ref().map_or(0, ToBytes::serialized_length)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
         match self {
             Some(v) => {
-                let mut value = v.to_bytes()?;
-                if value.len() >= u32::max_value() as usize - U8_SERIALIZED_LENGTH {
-                    return Err(Error::OutOfMemoryError);
-                }
-                let mut result: Vec<u8> = Vec::with_capacity(U8_SERIALIZED_LENGTH + value.len());
-                result.append(&mut 1u8.to_bytes()?);
-                result.append(&mut value);
-                Ok(result)
+                writer.push(1);
+                v.write_bytes(writer)
             }
             // In the case of None there is no value to serialize, but we still
             // need to write out a tag to indicate which variant we are using
-            None => Ok(0u8.to_bytes()?),
+            None => {
+                writer.push(0);
+                Ok(())
+            }
         }
     }
 }
@@ -249,22 +369,25 @@ impl<T: FromBytes> FromBytes for Option<T> {
 
 impl ToBytes for Vec<i32> {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH + self.len() * I32_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
         // Return error if size of vector would exceed length of serialized data
         if self.len() * I32_SERIALIZED_LENGTH >= u32::max_value() as usize - U32_SERIALIZED_LENGTH {
             return Err(Error::OutOfMemoryError);
         }
-        let size = self.len() as u32;
-        let mut result: Vec<u8> =
-            Vec::with_capacity(U32_SERIALIZED_LENGTH + (I32_SERIALIZED_LENGTH * size as usize));
-        result.extend(size.to_bytes()?);
-        result.extend(
-            self.iter()
-                .map(ToBytes::to_bytes)
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .flatten(),
-        );
-        Ok(result)
+        (self.len() as u32).write_bytes(writer)?;
+        for item in self.iter() {
+            item.write_bytes(writer)?;
+        }
+        Ok(())
     }
 }
 
@@ -297,35 +420,21 @@ impl FromBytes for Vec<Vec<u8>> {
 
 impl ToBytes for Vec<Vec<u8>> {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let self_len = self.len();
-        let serialized_len = self.iter().map(Vec::len).sum::<usize>()
-            + (U32_SERIALIZED_LENGTH as usize * (self_len + 1));
-        if serialized_len > u32::max_value() as usize {
-            return Err(Error::OutOfMemoryError);
-        }
-
-        let mut result: Vec<u8> = Vec::with_capacity(serialized_len);
-        result.append(&mut (self_len as u32).into_bytes()?);
-        for vec in self.iter() {
-            result.append(&mut vec.to_bytes()?)
-        }
+        let mut result = allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
 
-    fn into_bytes(self) -> Result<Vec<u8>, Error> {
-        let self_len = self.len();
-        let serialized_len = self.iter().map(Vec::len).sum::<usize>()
-            + (U32_SERIALIZED_LENGTH as usize * (self_len + 1));
-        if serialized_len > u32::max_value() as usize {
-            return Err(Error::OutOfMemoryError);
-        }
+    fn serialized_length(&self) -> usize {
+        self.iter().map(Vec::len).sum::<usize>() + (U32_SERIALIZED_LENGTH * (self.len() + 1))
+    }
 
-        let mut result: Vec<u8> = Vec::with_capacity(serialized_len);
-        result.append(&mut (self_len as u32).into_bytes()?);
-        for vec in self.into_iter() {
-            result.append(&mut vec.into_bytes()?)
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        (self.len() as u32).write_bytes(writer)?;
+        for vec in self.iter() {
+            vec.write_bytes(writer)?;
         }
-        Ok(result)
+        Ok(())
     }
 }
 
@@ -345,18 +454,22 @@ impl FromBytes for Vec<String> {
 
 impl ToBytes for Vec<String> {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let size = self.len() as u32;
-        let mut result: Vec<u8> = Vec::with_capacity(U32_SERIALIZED_LENGTH);
-        result.extend(size.to_bytes()?);
-        result.extend(
-            self.iter()
-                .map(ToBytes::to_bytes)
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .flatten(),
-        );
+        let mut result = allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH + self.iter().map(ToBytes::serialized_length).sum::<usize>()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        (self.len() as u32).write_bytes(writer)?;
+        for string in self.iter() {
+            string.write_bytes(writer)?;
+        }
+        Ok(())
+    }
 }
 
 macro_rules! impl_to_from_bytes_for_array {
@@ -364,21 +477,22 @@ macro_rules! impl_to_from_bytes_for_array {
         $(
             impl<T: ToBytes> ToBytes for [T; $N] {
                default fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-                    // Approximation, as `size_of::<T>()` is only roughly equal to the serialized
-                    // size of `T`.
-                    let approx_size = self.len() * size_of::<T>();
-                    if approx_size >= u32::max_value() as usize {
-                        return Err(Error::OutOfMemoryError);
-                    }
+                    let mut result = allocate_buffer(self)?;
+                    self.write_bytes(&mut result)?;
+                    Ok(result)
+                }
 
-                    let mut result = Vec::with_capacity(approx_size);
-                    result.append(&mut ($N as u32).to_bytes()?);
+               default fn serialized_length(&self) -> usize {
+                    U32_SERIALIZED_LENGTH
+                        + self.iter().map(ToBytes::serialized_length).sum::<usize>()
+                }
 
+               default fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+                    ($N as u32).write_bytes(writer)?;
                     for item in self.iter() {
-                        result.append(&mut item.to_bytes()?);
+                        item.write_bytes(writer)?;
                     }
-
-                    Ok(result)
+                    Ok(())
                 }
             }
 
@@ -429,6 +543,15 @@ macro_rules! impl_byte_array {
                 fn to_bytes(&self) -> Result<Vec<u8>, Error> {
                     Ok(self.to_vec())
                 }
+
+                fn serialized_length(&self) -> usize {
+                    $len
+                }
+
+                fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+                    writer.extend_from_slice(self);
+                    Ok(())
+                }
             }
 
             impl FromBytes for [u8; $len] {
@@ -449,6 +572,14 @@ impl ToBytes for String {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         self.as_str().to_bytes()
     }
+
+    fn serialized_length(&self) -> usize {
+        self.as_str().serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.as_str().write_bytes(writer)
+    }
 }
 
 impl FromBytes for String {
@@ -463,6 +594,14 @@ impl ToBytes for () {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         Ok(Vec::new())
     }
+
+    fn serialized_length(&self) -> usize {
+        0
+    }
+
+    fn write_bytes(&self, _writer: &mut Vec<u8>) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 impl FromBytes for () {
@@ -477,35 +616,27 @@ where
     V: ToBytes,
 {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let num_keys = self.len() as u32;
-        let bytes = self
-            .iter()
-            .map(move |(k, v)| {
-                let k_bytes = k.to_bytes().map_err(Error::from);
-                let v_bytes = v.to_bytes().map_err(Error::from);
-                // For each key and value pair create a vector of
-                // serialization results
-                let mut vs = Vec::with_capacity(2);
-                vs.push(k_bytes);
-                vs.push(v_bytes);
-                vs
-            })
-            // Flatten iterable of key and value pairs
-            .flatten()
-            // Collect into a single Result of bytes (if successful)
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter()
-            .flatten();
-
-        let (lower_bound, _upper_bound) = bytes.size_hint();
-        if lower_bound >= u32::max_value() as usize - U32_SERIALIZED_LENGTH {
-            return Err(Error::OutOfMemoryError);
-        }
-        let mut result: Vec<u8> = Vec::with_capacity(U32_SERIALIZED_LENGTH + lower_bound);
-        result.append(&mut num_keys.to_bytes()?);
-        result.extend(bytes);
+        let mut result = allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH
+            + self
+                .iter()
+                .map(|(key, value)| key.serialized_length() + value.serialized_length())
+                .sum::<usize>()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        (self.len() as u32).write_bytes(writer)?;
+        for (key, value) in self.iter() {
+            key.write_bytes(writer)?;
+            value.write_bytes(writer)?;
+        }
+        Ok(())
+    }
 }
 
 impl<K, V> FromBytes for BTreeMap<K, V>
@@ -528,10 +659,15 @@ where
 
 impl ToBytes for str {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        if self.len() >= u32::max_value() as usize - U32_SERIALIZED_LENGTH {
-            return Err(Error::OutOfMemoryError);
-        }
-        self.as_bytes().to_vec().into_bytes()
+        self.as_bytes().to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.as_bytes().serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.as_bytes().write_bytes(writer)
     }
 }
 
@@ -539,19 +675,43 @@ impl ToBytes for &str {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         (*self).to_bytes()
     }
+
+    fn serialized_length(&self) -> usize {
+        (*self).serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        (*self).write_bytes(writer)
+    }
 }
 
 impl<T: ToBytes, E: ToBytes> ToBytes for Result<T, E> {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let (mut variant, mut value) = match self {
-            Ok(result) => (1u8.to_bytes()?, result.to_bytes()?),
-            Err(error) => (0u8.to_bytes()?, error.to_bytes()?),
-        };
-        let mut result: Vec<u8> = Vec::with_capacity(U8_SERIALIZED_LENGTH + value.len());
-        result.append(&mut variant);
-        result.append(&mut value);
+        let mut result = allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                Ok(result) => result.serialized_length(),
+                Err(error) => error.serialized_length(),
+            }
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        match self {
+            Ok(result) => {
+                writer.push(1);
+                result.write_bytes(writer)
+            }
+            Err(error) => {
+                writer.push(0);
+                error.write_bytes(writer)
+            }
+        }
+    }
 }
 
 impl<T: FromBytes, E: FromBytes> FromBytes for Result<T, E> {
@@ -573,10 +733,18 @@ impl<T: FromBytes, E: FromBytes> FromBytes for Result<T, E> {
 
 impl<T1: ToBytes> ToBytes for (T1,) {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let mut result = Vec::new();
-        result.append(&mut self.0.to_bytes()?);
+        let mut result = allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.0.write_bytes(writer)
+    }
 }
 
 impl<T1: FromBytes> FromBytes for (T1,) {
@@ -588,11 +756,19 @@ impl<T1: FromBytes> FromBytes for (T1,) {
 
 impl<T1: ToBytes, T2: ToBytes> ToBytes for (T1, T2) {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let mut result = Vec::new();
-        result.append(&mut self.0.to_bytes()?);
-        result.append(&mut self.1.to_bytes()?);
+        let mut result = allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length() + self.1.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.0.write_bytes(writer)?;
+        self.1.write_bytes(writer)
+    }
 }
 
 impl<T1: FromBytes, T2: FromBytes> FromBytes for (T1, T2) {
@@ -605,12 +781,20 @@ impl<T1: FromBytes, T2: FromBytes> FromBytes for (T1, T2) {
 
 impl<T1: ToBytes, T2: ToBytes, T3: ToBytes> ToBytes for (T1, T2, T3) {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let mut result = Vec::new();
-        result.append(&mut self.0.to_bytes()?);
-        result.append(&mut self.1.to_bytes()?);
-        result.append(&mut self.2.to_bytes()?);
+        let mut result = allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length() + self.1.serialized_length() + self.2.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.0.write_bytes(writer)?;
+        self.1.write_bytes(writer)?;
+        self.2.write_bytes(writer)
+    }
 }
 
 impl<T1: FromBytes, T2: FromBytes, T3: FromBytes> FromBytes for (T1, T2, T3) {
@@ -622,6 +806,94 @@ impl<T1: FromBytes, T2: FromBytes, T3: FromBytes> FromBytes for (T1, T2, T3) {
     }
 }
 
+/// Returns a BLAKE2b digest of the serialized forms of a fixed set of representative values.
+///
+/// The digest only changes when the encoding of one of these values changes, so pinning it in a
+/// test flags edits which would stop data written by earlier protocol versions from decoding.
+pub fn schema_digest() -> Result<[u8; SCHEMA_DIGEST_LENGTH], Error> {
+    let mut digest = [0u8; SCHEMA_DIGEST_LENGTH];
+    // Safe to unwrap here because our digest length is constant and valid
+    let mut hasher = VarBlake2b::new(SCHEMA_DIGEST_LENGTH).unwrap();
+    for sample in schema_samples()? {
+        // Length-prefixed so that bytes moving from one sample to the next alter the digest
+        hasher.input((sample.len() as u32).to_bytes()?);
+        hasher.input(sample);
+    }
+    hasher.variable_result(|hash| digest.clone_from_slice(hash));
+    Ok(digest)
+}
+
+fn cl_value_bytes<T: CLTyped + ToBytes>(value: T) -> Result<Vec<u8>, Error> {
+    CLValue::from_components(T::cl_type(), value.into_bytes()?).into_bytes()
+}
+
+/// The values covered by [`schema_digest`].  Samples may be appended, but existing ones must not
+/// be altered, otherwise the digest no longer says anything about the encodings.
+fn schema_samples() -> Result<Vec<Vec<u8>>, Error> {
+    let uref = URef::new([1; 32], AccessRights::READ_ADD_WRITE);
+    let mut named_keys = BTreeMap::new();
+    named_keys.insert(String::from("a"), Key::Hash([3; 32]));
+    let tuple = (1u8, String::from("x"), true);
+    let entry_points: EntryPoints = vec![
+        EntryPoint::new(
+            "transfer",
+            vec![
+                Parameter::new("target", CLType::Key),
+                Parameter::new("amount", CLType::U512),
+            ],
+            CLType::Unit,
+        ),
+        EntryPoint::new("balance", vec![], CLType::Option(Box::new(CLType::U512))),
+    ]
+    .into();
+
+    Ok(vec![
+        true.to_bytes()?,
+        7u8.to_bytes()?,
+        (-1i32).to_bytes()?,
+        1000u32.to_bytes()?,
+        (-5i64).to_bytes()?,
+        123_456_789u64.to_bytes()?,
+        U128::from(1000).to_bytes()?,
+        U256::zero().to_bytes()?,
+        U512::from(1_000_000_000).to_bytes()?,
+        ().to_bytes()?,
+        String::from("hello").to_bytes()?,
+        vec![1u8, 2, 3].to_bytes()?,
+        Some(5u64).to_bytes()?,
+        None::<u64>.to_bytes()?,
+        Ok::<u64, String>(1).to_bytes()?,
+        tuple.to_bytes()?,
+        [7u8; 32].to_bytes()?,
+        named_keys.to_bytes()?,
+        AccessRights::READ_ADD_WRITE.to_bytes()?,
+        uref.to_bytes()?,
+        Key::Account([2; 32]).to_bytes()?,
+        Key::Hash([3; 32]).to_bytes()?,
+        Key::URef(uref).to_bytes()?,
+        Key::Local([4; 32]).to_bytes()?,
+        ProtocolVersion::V1_0_0.to_bytes()?,
+        Phase::Session.to_bytes()?,
+        cl_value_bytes(1u64)?,
+        cl_value_bytes(Some(U512::from(100)))?,
+        cl_value_bytes(vec![String::from("a"), String::from("b")])?,
+        cl_value_bytes(named_keys)?,
+        cl_value_bytes(Ok::<u64, String>(1))?,
+        cl_value_bytes(tuple)?,
+        cl_value_bytes(Key::Hash([3; 32]))?,
+        cl_value_bytes(uref)?,
+        entry_points.to_bytes()?,
+        EntryPoint::new("admin", vec![], CLType::Unit)
+            .with_access(EntryPointAccess::Groups(vec![uref]))
+            .to_bytes()?,
+        EntryPoint::new("admin", vec![], CLType::Unit)
+            .with_access(EntryPointAccess::Accounts(vec![PublicKey::new([5; 32])]))
+            .to_bytes()?,
+        CallStackElement::Account(PublicKey::new([5; 32])).to_bytes()?,
+        CallStackElement::Contract(Key::Hash([3; 32])).to_bytes()?,
+    ])
+}
+
 #[doc(hidden)]
 /// Returns `true` if a we can serialize and then deserialize a value
 pub fn test_serialization_roundtrip<T>(t: &T)
@@ -629,6 +901,10 @@ where
     T: ToBytes + FromBytes + PartialEq,
 {
     let serialized = ToBytes::to_bytes(t).expect("Unable to serialize data");
+    assert_eq!(serialized.len(), t.serialized_length());
+    let mut written = Vec::new();
+    t.write_bytes(&mut written).expect("Unable to write data");
+    assert_eq!(written, serialized);
     let deserialized = deserialize::<T>(serialized).expect("Unable to deserialize data");
     assert!(*t == deserialized)
 }
@@ -847,6 +1123,26 @@ mod proptests {
         fn test_tuple3(t in (any::<u8>(),any::<u32>(),any::<i32>())) {
             bytesrepr::test_serialization_roundtrip(&t);
         }
+
+        #[test]
+        fn test_block_time(block_time in block_time_arb()) {
+            bytesrepr::test_serialization_roundtrip(&block_time);
+        }
+
+        #[test]
+        fn test_call_stack_element(element in call_stack_element_arb()) {
+            bytesrepr::test_serialization_roundtrip(&element);
+        }
+
+        #[test]
+        fn test_install_manifest(install_manifest in install_manifest_arb()) {
+            bytesrepr::test_serialization_roundtrip(&install_manifest);
+        }
+
+        #[test]
+        fn test_contract_metadata(contract_metadata in contract_metadata_arb()) {
+            bytesrepr::test_serialization_roundtrip(&contract_metadata);
+        }
     }
 
     #[test]
@@ -858,3 +1154,123 @@ mod proptests {
         assert!(Vec::<u8>::from_bytes(&data_bytes[..U32_SERIALIZED_LENGTH + 2]).is_err());
     }
 }
+
+#[cfg(test)]
+mod compatibility_tests {
+    use std::{collections::BTreeMap, fmt::Debug, string::String, vec::Vec};
+
+    use super::{deserialize, schema_digest, FromBytes, ToBytes};
+    use crate::{AccessRights, CLValue, Key, ProtocolVersion, URef, U512};
+
+    /// The digest of the current encodings.  If this test fails, the serialized form of a value
+    /// has changed: unless that was intended (and the protocol version bumped accordingly), the
+    /// change must be reverted.  Otherwise update the digest and add fixtures for the new release.
+    const EXPECTED_SCHEMA_DIGEST: &str =
+        "725ffaaa01e33bb342d16698b98c55a0c793848f52c7764ceca6b7b8361352ea";
+
+    /// Encodings produced by the encoders of the 1.0.0 release.
+    mod v1_0_0 {
+        pub const KEY_ACCOUNT: &str =
+            "000202020202020202020202020202020202020202020202020202020202020202";
+        pub const KEY_HASH: &str =
+            "010303030303030303030303030303030303030303030303030303030303030303";
+        pub const KEY_UREF: &str =
+            "0201010101010101010101010101010101010101010101010101010101010101010107";
+        pub const KEY_LOCAL: &str =
+            "030404040404040404040404040404040404040404040404040404040404040404";
+        pub const UREF: &str =
+            "01010101010101010101010101010101010101010101010101010101010101010107";
+        pub const UREF_WITHOUT_ACCESS_RIGHTS: &str =
+            "010101010101010101010101010101010101010101010101010101010101010100";
+        pub const PROTOCOL_VERSION: &str = "010000000000000000000000";
+        pub const CL_VALUE_U512: &str = "050000000400ca9a3b08";
+        pub const CL_VALUE_OPTION_STRING: &str = "0a000000010500000068656c6c6f0d0a";
+        pub const CL_VALUE_NAMED_KEYS: &str = concat!(
+            "2a00000001000000010000006101",
+            "0303030303030303030303030303030303030303030303030303030303030303",
+            "110a0b"
+        );
+        pub const CL_VALUE_TUPLE3: &str = "070000000101000000780114030a00";
+    }
+
+    fn assert_decodes<T>(protocol_version: ProtocolVersion, fixture: &str, expected: T)
+    where
+        T: ToBytes + FromBytes + PartialEq + Debug,
+    {
+        let bytes = base16::decode(fixture).expect("fixture should be valid hex");
+        let decoded = deserialize::<T>(bytes.clone()).unwrap_or_else(|error| {
+            panic!(
+                "failed to decode {} fixture {}: {}",
+                protocol_version, fixture, error
+            )
+        });
+        assert_eq!(
+            decoded, expected,
+            "{} fixture {}",
+            protocol_version, fixture
+        );
+        assert_eq!(
+            expected.to_bytes().expect("should serialize"),
+            bytes,
+            "encoding differs from {} fixture",
+            protocol_version
+        );
+    }
+
+    #[test]
+    fn should_decode_v1_0_0_fixtures() {
+        let protocol_version = ProtocolVersion::V1_0_0;
+        let uref = URef::new([1; 32], AccessRights::READ_ADD_WRITE);
+
+        assert_decodes(protocol_version, v1_0_0::KEY_ACCOUNT, Key::Account([2; 32]));
+        assert_decodes(protocol_version, v1_0_0::KEY_HASH, Key::Hash([3; 32]));
+        assert_decodes(protocol_version, v1_0_0::KEY_UREF, Key::URef(uref));
+        assert_decodes(protocol_version, v1_0_0::KEY_LOCAL, Key::Local([4; 32]));
+        assert_decodes(protocol_version, v1_0_0::UREF, uref);
+        assert_decodes(
+            protocol_version,
+            v1_0_0::UREF_WITHOUT_ACCESS_RIGHTS,
+            uref.remove_access_rights(),
+        );
+        assert_decodes(
+            protocol_version,
+            v1_0_0::PROTOCOL_VERSION,
+            ProtocolVersion::V1_0_0,
+        );
+
+        let mut named_keys = BTreeMap::new();
+        named_keys.insert(String::from("a"), Key::Hash([3; 32]));
+        let cl_values: Vec<(&str, CLValue)> = vec![
+            (
+                v1_0_0::CL_VALUE_U512,
+                CLValue::from_t(U512::from(1_000_000_000)).unwrap(),
+            ),
+            (
+                v1_0_0::CL_VALUE_OPTION_STRING,
+                CLValue::from_t(Some(String::from("hello"))).unwrap(),
+            ),
+            (
+                v1_0_0::CL_VALUE_NAMED_KEYS,
+                CLValue::from_t(named_keys).unwrap(),
+            ),
+            (
+                v1_0_0::CL_VALUE_TUPLE3,
+                CLValue::from_t((1u8, String::from("x"), true)).unwrap(),
+            ),
+        ];
+        for (fixture, expected) in cl_values {
+            assert_decodes(protocol_version, fixture, expected);
+        }
+    }
+
+    #[test]
+    fn should_match_expected_schema_digest() {
+        let digest = schema_digest().expect("should compute schema digest");
+        assert_eq!(base16::encode_lower(&digest), EXPECTED_SCHEMA_DIGEST);
+    }
+
+    #[test]
+    fn schema_digest_should_be_deterministic() {
+        assert_eq!(schema_digest(), schema_digest());
+    }
+}
@@ -0,0 +1,93 @@
+use alloc::vec::Vec;
+
+use crate::{
+    account::PublicKey,
+    bytesrepr::{Error, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped, Key,
+};
+
+const ACCOUNT_TAG: u8 = 0;
+const CONTRACT_TAG: u8 = 1;
+
+/// A frame of the call stack of the currently executing code.
+///
+/// The first element is always the account executing the deploy; each call to a stored contract
+/// pushes a further element.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CallStackElement {
+    /// Session or payment code, executed on behalf of the given account.
+    Account(PublicKey),
+    /// A stored contract, identified by the key it is stored under.  `URef`s have their access
+    /// rights removed.
+    Contract(Key),
+}
+
+impl ToBytes for CallStackElement {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = Vec::new();
+        match self {
+            CallStackElement::Account(public_key) => {
+                result.push(ACCOUNT_TAG);
+                result.append(&mut public_key.to_bytes()?);
+            }
+            CallStackElement::Contract(key) => {
+                result.push(CONTRACT_TAG);
+                result.append(&mut key.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                CallStackElement::Account(public_key) => public_key.serialized_length(),
+                CallStackElement::Contract(key) => key.serialized_length(),
+            }
+    }
+}
+
+impl FromBytes for CallStackElement {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        match tag {
+            ACCOUNT_TAG => {
+                let (public_key, rem): (PublicKey, &[u8]) = FromBytes::from_bytes(rem)?;
+                Ok((CallStackElement::Account(public_key), rem))
+            }
+            CONTRACT_TAG => {
+                let (key, rem): (Key, &[u8]) = FromBytes::from_bytes(rem)?;
+                Ok((CallStackElement::Contract(key), rem))
+            }
+            _ => Err(Error::FormattingError),
+        }
+    }
+}
+
+/// Has no equivalent `CLType`, so is represented as [`CLType::Any`].
+impl CLTyped for CallStackElement {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::CallStackElement;
+    use crate::{account::PublicKey, bytesrepr, AccessRights, Key, URef};
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        let call_stack = vec![
+            CallStackElement::Account(PublicKey::new([1; 32])),
+            CallStackElement::Contract(Key::Hash([2; 32])),
+            CallStackElement::Contract(Key::URef(URef::new([3; 32], AccessRights::READ))),
+        ];
+        for element in &call_stack {
+            bytesrepr::test_serialization_roundtrip(element);
+        }
+        bytesrepr::test_serialization_roundtrip(&call_stack);
+    }
+}
@@ -651,6 +651,10 @@ mod tests {
             fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
                 self.0.to_bytes()
             }
+
+            fn serialized_length(&self) -> usize {
+                self.0.serialized_length()
+            }
         }
 
         impl FromBytes for Any {
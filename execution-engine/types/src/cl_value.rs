@@ -82,13 +82,19 @@ impl CLValue {
 
 impl ToBytes for CLValue {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        self.clone().into_bytes()
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
     }
 
-    fn into_bytes(self) -> Result<Vec<u8>, bytesrepr::Error> {
-        let mut result = self.bytes.into_bytes()?;
-        self.cl_type.append_bytes(&mut result);
-        Ok(result)
+    fn serialized_length(&self) -> usize {
+        self.serialized_len()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.bytes.write_bytes(writer)?;
+        self.cl_type.append_bytes(writer);
+        Ok(())
     }
 }
 
@@ -103,37 +109,21 @@ impl FromBytes for CLValue {
 
 impl ToBytes for Vec<CLValue> {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        let serialized_len = self.iter().map(CLValue::serialized_len).sum();
-        if serialized_len > u32::max_value() as usize - U32_SERIALIZED_LENGTH {
-            return Err(bytesrepr::Error::OutOfMemoryError);
-        }
-
-        let mut result = Vec::with_capacity(serialized_len);
-        let len = self.len() as u32;
-        result.append(&mut len.to_bytes()?);
-
-        for cl_value in self {
-            result.append(&mut cl_value.to_bytes()?);
-        }
-
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
 
-    fn into_bytes(self) -> Result<Vec<u8>, bytesrepr::Error> {
-        let serialized_len = self.iter().map(CLValue::serialized_len).sum();
-        if serialized_len > u32::max_value() as usize - U32_SERIALIZED_LENGTH {
-            return Err(bytesrepr::Error::OutOfMemoryError);
-        }
-
-        let mut result = Vec::with_capacity(serialized_len);
-        let len = self.len() as u32;
-        result.append(&mut len.to_bytes()?);
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH + self.iter().map(CLValue::serialized_len).sum::<usize>()
+    }
 
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        (self.len() as u32).write_bytes(writer)?;
         for cl_value in self {
-            result.append(&mut cl_value.into_bytes()?);
+            cl_value.write_bytes(writer)?;
         }
-
-        Ok(result)
+        Ok(())
     }
 }
 
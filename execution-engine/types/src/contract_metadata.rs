@@ -0,0 +1,114 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    bytesrepr::{Error, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+/// Name of the Wasm custom section holding the serialized [`ContractMetadata`] of a contract.
+pub const CONTRACT_METADATA_SECTION: &str = "casperlabs-meta";
+
+/// Describes how a contract was compiled.
+///
+/// Contracts may optionally embed this in a Wasm custom section named
+/// [`CONTRACT_METADATA_SECTION`], serialized with [`ToBytes`].  The section is preserved when the
+/// contract is stored, so the metadata can later be used to identify which source produced the
+/// on-chain bytes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContractMetadata {
+    crate_name: String,
+    version: String,
+    toolchain: String,
+}
+
+impl ContractMetadata {
+    pub fn new(crate_name: String, version: String, toolchain: String) -> Self {
+        ContractMetadata {
+            crate_name,
+            version,
+            toolchain,
+        }
+    }
+
+    /// Name of the crate the contract was compiled from.
+    pub fn crate_name(&self) -> &str {
+        &self.crate_name
+    }
+
+    /// Version of the crate the contract was compiled from.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Rust toolchain used to compile the contract, e.g. `nightly-2020-01-08`.
+    pub fn toolchain(&self) -> &str {
+        &self.toolchain
+    }
+}
+
+impl ToBytes for ContractMetadata {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut ret = Vec::new();
+        ret.append(&mut self.crate_name.to_bytes()?);
+        ret.append(&mut self.version.to_bytes()?);
+        ret.append(&mut self.toolchain.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.crate_name.serialized_length()
+            + self.version.serialized_length()
+            + self.toolchain.serialized_length()
+    }
+}
+
+impl FromBytes for ContractMetadata {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (crate_name, rem): (String, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (version, rem): (String, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (toolchain, rem): (String, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((ContractMetadata::new(crate_name, version, toolchain), rem))
+    }
+}
+
+/// Serialized the same way as a tuple of the crate name, version and toolchain.
+impl CLTyped for ContractMetadata {
+    fn cl_type() -> CLType {
+        <(String, String, String)>::cl_type()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::ContractMetadata;
+    use crate::{bytesrepr, CLValue};
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        let metadata = ContractMetadata::new(
+            "counter".to_string(),
+            "0.1.0".to_string(),
+            "nightly-2020-01-08".to_string(),
+        );
+        bytesrepr::test_serialization_roundtrip(&metadata);
+        bytesrepr::test_serialization_roundtrip(&ContractMetadata::default());
+    }
+
+    #[test]
+    fn should_convert_to_and_from_cl_value() {
+        let metadata = ContractMetadata::new(
+            "counter".to_string(),
+            "0.1.0".to_string(),
+            "nightly-2020-01-08".to_string(),
+        );
+        let cl_value = CLValue::from_t(metadata.clone()).expect("should create CLValue");
+        let (crate_name, version, toolchain): (String, String, String) =
+            cl_value.clone().into_t().expect("should convert to tuple");
+        assert_eq!(crate_name, metadata.crate_name());
+        assert_eq!(version, metadata.version());
+        assert_eq!(toolchain, metadata.toolchain());
+        assert_eq!(cl_value.into_t::<ContractMetadata>(), Ok(metadata));
+    }
+}
@@ -0,0 +1,388 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use crate::{
+    account::PublicKey,
+    bytesrepr::{self, Error, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
+    CLType, CLValue, URef,
+};
+
+/// Version number of a contract within a contract package.  Versions start at
+/// [`CONTRACT_INITIAL_VERSION`] and are never reused.
+pub type ContractVersion = u32;
+
+/// The version assigned to the first contract of a package.
+pub const CONTRACT_INITIAL_VERSION: ContractVersion = 1;
+
+/// A named, typed parameter of an [`EntryPoint`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Parameter {
+    name: String,
+    cl_type: CLType,
+}
+
+impl Parameter {
+    pub fn new<T: Into<String>>(name: T, cl_type: CLType) -> Self {
+        Parameter {
+            name: name.into(),
+            cl_type,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn cl_type(&self) -> &CLType {
+        &self.cl_type
+    }
+}
+
+impl ToBytes for Parameter {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.name.serialized_length() + self.cl_type.serialized_len()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.name.write_bytes(writer)?;
+        self.cl_type.append_bytes(writer);
+        Ok(())
+    }
+}
+
+impl FromBytes for Parameter {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (name, rem): (String, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (cl_type, rem): (CLType, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((Parameter { name, cl_type }, rem))
+    }
+}
+
+const ENTRY_POINT_ACCESS_TAG_PUBLIC: u8 = 0;
+const ENTRY_POINT_ACCESS_TAG_GROUPS: u8 = 1;
+const ENTRY_POINT_ACCESS_TAG_ACCOUNTS: u8 = 2;
+
+/// Specifies who may call an [`EntryPoint`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EntryPointAccess {
+    /// Anyone may call the entry point.
+    Public,
+    /// Only callers holding at least one of the given `URef`s may call the entry point.
+    Groups(Vec<URef>),
+    /// Only deploys executed as one of the given accounts may call the entry point.
+    Accounts(Vec<PublicKey>),
+}
+
+impl Default for EntryPointAccess {
+    fn default() -> Self {
+        EntryPointAccess::Public
+    }
+}
+
+impl ToBytes for EntryPointAccess {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                EntryPointAccess::Public => 0,
+                EntryPointAccess::Groups(urefs) => urefs.serialized_length(),
+                EntryPointAccess::Accounts(public_keys) => {
+                    U32_SERIALIZED_LENGTH
+                        + public_keys
+                            .iter()
+                            .map(ToBytes::serialized_length)
+                            .sum::<usize>()
+                }
+            }
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        match self {
+            EntryPointAccess::Public => writer.push(ENTRY_POINT_ACCESS_TAG_PUBLIC),
+            EntryPointAccess::Groups(urefs) => {
+                writer.push(ENTRY_POINT_ACCESS_TAG_GROUPS);
+                urefs.write_bytes(writer)?;
+            }
+            EntryPointAccess::Accounts(public_keys) => {
+                writer.push(ENTRY_POINT_ACCESS_TAG_ACCOUNTS);
+                (public_keys.len() as u32).write_bytes(writer)?;
+                for public_key in public_keys {
+                    public_key.write_bytes(writer)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromBytes for EntryPointAccess {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        match tag {
+            ENTRY_POINT_ACCESS_TAG_PUBLIC => Ok((EntryPointAccess::Public, rem)),
+            ENTRY_POINT_ACCESS_TAG_GROUPS => {
+                let (urefs, rem): (Vec<URef>, &[u8]) = FromBytes::from_bytes(rem)?;
+                Ok((EntryPointAccess::Groups(urefs), rem))
+            }
+            ENTRY_POINT_ACCESS_TAG_ACCOUNTS => {
+                let (count, mut rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+                let mut public_keys = Vec::new();
+                for _ in 0..count {
+                    let (public_key, remainder): (PublicKey, &[u8]) = FromBytes::from_bytes(rem)?;
+                    public_keys.push(public_key);
+                    rem = remainder;
+                }
+                Ok((EntryPointAccess::Accounts(public_keys), rem))
+            }
+            _ => Err(Error::FormattingError),
+        }
+    }
+}
+
+/// A function exported by a versioned contract, along with the signature callers must respect.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryPoint {
+    name: String,
+    parameters: Vec<Parameter>,
+    ret: CLType,
+    access: EntryPointAccess,
+}
+
+impl EntryPoint {
+    /// Creates a public entry point.
+    pub fn new<T: Into<String>>(name: T, parameters: Vec<Parameter>, ret: CLType) -> Self {
+        EntryPoint {
+            name: name.into(),
+            parameters,
+            ret,
+            access: EntryPointAccess::Public,
+        }
+    }
+
+    /// Restricts who may call the entry point.
+    pub fn with_access(mut self, access: EntryPointAccess) -> Self {
+        self.access = access;
+        self
+    }
+
+    /// Name of the exported Wasm function implementing the entry point.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn parameters(&self) -> &[Parameter] {
+        &self.parameters
+    }
+
+    /// Type of the value returned through `runtime::ret`.
+    pub fn ret(&self) -> &CLType {
+        &self.ret
+    }
+
+    pub fn access(&self) -> &EntryPointAccess {
+        &self.access
+    }
+
+    /// Returns `true` if `args` match the parameters of this entry point in number and type.
+    pub fn accepts(&self, args: &[CLValue]) -> bool {
+        self.parameters.len() == args.len()
+            && self
+                .parameters
+                .iter()
+                .zip(args)
+                .all(|(parameter, arg)| parameter.cl_type() == arg.cl_type())
+    }
+}
+
+impl ToBytes for EntryPoint {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.name.serialized_length()
+            + U32_SERIALIZED_LENGTH
+            + self
+                .parameters
+                .iter()
+                .map(ToBytes::serialized_length)
+                .sum::<usize>()
+            + self.ret.serialized_len()
+            + self.access.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.name.write_bytes(writer)?;
+        (self.parameters.len() as u32).write_bytes(writer)?;
+        for parameter in &self.parameters {
+            parameter.write_bytes(writer)?;
+        }
+        self.ret.append_bytes(writer);
+        self.access.write_bytes(writer)
+    }
+}
+
+impl FromBytes for EntryPoint {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (name, rem): (String, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (parameter_count, mut rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let mut parameters = Vec::new();
+        for _ in 0..parameter_count {
+            let (parameter, remainder): (Parameter, &[u8]) = FromBytes::from_bytes(rem)?;
+            parameters.push(parameter);
+            rem = remainder;
+        }
+        let (ret, rem): (CLType, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (access, rem): (EntryPointAccess, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((
+            EntryPoint {
+                name,
+                parameters,
+                ret,
+                access,
+            },
+            rem,
+        ))
+    }
+}
+
+/// The entry points of a versioned contract, keyed by name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EntryPoints(BTreeMap<String, EntryPoint>);
+
+impl EntryPoints {
+    pub fn new() -> Self {
+        EntryPoints::default()
+    }
+
+    /// Adds `entry_point`, replacing any entry point with the same name.
+    pub fn add_entry_point(&mut self, entry_point: EntryPoint) {
+        self.0.insert(entry_point.name.clone(), entry_point);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&EntryPoint> {
+        self.0.get(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &EntryPoint> {
+        self.0.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<EntryPoint>> for EntryPoints {
+    fn from(entry_points: Vec<EntryPoint>) -> Self {
+        let mut result = EntryPoints::new();
+        for entry_point in entry_points {
+            result.add_entry_point(entry_point);
+        }
+        result
+    }
+}
+
+impl ToBytes for EntryPoints {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.0.write_bytes(writer)
+    }
+}
+
+impl FromBytes for EntryPoints {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (entry_points, rem): (BTreeMap<String, EntryPoint>, &[u8]) =
+            FromBytes::from_bytes(bytes)?;
+        Ok((EntryPoints(entry_points), rem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, vec};
+
+    use super::{EntryPoint, EntryPointAccess, EntryPoints, Parameter};
+    use crate::{account::PublicKey, bytesrepr, AccessRights, CLType, CLValue, Key, URef, U512};
+
+    fn transfer_entry_point() -> EntryPoint {
+        EntryPoint::new(
+            "transfer",
+            vec![
+                Parameter::new("target", CLType::Key),
+                Parameter::new("amount", CLType::U512),
+            ],
+            CLType::Unit,
+        )
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        let entry_points: EntryPoints = vec![
+            transfer_entry_point(),
+            EntryPoint::new("balance", vec![], CLType::Option(Box::new(CLType::U512))),
+        ]
+        .into();
+        bytesrepr::test_serialization_roundtrip(&transfer_entry_point());
+        bytesrepr::test_serialization_roundtrip(&entry_points);
+        bytesrepr::test_serialization_roundtrip(&EntryPoints::new());
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_access() {
+        let uref = URef::new([1; 32], AccessRights::READ);
+        let accesses = vec![
+            EntryPointAccess::Public,
+            EntryPointAccess::Groups(vec![]),
+            EntryPointAccess::Groups(vec![uref, uref.remove_access_rights()]),
+            EntryPointAccess::Accounts(vec![PublicKey::new([2; 32]), PublicKey::new([3; 32])]),
+        ];
+        for access in accesses {
+            bytesrepr::test_serialization_roundtrip(&access);
+            bytesrepr::test_serialization_roundtrip(
+                &transfer_entry_point().with_access(access.clone()),
+            );
+        }
+        assert_eq!(transfer_entry_point().access(), &EntryPointAccess::Public);
+    }
+
+    #[test]
+    fn should_check_args_against_parameters() {
+        let entry_point = transfer_entry_point();
+        let target = CLValue::from_t(Key::Hash([1; 32])).unwrap();
+        let amount = CLValue::from_t(U512::from(10)).unwrap();
+        let wrong_amount = CLValue::from_t(10u64).unwrap();
+
+        assert!(entry_point.accepts(&[target.clone(), amount.clone()]));
+        assert!(!entry_point.accepts(&[target.clone()]));
+        assert!(!entry_point.accepts(&[target.clone(), wrong_amount]));
+        assert!(!entry_point.accepts(&[amount, target]));
+    }
+}
@@ -12,7 +12,8 @@ use proptest::{
 
 use crate::{
     account::{PublicKey, Weight},
-    AccessRights, CLType, CLValue, Key, Phase, ProtocolVersion, SemVer, URef, U128, U256, U512,
+    AccessRights, BlockTime, CLType, CLValue, CallStackElement, ContractMetadata, InstallManifest,
+    Key, Phase, ProtocolVersion, SemVer, URef, U128, U256, U512,
 };
 
 pub fn u8_slice_32() -> impl Strategy<Value = [u8; 32]> {
@@ -68,6 +69,31 @@ pub fn public_key_arb() -> impl Strategy<Value = PublicKey> {
     u8_slice_32().prop_map(Into::into)
 }
 
+pub fn block_time_arb() -> impl Strategy<Value = BlockTime> {
+    any::<u64>().prop_map(BlockTime::new)
+}
+
+pub fn call_stack_element_arb() -> impl Strategy<Value = CallStackElement> {
+    prop_oneof![
+        public_key_arb().prop_map(CallStackElement::Account),
+        key_arb().prop_map(CallStackElement::Contract),
+    ]
+}
+
+pub fn install_manifest_arb() -> impl Strategy<Value = InstallManifest> {
+    (u8_slice_32(), uref_arb(), named_keys_arb(3)).prop_map(
+        |(package_hash, access_key, named_keys)| {
+            InstallManifest::new(package_hash, access_key, named_keys)
+        },
+    )
+}
+
+pub fn contract_metadata_arb() -> impl Strategy<Value = ContractMetadata> {
+    ("\\PC*", "\\PC*", "\\PC*").prop_map(|(crate_name, version, toolchain)| {
+        ContractMetadata::new(crate_name, version, toolchain)
+    })
+}
+
 pub fn weight_arb() -> impl Strategy<Value = Weight> {
     any::<u8>().prop_map(Weight::new)
 }
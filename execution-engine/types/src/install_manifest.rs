@@ -0,0 +1,144 @@
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+
+use crate::{
+    bytesrepr::{Error, FromBytes, ToBytes},
+    CLType, CLTyped, Key, URef,
+};
+
+/// Suffix of the named key under which an installer stores the access key of a package.
+const ACCESS_KEY_SUFFIX: &str = "_access";
+
+/// Suffix of the named key under which an installer stores the [`InstallManifest`] of a package.
+const INSTALL_MANIFEST_SUFFIX: &str = "_install_manifest";
+
+/// Returns the named key under which an installer stores the access key of the package it
+/// installed as `package_name`.
+pub fn access_key_name(package_name: &str) -> String {
+    format!("{}{}", package_name, ACCESS_KEY_SUFFIX)
+}
+
+/// Returns the named key under which an installer stores the [`InstallManifest`] of the package
+/// it installed as `package_name`.
+pub fn install_manifest_key_name(package_name: &str) -> String {
+    format!("{}{}", package_name, INSTALL_MANIFEST_SUFFIX)
+}
+
+/// Describes what an installer contract stored: the contract package, the key required to add
+/// or disable its versions, and the named keys its contracts were given.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstallManifest {
+    package_hash: [u8; 32],
+    access_key: URef,
+    named_keys: BTreeMap<String, Key>,
+}
+
+impl InstallManifest {
+    pub fn new(
+        package_hash: [u8; 32],
+        access_key: URef,
+        named_keys: BTreeMap<String, Key>,
+    ) -> Self {
+        InstallManifest {
+            package_hash,
+            access_key,
+            named_keys,
+        }
+    }
+
+    /// Hash of the installed contract package.
+    pub fn package_hash(&self) -> [u8; 32] {
+        self.package_hash
+    }
+
+    /// Access key of the installed contract package.
+    pub fn access_key(&self) -> URef {
+        self.access_key
+    }
+
+    /// Named keys the contracts of the installed package were given.
+    pub fn named_keys(&self) -> &BTreeMap<String, Key> {
+        &self.named_keys
+    }
+}
+
+impl ToBytes for InstallManifest {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut ret = Vec::new();
+        ret.append(&mut self.package_hash.to_bytes()?);
+        ret.append(&mut self.access_key.to_bytes()?);
+        ret.append(&mut self.named_keys.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.package_hash.serialized_length()
+            + self.access_key.serialized_length()
+            + self.named_keys.serialized_length()
+    }
+}
+
+impl FromBytes for InstallManifest {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (package_hash, rem): ([u8; 32], &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (access_key, rem): (URef, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (named_keys, rem): (BTreeMap<String, Key>, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((
+            InstallManifest::new(package_hash, access_key, named_keys),
+            rem,
+        ))
+    }
+}
+
+/// Serialized the same way as a tuple of the package hash, access key and named keys.
+impl CLTyped for InstallManifest {
+    fn cl_type() -> CLType {
+        <([u8; 32], URef, BTreeMap<String, Key>)>::cl_type()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeMap, string::String};
+
+    use super::{access_key_name, install_manifest_key_name, InstallManifest};
+    use crate::{bytesrepr, AccessRights, CLValue, Key, URef};
+
+    fn manifest() -> InstallManifest {
+        let mut named_keys = BTreeMap::new();
+        named_keys.insert(
+            String::from("counter"),
+            Key::URef(URef::new([2u8; 32], AccessRights::READ_ADD_WRITE)),
+        );
+        InstallManifest::new(
+            [1u8; 32],
+            URef::new([3u8; 32], AccessRights::READ_ADD_WRITE),
+            named_keys,
+        )
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        bytesrepr::test_serialization_roundtrip(&manifest());
+    }
+
+    #[test]
+    fn should_convert_to_and_from_cl_value() {
+        let manifest = manifest();
+        let cl_value = CLValue::from_t(manifest.clone()).expect("should create CLValue");
+        let (package_hash, access_key, named_keys): ([u8; 32], URef, BTreeMap<String, Key>) =
+            cl_value.clone().into_t().expect("should convert to tuple");
+        assert_eq!(package_hash, manifest.package_hash());
+        assert_eq!(access_key, manifest.access_key());
+        assert_eq!(&named_keys, manifest.named_keys());
+        assert_eq!(cl_value.into_t::<InstallManifest>(), Ok(manifest));
+    }
+
+    #[test]
+    fn should_name_keys_after_package() {
+        assert_eq!(access_key_name("counter"), "counter_access");
+        assert_eq!(
+            install_manifest_key_name("counter"),
+            "counter_install_manifest"
+        );
+    }
+}
@@ -11,8 +11,9 @@ use blake2::{
 use hex_fmt::HexFmt;
 
 use crate::{
-    bytesrepr::{Error, FromBytes, ToBytes},
-    AccessRights, ContractRef, URef, UREF_SERIALIZED_LENGTH,
+    account::{PublicKey, PurseId},
+    bytesrepr::{self, Error, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
+    AccessRights, ApiError, ContractRef, URef, UREF_SERIALIZED_LENGTH,
 };
 
 const ACCOUNT_ID: u8 = 0;
@@ -31,6 +32,16 @@ const KEY_HASH_SERIALIZED_LENGTH: usize = KEY_ID_SERIALIZED_LENGTH + KEY_HASH_LE
 pub const KEY_UREF_SERIALIZED_LENGTH: usize = KEY_ID_SERIALIZED_LENGTH + UREF_SERIALIZED_LENGTH;
 const KEY_LOCAL_SERIALIZED_LENGTH: usize = KEY_ID_SERIALIZED_LENGTH + KEY_LOCAL_LENGTH;
 
+/// The variant of a [`Key`], which is also the first byte of its serialized form.
+#[repr(u8)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Debug)]
+pub enum KeyTag {
+    Account = ACCOUNT_ID,
+    Hash = HASH_ID,
+    URef = UREF_ID,
+    Local = LOCAL_ID,
+}
+
 /// Creates a 32-byte BLAKE2b hash digest from a given a piece of data
 fn hash(bytes: &[u8]) -> [u8; KEY_LOCAL_LENGTH] {
     let mut ret = [0u8; KEY_LOCAL_LENGTH];
@@ -41,6 +52,51 @@ fn hash(bytes: &[u8]) -> [u8; KEY_LOCAL_LENGTH] {
     ret
 }
 
+/// Returns the key under which the account with the given public key is stored.
+pub fn account_key(public_key: PublicKey) -> Key {
+    Key::Account(public_key.value())
+}
+
+/// Returns the local key of the mint under which it stores the [`Key`] of the balance of `purse`.
+///
+/// Reading the value under this key and then the value under the [`Key`] found there yields the
+/// balance of the purse as a [`U512`](crate::U512).
+pub fn purse_balance_key(mint_uref: URef, purse: PurseId) -> Key {
+    Key::local(mint_uref.addr(), &purse.value().addr())
+}
+
+/// Returns the address of the `index`th contract stored by the deploy with the given hash, counting
+/// from zero, e.g. the hash a contract is stored under by `store_function_at_hash`.
+///
+/// The address is the BLAKE2b hash of the deploy hash followed by the little-endian `index`, so
+/// clients can compute it before the deploy is executed.  The deploying account is not an input,
+/// as the deploy hash commits to it already.  Contracts installed with `new_contract` take two
+/// consecutive indices: the first for the contract, the second for its package.
+pub fn contract_address(deploy_hash: [u8; 32], index: u32) -> [u8; KEY_HASH_LENGTH] {
+    let mut bytes = Vec::with_capacity(deploy_hash.len() + U32_SERIALIZED_LENGTH);
+    bytes.extend_from_slice(&deploy_hash);
+    bytes.extend_from_slice(&index.to_le_bytes());
+    hash(&bytes)
+}
+
+/// The seed of a namespace of local state, from which [`Key::local`] derives the keys of the values
+/// stored in it.
+///
+/// Namespaces are obtained from the host by name, and can only be used by the contract which
+/// obtained them.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Debug)]
+pub struct Seed([u8; LOCAL_SEED_LENGTH]);
+
+impl Seed {
+    pub const fn new(value: [u8; LOCAL_SEED_LENGTH]) -> Self {
+        Seed(value)
+    }
+
+    pub fn value(&self) -> [u8; LOCAL_SEED_LENGTH] {
+        self.0
+    }
+}
+
 #[repr(C)]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum Key {
@@ -57,6 +113,16 @@ impl Key {
         Key::Local(hash)
     }
 
+    /// Returns the variant of the key.
+    pub fn tag(&self) -> KeyTag {
+        match self {
+            Key::Account(_) => KeyTag::Account,
+            Key::Hash(_) => KeyTag::Hash,
+            Key::URef(_) => KeyTag::URef,
+            Key::Local(_) => KeyTag::Local,
+        }
+    }
+
     pub fn type_string(&self) -> String {
         match self {
             Key::Account(_) => String::from("Key::Account"),
@@ -189,10 +255,30 @@ impl Key {
         }
     }
 
-    pub fn into_uref(self) -> Option<URef> {
+    /// Returns the wrapped [`URef`], or [`ApiError::UnexpectedKeyVariant`] if `self` is not a
+    /// `Key::URef`.
+    pub fn into_uref(self) -> Result<URef, ApiError> {
         match self {
-            Key::URef(uref) => Some(uref),
-            _ => None,
+            Key::URef(uref) => Ok(uref),
+            _ => Err(ApiError::UnexpectedKeyVariant),
+        }
+    }
+
+    /// Returns the wrapped hash, or [`ApiError::UnexpectedKeyVariant`] if `self` is not a
+    /// `Key::Hash`.
+    pub fn into_hash(self) -> Result<[u8; KEY_HASH_LENGTH], ApiError> {
+        match self {
+            Key::Hash(hash) => Ok(hash),
+            _ => Err(ApiError::UnexpectedKeyVariant),
+        }
+    }
+
+    /// Returns the wrapped account address, or [`ApiError::UnexpectedKeyVariant`] if `self` is
+    /// not a `Key::Account`.
+    pub fn into_account(self) -> Result<[u8; KEY_ACCOUNT_LENGTH], ApiError> {
+        match self {
+            Key::Account(account) => Ok(account),
+            _ => Err(ApiError::UnexpectedKeyVariant),
         }
     }
 
@@ -219,30 +305,32 @@ impl From<URef> for Key {
 
 impl ToBytes for Key {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
         match self {
             Key::Account(addr) => {
-                let mut result = Vec::with_capacity(KEY_ACCOUNT_SERIALIZED_LENGTH);
-                result.push(ACCOUNT_ID);
-                result.append(&mut addr.to_bytes()?);
-                Ok(result)
+                writer.push(ACCOUNT_ID);
+                addr.write_bytes(writer)
             }
             Key::Hash(hash) => {
-                let mut result = Vec::with_capacity(KEY_HASH_SERIALIZED_LENGTH);
-                result.push(HASH_ID);
-                result.append(&mut hash.to_bytes()?);
-                Ok(result)
+                writer.push(HASH_ID);
+                hash.write_bytes(writer)
             }
             Key::URef(uref) => {
-                let mut result = Vec::with_capacity(KEY_UREF_SERIALIZED_LENGTH);
-                result.push(UREF_ID);
-                result.append(&mut uref.to_bytes()?);
-                Ok(result)
+                writer.push(UREF_ID);
+                uref.write_bytes(writer)
             }
             Key::Local(hash) => {
-                let mut result = Vec::with_capacity(KEY_LOCAL_SERIALIZED_LENGTH);
-                result.push(LOCAL_ID);
-                result.append(&mut hash.to_bytes()?);
-                Ok(result)
+                writer.push(LOCAL_ID);
+                hash.write_bytes(writer)
             }
         }
     }
@@ -290,19 +378,22 @@ impl FromBytes for Vec<Key> {
 
 impl ToBytes for Vec<Key> {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let size = self.len() as u32;
-        let mut result: Vec<u8> =
-            Vec::with_capacity(4 + (size as usize) * KEY_UREF_SERIALIZED_LENGTH);
-        result.extend(size.to_bytes()?);
-        result.extend(
-            self.iter()
-                .map(ToBytes::to_bytes)
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .flatten(),
-        );
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH + self.iter().map(Key::serialized_size).sum::<usize>()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        (self.len() as u32).write_bytes(writer)?;
+        for key in self.iter() {
+            key.write_bytes(writer)?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -318,6 +409,55 @@ mod tests {
         AccessRights, URef,
     };
 
+    /// The contract addresses shared with clients, so that any change to how they are derived is
+    /// caught.
+    #[test]
+    fn should_derive_contract_addresses_of_test_vectors() {
+        let vectors = include_str!("../test-vectors/contract_address.txt");
+        let mut count = 0;
+        for line in vectors
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let deploy_hash = decode_from_hex(fields[0]).expect("should decode deploy hash");
+            let index: u32 = fields[1].parse().expect("should parse index");
+            let address = decode_from_hex(fields[2]).expect("should decode address");
+            assert_eq!(contract_address(deploy_hash, index), address, "{}", line);
+            count += 1;
+        }
+        assert!(count > 0);
+    }
+
+    /// The keys derived from fixed inputs, so that any change to how they are derived is caught.
+    #[test]
+    fn should_derive_known_keys() {
+        assert_eq!(
+            account_key(PublicKey::new([7u8; 32])),
+            Key::Account([7u8; 32])
+        );
+
+        let mint_uref = URef::new([1u8; 32], AccessRights::READ);
+        let purse = PurseId::new(URef::new([2u8; 32], AccessRights::READ_ADD_WRITE));
+        assert_eq!(
+            purse_balance_key(mint_uref, purse).as_string(),
+            "local-30b600fb1f0cc0b3f0fc28cdcb7389405a6659be81c7d5c5905725aa3a5119ce"
+        );
+
+        let mut mint_addr = [0u8; 32];
+        let mut purse_addr = [0u8; 32];
+        for index in 0..32 {
+            mint_addr[index] = index as u8;
+            purse_addr[index] = 32 + index as u8;
+        }
+        let mint_uref = URef::new(mint_addr, AccessRights::READ_ADD_WRITE);
+        let purse = PurseId::new(URef::new(purse_addr, AccessRights::READ));
+        assert_eq!(
+            purse_balance_key(mint_uref, purse).as_string(),
+            "local-10d8e6d534b00939843fe9dcc4dae48cdf008f6b8b2b82b156f5404d874887f5"
+        );
+    }
+
     fn test_readable(right: AccessRights, is_true: bool) {
         assert_eq!(right.is_readable(), is_true)
     }
@@ -519,6 +659,28 @@ mod tests {
         assert_eq!(key1.as_local(), Some(local));
     }
 
+    #[test]
+    fn check_key_checked_downcasts() {
+        let account = [42; KEY_ACCOUNT_LENGTH];
+        let hash = [43; KEY_HASH_LENGTH];
+        let uref = URef::new([44; 32], AccessRights::READ_ADD_WRITE);
+        let local = [45; KEY_LOCAL_LENGTH];
+
+        assert_eq!(Key::Account(account).into_account(), Ok(account));
+        assert_eq!(Key::Hash(hash).into_hash(), Ok(hash));
+        assert_eq!(Key::URef(uref).into_uref(), Ok(uref));
+
+        for key in &[Key::Hash(hash), Key::URef(uref), Key::Local(local)] {
+            assert_eq!(key.into_account(), Err(ApiError::UnexpectedKeyVariant));
+        }
+        for key in &[Key::Account(account), Key::URef(uref), Key::Local(local)] {
+            assert_eq!(key.into_hash(), Err(ApiError::UnexpectedKeyVariant));
+        }
+        for key in &[Key::Account(account), Key::Hash(hash), Key::Local(local)] {
+            assert_eq!(key.into_uref(), Err(ApiError::UnexpectedKeyVariant));
+        }
+    }
+
     #[test]
     fn serialized_size() {
         let account = [42; 32];
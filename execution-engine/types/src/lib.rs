@@ -11,11 +11,15 @@ pub mod account;
 pub mod api_error;
 mod block_time;
 pub mod bytesrepr;
+mod call_stack;
 mod cl_type;
 mod cl_value;
+mod contract_metadata;
+mod contract_package;
 mod contract_ref;
 #[cfg(any(feature = "gens", test))]
 pub mod gens;
+mod install_manifest;
 pub mod key;
 mod phase;
 mod protocol_version;
@@ -26,20 +30,38 @@ mod transfer_result;
 mod uint;
 mod uref;
 
-pub use crate::uint::{UIntParseError, U128, U256, U512};
+pub use crate::uint::{
+    checked_add, checked_div, checked_mul, checked_sub, ArithmeticError, UIntParseError, U128,
+    U256, U512,
+};
 pub use access_rights::{AccessRights, ACCESS_RIGHTS_SERIALIZED_LENGTH};
 pub use api_error::ApiError;
 pub use block_time::{BlockTime, BLOCKTIME_SERIALIZED_LENGTH};
+pub use call_stack::CallStackElement;
 pub use cl_type::{named_key_type, CLType, CLTyped};
 pub use cl_value::{CLTypeMismatch, CLValue, CLValueError};
+pub use contract_metadata::{ContractMetadata, CONTRACT_METADATA_SECTION};
+pub use contract_package::{
+    ContractVersion, EntryPoint, EntryPointAccess, EntryPoints, Parameter, CONTRACT_INITIAL_VERSION,
+};
 pub use contract_ref::ContractRef;
+pub use install_manifest::{access_key_name, install_manifest_key_name, InstallManifest};
 pub use key::{
-    Key, KEY_ACCOUNT_LENGTH, KEY_HASH_LENGTH, KEY_LOCAL_LENGTH, KEY_UREF_SERIALIZED_LENGTH,
-    LOCAL_SEED_LENGTH,
+    account_key, contract_address, purse_balance_key, Key, KeyTag, Seed, KEY_ACCOUNT_LENGTH,
+    KEY_HASH_LENGTH, KEY_LOCAL_LENGTH, KEY_UREF_SERIALIZED_LENGTH, LOCAL_SEED_LENGTH,
 };
 pub use phase::{Phase, PHASE_SERIALIZED_LENGTH};
 pub use protocol_version::{ProtocolVersion, VersionCheckResult};
-pub use semver::SemVer;
+pub use semver::{SemVer, SEM_VER_SERIALIZED_LENGTH};
 pub use system_contract_type::SystemContractType;
 pub use transfer_result::{TransferResult, TransferredTo};
 pub use uref::{URef, UREF_ADDR_LENGTH, UREF_SERIALIZED_LENGTH};
+
+/// The number of bytes produced by each call to the `random_bytes` host function.
+pub const RANDOM_BYTES_COUNT: usize = 32;
+
+/// The length in bytes of the digest produced by the `blake2b` host function.
+pub const BLAKE2B_DIGEST_LENGTH: usize = 32;
+
+/// The length in bytes of the digest produced by the `sha256` host function.
+pub const SHA256_DIGEST_LENGTH: usize = 32;
@@ -48,6 +48,12 @@ impl ProtocolVersion {
         patch: 0,
     });
 
+    pub const V1_1_0: ProtocolVersion = ProtocolVersion(SemVer {
+        major: 1,
+        minor: 1,
+        patch: 0,
+    });
+
     pub fn new(version: SemVer) -> ProtocolVersion {
         ProtocolVersion(version)
     }
@@ -116,8 +122,15 @@ impl ProtocolVersion {
 
 impl ToBytes for ProtocolVersion {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let value_bytes = self.value().to_bytes()?;
-        Ok(value_bytes.to_vec())
+        self.value().to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.value().serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.value().write_bytes(writer)
     }
 }
 
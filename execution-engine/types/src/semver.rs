@@ -31,11 +31,19 @@ impl SemVer {
 impl ToBytes for SemVer {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         let mut ret: Vec<u8> = Vec::with_capacity(SEM_VER_SERIALIZED_LENGTH);
-        ret.append(&mut self.major.to_bytes()?);
-        ret.append(&mut self.minor.to_bytes()?);
-        ret.append(&mut self.patch.to_bytes()?);
+        self.write_bytes(&mut ret)?;
         Ok(ret)
     }
+
+    fn serialized_length(&self) -> usize {
+        SEM_VER_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
+        self.major.write_bytes(writer)?;
+        self.minor.write_bytes(writer)?;
+        self.patch.write_bytes(writer)
+    }
 }
 
 impl FromBytes for SemVer {
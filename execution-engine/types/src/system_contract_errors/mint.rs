@@ -5,7 +5,7 @@ use core::convert::{TryFrom, TryInto};
 use failure::Fail;
 
 use crate::{
-    bytesrepr::{self, FromBytes, ToBytes},
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
     AccessRights, CLType, CLTyped,
 };
 
@@ -31,6 +31,10 @@ pub enum Error {
     StorageError = 6,
     #[fail(display = "Purse not found")]
     PurseNotFound = 7,
+    /// The transfer would leave less than the amount locked by the vesting schedule of the account
+    /// owning the source purse.
+    #[fail(display = "Funds locked")]
+    FundsLocked = 8,
     #[fail(display = "Missing argument")]
     MissingArgument = 102,
     #[fail(display = "Passed argument is invalid")]
@@ -75,6 +79,9 @@ impl TryFrom<u8> for Error {
             d if d == Error::InvalidNonEmptyPurseCreation as u8 => {
                 Ok(Error::InvalidNonEmptyPurseCreation)
             }
+            d if d == Error::StorageError as u8 => Ok(Error::StorageError),
+            d if d == Error::PurseNotFound as u8 => Ok(Error::PurseNotFound),
+            d if d == Error::FundsLocked as u8 => Ok(Error::FundsLocked),
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -85,6 +92,10 @@ impl ToBytes for Error {
         let value = *self as u8;
         value.to_bytes()
     }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
 }
 
 impl FromBytes for Error {
@@ -115,3 +126,37 @@ impl fmt::Display for PurseIdError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use crate::{bytesrepr, ApiError};
+
+    const ALL_ERRORS: [Error; 11] = [
+        Error::InsufficientFunds,
+        Error::SourceNotFound,
+        Error::DestNotFound,
+        Error::InvalidURef,
+        Error::InvalidAccessRights,
+        Error::InvalidNonEmptyPurseCreation,
+        Error::StorageError,
+        Error::PurseNotFound,
+        Error::FundsLocked,
+        Error::MissingArgument,
+        Error::InvalidArgument,
+    ];
+
+    #[test]
+    fn should_round_trip_all_variants() {
+        for error in ALL_ERRORS.iter() {
+            bytesrepr::test_serialization_roundtrip(error);
+        }
+    }
+
+    #[test]
+    fn should_map_into_api_error() {
+        for error in ALL_ERRORS.iter() {
+            assert_eq!(ApiError::from(*error), ApiError::Mint(*error as u8));
+        }
+    }
+}
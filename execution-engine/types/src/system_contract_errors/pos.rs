@@ -1,5 +1,7 @@
 use core::result;
 
+use crate::ArithmeticError;
+
 #[derive(Debug, PartialEq)]
 // TODO: Split this up into user errors vs. system errors.
 #[repr(u8)]
@@ -41,6 +43,44 @@ pub enum Error {
     FailedTransferToRewardsPurse,
     FailedTransferToAccountPurse,
     SetRefundPurseCalledOutsidePayment,
+    // Delegation errors
+    NotDelegated,
+    UndelegateTooLarge,
+    /// Returned when a validator tries to delegate to itself instead of bonding.
+    SelfDelegation,
+    /// Returned when a validator tries to unbond stakes delegated to it.
+    DelegatedStakes,
+    DelegateTransferFailed,
+    UndelegateTransferFailed,
+    DelegationsKeyDeserializationFailed,
+    DelegationsDeserializationFailed,
+    FailedTransferToBondingPurse,
+    // Unbonding delay errors
+    UnbondingDelayKeyUnexpectedType,
+    UnbondingDelayNotFound,
+    UnbondingDelayDeserializationFailed,
+    // Slashing errors
+    SlashPercentageKeyUnexpectedType,
+    SlashPercentageNotFound,
+    SlashPercentageDeserializationFailed,
+    // Reward distribution errors
+    LastRewardedEraKeyUnexpectedType,
+    LastRewardedEraDeserializationFailed,
+    /// Returned when the rewards for an era are distributed more than once.
+    EraAlreadyRewarded,
+    MintSeigniorageFailed,
+    ValidatorRewardPurseKeyUnexpectedType,
+    FailedTransferToValidatorRewardPurse,
+    UndistributedFeesKeyUnexpectedType,
+    UndistributedFeesDeserializationFailed,
+    /// Returned when arithmetic on stakes or amounts overflows or underflows.
+    ArithmeticOverflow,
+}
+
+impl From<ArithmeticError> for Error {
+    fn from(_error: ArithmeticError) -> Self {
+        Error::ArithmeticOverflow
+    }
 }
 
 pub type Result<T> = result::Result<T, Error>;
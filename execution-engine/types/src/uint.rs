@@ -1,6 +1,9 @@
 use alloc::vec::Vec;
 
-use num_traits::{AsPrimitive, Bounded, Num, One, Unsigned, WrappingAdd, WrappingSub, Zero};
+use num_traits::{
+    AsPrimitive, Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Num, One, Unsigned,
+    WrappingAdd, WrappingSub, Zero,
+};
 
 use crate::bytesrepr::{self, Error, FromBytes, ToBytes};
 
@@ -38,18 +41,101 @@ pub enum UIntParseError {
     InvalidRadix,
 }
 
+/// Error type for the checked arithmetic of U128, U256 and U512, which unlike the arithmetic
+/// operators doesn't panic.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ArithmeticError {
+    /// The result is too large to be represented by the type.
+    Overflow,
+    /// The result is negative.
+    Underflow,
+    /// The divisor is zero.
+    DivisionByZero,
+}
+
+/// Returns `lhs + rhs`, or [`ArithmeticError::Overflow`] if the sum doesn't fit in `T`.
+pub fn checked_add<T: CheckedAdd>(lhs: T, rhs: T) -> Result<T, ArithmeticError> {
+    lhs.checked_add(&rhs).ok_or(ArithmeticError::Overflow)
+}
+
+/// Returns `lhs - rhs`, or [`ArithmeticError::Underflow`] if `rhs` is greater than `lhs`.
+pub fn checked_sub<T: CheckedSub>(lhs: T, rhs: T) -> Result<T, ArithmeticError> {
+    lhs.checked_sub(&rhs).ok_or(ArithmeticError::Underflow)
+}
+
+/// Returns `lhs * rhs`, or [`ArithmeticError::Overflow`] if the product doesn't fit in `T`.
+pub fn checked_mul<T: CheckedMul>(lhs: T, rhs: T) -> Result<T, ArithmeticError> {
+    lhs.checked_mul(&rhs).ok_or(ArithmeticError::Overflow)
+}
+
+/// Returns `lhs / rhs` rounded down, or [`ArithmeticError::DivisionByZero`] if `rhs` is zero.
+pub fn checked_div<T: CheckedDiv>(lhs: T, rhs: T) -> Result<T, ArithmeticError> {
+    lhs.checked_div(&rhs).ok_or(ArithmeticError::DivisionByZero)
+}
+
 macro_rules! ser_and_num_impls {
     ($type:ident, $total_bytes:expr) => {
+        impl $type {
+            /// Returns the little-endian bytes of `self`, zero-padded to the width of the type.
+            pub fn to_le_bytes(self) -> [u8; $total_bytes] {
+                let mut bytes = [0u8; $total_bytes];
+                self.to_little_endian(&mut bytes);
+                bytes
+            }
+
+            /// Returns the big-endian bytes of `self`, zero-padded to the width of the type.
+            pub fn to_be_bytes(self) -> [u8; $total_bytes] {
+                let mut bytes = [0u8; $total_bytes];
+                self.to_big_endian(&mut bytes);
+                bytes
+            }
+
+            /// Constructs a value from its little-endian bytes.
+            pub fn from_le_bytes(bytes: [u8; $total_bytes]) -> Self {
+                $type::from_little_endian(&bytes)
+            }
+
+            /// Constructs a value from its big-endian bytes.
+            pub fn from_be_bytes(bytes: [u8; $total_bytes]) -> Self {
+                $type::from_big_endian(&bytes)
+            }
+
+            /// Returns whether `self` and `other` are equal, in constant time.
+            ///
+            /// All the limbs of both values are always inspected, and the result is computed
+            /// without branching on them, so the running time doesn't depend on the values.  This
+            /// is not the case for `==`, which returns as soon as two limbs differ, and so must not
+            /// be used to compare secrets such as commitments or signatures.
+            pub fn ct_eq(&self, other: &Self) -> bool {
+                let difference = self
+                    .0
+                    .iter()
+                    .zip(other.0.iter())
+                    .fold(0u64, |acc, (lhs, rhs)| acc | (lhs ^ rhs));
+                difference == 0
+            }
+        }
+
         impl ToBytes for $type {
             fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+                let mut result = bytesrepr::allocate_buffer(self)?;
+                self.write_bytes(&mut result)?;
+                Ok(result)
+            }
+
+            /// One length byte followed by the little-endian bytes up to the most significant
+            /// non-zero one.
+            fn serialized_length(&self) -> usize {
+                1 + (self.bits() + 7) / 8
+            }
+
+            fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), Error> {
                 let mut buf = [0u8; $total_bytes];
                 self.to_little_endian(&mut buf);
-                let mut non_zero_bytes: Vec<u8> =
-                    buf.iter().rev().skip_while(|b| **b == 0).cloned().collect();
-                let num_bytes = non_zero_bytes.len() as u8;
-                non_zero_bytes.push(num_bytes);
-                non_zero_bytes.reverse();
-                Ok(non_zero_bytes)
+                let num_bytes = (self.bits() + 7) / 8;
+                writer.push(num_bytes as u8);
+                writer.extend_from_slice(&buf[..num_bytes]);
+                Ok(())
             }
         }
 
@@ -125,6 +211,30 @@ macro_rules! ser_and_num_impls {
             }
         }
 
+        impl CheckedAdd for $type {
+            fn checked_add(&self, other: &$type) -> Option<$type> {
+                $type::checked_add(*self, *other)
+            }
+        }
+
+        impl CheckedSub for $type {
+            fn checked_sub(&self, other: &$type) -> Option<$type> {
+                $type::checked_sub(*self, *other)
+            }
+        }
+
+        impl CheckedMul for $type {
+            fn checked_mul(&self, other: &$type) -> Option<$type> {
+                $type::checked_mul(*self, *other)
+            }
+        }
+
+        impl CheckedDiv for $type {
+            fn checked_div(&self, other: &$type) -> Option<$type> {
+                $type::checked_div(*self, *other)
+            }
+        }
+
         impl AsPrimitive<$type> for i32 {
             fn as_(self) -> $type {
                 if self >= 0 {
@@ -590,4 +700,88 @@ mod tests {
         let value = min.wrapping_sub(&1.into());
         assert_eq!(value, U128::max_value());
     }
+
+    #[test]
+    fn checked_arithmetic_test() {
+        let max = U512::max_value();
+        let one = U512::one();
+
+        assert_eq!(checked_add(max - one, one), Ok(max));
+        assert_eq!(checked_add(max, one), Err(ArithmeticError::Overflow));
+
+        assert_eq!(checked_sub(one, one), Ok(U512::zero()));
+        assert_eq!(
+            checked_sub(U512::zero(), one),
+            Err(ArithmeticError::Underflow)
+        );
+
+        assert_eq!(checked_mul(max, one), Ok(max));
+        assert_eq!(
+            checked_mul(max, U512::from(2)),
+            Err(ArithmeticError::Overflow)
+        );
+
+        assert_eq!(checked_div(U512::from(7), U512::from(2)), Ok(U512::from(3)));
+        assert_eq!(
+            checked_div(max, U512::zero()),
+            Err(ArithmeticError::DivisionByZero)
+        );
+
+        assert_eq!(
+            checked_add(U128::max_value(), U128::one()),
+            Err(ArithmeticError::Overflow)
+        );
+        assert_eq!(
+            checked_sub(U256::zero(), U256::one()),
+            Err(ArithmeticError::Underflow)
+        );
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use proptest::prelude::*;
+
+    use super::{U128, U256, U512};
+    use crate::gens::{u128_arb, u256_arb, u512_arb};
+
+    macro_rules! fixed_bytes_tests {
+        ($type:ident, $arb:ident, $total_bytes:expr, $le_test:ident, $be_test:ident, $eq_test:ident) => {
+            proptest! {
+                #[test]
+                fn $le_test(value in $arb()) {
+                    let mut expected = [0u8; $total_bytes];
+                    value.to_little_endian(&mut expected);
+                    let bytes = value.to_le_bytes();
+                    prop_assert_eq!(&bytes[..], &expected[..]);
+                    prop_assert_eq!($type::from_le_bytes(bytes), value);
+                    prop_assert_eq!($type::from_little_endian(&bytes), value);
+                }
+
+                #[test]
+                fn $be_test(value in $arb()) {
+                    let mut expected = [0u8; $total_bytes];
+                    value.to_big_endian(&mut expected);
+                    let bytes = value.to_be_bytes();
+                    prop_assert_eq!(&bytes[..], &expected[..]);
+                    prop_assert_eq!($type::from_be_bytes(bytes), value);
+                    prop_assert_eq!($type::from_big_endian(&bytes), value);
+                }
+
+                #[test]
+                fn $eq_test(lhs in $arb(), rhs in $arb()) {
+                    prop_assert_eq!(lhs.ct_eq(&rhs), lhs == rhs);
+                    prop_assert!(lhs.ct_eq(&lhs));
+                    let flipped = lhs ^ $type::one();
+                    prop_assert!(!lhs.ct_eq(&flipped));
+                    let flipped = lhs ^ ($type::one() << ($total_bytes as u64 * 8 - 1));
+                    prop_assert!(!lhs.ct_eq(&flipped));
+                }
+            }
+        };
+    }
+
+    fixed_bytes_tests!(U128, u128_arb, 16, u128_le_bytes, u128_be_bytes, u128_ct_eq);
+    fixed_bytes_tests!(U256, u256_arb, 32, u256_le_bytes, u256_be_bytes, u256_ct_eq);
+    fixed_bytes_tests!(U512, u512_arb, 64, u512_le_bytes, u512_be_bytes, u512_ct_eq);
 }
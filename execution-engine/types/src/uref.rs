@@ -89,6 +89,26 @@ impl URef {
         URef(self.0, Some(AccessRights::READ_ADD_WRITE))
     }
 
+    /// Returns a new URef keeping only those of its access rights which are also in
+    /// `access_rights`.
+    ///
+    /// Unlike [`URef::with_access_rights`], this never grants rights the URef doesn't already have,
+    /// so it is safe to use for handing out restricted copies, e.g. an add-only copy of a purse
+    /// which allows deposits but no withdrawals.  If no rights remain, the URef has none.
+    pub fn attenuate(self, access_rights: AccessRights) -> URef {
+        match self.1 {
+            Some(current) if !(current & access_rights).is_empty() => {
+                URef(self.0, Some(current & access_rights))
+            }
+            _ => URef(self.0, None),
+        }
+    }
+
+    /// Returns a new URef with only the [`AccessRights::ADD`] right, if this URef has it.
+    pub fn into_add(self) -> URef {
+        self.attenuate(AccessRights::ADD)
+    }
+
     pub fn is_writeable(self) -> bool {
         if let Some(access_rights) = self.1 {
             access_rights.is_writeable()
@@ -125,11 +145,19 @@ impl URef {
 
 impl bytesrepr::ToBytes for URef {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        let mut result = Vec::with_capacity(UREF_SERIALIZED_LENGTH);
-        result.append(&mut self.0.to_bytes()?);
-        result.append(&mut self.1.to_bytes()?);
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
+
+    fn serialized_length(&self) -> usize {
+        UREF_ADDR_LENGTH + self.1.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.0.write_bytes(writer)?;
+        self.1.write_bytes(writer)
+    }
 }
 
 impl bytesrepr::FromBytes for URef {
@@ -157,18 +185,26 @@ impl bytesrepr::FromBytes for Vec<URef> {
 
 impl bytesrepr::ToBytes for Vec<URef> {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        let size = self.len() as u32;
-        let mut result: Vec<u8> = Vec::with_capacity(U32_SERIALIZED_LENGTH);
-        result.extend(size.to_bytes()?);
-        result.extend(
-            self.iter()
-                .map(bytesrepr::ToBytes::to_bytes)
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .flatten(),
-        );
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH
+            + self
+                .iter()
+                .map(bytesrepr::ToBytes::serialized_length)
+                .sum::<usize>()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        (self.len() as u32).write_bytes(writer)?;
+        for uref in self.iter() {
+            uref.write_bytes(writer)?;
+        }
+        Ok(())
+    }
 }
 
 impl TryFrom<Key> for URef {
@@ -210,4 +246,29 @@ mod tests {
             "uref-0000000000000000000000000000000000000000000000000000000000000000-000"
         );
     }
+
+    #[test]
+    fn should_attenuate_access_rights() {
+        let addr = [1u8; 32];
+        let uref = URef::new(addr, AccessRights::READ_ADD);
+
+        assert_eq!(
+            uref.attenuate(AccessRights::ADD_WRITE),
+            URef::new(addr, AccessRights::ADD)
+        );
+        assert_eq!(uref.attenuate(AccessRights::READ_ADD_WRITE), uref);
+        assert_eq!(uref.into_add(), URef::new(addr, AccessRights::ADD));
+        assert_eq!(uref.into_add().into_add(), uref.into_add());
+
+        // Rights are never granted
+        assert_eq!(
+            uref.attenuate(AccessRights::WRITE),
+            uref.remove_access_rights()
+        );
+        assert_eq!(
+            uref.remove_access_rights()
+                .attenuate(AccessRights::READ_ADD_WRITE),
+            uref.remove_access_rights()
+        );
+    }
 }
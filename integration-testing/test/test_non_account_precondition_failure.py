@@ -24,4 +24,5 @@ def test_non_account_precondition_failure(trillion_payment_node_network):
 
     # Verify reason for propose failure
     assert "DISCARDED" in str(e.value)
-    assert "Authorization failure: not authorized." in str(e.value)
+    assert "Authorization failure: account" in str(e.value)
+    assert "not found" in str(e.value)